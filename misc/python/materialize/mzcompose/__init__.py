@@ -66,10 +66,13 @@ DEFAULT_SYSTEM_PARAMETERS = {
     "enable_compute_chunked_stack": "true",
     "enable_connection_validation_syntax": "true",
     "enable_copy_to_expr": "true",
+    "enable_copy_from_remote": "true",
     "enable_disk_cluster_replicas": "true",
     "enable_eager_delta_joins": "true",
     "enable_envelope_debezium_in_subscribe": "true",
     "enable_expressions_in_limit_syntax": "true",
+    "enable_file_sources": "true",
+    "enable_kinesis_sources": "true",
     "enable_logical_compaction_window": "true",
     "enable_multi_worker_storage_persist_sink": "true",
     "enable_mysql_source": "true",
@@ -78,6 +81,7 @@ DEFAULT_SYSTEM_PARAMETERS = {
     "enable_refresh_every_mvs": "true",
     "enable_cluster_schedule_refresh": "true",
     "enable_sink_doc_on_option": "true",
+    "enable_sql_server_source": "true",
     "enable_statement_lifecycle_logging": "true",
     "enable_table_keys": "true",
     "enable_variadic_left_join_lowering": "true",
@@ -0,0 +1,57 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+
+from materialize.mzcompose.service import (
+    Service,
+)
+
+
+class MariaDb(Service):
+    DEFAULT_ROOT_PASSWORD = "p@ssw0rd"
+    DEFAULT_VERSION = "10.11"
+
+    DEFAULT_ADDITIONAL_ARGS = [
+        "--log-bin=mysql-bin",
+        "--binlog-format=row",
+        "--binlog-row-image=full",
+        "--gtid-strict-mode=ON",
+        "--server-id=1",
+    ]
+
+    def __init__(
+        self,
+        root_password: str = DEFAULT_ROOT_PASSWORD,
+        name: str = "mariadb",
+        version: str = DEFAULT_VERSION,
+        port: int = 3306,
+        additional_args: list[str] = DEFAULT_ADDITIONAL_ARGS,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": f"mariadb:{version}",
+                "init": True,
+                "ports": [port],
+                "environment": [
+                    f"MARIADB_ROOT_PASSWORD={root_password}",
+                ],
+                "command": additional_args,
+                "healthcheck": {
+                    "test": [
+                        "CMD",
+                        "healthcheck.sh",
+                        "--connect",
+                        "--innodb_initialized",
+                    ],
+                    "interval": "1s",
+                    "start_period": "60s",
+                },
+            },
+        )
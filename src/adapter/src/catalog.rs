@@ -14,6 +14,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::convert;
+use std::ops::Range;
 use std::sync::Arc;
 
 use futures::Future;
@@ -33,6 +34,7 @@ use mz_compute_types::dataflows::DataflowDescription;
 use mz_controller::clusters::ReplicaLocation;
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::OptimizedMirRelationExpr;
+use mz_ore::cast::CastFrom;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{EpochMillis, NowFn};
 use mz_ore::option::FallibleMapExt;
@@ -121,6 +123,9 @@ pub struct Catalog {
     state: CatalogState,
     plans: CatalogPlans,
     storage: Arc<tokio::sync::Mutex<Box<dyn mz_catalog::durable::DurableCatalogState>>>,
+    /// Sequence positions that have been reserved durably but not yet handed out, keyed by the
+    /// name of the id allocator backing the sequence.
+    sequence_caches: Arc<tokio::sync::Mutex<BTreeMap<String, Range<u64>>>>,
     transient_revision: u64,
 }
 
@@ -132,6 +137,7 @@ impl Clone for Catalog {
             state: self.state.clone(),
             plans: self.plans.clone(),
             storage: Arc::clone(&self.storage),
+            sequence_caches: Arc::clone(&self.sequence_caches),
             transient_revision: self.transient_revision,
         }
     }
@@ -625,6 +631,36 @@ impl Catalog {
             .err_into()
     }
 
+    /// Returns the next `amount` positions of the sequence backed by the id allocator `key`.
+    ///
+    /// Positions are reserved durably at least `cache` at a time, so that a `cache` greater than
+    /// one lets calls be served from memory. Reserved positions that are never handed out are lost
+    /// on restart, which leaves gaps in the sequence, as in PostgreSQL.
+    pub async fn allocate_sequence_positions(
+        &self,
+        key: String,
+        cache: u64,
+        amount: u64,
+    ) -> Result<Vec<u64>, Error> {
+        let mut sequence_caches = self.sequence_caches.lock().await;
+        let cached = sequence_caches.entry(key.clone()).or_default();
+        let mut positions: Vec<_> = cached.by_ref().take(usize::cast_from(amount)).collect();
+        let needed = amount - u64::cast_from(positions.len());
+        if needed > 0 {
+            let reserved = self
+                .storage()
+                .await
+                .allocate_id(&key, std::cmp::max(cache, needed))
+                .await
+                .maybe_terminate("allocating sequence values")?;
+            let start = reserved[0];
+            let mut reserved = start..start + u64::cast_from(reserved.len());
+            positions.extend(reserved.by_ref().take(usize::cast_from(needed)));
+            *cached = reserved;
+        }
+        Ok(positions)
+    }
+
     #[cfg(test)]
     pub async fn allocate_system_id(&self) -> Result<GlobalId, Error> {
         use mz_ore::collections::CollectionExt;
@@ -1247,6 +1283,7 @@ pub(crate) fn system_object_type_to_audit_object_type(
             mz_sql::catalog::ObjectType::Database => ObjectType::Database,
             mz_sql::catalog::ObjectType::Schema => ObjectType::Schema,
             mz_sql::catalog::ObjectType::Func => ObjectType::Func,
+            mz_sql::catalog::ObjectType::Sequence => ObjectType::Sequence,
         },
         SystemObjectType::System => ObjectType::System,
     }
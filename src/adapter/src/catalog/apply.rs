@@ -468,6 +468,10 @@ impl CatalogState {
                         create_sql: None,
                         desc: table.desc.clone(),
                         defaults: vec![Expr::null(); table.desc.arity()],
+                        generated_columns: BTreeMap::new(),
+                        checks: Vec::new(),
+                        identity_columns: BTreeMap::new(),
+                        nulls_not_distinct_keys: Vec::new(),
                         conn_id: None,
                         resolved_ids: ResolvedIds(BTreeSet::new()),
                         custom_logical_compaction_window: table.is_retained_metrics_object.then(
@@ -497,9 +497,9 @@ impl CatalogState {
                                         diff,
                                     )
                                 }
-                                // Load generator sources don't have any special
-                                // updates.
-                                "load-generator" => vec![],
+                                // Load generator and SQL Server sources don't
+                                // have any special updates.
+                                "load-generator" | "sql-server" => vec![],
                                 s => unreachable!("{s} sources do not have subsources"),
                             }
                         }
@@ -531,6 +531,8 @@ impl CatalogState {
                 CatalogItem::Connection(connection) => self.pack_connection_update(
                     id, oid, schema_id, name, owner_id, privileges, connection, diff,
                 ),
+                // Sequences are not yet exposed in any system table.
+                CatalogItem::Sequence(_) => vec![],
             };
 
         if !entry.item().is_temporary() {
@@ -811,6 +813,7 @@ impl CatalogState {
                     }
                     mz_storage_types::connections::Connection::Ssh { .. } => "ssh-tunnel",
                     mz_storage_types::connections::Connection::MySql { .. } => "mysql",
+                    mz_storage_types::connections::Connection::SqlServer { .. } => "sql-server",
                 }),
                 Datum::String(&owner_id.to_string()),
                 privileges,
@@ -857,7 +860,8 @@ impl CatalogState {
             }
             mz_storage_types::connections::Connection::Csr(_)
             | mz_storage_types::connections::Connection::Postgres(_)
-            | mz_storage_types::connections::Connection::MySql(_) => (),
+            | mz_storage_types::connections::Connection::MySql(_)
+            | mz_storage_types::connections::Connection::SqlServer(_) => (),
         };
         updates
     }
@@ -1183,7 +1187,7 @@ impl CatalogState {
             .ast;
 
         let envelope = sink.envelope();
-        let format = sink.format();
+        let (key_format, value_format) = sink.formats();
 
         updates.push(BuiltinTableUpdate {
             id: self.resolve_builtin_table(&MZ_SINKS),
@@ -1197,7 +1201,10 @@ impl CatalogState {
                 // size column now deprecated w/o linked clusters
                 Datum::Null,
                 Datum::from(envelope),
-                Datum::from(format),
+                // The deprecated `format` column reports the value format.
+                Datum::String(value_format),
+                Datum::from(key_format),
+                Datum::String(value_format),
                 Datum::String(&sink.cluster_id.to_string()),
                 Datum::String(&owner_id.to_string()),
                 Datum::String(&sink.create_sql),
@@ -1824,6 +1831,7 @@ impl CatalogState {
             | CommentObjectId::Func(global_id)
             | CommentObjectId::Connection(global_id)
             | CommentObjectId::Secret(global_id)
+            | CommentObjectId::Sequence(global_id)
             | CommentObjectId::Type(global_id) => global_id.to_string(),
             CommentObjectId::Role(role_id) => role_id.to_string(),
             CommentObjectId::Database(database_id) => database_id.to_string(),
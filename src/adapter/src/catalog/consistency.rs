@@ -237,7 +237,8 @@ impl CatalogState {
                 | CommentObjectId::Func(global_id)
                 | CommentObjectId::Connection(global_id)
                 | CommentObjectId::Type(global_id)
-                | CommentObjectId::Secret(global_id) => {
+                | CommentObjectId::Secret(global_id)
+                | CommentObjectId::Sequence(global_id) => {
                     let entry = self.entry_by_id.get(&global_id);
                     match entry {
                         None => comment_inconsistencies
@@ -502,7 +503,10 @@ impl CatalogState {
                         )
                         | Statement::CreateTable(ast::CreateTableStatement { name, .. })
                         | Statement::CreateType(ast::CreateTypeStatement { name, .. })
-                        | Statement::CreateSecret(ast::CreateSecretStatement { name, .. }) => {
+                        | Statement::CreateSecret(ast::CreateSecretStatement { name, .. })
+                        | Statement::CreateSequence(ast::CreateSequenceStatement {
+                            name, ..
+                        }) => {
                             let [db_component, schema_component, item_component] = &name.0[..]
                             else {
                                 let name =
@@ -99,7 +99,7 @@ pub(crate) async fn migrate(
     );
 
     rewrite_ast_items(tx, |_tx, _id, stmt| {
-        let _catalog_version = catalog_version.clone();
+        let catalog_version = catalog_version.clone();
         Box::pin(async move {
             // Add per-item AST migrations below.
             //
@@ -110,6 +110,9 @@ pub(crate) async fn migrate(
             // Migration functions may also take `tx` as input to stage
             // arbitrary changes to the catalog.
             ast_rewrite_create_source_loadgen_options_0_92_0(stmt)?;
+            if catalog_version < Version::parse("0.99.0-dev")? {
+                ast_rewrite_create_table_remove_check_constraints_0_99_0(stmt)?;
+            }
             Ok(())
         })
     })
@@ -315,7 +318,8 @@ fn _assign_new_user_global_ids(
                 | CommentObjectId::Func(id)
                 | CommentObjectId::Connection(id)
                 | CommentObjectId::Type(id)
-                | CommentObjectId::Secret(id) => *id = *new_id,
+                | CommentObjectId::Secret(id)
+                | CommentObjectId::Sequence(id) => *id = *new_id,
                 // These comments do not use `GlobalId`s.
                 id @ (CommentObjectId::Role(_)
                 | CommentObjectId::Database(_)
@@ -476,6 +480,25 @@ fn ast_rewrite_create_source_loadgen_options_0_92_0(
     Ok(())
 }
 
+/// Removes the `CHECK` constraints of tables that were created before `CHECK`
+/// constraints were enforced.
+///
+/// Such tables could only be created with the `enable_table_check_constraint`
+/// feature flag, which accepted table `CHECK` constraints without enforcing
+/// them, so the rows of these tables may violate their constraints. Enforcing
+/// them now would reject writes that were allowed when the table was created.
+fn ast_rewrite_create_table_remove_check_constraints_0_99_0(
+    stmt: &mut Statement<Raw>,
+) -> Result<(), anyhow::Error> {
+    use mz_sql::ast::{CreateTableStatement, TableConstraint};
+
+    if let Statement::CreateTable(CreateTableStatement { constraints, .. }) = stmt {
+        constraints.retain(|constraint| !matches!(constraint, TableConstraint::Check { .. }));
+    }
+
+    Ok(())
+}
+
 fn ast_rewrite_create_source_pg_database_details(
     cat: &ConnCatalog<'_>,
     stmt: &mut Statement<Raw>,
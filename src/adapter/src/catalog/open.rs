@@ -350,7 +350,8 @@ impl Catalog {
                     | CatalogItemType::Sink
                     | CatalogItemType::Func
                     | CatalogItemType::Secret
-                    | CatalogItemType::Connection => other_builtin_updates.push(StateUpdate {
+                    | CatalogItemType::Connection
+                    | CatalogItemType::Sequence => other_builtin_updates.push(StateUpdate {
                         kind: StateUpdateKind::SystemObjectMapping(builtin_item_update),
                         diff,
                     }),
@@ -639,6 +640,7 @@ impl Catalog {
                 plans: CatalogPlans::default(),
                 transient_revision: 1,
                 storage: Arc::new(tokio::sync::Mutex::new(storage)),
+                sequence_caches: Arc::default(),
             };
             let secrets_reader = &catalog.state.config.connection_context.secrets_reader;
 
@@ -864,7 +866,8 @@ impl Catalog {
                 CatalogItem::Type(_)
                 | CatalogItem::Func(_)
                 | CatalogItem::Secret(_)
-                | CatalogItem::Connection(_) => unreachable!(
+                | CatalogItem::Connection(_)
+                | CatalogItem::Sequence(_) => unreachable!(
                     "impossible to migrate schema for builtin {}",
                     entry.item().typ()
                 ),
@@ -1401,6 +1404,10 @@ mod builtin_migration_tests {
                         .with_column("a", ScalarType::Int32.nullable(true))
                         .with_key(vec![0]),
                     defaults: vec![Expr::null(); 1],
+                    generated_columns: BTreeMap::new(),
+                    checks: Vec::new(),
+                    identity_columns: BTreeMap::new(),
+                    nulls_not_distinct_keys: Vec::new(),
                     conn_id: None,
                     resolved_ids: ResolvedIds(BTreeSet::new()),
                     custom_logical_compaction_window: None,
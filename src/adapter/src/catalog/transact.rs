@@ -13,7 +13,7 @@ use mz_adapter_types::compaction::CompactionWindow;
 use mz_adapter_types::connection::ConnectionId;
 use mz_audit_log::{EventDetails, EventType, IdFullNameV1, ObjectType, VersionedEvent};
 use mz_catalog::builtin::BuiltinLog;
-use mz_catalog::durable::Transaction;
+use mz_catalog::durable::{sequence_alloc_key, Transaction};
 use mz_catalog::memory::error::{AmbiguousRename, Error, ErrorKind};
 use mz_catalog::memory::objects::{
     CatalogItem, ClusterConfig, ClusterReplicaProcessStatus, Database, Role, Schema,
@@ -904,6 +904,21 @@ impl Catalog {
                         )?;
                     }
 
+                    match &item {
+                        CatalogItem::Sequence(_) => {
+                            tx.insert_sequence_allocator(sequence_alloc_key(id, None))?;
+                        }
+                        CatalogItem::Table(table) => {
+                            for column in table.identity_columns.keys() {
+                                tx.insert_sequence_allocator(sequence_alloc_key(
+                                    id,
+                                    Some(*column),
+                                ))?;
+                            }
+                        }
+                        _ => {}
+                    }
+
                     if Self::should_audit_log_item(&item) {
                         let name = Self::full_name_detail(
                             &state
@@ -1212,6 +1227,7 @@ impl Catalog {
                             if !entry.item().is_temporary() {
                                 tx.remove_item(id)?;
                             }
+                            tx.remove_sequence_allocators(id);
 
                             if entry.item().is_storage_collection() {
                                 storage_collections_to_drop.insert(id);
@@ -721,14 +721,24 @@ impl SessionClient {
         // Build an optimizer for this VIEW.
         let mut optimizer = optimize::view::Optimizer::new(optimizer_config, None);
 
-        let result: Result<_, AdapterError> =
+        let values: Result<_, AdapterError> =
             mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
                 .err_into()
-                .and_then(|values| optimizer.optimize(values).err_into())
-                .and_then(|values| {
-                    // Copied rows must always be constants.
-                    Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
-                });
+                .and_then(|values| optimizer.optimize(values).err_into());
+        let result = match values {
+            // Copied rows must always be constants.
+            Ok(values) => {
+                Coordinator::insert_constant(
+                    &catalog,
+                    self.session(),
+                    id,
+                    values.into_inner(),
+                    &BTreeMap::new(),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
         self.retire_execute(ctx_extra, (&result).into());
         result
     }
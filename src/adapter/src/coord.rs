@@ -109,7 +109,7 @@ use mz_ore::{soft_assert_or_log, soft_panic_or_log, stack};
 use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::role_id::RoleId;
-use mz_repr::{GlobalId, RelationDesc, Timestamp};
+use mz_repr::{GlobalId, RelationDesc, Row, Timestamp};
 use mz_secrets::cache::CachingSecretsReader;
 use mz_secrets::{SecretsController, SecretsReader};
 use mz_sql::ast::{Raw, Statement};
@@ -147,7 +147,7 @@ use crate::catalog::{BuiltinTableUpdate, Catalog};
 use crate::client::{Client, Handle};
 use crate::command::{Command, ExecuteResponse};
 use crate::config::{SynchronizedParameters, SystemParameterFrontend, SystemParameterSyncConfig};
-use crate::coord::appends::{Deferred, GroupCommitPermit, PendingWriteTxn};
+use crate::coord::appends::{Deferred, GroupCommitPermit, PendingWriteTxn, TableKeyCounts};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::PendingPeek;
 use crate::coord::read_policy::ReadHoldsInner;
@@ -202,6 +202,7 @@ pub enum Message<T = mz_repr::Timestamp> {
     PurifiedStatementReady(PurifiedStatementReady),
     CreateConnectionValidationReady(CreateConnectionValidationReady),
     AlterConnectionValidationReady(AlterConnectionValidationReady),
+    CopyFromChunk(CopyFromChunk),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
     /// Initiates a group commit.
     GroupCommitInitiate(Span, Option<GroupCommitPermit>),
@@ -328,6 +329,7 @@ impl Message {
             Message::SubscribeStageReady { .. } => "subscribe_stage_ready",
             Message::DrainStatementLog => "drain_statement_log",
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
+            Message::CopyFromChunk(_) => "copy_from_chunk",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
             Message::CheckSchedulingPolicies => "check_scheduling_policies",
             Message::SchedulingDecisions { .. } => "scheduling_decision",
@@ -363,6 +365,25 @@ pub struct ValidationReady<T> {
 pub type CreateConnectionValidationReady = ValidationReady<CreateConnectionPlan>;
 pub type AlterConnectionValidationReady = ValidationReady<Connection>;
 
+/// A chunk of the rows fetched by a `COPY ... FROM <url>`, which is ready to be
+/// inserted.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct CopyFromChunk {
+    #[derivative(Debug = "ignore")]
+    pub ctx: ExecuteContext,
+    pub id: GlobalId,
+    pub columns: Vec<usize>,
+    #[derivative(Debug = "ignore")]
+    pub rows: Vec<Row>,
+    pub plan_validity: PlanValidity,
+    pub otel_ctx: OpenTelemetryContext,
+    /// Receives `ctx` back, together with the outcome of the insert, once the
+    /// chunk has been committed or has failed.
+    #[derivative(Debug = "ignore")]
+    pub tx: oneshot::Sender<(ExecuteContext, Result<(), AdapterError>)>,
+}
+
 #[derive(Debug)]
 pub enum RealTimeRecencyContext {
     ExplainTimestamp {
@@ -751,7 +772,7 @@ pub enum TargetCluster {
 
 /// A struct to hold information about the validity of plans and if they should be abandoned after
 /// doing work off of the Coordinator thread.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PlanValidity {
     /// The most recent revision at which this plan was verified as valid.
     transient_revision: u64,
@@ -1350,6 +1371,10 @@ pub struct Coordinator {
     write_lock_wait_group: VecDeque<Deferred>,
     /// Pending writes waiting for a group commit.
     pending_writes: Vec<PendingWriteTxn>,
+    /// The counts of the key values of the user tables with keys, which group commits use to
+    /// enforce that keys are unique. They are shared with the tasks that apply group commits,
+    /// which update them once their writes are durable.
+    table_key_counts: Arc<Mutex<BTreeMap<GlobalId, TableKeyCounts>>>,
     /// For the realtime timeline, an explicit SELECT or INSERT on a table will bump the
     /// table's timestamps, but there are cases where timestamps are not bumped but
     /// we expect the closed timestamps to advance (`AS OF X`, SUBSCRIBing views over
@@ -1703,7 +1728,8 @@ impl Coordinator {
                 CatalogItem::Log(_)
                 | CatalogItem::Type(_)
                 | CatalogItem::Func(_)
-                | CatalogItem::Secret(_) => {}
+                | CatalogItem::Secret(_)
+                | CatalogItem::Sequence(_) => {}
             }
         }
 
@@ -1788,6 +1814,28 @@ impl Coordinator {
             builtin_table_updates.extend(retractions);
         }
 
+        // Count the key values of user tables with keys, so that group commits don't have to read
+        // the tables to enforce that keys are unique.
+        debug!("coordinator init: counting table key values");
+        for entry in entries.iter().filter(|entry| entry.id().is_user()) {
+            let CatalogItem::Table(table) = entry.item() else {
+                continue;
+            };
+            if table.desc.typ().keys.is_empty() {
+                continue;
+            }
+            let contents = self
+                .controller
+                .storage
+                .snapshot(entry.id(), read_ts)
+                .await
+                .unwrap_or_terminate("cannot fail to fetch snapshot");
+            self.table_key_counts
+                .lock()
+                .expect("poisoned")
+                .insert(entry.id(), TableKeyCounts::new(table, &contents));
+        }
+
         debug!("coordinator init: sending builtin table updates");
         let builtin_updates_fut = self
             .builtin_table_update()
@@ -3066,6 +3114,7 @@ pub fn serve(
                     write_lock: Arc::new(tokio::sync::Mutex::new(())),
                     write_lock_wait_group: VecDeque::new(),
                     pending_writes: Vec::new(),
+                    table_key_counts: Arc::new(Mutex::new(BTreeMap::new())),
                     advance_timelines_interval,
                     secrets_controller,
                     caching_secrets_reader,
@@ -9,18 +9,22 @@
 
 //! Logic and types for all appends executed by the [`Coordinator`].
 
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use bytes::BytesMut;
 use derivative::Derivative;
+use differential_dataflow::consolidation::consolidate;
 use futures::future::{BoxFuture, FutureExt};
+use mz_catalog::memory::objects::{CatalogItem, Table};
 use mz_ore::instrument;
 use mz_ore::metrics::MetricsFutureExt;
 use mz_ore::task;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::vec::VecExt;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
 use mz_sql::plan::Plan;
 use mz_sql::session::metadata::SessionMetadata;
 use mz_storage_client::client::TimestamplessUpdate;
@@ -30,9 +34,9 @@ use tracing::{debug_span, warn, Instrument, Span};
 
 use crate::catalog::BuiltinTableUpdate;
 use crate::coord::{Coordinator, Message, PendingTxn, PlanValidity};
-use crate::session::{Session, WriteOp};
+use crate::session::{EndTransactionAction, Session, WriteOp};
 use crate::util::{CompletedClientTransmitter, ResultExt};
-use crate::ExecuteContext;
+use crate::{AdapterError, ExecuteContext};
 
 /// An operation that is deferred while waiting for a lock.
 #[derive(Debug)]
@@ -79,6 +83,16 @@ pub(crate) enum PendingWriteTxn {
         updates: Vec<BuiltinTableUpdate>,
         source: BuiltinTableUpdateSource,
     },
+    /// Write a chunk of the rows of a `COPY ... FROM <url>` to a user table.
+    /// Each chunk is committed on its own, outside of the transaction of the
+    /// statement.
+    CopyFrom {
+        span: Span,
+        writes: Vec<WriteOp>,
+        ctx: ExecuteContext,
+        /// Receives `ctx` back once the write has been committed or has failed.
+        tx: oneshot::Sender<(ExecuteContext, Result<(), AdapterError>)>,
+    },
 }
 
 impl PendingWriteTxn {
@@ -87,7 +101,7 @@ impl PendingWriteTxn {
             PendingWriteTxn::User {
                 write_lock_guard, ..
             } => std::mem::take(write_lock_guard),
-            PendingWriteTxn::System { .. } => None,
+            PendingWriteTxn::System { .. } | PendingWriteTxn::CopyFrom { .. } => None,
         }
     }
 
@@ -290,6 +304,8 @@ impl Coordinator {
         let mut appends: BTreeMap<GlobalId, Vec<(Row, Diff)>> = BTreeMap::new();
         let mut responses = Vec::with_capacity(self.pending_writes.len());
         let mut notifies = Vec::new();
+        let mut copy_from_chunks = Vec::new();
+        let mut accepted_keys = BTreeMap::new();
 
         for pending_write_txn in pending_writes {
             match pending_write_txn {
@@ -304,6 +320,14 @@ impl Coordinator {
                             action,
                         },
                 } => {
+                    if let Err(e) = self.check_unique_keys(&writes, &mut accepted_keys) {
+                        responses.push(CompletedClientTransmitter::new(
+                            ctx,
+                            Err(e),
+                            EndTransactionAction::Rollback,
+                        ));
+                        continue;
+                    }
                     for WriteOp { id, rows } in writes {
                         // If the table that some write was targeting has been deleted while the
                         // write was waiting, then the write will be ignored and we respond to the
@@ -320,6 +344,23 @@ impl Coordinator {
 
                     responses.push(CompletedClientTransmitter::new(ctx, response, action));
                 }
+                PendingWriteTxn::CopyFrom {
+                    span: _,
+                    writes,
+                    ctx,
+                    tx,
+                } => {
+                    if let Err(e) = self.check_unique_keys(&writes, &mut accepted_keys) {
+                        send_copy_from_result(tx, ctx, Err(e));
+                        continue;
+                    }
+                    for WriteOp { id, rows } in writes {
+                        if self.catalog().try_get_entry(&id).is_some() {
+                            appends.entry(id).or_default().extend(rows);
+                        }
+                    }
+                    copy_from_chunks.push((ctx, tx));
+                }
                 PendingWriteTxn::System { updates, source } => {
                     for update in updates {
                         appends
@@ -336,7 +377,7 @@ impl Coordinator {
         }
 
         for (_, updates) in &mut appends {
-            differential_dataflow::consolidation::consolidate(updates);
+            consolidate(updates);
         }
         // Add table advancements for all tables.
         for table in self.catalog().entries().filter(|entry| entry.is_table()) {
@@ -368,6 +409,7 @@ impl Coordinator {
 
         // Spawn a task to do the table writes.
         let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let table_key_counts = Arc::clone(&self.table_key_counts);
         let apply_write_fut = self.apply_local_write(timestamp);
 
         let mut span = debug_span!(parent: None, "group_commit_apply");
@@ -381,7 +423,11 @@ impl Coordinator {
                     .await
                 {
                     Ok(append_result) => {
-                        append_result.unwrap_or_terminate("cannot fail to apply appends")
+                        append_result.unwrap_or_terminate("cannot fail to apply appends");
+                        // The next group commit checks its writes against the key counts once it
+                        // holds the write lock, so they must include these writes before it is
+                        // released below.
+                        apply_key_counts(&table_key_counts, accepted_keys);
                     }
                     Err(_) => warn!("Writer terminated with writes in indefinite state"),
                 };
@@ -397,6 +443,10 @@ impl Coordinator {
                     ctx.session_mut().apply_write(timestamp);
                     ctx.retire(result);
                 }
+                for (mut ctx, tx) in copy_from_chunks {
+                    ctx.session_mut().apply_write(timestamp);
+                    send_copy_from_result(tx, ctx, Ok(()));
+                }
 
                 // IMPORTANT: Make sure we hold the permit and write lock until
                 // here, to prevent other writes from going through while we
@@ -455,6 +505,106 @@ impl Coordinator {
             .expect("sending to self.internal_cmd_tx cannot fail");
     }
 
+    /// Checks that `writes` don't give any user table two rows with the same value for one of its
+    /// keys, given the contents of the tables and the writes in `accepted`, which were accepted
+    /// earlier in the same group commit. If they don't, adds their effect on the tables' keys to
+    /// `accepted`. As in PostgreSQL, key values that contain a `NULL` don't conflict with each
+    /// other, unless the key was declared `NULLS NOT DISTINCT`.
+    ///
+    /// The contents of the tables are represented by `table_key_counts`, which only reflects
+    /// durable writes. The counts of a table are loaded when the coordinator starts or when the
+    /// table is created, and each group commit applies its accepted writes to them once its append
+    /// has succeeded. We must hold the write lock, so that no other writes to the tables are in
+    /// flight.
+    fn check_unique_keys(
+        &self,
+        writes: &[WriteOp],
+        accepted: &mut BTreeMap<GlobalId, Vec<BTreeMap<Row, Diff>>>,
+    ) -> Result<(), AdapterError> {
+        let mut keyed_writes: BTreeMap<GlobalId, (String, RelationDesc, Vec<(Row, Diff)>)> =
+            BTreeMap::new();
+        for WriteOp { id, rows } in writes {
+            let Some(entry) = self.catalog().try_get_entry(id) else {
+                continue;
+            };
+            let CatalogItem::Table(table) = entry.item() else {
+                continue;
+            };
+            if table.desc.typ().keys.is_empty() {
+                continue;
+            }
+            let (_, _, updates) = keyed_writes.entry(*id).or_insert_with(|| {
+                let name = self.catalog().resolve_full_name(entry.name(), None);
+                (name.to_string(), table.desc.clone(), Vec::new())
+            });
+            updates.extend(rows.iter().cloned());
+        }
+
+        let table_key_counts = self.table_key_counts.lock().expect("poisoned");
+        let mut deltas = BTreeMap::new();
+        for (id, (name, desc, mut updates)) in keyed_writes {
+            let Some(table_counts) = table_key_counts.get(&id) else {
+                return Err(AdapterError::Internal(format!(
+                    "missing key counts for table {name}"
+                )));
+            };
+
+            consolidate(&mut updates);
+            let mut table_deltas = Vec::new();
+            for (idx, ((key, nulls_distinct), counts)) in table_counts
+                .keys
+                .iter()
+                .zip(&table_counts.counts)
+                .enumerate()
+            {
+                let earlier = accepted.get(&id).map(|deltas| &deltas[idx]);
+                let delta = count_key_values(key, *nulls_distinct, &updates);
+                for (value, diff) in &delta {
+                    let count = counts.get(value).copied().unwrap_or(0)
+                        + earlier
+                            .and_then(|earlier| earlier.get(value))
+                            .copied()
+                            .unwrap_or(0);
+                    if count + diff > 1 {
+                        return Err(AdapterError::UniqueViolation {
+                            table: name,
+                            columns: key.iter().map(|i| desc.get_name(*i).to_string()).collect(),
+                            values: std::iter::zip(key, value.iter())
+                                .map(|(i, datum)| {
+                                    let typ = &desc.typ().column_types[*i].scalar_type;
+                                    match mz_pgrepr::Value::from_datum(datum, typ) {
+                                        Some(value) => {
+                                            let mut buf = BytesMut::new();
+                                            value.encode_text(&mut buf);
+                                            String::from_utf8_lossy(&buf).into_owned()
+                                        }
+                                        None => "null".into(),
+                                    }
+                                })
+                                .collect(),
+                        });
+                    }
+                }
+                table_deltas.push(delta);
+            }
+            deltas.insert(id, table_deltas);
+        }
+
+        for (id, table_deltas) in deltas {
+            match accepted.entry(id) {
+                Entry::Occupied(mut entry) => {
+                    for (counts, delta) in entry.get_mut().iter_mut().zip(table_deltas) {
+                        add_key_counts(counts, delta);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(table_deltas);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Submit a write to be executed during the next group commit and trigger a group commit.
     pub(crate) fn submit_write(&mut self, pending_write_txn: PendingWriteTxn) {
         self.pending_writes.push(pending_write_txn);
@@ -501,6 +651,102 @@ impl Coordinator {
     }
 }
 
+/// The number of rows with each value of each key of a user table, used to enforce that keys are
+/// unique.
+#[derive(Debug)]
+pub(crate) struct TableKeyCounts {
+    /// The columns of each key of the table, and whether values of the key that contain a `NULL`
+    /// are distinct from each other.
+    keys: Vec<(Vec<usize>, bool)>,
+    /// The counts of each value of each key, in the order of `keys`.
+    counts: Vec<BTreeMap<Row, Diff>>,
+}
+
+impl TableKeyCounts {
+    /// Counts the values of the keys of `table` in its `contents`.
+    pub(crate) fn new(table: &Table, contents: &[(Row, Diff)]) -> TableKeyCounts {
+        let keys: Vec<_> = table
+            .desc
+            .typ()
+            .keys
+            .iter()
+            .map(|key| (key.clone(), !table.nulls_not_distinct_keys.contains(key)))
+            .collect();
+        let counts = keys
+            .iter()
+            .map(|(key, nulls_distinct)| count_key_values(key, *nulls_distinct, contents))
+            .collect();
+        TableKeyCounts { keys, counts }
+    }
+}
+
+/// Applies the key counts of the writes that a group commit accepted, once they are durable.
+/// Tables that were dropped in the meantime are skipped.
+fn apply_key_counts(
+    table_key_counts: &Mutex<BTreeMap<GlobalId, TableKeyCounts>>,
+    accepted: BTreeMap<GlobalId, Vec<BTreeMap<Row, Diff>>>,
+) {
+    let mut table_key_counts = table_key_counts.lock().expect("poisoned");
+    for (id, deltas) in accepted {
+        let Some(table_counts) = table_key_counts.get_mut(&id) else {
+            continue;
+        };
+        for (counts, delta) in table_counts.counts.iter_mut().zip(deltas) {
+            add_key_counts(counts, delta);
+        }
+    }
+}
+
+/// Adds the counts in `delta` to `counts`, omitting values whose count drops to zero.
+fn add_key_counts(counts: &mut BTreeMap<Row, Diff>, delta: BTreeMap<Row, Diff>) {
+    for (value, diff) in delta {
+        match counts.entry(value) {
+            Entry::Occupied(mut entry) => {
+                *entry.get_mut() += diff;
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(diff);
+            }
+        }
+    }
+}
+
+/// Counts the rows in `updates` with each value of the columns in `key`, omitting values whose
+/// count is zero. If `nulls_distinct` is true, values that contain a `NULL` are omitted too, since
+/// they never conflict with each other.
+fn count_key_values(
+    key: &[usize],
+    nulls_distinct: bool,
+    updates: &[(Row, Diff)],
+) -> BTreeMap<Row, Diff> {
+    let mut counts = BTreeMap::new();
+    for (row, diff) in updates {
+        let datums = row.unpack();
+        if nulls_distinct && key.iter().any(|i| datums[*i].is_null()) {
+            continue;
+        }
+        let value = Row::pack(key.iter().map(|i| datums[*i]));
+        *counts.entry(value).or_insert(0) += diff;
+    }
+    counts.retain(|_, count| *count != 0);
+    counts
+}
+
+/// Hands `ctx` back to the `COPY ... FROM <url>` that wrote a chunk of rows, together with the
+/// outcome of the write. If the copy is no longer waiting for it, the statement is retired here.
+pub(crate) fn send_copy_from_result(
+    tx: oneshot::Sender<(ExecuteContext, Result<(), AdapterError>)>,
+    ctx: ExecuteContext,
+    result: Result<(), AdapterError>,
+) {
+    if let Err((ctx, result)) = tx.send((ctx, result)) {
+        ctx.retire(result.and(Err(AdapterError::Canceled)));
+    }
+}
+
 /// Helper struct to run a builtin table append.
 pub struct BuiltinTableAppend<'a> {
     coord: &'a mut Coordinator,
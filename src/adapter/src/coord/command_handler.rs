@@ -57,7 +57,7 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 use crate::command::{
     CatalogSnapshot, Command, ExecuteResponse, GetVariablesResponse, StartupResponse,
 };
-use crate::coord::appends::{Deferred, PendingWriteTxn};
+use crate::coord::appends::{send_copy_from_result, Deferred, PendingWriteTxn};
 use crate::coord::{
     ConnMeta, Coordinator, Message, PendingTxn, PlanValidity, PurifiedStatementReady,
 };
@@ -527,7 +527,10 @@ impl Coordinator {
                     }
 
                     Statement::Insert(InsertStatement {
-                        source, returning, ..
+                        source,
+                        on_conflict: None,
+                        returning,
+                        ..
                     }) if returning.is_empty() && ConstantVisitor::insert_source(source) => {
                         // Inserting from constant values statements that do not need to execute on
                         // any cluster (no RETURNING) is always safe.
@@ -573,6 +576,7 @@ impl Coordinator {
                     | Statement::CreateRole(_)
                     | Statement::CreateSchema(_)
                     | Statement::CreateSecret(_)
+                    | Statement::CreateSequence(_)
                     | Statement::CreateSink(_)
                     | Statement::CreateSource(_)
                     | Statement::CreateSubsource(_)
@@ -586,6 +590,7 @@ impl Coordinator {
                     | Statement::GrantPrivileges(_)
                     | Statement::GrantRole(_)
                     | Statement::Insert(_)
+                    | Statement::Merge(_)
                     | Statement::ReassignOwned(_)
                     | Statement::RevokePrivileges(_)
                     | Statement::RevokeRole(_)
@@ -975,6 +980,17 @@ impl Coordinator {
             }
         }
 
+        // Cancel pending chunks of a `COPY ... FROM <url>`, which hand the context back to the
+        // task that is copying the rows, so that it stops copying.
+        if let Some(idx) = self.pending_writes.iter().position(|pending_write_txn| {
+            matches!(pending_write_txn, PendingWriteTxn::CopyFrom { ctx, .. }
+                if *ctx.session().conn_id() == conn_id)
+        }) {
+            if let PendingWriteTxn::CopyFrom { ctx, tx, .. } = self.pending_writes.remove(idx) {
+                send_copy_from_result(tx, ctx, Err(AdapterError::Canceled));
+            }
+        }
+
         // Cancel deferred writes. There is at most one deferred write per session.
         if let Some(idx) = self
             .write_lock_wait_group
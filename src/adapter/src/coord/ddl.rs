@@ -700,6 +700,7 @@ impl Coordinator {
     fn drop_tables(&mut self, tables: Vec<GlobalId>, ts: Timestamp) {
         for id in &tables {
             self.drop_storage_read_policy(id);
+            self.table_key_counts.lock().expect("poisoned").remove(id);
         }
         self.controller
             .storage
@@ -1162,7 +1163,8 @@ impl Coordinator {
                                 Connection::Csr(_)
                                 | Connection::Ssh(_)
                                 | Connection::Aws(_)
-                                | Connection::MySql(_) => {}
+                                | Connection::MySql(_)
+                                | Connection::SqlServer(_) => {}
                             }
                         }
                         CatalogItem::Table(_) => {
@@ -1182,7 +1184,8 @@ impl Coordinator {
                         | CatalogItem::View(_)
                         | CatalogItem::Index(_)
                         | CatalogItem::Type(_)
-                        | CatalogItem::Func(_) => {}
+                        | CatalogItem::Func(_)
+                        | CatalogItem::Sequence(_) => {}
                     }
                 }
                 Op::DropObject(id) => match id {
@@ -1247,7 +1250,8 @@ impl Coordinator {
                             | CatalogItem::View(_)
                             | CatalogItem::Index(_)
                             | CatalogItem::Type(_)
-                            | CatalogItem::Func(_) => {}
+                            | CatalogItem::Func(_)
+                            | CatalogItem::Sequence(_) => {}
                         }
                     }
                 },
@@ -1275,7 +1279,8 @@ impl Coordinator {
                     | CatalogItem::View(_)
                     | CatalogItem::Index(_)
                     | CatalogItem::Type(_)
-                    | CatalogItem::Func(_) => {}
+                    | CatalogItem::Func(_)
+                    | CatalogItem::Sequence(_) => {}
                 },
                 Op::AlterRole { .. }
                 | Op::AlterRetainHistory { .. }
@@ -1317,7 +1322,8 @@ impl Coordinator {
                 Connection::Csr(_)
                 | Connection::Ssh(_)
                 | Connection::Aws(_)
-                | Connection::MySql(_) => {}
+                | Connection::MySql(_)
+                | Connection::SqlServer(_) => {}
             }
         }
         self.validate_resource_limit(
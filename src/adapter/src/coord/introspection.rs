@@ -76,6 +76,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::CreateSource(_)
         | Plan::CreateSources(_)
         | Plan::CreateSecret(_)
+        | Plan::CreateSequence(_)
         | Plan::CreateSink(_)
         | Plan::CreateTable(_)
         | Plan::CreateView(_)
@@ -85,6 +86,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::Comment(_)
         | Plan::DiscardTemp
         | Plan::DiscardAll
+        | Plan::DiscardSequences
         | Plan::DropObjects(_)
         | Plan::DropOwned(_)
         | Plan::EmptyQuery
@@ -29,19 +29,21 @@ use mz_sql::pure::PurifiedStatement;
 use mz_storage_types::controller::CollectionMetadata;
 use opentelemetry::trace::TraceContextExt;
 use rand::{rngs, Rng, SeedableRng};
-use tracing::{event, info_span, warn, Instrument, Level};
+use tracing::{event, info_span, warn, Instrument, Level, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReason};
 use crate::command::Command;
-use crate::coord::appends::Deferred;
+use crate::coord::appends::{send_copy_from_result, Deferred, PendingWriteTxn};
 use crate::coord::statement_logging::StatementLoggingId;
 use crate::coord::{
-    AlterConnectionValidationReady, Coordinator, CreateConnectionValidationReady, Message,
-    PeekStage, PeekStageTimestampReadHold, PlanValidity, PurifiedStatementReady,
+    AlterConnectionValidationReady, Coordinator, CopyFromChunk, CreateConnectionValidationReady,
+    Message, PeekStage, PeekStageTimestampReadHold, PlanValidity, PurifiedStatementReady,
     RealTimeRecencyContext,
 };
-use crate::session::Session;
+use crate::error::AdapterError;
+use crate::optimize::{self, Optimize};
+use crate::session::{Session, WriteOp};
 use crate::statement_logging::StatementLifecycleEvent;
 use crate::util::ResultExt;
 use crate::{catalog, AdapterNotice, TimestampContext};
@@ -95,6 +97,7 @@ impl Coordinator {
                 Message::AlterConnectionValidationReady(ready) => {
                     self.message_alter_connection_validation_ready(ready).await
                 }
+                Message::CopyFromChunk(chunk) => self.message_copy_from_chunk(chunk).await,
                 Message::WriteLockGrant(write_lock_guard) => {
                     self.message_write_lock_grant(write_lock_guard).await;
                 }
@@ -505,6 +508,36 @@ impl Coordinator {
                 )
                 .await
             }
+            PurifiedStatement::PurifiedAlterSourceRefreshSchema {
+                altered_id,
+                details,
+                subsources,
+            } => {
+                let action = mz_sql::plan::AlterSourceAction::RefreshSchema {
+                    details,
+                    subsources,
+                };
+                Ok((
+                    mz_sql::plan::Plan::AlterSource(mz_sql::plan::AlterSourcePlan {
+                        id: altered_id,
+                        action,
+                    }),
+                    ResolvedIds(BTreeSet::new()),
+                ))
+            }
+            PurifiedStatement::PurifiedAlterSourceResetTo {
+                altered_id,
+                start_offsets,
+            } => {
+                let action = mz_sql::plan::AlterSourceAction::ResetTo { start_offsets };
+                Ok((
+                    mz_sql::plan::Plan::AlterSource(mz_sql::plan::AlterSourcePlan {
+                        id: altered_id,
+                        action,
+                    }),
+                    ResolvedIds(BTreeSet::new()),
+                ))
+            }
             o @ (PurifiedStatement::PurifiedAlterSource { .. }
             | PurifiedStatement::PurifiedCreateSink(..)) => {
                 // Unify these into a `Statement`.
@@ -514,7 +547,9 @@ impl Coordinator {
                     }
                     PurifiedStatement::PurifiedCreateSink(stmt) => Statement::CreateSink(stmt),
                     PurifiedStatement::PurifiedCreateSource { .. }
-                    | PurifiedStatement::PurifiedAlterSourceAddSubsources { .. } => {
+                    | PurifiedStatement::PurifiedAlterSourceAddSubsources { .. }
+                    | PurifiedStatement::PurifiedAlterSourceRefreshSchema { .. }
+                    | PurifiedStatement::PurifiedAlterSourceResetTo { .. } => {
                         unreachable!("not part of exterior match stmt")
                     }
                 };
@@ -610,6 +645,64 @@ impl Coordinator {
         ctx.retire(result);
     }
 
+    #[mz_ore::instrument(level = "debug")]
+    async fn message_copy_from_chunk(
+        &mut self,
+        CopyFromChunk {
+            mut ctx,
+            id,
+            columns,
+            rows,
+            mut plan_validity,
+            otel_ctx,
+            tx,
+        }: CopyFromChunk,
+    ) {
+        otel_ctx.attach_as_parent();
+
+        // Ensure that the table and the connection still exist after fetching
+        // the rows.
+        if let Err(e) = plan_validity.check(self.catalog()) {
+            return send_copy_from_result(tx, ctx, Err(e));
+        }
+
+        let catalog = self.owned_catalog();
+        let pcx = ctx.session().pcx().clone();
+        let values = {
+            let conn_catalog = catalog.for_session(ctx.session());
+            // Collect optimizer parameters.
+            let optimizer_config = optimize::OptimizerConfig::from(conn_catalog.system_vars());
+            // Build an optimizer for this VIEW.
+            let mut optimizer = optimize::view::Optimizer::new(optimizer_config, None);
+            mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
+                .map_err(AdapterError::from)
+                .and_then(|values| optimizer.optimize(values).map_err(AdapterError::from))
+        };
+        let updates = match values {
+            // Copied rows must always be constants.
+            Ok(values) => {
+                Self::constant_updates(
+                    &catalog,
+                    ctx.session_mut(),
+                    id,
+                    values.into_inner(),
+                    &BTreeMap::new(),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        match updates {
+            Ok(updates) => self.submit_write(PendingWriteTxn::CopyFrom {
+                span: Span::current(),
+                writes: vec![WriteOp { id, rows: updates }],
+                ctx,
+                tx,
+            }),
+            Err(e) => send_copy_from_result(tx, ctx, Err(e)),
+        }
+    }
+
     #[mz_ore::instrument(level = "debug")]
     async fn message_write_lock_grant(
         &mut self,
@@ -15,21 +15,24 @@
 use futures::future::LocalBoxFuture;
 use futures::FutureExt;
 use inner::return_if_err;
+use mz_catalog::memory::objects::CatalogItem;
 use mz_controller_types::ClusterId;
-use mz_expr::{MirRelationExpr, OptimizedMirRelationExpr, RowSetFinishing};
+use mz_expr::{MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing};
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::explain::ExplainFormat;
-use mz_repr::{Diff, GlobalId, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_sql::catalog::CatalogError;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{
     self, AbortTransactionPlan, CommitTransactionPlan, CreateRolePlan, CreateSourcePlanBundle,
-    FetchPlan, MutationKind, Params, Plan, PlanKind, QueryWhen, RaisePlan,
+    FetchPlan, MutationKind, Params, Plan, PlanKind, QueryWhen, RaisePlan, TableCheck,
 };
 use mz_sql::rbac;
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql_parser::ast::{Raw, Statement};
 use mz_storage_types::connections::inline::IntoInlineConnection;
+use sequences::{fill_identity_columns, fill_sequence_defaults, resolve_sequence_calls};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 use tracing::{event, Instrument, Level, Span};
@@ -62,6 +65,7 @@ use crate::ExecuteContext;
 mod alter_set_cluster;
 mod cluster;
 mod inner;
+mod sequences;
 
 impl Coordinator {
     /// BOXED FUTURE: As of Nov 2023 the returned Future from this function was 34KB. This would
@@ -206,6 +210,10 @@ impl Coordinator {
                     let result = self.sequence_create_secret(ctx.session_mut(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::CreateSequence(plan) => {
+                    let result = self.sequence_create_sequence(ctx.session_mut(), plan).await;
+                    ctx.retire(result);
+                }
                 Plan::CreateSink(plan) => {
                     self.sequence_create_sink(ctx, plan, resolved_ids).await;
                 }
@@ -306,7 +314,17 @@ impl Coordinator {
                     }
                     self.sequence_end_transaction(ctx, action).await;
                 }
-                Plan::Select(plan) => {
+                Plan::Select(mut plan) => {
+                    return_if_err!(
+                        resolve_sequence_calls(
+                            self.catalog(),
+                            ctx.session_mut(),
+                            vec![&mut plan.source],
+                            vec![],
+                        )
+                        .await,
+                        ctx
+                    );
                     self.sequence_peek(ctx, plan, target_cluster).await;
                 }
                 Plan::Subscribe(plan) => {
@@ -322,18 +340,23 @@ impl Coordinator {
                     self.sequence_peek(ctx, show_columns_plan.select_plan, target_cluster)
                         .await;
                 }
-                Plan::CopyFrom(plan) => {
-                    let (tx, _, session, ctx_extra) = ctx.into_parts();
-                    tx.send(
-                        Ok(ExecuteResponse::CopyFrom {
-                            id: plan.id,
-                            columns: plan.columns,
-                            params: plan.params,
-                            ctx_extra,
-                        }),
-                        session,
-                    );
-                }
+                Plan::CopyFrom(plan) => match plan.source {
+                    plan::CopyFromSource::Stdin => {
+                        let (tx, _, session, ctx_extra) = ctx.into_parts();
+                        tx.send(
+                            Ok(ExecuteResponse::CopyFrom {
+                                id: plan.id,
+                                columns: plan.columns,
+                                params: plan.params,
+                                ctx_extra,
+                            }),
+                            session,
+                        );
+                    }
+                    plan::CopyFromSource::Url { .. } => {
+                        self.sequence_copy_from(ctx, plan, resolved_ids).await;
+                    }
+                },
                 Plan::ExplainPlan(plan) => {
                     self.sequence_explain_plan(ctx, plan, target_cluster).await;
                 }
@@ -349,10 +372,33 @@ impl Coordinator {
                     self.sequence_explain_timestamp(ctx, plan, target_cluster)
                         .await;
                 }
-                Plan::Insert(plan) => {
+                Plan::Insert(mut plan) => {
+                    return_if_err!(
+                        resolve_sequence_calls(
+                            self.catalog(),
+                            ctx.session_mut(),
+                            vec![&mut plan.values],
+                            plan.returning.iter_mut().collect(),
+                        )
+                        .await,
+                        ctx
+                    );
                     self.sequence_insert(ctx, plan).await;
                 }
-                Plan::ReadThenWrite(plan) => {
+                Plan::ReadThenWrite(mut plan) => {
+                    return_if_err!(
+                        resolve_sequence_calls(
+                            self.catalog(),
+                            ctx.session_mut(),
+                            vec![&mut plan.selection],
+                            plan.assignments
+                                .values_mut()
+                                .chain(plan.returning.iter_mut())
+                                .collect(),
+                        )
+                        .await,
+                        ctx
+                    );
                     self.sequence_read_then_write(ctx, plan).await;
                 }
                 Plan::AlterNoop(plan) => {
@@ -459,6 +505,10 @@ impl Coordinator {
                     };
                     ctx.retire(ret);
                 }
+                Plan::DiscardSequences => {
+                    ctx.session_mut().sequence_values_mut().clear();
+                    ctx.retire(Ok(ExecuteResponse::DiscardedSequences));
+                }
                 Plan::Declare(plan) => {
                     self.declare(ctx, plan.name, plan.stmt, plan.sql, plan.params);
                 }
@@ -719,16 +769,46 @@ impl Coordinator {
         }
     }
 
-    pub(crate) fn insert_constant(
+    pub(crate) async fn insert_constant(
         catalog: &Catalog,
         session: &mut Session,
         id: GlobalId,
         constants: MirRelationExpr,
+        sequence_defaults: &BTreeMap<usize, MirScalarExpr>,
     ) -> Result<ExecuteResponse, AdapterError> {
+        let rows =
+            Self::constant_updates(catalog, session, id, constants, sequence_defaults).await?;
+        let diffs_plan = plan::SendDiffsPlan {
+            id,
+            updates: rows,
+            kind: MutationKind::Insert,
+            returning: Vec::new(),
+            max_result_size: catalog.system_config().max_result_size(),
+            affected_rows: None,
+        };
+        Self::send_diffs(session, diffs_plan)
+    }
+
+    /// Returns the updates that inserting `constants` into the table `id`
+    /// makes, with their identity columns and the columns with the
+    /// `sequence_defaults` filled in, after checking that they satisfy the
+    /// constraints of the table.
+    pub(crate) async fn constant_updates(
+        catalog: &Catalog,
+        session: &mut Session,
+        id: GlobalId,
+        constants: MirRelationExpr,
+        sequence_defaults: &BTreeMap<usize, MirScalarExpr>,
+    ) -> Result<Vec<(Row, Diff)>, AdapterError> {
         // Insert can be queued, so we need to re-verify the id exists.
-        let desc = match catalog.try_get_entry(&id) {
-            Some(table) => {
-                table.desc(&catalog.resolve_full_name(table.name(), Some(session.conn_id())))?
+        let (desc, name, table) = match catalog.try_get_entry(&id) {
+            Some(entry) => {
+                let name = catalog.resolve_full_name(entry.name(), Some(session.conn_id()));
+                let table = match entry.item() {
+                    CatalogItem::Table(table) => Some(table),
+                    _ => None,
+                };
+                (entry.desc(&name)?, name, table)
             }
             None => {
                 return Err(AdapterError::Catalog(mz_catalog::memory::error::Error {
@@ -741,20 +821,32 @@ impl Coordinator {
 
         match constants.as_const() {
             Some((rows, ..)) => {
-                let rows = rows.clone()?;
+                let mut rows = rows.clone()?;
+                let checks = match table {
+                    Some(table) => {
+                        rows = fill_sequence_defaults(catalog, session, sequence_defaults, rows)
+                            .await?;
+                        rows = fill_identity_columns(
+                            catalog,
+                            id,
+                            &name,
+                            &table.identity_columns,
+                            &desc,
+                            rows,
+                        )
+                        .await?;
+                        table.checks.as_slice()
+                    }
+                    None => &[],
+                };
+                let name = name.to_string();
                 for (row, _) in &rows {
                     for (i, datum) in row.iter().enumerate() {
                         desc.constraints_met(i, &datum)?;
                     }
+                    check_constraints_met(&name, checks, row)?;
                 }
-                let diffs_plan = plan::SendDiffsPlan {
-                    id,
-                    updates: rows,
-                    kind: MutationKind::Insert,
-                    returning: Vec::new(),
-                    max_result_size: catalog.system_config().max_result_size(),
-                };
-                Self::send_diffs(session, diffs_plan)
+                Ok(rows)
             }
             None => panic!(
                 "tried using sequence_insert_constant on non-constant MirRelationExpr {:?}",
@@ -768,7 +860,19 @@ impl Coordinator {
         session: &mut Session,
         mut plan: plan::SendDiffsPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let affected_rows = {
+        let affected_rows = if let Some(affected_rows) = plan.affected_rows {
+            affected_rows
+        } else if let MutationKind::Upsert = plan.kind {
+            // Every inserted or updated row counts once, even if the update
+            // leaves it unchanged.
+            let affected_rows: Diff = plan
+                .updates
+                .iter()
+                .filter(|(_, diff)| *diff > 0)
+                .map(|(_, diff)| diff)
+                .sum();
+            usize::try_from(affected_rows).expect("positive isize must fit")
+        } else {
             let mut affected_rows = Diff::from(0);
             let mut all_positive_diffs = true;
             // If all diffs are positive, the number of affected rows is just the
@@ -827,8 +931,32 @@ impl Coordinator {
         }
         Ok(match plan.kind {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
-            MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
+            MutationKind::Insert | MutationKind::Upsert => ExecuteResponse::Inserted(affected_rows),
             MutationKind::Update => ExecuteResponse::Updated(affected_rows / 2),
+            MutationKind::Merge => ExecuteResponse::Merged(affected_rows),
         })
     }
 }
+
+/// Checks that `row`, which is to be added to the table named `table`, satisfies the table's
+/// `CHECK` constraints. As in PostgreSQL, a condition that evaluates to `NULL` is satisfied.
+pub(crate) fn check_constraints_met(
+    table: &str,
+    checks: &[TableCheck],
+    row: &Row,
+) -> Result<(), AdapterError> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+    let datums = row.unpack();
+    let arena = RowArena::new();
+    for check in checks {
+        if check.expr.eval(&datums, &arena)? == Datum::False {
+            return Err(AdapterError::CheckViolation {
+                table: table.to_string(),
+                constraint: check.name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
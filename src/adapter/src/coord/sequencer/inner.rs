@@ -43,15 +43,17 @@ use mz_sql::names::{
     Aug, ObjectId, QualifiedItemName, ResolvedDatabaseSpecifier, ResolvedIds, ResolvedItemName,
     SchemaSpecifier, SystemObjectId,
 };
+use mz_storage_types::sources::kafka::KAFKA_MAX_EPOCH;
 use mz_storage_types::sources::postgres::{
     PostgresSourcePublicationDetails, ProtoPostgresSourcePublicationDetails,
 };
+use mz_storage_types::sources::GenericSourceConnection;
 use prost::Message as _;
 use timely::progress::Timestamp as TimelyTimestamp;
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
 use mz_adapter_types::connection::ConnectionId;
 use mz_catalog::memory::objects::{
-    CatalogItem, Cluster, Connection, DataSourceDesc, Secret, Sink, Source, Table, Type,
+    CatalogItem, Cluster, Connection, DataSourceDesc, Secret, Sequence, Sink, Source, Table, Type,
 };
 use mz_ore::instrument;
 use mz_sql::ast::AlterSourceAddSubsourceOption;
@@ -70,6 +72,7 @@ use mz_sql::{plan, rbac};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     ConnectionOption, ConnectionOptionName, CreateSourceConnection, DeferredItemName,
+    KafkaSourceConfigOption, KafkaSourceConfigOptionName, MySqlConfigOption, MySqlConfigOptionName,
     PgConfigOption, PgConfigOptionName, Statement, TransactionMode, WithOptionValue,
 };
 use mz_ssh_util::keys::SshKeyPairSet;
@@ -85,8 +88,10 @@ use tracing::{warn, Instrument, Span};
 
 use crate::catalog::{self, Catalog, ConnCatalog, UpdatePrivilegeVariant};
 use crate::command::{ExecuteResponse, Response};
-use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
+use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn, TableKeyCounts};
 use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::sequencer::check_constraints_met;
+use crate::coord::sequencer::sequences::{fill_identity_columns, fill_sequence_defaults};
 use crate::coord::timestamp_selection::{TimestampDetermination, TimestampSource};
 use crate::coord::{
     AlterConnectionValidationReady, Coordinator, CreateConnectionValidationReady, ExecuteContext,
@@ -105,6 +110,7 @@ use crate::{
     guard_write_critical_section, PeekResponseUnary, TimelineContext, TimestampExplanation,
 };
 
+mod copy_from;
 mod create_index;
 mod create_materialized_view;
 mod create_view;
@@ -774,6 +780,10 @@ impl Coordinator {
             create_sql: Some(table.create_sql),
             desc: table.desc,
             defaults: table.defaults,
+            generated_columns: table.generated_columns,
+            checks: table.checks,
+            identity_columns: table.identity_columns,
+            nulls_not_distinct_keys: table.nulls_not_distinct_keys,
             conn_id: conn_id.cloned(),
             resolved_ids,
             custom_logical_compaction_window: table.compaction_window,
@@ -811,6 +821,14 @@ impl Coordinator {
                     .unwrap_or_terminate("cannot fail to create collections");
                 coord.apply_local_write(register_ts).await;
 
+                if !table.desc.typ().keys.is_empty() {
+                    coord
+                        .table_key_counts
+                        .lock()
+                        .expect("poisoned")
+                        .insert(table_id, TableKeyCounts::new(&table, &[]));
+                }
+
                 coord
                     .initialize_storage_read_policies(
                         btreeset![table_id],
@@ -891,6 +909,45 @@ impl Coordinator {
         }
     }
 
+    #[instrument]
+    pub(super) async fn sequence_create_sequence(
+        &mut self,
+        session: &mut Session,
+        plan: plan::CreateSequencePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let plan::CreateSequencePlan {
+            name,
+            sequence,
+            if_not_exists,
+        } = plan;
+
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let ops = vec![catalog::Op::CreateItem {
+            id,
+            name: name.clone(),
+            item: CatalogItem::Sequence(Sequence {
+                create_sql: sequence.create_sql,
+                options: sequence.options,
+            }),
+            owner_id: *session.current_role_id(),
+        }];
+
+        match self.catalog_transact(Some(session), ops).await {
+            Ok(()) => Ok(ExecuteResponse::CreatedSequence),
+            Err(AdapterError::Catalog(mz_catalog::memory::error::Error {
+                kind:
+                    mz_catalog::memory::error::ErrorKind::Sql(CatalogError::ItemAlreadyExists(_, _)),
+            })) if if_not_exists => {
+                session.add_notice(AdapterNotice::ObjectAlreadyExists {
+                    name: name.item,
+                    ty: "sequence",
+                });
+                Ok(ExecuteResponse::CreatedSequence)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     #[instrument]
     pub(super) async fn sequence_create_sink(
         &mut self,
@@ -2362,8 +2419,14 @@ impl Coordinator {
             selection if selection.as_const().is_some() && plan.returning.is_empty() => {
                 let catalog = self.owned_catalog();
                 mz_ore::task::spawn(|| "coord::sequence_inner", async move {
-                    let result =
-                        Self::insert_constant(&catalog, ctx.session_mut(), plan.id, selection);
+                    let result = Self::insert_constant(
+                        &catalog,
+                        ctx.session_mut(),
+                        plan.id,
+                        selection,
+                        &plan.sequence_defaults,
+                    )
+                    .await;
                     ctx.retire(result);
                 });
             }
@@ -2410,6 +2473,7 @@ impl Coordinator {
                     finishing,
                     assignments: BTreeMap::new(),
                     kind: MutationKind::Insert,
+                    sequence_defaults: plan.sequence_defaults,
                     returning: plan.returning,
                 };
 
@@ -2439,19 +2503,28 @@ impl Coordinator {
             selection,
             assignments,
             finishing,
+            sequence_defaults,
             returning,
         } = plan;
 
         // Read then writes can be queued, so re-verify the id exists.
-        let desc = match self.catalog().try_get_entry(&id) {
-            Some(table) => table
-                .desc(
-                    &self
-                        .catalog()
-                        .resolve_full_name(table.name(), Some(ctx.session().conn_id())),
-                )
-                .expect("desc called on table")
-                .into_owned(),
+        let (desc, table_name, checks, identity_columns) = match self.catalog().try_get_entry(&id) {
+            Some(table) => {
+                let name = self
+                    .catalog()
+                    .resolve_full_name(table.name(), Some(ctx.session().conn_id()));
+                let (checks, identity_columns) = match table.item() {
+                    CatalogItem::Table(table) => {
+                        (table.checks.clone(), table.identity_columns.clone())
+                    }
+                    _ => (Vec::new(), BTreeMap::new()),
+                };
+                let desc = table
+                    .desc(&name)
+                    .expect("desc called on table")
+                    .into_owned();
+                (desc, name, checks, identity_columns)
+            }
             None => {
                 ctx.retire(Err(AdapterError::Catalog(
                     mz_catalog::memory::error::Error {
@@ -2493,7 +2566,8 @@ impl Coordinator {
                     // Cannot select from sinks or indexes
                     Sink | Index => unreachable!(),
                     Table => id.is_user(),
-                    Type => true,
+                    // Sequence calls are resolved before the read occurs.
+                    Type | Sequence => true,
                 },
                 None => false,
             }
@@ -2541,6 +2615,7 @@ impl Coordinator {
         let internal_cmd_tx = self.internal_cmd_tx.clone();
         let strict_serializable_reads_tx = self.strict_serializable_reads_tx.clone();
         let max_result_size = self.catalog().system_config().max_result_size();
+        let catalog = self.owned_catalog();
         task::spawn(|| format!("sequence_read_then_write:{id}"), async move {
             let (peek_response, session) = match peek_rx.await {
                 Ok(Response {
@@ -2578,7 +2653,55 @@ impl Coordinator {
                 // Use 2x row len incase there's some assignments.
                 let mut diffs = Vec::with_capacity(rows.len() * 2);
                 let mut datum_vec = mz_repr::DatumVec::new();
+                let mut upserted_rows = BTreeSet::new();
+                let mut merged_rows = BTreeSet::new();
                 for row in rows {
+                    if let MutationKind::Merge = kind {
+                        // Merges read rows of the form `[existing, new,
+                        // retract, insert]`.
+                        let datums = datum_vec.borrow_with(&row);
+                        let arity = (datums.len() - 2) / 2;
+                        if datums[2 * arity] == Datum::True {
+                            let existing = Row::pack_slice(&datums[..arity]);
+                            if !merged_rows.insert(existing.clone()) {
+                                return Err(AdapterError::MergeCardinalityViolation);
+                            }
+                            diffs.push((existing, -1));
+                        }
+                        if datums[2 * arity + 1] == Datum::True {
+                            diffs.push((Row::pack_slice(&datums[arity..2 * arity]), 1));
+                        }
+                        continue;
+                    }
+                    if let MutationKind::Upsert = kind {
+                        // Upserts read rows of the form `[existing, excluded,
+                        // conflicted]`. Conflicting rows update the existing
+                        // row, all others insert the excluded one.
+                        let mut datums = datum_vec.borrow_with(&row);
+                        let arity = (datums.len() - 1) / 2;
+                        if datums[2 * arity] != Datum::True {
+                            diffs.push((Row::pack_slice(&datums[arity..2 * arity]), 1));
+                            continue;
+                        }
+                        let existing = Row::pack_slice(&datums[..arity]);
+                        if !upserted_rows.insert(existing.clone()) {
+                            return Err(AdapterError::OnConflictCardinalityViolation);
+                        }
+                        let mut updates = vec![];
+                        for (idx, expr) in &assignments {
+                            let updated = match expr.eval(&datums, &arena) {
+                                Ok(updated) => updated,
+                                Err(e) => return Err(AdapterError::Unstructured(anyhow!(e))),
+                            };
+                            updates.push((*idx, updated));
+                        }
+                        for (idx, new_value) in updates {
+                            datums[idx] = new_value;
+                        }
+                        diffs.push((Row::pack_slice(&datums[..arity]), 1));
+                        diffs.push((existing, -1));
+                        continue;
+                    }
                     if !assignments.is_empty() {
                         assert!(
                             matches!(kind, MutationKind::Update),
@@ -2605,17 +2728,15 @@ impl Coordinator {
                         // updated value.
                         MutationKind::Update | MutationKind::Delete => diffs.push((row, -1)),
                         MutationKind::Insert => diffs.push((row, 1)),
-                    }
-                }
-                for (row, diff) in &diffs {
-                    if *diff > 0 {
-                        for (idx, datum) in row.iter().enumerate() {
-                            desc.constraints_met(idx, &datum)?;
+                        MutationKind::Upsert | MutationKind::Merge => {
+                            unreachable!("handled above")
                         }
                     }
                 }
                 Ok(diffs)
             };
+            // Every row read by a merge is affected by one of its clauses.
+            let mut read_rows = 0;
             let diffs = match peek_response {
                 ExecuteResponse::SendingRows { future: batch } => {
                     // TODO(jkosh44): This timeout should be removed;
@@ -2624,7 +2745,10 @@ impl Coordinator {
                     // clusters.
                     match tokio::time::timeout(timeout_dur, batch).await {
                         Ok(res) => match res {
-                            PeekResponseUnary::Rows(rows) => make_diffs(rows),
+                            PeekResponseUnary::Rows(rows) => {
+                                read_rows = rows.len();
+                                make_diffs(rows)
+                            }
                             PeekResponseUnary::Canceled => Err(AdapterError::Canceled),
                             PeekResponseUnary::Error(e) => {
                                 Err(AdapterError::Unstructured(anyhow!(e)))
@@ -2645,11 +2769,53 @@ impl Coordinator {
                         }
                     }
                 }
-                ExecuteResponse::SendingRowsImmediate { rows } => make_diffs(rows),
+                ExecuteResponse::SendingRowsImmediate { rows } => {
+                    read_rows = rows.len();
+                    make_diffs(rows)
+                }
                 resp => Err(AdapterError::Unstructured(anyhow!(
                     "unexpected peek response: {resp:?}"
                 ))),
             };
+            // Identity columns and columns whose defaults call `nextval` must be
+            // filled in before the rows are checked against the table's
+            // constraints and returned.
+            let diffs = match diffs {
+                Ok(diffs) => match fill_sequence_defaults(
+                    &catalog,
+                    ctx.session_mut(),
+                    &sequence_defaults,
+                    diffs,
+                )
+                .await
+                {
+                    Ok(diffs) => {
+                        fill_identity_columns(
+                            &catalog,
+                            id,
+                            &table_name,
+                            &identity_columns,
+                            &desc,
+                            diffs,
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                }
+                .and_then(|diffs| {
+                    let table_name = table_name.to_string();
+                    for (row, diff) in &diffs {
+                        if *diff > 0 {
+                            for (idx, datum) in row.iter().enumerate() {
+                                desc.constraints_met(idx, &datum)?;
+                            }
+                            check_constraints_met(&table_name, &checks, row)?;
+                        }
+                    }
+                    Ok(diffs)
+                }),
+                Err(e) => Err(e),
+            };
             let mut returning_rows = Vec::new();
             let mut diff_err: Option<AdapterError> = None;
             if !returning.is_empty() && diffs.is_ok() {
@@ -2754,6 +2920,7 @@ impl Coordinator {
                             kind,
                             returning: returning_rows,
                             max_result_size,
+                            affected_rows: matches!(kind, MutationKind::Merge).then_some(read_rows),
                         },
                     );
                     ctx.retire(result);
@@ -2811,7 +2978,8 @@ impl Coordinator {
                 | CatalogItem::Type(_)
                 | CatalogItem::Func(_)
                 | CatalogItem::Secret(_)
-                | CatalogItem::Connection(_) => unreachable!(),
+                | CatalogItem::Connection(_)
+                | CatalogItem::Sequence(_) => unreachable!(),
             };
             match cluster {
                 Some(cluster) => {
@@ -3353,15 +3521,25 @@ impl Coordinator {
                 let mz_sql::plan::PgConfigOptionExtracted {
                     details,
                     mut text_columns,
+                    mut retract_on_truncate,
+                    mut upsert_tables,
+                    mut include_columns,
+                    mut row_filters,
                     ..
                 } = curr_options.clone().try_into()?;
 
-                // Drop both details and text columns; we will add them back in
+                // Drop details, text columns, retracted tables, upsert tables,
+                // included columns, and row filters; we will add them back in
                 // as appropriate below.
                 curr_options.retain(|o| {
                     !matches!(
                         o.name,
-                        PgConfigOptionName::Details | PgConfigOptionName::TextColumns
+                        PgConfigOptionName::Details
+                            | PgConfigOptionName::TextColumns
+                            | PgConfigOptionName::RetractOnTruncate
+                            | PgConfigOptionName::UpsertTables
+                            | PgConfigOptionName::IncludeColumns
+                            | PgConfigOptionName::RowFilters
                     )
                 });
 
@@ -3492,6 +3670,71 @@ impl Coordinator {
                     });
                 }
 
+                // Drop all retracted tables that are not currently referred to.
+                retract_on_truncate.retain(|table| curr_references.contains(table));
+                if !retract_on_truncate.is_empty() {
+                    let retract_on_truncate = retract_on_truncate
+                        .into_iter()
+                        .map(WithOptionValue::UnresolvedItemName)
+                        .collect();
+
+                    curr_options.push(PgConfigOption {
+                        name: PgConfigOptionName::RetractOnTruncate,
+                        value: Some(WithOptionValue::Sequence(retract_on_truncate)),
+                    });
+                }
+
+                // Drop all upsert tables that are not currently referred to.
+                upsert_tables.retain(|table| curr_references.contains(table));
+                if !upsert_tables.is_empty() {
+                    let upsert_tables = upsert_tables
+                        .into_iter()
+                        .map(WithOptionValue::UnresolvedItemName)
+                        .collect();
+
+                    curr_options.push(PgConfigOption {
+                        name: PgConfigOptionName::UpsertTables,
+                        value: Some(WithOptionValue::Sequence(upsert_tables)),
+                    });
+                }
+
+                // Drop all included columns that are not currently referred to.
+                include_columns.retain(|column_qualified_reference| {
+                    let mut table = column_qualified_reference.clone();
+                    table.0.truncate(3);
+                    curr_references.contains(&table)
+                });
+                if !include_columns.is_empty() {
+                    let include_columns = include_columns
+                        .into_iter()
+                        .map(WithOptionValue::UnresolvedItemName)
+                        .collect();
+
+                    curr_options.push(PgConfigOption {
+                        name: PgConfigOptionName::IncludeColumns,
+                        value: Some(WithOptionValue::Sequence(include_columns)),
+                    });
+                }
+
+                // Drop all row filters that are not currently referred to.
+                row_filters.retain(|(table, _)| curr_references.contains(table));
+                if !row_filters.is_empty() {
+                    let row_filters = row_filters
+                        .into_iter()
+                        .map(|(table, filter)| {
+                            WithOptionValue::Sequence(vec![
+                                WithOptionValue::UnresolvedItemName(table),
+                                WithOptionValue::Expr(filter),
+                            ])
+                        })
+                        .collect();
+
+                    curr_options.push(PgConfigOption {
+                        name: PgConfigOptionName::RowFilters,
+                        value: Some(WithOptionValue::Sequence(row_filters)),
+                    });
+                }
+
                 let mut catalog = self.catalog().for_system_session();
                 catalog.mark_id_unresolvable_for_replanning(cur_entry.id());
 
@@ -3621,6 +3864,294 @@ impl Coordinator {
                 )
                 .await;
             }
+            plan::AlterSourceAction::RefreshSchema {
+                details,
+                subsources,
+            } => {
+                const ALTER_SOURCE: &str = "ALTER SOURCE...REFRESH SCHEMA";
+
+                // None of the upstream tables changed.
+                if subsources.is_empty() {
+                    return Ok(ExecuteResponse::AlteredObject(ObjectType::Source));
+                }
+
+                // Resolve items in statement
+                let (mut create_source_stmt, resolved_ids) =
+                    create_sql_to_stmt_deps(self, ALTER_SOURCE, cur_entry.create_sql())?;
+
+                // Replace the details of the source with the refreshed ones.
+                let details = WithOptionValue::Value(Value::String(details));
+                match &mut create_source_stmt.connection {
+                    CreateSourceConnection::Postgres { options, .. } => {
+                        options.retain(|o| o.name != PgConfigOptionName::Details);
+                        options.push(PgConfigOption {
+                            name: PgConfigOptionName::Details,
+                            value: Some(details),
+                        });
+                    }
+                    CreateSourceConnection::MySql { options, .. } => {
+                        options.retain(|o| o.name != MySqlConfigOptionName::Details);
+                        options.push(MySqlConfigOption {
+                            name: MySqlConfigOptionName::Details,
+                            value: Some(details),
+                        });
+                    }
+                    _ => {
+                        return Err(AdapterError::internal(
+                            ALTER_SOURCE,
+                            "error in source purification",
+                        ))
+                    }
+                };
+
+                let mut catalog = self.catalog().for_system_session();
+                catalog.mark_id_unresolvable_for_replanning(cur_entry.id());
+
+                // Re-define our source in terms of the amended statement
+                let plan = match mz_sql::plan::plan(
+                    None,
+                    &catalog,
+                    Statement::CreateSource(create_source_stmt),
+                    &Params::empty(),
+                    &resolved_ids,
+                )
+                .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?
+                {
+                    Plan::CreateSource(plan) => plan,
+                    _ => unreachable!("create source plan is only valid response"),
+                };
+
+                let source = Source::new(
+                    plan,
+                    resolved_ids,
+                    cur_source.custom_logical_compaction_window,
+                    cur_source.is_retained_metrics_object,
+                );
+
+                // Get new ingestion description for storage.
+                let desc = match &source.data_source {
+                    DataSourceDesc::Ingestion { ingestion_desc, .. } => ingestion_desc
+                        .desc
+                        .clone()
+                        .into_inline_connection(self.catalog().state()),
+                    _ => unreachable!("already verified of type ingestion"),
+                };
+
+                self.controller
+                    .storage
+                    .check_alter_ingestion_source_desc(id, &desc)
+                    .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?;
+
+                let mut ops = vec![catalog::Op::UpdateItem {
+                    id,
+                    name: self.catalog.get_entry(&id).name().clone(),
+                    to_item: CatalogItem::Source(source),
+                }];
+
+                // Re-define each refreshed subsource in terms of its new columns.
+                let catalog = self.catalog().for_system_session();
+                let mut export_descs = BTreeMap::new();
+                for (subsource_id, columns) in subsources {
+                    let subsource_entry = self.catalog().get_entry(&subsource_id);
+                    let cur_subsource = subsource_entry.source().expect("known to be source");
+
+                    // Objects that depend on the subsource were planned
+                    // against its current columns, so we only allow widening
+                    // the types of the columns underneath them.
+                    if columns.len() > cur_subsource.desc.arity()
+                        && !subsource_entry.used_by().is_empty()
+                    {
+                        let name = self
+                            .catalog()
+                            .resolve_full_name(subsource_entry.name(), Some(session.conn_id()))
+                            .to_string();
+                        let dependents = subsource_entry
+                            .used_by()
+                            .iter()
+                            .map(|id| {
+                                let entry = self.catalog().get_entry(id);
+                                self.catalog()
+                                    .resolve_full_name(entry.name(), Some(session.conn_id()))
+                                    .to_string()
+                            })
+                            .collect();
+                        return Err(AdapterError::SubsourceHasDependents { name, dependents });
+                    }
+
+                    let subsource_stmt = match mz_sql::parse::parse(subsource_entry.create_sql())
+                        .expect("invalid create sql persisted to catalog")
+                        .into_element()
+                        .ast
+                    {
+                        Statement::CreateSubsource(stmt) => stmt,
+                        _ => unreachable!("proved type is subsource"),
+                    };
+                    let (mut subsource_stmt, _) = mz_sql::names::resolve(&catalog, subsource_stmt)
+                        .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?;
+                    subsource_stmt.columns = columns;
+
+                    let resolved_ids = mz_sql::names::visit_dependencies(&subsource_stmt);
+                    let plan = match mz_sql::plan::plan(
+                        None,
+                        &catalog,
+                        Statement::CreateSubsource(subsource_stmt),
+                        &Params::empty(),
+                        &resolved_ids,
+                    )
+                    .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?
+                    {
+                        Plan::CreateSource(plan) => plan,
+                        _ => unreachable!("create subsource plan is only valid response"),
+                    };
+
+                    let subsource = Source::new(
+                        plan,
+                        resolved_ids,
+                        cur_subsource.custom_logical_compaction_window,
+                        cur_subsource.is_retained_metrics_object,
+                    );
+                    export_descs.insert(subsource_id, subsource.desc.clone());
+
+                    ops.push(catalog::Op::UpdateItem {
+                        id: subsource_id,
+                        name: subsource_entry.name().clone(),
+                        to_item: CatalogItem::Source(subsource),
+                    });
+                }
+
+                self.catalog_transact(Some(session), ops).await?;
+
+                self.controller
+                    .storage
+                    .alter_ingestion_export_descs(id, desc, export_descs)
+                    .await
+                    .unwrap_or_terminate("cannot fail to alter source and subsource descs");
+            }
+            plan::AlterSourceAction::ResetTo { start_offsets } => {
+                const ALTER_SOURCE: &str = "ALTER SOURCE...RESET TO";
+
+                let cur_epoch = match &cur_source.data_source {
+                    DataSourceDesc::Ingestion { ingestion_desc, .. } => {
+                        match &ingestion_desc.desc.connection {
+                            GenericSourceConnection::Kafka(connection) => connection.start_epoch,
+                            _ => {
+                                return Err(AdapterError::internal(
+                                    ALTER_SOURCE,
+                                    "error in source purification",
+                                ))
+                            }
+                        }
+                    }
+                    _ => unreachable!("already verified of type ingestion"),
+                };
+                // Each reset moves the source to a new epoch, which keeps the
+                // timestamps it assigns to the data it reads from going
+                // backwards.
+                let start_epoch = cur_epoch + 1;
+                if start_epoch > KAFKA_MAX_EPOCH {
+                    coord_bail!(
+                        "{} has been reset the maximum number of times ({})",
+                        self.catalog()
+                            .resolve_full_name(cur_entry.name(), Some(session.conn_id())),
+                        KAFKA_MAX_EPOCH
+                    );
+                }
+
+                // Resolve items in statement
+                let (mut create_source_stmt, resolved_ids) =
+                    create_sql_to_stmt_deps(self, ALTER_SOURCE, cur_entry.create_sql())?;
+
+                // Replace the start position of the source with the new one.
+                match &mut create_source_stmt.connection {
+                    CreateSourceConnection::Kafka { options, .. } => {
+                        options.retain(|o| {
+                            !matches!(
+                                o.name,
+                                KafkaSourceConfigOptionName::StartOffset
+                                    | KafkaSourceConfigOptionName::StartTimestamp
+                                    | KafkaSourceConfigOptionName::StartEpoch
+                            )
+                        });
+                        options.push(KafkaSourceConfigOption {
+                            name: KafkaSourceConfigOptionName::StartOffset,
+                            value: Some(WithOptionValue::Sequence(
+                                start_offsets
+                                    .iter()
+                                    .map(|offset| {
+                                        WithOptionValue::Value(Value::Number(offset.to_string()))
+                                    })
+                                    .collect(),
+                            )),
+                        });
+                        options.push(KafkaSourceConfigOption {
+                            name: KafkaSourceConfigOptionName::StartEpoch,
+                            value: Some(WithOptionValue::Value(Value::Number(
+                                start_epoch.to_string(),
+                            ))),
+                        });
+                    }
+                    _ => {
+                        return Err(AdapterError::internal(
+                            ALTER_SOURCE,
+                            "error in source purification",
+                        ))
+                    }
+                };
+
+                let mut catalog = self.catalog().for_system_session();
+                catalog.mark_id_unresolvable_for_replanning(cur_entry.id());
+
+                // Re-define our source in terms of the amended statement
+                let plan = match mz_sql::plan::plan(
+                    None,
+                    &catalog,
+                    Statement::CreateSource(create_source_stmt),
+                    &Params::empty(),
+                    &resolved_ids,
+                )
+                .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?
+                {
+                    Plan::CreateSource(plan) => plan,
+                    _ => unreachable!("create source plan is only valid response"),
+                };
+
+                let source = Source::new(
+                    plan,
+                    resolved_ids,
+                    cur_source.custom_logical_compaction_window,
+                    cur_source.is_retained_metrics_object,
+                );
+
+                // Get new ingestion description for storage.
+                let desc = match &source.data_source {
+                    DataSourceDesc::Ingestion { ingestion_desc, .. } => ingestion_desc
+                        .desc
+                        .clone()
+                        .into_inline_connection(self.catalog().state()),
+                    _ => unreachable!("already verified of type ingestion"),
+                };
+
+                self.controller
+                    .storage
+                    .check_alter_ingestion_source_desc(id, &desc)
+                    .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?;
+
+                let ops = vec![catalog::Op::UpdateItem {
+                    id,
+                    name: self.catalog.get_entry(&id).name().clone(),
+                    to_item: CatalogItem::Source(source),
+                }];
+
+                self.catalog_transact(Some(session), ops).await?;
+
+                // Restarting the ingestion with its new description makes it
+                // resume reading from the new start offsets.
+                self.controller
+                    .storage
+                    .alter_ingestion_export_descs(id, desc, BTreeMap::new())
+                    .await
+                    .unwrap_or_terminate("cannot fail to alter source desc");
+            }
         }
 
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
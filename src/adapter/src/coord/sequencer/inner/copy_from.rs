@@ -0,0 +1,300 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use http::Uri;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_ore::task;
+use mz_ore::tracing::OpenTelemetryContext;
+use mz_pgcopy::CopyFormatParams;
+use mz_repr::{Datum, GlobalId, RelationDesc, Row, RowArena};
+use mz_sql::names::ResolvedIds;
+use mz_sql::plan::{self, HirScalarExpr};
+use mz_sql::session::metadata::SessionMetadata;
+use mz_storage_types::connections::inline::IntoInlineConnection;
+use mz_storage_types::connections::Connection;
+use mz_storage_types::sources::file::FileStore;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::command::ExecuteResponse;
+use crate::coord::sequencer::inner::return_if_err;
+use crate::coord::{Coordinator, CopyFromChunk, Message, PlanValidity};
+use crate::error::AdapterError;
+use crate::optimize::dataflows::{prep_scalar_expr, EvalTime, ExprPrepStyle};
+use crate::ExecuteContext;
+
+impl Coordinator {
+    /// Sequences a `COPY <table> FROM <url>` statement.
+    ///
+    /// The objects under the url are fetched and decoded one at a time in a
+    /// background task, which hands the decoded rows to the coordinator in
+    /// chunks of at most `max_copy_from_size` bytes. Each chunk is inserted
+    /// into the table like the rows of a `COPY ... FROM STDIN`, but committed
+    /// on its own, so that the rows never have to be held in memory all at
+    /// once. The next chunk is only fetched once the previous one has been
+    /// committed.
+    #[mz_ore::instrument(level = "debug")]
+    pub(crate) async fn sequence_copy_from(
+        &mut self,
+        ctx: ExecuteContext,
+        plan: plan::CopyFromPlan,
+        resolved_ids: ResolvedIds,
+    ) {
+        let plan::CopyFromPlan {
+            id,
+            source,
+            columns,
+            params,
+        } = plan;
+        let plan::CopyFromSource::Url {
+            url,
+            connection,
+            connection_id,
+        } = source
+        else {
+            unreachable!("COPY FROM STDIN is handled by the client");
+        };
+
+        let eval_uri = |from: HirScalarExpr| -> Result<Uri, AdapterError> {
+            let style = ExprPrepStyle::OneShot {
+                logical_time: EvalTime::NotAvailable,
+                session: ctx.session(),
+                catalog_state: self.catalog().state(),
+            };
+            let mut from = from.lower_uncorrelated()?;
+            prep_scalar_expr(&mut from, style)?;
+            let temp_storage = RowArena::new();
+            let evaled = from.eval(&[], &temp_storage)?;
+            if evaled == Datum::Null {
+                coord_bail!("COPY FROM source value can not be null");
+            }
+            let from_url = match Uri::from_str(evaled.unwrap_str()) {
+                Ok(url) => {
+                    if url.scheme_str() != Some("s3") {
+                        coord_bail!("only 's3://...' urls are supported as COPY FROM source");
+                    }
+                    url
+                }
+                Err(e) => coord_bail!("could not parse COPY FROM source url: {}", e),
+            };
+            Ok(from_url)
+        };
+        // Chunks are committed as they are copied, which would break the
+        // atomicity of a surrounding transaction.
+        if ctx
+            .session()
+            .transaction()
+            .is_in_multi_statement_transaction()
+        {
+            return ctx.retire(Err(AdapterError::OperationProhibitsTransaction(
+                "COPY FROM".into(),
+            )));
+        }
+        let uri = return_if_err!(eval_uri(url), ctx);
+        let Some(bucket) = uri.host() else {
+            return ctx.retire(Err(AdapterError::Unstructured(anyhow!(
+                "COPY FROM source url must include a bucket"
+            ))));
+        };
+        let bucket = bucket.to_string();
+        let prefix = uri.path().trim_start_matches('/').to_string();
+
+        let Connection::Aws(connection) = connection.into_inline_connection(self.catalog().state())
+        else {
+            unreachable!("planning ensures COPY FROM uses an AWS connection");
+        };
+        let store = FileStore::S3 {
+            connection_id,
+            connection,
+            bucket,
+        };
+
+        // The rows in the objects contain the columns being copied into, in the
+        // order in which they were specified.
+        let desc = {
+            let entry = self.catalog().get_entry(&id);
+            let full_name = self
+                .catalog()
+                .resolve_full_name(entry.name(), Some(ctx.session().conn_id()));
+            let desc = return_if_err!(entry.desc(&full_name), ctx);
+            RelationDesc::from_names_and_types(columns.iter().map(|i| {
+                (
+                    desc.get_name(*i).clone(),
+                    desc.typ().column_types[*i].clone(),
+                )
+            }))
+        };
+
+        let max_size = usize::cast_from(self.catalog().system_config().max_copy_from_size());
+        let connection_context = self.controller.storage.config().connection_context.clone();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let plan_validity = PlanValidity {
+            transient_revision: self.catalog().transient_revision(),
+            dependency_ids: resolved_ids.0,
+            cluster_id: None,
+            replica_id: None,
+            role_metadata: ctx.session().role_metadata().clone(),
+        };
+        let conn_id = ctx.session().conn_id().clone();
+        let otel_ctx = OpenTelemetryContext::obtain();
+
+        task::spawn(|| format!("copy_from:{conn_id}"), async move {
+            let mut inserter = ChunkInserter {
+                ctx: Some(ctx),
+                internal_cmd_tx,
+                id,
+                columns,
+                plan_validity,
+                otel_ctx,
+                copied: 0,
+            };
+            let result = copy_rows(
+                &mut inserter,
+                &store,
+                &connection_context,
+                &prefix,
+                &desc,
+                &params,
+                max_size,
+            )
+            .await;
+            // The context is only missing if the coordinator went away while
+            // a chunk was being inserted, in which case there is no one left
+            // to respond to.
+            if let Some(ctx) = inserter.ctx {
+                ctx.retire(result.map(|()| ExecuteResponse::Copied(inserter.copied)));
+            }
+        });
+    }
+}
+
+/// Hands the chunks of rows of a `COPY ... FROM <url>` to the coordinator to be
+/// inserted.
+struct ChunkInserter {
+    /// The context of the statement, which is passed to the coordinator along
+    /// with each chunk and handed back once the chunk has been committed.
+    ctx: Option<ExecuteContext>,
+    internal_cmd_tx: mpsc::UnboundedSender<Message>,
+    id: GlobalId,
+    columns: Vec<usize>,
+    plan_validity: PlanValidity,
+    otel_ctx: OpenTelemetryContext,
+    /// The number of rows that have been committed.
+    copied: usize,
+}
+
+impl ChunkInserter {
+    /// Inserts `rows` and waits for them to be committed.
+    async fn insert(&mut self, rows: Vec<Row>) -> Result<(), AdapterError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let Some(ctx) = self.ctx.take() else {
+            return Err(AdapterError::Canceled);
+        };
+        let len = rows.len();
+        let (tx, rx) = oneshot::channel();
+        let chunk = CopyFromChunk {
+            ctx,
+            id: self.id,
+            columns: self.columns.clone(),
+            rows,
+            plan_validity: self.plan_validity.clone(),
+            otel_ctx: self.otel_ctx.clone(),
+            tx,
+        };
+        // It is not an error for the copy to complete after `internal_cmd_rx` is dropped.
+        if let Err(e) = self.internal_cmd_tx.send(Message::CopyFromChunk(chunk)) {
+            tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            return Err(AdapterError::Canceled);
+        }
+        let Ok((ctx, result)) = rx.await else {
+            return Err(AdapterError::Canceled);
+        };
+        self.ctx = Some(ctx);
+        result?;
+        self.copied += len;
+        Ok(())
+    }
+}
+
+/// Fetches all objects under `prefix`, decodes them into rows of `desc` and
+/// inserts the rows in chunks of at most `max_size` bytes.
+async fn copy_rows(
+    inserter: &mut ChunkInserter,
+    store: &FileStore,
+    connection_context: &mz_storage_types::connections::ConnectionContext,
+    prefix: &str,
+    desc: &RelationDesc,
+    params: &CopyFormatParams<'static>,
+    max_size: usize,
+) -> Result<(), AdapterError> {
+    let client = store
+        .client(connection_context, InTask::No)
+        .await
+        .map_err(|e| anyhow!("failed to create client: {}", e.display_with_causes()))?;
+    let keys = client
+        .list_objects(prefix)
+        .await
+        .map_err(|e| anyhow!("failed to list objects: {}", e.display_with_causes()))?;
+    if keys.is_empty() {
+        return Err(anyhow!("no objects found under COPY FROM source url").into());
+    }
+
+    let column_types = desc
+        .iter_types()
+        .map(|x| &x.scalar_type)
+        .map(mz_pgrepr::Type::from)
+        .collect::<Vec<mz_pgrepr::Type>>();
+
+    let mut chunk_size = 0;
+    let mut chunk = vec![];
+    for key in keys {
+        let contents = client.get_object(&key).await.map_err(|e| {
+            anyhow!(
+                "failed to fetch object {}: {}",
+                key,
+                e.display_with_causes()
+            )
+        })?;
+        // Skip objects that were deleted since they were listed, as well as
+        // empty objects, like the sentinels that `COPY ... TO` writes.
+        let Some(contents) = contents.filter(|contents| !contents.is_empty()) else {
+            continue;
+        };
+        // Bail before we OOM.
+        if contents.len() > max_size {
+            return Err(anyhow!("COPY FROM source object {} too large", key).into());
+        }
+        // Release the encoded object before its rows are inserted.
+        let decoded = {
+            let contents = contents;
+            match params {
+                CopyFormatParams::Parquet => mz_arrow_util::reader::read_parquet(contents, desc)
+                    .map_err(|e| anyhow!("{}", e.display_with_causes())),
+                params => mz_pgcopy::decode_copy_format(&contents, &column_types, params.clone())
+                    .map_err(|e| anyhow!("{}", e)),
+            }
+        };
+        let decoded = decoded.map_err(|e| anyhow!("failed to decode object {}: {}", key, e))?;
+        for row in decoded {
+            chunk_size += row.byte_len();
+            chunk.push(row);
+            if chunk_size >= max_size {
+                inserter.insert(std::mem::take(&mut chunk)).await?;
+                chunk_size = 0;
+            }
+        }
+    }
+    inserter.insert(chunk).await
+}
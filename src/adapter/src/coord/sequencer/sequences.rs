@@ -0,0 +1,399 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Coordinator functionality to hand out the values of sequences.
+//!
+//! Sequence values are drawn from id allocators in the durable catalog, which
+//! [`Catalog::allocate_sequence_positions`] reserves in batches. Calls to
+//! `nextval` and `currval` are replaced with literals before a statement is
+//! optimized, while identity columns and columns whose defaults call `nextval`
+//! are filled in for every row when their rows are written.
+
+use std::collections::BTreeMap;
+
+use mz_catalog::durable::sequence_alloc_key;
+use mz_expr::{MirScalarExpr, UnmaterializableFunc};
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, RelationDesc, Row, RowArena, ScalarType};
+use mz_sql::catalog::CatalogError;
+use mz_sql::names::FullItemName;
+use mz_sql::plan::{HirRelationExpr, HirScalarExpr, IdentityColumn, SequenceOptions};
+use mz_sql::session::metadata::SessionMetadata;
+
+use crate::catalog::Catalog;
+use crate::error::AdapterError;
+use crate::session::Session;
+
+/// Returns the next `amount` values of the sequence named `name`, whose
+/// options are `options` and whose state is held by the id allocator `key`.
+async fn allocate_sequence_values(
+    catalog: &Catalog,
+    key: String,
+    name: &str,
+    options: &SequenceOptions,
+    amount: u64,
+) -> Result<Vec<i64>, AdapterError> {
+    let positions = catalog
+        .allocate_sequence_positions(key, options.cache, amount)
+        .await?;
+    positions
+        .into_iter()
+        .map(|n| {
+            options
+                .nth_value(n)
+                .ok_or_else(|| AdapterError::SequenceLimitExceeded {
+                    sequence: name.to_string(),
+                    limit: options.limit(),
+                    ascending: options.increment > 0,
+                })
+        })
+        .collect()
+}
+
+/// The values that calls to sequence functions in a statement evaluate to.
+struct SequenceValues {
+    /// The values that remain for the `nextval` calls of each sequence.
+    next: BTreeMap<GlobalId, std::vec::IntoIter<i64>>,
+    /// The value most recently produced by each sequence in the session.
+    current: BTreeMap<GlobalId, i64>,
+    names: BTreeMap<GlobalId, String>,
+}
+
+impl SequenceValues {
+    /// Allocates the values for `calls`, the number of `nextval` calls to
+    /// each sequence that is called, starting from the current values of the
+    /// sequences in `session`.
+    async fn allocate(
+        catalog: &Catalog,
+        session: &Session,
+        calls: BTreeMap<GlobalId, u64>,
+    ) -> Result<SequenceValues, AdapterError> {
+        let mut values = SequenceValues {
+            next: BTreeMap::new(),
+            current: session.sequence_values().clone(),
+            names: BTreeMap::new(),
+        };
+        for (id, amount) in calls {
+            // The sequence may have been dropped since the statement was planned.
+            let entry = catalog.try_get_entry(&id).ok_or_else(|| {
+                AdapterError::Catalog(mz_catalog::memory::error::Error {
+                    kind: mz_catalog::memory::error::ErrorKind::Sql(CatalogError::UnknownItem(
+                        id.to_string(),
+                    )),
+                })
+            })?;
+            let sequence = entry.sequence().expect("sequence functions take sequences");
+            let name = catalog
+                .resolve_full_name(entry.name(), Some(session.conn_id()))
+                .to_string();
+            if amount > 0 {
+                let next = allocate_sequence_values(
+                    catalog,
+                    sequence_alloc_key(id, None),
+                    &name,
+                    &sequence.options,
+                    amount,
+                )
+                .await?;
+                values.next.insert(id, next.into_iter());
+            }
+            values.names.insert(id, name);
+        }
+        Ok(values)
+    }
+
+    fn resolve(&mut self, func: &UnmaterializableFunc) -> Result<i64, AdapterError> {
+        match func {
+            UnmaterializableFunc::NextVal(id) => {
+                let value = self
+                    .next
+                    .get_mut(id)
+                    .and_then(|values| values.next())
+                    .expect("values allocated for every nextval call");
+                self.current.insert(*id, value);
+                Ok(value)
+            }
+            UnmaterializableFunc::CurrVal(id) => self
+                .current
+                .get(id)
+                .copied()
+                .ok_or_else(|| AdapterError::CurrvalNotYetDefined(self.names[id].clone())),
+            func => unreachable!("{func} is not a sequence function"),
+        }
+    }
+
+    /// Replaces the calls to sequence functions in `expr` with their values.
+    fn resolve_scalar(&mut self, expr: &mut MirScalarExpr) -> Result<(), AdapterError> {
+        let mut result = Ok(());
+        expr.visit_pre_mut(|e| {
+            if let MirScalarExpr::CallUnmaterializable(
+                func @ (UnmaterializableFunc::NextVal(_) | UnmaterializableFunc::CurrVal(_)),
+            ) = e
+            {
+                match self.resolve(func) {
+                    Ok(value) => {
+                        *e = MirScalarExpr::literal_ok(Datum::Int64(value), ScalarType::Int64)
+                    }
+                    Err(err) => {
+                        if result.is_ok() {
+                            result = Err(err);
+                        }
+                    }
+                }
+            }
+        });
+        result
+    }
+}
+
+/// Counts a call to `func`, if it is a sequence function, in `calls`, as
+/// `copies` calls to `nextval`.
+fn count_call(calls: &mut BTreeMap<GlobalId, u64>, func: &UnmaterializableFunc, copies: u64) {
+    match func {
+        UnmaterializableFunc::NextVal(id) => *calls.entry(*id).or_default() += copies,
+        UnmaterializableFunc::CurrVal(id) => {
+            calls.entry(*id).or_default();
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the calls to `nextval` and `currval` in `relations` and `scalars`
+/// with the values they evaluate to, advancing the sequences and the session's
+/// current value of each sequence.
+///
+/// Every call site produces a single value, so calls to `nextval` that may be
+/// evaluated for more than one row are rejected. This includes all calls in
+/// `scalars`, which are evaluated for every row that a statement writes or
+/// returns. Defaults that call `nextval` are instead evaluated for every row by
+/// [`fill_sequence_defaults`].
+pub(super) async fn resolve_sequence_calls(
+    catalog: &Catalog,
+    session: &mut Session,
+    mut relations: Vec<&mut HirRelationExpr>,
+    mut scalars: Vec<&mut MirScalarExpr>,
+) -> Result<(), AdapterError> {
+    let mut scalar_calls_nextval = false;
+    for scalar in scalars.iter() {
+        scalar.visit_pre(|e| {
+            if let MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::NextVal(_)) = e {
+                scalar_calls_nextval = true;
+            }
+        });
+    }
+    if scalar_calls_nextval
+        || relations
+            .iter()
+            .any(|relation| relation.calls_nextval_repeatedly())
+    {
+        return Err(AdapterError::Unsupported(
+            "calls to nextval that may be evaluated for more than one row",
+        ));
+    }
+
+    let mut calls: BTreeMap<GlobalId, u64> = BTreeMap::new();
+    for relation in relations.iter_mut() {
+        relation.visit_sequence_calls_mut(&mut |e| {
+            if let HirScalarExpr::CallUnmaterializable(func) = e {
+                count_call(&mut calls, func, 1);
+            }
+            Ok::<_, AdapterError>(())
+        })?;
+    }
+    for scalar in scalars.iter() {
+        scalar.visit_pre(|e| {
+            if let MirScalarExpr::CallUnmaterializable(func) = e {
+                count_call(&mut calls, func, 1);
+            }
+        });
+    }
+    if calls.is_empty() {
+        return Ok(());
+    }
+
+    let mut values = SequenceValues::allocate(catalog, session, calls).await?;
+    let mut result = Ok(());
+    for relation in relations {
+        result = relation.visit_sequence_calls_mut(&mut |e| {
+            if let HirScalarExpr::CallUnmaterializable(func) = e {
+                let value = values.resolve(func)?;
+                *e = HirScalarExpr::literal(Datum::Int64(value), ScalarType::Int64);
+            }
+            Ok(())
+        });
+        if result.is_err() {
+            break;
+        }
+    }
+    for scalar in scalars.iter_mut() {
+        if result.is_err() {
+            break;
+        }
+        result = values.resolve_scalar(scalar);
+    }
+    // As in PostgreSQL, values handed out by `nextval` are consumed even if
+    // the statement fails.
+    *session.sequence_values_mut() = values.current;
+    result
+}
+
+/// Fills the `NULL` values of the columns whose defaults are `defaults` in the
+/// rows that `diffs` add by evaluating the defaults, which call `nextval`, for
+/// every row, advancing the session's current value of each sequence.
+///
+/// Rows that are added more than once are split up, so that each copy receives
+/// distinct values.
+pub(super) async fn fill_sequence_defaults(
+    catalog: &Catalog,
+    session: &mut Session,
+    defaults: &BTreeMap<usize, MirScalarExpr>,
+    diffs: Vec<(Row, Diff)>,
+) -> Result<Vec<(Row, Diff)>, AdapterError> {
+    if defaults.is_empty() {
+        return Ok(diffs);
+    }
+    let is_unfilled = |idx: usize, datum: Datum| datum.is_null() && defaults.contains_key(&idx);
+
+    let mut calls: BTreeMap<GlobalId, u64> = BTreeMap::new();
+    for (row, diff) in &diffs {
+        if *diff > 0 {
+            let copies = u64::try_from(*diff)?;
+            for (idx, datum) in row.iter().enumerate() {
+                if is_unfilled(idx, datum) {
+                    defaults[&idx].visit_pre(|e| {
+                        if let MirScalarExpr::CallUnmaterializable(func) = e {
+                            count_call(&mut calls, func, copies);
+                        }
+                    });
+                }
+            }
+        }
+    }
+    if calls.is_empty() {
+        return Ok(diffs);
+    }
+
+    let mut values = SequenceValues::allocate(catalog, session, calls).await?;
+    let fill = || -> Result<_, AdapterError> {
+        let arena = RowArena::new();
+        let mut filled = Vec::with_capacity(diffs.len());
+        let mut datum_vec = DatumVec::new();
+        for (row, diff) in diffs {
+            if diff <= 0
+                || !row
+                    .iter()
+                    .enumerate()
+                    .any(|(idx, datum)| is_unfilled(idx, datum))
+            {
+                filled.push((row, diff));
+                continue;
+            }
+            for _ in 0..diff {
+                let mut datums = datum_vec.borrow_with(&row);
+                for (idx, default) in defaults {
+                    if !datums[*idx].is_null() {
+                        continue;
+                    }
+                    let mut default = default.clone();
+                    values.resolve_scalar(&mut default)?;
+                    datums[*idx] = default.eval(&[], &arena)?;
+                }
+                filled.push((Row::pack_slice(&datums), 1));
+            }
+        }
+        Ok(filled)
+    };
+    let result = fill();
+    // As in PostgreSQL, values handed out by `nextval` are consumed even if
+    // the statement fails.
+    *session.sequence_values_mut() = values.current;
+    result
+}
+
+/// Fills the `NULL` values of the identity columns in the rows that `diffs`
+/// add to the table `table_id`, named `table_name`, with values drawn from the
+/// columns' sequences.
+///
+/// Rows that are added more than once are split up, so that each copy receives
+/// distinct values.
+pub(super) async fn fill_identity_columns(
+    catalog: &Catalog,
+    table_id: GlobalId,
+    table_name: &FullItemName,
+    identity_columns: &BTreeMap<usize, IdentityColumn>,
+    desc: &RelationDesc,
+    diffs: Vec<(Row, Diff)>,
+) -> Result<Vec<(Row, Diff)>, AdapterError> {
+    if identity_columns.is_empty() {
+        return Ok(diffs);
+    }
+    let is_unfilled =
+        |idx: usize, datum: Datum| datum.is_null() && identity_columns.contains_key(&idx);
+
+    let mut needed: BTreeMap<usize, u64> = BTreeMap::new();
+    for (row, diff) in &diffs {
+        if *diff > 0 {
+            for (idx, datum) in row.iter().enumerate() {
+                if is_unfilled(idx, datum) {
+                    *needed.entry(idx).or_default() += u64::try_from(*diff)?;
+                }
+            }
+        }
+    }
+    if needed.is_empty() {
+        return Ok(diffs);
+    }
+
+    let mut values = BTreeMap::new();
+    for (idx, amount) in needed {
+        let name = format!("{}_{}_seq", table_name.item, desc.get_name(idx).as_str());
+        let column_values = allocate_sequence_values(
+            catalog,
+            sequence_alloc_key(table_id, Some(idx)),
+            &name,
+            &identity_columns[&idx].options,
+            amount,
+        )
+        .await?;
+        values.insert(idx, column_values.into_iter());
+    }
+
+    let column_types = &desc.typ().column_types;
+    let mut filled = Vec::with_capacity(diffs.len());
+    let mut datum_vec = DatumVec::new();
+    for (row, diff) in diffs {
+        if diff <= 0
+            || !row
+                .iter()
+                .enumerate()
+                .any(|(idx, datum)| is_unfilled(idx, datum))
+        {
+            filled.push((row, diff));
+            continue;
+        }
+        for _ in 0..diff {
+            let mut datums = datum_vec.borrow_with(&row);
+            for (idx, column_values) in values.iter_mut() {
+                if !datums[*idx].is_null() {
+                    continue;
+                }
+                let value = column_values
+                    .next()
+                    .expect("values allocated for every NULL");
+                datums[*idx] = match column_types[*idx].scalar_type {
+                    ScalarType::Int16 => Datum::Int16(i16::try_from(value)?),
+                    ScalarType::Int32 => Datum::Int32(i32::try_from(value)?),
+                    ScalarType::Int64 => Datum::Int64(value),
+                    ref ty => unreachable!("identity column of type {ty:?}"),
+                };
+            }
+            filled.push((Row::pack_slice(&datums), 1));
+        }
+    }
+    Ok(filled)
+}
@@ -347,6 +347,7 @@ impl Coordinator {
                         | CatalogItem::Func(_)
                         | CatalogItem::Secret(_)
                         | CatalogItem::Connection(_)
+                        | CatalogItem::Sequence(_)
                         | CatalogItem::Log(_) => {}
                     }
                 }
@@ -470,7 +471,8 @@ impl Coordinator {
                     | CatalogItem::Type(_)
                     | CatalogItem::Func(_)
                     | CatalogItem::Secret(_)
-                    | CatalogItem::Connection(_) => {}
+                    | CatalogItem::Connection(_)
+                    | CatalogItem::Sequence(_) => {}
                 }
             }
         }
@@ -96,6 +96,37 @@ pub enum AdapterError {
     InvalidTableMutationSelection,
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
+    /// A write would have duplicated the value of one of a table's keys.
+    UniqueViolation {
+        /// The name of the table.
+        table: String,
+        /// The names of the key's columns.
+        columns: Vec<String>,
+        /// The duplicated value of each of the key's columns, as text.
+        values: Vec<String>,
+    },
+    /// A write would have added a row that violates one of a table's `CHECK` constraints.
+    CheckViolation {
+        /// The name of the table.
+        table: String,
+        /// The name of the constraint.
+        constraint: String,
+    },
+    /// An `INSERT ... ON CONFLICT DO UPDATE` would have updated a row more than once.
+    OnConflictCardinalityViolation,
+    /// A `MERGE` would have modified a row of its target more than once.
+    MergeCardinalityViolation,
+    /// A sequence has run past its maximum (or, if descending, minimum) value.
+    SequenceLimitExceeded {
+        /// The name of the sequence.
+        sequence: String,
+        /// The value that was reached.
+        limit: i64,
+        /// Whether the sequence is ascending.
+        ascending: bool,
+    },
+    /// `currval` was called before `nextval` for the named sequence in this session.
+    CurrvalNotYetDefined(String),
     /// Transaction cluster was dropped in the middle of a transaction.
     ConcurrentClusterDrop,
     /// Target cluster has no replicas to service query.
@@ -225,6 +256,12 @@ pub enum AdapterError {
     SubsourceAlreadyReferredTo {
         name: UnresolvedItemName,
     },
+    /// An ALTER SOURCE...REFRESH SCHEMA tried to add columns to a subsource
+    /// that other objects depend on.
+    SubsourceHasDependents {
+        name: String,
+        dependents: Vec<String>,
+    },
 }
 
 impl AdapterError {
@@ -255,6 +292,15 @@ impl AdapterError {
             },
             AdapterError::Catalog(c) => c.detail(),
             AdapterError::Eval(e) => e.detail(),
+            AdapterError::UniqueViolation {
+                table: _,
+                columns,
+                values,
+            } => Some(format!(
+                "Key ({})=({}) already exists.",
+                columns.join(", "),
+                values.join(", ")
+            )),
             AdapterError::RelationOutsideTimeDomain { relations, names } => Some(format!(
                 "The following relations in the query are outside the transaction's time domain:\n{}\n{}",
                 relations
@@ -335,6 +381,9 @@ impl AdapterError {
                     }
                 ))
             }
+            AdapterError::SubsourceHasDependents { dependents, .. } => {
+                Some(format!("dependent objects: {}", dependents.join(", ")))
+            }
             _ => None,
         }
     }
@@ -366,6 +415,14 @@ impl AdapterError {
             AdapterError::NoClusterReplicasAvailable(_) => {
                 Some("You can create cluster replicas using CREATE CLUSTER REPLICA".into())
             }
+            AdapterError::OnConflictCardinalityViolation => Some(
+                "Ensure that no rows proposed for insertion within the same command have \
+                 duplicate constrained values."
+                    .into(),
+            ),
+            AdapterError::MergeCardinalityViolation => Some(
+                "Ensure that not more than one source row matches any one target row.".into(),
+            ),
             AdapterError::UntargetedLogRead { .. } => Some(
                 "Use `SET cluster_replica = <replica-name>` to target a specific replica in the \
                  active cluster. Note that subsequent queries will only be answered by \
@@ -403,6 +460,10 @@ impl AdapterError {
             Self::SubsourceAlreadyReferredTo { .. } => {
                 Some("Specify target table names using FOR TABLES (foo AS bar), or limit the upstream tables using FOR SCHEMAS (foo)".into())
             },
+            Self::SubsourceHasDependents { .. } => Some(
+                "Drop the dependent objects, refresh the schema of the source, and create them again."
+                    .into(),
+            ),
             _ => None,
         }
     }
@@ -455,6 +516,14 @@ impl AdapterError {
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
+            AdapterError::UniqueViolation { .. } => SqlState::UNIQUE_VIOLATION,
+            AdapterError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
+            AdapterError::SequenceLimitExceeded { .. } => {
+                SqlState::SEQUENCE_GENERATOR_LIMIT_EXCEEDED
+            }
+            AdapterError::CurrvalNotYetDefined(_) => SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE,
+            AdapterError::OnConflictCardinalityViolation => SqlState::CARDINALITY_VIOLATION,
+            AdapterError::MergeCardinalityViolation => SqlState::CARDINALITY_VIOLATION,
             AdapterError::ConcurrentClusterDrop => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
@@ -528,6 +597,7 @@ impl AdapterError {
             // Calling this `FEATURE_NOT_SUPPORTED` because we will eventually allow multiple
             // references to the same subsource (albeit with different schemas).
             AdapterError::SubsourceAlreadyReferredTo { .. } => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::SubsourceHasDependents { .. } => SqlState::DEPENDENT_OBJECTS_STILL_EXIST,
         }
     }
 
@@ -592,6 +662,47 @@ impl fmt::Display for AdapterError {
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
+            AdapterError::UniqueViolation { table, .. } => {
+                write!(
+                    f,
+                    "duplicate key value violates unique constraint on {}",
+                    table.quoted()
+                )
+            }
+            AdapterError::CheckViolation { table, constraint } => {
+                write!(
+                    f,
+                    "new row for relation {} violates check constraint {}",
+                    table.quoted(),
+                    constraint.quoted()
+                )
+            }
+            AdapterError::OnConflictCardinalityViolation => {
+                f.write_str("ON CONFLICT DO UPDATE command cannot affect row a second time")
+            }
+            AdapterError::MergeCardinalityViolation => {
+                f.write_str("MERGE command cannot affect row a second time")
+            }
+            AdapterError::SequenceLimitExceeded {
+                sequence,
+                limit,
+                ascending,
+            } => {
+                write!(
+                    f,
+                    "nextval: reached {} value of sequence {} ({})",
+                    if *ascending { "maximum" } else { "minimum" },
+                    sequence.quoted(),
+                    limit
+                )
+            }
+            AdapterError::CurrvalNotYetDefined(sequence) => {
+                write!(
+                    f,
+                    "currval of sequence {} is not yet defined in this session",
+                    sequence.quoted()
+                )
+            }
             AdapterError::ConcurrentClusterDrop => {
                 write!(f, "the transaction's active cluster has been dropped")
             }
@@ -756,6 +867,12 @@ impl fmt::Display for AdapterError {
             Self::SubsourceAlreadyReferredTo { name } => {
                 write!(f, "another subsource already refers to {}", name)
             }
+            Self::SubsourceHasDependents { name, .. } => {
+                write!(
+                    f,
+                    "cannot add columns to subsource {name} because other objects depend on it"
+                )
+            }
         }
     }
 }
@@ -377,7 +377,8 @@ impl<'a> DataflowBuilder<'a> {
                 | CatalogItem::Log(_)
                 | CatalogItem::Index(_)
                 | CatalogItem::Sink(_)
-                | CatalogItem::Func(_) => Ok(false),
+                | CatalogItem::Func(_)
+                | CatalogItem::Sequence(_) => Ok(false),
             }
         })
     }
@@ -624,6 +625,14 @@ fn eval_unmaterializable_func(
             });
             Ok(MirScalarExpr::Literal(Ok(row), f.output_type()))
         }
+        // The coordinator replaces sequence calls with their values in the statements that
+        // support them, before optimization.
+        UnmaterializableFunc::NextVal(_) | UnmaterializableFunc::CurrVal(_) => {
+            Err(OptimizerError::UncallableFunction {
+                func: f.clone(),
+                context: "this context",
+            })
+        }
         UnmaterializableFunc::MzSessionId => pack(Datum::from(state.config().session_id)),
         UnmaterializableFunc::MzUptime => {
             let uptime = state.config().start_instant.elapsed();
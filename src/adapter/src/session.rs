@@ -107,6 +107,9 @@ where
     #[derivative(Debug = "ignore")]
     qcell_owner: QCellOwner,
     session_oracles: BTreeMap<Timeline, InMemoryTimestampOracle<T, NowFn<T>>>,
+    /// The value most recently returned by `nextval` for each sequence, which
+    /// is what `currval` reports.
+    sequence_values: BTreeMap<GlobalId, i64>,
 }
 
 impl<T> SessionMetadata for Session<T>
@@ -288,6 +291,7 @@ impl<T: TimestampManipulation> Session<T> {
             external_metadata_rx,
             qcell_owner: QCellOwner::new(),
             session_oracles: BTreeMap::new(),
+            sequence_values: BTreeMap::new(),
         }
     }
 
@@ -735,6 +739,17 @@ impl<T: TimestampManipulation> Session<T> {
         let _ = self.clear_transaction();
         self.prepared_statements.clear();
         self.vars.reset_all();
+        self.sequence_values.clear();
+    }
+
+    /// Returns the value most recently returned by `nextval` for each sequence in this session.
+    pub fn sequence_values(&self) -> &BTreeMap<GlobalId, i64> {
+        &self.sequence_values
+    }
+
+    /// Returns a mutable reference to the sequence values of this session.
+    pub fn sequence_values_mut(&mut self) -> &mut BTreeMap<GlobalId, i64> {
+        &mut self.sequence_values
     }
 
     /// Returns the [application_name] that created this session.
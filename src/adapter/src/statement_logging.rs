@@ -196,6 +196,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::CreatedClusterReplica
             | ExecuteResponse::CreatedIndex
             | ExecuteResponse::CreatedSecret
+            | ExecuteResponse::CreatedSequence
             | ExecuteResponse::CreatedSink
             | ExecuteResponse::CreatedSource
             | ExecuteResponse::CreatedTable
@@ -208,12 +209,14 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::Deleted(_)
             | ExecuteResponse::DiscardedTemp
             | ExecuteResponse::DiscardedAll
+            | ExecuteResponse::DiscardedSequences
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
             | ExecuteResponse::EmptyQuery
             | ExecuteResponse::GrantedPrivilege
             | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(_)
+            | ExecuteResponse::Merged(_)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
             | ExecuteResponse::ReassignOwned
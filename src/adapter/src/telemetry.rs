@@ -142,6 +142,7 @@ pub fn analyze_audited_statement(
         StatementKind::CreateRole => Some((StatementAction::Create, ObjectType::Role)),
         StatementKind::CreateSchema => Some((StatementAction::Create, ObjectType::Schema)),
         StatementKind::CreateSecret => Some((StatementAction::Create, ObjectType::Secret)),
+        StatementKind::CreateSequence => Some((StatementAction::Create, ObjectType::Sequence)),
         StatementKind::CreateSink => Some((StatementAction::Create, ObjectType::Sink)),
         StatementKind::CreateSource => Some((StatementAction::Create, ObjectType::Source)),
         StatementKind::CreateTable => Some((StatementAction::Create, ObjectType::Table)),
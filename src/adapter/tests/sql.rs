@@ -18,7 +18,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use mz_adapter::catalog::{Catalog, Op};
@@ -96,6 +96,10 @@ async fn datadriven() {
                                             )),
                                             desc: RelationDesc::empty(),
                                             defaults: vec![Expr::null(); 0],
+                                            generated_columns: BTreeMap::new(),
+                                            checks: Vec::new(),
+                                            identity_columns: BTreeMap::new(),
+                                            nulls_not_distinct_keys: Vec::new(),
                                             conn_id: None,
                                             resolved_ids: ResolvedIds(BTreeSet::new()),
                                             custom_logical_compaction_window: None,
@@ -0,0 +1,298 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Reading Arrow record batches, and Parquet files containing them, into [`Row`]s.
+
+use anyhow::{anyhow, bail, Context};
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::datatypes::{
+    DataType, Date32Type, Decimal128Type, Field, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, Schema, Time64MicrosecondType, Time64NanosecondType, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::record_batch::RecordBatch;
+use bytes::Bytes;
+use chrono::{DateTime, NaiveTime, Utc};
+use mz_repr::adt::date::Date;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::adt::numeric::{self, NumericMaxScale};
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use uuid::Uuid;
+
+/// The number of rows decoded from a Parquet file at a time.
+const PARQUET_BATCH_SIZE: usize = 1024;
+
+/// Returns the [`RelationDesc`] of the rows stored in the Parquet file `bytes`.
+pub fn parquet_desc(bytes: Bytes) -> Result<RelationDesc, anyhow::Error> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)?;
+    desc_from_arrow_schema(builder.schema())
+}
+
+/// Decodes all rows of the Parquet file `bytes`, which must contain the columns
+/// of `desc`.
+pub fn read_parquet(bytes: Bytes, desc: &RelationDesc) -> Result<Vec<Row>, anyhow::Error> {
+    let builder =
+        ParquetRecordBatchReaderBuilder::try_new(bytes)?.with_batch_size(PARQUET_BATCH_SIZE);
+    let reader = ArrowReader::new(desc, builder.schema())?;
+    let mut rows = vec![];
+    for batch in builder.build()? {
+        reader.read(&batch?, &mut rows)?;
+    }
+    Ok(rows)
+}
+
+/// Returns a [`RelationDesc`] with a column for each field of `schema`.
+pub fn desc_from_arrow_schema(schema: &Schema) -> Result<RelationDesc, anyhow::Error> {
+    let mut desc = RelationDesc::empty();
+    let mut errs = vec![];
+    for field in schema.fields() {
+        match field_to_scalar_type(field) {
+            Ok(scalar_type) => {
+                desc = desc.with_column(field.name(), scalar_type.nullable(field.is_nullable()));
+            }
+            Err(err) => errs.push(format!("{}: {}", field.name(), err)),
+        }
+    }
+    if !errs.is_empty() {
+        bail!("Cannot decode the following columns/types: {:?}", errs);
+    }
+    Ok(desc)
+}
+
+/// Return the appropriate ScalarType for the given Arrow field.
+///
+/// This is the inverse of the mapping used by
+/// [`ArrowBuilder`](crate::builder::ArrowBuilder), so files written by
+/// Materialize are read back with their original types.
+fn field_to_scalar_type(field: &Field) -> Result<ScalarType, anyhow::Error> {
+    let extension_name = field
+        .metadata()
+        .get("ARROW:extension:name")
+        .map(|name| name.as_str());
+    let scalar_type = match (field.data_type(), extension_name) {
+        (DataType::Boolean, _) => ScalarType::Bool,
+        (DataType::Int8 | DataType::Int16, _) => ScalarType::Int16,
+        (DataType::Int32, _) => ScalarType::Int32,
+        (DataType::Int64, _) => ScalarType::Int64,
+        (DataType::UInt8 | DataType::UInt16, _) => ScalarType::UInt16,
+        (DataType::UInt32, _) => ScalarType::UInt32,
+        (DataType::UInt64, Some("materialize.v1.mz_timestamp")) => ScalarType::MzTimestamp,
+        (DataType::UInt64, _) => ScalarType::UInt64,
+        (DataType::Float32, _) => ScalarType::Float32,
+        (DataType::Float64, _) => ScalarType::Float64,
+        (DataType::Date32, _) => ScalarType::Date,
+        (DataType::Time64(TimeUnit::Microsecond | TimeUnit::Nanosecond), _) => ScalarType::Time,
+        (DataType::Timestamp(_, None), _) => ScalarType::Timestamp { precision: None },
+        (DataType::Timestamp(_, Some(_)), _) => ScalarType::TimestampTz { precision: None },
+        (DataType::Binary | DataType::LargeBinary, _) => ScalarType::Bytes,
+        (DataType::FixedSizeBinary(16), Some("materialize.v1.uuid")) => ScalarType::Uuid,
+        (DataType::FixedSizeBinary(_), _) => ScalarType::Bytes,
+        (DataType::Utf8 | DataType::LargeUtf8, Some("materialize.v1.jsonb")) => ScalarType::Jsonb,
+        (DataType::Utf8 | DataType::LargeUtf8, _) => ScalarType::String,
+        (DataType::Decimal128(_, scale), _) => {
+            let max_scale = NumericMaxScale::try_from(i64::from(*scale))
+                .map_err(|_| anyhow!("decimal scale {} out of range", scale))?;
+            ScalarType::Numeric {
+                max_scale: Some(max_scale),
+            }
+        }
+        (data_type, _) => bail!("{:?} unimplemented", data_type),
+    };
+    Ok(scalar_type)
+}
+
+/// Decodes Arrow record batches into rows of a [`RelationDesc`].
+///
+/// Columns are matched up by name, so the record batches may contain
+/// additional columns or store them in a different order than the relation.
+pub struct ArrowReader {
+    /// For each column of the relation, the index of the corresponding column
+    /// in the record batches and the type it is decoded as.
+    columns: Vec<(String, usize, ColumnType)>,
+}
+
+impl ArrowReader {
+    /// Creates a reader that decodes record batches with the given `schema`
+    /// into rows of `desc`.
+    ///
+    /// Errors if `schema` is missing any column of `desc`, or stores it with an
+    /// incompatible type.
+    pub fn new(desc: &RelationDesc, schema: &Schema) -> Result<Self, anyhow::Error> {
+        let mut columns = vec![];
+        for (name, typ) in desc.iter() {
+            let (idx, field) = schema
+                .column_with_name(name.as_str())
+                .ok_or_else(|| anyhow!("missing column {}", name))?;
+            let scalar_type = field_to_scalar_type(field)
+                .with_context(|| format!("cannot decode column {}", name))?;
+            if !typ.scalar_type.base_eq(&scalar_type) {
+                bail!(
+                    "column {} has type {:?}, but expected {:?}",
+                    name,
+                    scalar_type,
+                    typ.scalar_type
+                );
+            }
+            columns.push((name.to_string(), idx, typ.clone()));
+        }
+        Ok(ArrowReader { columns })
+    }
+
+    /// Decodes each row of `batch` and appends it to `rows`.
+    pub fn read(&self, batch: &RecordBatch, rows: &mut Vec<Row>) -> Result<(), anyhow::Error> {
+        let mut row = Row::default();
+        for i in 0..batch.num_rows() {
+            let mut packer = row.packer();
+            for (name, idx, typ) in &self.columns {
+                let array = batch.column(*idx);
+                if array.is_null(i) {
+                    if !typ.nullable {
+                        bail!("column {} contains a null value", name);
+                    }
+                    packer.push(Datum::Null);
+                } else {
+                    push_value(&mut packer, array, i, &typ.scalar_type)
+                        .with_context(|| format!("cannot decode column {}", name))?;
+                }
+            }
+            rows.push(row.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Pushes the non-null value at index `i` of `array` onto `packer`.
+fn push_value(
+    packer: &mut RowPacker,
+    array: &ArrayRef,
+    i: usize,
+    scalar_type: &ScalarType,
+) -> Result<(), anyhow::Error> {
+    match (array.data_type(), scalar_type) {
+        (DataType::Boolean, _) => packer.push(Datum::from(array.as_boolean().value(i))),
+        (DataType::Int8, _) => packer.push(Datum::Int16(
+            array.as_primitive::<Int8Type>().value(i).into(),
+        )),
+        (DataType::Int16, _) => {
+            packer.push(Datum::Int16(array.as_primitive::<Int16Type>().value(i)))
+        }
+        (DataType::Int32, _) => {
+            packer.push(Datum::Int32(array.as_primitive::<Int32Type>().value(i)))
+        }
+        (DataType::Int64, _) => {
+            packer.push(Datum::Int64(array.as_primitive::<Int64Type>().value(i)))
+        }
+        (DataType::UInt8, _) => packer.push(Datum::UInt16(
+            array.as_primitive::<UInt8Type>().value(i).into(),
+        )),
+        (DataType::UInt16, _) => {
+            packer.push(Datum::UInt16(array.as_primitive::<UInt16Type>().value(i)))
+        }
+        (DataType::UInt32, _) => {
+            packer.push(Datum::UInt32(array.as_primitive::<UInt32Type>().value(i)))
+        }
+        (DataType::UInt64, ScalarType::MzTimestamp) => packer.push(Datum::MzTimestamp(
+            array.as_primitive::<UInt64Type>().value(i).into(),
+        )),
+        (DataType::UInt64, _) => {
+            packer.push(Datum::UInt64(array.as_primitive::<UInt64Type>().value(i)))
+        }
+        (DataType::Float32, _) => {
+            packer.push(Datum::from(array.as_primitive::<Float32Type>().value(i)))
+        }
+        (DataType::Float64, _) => {
+            packer.push(Datum::from(array.as_primitive::<Float64Type>().value(i)))
+        }
+        (DataType::Date32, _) => {
+            let days = array.as_primitive::<Date32Type>().value(i);
+            packer.push(Datum::Date(Date::from_unix_epoch(days)?))
+        }
+        (DataType::Time64(unit), _) => {
+            let nanos = match unit {
+                TimeUnit::Microsecond => {
+                    array.as_primitive::<Time64MicrosecondType>().value(i) * 1_000
+                }
+                TimeUnit::Nanosecond => array.as_primitive::<Time64NanosecondType>().value(i),
+                unit => bail!("unsupported time unit {:?}", unit),
+            };
+            let secs = u32::try_from(nanos.div_euclid(1_000_000_000))?;
+            let nanos = u32::try_from(nanos.rem_euclid(1_000_000_000))?;
+            let time = NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                .ok_or_else(|| anyhow!("time out of range"))?;
+            packer.push(Datum::Time(time))
+        }
+        (DataType::Timestamp(unit, _), _) => {
+            let (secs, nanos) = match unit {
+                TimeUnit::Second => (array.as_primitive::<TimestampSecondType>().value(i), 0),
+                TimeUnit::Millisecond => {
+                    let millis = array.as_primitive::<TimestampMillisecondType>().value(i);
+                    (
+                        millis.div_euclid(1_000),
+                        millis.rem_euclid(1_000) * 1_000_000,
+                    )
+                }
+                TimeUnit::Microsecond => {
+                    let micros = array.as_primitive::<TimestampMicrosecondType>().value(i);
+                    (
+                        micros.div_euclid(1_000_000),
+                        micros.rem_euclid(1_000_000) * 1_000,
+                    )
+                }
+                TimeUnit::Nanosecond => {
+                    let nanos = array.as_primitive::<TimestampNanosecondType>().value(i);
+                    (
+                        nanos.div_euclid(1_000_000_000),
+                        nanos.rem_euclid(1_000_000_000),
+                    )
+                }
+            };
+            let ts = DateTime::<Utc>::from_timestamp(secs, u32::try_from(nanos)?)
+                .ok_or_else(|| anyhow!("timestamp out of range"))?;
+            match scalar_type {
+                ScalarType::TimestampTz { .. } => packer.push(Datum::TimestampTz(
+                    CheckedTimestamp::from_timestamplike(ts)?,
+                )),
+                _ => packer.push(Datum::Timestamp(CheckedTimestamp::from_timestamplike(
+                    ts.naive_utc(),
+                )?)),
+            }
+        }
+        (DataType::Binary, _) => packer.push(Datum::Bytes(array.as_binary::<i32>().value(i))),
+        (DataType::LargeBinary, _) => packer.push(Datum::Bytes(array.as_binary::<i64>().value(i))),
+        (DataType::FixedSizeBinary(_), ScalarType::Uuid) => {
+            let uuid = Uuid::from_slice(array.as_fixed_size_binary().value(i))?;
+            packer.push(Datum::Uuid(uuid))
+        }
+        (DataType::FixedSizeBinary(_), _) => {
+            packer.push(Datum::Bytes(array.as_fixed_size_binary().value(i)))
+        }
+        (DataType::Utf8 | DataType::LargeUtf8, ScalarType::Jsonb) => {
+            let s = match array.data_type() {
+                DataType::Utf8 => array.as_string::<i32>().value(i),
+                _ => array.as_string::<i64>().value(i),
+            };
+            let json: serde_json::Value = serde_json::from_str(s)?;
+            JsonbPacker::new(packer).pack_serde_json(json)?;
+        }
+        (DataType::Utf8, _) => packer.push(Datum::String(array.as_string::<i32>().value(i))),
+        (DataType::LargeUtf8, _) => packer.push(Datum::String(array.as_string::<i64>().value(i))),
+        (DataType::Decimal128(_, scale), _) => {
+            let value = array.as_primitive::<Decimal128Type>().value(i);
+            let scale = u8::try_from(*scale)
+                .map_err(|_| anyhow!("negative decimal scale {} unsupported", scale))?;
+            let n = numeric::twos_complement_be_to_numeric(&mut value.to_be_bytes(), scale)?;
+            packer.push(Datum::from(n))
+        }
+        (data_type, _) => bail!("{:?} unimplemented", data_type),
+    }
+    Ok(())
+}
@@ -113,6 +113,7 @@ pub enum ObjectType {
     Role,
     Secret,
     Schema,
+    Sequence,
     Sink,
     Source,
     System,
@@ -134,6 +135,7 @@ impl ObjectType {
             ObjectType::Role => "Role",
             ObjectType::Schema => "Schema",
             ObjectType::Secret => "Secret",
+            ObjectType::Sequence => "Sequence",
             ObjectType::Sink => "Sink",
             ObjectType::Source => "Source",
             ObjectType::System => "System",
@@ -0,0 +1,43 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use aws_sdk_kinesis::types::Shard;
+use aws_sdk_kinesis::Client;
+use aws_types::sdk_config::SdkConfig;
+
+/// Creates a new client from an [SDK config](aws_types::sdk_config::SdkConfig)
+/// with Materialize-specific customizations.
+pub fn new_client(sdk_config: &SdkConfig) -> Client {
+    Client::new(sdk_config)
+}
+
+/// Lists all shards of the stream named `stream_name`, including closed
+/// shards that have not yet aged out of the stream's retention period.
+///
+/// Unlike a single `ListShards` call, this follows continuation tokens, so it
+/// returns every shard rather than only the first page of results.
+pub async fn list_shards(client: &Client, stream_name: &str) -> Result<Vec<Shard>, anyhow::Error> {
+    let mut shards = vec![];
+    let mut next_token: Option<String> = None;
+    loop {
+        // The API rejects requests that specify both a stream name and a
+        // continuation token.
+        let req = match next_token {
+            None => client.list_shards().stream_name(stream_name),
+            Some(token) => client.list_shards().next_token(token),
+        };
+        let res = req.send().await?;
+        shards.extend(res.shards.unwrap_or_default());
+        match res.next_token {
+            Some(token) => next_token = Some(token),
+            None => break,
+        }
+    }
+    Ok(shards)
+}
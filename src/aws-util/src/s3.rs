@@ -8,8 +8,10 @@
 // by the Apache License, Version 2.0.
 
 use aws_sdk_s3::config::Builder;
+use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::Client;
 use aws_types::sdk_config::SdkConfig;
+use bytes::Bytes;
 
 /// Creates a new client from an [SDK config](aws_types::sdk_config::SdkConfig)
 /// with Materialize-specific customizations.
@@ -47,3 +49,58 @@ pub async fn list_bucket_path(
         })
         .transpose()?)
 }
+
+/// Lists the keys of all objects in `bucket` that start with `prefix`.
+///
+/// Unlike [`list_bucket_path`], this follows continuation tokens, so it returns
+/// every matching key rather than only the first page of results.
+pub async fn list_all_objects(
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<String>, anyhow::Error> {
+    let mut keys = vec![];
+    let mut pages = client
+        .list_objects_v2()
+        .bucket(bucket)
+        .prefix(prefix)
+        .into_paginator()
+        .send();
+    while let Some(page) = pages.next().await {
+        for obj in page?.contents() {
+            let key = obj
+                .key()
+                .ok_or(anyhow::anyhow!("key not provided from list_objects_v2"))?;
+            keys.push(key.to_string());
+        }
+    }
+    Ok(keys)
+}
+
+/// Fetches the contents of the object at `key` in `bucket`.
+///
+/// Returns `None` if the object does not exist.
+pub async fn get_object(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<Bytes>, anyhow::Error> {
+    match get_object_stream(client, bucket, key).await? {
+        Some(body) => Ok(Some(body.collect().await?.into_bytes())),
+        None => Ok(None),
+    }
+}
+
+/// Like [`get_object`], but returns the body of the object as a stream of
+/// chunks rather than reading it into memory.
+pub async fn get_object_stream(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<ByteStream>, anyhow::Error> {
+    match client.get_object().bucket(bucket).key(key).send().await {
+        Ok(res) => Ok(Some(res.body)),
+        Err(err) if err.as_service_error().map_or(false, |e| e.is_no_such_key()) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -64,6 +64,18 @@ pub const STORAGE_USAGE_ID_ALLOC_KEY: &str = "storage_usage";
 pub const OID_ALLOC_KEY: &str = "oid";
 pub(crate) const CATALOG_CONTENT_VERSION_KEY: &str = "catalog_content_version";
 
+/// Returns the name of the id allocator that backs the sequence `id`, or, if `column` is
+/// specified, the identity column at that position of table `id`.
+///
+/// The allocator's next id is the number of values that have been handed out so far; mapping
+/// that count to actual sequence values is left to the caller.
+pub fn sequence_alloc_key(id: GlobalId, column: Option<usize>) -> String {
+    match column {
+        None => format!("sequence_{id}"),
+        Some(column) => format!("sequence_{id}_{column}"),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BootstrapArgs {
     pub default_cluster_replica_size: String,
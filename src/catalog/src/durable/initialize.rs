@@ -253,6 +253,7 @@ pub(crate) async fn initialize(
             ObjectType::Database => mz_audit_log::ObjectType::Database,
             ObjectType::Schema => mz_audit_log::ObjectType::Schema,
             ObjectType::Func => mz_audit_log::ObjectType::Func,
+            ObjectType::Sequence => mz_audit_log::ObjectType::Sequence,
         };
         audit_events.push((
             mz_audit_log::EventType::Grant,
@@ -927,6 +927,7 @@ impl ItemValue {
             Some("FUNCTION") => CatalogItemType::Func,
             Some("SECRET") => CatalogItemType::Secret,
             Some("CONNECTION") => CatalogItemType::Connection,
+            Some("SEQUENCE") => CatalogItemType::Sequence,
             _ => panic!("unexpected create sql: {}", self.create_sql),
         }
     }
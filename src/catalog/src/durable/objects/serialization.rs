@@ -1053,6 +1053,7 @@ impl RustType<proto::CatalogItemType> for CatalogItemType {
             CatalogItemType::Func => proto::CatalogItemType::Func,
             CatalogItemType::Secret => proto::CatalogItemType::Secret,
             CatalogItemType::Connection => proto::CatalogItemType::Connection,
+            CatalogItemType::Sequence => proto::CatalogItemType::Sequence,
         }
     }
 
@@ -1068,6 +1069,7 @@ impl RustType<proto::CatalogItemType> for CatalogItemType {
             proto::CatalogItemType::Func => CatalogItemType::Func,
             proto::CatalogItemType::Secret => CatalogItemType::Secret,
             proto::CatalogItemType::Connection => CatalogItemType::Connection,
+            proto::CatalogItemType::Sequence => CatalogItemType::Sequence,
             proto::CatalogItemType::Unknown => {
                 return Err(TryFromProtoError::unknown_enum_variant("CatalogItemType"));
             }
@@ -1094,6 +1096,7 @@ impl RustType<proto::ObjectType> for ObjectType {
             ObjectType::Database => proto::ObjectType::Database,
             ObjectType::Schema => proto::ObjectType::Schema,
             ObjectType::Func => proto::ObjectType::Func,
+            ObjectType::Sequence => proto::ObjectType::Sequence,
         }
     }
 
@@ -1114,6 +1117,7 @@ impl RustType<proto::ObjectType> for ObjectType {
             proto::ObjectType::Database => Ok(ObjectType::Database),
             proto::ObjectType::Schema => Ok(ObjectType::Schema),
             proto::ObjectType::Func => Ok(ObjectType::Func),
+            proto::ObjectType::Sequence => Ok(ObjectType::Sequence),
             proto::ObjectType::Unknown => Err(TryFromProtoError::unknown_enum_variant(
                 "ObjectType::Unknown",
             )),
@@ -1281,6 +1285,9 @@ impl RustType<proto::comment_key::Object> for CommentObjectId {
             CommentObjectId::Secret(global_id) => {
                 proto::comment_key::Object::Secret(global_id.into_proto())
             }
+            CommentObjectId::Sequence(global_id) => {
+                proto::comment_key::Object::Sequence(global_id.into_proto())
+            }
             CommentObjectId::Role(role_id) => {
                 proto::comment_key::Object::Role(role_id.into_proto())
             }
@@ -1338,6 +1345,9 @@ impl RustType<proto::comment_key::Object> for CommentObjectId {
             proto::comment_key::Object::Secret(global_id) => {
                 CommentObjectId::Secret(global_id.into_rust()?)
             }
+            proto::comment_key::Object::Sequence(global_id) => {
+                CommentObjectId::Sequence(global_id.into_rust()?)
+            }
             proto::comment_key::Object::Role(role_id) => {
                 CommentObjectId::Role(role_id.into_rust()?)
             }
@@ -1485,6 +1495,7 @@ impl RustType<proto::audit_log_event_v1::ObjectType> for mz_audit_log::ObjectTyp
             }
             mz_audit_log::ObjectType::Role => proto::audit_log_event_v1::ObjectType::Role,
             mz_audit_log::ObjectType::Secret => proto::audit_log_event_v1::ObjectType::Secret,
+            mz_audit_log::ObjectType::Sequence => proto::audit_log_event_v1::ObjectType::Sequence,
             mz_audit_log::ObjectType::Schema => proto::audit_log_event_v1::ObjectType::Schema,
             mz_audit_log::ObjectType::Sink => proto::audit_log_event_v1::ObjectType::Sink,
             mz_audit_log::ObjectType::Source => proto::audit_log_event_v1::ObjectType::Source,
@@ -1514,6 +1525,9 @@ impl RustType<proto::audit_log_event_v1::ObjectType> for mz_audit_log::ObjectTyp
             }
             proto::audit_log_event_v1::ObjectType::Role => Ok(mz_audit_log::ObjectType::Role),
             proto::audit_log_event_v1::ObjectType::Secret => Ok(mz_audit_log::ObjectType::Secret),
+            proto::audit_log_event_v1::ObjectType::Sequence => {
+                Ok(mz_audit_log::ObjectType::Sequence)
+            }
             proto::audit_log_event_v1::ObjectType::Schema => Ok(mz_audit_log::ObjectType::Schema),
             proto::audit_log_event_v1::ObjectType::Sink => Ok(mz_audit_log::ObjectType::Sink),
             proto::audit_log_event_v1::ObjectType::Source => Ok(mz_audit_log::ObjectType::Source),
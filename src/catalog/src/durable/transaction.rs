@@ -49,10 +49,10 @@ use crate::durable::objects::{
     SystemPrivilegesKey, SystemPrivilegesValue, UnfinalizedShardKey,
 };
 use crate::durable::{
-    CatalogError, DefaultPrivilege, DurableCatalogError, DurableCatalogState, Snapshot,
-    AUDIT_LOG_ID_ALLOC_KEY, CATALOG_CONTENT_VERSION_KEY, DATABASE_ID_ALLOC_KEY, OID_ALLOC_KEY,
-    SCHEMA_ID_ALLOC_KEY, SYSTEM_ITEM_ALLOC_KEY, SYSTEM_REPLICA_ID_ALLOC_KEY, USER_ITEM_ALLOC_KEY,
-    USER_ROLE_ID_ALLOC_KEY,
+    sequence_alloc_key, CatalogError, DefaultPrivilege, DurableCatalogError, DurableCatalogState,
+    Snapshot, AUDIT_LOG_ID_ALLOC_KEY, CATALOG_CONTENT_VERSION_KEY, DATABASE_ID_ALLOC_KEY,
+    OID_ALLOC_KEY, SCHEMA_ID_ALLOC_KEY, SYSTEM_ITEM_ALLOC_KEY, SYSTEM_REPLICA_ID_ALLOC_KEY,
+    USER_ITEM_ALLOC_KEY, USER_ROLE_ID_ALLOC_KEY,
 };
 use crate::memory::objects::{StateUpdate, StateUpdateKind};
 
@@ -588,7 +588,8 @@ impl<'a> Transaction<'a> {
             .id_allocator
             .items()
             .get(&IdAllocKey { name: key.clone() })
-            .unwrap_or_else(|| panic!("{key} id allocator missing"))
+            // Sequences may be dropped concurrently with allocating their values.
+            .ok_or_else(|| SqlCatalogError::UnknownItem(key.clone()))?
             .next_id;
         let next_id = current_id
             .checked_add(amount)
@@ -755,6 +756,22 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Inserts a new, unused allocator for a sequence. See [`sequence_alloc_key`].
+    pub fn insert_sequence_allocator(&mut self, name: String) -> Result<(), CatalogError> {
+        self.insert_id_allocator(name, 0)
+    }
+
+    /// Removes the sequence allocators belonging to `id`, including those of any identity columns.
+    /// Items without allocators are ignored.
+    pub fn remove_sequence_allocators(&mut self, id: GlobalId) {
+        let name = sequence_alloc_key(id, None);
+        let prefix = format!("{name}_");
+        self.id_allocator.delete(
+            |k, _v| k.name == name || k.name.starts_with(&prefix),
+            self.op_id,
+        );
+    }
+
     pub fn remove_database(&mut self, id: &DatabaseId) -> Result<(), CatalogError> {
         let prev = self
             .databases
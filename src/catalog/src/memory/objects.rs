@@ -43,15 +43,15 @@ use mz_sql::names::{
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier,
 };
 use mz_sql::plan::{
-    ClusterSchedule, CreateSourcePlan, HirRelationExpr, Ingestion as PlanIngestion,
-    WebhookBodyFormat, WebhookHeaders, WebhookValidation,
+    ClusterSchedule, CreateSourcePlan, HirRelationExpr, IdentityColumn, Ingestion as PlanIngestion,
+    SequenceOptions, TableCheck, WebhookBodyFormat, WebhookHeaders, WebhookValidation,
 };
 use mz_sql::rbac;
 use mz_sql::session::vars::OwnedVarInput;
 use mz_storage_client::controller::IntrospectionType;
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::instances::StorageInstanceId;
-use mz_storage_types::sinks::{KafkaSinkFormat, SinkEnvelope, StorageSinkConnection};
+use mz_storage_types::sinks::{SinkEnvelope, StorageSinkConnection};
 use mz_storage_types::sources::{
     GenericSourceConnection, SourceConnection, SourceDesc, SourceEnvelope, Timeline,
 };
@@ -353,6 +353,7 @@ pub enum CatalogItem {
     Type(Type),
     Func(Func),
     Secret(Secret),
+    Sequence(Sequence),
     Connection(Connection),
 }
 
@@ -376,6 +377,17 @@ pub struct Table {
     pub desc: RelationDesc,
     #[serde(skip)]
     pub defaults: Vec<Expr<Aug>>,
+    /// The expressions that compute the table's generated columns, by column
+    /// index.
+    #[serde(skip)]
+    pub generated_columns: BTreeMap<usize, Expr<Aug>>,
+    /// The `CHECK` constraints that the table's rows must satisfy.
+    pub checks: Vec<TableCheck>,
+    /// The table's identity columns, by column index.
+    pub identity_columns: BTreeMap<usize, IdentityColumn>,
+    /// The keys of the table that were declared `UNIQUE NULLS NOT DISTINCT`. Values of the table's
+    /// other keys that contain a `NULL` never conflict with each other.
+    pub nulls_not_distinct_keys: Vec<Vec<usize>>,
     #[serde(skip)]
     pub conn_id: Option<ConnectionId>,
     pub resolved_ids: ResolvedIds,
@@ -617,7 +629,9 @@ impl Source {
                     // These multi-output sources do not use their primary
                     // source's data shard, so we don't include it in accounting
                     // for users.
-                    GenericSourceConnection::Postgres(_) | GenericSourceConnection::MySql(_) => 0,
+                    GenericSourceConnection::Postgres(_)
+                    | GenericSourceConnection::MySql(_)
+                    | GenericSourceConnection::SqlServer(_) => 0,
                     GenericSourceConnection::LoadGenerator(lg) => {
                         // TODO: make this a method on the load generator.
                         if lg.load_generator.views().is_empty() {
@@ -629,7 +643,9 @@ impl Source {
                             0
                         }
                     }
-                    GenericSourceConnection::Kafka(_) => 1,
+                    GenericSourceConnection::Kafka(_)
+                    | GenericSourceConnection::File(_)
+                    | GenericSourceConnection::Kinesis(_) => 1,
                 }
             }
             //  DataSourceDesc::IngestionExport represents a subsource, which
@@ -675,13 +691,16 @@ impl Sink {
         }
     }
 
-    /// Output format of the sink.
-    pub fn format(&self) -> &str {
+    /// Output formats of the sink's message keys, if any, and values.
+    pub fn formats(&self) -> (Option<&str>, &str) {
         let StorageSinkConnection::Kafka(connection) = &self.connection;
-        match &connection.format {
-            KafkaSinkFormat::Avro { .. } => "avro",
-            KafkaSinkFormat::Json => "json",
-        }
+        let key_format = connection
+            .format
+            .key_format
+            .as_ref()
+            .map(|format| format.get_format_name());
+        let value_format = connection.format.value_format.get_format_name();
+        (key_format, value_format)
     }
 
     pub fn connection_id(&self) -> Option<GlobalId> {
@@ -747,6 +766,12 @@ pub struct Secret {
     pub create_sql: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct Sequence {
+    pub create_sql: String,
+    pub options: SequenceOptions,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Connection {
     pub create_sql: String,
@@ -768,6 +793,7 @@ impl CatalogItem {
             CatalogItem::Type(_) => mz_sql::catalog::CatalogItemType::Type,
             CatalogItem::Func(_) => mz_sql::catalog::CatalogItemType::Func,
             CatalogItem::Secret(_) => mz_sql::catalog::CatalogItemType::Secret,
+            CatalogItem::Sequence(_) => mz_sql::catalog::CatalogItemType::Sequence,
             CatalogItem::Connection(_) => mz_sql::catalog::CatalogItemType::Connection,
         }
     }
@@ -785,6 +811,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => false,
         }
     }
@@ -808,6 +835,7 @@ impl CatalogItem {
             | CatalogItem::Index(_)
             | CatalogItem::Sink(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -872,6 +900,7 @@ impl CatalogItem {
             CatalogItem::View(view) => &view.resolved_ids,
             CatalogItem::MaterializedView(mview) => &mview.resolved_ids,
             CatalogItem::Secret(_) => &*EMPTY,
+            CatalogItem::Sequence(_) => &*EMPTY,
             CatalogItem::Connection(connection) => &connection.resolved_ids,
         }
     }
@@ -896,6 +925,7 @@ impl CatalogItem {
             CatalogItem::View(view) => uses.extend(view.raw_expr.depends_on()),
             CatalogItem::MaterializedView(mview) => uses.extend(mview.raw_expr.depends_on()),
             CatalogItem::Secret(_) => {}
+            CatalogItem::Sequence(_) => {}
             CatalogItem::Connection(_) => {}
         }
         uses
@@ -913,6 +943,7 @@ impl CatalogItem {
             | CatalogItem::Sink(_)
             | CatalogItem::MaterializedView(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Connection(_) => None,
@@ -984,6 +1015,11 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Secret(i))
             }
+            CatalogItem::Sequence(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Sequence(i))
+            }
             CatalogItem::Connection(i) => {
                 let mut i = i.clone();
                 i.create_sql = do_rewrite(i.create_sql)?;
@@ -1057,6 +1093,11 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Secret(i))
             }
+            CatalogItem::Sequence(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Sequence(i))
+            }
             CatalogItem::Func(_) | CatalogItem::Type(_) => {
                 unreachable!("{}s cannot be renamed", self.typ())
             }
@@ -1150,6 +1191,7 @@ impl CatalogItem {
             | CatalogItem::MaterializedView(MaterializedView { create_sql, .. })
             | CatalogItem::Index(Index { create_sql, .. })
             | CatalogItem::Secret(Secret { create_sql, .. })
+            | CatalogItem::Sequence(Sequence { create_sql, .. })
             | CatalogItem::Connection(Connection { create_sql, .. }) => Some(create_sql),
             CatalogItem::Func(_) | CatalogItem::Log(_) => None,
         };
@@ -1185,6 +1227,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -1209,6 +1252,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -1227,6 +1271,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -1248,6 +1293,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => return None,
         };
         Some(cw)
@@ -1272,6 +1318,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => return None,
         };
         Some(custom_logical_compaction_window.unwrap_or(CompactionWindow::Default))
@@ -1292,6 +1339,7 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => false,
         }
     }
@@ -1325,6 +1373,7 @@ impl CatalogItem {
                 .expect("builtin types cannot be serialized")
                 .clone(),
             CatalogItem::Secret(secret) => secret.create_sql.clone(),
+            CatalogItem::Sequence(sequence) => sequence.create_sql.clone(),
             CatalogItem::Connection(connection) => connection.create_sql.clone(),
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         }
@@ -1351,6 +1400,7 @@ impl CatalogItem {
             CatalogItem::Sink(sink) => sink.create_sql,
             CatalogItem::Type(typ) => typ.create_sql.expect("builtin types cannot be serialized"),
             CatalogItem::Secret(secret) => secret.create_sql,
+            CatalogItem::Sequence(sequence) => sequence.create_sql,
             CatalogItem::Connection(connection) => connection.create_sql,
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         }
@@ -1412,6 +1462,14 @@ impl CatalogEntry {
         }
     }
 
+    /// Returns the inner [`Sequence`] if this entry is a sequence, else `None`.
+    pub fn sequence(&self) -> Option<&Sequence> {
+        match self.item() {
+            CatalogItem::Sequence(sequence) => Some(sequence),
+            _ => None,
+        }
+    }
+
     pub fn connection(&self) -> Result<&Connection, SqlCatalogError> {
         match self.item() {
             CatalogItem::Connection(connection) => Ok(connection),
@@ -1495,6 +1553,7 @@ impl CatalogEntry {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -2174,6 +2233,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
                 create_sql.as_deref().unwrap_or("<builtin>")
             }
             CatalogItem::Secret(Secret { create_sql, .. }) => create_sql,
+            CatalogItem::Sequence(Sequence { create_sql, .. }) => create_sql,
             CatalogItem::Connection(Connection { create_sql, .. }) => create_sql,
             CatalogItem::Func(_) => "<builtin>",
             CatalogItem::Log(_) => "<builtin>",
@@ -2200,6 +2260,36 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
+    fn table_generated_columns(&self) -> Option<&BTreeMap<usize, Expr<Aug>>> {
+        if let CatalogItem::Table(Table {
+            generated_columns, ..
+        }) = self.item()
+        {
+            Some(generated_columns)
+        } else {
+            None
+        }
+    }
+
+    fn table_identity_columns(&self) -> Option<&BTreeMap<usize, IdentityColumn>> {
+        if let CatalogItem::Table(Table {
+            identity_columns, ..
+        }) = self.item()
+        {
+            Some(identity_columns)
+        } else {
+            None
+        }
+    }
+
+    fn sequence_options(&self) -> Option<&SequenceOptions> {
+        if let CatalogItem::Sequence(Sequence { options, .. }) = self.item() {
+            Some(options)
+        } else {
+            None
+        }
+    }
+
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>> {
         if let CatalogItem::Type(Type { details, .. }) = self.item() {
             Some(details)
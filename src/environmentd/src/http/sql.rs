@@ -1176,6 +1176,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::CreatedTable { .. }
         | ExecuteResponse::CreatedIndex { .. }
         | ExecuteResponse::CreatedSecret { .. }
+        | ExecuteResponse::CreatedSequence
         | ExecuteResponse::CreatedSource { .. }
         | ExecuteResponse::CreatedSink { .. }
         | ExecuteResponse::CreatedView { .. }
@@ -1186,6 +1187,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
         | ExecuteResponse::DiscardedAll
+        | ExecuteResponse::DiscardedSequences
         | ExecuteResponse::DroppedObject(_)
         | ExecuteResponse::DroppedOwned
         | ExecuteResponse::EmptyQuery
@@ -1193,6 +1195,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::GrantedRole
         | ExecuteResponse::Inserted(_)
         | ExecuteResponse::Copied(_)
+        | ExecuteResponse::Merged(_)
         | ExecuteResponse::Raised
         | ExecuteResponse::ReassignOwned
         | ExecuteResponse::RevokedPrivilege
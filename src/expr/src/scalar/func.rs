@@ -48,7 +48,9 @@ use mz_repr::adt::range::{self, Range, RangeBound, RangeOps};
 use mz_repr::adt::regex::{any_regex, Regex};
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
 use mz_repr::role_id::RoleId;
-use mz_repr::{strconv, ColumnName, ColumnType, Datum, DatumType, Row, RowArena, ScalarType};
+use mz_repr::{
+    strconv, ColumnName, ColumnType, Datum, DatumType, GlobalId, Row, RowArena, ScalarType,
+};
 use mz_sql_pretty::pretty_str;
 use num::traits::CheckedNeg;
 use proptest::prelude::*;
@@ -98,6 +100,11 @@ pub enum UnmaterializableFunc {
     MzUptime,
     MzVersion,
     MzVersionNum,
+    /// The next value of the identified sequence.
+    NextVal(GlobalId),
+    /// The value most recently produced by the identified sequence in the
+    /// current session.
+    CurrVal(GlobalId),
     PgBackendPid,
     PgPostmasterStartTime,
     SessionUser,
@@ -140,6 +147,8 @@ impl UnmaterializableFunc {
             UnmaterializableFunc::MzUptime => ScalarType::Interval.nullable(true),
             UnmaterializableFunc::MzVersion => ScalarType::String.nullable(false),
             UnmaterializableFunc::MzVersionNum => ScalarType::Int32.nullable(false),
+            UnmaterializableFunc::NextVal(_) => ScalarType::Int64.nullable(false),
+            UnmaterializableFunc::CurrVal(_) => ScalarType::Int64.nullable(false),
             UnmaterializableFunc::PgBackendPid => ScalarType::Int32.nullable(false),
             UnmaterializableFunc::PgPostmasterStartTime => {
                 ScalarType::TimestampTz { precision: None }.nullable(false)
@@ -175,6 +184,8 @@ impl fmt::Display for UnmaterializableFunc {
             UnmaterializableFunc::MzUptime => f.write_str("mz_uptime"),
             UnmaterializableFunc::MzVersion => f.write_str("mz_version"),
             UnmaterializableFunc::MzVersionNum => f.write_str("mz_version_num"),
+            UnmaterializableFunc::NextVal(id) => write!(f, "nextval({id})"),
+            UnmaterializableFunc::CurrVal(id) => write!(f, "currval({id})"),
             UnmaterializableFunc::PgBackendPid => f.write_str("pg_backend_pid"),
             UnmaterializableFunc::PgPostmasterStartTime => f.write_str("pg_postmaster_start_time"),
             UnmaterializableFunc::SessionUser => f.write_str("session_user"),
@@ -204,6 +215,8 @@ impl RustType<ProtoUnmaterializableFunc> for UnmaterializableFunc {
             UnmaterializableFunc::MzUptime => MzUptime(()),
             UnmaterializableFunc::MzVersion => MzVersion(()),
             UnmaterializableFunc::MzVersionNum => MzVersionNum(()),
+            UnmaterializableFunc::NextVal(id) => NextVal(id.into_proto()),
+            UnmaterializableFunc::CurrVal(id) => CurrVal(id.into_proto()),
             UnmaterializableFunc::PgBackendPid => PgBackendPid(()),
             UnmaterializableFunc::PgPostmasterStartTime => PgPostmasterStartTime(()),
             UnmaterializableFunc::SessionUser => SessionUser(()),
@@ -234,6 +247,8 @@ impl RustType<ProtoUnmaterializableFunc> for UnmaterializableFunc {
                 MzUptime(()) => Ok(UnmaterializableFunc::MzUptime),
                 MzVersion(()) => Ok(UnmaterializableFunc::MzVersion),
                 MzVersionNum(()) => Ok(UnmaterializableFunc::MzVersionNum),
+                NextVal(id) => Ok(UnmaterializableFunc::NextVal(id.into_rust()?)),
+                CurrVal(id) => Ok(UnmaterializableFunc::CurrVal(id.into_rust()?)),
                 PgBackendPid(()) => Ok(UnmaterializableFunc::PgBackendPid),
                 PgPostmasterStartTime(()) => Ok(UnmaterializableFunc::PgPostmasterStartTime),
                 SessionUser(()) => Ok(UnmaterializableFunc::SessionUser),
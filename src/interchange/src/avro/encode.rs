@@ -100,13 +100,15 @@ struct KeyInfo {
 }
 
 fn encode_message_unchecked(
-    schema_id: i32,
+    schema_id: Option<i32>,
     row: Row,
     schema: &Schema,
     columns: &[(ColumnName, ColumnType)],
 ) -> Vec<u8> {
     let mut buf = vec![];
-    encode_avro_header(&mut buf, schema_id);
+    if let Some(schema_id) = schema_id {
+        encode_avro_header(&mut buf, schema_id);
+    }
     let value = encode_datums_as_avro(row.iter(), columns);
     mz_avro::encode_unchecked(&value, schema, &mut buf);
     buf
@@ -268,42 +270,40 @@ impl fmt::Debug for AvroSchemaGenerator {
 
 /// Manages encoding of Avro-encoded bytes.
 pub struct AvroEncoder {
-    schema_generator: AvroSchemaGenerator,
-    key_schema_id: Option<i32>,
-    value_schema_id: i32,
+    columns: Vec<(ColumnName, ColumnType)>,
+    schema: Schema,
+    /// The ID of the schema in the schema registry, if any. Messages are only
+    /// framed with the Confluent wire format header if this is set.
+    schema_id: Option<i32>,
 }
 
 impl fmt::Debug for AvroEncoder {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("AvroEncoder")
-            .field("writer_schema", &self.schema_generator.writer_schema)
+            .field("writer_schema", &self.schema)
             .finish()
     }
 }
 
 impl AvroEncoder {
+    /// Constructs an encoder that writes rows of shape `desc` using `schema`,
+    /// which must have been generated from `desc` by an
+    /// [`AvroSchemaGenerator`].
     pub fn new(
-        schema_generator: AvroSchemaGenerator,
-        key_schema_id: Option<i32>,
-        value_schema_id: i32,
-    ) -> Self {
-        AvroEncoder {
-            schema_generator,
-            key_schema_id,
-            value_schema_id,
+        desc: RelationDesc,
+        debezium: bool,
+        schema: &str,
+        schema_id: Option<i32>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut columns = column_names_and_types(desc);
+        if debezium {
+            columns = envelopes::dbz_envelope(columns);
         }
-    }
-
-    pub fn encode_key_unchecked(&self, schema_id: i32, row: Row) -> Vec<u8> {
-        let schema = self.schema_generator.key_writer_schema().unwrap();
-        let columns = self.schema_generator.key_columns().unwrap();
-        encode_message_unchecked(schema_id, row, schema, columns)
-    }
-
-    pub fn encode_value_unchecked(&self, schema_id: i32, row: Row) -> Vec<u8> {
-        let schema = self.schema_generator.value_writer_schema();
-        let columns = self.schema_generator.value_columns();
-        encode_message_unchecked(schema_id, row, schema, columns)
+        Ok(AvroEncoder {
+            columns,
+            schema: Schema::parse(&serde_json::from_str(schema)?)?,
+            schema_id,
+        })
     }
 }
 
@@ -312,12 +312,13 @@ impl Encode for AvroEncoder {
         "avro"
     }
 
-    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
-        self.encode_key_unchecked(self.key_schema_id.unwrap(), row)
-    }
-
-    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
-        self.encode_value_unchecked(self.value_schema_id, row)
+    fn encode_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(encode_message_unchecked(
+            self.schema_id,
+            row,
+            &self.schema,
+            &self.columns,
+        ))
     }
 }
 
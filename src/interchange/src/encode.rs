@@ -11,12 +11,19 @@ use std::collections::BTreeSet;
 
 use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row};
 
+/// Encodes rows of a particular shape into a serialization format.
+///
+/// Kafka sinks use one `Encode` implementation for message keys and another for
+/// message values, which need not use the same format.
 pub trait Encode {
     fn get_format_name(&self) -> &str;
 
-    fn encode_key_unchecked(&self, row: Row) -> Vec<u8>;
-
-    fn encode_value_unchecked(&self, row: Row) -> Vec<u8>;
+    /// Encodes `row`, which must have the shape the encoder was constructed
+    /// for.
+    ///
+    /// Returns an error if a value of `row` cannot be represented in the
+    /// format.
+    fn encode_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error>;
 }
 
 /// Bundled information sufficient to encode Datums.
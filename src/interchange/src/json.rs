@@ -10,6 +10,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
+use byteorder::{NetworkEndian, WriteBytesExt};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char;
 use mz_repr::adt::jsonb::JsonbRef;
@@ -26,33 +27,19 @@ const MICROS_PER_MILLIS: u32 = 1_000;
 
 // Manages encoding of JSON-encoded bytes
 pub struct JsonEncoder {
-    key_columns: Option<Vec<(ColumnName, ColumnType)>>,
-    value_columns: Vec<(ColumnName, ColumnType)>,
+    columns: Vec<(ColumnName, ColumnType)>,
+    /// The ID of the JSON Schema in the schema registry, if any. Messages are
+    /// only framed with the Confluent wire format header if this is set.
+    schema_id: Option<i32>,
 }
 
 impl JsonEncoder {
-    pub fn new(key_desc: Option<RelationDesc>, value_desc: RelationDesc, debezium: bool) -> Self {
-        let mut value_columns = column_names_and_types(value_desc);
+    pub fn new(desc: RelationDesc, debezium: bool, schema_id: Option<i32>) -> Self {
+        let mut columns = column_names_and_types(desc);
         if debezium {
-            value_columns = envelopes::dbz_envelope(value_columns);
+            columns = envelopes::dbz_envelope(columns);
         }
-        JsonEncoder {
-            key_columns: if let Some(desc) = key_desc {
-                Some(column_names_and_types(desc))
-            } else {
-                None
-            },
-            value_columns,
-        }
-    }
-
-    pub fn encode_row(
-        &self,
-        row: mz_repr::Row,
-        names_types: &[(ColumnName, ColumnType)],
-    ) -> Vec<u8> {
-        let value = encode_datums_as_json(row.iter(), names_types);
-        value.to_string().into_bytes()
+        JsonEncoder { columns, schema_id }
     }
 }
 
@@ -61,15 +48,19 @@ impl Encode for JsonEncoder {
         "json"
     }
 
-    fn encode_key_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        self.encode_row(
-            row,
-            self.key_columns.as_ref().expect("key schema must exist"),
-        )
-    }
-
-    fn encode_value_unchecked(&self, row: mz_repr::Row) -> Vec<u8> {
-        self.encode_row(row, &self.value_columns)
+    fn encode_unchecked(&self, row: mz_repr::Row) -> Result<Vec<u8>, anyhow::Error> {
+        let mut buf = vec![];
+        if let Some(schema_id) = self.schema_id {
+            // The Confluent JSON Schema serializers use the same header as
+            // the Avro serializers: a magic byte (0) followed by the 32-bit
+            // schema ID.
+            buf.write_u8(0).expect("writing to vec cannot fail");
+            buf.write_i32::<NetworkEndian>(schema_id)
+                .expect("writing to vec cannot fail");
+        }
+        let value = encode_datums_as_json(row.iter(), &self.columns);
+        serde_json::to_writer(&mut buf, &value).expect("writing to vec cannot fail");
+        Ok(buf)
     }
 }
 
@@ -81,7 +72,7 @@ impl fmt::Debug for JsonEncoder {
                 &format!(
                     "{:?}",
                     build_row_schema_json(
-                        &self.value_columns,
+                        &self.columns,
                         "schema",
                         &BTreeMap::new(),
                         None,
@@ -271,6 +262,112 @@ impl ToJson for TypedDatum<'_> {
     }
 }
 
+/// Builds a [JSON Schema] that describes the JSON objects produced by a
+/// [`JsonEncoder`] for the given relation.
+///
+/// The generated schema uses draft 7 of the specification, which is the
+/// default version understood by the Confluent Schema Registry.
+///
+/// [JSON Schema]: https://json-schema.org
+pub fn build_json_schema(desc: RelationDesc, debezium: bool) -> serde_json::Value {
+    let mut columns = column_names_and_types(desc);
+    if debezium {
+        columns = envelopes::dbz_envelope(columns);
+    }
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".into(),
+        json!("http://json-schema.org/draft-07/schema#"),
+    );
+    schema.extend(build_json_schema_object(
+        columns.iter().map(|(name, typ)| (name, typ)),
+    ));
+    serde_json::Value::Object(schema)
+}
+
+fn build_json_schema_object<'a, I>(fields: I) -> Map<String, serde_json::Value>
+where
+    I: IntoIterator<Item = (&'a ColumnName, &'a ColumnType)>,
+{
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for (name, typ) in fields {
+        properties.insert(name.to_string(), build_json_schema_type(typ));
+        required.push(json!(name.to_string()));
+    }
+    let mut object = Map::new();
+    object.insert("type".into(), json!("object"));
+    object.insert("properties".into(), properties.into());
+    object.insert("required".into(), required.into());
+    object.insert("additionalProperties".into(), json!(false));
+    object
+}
+
+fn build_json_schema_type(typ: &ColumnType) -> serde_json::Value {
+    let schema = match &typ.scalar_type {
+        ScalarType::Bool => json!({"type": "boolean"}),
+        ScalarType::PgLegacyChar
+        | ScalarType::Int16
+        | ScalarType::Int32
+        | ScalarType::Int64
+        | ScalarType::UInt16
+        | ScalarType::UInt32
+        | ScalarType::UInt64
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType => json!({"type": "integer"}),
+        ScalarType::Float32 | ScalarType::Float64 => json!({"type": "number"}),
+        ScalarType::Date => json!({"type": "string", "format": "date"}),
+        ScalarType::Uuid => json!({"type": "string", "format": "uuid"}),
+        ScalarType::AclItem
+        | ScalarType::Numeric { .. }
+        | ScalarType::Time
+        | ScalarType::Timestamp { .. }
+        | ScalarType::TimestampTz { .. }
+        | ScalarType::Interval
+        | ScalarType::String
+        | ScalarType::Char { .. }
+        | ScalarType::VarChar { .. }
+        | ScalarType::PgLegacyName
+        | ScalarType::MzTimestamp
+        | ScalarType::Range { .. }
+        | ScalarType::MzAclItem => json!({"type": "string"}),
+        // Byte strings are encoded as arrays of their bytes.
+        ScalarType::Bytes => json!({
+            "type": "array",
+            "items": {"type": "integer", "minimum": 0, "maximum": 255},
+        }),
+        // Any JSON value is valid.
+        ScalarType::Jsonb => json!({}),
+        // Multidimensional arrays are encoded as nested arrays, and the number
+        // of dimensions is not part of the type.
+        ScalarType::Array(..) | ScalarType::Int2Vector => json!({"type": "array"}),
+        ScalarType::List { element_type, .. } => json!({
+            "type": "array",
+            "items": build_json_schema_type(&ColumnType {
+                nullable: true,
+                scalar_type: (**element_type).clone(),
+            }),
+        }),
+        ScalarType::Map { value_type, .. } => json!({
+            "type": "object",
+            "additionalProperties": build_json_schema_type(&ColumnType {
+                nullable: true,
+                scalar_type: (**value_type).clone(),
+            }),
+        }),
+        ScalarType::Record { fields, .. } => {
+            build_json_schema_object(fields.iter().map(|(name, typ)| (name, typ))).into()
+        }
+    };
+    if typ.nullable {
+        json!({"anyOf": [{"type": "null"}, schema]})
+    } else {
+        schema
+    }
+}
+
 fn encode_array<'a>(
     elems: &mut impl Iterator<Item = Datum<'a>>,
     dims: &[ArrayDimension],
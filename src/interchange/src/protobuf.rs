@@ -11,12 +11,16 @@ use std::collections::BTreeSet;
 
 use anyhow::{anyhow, bail, Context};
 use mz_ore::str::StrExt;
-use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType};
+use prost::Message;
 use prost_reflect::{
     Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
     ReflectMessage, Value,
 };
 
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes;
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
@@ -111,6 +115,231 @@ impl Decoder {
     }
 }
 
+/// Encodes rows as a particular Protobuf message.
+///
+/// Each column of the encoded relation is written to the message field of the
+/// same name. Message fields that do not correspond to a column are left
+/// unset, as are fields whose column is `NULL`.
+#[derive(Debug)]
+pub struct ProtobufEncoder {
+    message_descriptor: MessageDescriptor,
+    columns: Vec<(ColumnName, ColumnType)>,
+    fields: Vec<FieldDescriptor>,
+}
+
+impl ProtobufEncoder {
+    /// Constructs an encoder that writes rows of shape `desc` as the message
+    /// described by `descriptors`.
+    ///
+    /// Returns an error if a column of `desc` (or of its Debezium envelope, if
+    /// `debezium` is set) has no corresponding field in the message, or if the
+    /// type of a column cannot be represented by the type of its field.
+    pub fn new(
+        descriptors: DecodedDescriptors,
+        desc: RelationDesc,
+        debezium: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let mut columns = column_names_and_types(desc);
+        if debezium {
+            columns = envelopes::dbz_envelope(columns);
+        }
+        let message_descriptor = descriptors.message_descriptor;
+        let fields = validate_fields(&message_descriptor, &columns)?;
+        Ok(ProtobufEncoder {
+            message_descriptor,
+            columns,
+            fields,
+        })
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let message = encode_message(
+            self.message_descriptor.clone(),
+            row.iter(),
+            self.columns.iter().map(|(_name, typ)| &typ.scalar_type),
+            self.fields.iter().cloned(),
+        )?;
+        Ok(message.encode_to_vec())
+    }
+}
+
+/// Resolves the field of `message` that corresponds to each of `columns`.
+fn validate_fields(
+    message: &MessageDescriptor,
+    columns: &[(ColumnName, ColumnType)],
+) -> Result<Vec<FieldDescriptor>, anyhow::Error> {
+    let mut fields = Vec::with_capacity(columns.len());
+    for (name, typ) in columns {
+        let field = message.get_field_by_name(name.as_str()).ok_or_else(|| {
+            anyhow!(
+                "protobuf message {} has no field named {}",
+                message.full_name().quoted(),
+                name.as_str().quoted()
+            )
+        })?;
+        if field.is_map() {
+            bail!("Protobuf map fields are not supported");
+        }
+        let scalar_type = match (&typ.scalar_type, field.is_list()) {
+            (ScalarType::List { element_type, .. }, true) => &**element_type,
+            (_, true) => bail!(
+                "column {} of type {:?} cannot be written to repeated protobuf field {}",
+                name.as_str().quoted(),
+                typ.scalar_type,
+                field.full_name().quoted()
+            ),
+            (scalar_type, false) => scalar_type,
+        };
+        validate_kind(name, scalar_type, &field)?;
+        fields.push(field);
+    }
+    Ok(fields)
+}
+
+/// Checks that values of `scalar_type` can be written to `field`, ignoring
+/// whether the field is repeated.
+fn validate_kind(
+    name: &ColumnName,
+    scalar_type: &ScalarType,
+    field: &FieldDescriptor,
+) -> Result<(), anyhow::Error> {
+    let compatible = match (field.kind(), scalar_type) {
+        (Kind::Bool, ScalarType::Bool) => true,
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, ScalarType::Int16 | ScalarType::Int32) => {
+            true
+        }
+        (
+            Kind::Int64 | Kind::Sint64 | Kind::Sfixed64,
+            ScalarType::Int16 | ScalarType::Int32 | ScalarType::Int64,
+        ) => true,
+        (Kind::Uint32 | Kind::Fixed32, ScalarType::UInt16 | ScalarType::UInt32) => true,
+        (
+            Kind::Uint64 | Kind::Fixed64,
+            ScalarType::UInt16 | ScalarType::UInt32 | ScalarType::UInt64,
+        ) => true,
+        (Kind::Float, ScalarType::Float32) => true,
+        (Kind::Double, ScalarType::Float32 | ScalarType::Float64) => true,
+        (
+            Kind::String,
+            ScalarType::String | ScalarType::VarChar { .. } | ScalarType::Char { .. },
+        ) => true,
+        (Kind::Bytes, ScalarType::Bytes) => true,
+        (Kind::Message(m), ScalarType::Record { fields, .. }) => {
+            validate_fields(&m, fields)?;
+            true
+        }
+        _ => false,
+    };
+    if !compatible {
+        bail!(
+            "column {} of type {:?} cannot be written to protobuf field {} of type {:?}",
+            name.as_str().quoted(),
+            scalar_type,
+            field.full_name().quoted(),
+            field.kind()
+        );
+    }
+    Ok(())
+}
+
+/// Encodes `datums` as the fields of a message of type `descriptor`.
+///
+/// `NULL` values leave their field unset. Repeated fields have no way to
+/// represent a `NULL` element, so lists that contain one are rejected rather
+/// than silently shortened.
+fn encode_message<'a, 'b>(
+    descriptor: MessageDescriptor,
+    datums: impl IntoIterator<Item = Datum<'a>>,
+    types: impl IntoIterator<Item = &'b ScalarType>,
+    fields: impl IntoIterator<Item = FieldDescriptor>,
+) -> Result<DynamicMessage, anyhow::Error> {
+    let mut message = DynamicMessage::new(descriptor);
+    for ((datum, scalar_type), field) in datums.into_iter().zip(types).zip(fields) {
+        if datum.is_null() {
+            continue;
+        }
+        let value = if field.is_list() {
+            let element_type = scalar_type.unwrap_list_element_type();
+            let values = datum
+                .unwrap_list()
+                .iter()
+                .map(|datum| {
+                    if datum.is_null() {
+                        bail!(
+                            "repeated protobuf field {} cannot contain NULL",
+                            field.full_name().quoted()
+                        );
+                    }
+                    encode_value(datum, element_type, &field)
+                })
+                .collect::<Result<_, _>>()?;
+            Value::List(values)
+        } else {
+            encode_value(datum, scalar_type, &field)?
+        };
+        message.set_field(&field, value);
+    }
+    Ok(message)
+}
+
+fn encode_value(
+    datum: Datum,
+    scalar_type: &ScalarType,
+    field: &FieldDescriptor,
+) -> Result<Value, anyhow::Error> {
+    let value = match (field.kind(), scalar_type) {
+        (_, ScalarType::Bool) => Value::Bool(datum.unwrap_bool()),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, ScalarType::Int16) => {
+            Value::I32(i32::from(datum.unwrap_int16()))
+        }
+        (_, ScalarType::Int16) => Value::I64(i64::from(datum.unwrap_int16())),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, ScalarType::Int32) => {
+            Value::I32(datum.unwrap_int32())
+        }
+        (_, ScalarType::Int32) => Value::I64(i64::from(datum.unwrap_int32())),
+        (_, ScalarType::Int64) => Value::I64(datum.unwrap_int64()),
+        (Kind::Uint32 | Kind::Fixed32, ScalarType::UInt16) => {
+            Value::U32(u32::from(datum.unwrap_uint16()))
+        }
+        (_, ScalarType::UInt16) => Value::U64(u64::from(datum.unwrap_uint16())),
+        (Kind::Uint32 | Kind::Fixed32, ScalarType::UInt32) => Value::U32(datum.unwrap_uint32()),
+        (_, ScalarType::UInt32) => Value::U64(u64::from(datum.unwrap_uint32())),
+        (_, ScalarType::UInt64) => Value::U64(datum.unwrap_uint64()),
+        (Kind::Float, ScalarType::Float32) => Value::F32(datum.unwrap_float32()),
+        (_, ScalarType::Float32) => Value::F64(f64::from(datum.unwrap_float32())),
+        (_, ScalarType::Float64) => Value::F64(datum.unwrap_float64()),
+        (_, ScalarType::String | ScalarType::VarChar { .. } | ScalarType::Char { .. }) => {
+            Value::String(datum.unwrap_str().to_owned())
+        }
+        (_, ScalarType::Bytes) => Value::Bytes(datum.unwrap_bytes().to_vec().into()),
+        (Kind::Message(m), ScalarType::Record { fields, .. }) => {
+            let message_fields = fields
+                .iter()
+                .map(|(name, _typ)| {
+                    m.get_field_by_name(name.as_str())
+                        .expect("field validated on construction")
+                })
+                .collect::<Vec<_>>();
+            Value::Message(encode_message(
+                m,
+                datum.unwrap_list().iter(),
+                fields.iter().map(|(_name, typ)| &typ.scalar_type),
+                message_fields,
+            )?)
+        }
+        (kind, scalar_type) => unreachable!(
+            "protobuf field of type {kind:?} validated against {scalar_type:?} on construction"
+        ),
+    };
+    Ok(value)
+}
+
 fn derive_column_type(
     seen_messages: &mut BTreeSet<String>,
     field: &FieldDescriptor,
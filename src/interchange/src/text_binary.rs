@@ -0,0 +1,95 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoders for the `TEXT` and `BYTES` formats, which write the single column of
+//! a relation without any framing.
+
+use anyhow::bail;
+use bytes::BytesMut;
+use mz_repr::{ColumnType, RelationDesc, Row, ScalarType};
+
+use crate::encode::Encode;
+
+/// Returns the type of the single column of `desc`, or an error mentioning
+/// `format` if `desc` does not have exactly one column.
+fn single_column_type(desc: &RelationDesc, format: &str) -> Result<ColumnType, anyhow::Error> {
+    match desc.typ().column_types.as_slice() {
+        [typ] => Ok(typ.clone()),
+        types => bail!(
+            "{} format requires exactly one column, but got {}",
+            format,
+            types.len()
+        ),
+    }
+}
+
+/// Encodes the single column of a relation in the Postgres text format.
+///
+/// `NULL` is encoded as an empty message.
+#[derive(Debug)]
+pub struct TextEncoder {
+    typ: ColumnType,
+}
+
+impl TextEncoder {
+    pub fn new(desc: RelationDesc) -> Result<Self, anyhow::Error> {
+        Ok(TextEncoder {
+            typ: single_column_type(&desc, "TEXT")?,
+        })
+    }
+}
+
+impl Encode for TextEncoder {
+    fn get_format_name(&self) -> &str {
+        "text"
+    }
+
+    fn encode_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let mut buf = BytesMut::new();
+        let datum = row.unpack_first();
+        if let Some(value) = mz_pgrepr::Value::from_datum(datum, &self.typ.scalar_type) {
+            value.encode_text(&mut buf);
+        }
+        Ok(buf.to_vec())
+    }
+}
+
+/// Encodes the single `bytea` column of a relation as its raw bytes.
+///
+/// `NULL` is encoded as an empty message.
+#[derive(Debug)]
+pub struct BytesEncoder;
+
+impl BytesEncoder {
+    pub fn new(desc: RelationDesc) -> Result<Self, anyhow::Error> {
+        let typ = single_column_type(&desc, "BYTES")?;
+        if typ.scalar_type != ScalarType::Bytes {
+            bail!(
+                "BYTES format requires a bytea column, but got {:?}",
+                typ.scalar_type
+            );
+        }
+        Ok(BytesEncoder)
+    }
+}
+
+impl Encode for BytesEncoder {
+    fn get_format_name(&self) -> &str {
+        "bytes"
+    }
+
+    fn encode_unchecked(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let datum = row.unpack_first();
+        if datum.is_null() {
+            Ok(vec![])
+        } else {
+            Ok(datum.unwrap_bytes().to_vec())
+        }
+    }
+}
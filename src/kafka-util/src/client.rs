@@ -675,6 +675,21 @@ pub fn get_partitions<C: ClientContext>(
     Ok(partition_ids)
 }
 
+/// Retrieve the names of all the topics in the cluster using the given `client`.
+pub fn get_topics<C: ClientContext>(
+    client: &Client<C>,
+    timeout: Duration,
+) -> Result<Vec<String>, KafkaError> {
+    let meta = client.fetch_metadata(None, timeout)?;
+    let topics = meta
+        .topics()
+        .iter()
+        .filter(|topic| topic.error().is_none())
+        .map(|topic| topic.name().to_owned())
+        .collect();
+    Ok(topics)
+}
+
 /// Default to true as they have no downsides <https://github.com/confluentinc/librdkafka/issues/283>.
 pub const DEFAULT_KEEPALIVE: bool = true;
 /// The `rdkafka` default.
@@ -115,9 +115,11 @@ fn val_to_datum<'a>(
                 temp_strs.push(val);
                 Datum::from(temp_strs.last().unwrap().as_str())
             }
-            ScalarType::VarChar { max_length } => {
+            ScalarType::VarChar { .. } => {
+                // MySQL enforces the maximum length of the column itself, so
+                // a longer value means that the column was widened upstream.
+                // We ingest such values as is until the schema is refreshed.
                 let val = from_value_opt::<String>(value)?;
-                check_char_length(max_length.map(|l| l.into_u32()), &val, col_desc)?;
                 temp_strs.push(val);
                 Datum::from(temp_strs.last().unwrap().as_str())
             }
@@ -15,7 +15,7 @@ use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
-use mz_repr::ColumnType;
+use mz_repr::{ColumnType, ScalarType};
 
 use self::proto_my_sql_column_desc::Meta;
 
@@ -74,7 +74,8 @@ impl MySqlTableDesc {
     ///
     /// Currently this means that the values are equal except for the following
     /// exceptions:
-    /// - `self`'s columns are a prefix of `other`'s columns.
+    /// - `self`'s columns are a prefix of `other`'s columns, although
+    ///   `varchar` columns may have been widened.
     /// - `self`'s keys are all present in `other`
     pub fn determine_compatibility(&self, other: &MySqlTableDesc) -> Result<(), anyhow::Error> {
         if self == other {
@@ -131,6 +132,23 @@ impl MySqlTableDesc {
 
         Ok(())
     }
+
+    /// Describes the additive changes that `other` makes to `self`, i.e. the
+    /// columns that were added to the table and the columns whose type was
+    /// widened. `other` is expected to be compatible with `self` as determined
+    /// by [`Self::determine_compatibility`].
+    pub fn additive_changes(&self, other: &MySqlTableDesc) -> Vec<String> {
+        let mut changes = vec![];
+        for (column, other_column) in self.columns.iter().zip(other.columns.iter()) {
+            if column.is_widened_by(other_column) {
+                changes.push(format!("column {} was widened", column.name));
+            }
+        }
+        for column in other.columns.iter().skip(self.columns.len()) {
+            changes.push(format!("column {} was added", column.name));
+        }
+        changes
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
@@ -233,6 +251,30 @@ impl RustType<ProtoMySqlColumnDesc> for MySqlColumnDesc {
     }
 }
 
+impl MySqlColumnDesc {
+    /// Reports whether `other` describes the same `varchar` column as `self`
+    /// with a larger maximum length. Every value of `self` is a valid value of
+    /// `other`, so the column can continue to be ingested.
+    fn is_widened_by(&self, other: &MySqlColumnDesc) -> bool {
+        let (Some(self_type), Some(other_type)) = (&self.column_type, &other.column_type) else {
+            return false;
+        };
+        match (&self_type.scalar_type, &other_type.scalar_type) {
+            (
+                ScalarType::VarChar {
+                    max_length: Some(self_length),
+                },
+                ScalarType::VarChar {
+                    max_length: other_length,
+                },
+            ) => other_length.map_or(true, |other_length| {
+                other_length.into_u32() > self_length.into_u32()
+            }),
+            _ => false,
+        }
+    }
+}
+
 impl IsCompatible for MySqlColumnDesc {
     /// Determines if two `MySqlColumnDesc` are compatible with one another in
     /// a way that Materialize can handle.
@@ -241,7 +283,7 @@ impl IsCompatible for MySqlColumnDesc {
             && match (&self.column_type, &other.column_type) {
                 (None, None) => true,
                 (Some(self_type), Some(other_type)) => {
-                    self_type.scalar_type == other_type.scalar_type
+                    (self_type.scalar_type == other_type.scalar_type || self.is_widened_by(other))
                     // Columns are compatible if:
                     // - self is nullable; introducing a not null constraint doesn't
                     //   change this column's behavior.
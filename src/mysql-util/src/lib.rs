@@ -23,7 +23,8 @@ pub use desc::{
 
 mod replication;
 pub use replication::{
-    ensure_full_row_binlog_format, ensure_gtid_consistency, ensure_replication_commit_order,
+    ensure_full_row_binlog_format, ensure_gtid_consistency, ensure_mariadb_gtid_consistency,
+    ensure_replication_commit_order, gtids_enabled, is_mariadb, query_binlog_position,
     query_sys_var,
 };
 
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use mysql_async::prelude::Queryable;
-use mysql_async::Conn;
+use mysql_async::{Conn, Row};
 
 use crate::MySqlError;
 
@@ -44,6 +44,26 @@ pub async fn ensure_full_row_binlog_format(conn: &mut Conn) -> Result<(), MySqlE
     Ok(())
 }
 
+/// Returns whether the server assigns a GTID to every transaction it commits. Servers that don't
+/// can only be replicated from by binlog file and position.
+pub async fn gtids_enabled(conn: &mut Conn) -> Result<bool, MySqlError> {
+    Ok(query_sys_var(conn, "global.gtid_mode").await? == "ON")
+}
+
+/// Query the binlog file and position that the server will write the next transaction at.
+pub async fn query_binlog_position(conn: &mut Conn) -> Result<(String, u64), MySqlError> {
+    // `SHOW MASTER STATUS` was renamed to `SHOW BINARY LOG STATUS` in MySQL 8.2 and removed in 8.4
+    let status: Option<Row> = match conn.query_first("SHOW BINARY LOG STATUS").await {
+        Ok(status) => status,
+        Err(_) => conn.query_first("SHOW MASTER STATUS").await?,
+    };
+    let status = status.ok_or_else(|| anyhow::anyhow!("binary logging is not enabled"))?;
+    match (status.get_opt("File"), status.get_opt("Position")) {
+        (Some(Ok(file)), Some(Ok(position))) => Ok((file, position)),
+        _ => Err(anyhow::anyhow!("unexpected binary log status: {:?}", status).into()),
+    }
+}
+
 pub async fn ensure_gtid_consistency(conn: &mut Conn) -> Result<(), MySqlError> {
     verify_sys_setting(conn, "gtid_mode", "ON").await?;
     verify_sys_setting(conn, "enforce_gtid_consistency", "ON").await?;
@@ -51,6 +71,20 @@ pub async fn ensure_gtid_consistency(conn: &mut Conn) -> Result<(), MySqlError>
     Ok(())
 }
 
+/// Returns whether the server is a MariaDB server rather than a MySQL server.
+pub async fn is_mariadb(conn: &mut Conn) -> Result<bool, MySqlError> {
+    Ok(query_sys_var(conn, "version").await?.contains("MariaDB"))
+}
+
+/// MariaDB always assigns a GTID to every transaction, but only guarantees that the sequence
+/// numbers of each server within a replication domain are increasing in strict mode. Compressed
+/// binlog events are not understood by the replication client.
+pub async fn ensure_mariadb_gtid_consistency(conn: &mut Conn) -> Result<(), MySqlError> {
+    verify_sys_setting(conn, "gtid_strict_mode", "1").await?;
+    verify_sys_setting(conn, "log_bin_compress", "0").await?;
+    Ok(())
+}
+
 /// In case this is a MySQL replica, we ensure that the replication settings are such that
 /// the replica would commit all transactions in the order they were committed on the primary.
 /// We don't really know that this is a replica, but if the settings indicate multi-threaded
@@ -21,8 +21,31 @@ use serde::Serialize;
 
 static END_OF_COPY_MARKER: &[u8] = b"\\.";
 
+/// The signature that starts the header of the binary format.
+static BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xFF\r\n\0";
+
+/// The flag in the header of the binary format that indicates that each tuple
+/// includes an OID.
+const BINARY_FLAG_HAS_OIDS: u32 = 1 << 16;
+
 include!(concat!(env!("OUT_DIR"), "/mz_pgcopy.copy.rs"));
 
+/// Writes the header that precedes the first tuple of the binary format.
+pub fn encode_copy_binary_header(out: &mut Vec<u8>) {
+    // 11-byte signature.
+    out.extend(BINARY_SIGNATURE);
+    // 32-bit flags field.
+    out.extend([0, 0, 0, 0]);
+    // 32-bit header extension length field.
+    out.extend([0, 0, 0, 0]);
+}
+
+/// Writes the trailer that follows the last tuple of the binary format.
+pub fn encode_copy_binary_trailer(out: &mut Vec<u8>) {
+    let trailer: i16 = -1;
+    out.extend(trailer.to_be_bytes());
+}
+
 fn encode_copy_row_binary(
     row: &Row,
     typ: &RelationType,
@@ -436,6 +459,7 @@ pub enum CopyFormatParams<'a> {
     Text(CopyTextFormatParams<'a>),
     Csv(CopyCsvFormatParams<'a>),
     Binary,
+    Parquet,
 }
 
 impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
@@ -446,6 +470,7 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
                 Self::Text(f) => Kind::Text(f.into_proto()),
                 Self::Csv(f) => Kind::Csv(f.into_proto()),
                 Self::Binary => Kind::Binary(()),
+                Self::Parquet => Kind::Parquet(()),
             }),
         }
     }
@@ -456,6 +481,7 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
             Some(Kind::Text(f)) => Ok(Self::Text(f.into_rust()?)),
             Some(Kind::Csv(f)) => Ok(Self::Csv(f.into_rust()?)),
             Some(Kind::Binary(())) => Ok(Self::Binary),
+            Some(Kind::Parquet(())) => Ok(Self::Parquet),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoCopyFormatParams::kind",
             )),
@@ -472,6 +498,7 @@ impl Arbitrary for CopyFormatParams<'static> {
             any::<CopyTextFormatParams>().prop_map(Self::Text).boxed(),
             any::<CopyCsvFormatParams>().prop_map(Self::Csv).boxed(),
             Just(Self::Binary).boxed(),
+            Just(Self::Parquet).boxed(),
         ])
     }
 }
@@ -482,6 +509,7 @@ impl CopyFormatParams<'static> {
             &CopyFormatParams::Text(_) => "txt",
             &CopyFormatParams::Csv(_) => "csv",
             &CopyFormatParams::Binary => "bin",
+            &CopyFormatParams::Parquet => "parquet",
         }
     }
 }
@@ -495,9 +523,10 @@ pub fn decode_copy_format<'a>(
     match params {
         CopyFormatParams::Text(params) => decode_copy_format_text(data, column_types, params),
         CopyFormatParams::Csv(params) => decode_copy_format_csv(data, column_types, params),
-        CopyFormatParams::Binary => Err(io::Error::new(
+        CopyFormatParams::Binary => decode_copy_format_binary(data, column_types),
+        CopyFormatParams::Parquet => Err(io::Error::new(
             io::ErrorKind::Unsupported,
-            "cannot decode as binary format",
+            "cannot decode as parquet format",
         )),
     }
 }
@@ -513,6 +542,10 @@ pub fn encode_copy_format<'a>(
         CopyFormatParams::Text(params) => encode_copy_row_text(params, row, typ, out),
         CopyFormatParams::Csv(params) => encode_copy_row_csv(params, row, typ, out),
         CopyFormatParams::Binary => encode_copy_row_binary(row, typ, out),
+        CopyFormatParams::Parquet => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cannot encode as parquet format",
+        )),
     }
 }
 
@@ -598,6 +631,118 @@ pub fn decode_copy_format_text(
     Ok(rows)
 }
 
+/// A cursor over data in the binary format.
+struct CopyBinaryFormatParser<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> CopyBinaryFormatParser<'a> {
+    fn consume_n(&mut self, n: usize) -> Result<&'a [u8], io::Error> {
+        if self.data.len() < n {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected EOF in COPY data",
+            ));
+        }
+        let (bytes, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn consume_i16(&mut self) -> Result<i16, io::Error> {
+        let bytes = self.consume_n(2)?;
+        Ok(i16::from_be_bytes(
+            bytes.try_into().expect("known to be 2 bytes"),
+        ))
+    }
+
+    fn consume_i32(&mut self) -> Result<i32, io::Error> {
+        let bytes = self.consume_n(4)?;
+        Ok(i32::from_be_bytes(
+            bytes.try_into().expect("known to be 4 bytes"),
+        ))
+    }
+
+    fn expect_header(&mut self) -> Result<(), io::Error> {
+        if self.consume_n(BINARY_SIGNATURE.len()).ok() != Some(BINARY_SIGNATURE) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "COPY file signature not recognized",
+            ));
+        }
+        // The lower 16 bits of the flags are reserved for backwards-compatible
+        // format issues and can be ignored, but the upper 16 bits are critical.
+        let flags = u32::from_be_bytes(self.consume_n(4)?.try_into().expect("known to be 4 bytes"));
+        if flags & BINARY_FLAG_HAS_OIDS != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "COPY file with OIDs is not supported",
+            ));
+        }
+        if flags & 0xFFFF0000 & !BINARY_FLAG_HAS_OIDS != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unrecognized critical flags in COPY file header",
+            ));
+        }
+        let extension_len = usize::try_from(self.consume_i32()?).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid COPY file header (negative extension length)",
+            )
+        })?;
+        self.consume_n(extension_len)?;
+        Ok(())
+    }
+}
+
+pub fn decode_copy_format_binary(
+    data: &[u8],
+    column_types: &[mz_pgrepr::Type],
+) -> Result<Vec<Row>, io::Error> {
+    let mut rows = Vec::new();
+
+    let mut parser = CopyBinaryFormatParser { data };
+    parser.expect_header()?;
+    loop {
+        let field_count = parser.consume_i16()?;
+        // A field count of -1 marks the trailer. Like PG, we drop any data
+        // after it on the floor.
+        if field_count == -1 {
+            break;
+        }
+        if usize::try_from(field_count).ok() != Some(column_types.len()) {
+            let msg = format!(
+                "row field count is {}, expected {}",
+                field_count,
+                column_types.len()
+            );
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+        let mut row = Vec::new();
+        let buf = RowArena::new();
+        for typ in column_types {
+            let len = parser.consume_i32()?;
+            if len == -1 {
+                row.push(Datum::Null);
+                continue;
+            }
+            let len = usize::try_from(len)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid field size"))?;
+            let raw_value = parser.consume_n(len)?;
+            match mz_pgrepr::Value::decode_binary(typ, raw_value) {
+                Ok(value) => row.push(value.into_datum(&buf, typ)),
+                Err(err) => {
+                    let msg = format!("unable to decode column: {}", err);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+        rows.push(Row::pack(row));
+    }
+    Ok(rows)
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct CopyCsvFormatParams<'a> {
     pub delimiter: u8,
@@ -1058,6 +1203,68 @@ mod tests {
         Ok(())
     }
 
+    #[mz_ore::test]
+    fn test_copy_binary_roundtrip() -> Result<(), io::Error> {
+        let typ: RelationType = RelationType::new(vec![
+            ColumnType {
+                scalar_type: mz_repr::ScalarType::String,
+                nullable: true,
+            },
+            ColumnType {
+                scalar_type: mz_repr::ScalarType::Int64,
+                nullable: true,
+            },
+            ColumnType {
+                scalar_type: mz_repr::ScalarType::Bool,
+                nullable: false,
+            },
+        ]);
+        let rows = vec![
+            Row::pack_slice(&[Datum::from("a\tb"), Datum::Int64(-1), Datum::True]),
+            Row::pack_slice(&[Datum::Null, Datum::Null, Datum::False]),
+        ];
+        let column_types = typ
+            .column_types
+            .iter()
+            .map(|x| &x.scalar_type)
+            .map(mz_pgrepr::Type::from)
+            .collect::<Vec<mz_pgrepr::Type>>();
+
+        let mut out = Vec::new();
+        encode_copy_binary_header(&mut out);
+        for row in &rows {
+            encode_copy_format(&CopyFormatParams::Binary, row, &typ, &mut out)?;
+        }
+        encode_copy_binary_trailer(&mut out);
+        let decoded = decode_copy_format(&out, &column_types, CopyFormatParams::Binary)?;
+        assert_eq!(decoded, rows);
+
+        // Data after the trailer is ignored.
+        out.extend(b"junk");
+        let decoded = decode_copy_format(&out, &column_types, CopyFormatParams::Binary)?;
+        assert_eq!(decoded, rows);
+
+        // A missing trailer is an error.
+        let mut truncated = Vec::new();
+        encode_copy_binary_header(&mut truncated);
+        encode_copy_format(&CopyFormatParams::Binary, &rows[0], &typ, &mut truncated)?;
+        let err =
+            decode_copy_format(&truncated, &column_types, CopyFormatParams::Binary).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected EOF in COPY data");
+
+        // The number of fields must match the number of columns.
+        let err =
+            decode_copy_format(&out, &column_types[..2], CopyFormatParams::Binary).unwrap_err();
+        assert_eq!(err.to_string(), "row field count is 3, expected 2");
+
+        // So must the signature.
+        let err =
+            decode_copy_format(b"1\t2\n", &column_types, CopyFormatParams::Binary).unwrap_err();
+        assert_eq!(err.to_string(), "COPY file signature not recognized");
+
+        Ok(())
+    }
+
     proptest! {
         #[mz_ore::test]
         #[cfg_attr(miri, ignore)]
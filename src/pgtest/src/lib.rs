@@ -569,8 +569,13 @@ pub fn run_test(tf: &mut datadriven::TestFile, addr: String, user: String, timeo
                             .unwrap();
                         }
                         "CopyData" => {
-                            let v: String = serde_json::from_str(args).unwrap();
-                            frontend::CopyData::new(v.as_bytes()).unwrap().write(buf);
+                            // Data that is not valid UTF-8, like that of the
+                            // binary format, is specified as an array of bytes.
+                            let v: Vec<u8> = match serde_json::from_str::<String>(args) {
+                                Ok(v) => v.into_bytes(),
+                                Err(_) => serde_json::from_str(args).unwrap(),
+                            };
+                            frontend::CopyData::new(&v[..]).unwrap().write(buf);
                         }
                         "CopyDone" => {
                             frontend::copy_done(buf);
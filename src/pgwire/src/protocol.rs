@@ -1673,6 +1673,7 @@ where
             | ExecuteResponse::CreatedRole
             | ExecuteResponse::CreatedSchema { .. }
             | ExecuteResponse::CreatedSecret { .. }
+            | ExecuteResponse::CreatedSequence
             | ExecuteResponse::CreatedSink { .. }
             | ExecuteResponse::CreatedSource { .. }
             | ExecuteResponse::CreatedTable { .. }
@@ -1683,6 +1684,7 @@ where
             | ExecuteResponse::Deallocate { .. }
             | ExecuteResponse::Deleted(..)
             | ExecuteResponse::DiscardedAll
+            | ExecuteResponse::DiscardedSequences
             | ExecuteResponse::DiscardedTemp
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
@@ -1690,6 +1692,7 @@ where
             | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(..)
             | ExecuteResponse::Copied(..)
+            | ExecuteResponse::Merged(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
             | ExecuteResponse::ReassignOwned
@@ -1917,12 +1920,7 @@ where
         let mut out = Vec::new();
 
         if let CopyFormat::Binary = format {
-            // 11-byte signature.
-            out.extend(b"PGCOPY\n\xFF\r\n\0");
-            // 32-bit flags field.
-            out.extend([0, 0, 0, 0]);
-            // 32-bit header extension length field.
-            out.extend([0, 0, 0, 0]);
+            mz_pgcopy::encode_copy_binary_header(&mut out);
         }
 
         let mut count = 0;
@@ -1964,8 +1962,7 @@ where
         }
         // Send required trailers.
         if let CopyFormat::Binary = format {
-            let trailer: i16 = -1;
-            out.extend(trailer.to_be_bytes());
+            mz_pgcopy::encode_copy_binary_trailer(&mut out);
             self.send(BackendMessage::CopyData(mem::take(&mut out)))
                 .await?;
         }
@@ -2029,9 +2026,13 @@ where
         ctx_extra: &mut ExecuteContextExtra,
     ) -> Result<State, io::Error> {
         let typ = row_desc.typ();
-        let column_formats = vec![Format::Text; typ.column_types.len()];
+        let format = match params {
+            CopyFormatParams::Binary => Format::Binary,
+            _ => Format::Text,
+        };
+        let column_formats = vec![format; typ.column_types.len()];
         self.send(BackendMessage::CopyInResponse {
-            overall_format: Format::Text,
+            overall_format: format,
             column_formats,
         })
         .await?;
@@ -16,7 +16,7 @@ use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use tokio_postgres::types::Oid;
+use tokio_postgres::types::{Oid, Type};
 use tracing::warn;
 
 include!(concat!(env!("OUT_DIR"), "/mz_postgres_util.desc.rs"));
@@ -60,7 +60,8 @@ impl PostgresTableDesc {
     /// exceptions:
     /// - `self`'s columns are a compatible prefix of `other`'s columns.
     ///   Compatibility is defined as returning `true` for
-    ///   `PostgresColumnDesc::is_compatible`.
+    ///   `PostgresColumnDesc::is_compatible`, which permits `varchar` columns
+    ///   to have been widened.
     /// - `self`'s keys are all present in `other`
     pub fn determine_compatibility(
         &self,
@@ -102,6 +103,23 @@ impl PostgresTableDesc {
             )
         }
     }
+
+    /// Describes the additive changes that `other` makes to `self`, i.e. the
+    /// columns that were added to the table and the columns whose type was
+    /// widened. `other` is expected to be compatible with `self` as determined
+    /// by [`Self::determine_compatibility`].
+    pub fn additive_changes(&self, other: &PostgresTableDesc) -> Vec<String> {
+        let mut changes = vec![];
+        for (column, other_column) in self.columns.iter().zip(other.columns.iter()) {
+            if column.is_widened_by(other_column) {
+                changes.push(format!("column {} was widened", column.name));
+            }
+        }
+        for column in other.columns.iter().skip(self.columns.len()) {
+            changes.push(format!("column {} was added", column.name));
+        }
+        changes
+    }
 }
 
 impl RustType<ProtoPostgresTableDesc> for PostgresTableDesc {
@@ -167,13 +185,26 @@ impl PostgresColumnDesc {
         self.name == other.name
             && self.col_num == other.col_num
             && (self.type_oid == other.type_oid || allow_type_change)
-            && (self.type_mod == other.type_mod || allow_type_change)
+            && (self.type_mod == other.type_mod || allow_type_change || self.is_widened_by(other))
             // Columns are compatible if:
             // - self is nullable; introducing a not null constraint doesn't
             //   change this column's behavior.
             // - self and other are both not nullable
             && (self.nullable || self.nullable == other.nullable)
     }
+
+    /// Reports whether `other` describes the same `varchar` column as `self`
+    /// with a larger maximum length. Every value of `self` is a valid value of
+    /// `other`, so the column can continue to be ingested.
+    fn is_widened_by(&self, other: &PostgresColumnDesc) -> bool {
+        // A type modifier of -1 means that the length is unbounded. For all
+        // other values the modifier is the maximum length plus `VARHDRSZ`.
+        self.type_oid == Type::VARCHAR.oid()
+            && self.type_oid == other.type_oid
+            && self.type_mod != other.type_mod
+            && self.type_mod != -1
+            && (other.type_mod == -1 || other.type_mod > self.type_mod)
+    }
 }
 
 impl RustType<ProtoPostgresColumnDesc> for PostgresColumnDesc {
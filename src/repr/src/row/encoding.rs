@@ -638,8 +638,11 @@ impl RowEncoder {
 impl PartEncoder<Row> for RowEncoder {
     fn encode(&mut self, val: &Row) {
         self.len += 1;
-        for (encoder, datum) in self.col_encoders.iter_mut().zip(val.iter()) {
-            encoder.encode(datum);
+        // Rows written before nullable columns were added to the schema lack
+        // their trailing datums, which we encode as nulls.
+        let mut datums = val.iter();
+        for encoder in self.col_encoders.iter_mut() {
+            encoder.encode(datums.next().unwrap_or(Datum::Null));
         }
     }
 
@@ -1272,6 +1275,28 @@ mod tests {
         );
     }
 
+    #[mz_ore::test]
+    fn columnar_missing_trailing_columns() {
+        use mz_persist_types::codec_impls::UnitSchema;
+        use mz_persist_types::columnar::{PartDecoder, Schema};
+        use mz_persist_types::part::PartBuilder;
+
+        let (schema, row) = schema_and_row();
+        // A row that was written before the last column was added to the
+        // schema.
+        let short_row = Row::pack(row.iter().take(4));
+
+        let mut builder = PartBuilder::new(&schema, &UnitSchema).expect("valid schema");
+        builder.push(&short_row, &(), 1u64, 1i64);
+        let part = builder.finish();
+
+        let mut actual = Row::default();
+        <RelationDesc as Schema<Row>>::decoder(&schema, part.key_ref())
+            .expect("valid schema")
+            .decode(0, &mut actual);
+        assert_eq!(actual, row);
+    }
+
     fn scalar_type_columnar_roundtrip(scalar_type: ScalarType) {
         use mz_persist_types::columnar::validate_roundtrip;
         let mut rows = Vec::new();
@@ -186,6 +186,8 @@ pub enum CsrConfigOptionName<T: AstInfo> {
     AvroValueFullname,
     NullDefaults,
     AvroDocOn(AvroDocOn<T>),
+    JsonKeySchema,
+    JsonValueSchema,
 }
 
 impl<T: AstInfo> WithOptionName for CsrConfigOptionName<T> {
@@ -199,7 +201,9 @@ impl<T: AstInfo> WithOptionName for CsrConfigOptionName<T> {
             Self::AvroKeyFullname
             | Self::AvroValueFullname
             | Self::NullDefaults
-            | Self::AvroDocOn(_) => false,
+            | Self::AvroDocOn(_)
+            | Self::JsonKeySchema
+            | Self::JsonValueSchema => false,
         }
     }
 }
@@ -250,6 +254,8 @@ impl<T: AstInfo> AstDisplay for CsrConfigOptionName<T> {
             CsrConfigOptionName::AvroValueFullname => f.write_str("AVRO VALUE FULLNAME"),
             CsrConfigOptionName::NullDefaults => f.write_str("NULL DEFAULTS"),
             CsrConfigOptionName::AvroDocOn(doc_on) => f.write_node(doc_on),
+            CsrConfigOptionName::JsonKeySchema => f.write_str("JSON KEY SCHEMA"),
+            CsrConfigOptionName::JsonValueSchema => f.write_str("JSON VALUE SCHEMA"),
         }
     }
 }
@@ -393,21 +399,21 @@ impl AstDisplay for CsrSeedProtobufSchema {
 impl_display!(CsrSeedProtobufSchema);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum CreateSourceFormat<T: AstInfo> {
-    /// `CREATE SOURCE .. FORMAT`
+pub enum FormatSpecifier<T: AstInfo> {
+    /// `CREATE SOURCE|SINK .. FORMAT`
     Bare(Format<T>),
-    /// `CREATE SOURCE .. KEY FORMAT .. VALUE FORMAT`
+    /// `CREATE SOURCE|SINK .. KEY FORMAT .. VALUE FORMAT`
     KeyValue { key: Format<T>, value: Format<T> },
 }
 
-impl<T: AstInfo> AstDisplay for CreateSourceFormat<T> {
+impl<T: AstInfo> AstDisplay for FormatSpecifier<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
-            CreateSourceFormat::Bare(format) => {
+            FormatSpecifier::Bare(format) => {
                 f.write_str(" FORMAT ");
                 f.write_node(format)
             }
-            CreateSourceFormat::KeyValue { key, value } => {
+            FormatSpecifier::KeyValue { key, value } => {
                 f.write_str(" KEY FORMAT ");
                 f.write_node(key);
                 f.write_str(" VALUE FORMAT ");
@@ -416,7 +422,7 @@ impl<T: AstInfo> AstDisplay for CreateSourceFormat<T> {
         }
     }
 }
-impl_display_t!(CreateSourceFormat);
+impl_display_t!(FormatSpecifier);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Format<T: AstInfo> {
@@ -429,9 +435,18 @@ pub enum Format<T: AstInfo> {
         delimiter: char,
     },
     Json {
+        /// Whether each message is a JSON array whose elements are decoded as
+        /// separate rows.
         array: bool,
+        /// Whether each message contains newline-delimited JSON values that
+        /// are decoded as separate rows.
+        lines: bool,
+        /// The schema registry connection to which a JSON Schema describing
+        /// the data is published, if any.
+        csr_connection: Option<CsrConnection<T>>,
     },
     Text,
+    Parquet,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -475,6 +490,9 @@ pub enum SourceIncludeMetadata {
     Offset {
         alias: Option<Ident>,
     },
+    Topic {
+        alias: Option<Ident>,
+    },
     Headers {
         alias: Option<Ident>,
     },
@@ -511,6 +529,10 @@ impl AstDisplay for SourceIncludeMetadata {
                 f.write_str("OFFSET");
                 print_alias(f, alias);
             }
+            SourceIncludeMetadata::Topic { alias } => {
+                f.write_str("TOPIC");
+                print_alias(f, alias);
+            }
             SourceIncludeMetadata::Headers { alias } => {
                 f.write_str("HEADERS");
                 print_alias(f, alias);
@@ -653,13 +675,25 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_str("'");
                 }
             }
-            Self::Json { array } => {
+            Self::Json {
+                array,
+                lines,
+                csr_connection,
+            } => {
                 f.write_str("JSON");
                 if *array {
                     f.write_str(" ARRAY");
                 }
+                if *lines {
+                    f.write_str(" LINES");
+                }
+                if let Some(csr_connection) = csr_connection {
+                    f.write_str(" USING CONFLUENT SCHEMA REGISTRY ");
+                    f.write_node(csr_connection);
+                }
             }
             Self::Text => f.write_str("TEXT"),
+            Self::Parquet => f.write_str("PARQUET"),
         }
     }
 }
@@ -794,6 +828,7 @@ pub enum CreateConnectionType {
     Postgres,
     Ssh,
     MySql,
+    SqlServer,
 }
 
 impl AstDisplay for CreateConnectionType {
@@ -820,6 +855,9 @@ impl AstDisplay for CreateConnectionType {
             Self::MySql => {
                 f.write_str("MYSQL");
             }
+            Self::SqlServer => {
+                f.write_str("SQL SERVER");
+            }
         }
     }
 }
@@ -865,9 +903,13 @@ impl_display_t!(CreateConnectionOption);
 pub enum KafkaSourceConfigOptionName {
     GroupIdPrefix,
     Topic,
+    TopicPattern,
     TopicMetadataRefreshInterval,
     StartTimestamp,
     StartOffset,
+    /// The ingestion epoch of the source, which `ALTER SOURCE ... RESET TO`
+    /// records along with the new `StartOffset`.
+    StartEpoch,
 }
 
 impl AstDisplay for KafkaSourceConfigOptionName {
@@ -875,11 +917,13 @@ impl AstDisplay for KafkaSourceConfigOptionName {
         f.write_str(match self {
             KafkaSourceConfigOptionName::GroupIdPrefix => "GROUP ID PREFIX",
             KafkaSourceConfigOptionName::Topic => "TOPIC",
+            KafkaSourceConfigOptionName::TopicPattern => "TOPIC PATTERN",
             KafkaSourceConfigOptionName::TopicMetadataRefreshInterval => {
                 "TOPIC METADATA REFRESH INTERVAL"
             }
             KafkaSourceConfigOptionName::StartOffset => "START OFFSET",
             KafkaSourceConfigOptionName::StartTimestamp => "START TIMESTAMP",
+            KafkaSourceConfigOptionName::StartEpoch => "START EPOCH",
         })
     }
 }
@@ -895,9 +939,11 @@ impl WithOptionName for KafkaSourceConfigOptionName {
         match self {
             KafkaSourceConfigOptionName::GroupIdPrefix
             | KafkaSourceConfigOptionName::Topic
+            | KafkaSourceConfigOptionName::TopicPattern
             | KafkaSourceConfigOptionName::TopicMetadataRefreshInterval
             | KafkaSourceConfigOptionName::StartOffset
-            | KafkaSourceConfigOptionName::StartTimestamp => false,
+            | KafkaSourceConfigOptionName::StartTimestamp
+            | KafkaSourceConfigOptionName::StartEpoch => false,
         }
     }
 }
@@ -913,7 +959,9 @@ impl_display_t!(KafkaSourceConfigOption);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum KafkaSinkConfigOptionName {
     CompressionType,
+    PartitionBy,
     ProgressGroupIdPrefix,
+    Tombstones,
     Topic,
     TransactionalIdPrefix,
     LegacyIds,
@@ -923,7 +971,9 @@ impl AstDisplay for KafkaSinkConfigOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             KafkaSinkConfigOptionName::CompressionType => "COMPRESSION TYPE",
+            KafkaSinkConfigOptionName::PartitionBy => "PARTITION BY",
             KafkaSinkConfigOptionName::ProgressGroupIdPrefix => "PROGRESS GROUP ID PREFIX",
+            KafkaSinkConfigOptionName::Tombstones => "TOMBSTONES",
             KafkaSinkConfigOptionName::Topic => "TOPIC",
             KafkaSinkConfigOptionName::TransactionalIdPrefix => "TRANSACTIONAL ID PREFIX",
             KafkaSinkConfigOptionName::LegacyIds => "LEGACY IDS",
@@ -941,7 +991,9 @@ impl WithOptionName for KafkaSinkConfigOptionName {
     fn redact_value(&self) -> bool {
         match self {
             KafkaSinkConfigOptionName::CompressionType
+            | KafkaSinkConfigOptionName::PartitionBy
             | KafkaSinkConfigOptionName::ProgressGroupIdPrefix
+            | KafkaSinkConfigOptionName::Tombstones
             | KafkaSinkConfigOptionName::Topic
             | KafkaSinkConfigOptionName::TransactionalIdPrefix
             | KafkaSinkConfigOptionName::LegacyIds => false,
@@ -966,6 +1018,14 @@ pub enum PgConfigOptionName {
     Publication,
     /// Columns whose types you want to unconditionally format as text
     TextColumns,
+    /// Tables whose contents are retracted when they are truncated upstream
+    RetractOnTruncate,
+    /// Tables whose changes are applied by primary key
+    UpsertTables,
+    /// Columns to ingest from tables that are only partially ingested
+    IncludeColumns,
+    /// Predicates that rows must satisfy to be ingested, keyed by table
+    RowFilters,
 }
 
 impl AstDisplay for PgConfigOptionName {
@@ -974,6 +1034,10 @@ impl AstDisplay for PgConfigOptionName {
             PgConfigOptionName::Details => "DETAILS",
             PgConfigOptionName::Publication => "PUBLICATION",
             PgConfigOptionName::TextColumns => "TEXT COLUMNS",
+            PgConfigOptionName::RetractOnTruncate => "RETRACT ON TRUNCATE",
+            PgConfigOptionName::UpsertTables => "UPSERT TABLES",
+            PgConfigOptionName::IncludeColumns => "INCLUDE COLUMNS",
+            PgConfigOptionName::RowFilters => "ROW FILTERS",
         })
     }
 }
@@ -989,7 +1053,12 @@ impl WithOptionName for PgConfigOptionName {
         match self {
             PgConfigOptionName::Details
             | PgConfigOptionName::Publication
-            | PgConfigOptionName::TextColumns => false,
+            | PgConfigOptionName::TextColumns
+            | PgConfigOptionName::RetractOnTruncate
+            | PgConfigOptionName::UpsertTables
+            | PgConfigOptionName::IncludeColumns => false,
+            // Row filters may compare columns to sensitive values.
+            PgConfigOptionName::RowFilters => true,
         }
     }
 }
@@ -1012,6 +1081,8 @@ pub enum MySqlConfigOptionName {
     TextColumns,
     /// Columns you want to ignore
     IgnoreColumns,
+    /// Tables whose contents are retracted when they are truncated upstream
+    RetractOnTruncate,
 }
 
 impl AstDisplay for MySqlConfigOptionName {
@@ -1020,6 +1091,7 @@ impl AstDisplay for MySqlConfigOptionName {
             MySqlConfigOptionName::Details => "DETAILS",
             MySqlConfigOptionName::TextColumns => "TEXT COLUMNS",
             MySqlConfigOptionName::IgnoreColumns => "IGNORE COLUMNS",
+            MySqlConfigOptionName::RetractOnTruncate => "RETRACT ON TRUNCATE",
         })
     }
 }
@@ -1035,7 +1107,8 @@ impl WithOptionName for MySqlConfigOptionName {
         match self {
             MySqlConfigOptionName::Details
             | MySqlConfigOptionName::TextColumns
-            | MySqlConfigOptionName::IgnoreColumns => false,
+            | MySqlConfigOptionName::IgnoreColumns
+            | MySqlConfigOptionName::RetractOnTruncate => false,
         }
     }
 }
@@ -1049,6 +1122,131 @@ pub struct MySqlConfigOption<T: AstInfo> {
 impl_display_for_with_option!(MySqlConfigOption);
 impl_display_t!(MySqlConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SqlServerConfigOptionName {
+    /// Hex encoded string of binary serialization of
+    /// `mz_storage_types::sources::sql_server::SqlServerSourceDetails`
+    Details,
+}
+
+impl AstDisplay for SqlServerConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqlServerConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(SqlServerConfigOptionName);
+
+impl WithOptionName for SqlServerConfigOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            SqlServerConfigOptionName::Details => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM SQL SERVER CONNECTION ...` clause.
+pub struct SqlServerConfigOption<T: AstInfo> {
+    pub name: SqlServerConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(SqlServerConfigOption);
+impl_display_t!(SqlServerConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FileSourceOptionName {
+    /// The S3 bucket to read objects from.
+    Bucket,
+    /// The local directory to read files from.
+    Path,
+    /// Only objects whose key begins with this prefix are read.
+    Prefix,
+    /// Hex encoded string of binary serialization of
+    /// `mz_storage_types::sources::file::FileSourceDetails`
+    Details,
+}
+
+impl AstDisplay for FileSourceOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            FileSourceOptionName::Bucket => "BUCKET",
+            FileSourceOptionName::Path => "PATH",
+            FileSourceOptionName::Prefix => "PREFIX",
+            FileSourceOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(FileSourceOptionName);
+
+impl WithOptionName for FileSourceOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            FileSourceOptionName::Bucket
+            | FileSourceOptionName::Path
+            | FileSourceOptionName::Prefix
+            | FileSourceOptionName::Details => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM {S3 CONNECTION | LOCAL FILES} ...` clause.
+pub struct FileSourceOption<T: AstInfo> {
+    pub name: FileSourceOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(FileSourceOption);
+impl_display_t!(FileSourceOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KinesisSourceOptionName {
+    /// The name of the Kinesis data stream to read records from.
+    Stream,
+}
+
+impl AstDisplay for KinesisSourceOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            KinesisSourceOptionName::Stream => "STREAM",
+        })
+    }
+}
+impl_display!(KinesisSourceOptionName);
+
+impl WithOptionName for KinesisSourceOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            KinesisSourceOptionName::Stream => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM KINESIS CONNECTION ...` clause.
+pub struct KinesisSourceOption<T: AstInfo> {
+    pub name: KinesisSourceOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(KinesisSourceOption);
+impl_display_t!(KinesisSourceOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceConnection<T: AstInfo> {
     Kafka {
@@ -1067,6 +1265,21 @@ pub enum CreateSourceConnection<T: AstInfo> {
         generator: LoadGenerator,
         options: Vec<LoadGeneratorOption<T>>,
     },
+    S3 {
+        connection: T::ItemName,
+        options: Vec<FileSourceOption<T>>,
+    },
+    LocalFiles {
+        options: Vec<FileSourceOption<T>>,
+    },
+    Kinesis {
+        connection: T::ItemName,
+        options: Vec<KinesisSourceOption<T>>,
+    },
+    SqlServer {
+        connection: T::ItemName,
+        options: Vec<SqlServerConfigOption<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
@@ -1117,6 +1330,50 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::S3 {
+                connection,
+                options,
+            } => {
+                f.write_str("S3 CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::LocalFiles { options } => {
+                f.write_str("LOCAL FILES");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::Kinesis {
+                connection,
+                options,
+            } => {
+                f.write_str("KINESIS CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::SqlServer {
+                connection,
+                options,
+            } => {
+                f.write_str("SQL SERVER CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
@@ -1370,6 +1627,7 @@ pub enum CreateSourceOptionName {
     Timeline,
     TimestampInterval,
     RetainHistory,
+    OnDecodeError,
 }
 
 impl AstDisplay for CreateSourceOptionName {
@@ -1379,6 +1637,7 @@ impl AstDisplay for CreateSourceOptionName {
             CreateSourceOptionName::Timeline => "TIMELINE",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
             CreateSourceOptionName::RetainHistory => "RETAIN HISTORY",
+            CreateSourceOptionName::OnDecodeError => "ON DECODE ERROR",
         })
     }
 }
@@ -1395,7 +1654,8 @@ impl WithOptionName for CreateSourceOptionName {
             CreateSourceOptionName::IgnoreKeys
             | CreateSourceOptionName::Timeline
             | CreateSourceOptionName::TimestampInterval
-            | CreateSourceOptionName::RetainHistory => false,
+            | CreateSourceOptionName::RetainHistory
+            | CreateSourceOptionName::OnDecodeError => false,
         }
     }
 }
@@ -1487,6 +1747,13 @@ pub enum ColumnOption<T: AstInfo> {
     },
     // `CHECK (<expr>)`
     Check(Expr<T>),
+    /// `GENERATED ALWAYS AS (<expr>) STORED`
+    Generated(Expr<T>),
+    /// `GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY [ ( <sequence options> ) ]`
+    Identity {
+        always: bool,
+        options: Vec<SequenceOption<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for ColumnOption<T> {
@@ -1521,11 +1788,83 @@ impl<T: AstInfo> AstDisplay for ColumnOption<T> {
                 f.write_node(expr);
                 f.write_str(")");
             }
+            Generated(expr) => {
+                f.write_str("GENERATED ALWAYS AS (");
+                f.write_node(expr);
+                f.write_str(") STORED");
+            }
+            Identity { always, options } => {
+                if *always {
+                    f.write_str("GENERATED ALWAYS AS IDENTITY");
+                } else {
+                    f.write_str("GENERATED BY DEFAULT AS IDENTITY");
+                }
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::separated(options, " "));
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
 impl_display_t!(ColumnOption);
 
+/// An option of a `CREATE SEQUENCE` statement or of an identity column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceOption<T: AstInfo> {
+    /// `AS <data_type>`
+    As(T::DataType),
+    /// `INCREMENT [BY] <n>`
+    IncrementBy(i64),
+    /// `MINVALUE <n>` or `NO MINVALUE`
+    MinValue(Option<i64>),
+    /// `MAXVALUE <n>` or `NO MAXVALUE`
+    MaxValue(Option<i64>),
+    /// `START [WITH] <n>`
+    StartWith(i64),
+    /// `CACHE <n>`
+    Cache(u64),
+    /// `CYCLE` or `NO CYCLE`
+    Cycle(bool),
+}
+
+impl<T: AstInfo> AstDisplay for SequenceOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            SequenceOption::As(data_type) => {
+                f.write_str("AS ");
+                f.write_node(data_type);
+            }
+            SequenceOption::IncrementBy(n) => {
+                f.write_str("INCREMENT BY ");
+                f.write_str(n);
+            }
+            SequenceOption::MinValue(Some(n)) => {
+                f.write_str("MINVALUE ");
+                f.write_str(n);
+            }
+            SequenceOption::MinValue(None) => f.write_str("NO MINVALUE"),
+            SequenceOption::MaxValue(Some(n)) => {
+                f.write_str("MAXVALUE ");
+                f.write_str(n);
+            }
+            SequenceOption::MaxValue(None) => f.write_str("NO MAXVALUE"),
+            SequenceOption::StartWith(n) => {
+                f.write_str("START WITH ");
+                f.write_str(n);
+            }
+            SequenceOption::Cache(n) => {
+                f.write_str("CACHE ");
+                f.write_str(n);
+            }
+            SequenceOption::Cycle(true) => f.write_str("CYCLE"),
+            SequenceOption::Cycle(false) => f.write_str("NO CYCLE"),
+        }
+    }
+}
+impl_display_t!(SequenceOption);
+
 fn display_constraint_name<'a>(name: &'a Option<Ident>) -> impl AstDisplay + 'a {
     struct ConstraintName<'a>(&'a Option<Ident>);
     impl<'a> AstDisplay for ConstraintName<'a> {
@@ -27,11 +27,11 @@ use smallvec::{smallvec, SmallVec};
 use crate::ast::display::{self, AstDisplay, AstFormatter, WithOptionName};
 use crate::ast::{
     AstInfo, ColumnDef, ConnectionOption, ConnectionOptionName, CreateConnectionOption,
-    CreateConnectionType, CreateSinkConnection, CreateSourceConnection, CreateSourceFormat,
-    CreateSourceOption, CreateSourceOptionName, DeferredItemName, Expr, Format, Ident,
-    IntervalValue, KeyConstraint, MaterializedViewOption, Query, SelectItem, SinkEnvelope,
+    CreateConnectionType, CreateSinkConnection, CreateSourceConnection, CreateSourceOption,
+    CreateSourceOptionName, DeferredItemName, Expr, Format, FormatSpecifier, Ident, IntervalValue,
+    KeyConstraint, MaterializedViewOption, Query, SelectItem, SequenceOption, SinkEnvelope,
     SourceEnvelope, SourceIncludeMetadata, SubscribeOutput, TableAlias, TableConstraint,
-    TableWithJoins, UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName,
+    TableFactor, TableWithJoins, UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName,
     UnresolvedSchemaName, Value,
 };
 
@@ -45,6 +45,7 @@ pub enum Statement<T: AstInfo> {
     Copy(CopyStatement<T>),
     Update(UpdateStatement<T>),
     Delete(DeleteStatement<T>),
+    Merge(MergeStatement<T>),
     CreateConnection(CreateConnectionStatement<T>),
     CreateDatabase(CreateDatabaseStatement),
     CreateSchema(CreateSchemaStatement),
@@ -61,6 +62,7 @@ pub enum Statement<T: AstInfo> {
     CreateCluster(CreateClusterStatement<T>),
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
+    CreateSequence(CreateSequenceStatement<T>),
     AlterCluster(AlterClusterStatement<T>),
     AlterOwner(AlterOwnerStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
@@ -116,6 +118,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Copy(stmt) => f.write_node(stmt),
             Statement::Update(stmt) => f.write_node(stmt),
             Statement::Delete(stmt) => f.write_node(stmt),
+            Statement::Merge(stmt) => f.write_node(stmt),
             Statement::CreateConnection(stmt) => f.write_node(stmt),
             Statement::CreateDatabase(stmt) => f.write_node(stmt),
             Statement::CreateSchema(stmt) => f.write_node(stmt),
@@ -129,6 +132,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateIndex(stmt) => f.write_node(stmt),
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateSecret(stmt) => f.write_node(stmt),
+            Statement::CreateSequence(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
@@ -190,6 +194,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Copy => "copy",
         StatementKind::Update => "update",
         StatementKind::Delete => "delete",
+        StatementKind::Merge => "merge",
         StatementKind::CreateConnection => "create_connection",
         StatementKind::CreateDatabase => "create_database",
         StatementKind::CreateSchema => "create_schema",
@@ -206,6 +211,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::CreateCluster => "create_cluster",
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
+        StatementKind::CreateSequence => "create_sequence",
         StatementKind::AlterCluster => "alter_cluster",
         StatementKind::AlterObjectRename => "alter_object_rename",
         StatementKind::AlterRetainHistory => "alter_retain_history",
@@ -281,6 +287,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// ON CONFLICT
+    pub on_conflict: Option<OnConflict<T>>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
 }
@@ -296,6 +304,10 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
         if !self.returning.is_empty() {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
@@ -304,6 +316,59 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
 }
 impl_display_t!(InsertStatement);
 
+/// `ON CONFLICT` clause of an `INSERT`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns of the unique constraint whose conflicts are handled. If
+    /// empty, conflicts with any unique constraint are handled.
+    pub target: Vec<Ident>,
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT ");
+        if !self.target.is_empty() {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(&self.target));
+            f.write_str(") ");
+        }
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET ... [WHERE ...]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("DO UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CopyRelation<T: AstInfo> {
     Named {
@@ -516,6 +581,114 @@ impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
 }
 impl_display_t!(DeleteStatement);
 
+/// `MERGE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeStatement<T: AstInfo> {
+    /// `INTO`
+    pub table_name: T::ItemName,
+    /// `AS`
+    pub alias: Option<TableAlias>,
+    /// `USING`
+    pub source: TableFactor<T>,
+    /// `ON`
+    pub on: Expr<T>,
+    /// `WHEN [NOT] MATCHED`
+    pub clauses: Vec<MergeClause<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("MERGE INTO ");
+        f.write_node(&self.table_name);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+        f.write_str(" USING ");
+        f.write_node(&self.source);
+        f.write_str(" ON ");
+        f.write_node(&self.on);
+        for clause in &self.clauses {
+            f.write_str(" ");
+            f.write_node(clause);
+        }
+    }
+}
+impl_display_t!(MergeStatement);
+
+/// A `WHEN [NOT] MATCHED [AND condition] THEN action` clause of a `MERGE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeClause<T: AstInfo> {
+    /// Whether the clause applies to source rows that match a target row.
+    pub matched: bool,
+    /// `AND`
+    pub condition: Option<Expr<T>>,
+    /// `THEN`
+    pub action: MergeAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeClause<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("WHEN ");
+        if !self.matched {
+            f.write_str("NOT ");
+        }
+        f.write_str("MATCHED");
+        if let Some(condition) = &self.condition {
+            f.write_str(" AND ");
+            f.write_node(condition);
+        }
+        f.write_str(" THEN ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(MergeClause);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MergeAction<T: AstInfo> {
+    /// `UPDATE SET ...`
+    Update { assignments: Vec<Assignment<T>> },
+    /// `DELETE`
+    Delete,
+    /// `INSERT [(columns)] VALUES (...)`, or `INSERT [(columns)] DEFAULT
+    /// VALUES` if `values` is empty.
+    Insert {
+        columns: Vec<Ident>,
+        values: Vec<Expr<T>>,
+    },
+    /// `DO NOTHING`
+    DoNothing,
+}
+
+impl<T: AstInfo> AstDisplay for MergeAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            MergeAction::Update { assignments } => {
+                f.write_str("UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+            }
+            MergeAction::Delete => f.write_str("DELETE"),
+            MergeAction::Insert { columns, values } => {
+                f.write_str("INSERT");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+                if values.is_empty() {
+                    f.write_str(" DEFAULT VALUES");
+                } else {
+                    f.write_str(" VALUES (");
+                    f.write_node(&display::comma_separated(values));
+                    f.write_str(")");
+                }
+            }
+            MergeAction::DoNothing => f.write_str("DO NOTHING"),
+        }
+    }
+}
+impl_display_t!(MergeAction);
+
 /// `CREATE DATABASE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateDatabaseStatement {
@@ -968,12 +1141,12 @@ pub struct CreateSourceStatement<T: AstInfo> {
     pub col_names: Vec<Ident>,
     pub connection: CreateSourceConnection<T>,
     pub include_metadata: Vec<SourceIncludeMetadata>,
-    pub format: Option<CreateSourceFormat<T>>,
+    pub format: Option<FormatSpecifier<T>>,
     pub envelope: Option<SourceEnvelope>,
     pub if_not_exists: bool,
     pub key_constraint: Option<KeyConstraint>,
     pub with_options: Vec<CreateSourceOption<T>>,
-    pub referenced_subsources: Option<ReferencedSubsources>,
+    pub referenced_subsources: Option<ReferencedSubsources<T>>,
     pub progress_subsource: Option<DeferredItemName<T>>,
 }
 
@@ -1037,33 +1210,46 @@ impl_display_t!(CreateSourceStatement);
 
 /// A selected subsource in a FOR TABLES (..) statement
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct CreateSourceSubsource {
+pub struct CreateSourceSubsource<T: AstInfo> {
     pub reference: UnresolvedItemName,
     pub subsource: Option<UnresolvedItemName>,
+    /// The upstream columns to ingest; empty if all columns are ingested.
+    pub columns: Vec<Ident>,
+    /// The predicate that upstream rows must satisfy to be ingested.
+    pub filter: Option<Expr<T>>,
 }
 
-impl AstDisplay for CreateSourceSubsource {
+impl<T: AstInfo> AstDisplay for CreateSourceSubsource<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_node(&self.reference);
+        if !self.columns.is_empty() {
+            f.write_str(" (");
+            f.write_node(&display::comma_separated(&self.columns));
+            f.write_str(")");
+        }
         if let Some(subsource) = &self.subsource {
             f.write_str(" AS ");
             f.write_node(subsource);
         }
+        if let Some(filter) = &self.filter {
+            f.write_str(" WHERE ");
+            f.write_node(filter);
+        }
     }
 }
-impl_display!(CreateSourceSubsource);
+impl_display_t!(CreateSourceSubsource);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum ReferencedSubsources {
+pub enum ReferencedSubsources<T: AstInfo> {
     /// A subset defined with FOR TABLES (...)
-    SubsetTables(Vec<CreateSourceSubsource>),
+    SubsetTables(Vec<CreateSourceSubsource<T>>),
     /// A subset defined with FOR SCHEMAS (...)
     SubsetSchemas(Vec<Ident>),
     /// FOR ALL TABLES
     All,
 }
 
-impl AstDisplay for ReferencedSubsources {
+impl<T: AstInfo> AstDisplay for ReferencedSubsources<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             Self::SubsetTables(subsources) => {
@@ -1080,7 +1266,7 @@ impl AstDisplay for ReferencedSubsources {
         }
     }
 }
-impl_display!(ReferencedSubsources);
+impl_display_t!(ReferencedSubsources);
 
 /// An option in a `CREATE SUBSOURCE` statement.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -1212,7 +1398,7 @@ pub struct CreateSinkStatement<T: AstInfo> {
     pub if_not_exists: bool,
     pub from: T::ItemName,
     pub connection: CreateSinkConnection<T>,
-    pub format: Option<Format<T>>,
+    pub format: Option<FormatSpecifier<T>>,
     pub envelope: Option<SinkEnvelope>,
     pub with_options: Vec<CreateSinkOption<T>>,
 }
@@ -1237,7 +1423,6 @@ impl<T: AstInfo> AstDisplay for CreateSinkStatement<T> {
         f.write_str(" INTO ");
         f.write_node(&self.connection);
         if let Some(format) = &self.format {
-            f.write_str(" FORMAT ");
             f.write_node(format);
         }
         if let Some(envelope) = &self.envelope {
@@ -1683,6 +1868,29 @@ impl<T: AstInfo> AstDisplay for CreateSecretStatement<T> {
 }
 impl_display_t!(CreateSecretStatement);
 
+/// A `CREATE SEQUENCE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateSequenceStatement<T: AstInfo> {
+    pub name: UnresolvedItemName,
+    pub if_not_exists: bool,
+    pub options: Vec<SequenceOption<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateSequenceStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE SEQUENCE ");
+        if self.if_not_exists {
+            f.write_str("IF NOT EXISTS ");
+        }
+        f.write_node(&self.name);
+        for option in &self.options {
+            f.write_str(" ");
+            f.write_node(option);
+        }
+    }
+}
+impl_display_t!(CreateSequenceStatement);
+
 /// `CREATE TYPE ..`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateTypeStatement<T: AstInfo> {
@@ -2335,7 +2543,7 @@ pub enum AlterSourceAction<T: AstInfo> {
     SetOptions(Vec<CreateSourceOption<T>>),
     ResetOptions(Vec<CreateSourceOptionName>),
     AddSubsources {
-        subsources: Vec<CreateSourceSubsource>,
+        subsources: Vec<CreateSourceSubsource<T>>,
         options: Vec<AlterSourceAddSubsourceOption<T>>,
     },
     DropSubsources {
@@ -2343,7 +2551,34 @@ pub enum AlterSourceAction<T: AstInfo> {
         cascade: bool,
         names: Vec<UnresolvedItemName>,
     },
+    /// Evolves the schema of the subsources to include the additive changes
+    /// that were made to their upstream tables.
+    RefreshSchema,
+    /// Restarts the ingestion of the source from a new upstream position, in a
+    /// new ingestion epoch.
+    ResetTo {
+        position: SourceResetPosition,
+        value: WithOptionValue<T>,
+    },
+}
+
+/// The kind of upstream position that `ALTER SOURCE ... RESET TO` moves a
+/// source to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceResetPosition {
+    Offset,
+    Timestamp,
+}
+
+impl AstDisplay for SourceResetPosition {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SourceResetPosition::Offset => "OFFSET",
+            SourceResetPosition::Timestamp => "TIMESTAMP",
+        })
+    }
 }
+impl_display!(SourceResetPosition);
 
 impl<T: AstInfo> AstDisplay for AlterSourceAction<T> {
     fn fmt<W>(&self, f: &mut AstFormatter<W>)
@@ -2391,6 +2626,13 @@ impl<T: AstInfo> AstDisplay for AlterSourceAction<T> {
                     f.write_str(")");
                 }
             }
+            AlterSourceAction::RefreshSchema => f.write_str("REFRESH SCHEMA"),
+            AlterSourceAction::ResetTo { position, value } => {
+                f.write_str("RESET TO ");
+                f.write_node(position);
+                f.write_str(" ");
+                f.write_node(value);
+            }
         }
     }
 }
@@ -3380,6 +3622,7 @@ pub enum ObjectType {
     Schema,
     Func,
     Subsource,
+    Sequence,
 }
 
 impl ObjectType {
@@ -3395,7 +3638,8 @@ impl ObjectType {
             | ObjectType::Secret
             | ObjectType::Connection
             | ObjectType::Func
-            | ObjectType::Subsource => true,
+            | ObjectType::Subsource
+            | ObjectType::Sequence => true,
             ObjectType::Database
             | ObjectType::Schema
             | ObjectType::Cluster
@@ -3424,6 +3668,7 @@ impl AstDisplay for ObjectType {
             ObjectType::Schema => "SCHEMA",
             ObjectType::Func => "FUNCTION",
             ObjectType::Subsource => "SUBSOURCE",
+            ObjectType::Sequence => "SEQUENCE",
         })
     }
 }
@@ -3477,6 +3722,7 @@ pub enum WithOptionValue<T: AstInfo> {
     Item(T::ItemName),
     UnresolvedItemName(UnresolvedItemName),
     Sequence(Vec<WithOptionValue<T>>),
+    Expr(Expr<T>),
     // Special cases.
     ClusterReplicas(Vec<ReplicaDefinition<T>>),
     ConnectionKafkaBroker(KafkaBroker<T>),
@@ -3494,6 +3740,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
             match self {
                 WithOptionValue::Value(_)
                 | WithOptionValue::Sequence(_)
+                | WithOptionValue::Expr(_)
                 | WithOptionValue::RetainHistoryFor(_)
                 | WithOptionValue::Refresh(_) => {
                     // These are redact-aware.
@@ -3519,6 +3766,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_str(")");
             }
             WithOptionValue::Value(value) => f.write_node(value),
+            WithOptionValue::Expr(expr) => f.write_node(expr),
             WithOptionValue::DataType(typ) => f.write_node(typ),
             WithOptionValue::Secret(name) => {
                 f.write_str("SECRET ");
@@ -178,6 +178,22 @@ pub fn parse_data_type(sql: &str) -> Result<RawDataType, ParserError> {
     }
 }
 
+/// Parses a SQL string containing a single item name.
+pub fn parse_item_name(sql: &str) -> Result<UnresolvedItemName, ParserError> {
+    let tokens = lexer::lex(sql)?;
+    let mut parser = Parser::new(sql, tokens);
+    let name = parser.parse_item_name()?;
+    if parser.next_token().is_some() {
+        parser_err!(
+            parser,
+            parser.peek_prev_pos(),
+            "extra token after item name"
+        )
+    } else {
+        Ok(name)
+    }
+}
+
 /// Parses a string containing a comma-separated list of identifiers and
 /// returns their underlying string values.
 ///
@@ -198,6 +214,27 @@ pub fn split_identifier_string(s: &str) -> Result<Vec<String>, ParserError> {
     }
 }
 
+/// Returns the integer type that the serial type `data_type` stands for, if it
+/// is one.
+fn serial_type_integer(data_type: &RawDataType) -> Option<Ident> {
+    let RawDataType::Other {
+        name: RawItemName::Name(UnresolvedItemName(name)),
+        typ_mod,
+    } = data_type
+    else {
+        return None;
+    };
+    match (name.as_slice(), typ_mod.is_empty()) {
+        ([name], true) => match name.as_str() {
+            "smallserial" | "serial2" => Some(ident!("int2")),
+            "serial" | "serial4" => Some(ident!("int4")),
+            "bigserial" | "serial8" => Some(ident!("int8")),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 macro_rules! maybe {
     ($e:expr) => {{
         if let Some(v) = $e {
@@ -396,6 +433,9 @@ impl<'a> Parser<'a> {
                 Token::Keyword(UPDATE) => {
                     Ok(self.parse_update().map_parser_err(StatementKind::Update)?)
                 }
+                Token::Keyword(MERGE) => {
+                    Ok(self.parse_merge().map_parser_err(StatementKind::Merge)?)
+                }
                 Token::Keyword(ALTER) => Ok(self.parse_alter()?),
                 Token::Keyword(COPY) => Ok(self.parse_copy()?),
                 Token::Keyword(SET) => Ok(self.parse_set()?),
@@ -1857,6 +1897,9 @@ impl<'a> Parser<'a> {
         } else if self.peek_keyword(SECRET) {
             self.parse_create_secret()
                 .map_parser_err(StatementKind::CreateSecret)
+        } else if self.peek_keyword(SEQUENCE) {
+            self.parse_create_sequence()
+                .map_parser_err(StatementKind::CreateSequence)
         } else if self.peek_keyword(CONNECTION) {
             self.parse_create_connection()
                 .map_parser_err(StatementKind::CreateConnection)
@@ -1912,6 +1955,23 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parses `FORMAT ..` or `KEY FORMAT .. VALUE FORMAT ..`, if present.
+    fn parse_format_specifier(&mut self) -> Result<Option<FormatSpecifier<Raw>>, ParserError> {
+        let format = match self.parse_one_of_keywords(&[KEY, FORMAT]) {
+            Some(KEY) => {
+                self.expect_keyword(FORMAT)?;
+                let key = self.parse_format()?;
+                self.expect_keywords(&[VALUE, FORMAT])?;
+                let value = self.parse_format()?;
+                Some(FormatSpecifier::KeyValue { key, value })
+            }
+            Some(FORMAT) => Some(FormatSpecifier::Bare(self.parse_format()?)),
+            Some(_) => unreachable!("parse_one_of_keywords returns None for this"),
+            None => None,
+        };
+        Ok(format)
+    }
+
     fn parse_format(&mut self) -> Result<Format<Raw>, ParserError> {
         let format = if self.parse_keyword(AVRO) {
             self.expect_keyword(USING)?;
@@ -1944,15 +2004,27 @@ impl<'a> Parser<'a> {
             Format::Csv { columns, delimiter }
         } else if self.parse_keyword(JSON) {
             let array = self.parse_keyword(ARRAY);
-            Format::Json { array }
+            let lines = !array && self.parse_keyword(LINES);
+            let csr_connection = if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
+                Some(self.parse_csr_connection_reference()?)
+            } else {
+                None
+            };
+            Format::Json {
+                array,
+                lines,
+                csr_connection,
+            }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
             Format::Bytes
+        } else if self.parse_keyword(PARQUET) {
+            Format::Parquet
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, BYTES, or PARQUET",
                 self.peek_token(),
             );
         };
@@ -2041,7 +2113,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_csr_config_option(&mut self) -> Result<CsrConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[AVRO, NULL, KEY, VALUE, DOC])? {
+        let name = match self.expect_one_of_keywords(&[AVRO, JSON, NULL, KEY, VALUE, DOC])? {
             AVRO => {
                 let name = match self.expect_one_of_keywords(&[KEY, VALUE])? {
                     KEY => CsrConfigOptionName::AvroKeyFullname,
@@ -2051,6 +2123,15 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(FULLNAME)?;
                 name
             }
+            JSON => {
+                let name = match self.expect_one_of_keywords(&[KEY, VALUE])? {
+                    KEY => CsrConfigOptionName::JsonKeySchema,
+                    VALUE => CsrConfigOptionName::JsonValueSchema,
+                    _ => unreachable!(),
+                };
+                self.expect_keyword(SCHEMA)?;
+                name
+            }
             NULL => {
                 self.expect_keyword(DEFAULTS)?;
                 CsrConfigOptionName::NullDefaults
@@ -2230,28 +2311,33 @@ impl<'a> Parser<'a> {
             TO => true,
             _ => unreachable!(),
         };
-        let connection_type =
-            match self.expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH, MYSQL])? {
-                AWS => {
-                    if self.parse_keyword(PRIVATELINK) {
-                        CreateConnectionType::AwsPrivatelink
-                    } else {
-                        CreateConnectionType::Aws
-                    }
-                }
-                KAFKA => CreateConnectionType::Kafka,
-                CONFLUENT => {
-                    self.expect_keywords(&[SCHEMA, REGISTRY])?;
-                    CreateConnectionType::Csr
-                }
-                POSTGRES => CreateConnectionType::Postgres,
-                SSH => {
-                    self.expect_keyword(TUNNEL)?;
-                    CreateConnectionType::Ssh
+        let connection_type = match self
+            .expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH, MYSQL, SQL])?
+        {
+            AWS => {
+                if self.parse_keyword(PRIVATELINK) {
+                    CreateConnectionType::AwsPrivatelink
+                } else {
+                    CreateConnectionType::Aws
                 }
-                MYSQL => CreateConnectionType::MySql,
-                _ => unreachable!(),
-            };
+            }
+            KAFKA => CreateConnectionType::Kafka,
+            CONFLUENT => {
+                self.expect_keywords(&[SCHEMA, REGISTRY])?;
+                CreateConnectionType::Csr
+            }
+            POSTGRES => CreateConnectionType::Postgres,
+            SSH => {
+                self.expect_keyword(TUNNEL)?;
+                CreateConnectionType::Ssh
+            }
+            MYSQL => CreateConnectionType::MySql,
+            SQL => {
+                self.expect_keyword(SERVER)?;
+                CreateConnectionType::SqlServer
+            }
+            _ => unreachable!(),
+        };
         if expect_paren {
             self.expect_token(&Token::LParen)?;
         }
@@ -2379,15 +2465,18 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[ID, PREFIX])?;
                 KafkaSourceConfigOptionName::GroupIdPrefix
             }
-            START => match self.expect_one_of_keywords(&[OFFSET, TIMESTAMP])? {
+            START => match self.expect_one_of_keywords(&[OFFSET, TIMESTAMP, EPOCH])? {
                 OFFSET => KafkaSourceConfigOptionName::StartOffset,
                 TIMESTAMP => KafkaSourceConfigOptionName::StartTimestamp,
+                EPOCH => KafkaSourceConfigOptionName::StartEpoch,
                 _ => unreachable!(),
             },
             TOPIC => {
                 if self.parse_keyword(METADATA) {
                     self.expect_keywords(&[REFRESH, INTERVAL])?;
                     KafkaSourceConfigOptionName::TopicMetadataRefreshInterval
+                } else if self.parse_keyword(PATTERN) {
+                    KafkaSourceConfigOptionName::TopicPattern
                 } else {
                     KafkaSourceConfigOptionName::Topic
                 }
@@ -2405,7 +2494,9 @@ impl<'a> Parser<'a> {
     ) -> Result<KafkaSinkConfigOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[
             COMPRESSION,
+            PARTITION,
             PROGRESS,
+            TOMBSTONES,
             TOPIC,
             LEGACY,
             TRANSACTIONAL,
@@ -2414,10 +2505,19 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(TYPE)?;
                 KafkaSinkConfigOptionName::CompressionType
             }
+            PARTITION => {
+                self.expect_keyword(BY)?;
+                let _ = self.consume_token(&Token::Eq);
+                return Ok(KafkaSinkConfigOption {
+                    name: KafkaSinkConfigOptionName::PartitionBy,
+                    value: Some(WithOptionValue::Expr(self.parse_expr()?)),
+                });
+            }
             PROGRESS => {
                 self.expect_keywords(&[GROUP, ID, PREFIX])?;
                 KafkaSinkConfigOptionName::ProgressGroupIdPrefix
             }
+            TOMBSTONES => KafkaSinkConfigOptionName::Tombstones,
             TOPIC => KafkaSinkConfigOptionName::Topic,
             TRANSACTIONAL => {
                 self.expect_keywords(&[ID, PREFIX])?;
@@ -2657,18 +2757,7 @@ impl<'a> Parser<'a> {
         }
 
         let connection = self.parse_create_source_connection()?;
-        let format = match self.parse_one_of_keywords(&[KEY, FORMAT]) {
-            Some(KEY) => {
-                self.expect_keyword(FORMAT)?;
-                let key = self.parse_format()?;
-                self.expect_keywords(&[VALUE, FORMAT])?;
-                let value = self.parse_format()?;
-                Some(CreateSourceFormat::KeyValue { key, value })
-            }
-            Some(FORMAT) => Some(CreateSourceFormat::Bare(self.parse_format()?)),
-            Some(_) => unreachable!("parse_one_of_keywords returns None for this"),
-            None => None,
-        };
+        let format = self.parse_format_specifier()?;
         let include_metadata = self.parse_source_include_metadata()?;
 
         let envelope = if self.parse_keyword(ENVELOPE) {
@@ -2725,17 +2814,25 @@ impl<'a> Parser<'a> {
         }))
     }
 
-    fn parse_subsource_references(&mut self) -> Result<CreateSourceSubsource, ParserError> {
+    fn parse_subsource_references(&mut self) -> Result<CreateSourceSubsource<Raw>, ParserError> {
         let reference = self.parse_item_name()?;
+        let columns = self.parse_parenthesized_column_list(Optional)?;
         let subsource = if self.parse_one_of_keywords(&[AS, INTO]).is_some() {
             Some(self.parse_item_name()?)
         } else {
             None
         };
+        let filter = if self.parse_keyword(WHERE) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
 
         Ok(CreateSourceSubsource {
             reference,
             subsource,
+            columns,
+            filter,
         })
     }
 
@@ -2782,7 +2879,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[IGNORE, TIMELINE, TIMESTAMP, RETAIN])? {
+        let name = match self.expect_one_of_keywords(&[IGNORE, TIMELINE, TIMESTAMP, RETAIN, ON])? {
             IGNORE => {
                 self.expect_keyword(KEYS)?;
                 CreateSourceOptionName::IgnoreKeys
@@ -2796,6 +2893,10 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(HISTORY)?;
                 CreateSourceOptionName::RetainHistory
             }
+            ON => {
+                self.expect_keywords(&[DECODE, ERROR])?;
+                CreateSourceOptionName::OnDecodeError
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -2833,7 +2934,11 @@ impl<'a> Parser<'a> {
         let body_format = match self.expect_one_of_keywords(&[JSON, TEXT, BYTES])? {
             JSON => {
                 let array = self.parse_keyword(ARRAY);
-                Format::Json { array }
+                Format::Json {
+                    array,
+                    lines: false,
+                    csr_connection: None,
+                }
             }
             TEXT => Format::Text,
             BYTES => Format::Bytes,
@@ -2986,11 +3091,7 @@ impl<'a> Parser<'a> {
         let from = self.parse_raw_name()?;
         self.expect_keyword(INTO)?;
         let connection = self.parse_create_sink_connection()?;
-        let format = if self.parse_keyword(FORMAT) {
-            Some(self.parse_format()?)
-        } else {
-            None
-        };
+        let format = self.parse_format_specifier()?;
         let envelope = if self.parse_keyword(ENVELOPE) {
             Some(self.parse_sink_envelope()?)
         } else {
@@ -3038,7 +3139,9 @@ impl<'a> Parser<'a> {
     fn parse_create_source_connection(
         &mut self,
     ) -> Result<CreateSourceConnection<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, POSTGRES, MYSQL, LOAD])? {
+        match self
+            .expect_one_of_keywords(&[KAFKA, POSTGRES, MYSQL, SQL, LOAD, S3, LOCAL, KINESIS])?
+        {
             POSTGRES => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -3074,6 +3177,24 @@ impl<'a> Parser<'a> {
                     options,
                 })
             }
+            SQL => {
+                self.expect_keywords(&[SERVER, CONNECTION])?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_sql_server_connection_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::SqlServer {
+                    connection,
+                    options,
+                })
+            }
             KAFKA => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -3118,14 +3239,159 @@ impl<'a> Parser<'a> {
                 };
                 Ok(CreateSourceConnection::LoadGenerator { generator, options })
             }
+            S3 => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_file_source_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::S3 {
+                    connection,
+                    options,
+                })
+            }
+            LOCAL => {
+                self.expect_keyword(FILES)?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_file_source_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::LocalFiles { options })
+            }
+            KINESIS => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_kinesis_source_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::Kinesis {
+                    connection,
+                    options,
+                })
+            }
             _ => unreachable!(),
         }
     }
 
+    fn parse_kinesis_source_option(&mut self) -> Result<KinesisSourceOption<Raw>, ParserError> {
+        self.expect_keyword(STREAM)?;
+        Ok(KinesisSourceOption {
+            name: KinesisSourceOptionName::Stream,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_file_source_option(&mut self) -> Result<FileSourceOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[BUCKET, DETAILS, PATH, PREFIX])? {
+            BUCKET => FileSourceOptionName::Bucket,
+            DETAILS => FileSourceOptionName::Details,
+            PATH => FileSourceOptionName::Path,
+            PREFIX => FileSourceOptionName::Prefix,
+            _ => unreachable!(),
+        };
+        Ok(FileSourceOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_pg_connection_option(&mut self) -> Result<PgConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[DETAILS, PUBLICATION, TEXT])? {
+        let name = match self.expect_one_of_keywords(&[
+            DETAILS,
+            INCLUDE,
+            PUBLICATION,
+            RETRACT,
+            ROW,
+            TEXT,
+            UPSERT,
+        ])? {
             DETAILS => PgConfigOptionName::Details,
+            INCLUDE => {
+                self.expect_keyword(COLUMNS)?;
+
+                let _ = self.consume_token(&Token::Eq);
+
+                let value = self
+                    .parse_option_sequence(Parser::parse_item_name)?
+                    .map(|inner| {
+                        WithOptionValue::Sequence(
+                            inner
+                                .into_iter()
+                                .map(WithOptionValue::UnresolvedItemName)
+                                .collect_vec(),
+                        )
+                    });
+
+                return Ok(PgConfigOption {
+                    name: PgConfigOptionName::IncludeColumns,
+                    value,
+                });
+            }
             PUBLICATION => PgConfigOptionName::Publication,
+            RETRACT => {
+                self.expect_keywords(&[ON, TRUNCATE])?;
+
+                let _ = self.consume_token(&Token::Eq);
+
+                let value = self
+                    .parse_option_sequence(Parser::parse_item_name)?
+                    .map(|inner| {
+                        WithOptionValue::Sequence(
+                            inner
+                                .into_iter()
+                                .map(WithOptionValue::UnresolvedItemName)
+                                .collect_vec(),
+                        )
+                    });
+
+                return Ok(PgConfigOption {
+                    name: PgConfigOptionName::RetractOnTruncate,
+                    value,
+                });
+            }
+            ROW => {
+                self.expect_keyword(FILTERS)?;
+
+                let _ = self.consume_token(&Token::Eq);
+
+                // Each filter is a `(table, predicate)` pair.
+                let value = self
+                    .parse_option_sequence(|parser| {
+                        parser.expect_token(&Token::LParen)?;
+                        let table = parser.parse_item_name()?;
+                        parser.expect_token(&Token::Comma)?;
+                        let filter = parser.parse_expr()?;
+                        parser.expect_token(&Token::RParen)?;
+                        Ok(WithOptionValue::Sequence(vec![
+                            WithOptionValue::UnresolvedItemName(table),
+                            WithOptionValue::Expr(filter),
+                        ]))
+                    })?
+                    .map(WithOptionValue::Sequence);
+
+                return Ok(PgConfigOption {
+                    name: PgConfigOptionName::RowFilters,
+                    value,
+                });
+            }
             TEXT => {
                 self.expect_keyword(COLUMNS)?;
 
@@ -3147,6 +3413,27 @@ impl<'a> Parser<'a> {
                     value,
                 });
             }
+            UPSERT => {
+                self.expect_keyword(TABLES)?;
+
+                let _ = self.consume_token(&Token::Eq);
+
+                let value = self
+                    .parse_option_sequence(Parser::parse_item_name)?
+                    .map(|inner| {
+                        WithOptionValue::Sequence(
+                            inner
+                                .into_iter()
+                                .map(WithOptionValue::UnresolvedItemName)
+                                .collect_vec(),
+                        )
+                    });
+
+                return Ok(PgConfigOption {
+                    name: PgConfigOptionName::UpsertTables,
+                    value,
+                });
+            }
             _ => unreachable!(),
         };
         Ok(PgConfigOption {
@@ -3156,11 +3443,32 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_mysql_connection_option(&mut self) -> Result<MySqlConfigOption<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[DETAILS, TEXT, IGNORE])? {
+        match self.expect_one_of_keywords(&[DETAILS, TEXT, IGNORE, RETRACT])? {
             DETAILS => Ok(MySqlConfigOption {
                 name: MySqlConfigOptionName::Details,
                 value: self.parse_optional_option_value()?,
             }),
+            RETRACT => {
+                self.expect_keywords(&[ON, TRUNCATE])?;
+
+                let _ = self.consume_token(&Token::Eq);
+
+                let value = self
+                    .parse_option_sequence(Parser::parse_item_name)?
+                    .map(|inner| {
+                        WithOptionValue::Sequence(
+                            inner
+                                .into_iter()
+                                .map(WithOptionValue::UnresolvedItemName)
+                                .collect_vec(),
+                        )
+                    });
+
+                Ok(MySqlConfigOption {
+                    name: MySqlConfigOptionName::RetractOnTruncate,
+                    value,
+                })
+            }
             TEXT => {
                 self.expect_keyword(COLUMNS)?;
 
@@ -3207,6 +3515,19 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_sql_server_connection_option(
+        &mut self,
+    ) -> Result<SqlServerConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DETAILS])? {
+            DETAILS => SqlServerConfigOptionName::Details,
+            _ => unreachable!(),
+        };
+        Ok(SqlServerConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[
             SCALE,
@@ -3645,6 +3966,54 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_create_sequence(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(SEQUENCE)?;
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_item_name()?;
+        let options = self.parse_sequence_options()?;
+        Ok(Statement::CreateSequence(CreateSequenceStatement {
+            name,
+            if_not_exists,
+            options,
+        }))
+    }
+
+    /// Parses the options of a `CREATE SEQUENCE` statement or an identity
+    /// column, which, as in PostgreSQL, are separated by whitespace.
+    fn parse_sequence_options(&mut self) -> Result<Vec<SequenceOption<Raw>>, ParserError> {
+        let mut options = vec![];
+        loop {
+            let option = if self.parse_keyword(AS) {
+                SequenceOption::As(self.parse_data_type()?)
+            } else if self.parse_keyword(INCREMENT) {
+                let _ = self.parse_keyword(BY);
+                SequenceOption::IncrementBy(self.parse_literal_int()?)
+            } else if self.parse_keyword(MINVALUE) {
+                SequenceOption::MinValue(Some(self.parse_literal_int()?))
+            } else if self.parse_keyword(MAXVALUE) {
+                SequenceOption::MaxValue(Some(self.parse_literal_int()?))
+            } else if self.parse_keyword(START) {
+                let _ = self.parse_keyword(WITH);
+                SequenceOption::StartWith(self.parse_literal_int()?)
+            } else if self.parse_keyword(CACHE) {
+                SequenceOption::Cache(self.parse_literal_uint()?)
+            } else if self.parse_keyword(CYCLE) {
+                SequenceOption::Cycle(true)
+            } else if self.parse_keyword(NO) {
+                match self.expect_one_of_keywords(&[MINVALUE, MAXVALUE, CYCLE])? {
+                    MINVALUE => SequenceOption::MinValue(None),
+                    MAXVALUE => SequenceOption::MaxValue(None),
+                    CYCLE => SequenceOption::Cycle(false),
+                    _ => unreachable!(),
+                }
+            } else {
+                break;
+            };
+            options.push(option);
+        }
+        Ok(options)
+    }
+
     fn parse_create_type(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(TYPE)?;
         let name = self.parse_item_name()?;
@@ -3940,9 +4309,9 @@ impl<'a> Parser<'a> {
     fn parse_source_include_metadata(&mut self) -> Result<Vec<SourceIncludeMetadata>, ParserError> {
         if self.parse_keyword(INCLUDE) {
             self.parse_comma_separated(|parser| {
-                let metadata = match parser
-                    .expect_one_of_keywords(&[KEY, TIMESTAMP, PARTITION, OFFSET, HEADERS, HEADER])?
-                {
+                let metadata = match parser.expect_one_of_keywords(&[
+                    KEY, TIMESTAMP, PARTITION, OFFSET, TOPIC, HEADERS, HEADER,
+                ])? {
                     KEY => SourceIncludeMetadata::Key {
                         alias: parser.parse_alias()?,
                     },
@@ -3955,6 +4324,9 @@ impl<'a> Parser<'a> {
                     OFFSET => SourceIncludeMetadata::Offset {
                         alias: parser.parse_alias()?,
                     },
+                    TOPIC => SourceIncludeMetadata::Topic {
+                        alias: parser.parse_alias()?,
+                    },
                     HEADERS => SourceIncludeMetadata::Headers {
                         alias: parser.parse_alias()?,
                     },
@@ -4053,7 +4425,8 @@ impl<'a> Parser<'a> {
             | ObjectType::Index
             | ObjectType::Type
             | ObjectType::Secret
-            | ObjectType::Connection => {
+            | ObjectType::Connection
+            | ObjectType::Sequence => {
                 let names = self.parse_comma_separated(|parser| {
                     Ok(UnresolvedObjectName::Item(parser.parse_item_name()?))
                 })?;
@@ -4187,13 +4560,29 @@ impl<'a> Parser<'a> {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Some(column_name) = self.consume_identifier()? {
-                let data_type = self.parse_data_type()?;
+                let mut data_type = self.parse_data_type()?;
                 let collation = if self.parse_keyword(COLLATE) {
                     Some(self.parse_item_name()?)
                 } else {
                     None
                 };
                 let mut options = vec![];
+                // As in PostgreSQL, the serial types are not true types but
+                // shorthand for an integer column whose values are drawn from
+                // a sequence.
+                if let Some(int_type) = serial_type_integer(&data_type) {
+                    data_type = RawDataType::Other {
+                        name: RawItemName::Name(UnresolvedItemName::unqualified(int_type)),
+                        typ_mod: vec![],
+                    };
+                    options.push(ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::Identity {
+                            always: false,
+                            options: vec![],
+                        },
+                    });
+                }
                 loop {
                     match self.peek_token() {
                         None | Some(Token::Comma) | Some(Token::RParen) => break,
@@ -4259,6 +4648,34 @@ impl<'a> Parser<'a> {
             let expr = self.parse_expr()?;
             self.expect_token(&Token::RParen)?;
             ColumnOption::Check(expr)
+        } else if self.parse_keyword(GENERATED) {
+            let always = match self.expect_one_of_keywords(&[ALWAYS, BY])? {
+                ALWAYS => true,
+                BY => {
+                    self.expect_keyword(DEFAULT)?;
+                    false
+                }
+                _ => unreachable!(),
+            };
+            self.expect_keyword(AS)?;
+            if self.parse_keyword(IDENTITY) {
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_sequence_options()?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                ColumnOption::Identity { always, options }
+            } else if always {
+                self.expect_token(&Token::LParen)?;
+                let expr = self.parse_expr()?;
+                self.expect_token(&Token::RParen)?;
+                self.expect_keyword(STORED)?;
+                ColumnOption::Generated(expr)
+            } else {
+                return self.expected(self.peek_pos(), "IDENTITY", self.peek_token());
+            }
         } else {
             return self.expected(self.peek_pos(), "column option", self.peek_token());
         };
@@ -4456,6 +4873,7 @@ impl<'a> Parser<'a> {
                 }))
             }
             ObjectType::Cluster => self.parse_alter_cluster(object_type),
+            ObjectType::Sequence => self.parse_alter_sequence(),
             ObjectType::ClusterReplica => {
                 let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
                 let name = UnresolvedObjectName::ClusterReplica(
@@ -4611,7 +5029,7 @@ impl<'a> Parser<'a> {
 
         Ok(
             match self
-                .expect_one_of_keywords(&[ADD, DROP, RESET, SET, RENAME, OWNER])
+                .expect_one_of_keywords(&[ADD, DROP, RESET, SET, RENAME, OWNER, REFRESH])
                 .map_no_statement_parser_err()?
             {
                 ADD => {
@@ -4673,7 +5091,38 @@ impl<'a> Parser<'a> {
                         },
                     })
                 }
+                REFRESH => {
+                    self.expect_keyword(SCHEMA)
+                        .map_parser_err(StatementKind::AlterSource)?;
+
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name,
+                        if_exists,
+                        action: AlterSourceAction::RefreshSchema,
+                    })
+                }
                 RESET => {
+                    if self.parse_keyword(TO) {
+                        let position = match self
+                            .expect_one_of_keywords(&[OFFSET, TIMESTAMP])
+                            .map_parser_err(StatementKind::AlterSource)?
+                        {
+                            OFFSET => SourceResetPosition::Offset,
+                            TIMESTAMP => SourceResetPosition::Timestamp,
+                            _ => unreachable!(),
+                        };
+                        let _ = self.consume_token(&Token::Eq);
+                        let value = self
+                            .parse_option_value()
+                            .map_parser_err(StatementKind::AlterSource)?;
+
+                        return Ok(Statement::AlterSource(AlterSourceStatement {
+                            source_name,
+                            if_exists,
+                            action: AlterSourceAction::ResetTo { position, value },
+                        }));
+                    }
+
                     self.expect_token(&Token::LParen)
                         .map_parser_err(StatementKind::AlterSource)?;
                     let reset_options = self
@@ -4894,6 +5343,49 @@ impl<'a> Parser<'a> {
         )
     }
 
+    fn parse_alter_sequence(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
+        let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
+        let name =
+            UnresolvedObjectName::Item(self.parse_item_name().map_no_statement_parser_err()?);
+
+        Ok(
+            match self
+                .expect_one_of_keywords(&[RENAME, OWNER])
+                .map_no_statement_parser_err()?
+            {
+                RENAME => {
+                    self.expect_keyword(TO)
+                        .map_parser_err(StatementKind::AlterObjectRename)?;
+                    let to_item_name = self
+                        .parse_identifier()
+                        .map_parser_err(StatementKind::AlterObjectRename)?;
+
+                    Statement::AlterObjectRename(AlterObjectRenameStatement {
+                        object_type: ObjectType::Sequence,
+                        if_exists,
+                        name,
+                        to_item_name,
+                    })
+                }
+                OWNER => {
+                    self.expect_keyword(TO)
+                        .map_parser_err(StatementKind::AlterOwner)?;
+                    let new_owner = self
+                        .parse_identifier()
+                        .map_parser_err(StatementKind::AlterOwner)?;
+
+                    Statement::AlterOwner(AlterOwnerStatement {
+                        object_type: ObjectType::Sequence,
+                        if_exists,
+                        name,
+                        new_owner,
+                    })
+                }
+                _ => unreachable!(),
+            },
+        )
+    }
+
     /// Parse an ALTER SINK statement.
     fn parse_alter_sink(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
         let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
@@ -5405,9 +5897,12 @@ impl<'a> Parser<'a> {
                     )
                     .map_no_statement_parser_err();
                 }
-                self.expect_keyword(STDIN)
-                    .map_parser_err(StatementKind::Copy)?;
-                (CopyDirection::From, CopyTarget::Stdin)
+                if self.parse_keyword(STDIN) {
+                    (CopyDirection::From, CopyTarget::Stdin)
+                } else {
+                    let url_expr = self.parse_expr().map_parser_err(StatementKind::Copy)?;
+                    (CopyDirection::From, CopyTarget::Expr(url_expr))
+                }
             }
             TO => {
                 if self.parse_keyword(STDOUT) {
@@ -5926,7 +6421,8 @@ impl<'a> Parser<'a> {
             | ObjectType::Type
             | ObjectType::Secret
             | ObjectType::Connection
-            | ObjectType::Func => UnresolvedObjectName::Item(self.parse_item_name()?),
+            | ObjectType::Func
+            | ObjectType::Sequence => UnresolvedObjectName::Item(self.parse_item_name()?),
             ObjectType::Role => UnresolvedObjectName::Role(self.parse_identifier()?),
             ObjectType::Cluster => UnresolvedObjectName::Cluster(self.parse_identifier()?),
             ObjectType::ClusterReplica => {
@@ -6081,6 +6577,88 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_merge(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(INTO)?;
+        let table_name = RawItemName::Name(self.parse_item_name()?);
+        // As in UPDATE, the alias doesn't support columns.
+        let alias = self.parse_optional_alias(Keyword::is_reserved_in_table_alias)?;
+        let alias = alias.map(|name| TableAlias {
+            name,
+            columns: Vec::new(),
+            strict: false,
+        });
+        self.expect_keyword(USING)?;
+        let source = self.parse_table_factor()?;
+        self.expect_keyword(ON)?;
+        let on = self.parse_expr()?;
+        self.expect_keyword(WHEN)?;
+        let mut clauses = vec![self.parse_merge_clause()?];
+        while self.parse_keyword(WHEN) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+
+        Ok(Statement::Merge(MergeStatement {
+            table_name,
+            alias,
+            source,
+            on,
+            clauses,
+        }))
+    }
+
+    fn parse_merge_clause(&mut self) -> Result<MergeClause<Raw>, ParserError> {
+        let matched = !self.parse_keyword(NOT);
+        self.expect_keyword(MATCHED)?;
+        let condition = if self.parse_keyword(AND) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        self.expect_keyword(THEN)?;
+        let action = if matched {
+            match self.expect_one_of_keywords(&[UPDATE, DELETE, DO])? {
+                UPDATE => {
+                    self.expect_keyword(SET)?;
+                    MergeAction::Update {
+                        assignments: self.parse_comma_separated(Parser::parse_assignment)?,
+                    }
+                }
+                DELETE => MergeAction::Delete,
+                DO => {
+                    self.expect_keyword(NOTHING)?;
+                    MergeAction::DoNothing
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            match self.expect_one_of_keywords(&[INSERT, DO])? {
+                INSERT => {
+                    let columns = self.parse_parenthesized_column_list(Optional)?;
+                    let values = if self.parse_keywords(&[DEFAULT, VALUES]) {
+                        vec![]
+                    } else {
+                        self.expect_keyword(VALUES)?;
+                        self.expect_token(&Token::LParen)?;
+                        let values = self.parse_comma_separated(Parser::parse_expr)?;
+                        self.expect_token(&Token::RParen)?;
+                        values
+                    };
+                    MergeAction::Insert { columns, values }
+                }
+                DO => {
+                    self.expect_keyword(NOTHING)?;
+                    MergeAction::DoNothing
+                }
+                _ => unreachable!(),
+            }
+        };
+        Ok(MergeClause {
+            matched,
+            condition,
+            action,
+        })
+    }
+
     /// Parses a SELECT (or WITH, VALUES, TABLE) statement with optional AS OF.
     fn parse_select_statement(&mut self) -> Result<SelectStatement<Raw>, ParserError> {
         Ok(SelectStatement {
@@ -6708,7 +7286,7 @@ impl<'a> Parser<'a> {
                         on_object,
                     }
                 }
-                ObjectType::Func => {
+                ObjectType::Func | ObjectType::Sequence => {
                     return parser_err!(
                         self,
                         self.peek_prev_pos(),
@@ -7094,15 +7672,44 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         let returning = self.parse_returning()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         }))
     }
 
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let target = self.parse_parenthesized_column_list(Optional)?;
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { target, action })
+    }
+
     fn parse_returning(&mut self) -> Result<Vec<SelectItem<Raw>>, ParserError> {
         Ok(if self.parse_keyword(RETURNING) {
             self.parse_comma_separated(Parser::parse_select_item)?
@@ -7727,7 +8334,8 @@ impl<'a> Parser<'a> {
             Statement::Select(_)
                 | Statement::Insert(_)
                 | Statement::Delete(_)
-                | Statement::Update(_),
+                | Statement::Update(_)
+                | Statement::Merge(_),
         ) {
             return parser_err!(self, pos, "unpreparable statement").map_no_statement_parser_err();
         }
@@ -8004,7 +8612,8 @@ impl<'a> Parser<'a> {
             | ObjectType::ClusterReplica
             | ObjectType::Role
             | ObjectType::Func
-            | ObjectType::Subsource => {
+            | ObjectType::Subsource
+            | ObjectType::Sequence => {
                 parser_err!(
                     self,
                     self.peek_prev_pos(),
@@ -8040,6 +8649,7 @@ impl<'a> Parser<'a> {
                 DATABASE,
                 SCHEMA,
                 FUNCTION,
+                SEQUENCE,
             ])? {
                 TABLE => ObjectType::Table,
                 VIEW => ObjectType::View,
@@ -8067,6 +8677,7 @@ impl<'a> Parser<'a> {
                 DATABASE => ObjectType::Database,
                 SCHEMA => ObjectType::Schema,
                 FUNCTION => ObjectType::Func,
+                SEQUENCE => ObjectType::Sequence,
                 _ => unreachable!(),
             },
         )
@@ -8091,6 +8702,7 @@ impl<'a> Parser<'a> {
                 DATABASE,
                 SCHEMA,
                 FUNCTION,
+                SEQUENCE,
             ])? {
                 TABLE => ObjectType::Table,
                 VIEW => ObjectType::View,
@@ -8119,6 +8731,7 @@ impl<'a> Parser<'a> {
                 DATABASE => ObjectType::Database,
                 SCHEMA => ObjectType::Schema,
                 FUNCTION => ObjectType::Func,
+                SEQUENCE => ObjectType::Sequence,
                 _ => unreachable!(),
             },
         )
@@ -0,0 +1,235 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Helpers for reading from SQL Server's change data capture (CDC) tables.
+//!
+//! SQL Server's CDC is driven by a capture job that scans the transaction
+//! log and copies changes made to tracked tables into change tables, one per
+//! "capture instance". Every change is tagged with the LSN of the commit
+//! record of the transaction that made it, which is what we use as our
+//! timestamp.
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use tiberius::{Client, ColumnData};
+use tokio::net::TcpStream;
+use tokio_util::compat::Compat;
+
+use crate::{quote_identifier, Lsn, SqlServerError, SqlServerTableDesc};
+
+type SqlServerClient = Client<Compat<TcpStream>>;
+
+/// Ensures CDC is enabled for the database the client is connected to.
+pub async fn ensure_cdc_enabled(client: &mut SqlServerClient) -> Result<(), SqlServerError> {
+    let row = client
+        .simple_query("SELECT is_cdc_enabled FROM sys.databases WHERE database_id = DB_ID()")
+        .await?
+        .into_row()
+        .await?
+        .ok_or_else(|| anyhow!("database not found in sys.databases"))?;
+    let enabled: bool = row.try_get(0)?.unwrap_or(false);
+    if !enabled {
+        return Err(SqlServerError::InvalidSystemSetting {
+            setting: "is_cdc_enabled".to_string(),
+            expected: "1".to_string(),
+            actual: "0".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Ensures `SNAPSHOT` isolation is allowed for the database the client is
+/// connected to, which we require to take consistent snapshots of tables
+/// without blocking writers.
+pub async fn ensure_snapshot_isolation_enabled(
+    client: &mut SqlServerClient,
+) -> Result<(), SqlServerError> {
+    let row = client
+        .simple_query(
+            "SELECT snapshot_isolation_state FROM sys.databases WHERE database_id = DB_ID()",
+        )
+        .await?
+        .into_row()
+        .await?
+        .ok_or_else(|| anyhow!("database not found in sys.databases"))?;
+    let state: u8 = row.try_get(0)?.unwrap_or(0);
+    if state != 1 {
+        return Err(SqlServerError::InvalidSystemSetting {
+            setting: "snapshot_isolation_state".to_string(),
+            expected: "1".to_string(),
+            actual: state.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the LSN of the end of the transaction log. Every transaction that
+/// has committed at the time of the call has a commit LSN strictly less than
+/// the returned LSN.
+pub async fn get_log_end_lsn(client: &mut SqlServerClient) -> Result<Lsn, SqlServerError> {
+    let row = client
+        .simple_query("SELECT log_end_lsn FROM sys.dm_db_log_stats(DB_ID())")
+        .await?
+        .into_row()
+        .await?
+        .ok_or_else(|| anyhow!("no log stats for database"))?;
+    let lsn: &str = row
+        .try_get(0)?
+        .ok_or_else(|| anyhow!("log_end_lsn is NULL"))?;
+    Ok(lsn.parse()?)
+}
+
+/// Returns the largest LSN the CDC capture job has processed, or `None` if it
+/// has not yet processed anything.
+///
+/// The capture job periodically records its progress in
+/// `cdc.lsn_time_mapping`, even when none of the tracked tables change, so
+/// this value keeps advancing as long as the capture job is running. All
+/// changes with an LSN less than or equal to the returned LSN are present in
+/// the change tables.
+pub async fn get_max_lsn(client: &mut SqlServerClient) -> Result<Option<Lsn>, SqlServerError> {
+    let row = client
+        .simple_query("SELECT sys.fn_cdc_get_max_lsn()")
+        .await?
+        .into_row()
+        .await?;
+    let bytes: Option<&[u8]> = match &row {
+        Some(row) => row.try_get(0)?,
+        None => None,
+    };
+    match bytes {
+        Some(bytes) => {
+            let lsn = Lsn::from_bytes(bytes)?;
+            Ok((!lsn.is_zero()).then_some(lsn))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Returns the smallest LSN available in the change table of the given
+/// capture instance.
+pub async fn get_min_lsn(
+    client: &mut SqlServerClient,
+    capture_instance: &str,
+) -> Result<Lsn, SqlServerError> {
+    let row = client
+        .query("SELECT sys.fn_cdc_get_min_lsn(@P1)", &[&capture_instance])
+        .await?
+        .into_row()
+        .await?;
+    let bytes: Option<&[u8]> = match &row {
+        Some(row) => row.try_get(0)?,
+        None => None,
+    };
+    // SQL Server reports the all-zero LSN if the capture instance does not
+    // exist.
+    match bytes.map(Lsn::from_bytes).transpose()? {
+        Some(lsn) if !lsn.is_zero() => Ok(lsn),
+        _ => Err(SqlServerError::CaptureInstanceMissing {
+            capture_instance: capture_instance.to_string(),
+        }),
+    }
+}
+
+/// The type of a change read from a change table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Delete,
+    Insert,
+    /// The values of a row before an update.
+    UpdateOld,
+    /// The values of a row after an update.
+    UpdateNew,
+}
+
+impl Operation {
+    /// The diff this operation contributes to a collection.
+    pub fn diff(&self) -> i64 {
+        match self {
+            Operation::Delete | Operation::UpdateOld => -1,
+            Operation::Insert | Operation::UpdateNew => 1,
+        }
+    }
+}
+
+impl TryFrom<i32> for Operation {
+    type Error = SqlServerError;
+
+    fn try_from(op: i32) -> Result<Self, Self::Error> {
+        match op {
+            1 => Ok(Operation::Delete),
+            2 => Ok(Operation::Insert),
+            3 => Ok(Operation::UpdateOld),
+            4 => Ok(Operation::UpdateNew),
+            op => Err(anyhow!("unknown CDC operation {op}").into()),
+        }
+    }
+}
+
+/// A change read from a change table.
+#[derive(Debug)]
+pub struct Change {
+    /// The commit LSN of the transaction that made this change.
+    pub lsn: Lsn,
+    pub operation: Operation,
+    /// The values of the captured columns of the table, in the order of the
+    /// columns of the table's [`SqlServerTableDesc`].
+    pub values: Vec<ColumnData<'static>>,
+}
+
+/// Reads all changes to the table described by `table` with an LSN in the
+/// range `[from, to]`, ordered by LSN.
+///
+/// `from` must be at least the minimum LSN of the table's capture instance and
+/// `to` must be at most the maximum LSN processed by the capture job.
+pub async fn get_changes(
+    client: &mut SqlServerClient,
+    table: &SqlServerTableDesc,
+    from: Lsn,
+    to: Lsn,
+) -> Result<Vec<Change>, SqlServerError> {
+    let mut columns = vec!["__$start_lsn".to_string(), "__$operation".to_string()];
+    columns.extend(table.columns.iter().map(|c| quote_identifier(&c.name)));
+    // We ask for the old values of updated rows so that we can retract them,
+    // and order by the sequence value within each transaction so that changes
+    // to the same row are applied in order.
+    let query = format!(
+        "SELECT {} FROM cdc.{}({from}, {to}, N'all update old') \
+         ORDER BY __$start_lsn, __$seqval, __$operation",
+        columns.iter().join(", "),
+        quote_identifier(&format!(
+            "fn_cdc_get_all_changes_{}",
+            table.capture_instance
+        )),
+    );
+    let rows = client
+        .simple_query(query)
+        .await?
+        .into_first_result()
+        .await?;
+
+    let mut changes = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut values = row.into_iter();
+        let lsn = match values.next() {
+            Some(ColumnData::Binary(Some(bytes))) => Lsn::from_bytes(&bytes)?,
+            value => Err(anyhow!("unexpected __$start_lsn value: {value:?}"))?,
+        };
+        let operation = match values.next() {
+            Some(ColumnData::I32(Some(op))) => Operation::try_from(op)?,
+            value => Err(anyhow!("unexpected __$operation value: {value:?}"))?,
+        };
+        changes.push(Change {
+            lsn,
+            operation,
+            values: values.collect(),
+        });
+    }
+    Ok(changes)
+}
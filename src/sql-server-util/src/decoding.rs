@@ -0,0 +1,194 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use itertools::{EitherOrBoth, Itertools};
+use tiberius::{ColumnData, FromSql};
+
+use mz_repr::adt::date::Date;
+use mz_repr::adt::numeric::{get_scale, Numeric};
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{Datum, Row, ScalarType};
+
+use crate::{SqlServerColumnDesc, SqlServerError, SqlServerTableDesc};
+
+/// Packs the values of a row read from SQL Server into `row_container`.
+///
+/// `values` must yield the values of the captured columns of the table in the
+/// order of `table_desc.columns`.
+pub fn pack_sql_server_row(
+    row_container: &mut Row,
+    values: impl IntoIterator<Item = ColumnData<'static>>,
+    table_desc: &SqlServerTableDesc,
+) -> Result<Row, SqlServerError> {
+    let mut packer = row_container.packer();
+    let mut temp_strs = vec![];
+    let mut temp_bytes = vec![];
+
+    for values in table_desc.columns.iter().zip_longest(values) {
+        let (col_desc, value) = match values {
+            EitherOrBoth::Both(col_desc, value) => (col_desc, value),
+            EitherOrBoth::Left(col_desc) => {
+                tracing::error!(
+                    "sql server: extra column description {col_desc:?} for table {}",
+                    table_desc.name
+                );
+                Err(SqlServerError::ValueDecodeError {
+                    column_name: col_desc.name.clone(),
+                    qualified_table_name: format!("{}.{}", table_desc.schema_name, table_desc.name),
+                    error: "extra column description".to_string(),
+                })?
+            }
+            EitherOrBoth::Right(_) => {
+                // If there are extra columns on the upstream table we can safely ignore them
+                break;
+            }
+        };
+        if col_desc.column_type.is_none() {
+            // This column is ignored, so don't decode it.
+            continue;
+        }
+        let datum = match val_to_datum(value, col_desc, &mut temp_strs, &mut temp_bytes) {
+            Err(err) => Err(SqlServerError::ValueDecodeError {
+                column_name: col_desc.name.clone(),
+                qualified_table_name: format!("{}.{}", table_desc.schema_name, table_desc.name),
+                error: err.to_string(),
+            })?,
+            Ok(datum) => datum,
+        };
+        packer.push(datum);
+    }
+
+    Ok(row_container.clone())
+}
+
+fn val_to_datum<'a>(
+    value: ColumnData<'static>,
+    col_desc: &SqlServerColumnDesc,
+    temp_strs: &'a mut Vec<String>,
+    temp_bytes: &'a mut Vec<Vec<u8>>,
+) -> Result<Datum<'a>, anyhow::Error> {
+    let column_type = match col_desc.column_type {
+        Some(ref column_type) => column_type,
+        None => anyhow::bail!("column type is not set for column: {}", col_desc.name),
+    };
+
+    // tiberius represents NULL as `None` within the variant of the column's
+    // type.
+    let is_null = match &value {
+        ColumnData::U8(v) => v.is_none(),
+        ColumnData::I16(v) => v.is_none(),
+        ColumnData::I32(v) => v.is_none(),
+        ColumnData::I64(v) => v.is_none(),
+        ColumnData::F32(v) => v.is_none(),
+        ColumnData::F64(v) => v.is_none(),
+        ColumnData::Bit(v) => v.is_none(),
+        ColumnData::String(v) => v.is_none(),
+        ColumnData::Guid(v) => v.is_none(),
+        ColumnData::Binary(v) => v.is_none(),
+        ColumnData::Numeric(v) => v.is_none(),
+        ColumnData::Xml(v) => v.is_none(),
+        ColumnData::DateTime(v) => v.is_none(),
+        ColumnData::SmallDateTime(v) => v.is_none(),
+        ColumnData::Time(v) => v.is_none(),
+        ColumnData::Date(v) => v.is_none(),
+        ColumnData::DateTime2(v) => v.is_none(),
+        ColumnData::DateTimeOffset(v) => v.is_none(),
+    };
+    if is_null {
+        if column_type.nullable {
+            return Ok(Datum::Null);
+        } else {
+            anyhow::bail!("received a null value in a non-null column");
+        }
+    }
+
+    Ok(match (&column_type.scalar_type, value) {
+        (ScalarType::Bool, ColumnData::Bit(Some(v))) => Datum::from(v),
+        (ScalarType::Int16, ColumnData::U8(Some(v))) => Datum::from(i16::from(v)),
+        (ScalarType::Int16, ColumnData::I16(Some(v))) => Datum::from(v),
+        (ScalarType::Int32, ColumnData::I32(Some(v))) => Datum::from(v),
+        (ScalarType::Int64, ColumnData::I64(Some(v))) => Datum::from(v),
+        (ScalarType::Float32, ColumnData::F32(Some(v))) => Datum::from(v),
+        (ScalarType::Float64, ColumnData::F64(Some(v))) => Datum::from(v),
+        (ScalarType::Numeric { max_scale }, value) => {
+            // `decimal` and `numeric` values are decoded as tiberius'
+            // `Numeric`, whereas `money` and `smallmoney` values are decoded
+            // as floats. Either way we go through their string representation.
+            let val = match value {
+                ColumnData::Numeric(Some(v)) => v.to_string(),
+                ColumnData::F64(Some(v)) => v.to_string(),
+                ColumnData::F32(Some(v)) => v.to_string(),
+                value => anyhow::bail!("received unexpected value for numeric type: {value:?}"),
+            };
+            let val = Numeric::from_str(&val)?;
+            if let Some(max_scale) = max_scale {
+                if get_scale(&val) > max_scale.into_u8().into() {
+                    anyhow::bail!(
+                        "received numeric value with scale {} for column {} which has a max scale of {}",
+                        get_scale(&val),
+                        col_desc.name,
+                        max_scale.into_u8()
+                    );
+                }
+            }
+            Datum::from(val)
+        }
+        (ScalarType::String, ColumnData::String(Some(v))) => {
+            temp_strs.push(v.into_owned());
+            Datum::from(temp_strs.last().unwrap().as_str())
+        }
+        (ScalarType::Bytes, ColumnData::Binary(Some(v))) => {
+            temp_bytes.push(v.into_owned());
+            Datum::from(temp_bytes.last().unwrap().as_slice())
+        }
+        (ScalarType::Uuid, ColumnData::Guid(Some(v))) => Datum::from(v),
+        (ScalarType::Date, value) => {
+            let date = from_sql::<NaiveDate>(&value)?;
+            Datum::from(Date::try_from(date)?)
+        }
+        (ScalarType::Time, value) => {
+            let time = from_sql::<NaiveTime>(&value)?;
+            Datum::from(truncate_nanos(time))
+        }
+        (ScalarType::Timestamp { .. }, value) => {
+            let ts = from_sql::<NaiveDateTime>(&value)?;
+            let ts = ts.date().and_time(truncate_nanos(ts.time()));
+            Datum::try_from(CheckedTimestamp::try_from(ts)?)?
+        }
+        (ScalarType::TimestampTz { .. }, value) => {
+            let ts = from_sql::<DateTime<Utc>>(&value)?;
+            let ts = ts
+                .date_naive()
+                .and_time(truncate_nanos(ts.time()))
+                .and_utc();
+            Datum::try_from(CheckedTimestamp::try_from(ts)?)?
+        }
+        (scalar_type, value) => anyhow::bail!(
+            "received unexpected value for type: {:?}: {:?}",
+            scalar_type,
+            value
+        ),
+    })
+}
+
+/// Decodes a non-null temporal value using tiberius' `FromSql` conversions.
+fn from_sql<'a, T: FromSql<'a>>(value: &'a ColumnData<'static>) -> Result<T, anyhow::Error> {
+    T::from_sql(value)?.ok_or_else(|| anyhow::anyhow!("unexpected null value"))
+}
+
+/// SQL Server supports a precision of up to 100ns, but we only support
+/// microseconds, so we truncate any additional precision.
+fn truncate_nanos(time: NaiveTime) -> NaiveTime {
+    let nanos = time.nanosecond();
+    time.with_nanosecond(nanos - nanos % 1000)
+        .expect("truncated nanoseconds are valid")
+}
@@ -0,0 +1,167 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::bail;
+use proptest::prelude::any;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_proto::{ProtoType, RustType, TryFromProtoError};
+use mz_repr::ColumnType;
+
+include!(concat!(env!("OUT_DIR"), "/mz_sql_server_util.rs"));
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerTableDesc {
+    /// The name of the schema the table belongs to.
+    pub schema_name: String,
+    /// The name of the table.
+    pub name: String,
+    /// The name of the CDC capture instance we read changes for this table
+    /// from.
+    pub capture_instance: String,
+    /// Columns for the table
+    ///
+    /// The index of each column is based on its `column_ordinal` reported by
+    /// the `cdc.captured_columns` table, which defines the order of column
+    /// values when we query the change table.
+    #[proptest(strategy = "proptest::collection::vec(any::<SqlServerColumnDesc>(), 0..4)")]
+    pub columns: Vec<SqlServerColumnDesc>,
+}
+
+impl RustType<ProtoSqlServerTableDesc> for SqlServerTableDesc {
+    fn into_proto(&self) -> ProtoSqlServerTableDesc {
+        ProtoSqlServerTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            capture_instance: self.capture_instance.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            capture_instance: proto.capture_instance,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(SqlServerColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl SqlServerTableDesc {
+    /// Determines if two `SqlServerTableDesc` are compatible with one another
+    /// in a way that Materialize can handle.
+    ///
+    /// Currently this means that the values are equal except for the following
+    /// exceptions:
+    /// - `self`'s columns are a prefix of `other`'s columns.
+    pub fn determine_compatibility(&self, other: &SqlServerTableDesc) -> Result<(), anyhow::Error> {
+        if self == other {
+            return Ok(());
+        }
+
+        if self.schema_name != other.schema_name || self.name != other.name {
+            bail!(
+                "table name mismatch: self: {}.{}, other: {}.{}",
+                self.schema_name,
+                self.name,
+                other.schema_name,
+                other.name
+            );
+        }
+
+        if self.capture_instance != other.capture_instance {
+            bail!(
+                "capture instance of table {} changed from {} to {}",
+                self.name,
+                self.capture_instance,
+                other.capture_instance
+            );
+        }
+
+        // `columns` is ordered by the ordinal of each captured column, so as
+        // long as `self.columns` is a compatible prefix of `other.columns`, we
+        // can ignore extra columns from `other.columns`.
+        let mut other_columns = other.columns.iter();
+        for self_column in &self.columns {
+            let other_column = other_columns.next().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "column {} no longer present in table {}",
+                    self_column.name,
+                    self.name
+                )
+            })?;
+            if !self_column.is_compatible(other_column) {
+                bail!(
+                    "column {} in table {} has been altered",
+                    self_column.name,
+                    self.name
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerColumnDesc {
+    /// The name of the column.
+    pub name: String,
+    /// The intended data type of this column within Materialize
+    /// If this is None, the column is intended to be skipped within Materialize
+    pub column_type: Option<ColumnType>,
+    /// The name of the upstream data type of this column.
+    pub data_type: String,
+}
+
+impl RustType<ProtoSqlServerColumnDesc> for SqlServerColumnDesc {
+    fn into_proto(&self) -> ProtoSqlServerColumnDesc {
+        ProtoSqlServerColumnDesc {
+            name: self.name.clone(),
+            column_type: self.column_type.into_proto(),
+            data_type: self.data_type.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            name: proto.name,
+            column_type: proto.column_type.into_rust()?,
+            data_type: proto.data_type,
+        })
+    }
+}
+
+impl SqlServerColumnDesc {
+    /// Determines if two `SqlServerColumnDesc` are compatible with one another
+    /// in a way that Materialize can handle.
+    fn is_compatible(&self, other: &SqlServerColumnDesc) -> bool {
+        self.name == other.name
+            && self.data_type == other.data_type
+            && match (&self.column_type, &other.column_type) {
+                (None, None) => true,
+                (Some(self_type), Some(other_type)) => {
+                    self_type.scalar_type == other_type.scalar_type
+                    // Columns are compatible if:
+                    // - self is nullable; introducing a not null constraint doesn't
+                    //   change this column's behavior.
+                    // - self and other are both not nullable
+                    && (self_type.nullable || self_type.nullable == other_type.nullable)
+                }
+                (Some(_), None) => false,
+                (None, Some(_)) => false,
+            }
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server utility library.
+
+mod tunnel;
+pub use tunnel::{Config, SqlServerConn, TunnelConfig};
+
+mod desc;
+pub use desc::{
+    ProtoLsn, ProtoSqlServerColumnDesc, ProtoSqlServerTableDesc, SqlServerColumnDesc,
+    SqlServerTableDesc,
+};
+
+mod lsn;
+pub use lsn::Lsn;
+
+pub mod cdc;
+pub use cdc::{
+    ensure_cdc_enabled, ensure_snapshot_isolation_enabled, get_changes, get_log_end_lsn,
+    get_max_lsn, get_min_lsn, Change, Operation,
+};
+
+pub mod schemas;
+pub use schemas::{schema_info, SchemaRequest};
+
+pub mod decoding;
+pub use decoding::pack_sql_server_row;
+
+#[derive(Debug, Clone)]
+pub struct UnsupportedDataType {
+    pub column_type: String,
+    pub qualified_table_name: String,
+    pub column_name: String,
+}
+
+impl std::fmt::Display for UnsupportedDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}.{}' of type '{}'",
+            self.qualified_table_name, self.column_name, self.column_type
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlServerError {
+    #[error(
+        "invalid sql server system setting '{setting}'. Expected '{expected}'. Got '{actual}'."
+    )]
+    InvalidSystemSetting {
+        setting: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("error setting up ssh: {0}")]
+    Ssh(#[source] anyhow::Error),
+    #[error("error decoding value for '{qualified_table_name}' column '{column_name}': {error}")]
+    ValueDecodeError {
+        column_name: String,
+        qualified_table_name: String,
+        error: String,
+    },
+    #[error("unsupported data types: {columns:?}")]
+    UnsupportedDataTypes { columns: Vec<UnsupportedDataType> },
+    /// The capture instance for a table no longer exists, e.g. because CDC
+    /// was disabled for the table or the table was dropped.
+    #[error("capture instance '{capture_instance}' does not exist")]
+    CaptureInstanceMissing { capture_instance: String },
+    /// The change data requested has already been removed by the CDC cleanup
+    /// job.
+    #[error(
+        "change data for capture instance '{capture_instance}' before LSN {requested} is no \
+        longer available; earliest available LSN is {available}"
+    )]
+    CdcDataUnavailable {
+        capture_instance: String,
+        requested: Lsn,
+        available: Lsn,
+    },
+    /// Any other error we bail on.
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+    /// A tiberius error.
+    #[error(transparent)]
+    Tiberius(#[from] tiberius::error::Error),
+    /// An I/O error establishing the TCP connection.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Quotes a SQL Server identifier, e.g. a schema or table name.
+pub fn quote_identifier(ident: &str) -> String {
+    format!("[{}]", ident.replace(']', "]]"))
+}
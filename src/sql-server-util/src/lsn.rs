@@ -0,0 +1,232 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server log sequence numbers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use mz_proto::{RustType, TryFromProtoError};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::{PathSummary, Refines, Timestamp};
+
+use crate::desc::ProtoLsn;
+
+/// A SQL Server log sequence number (LSN).
+///
+/// SQL Server identifies every record in the transaction log with an LSN made
+/// up of the ID of the virtual log file (VLF) the record lives in, the ID of
+/// the log block within that VLF, and the ID of the record within that block.
+/// LSNs are totally ordered and the ordering of its fields matches the
+/// ordering of the LSNs themselves.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    Arbitrary,
+)]
+pub struct Lsn {
+    /// The virtual log file ID.
+    pub vlf_id: u32,
+    /// The log block ID.
+    pub block_id: u32,
+    /// The log record ID.
+    pub record_id: u16,
+}
+
+impl Lsn {
+    /// The number of bytes in the `binary(10)` representation of an LSN.
+    pub const SIZE: usize = 10;
+
+    /// The largest possible LSN.
+    pub const MAX: Lsn = Lsn {
+        vlf_id: u32::MAX,
+        block_id: u32::MAX,
+        record_id: u16::MAX,
+    };
+
+    /// Decodes an LSN from the `binary(10)` representation used by the CDC
+    /// functions and tables.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, anyhow::Error> {
+        let bytes: [u8; Self::SIZE] = bytes
+            .try_into()
+            .map_err(|_| anyhow!("invalid LSN length: {}", bytes.len()))?;
+        Ok(Lsn {
+            vlf_id: u32::from_be_bytes(bytes[0..4].try_into().expect("known size")),
+            block_id: u32::from_be_bytes(bytes[4..8].try_into().expect("known size")),
+            record_id: u16::from_be_bytes(bytes[8..10].try_into().expect("known size")),
+        })
+    }
+
+    /// Encodes this LSN in the `binary(10)` representation used by the CDC
+    /// functions and tables.
+    pub fn as_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.vlf_id.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.block_id.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.record_id.to_be_bytes());
+        bytes
+    }
+
+    /// Returns whether this is the all-zero LSN, which SQL Server uses to
+    /// signal the absence of an LSN.
+    pub fn is_zero(&self) -> bool {
+        *self == Lsn::default()
+    }
+
+    /// Returns the smallest LSN that is strictly greater than this one.
+    pub fn increment(&self) -> Lsn {
+        match self.record_id.checked_add(1) {
+            Some(record_id) => Lsn { record_id, ..*self },
+            None => match self.block_id.checked_add(1) {
+                Some(block_id) => Lsn {
+                    block_id,
+                    record_id: 0,
+                    ..*self
+                },
+                None => Lsn {
+                    vlf_id: self.vlf_id.checked_add(1).expect("LSN overflow"),
+                    block_id: 0,
+                    record_id: 0,
+                },
+            },
+        }
+    }
+}
+
+impl fmt::Display for Lsn {
+    /// Formats the LSN as the hexadecimal literal of its `binary(10)`
+    /// representation, which is directly usable in SQL Server queries.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{:08X}{:08X}{:04X}",
+            self.vlf_id, self.block_id, self.record_id
+        )
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = anyhow::Error;
+
+    /// Parses the `xxxxxxxx:xxxxxxxx:xxxx` hexadecimal format used by dynamic
+    /// management views like `sys.dm_db_log_stats`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(':').collect();
+        let [vlf_id, block_id, record_id] = parts.as_slice() else {
+            bail!("invalid LSN: {s}");
+        };
+        Ok(Lsn {
+            vlf_id: u32::from_str_radix(vlf_id, 16)?,
+            block_id: u32::from_str_radix(block_id, 16)?,
+            record_id: u16::from_str_radix(record_id, 16)?,
+        })
+    }
+}
+
+impl RustType<ProtoLsn> for Lsn {
+    fn into_proto(&self) -> ProtoLsn {
+        ProtoLsn {
+            vlf_id: self.vlf_id,
+            block_id: self.block_id,
+            record_id: self.record_id.into(),
+        }
+    }
+
+    fn from_proto(proto: ProtoLsn) -> Result<Self, TryFromProtoError> {
+        Ok(Lsn {
+            vlf_id: proto.vlf_id,
+            block_id: proto.block_id,
+            record_id: proto.record_id.try_into()?,
+        })
+    }
+}
+
+impl Timestamp for Lsn {
+    type Summary = ();
+
+    fn minimum() -> Self {
+        Lsn::default()
+    }
+}
+
+impl TotalOrder for Lsn {}
+
+impl PartialOrder for Lsn {
+    fn less_equal(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl PathSummary<Lsn> for () {
+    fn results_in(&self, src: &Lsn) -> Option<Lsn> {
+        Some(*src)
+    }
+
+    fn followed_by(&self, _other: &Self) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Refines<()> for Lsn {
+    fn to_inner(_other: ()) -> Self {
+        Self::minimum()
+    }
+
+    fn to_outer(self) -> () {}
+
+    fn summarize(_path: <Self as Timestamp>::Summary) -> <() as Timestamp>::Summary {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_lsn_roundtrip() {
+        let lsn = Lsn {
+            vlf_id: 0x2A,
+            block_id: 0x1F8,
+            record_id: 0x3,
+        };
+        assert_eq!(Lsn::from_bytes(&lsn.as_bytes()).unwrap(), lsn);
+        assert_eq!(lsn.to_string(), "0x0000002A000001F80003");
+        assert_eq!("0000002a:000001f8:0003".parse::<Lsn>().unwrap(), lsn);
+        assert!(Lsn::from_bytes(&[0; 9]).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_lsn_increment() {
+        let lsn = Lsn {
+            vlf_id: 1,
+            block_id: u32::MAX,
+            record_id: u16::MAX,
+        };
+        assert_eq!(
+            lsn.increment(),
+            Lsn {
+                vlf_id: 2,
+                block_id: 0,
+                record_id: 0
+            }
+        );
+        assert!(lsn < lsn.increment());
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use tiberius::{Client, ToSql};
+use tokio::net::TcpStream;
+use tokio_util::compat::Compat;
+
+use mz_repr::adt::numeric::NumericMaxScale;
+use mz_repr::{ColumnType, ScalarType};
+
+use crate::desc::{SqlServerColumnDesc, SqlServerTableDesc};
+use crate::{SqlServerError, UnsupportedDataType};
+
+/// Request for table schemas from SQL Server
+pub enum SchemaRequest<'a> {
+    /// Request schemas for all CDC-enabled tables in the database
+    All,
+    /// Request schemas for all CDC-enabled tables in the specified schemas
+    Schemas(Vec<&'a str>),
+    /// Request schemas for all specified tables, specified as (schema_name, table_name)
+    Tables(Vec<(&'a str, &'a str)>),
+}
+
+/// Helper for reading the captured columns of capture instances.
+// NOTE: The order of these names *must* match the order in which
+// `schema_info` reads the columns.
+const CAPTURED_COLUMN_COLS: &str = "s.name AS schema_name,
+    t.name AS table_name,
+    ct.capture_instance,
+    c.name AS column_name,
+    ty.name AS data_type,
+    c.is_nullable,
+    c.scale";
+
+/// Retrieve the tables and column descriptions for CDC-enabled tables.
+///
+/// Tables that do not have CDC enabled are not reported. If a table has more
+/// than one capture instance, which SQL Server allows to support schema
+/// changes, the most recently created one is used.
+pub async fn schema_info<'a>(
+    client: &mut Client<Compat<TcpStream>>,
+    schema_request: &SchemaRequest<'a>,
+) -> Result<Vec<SqlServerTableDesc>, SqlServerError> {
+    let mut params: Vec<&dyn ToSql> = vec![];
+    let filter = match schema_request {
+        SchemaRequest::All => "1 = 1".to_string(),
+        SchemaRequest::Schemas(schemas) => {
+            if schemas.is_empty() {
+                return Ok(vec![]);
+            }
+            let filter = format!(
+                "s.name IN ({})",
+                (1..=schemas.len()).map(|i| format!("@P{i}")).join(", ")
+            );
+            params.extend(schemas.iter().map(|s| s as &dyn ToSql));
+            filter
+        }
+        SchemaRequest::Tables(tables) => {
+            if tables.is_empty() {
+                return Ok(vec![]);
+            }
+            let filter = (0..tables.len())
+                .map(|i| format!("(s.name = @P{} AND t.name = @P{})", 2 * i + 1, 2 * i + 2))
+                .join(" OR ");
+            params.extend(
+                tables
+                    .iter()
+                    .flat_map(|(s, t)| [s as &dyn ToSql, t as &dyn ToSql]),
+            );
+            filter
+        }
+    };
+
+    // NOTE: It's important that we order by column_ordinal ASC since we rely
+    // on this as the order of the columns we read from the change table.
+    let query = format!(
+        "SELECT {CAPTURED_COLUMN_COLS}
+        FROM cdc.change_tables ct
+        JOIN sys.tables t ON t.object_id = ct.source_object_id
+        JOIN sys.schemas s ON s.schema_id = t.schema_id
+        JOIN cdc.captured_columns cc ON cc.object_id = ct.object_id
+        JOIN sys.columns c ON c.object_id = t.object_id AND c.column_id = cc.column_id
+        JOIN sys.types ty ON ty.user_type_id = c.user_type_id
+        WHERE {filter}
+        ORDER BY s.name, t.name, ct.create_date DESC, ct.capture_instance, cc.column_ordinal"
+    );
+    let rows = client
+        .query(query, &params)
+        .await?
+        .into_first_result()
+        .await?;
+
+    let mut tables: BTreeMap<(String, String), SqlServerTableDesc> = BTreeMap::new();
+    let mut error_cols = vec![];
+    for row in rows {
+        let get_str = |idx: usize| -> Result<String, SqlServerError> {
+            let value: Option<&str> = row.try_get(idx)?;
+            Ok(value.unwrap_or_default().to_string())
+        };
+        let schema_name = get_str(0)?;
+        let table_name = get_str(1)?;
+        let capture_instance = get_str(2)?;
+        let column_name = get_str(3)?;
+        let data_type = get_str(4)?;
+        let is_nullable: bool = row.try_get(5)?.unwrap_or(true);
+        let scale: u8 = row.try_get(6)?.unwrap_or(0);
+
+        let table = tables
+            .entry((schema_name.clone(), table_name.clone()))
+            .or_insert_with(|| SqlServerTableDesc {
+                schema_name: schema_name.clone(),
+                name: table_name.clone(),
+                capture_instance: capture_instance.clone(),
+                columns: vec![],
+            });
+        // Rows for older capture instances of the same table sort after those
+        // of the newest one, so we skip them.
+        if table.capture_instance != capture_instance {
+            continue;
+        }
+
+        match parse_data_type(&data_type, scale) {
+            Some(scalar_type) => table.columns.push(SqlServerColumnDesc {
+                name: column_name,
+                column_type: Some(ColumnType {
+                    scalar_type,
+                    nullable: is_nullable,
+                }),
+                data_type,
+            }),
+            None => error_cols.push(UnsupportedDataType {
+                column_type: data_type,
+                qualified_table_name: format!("{:?}.{:?}", schema_name, table_name),
+                column_name,
+            }),
+        }
+    }
+
+    if error_cols.len() > 0 {
+        Err(SqlServerError::UnsupportedDataTypes {
+            columns: error_cols,
+        })?;
+    }
+    Ok(tables.into_values().collect())
+}
+
+/// Returns the Materialize type we represent values of the given SQL Server
+/// data type with, or `None` if the data type is not supported.
+fn parse_data_type(data_type: &str, scale: u8) -> Option<ScalarType> {
+    let scalar_type = match data_type {
+        "bit" => ScalarType::Bool,
+        "tinyint" | "smallint" => ScalarType::Int16,
+        "int" => ScalarType::Int32,
+        "bigint" => ScalarType::Int64,
+        "real" => ScalarType::Float32,
+        "float" => ScalarType::Float64,
+        // SQL Server's maximum precision of 38 is within the bounds of our
+        // numeric type, so we only need to carry over the scale.
+        "decimal" | "numeric" => ScalarType::Numeric {
+            max_scale: Some(NumericMaxScale::try_from(i64::from(scale)).ok()?),
+        },
+        "money" | "smallmoney" => ScalarType::Numeric {
+            max_scale: Some(NumericMaxScale::try_from(4i64).ok()?),
+        },
+        "char" | "varchar" | "nchar" | "nvarchar" | "text" | "ntext" => ScalarType::String,
+        "binary" | "varbinary" | "image" => ScalarType::Bytes,
+        "date" => ScalarType::Date,
+        "time" => ScalarType::Time,
+        // SQL Server supports up to seven fractional digits, whereas we only
+        // support six, so these values are truncated to microseconds.
+        "datetime" | "datetime2" | "smalldatetime" => ScalarType::Timestamp { precision: None },
+        "datetimeoffset" => ScalarType::TimestampTz { precision: None },
+        "uniqueidentifier" => ScalarType::Uuid,
+        _ => return None,
+    };
+    Some(scalar_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_data_type() {
+        assert_eq!(parse_data_type("int", 0), Some(ScalarType::Int32));
+        assert_eq!(
+            parse_data_type("decimal", 2),
+            Some(ScalarType::Numeric {
+                max_scale: Some(NumericMaxScale::try_from(2i64).unwrap())
+            })
+        );
+        assert_eq!(parse_data_type("xml", 0), None);
+        assert_eq!(parse_data_type("geography", 0), None);
+    }
+}
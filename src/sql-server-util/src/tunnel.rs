@@ -0,0 +1,194 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeSet;
+use std::net::{IpAddr, SocketAddr};
+use std::ops::{Deref, DerefMut};
+
+use mz_ore::future::{InTask, OreFutureExt};
+use mz_repr::GlobalId;
+use mz_ssh_util::tunnel::{SshTimeoutConfig, SshTunnelConfig};
+use mz_ssh_util::tunnel_manager::{ManagedSshTunnelHandle, SshTunnelManager};
+use tiberius::Client;
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+use tracing::{info, warn};
+
+use crate::SqlServerError;
+
+/// Configures an optional tunnel for use when connecting to a SQL Server
+/// database.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TunnelConfig {
+    /// Establish a direct TCP connection to the database host.
+    /// If `resolved_ips` is not None, the provided IPs will be used
+    /// rather than resolving the hostname.
+    Direct {
+        resolved_ips: Option<BTreeSet<IpAddr>>,
+    },
+    /// Establish a TCP connection to the database via an SSH tunnel.
+    /// This means first establishing an SSH connection to a bastion host,
+    /// and then opening a separate connection from that host to the database.
+    /// This is commonly referred by vendors as a "direct SSH tunnel", in
+    /// opposition to "reverse SSH tunnel", which is currently unsupported.
+    Ssh { config: SshTunnelConfig },
+    /// Establish a TCP connection to the database via an AWS PrivateLink
+    /// service.
+    AwsPrivatelink {
+        /// The ID of the AWS PrivateLink service.
+        connection_id: GlobalId,
+    },
+}
+
+/// A SQL Server client with an optional SSH tunnel handle.
+///
+/// This wrapper is intended to be used in place of `tiberius::Client` to
+/// keep the SSH tunnel alive for the lifecycle of the connection by holding
+/// a reference to the tunnel handle.
+#[derive(Debug)]
+pub struct SqlServerConn {
+    client: Client<Compat<TcpStream>>,
+    _ssh_tunnel_handle: Option<ManagedSshTunnelHandle>,
+}
+
+impl Deref for SqlServerConn {
+    type Target = Client<Compat<TcpStream>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl DerefMut for SqlServerConn {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
+impl SqlServerConn {
+    pub async fn close(mut self) -> Result<(), SqlServerError> {
+        self.client.close().await?;
+        self._ssh_tunnel_handle.take();
+        Ok(())
+    }
+}
+
+/// Configuration for SQL Server connections.
+///
+/// This wraps [`tiberius::Config`] to allow the configuration of a
+/// tunnel via a [`TunnelConfig`].
+#[derive(Clone, Debug)]
+pub struct Config {
+    inner: tiberius::Config,
+    // `tiberius::Config` does not expose the host and port it was configured
+    // with, so we keep our own copy to establish the TCP connection.
+    host: String,
+    port: u16,
+    tunnel: TunnelConfig,
+    // Whether to poll I/O for this connection in a tokio task
+    in_task: InTask,
+    ssh_timeout_config: SshTimeoutConfig,
+}
+
+impl Config {
+    /// Creates a new configuration. The `host` and `port` must match the
+    /// values configured on `inner`.
+    pub fn new(
+        inner: tiberius::Config,
+        host: String,
+        port: u16,
+        tunnel: TunnelConfig,
+        ssh_timeout_config: SshTimeoutConfig,
+        in_task: InTask,
+    ) -> Self {
+        Self {
+            inner,
+            host,
+            port,
+            tunnel,
+            in_task,
+            ssh_timeout_config,
+        }
+    }
+
+    pub async fn connect(
+        &self,
+        task_name: &str,
+        ssh_tunnel_manager: &SshTunnelManager,
+    ) -> Result<SqlServerConn, SqlServerError> {
+        let address = format!("sqlserver://{}:{}", self.host, self.port);
+        info!(%task_name, %address, "connecting");
+        match self.connect_internal(ssh_tunnel_manager).await {
+            Ok(t) => {
+                info!(%task_name, %address, "connected");
+                Ok(t)
+            }
+            Err(e) => {
+                warn!(%task_name, %address, "connection failed: {e:#}");
+                Err(e)
+            }
+        }
+    }
+
+    async fn connect_internal(
+        &self,
+        ssh_tunnel_manager: &SshTunnelManager,
+    ) -> Result<SqlServerConn, SqlServerError> {
+        // Note that in all cases we only override the address of the TCP
+        // connection. The configured host is kept on `inner` so that TLS
+        // hostname verification happens against the actual upstream host.
+        let (tcp, ssh_tunnel_handle) = match &self.tunnel {
+            TunnelConfig::Direct { resolved_ips } => {
+                let tcp = match resolved_ips {
+                    Some(ips) => {
+                        let addrs: Vec<_> = ips
+                            .iter()
+                            .map(|ip| SocketAddr::new(*ip, self.port))
+                            .collect();
+                        TcpStream::connect(addrs.as_slice()).await?
+                    }
+                    None => TcpStream::connect((self.host.as_str(), self.port)).await?,
+                };
+                (tcp, None)
+            }
+            TunnelConfig::Ssh { config } => {
+                let tunnel = ssh_tunnel_manager
+                    .connect(
+                        config.clone(),
+                        &self.host,
+                        self.port,
+                        self.ssh_timeout_config,
+                        self.in_task,
+                    )
+                    .await
+                    .map_err(SqlServerError::Ssh)?;
+                let tcp = TcpStream::connect(tunnel.local_addr()).await?;
+                (tcp, Some(tunnel))
+            }
+            TunnelConfig::AwsPrivatelink { connection_id } => {
+                let privatelink_host = mz_cloud_resources::vpc_endpoint_name(*connection_id);
+                let tcp = TcpStream::connect((privatelink_host.as_str(), self.port)).await?;
+                (tcp, None)
+            }
+        };
+        tcp.set_nodelay(true)?;
+
+        // tiberius is written against the `futures` I/O traits, so we adapt
+        // tokio's `TcpStream` with `compat_write`.
+        let inner = self.inner.clone();
+        let client = Client::connect(inner, tcp.compat_write())
+            .run_in_task_if(self.in_task, || "sql_server_connect".to_string())
+            .await?;
+
+        Ok(SqlServerConn {
+            client,
+            _ssh_tunnel_handle: ssh_tunnel_handle,
+        })
+    }
+}
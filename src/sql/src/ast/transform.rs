@@ -18,9 +18,10 @@ use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
     AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateViewStatement, CreateWebhookSourceStatement, Expr, Ident, Query,
-    Raw, RawItemName, Statement, UnresolvedItemName, ViewDefinition,
+    CreateSecretStatement, CreateSequenceStatement, CreateSinkStatement, CreateSourceStatement,
+    CreateSubsourceStatement, CreateTableStatement, CreateViewStatement,
+    CreateWebhookSourceStatement, Expr, Ident, Query, Raw, RawItemName, Statement,
+    UnresolvedItemName, ViewDefinition,
 };
 use crate::names::FullItemName;
 
@@ -45,7 +46,8 @@ pub fn create_stmt_rename_schema_refs(
         | stmt @ Statement::CreateTable(_)
         | stmt @ Statement::CreateIndex(_)
         | stmt @ Statement::CreateType(_)
-        | stmt @ Statement::CreateSecret(_) => {
+        | stmt @ Statement::CreateSecret(_)
+        | stmt @ Statement::CreateSequence(_) => {
             let mut visitor = CreateSqlRewriteSchema {
                 database,
                 cur_schema,
@@ -148,6 +150,7 @@ pub fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String
         | Statement::CreateMaterializedView(CreateMaterializedViewStatement { name, .. })
         | Statement::CreateTable(CreateTableStatement { name, .. })
         | Statement::CreateSecret(CreateSecretStatement { name, .. })
+        | Statement::CreateSequence(CreateSequenceStatement { name, .. })
         | Statement::CreateConnection(CreateConnectionStatement { name, .. })
         | Statement::CreateWebhookSource(CreateWebhookSourceStatement { name, .. }) => {
             // The last name in an ItemName is the item name. The item name
@@ -207,6 +210,7 @@ pub fn create_stmt_rename_refs(
         | Statement::CreateSubsource(_)
         | Statement::CreateTable(_)
         | Statement::CreateSecret(_)
+        | Statement::CreateSequence(_)
         | Statement::CreateConnection(_)
         | Statement::CreateWebhookSource(_) => {}
         item => {
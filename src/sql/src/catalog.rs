@@ -49,7 +49,7 @@ use crate::names::{
 use crate::normalize;
 use crate::plan::statement::ddl::PlannedRoleAttributes;
 use crate::plan::statement::StatementDesc;
-use crate::plan::{query, ClusterSchedule, PlanError, PlanNotice};
+use crate::plan::{query, ClusterSchedule, IdentityColumn, PlanError, PlanNotice, SequenceOptions};
 use crate::session::vars::{OwnedVarInput, SystemVars};
 
 /// A catalog keeps track of SQL objects and session state available to the
@@ -632,6 +632,18 @@ pub trait CatalogItem {
     /// catalog item is a table.
     fn table_details(&self) -> Option<&[Expr<Aug>]>;
 
+    /// Returns the expressions of the generated columns of the catalog item,
+    /// by column index, if the catalog item is a table.
+    fn table_generated_columns(&self) -> Option<&BTreeMap<usize, Expr<Aug>>>;
+
+    /// Returns the identity columns of the catalog item, by column index, if
+    /// the catalog item is a table.
+    fn table_identity_columns(&self) -> Option<&BTreeMap<usize, IdentityColumn>>;
+
+    /// Returns the options of the catalog item, if the catalog item is a
+    /// sequence.
+    fn sequence_options(&self) -> Option<&SequenceOptions>;
+
     /// Returns the type information associated with the catalog item, if the
     /// catalog item is a type.
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>>;
@@ -667,6 +679,8 @@ pub enum CatalogItemType {
     Func,
     /// A secret.
     Secret,
+    /// A sequence.
+    Sequence,
     /// A connection.
     Connection,
 }
@@ -701,6 +715,7 @@ impl CatalogItemType {
             CatalogItemType::Sink => false,
             CatalogItemType::Func => false,
             CatalogItemType::Secret => false,
+            CatalogItemType::Sequence => true,
             CatalogItemType::Connection => false,
         }
     }
@@ -718,6 +733,7 @@ impl fmt::Display for CatalogItemType {
             CatalogItemType::Type => f.write_str("type"),
             CatalogItemType::Func => f.write_str("func"),
             CatalogItemType::Secret => f.write_str("secret"),
+            CatalogItemType::Sequence => f.write_str("sequence"),
             CatalogItemType::Connection => f.write_str("connection"),
         }
     }
@@ -735,6 +751,7 @@ impl From<CatalogItemType> for ObjectType {
             CatalogItemType::Type => ObjectType::Type,
             CatalogItemType::Func => ObjectType::Func,
             CatalogItemType::Secret => ObjectType::Secret,
+            CatalogItemType::Sequence => ObjectType::Sequence,
             CatalogItemType::Connection => ObjectType::Connection,
         }
     }
@@ -752,6 +769,7 @@ impl From<CatalogItemType> for mz_audit_log::ObjectType {
             CatalogItemType::Sink => mz_audit_log::ObjectType::Sink,
             CatalogItemType::Func => mz_audit_log::ObjectType::Func,
             CatalogItemType::Secret => mz_audit_log::ObjectType::Secret,
+            CatalogItemType::Sequence => mz_audit_log::ObjectType::Sequence,
             CatalogItemType::Connection => mz_audit_log::ObjectType::Connection,
         }
     }
@@ -1376,6 +1394,7 @@ pub enum ObjectType {
     Cluster,
     ClusterReplica,
     Secret,
+    Sequence,
     Connection,
     Database,
     Schema,
@@ -1394,6 +1413,7 @@ impl ObjectType {
             | ObjectType::Index
             | ObjectType::Type
             | ObjectType::Secret
+            | ObjectType::Sequence
             | ObjectType::Connection
             | ObjectType::Func
             | ObjectType::Database
@@ -1420,6 +1440,7 @@ impl From<mz_sql_parser::ast::ObjectType> for ObjectType {
             mz_sql_parser::ast::ObjectType::Cluster => ObjectType::Cluster,
             mz_sql_parser::ast::ObjectType::ClusterReplica => ObjectType::ClusterReplica,
             mz_sql_parser::ast::ObjectType::Secret => ObjectType::Secret,
+            mz_sql_parser::ast::ObjectType::Sequence => ObjectType::Sequence,
             mz_sql_parser::ast::ObjectType::Connection => ObjectType::Connection,
             mz_sql_parser::ast::ObjectType::Database => ObjectType::Database,
             mz_sql_parser::ast::ObjectType::Schema => ObjectType::Schema,
@@ -1441,6 +1462,7 @@ impl From<CommentObjectId> for ObjectType {
             CommentObjectId::Connection(_) => ObjectType::Connection,
             CommentObjectId::Type(_) => ObjectType::Type,
             CommentObjectId::Secret(_) => ObjectType::Secret,
+            CommentObjectId::Sequence(_) => ObjectType::Sequence,
             CommentObjectId::Role(_) => ObjectType::Role,
             CommentObjectId::Database(_) => ObjectType::Database,
             CommentObjectId::Schema(_) => ObjectType::Schema,
@@ -1464,6 +1486,7 @@ impl Display for ObjectType {
             ObjectType::Cluster => "CLUSTER",
             ObjectType::ClusterReplica => "CLUSTER REPLICA",
             ObjectType::Secret => "SECRET",
+            ObjectType::Sequence => "SEQUENCE",
             ObjectType::Connection => "CONNECTION",
             ObjectType::Database => "DATABASE",
             ObjectType::Schema => "SCHEMA",
@@ -20,12 +20,15 @@ use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_pgrepr::oid;
 use mz_repr::role_id::RoleId;
-use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, Datum, GlobalId, RelationType, Row, ScalarBaseType, ScalarType,
+};
 use once_cell::sync::Lazy;
 
 use crate::ast::{SelectStatement, Statement};
-use crate::catalog::{CatalogType, TypeCategory, TypeReference};
+use crate::catalog::{CatalogItemType, CatalogType, TypeCategory, TypeReference};
 use crate::names::{self, ResolvedItemName};
+use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
     AggregateFunc, BinaryFunc, CoercibleScalarExpr, ColumnOrder, HirRelationExpr, HirScalarExpr,
@@ -1938,6 +1941,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "cot" => Scalar {
             params!(Float64) => UnaryFunc::Cot(func::Cot) => Float64, 1607;
         },
+        "currval" => Scalar {
+            params!(String) => sequence_func("currval", UnmaterializableFunc::CurrVal) => Int64, 1575;
+        },
         "current_schema" => Scalar {
             // TODO: this should be `name`. This is tricky in Materialize
             // because `name` truncates to 63 characters but Materialize does
@@ -2243,6 +2249,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(UInt32, UInt32) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt32, oid::FUNC_MOD_UINT32_OID;
             params!(UInt64, UInt64) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt64, oid::FUNC_MOD_UINT64_OID;
         },
+        "nextval" => Scalar {
+            params!(String) => sequence_func("nextval", UnmaterializableFunc::NextVal) => Int64, 1574;
+        },
         "now" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp => TimestampTz, 1299;
         },
@@ -4340,6 +4349,28 @@ fn digest(algorithm: &'static str) -> Operation<HirScalarExpr> {
     })
 }
 
+// The sequence functions name their sequence with a string literal, which is
+// resolved when the call is planned. The calls are replaced with their values
+// when the statement is executed.
+fn sequence_func(
+    name: &'static str,
+    func: fn(GlobalId) -> UnmaterializableFunc,
+) -> Operation<HirScalarExpr> {
+    Operation::unary(move |ecx, sequence| {
+        let Some(sequence) = sequence.into_literal_string() else {
+            sql_bail!("{name} requires a string literal as its argument");
+        };
+        let item_name = mz_sql_parser::parser::parse_item_name(&sequence)?;
+        let item = ecx
+            .catalog()
+            .resolve_item(&normalize::unresolved_item_name(item_name)?)?;
+        if item.item_type() != CatalogItemType::Sequence {
+            sql_bail!("{} is not a sequence", sequence.quoted());
+        }
+        Ok(HirScalarExpr::CallUnmaterializable(func(item.id())))
+    })
+}
+
 fn array_to_string(
     ecx: &ExprContext,
     exprs: Vec<HirScalarExpr>,
@@ -23,7 +23,7 @@ use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::{Offset, TopicPartitionList};
 use tokio::time::Duration;
 
-use crate::ast::Value;
+use crate::ast::{Expr, Value};
 use crate::names::Aug;
 use crate::normalize::generate_extracted_config;
 use crate::plan::with_options::{ImpliedValue, TryFromValue};
@@ -33,13 +33,15 @@ generate_extracted_config!(
     KafkaSourceConfigOption,
     (GroupIdPrefix, String),
     (Topic, String),
+    (TopicPattern, String),
     (
         TopicMetadataRefreshInterval,
         Duration,
         Default(DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL)
     ),
     (StartTimestamp, i64),
-    (StartOffset, Vec<i64>)
+    (StartOffset, Vec<i64>),
+    (StartEpoch, u64, Default(0))
 );
 
 generate_extracted_config!(
@@ -49,7 +51,9 @@ generate_extracted_config!(
         KafkaSinkCompressionType,
         Default(KafkaSinkCompressionType::None)
     ),
+    (PartitionBy, Expr<Aug>),
     (ProgressGroupIdPrefix, String),
+    (Tombstones, bool, Default(true)),
     (Topic, String),
     (TransactionalIdPrefix, String),
     (LegacyIds, bool)
@@ -1131,7 +1131,8 @@ impl From<CommentObjectId> for ObjectId {
             | CommentObjectId::Func(global_id)
             | CommentObjectId::Connection(global_id)
             | CommentObjectId::Type(global_id)
-            | CommentObjectId::Secret(global_id) => ObjectId::Item(global_id),
+            | CommentObjectId::Secret(global_id)
+            | CommentObjectId::Sequence(global_id) => ObjectId::Item(global_id),
             CommentObjectId::Role(id) => ObjectId::Role(id),
             CommentObjectId::Database(id) => ObjectId::Database(id),
             CommentObjectId::Schema(id) => ObjectId::Schema(id),
@@ -1184,6 +1185,7 @@ pub enum CommentObjectId {
     Connection(GlobalId),
     Type(GlobalId),
     Secret(GlobalId),
+    Sequence(GlobalId),
     Role(RoleId),
     Database(DatabaseId),
     Schema((ResolvedDatabaseSpecifier, SchemaSpecifier)),
@@ -1771,6 +1773,7 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                     .collect(),
             ),
             Value(v) => Value(self.fold_value(v)),
+            Expr(e) => Expr(self.fold_expr(e)),
             DataType(dt) => DataType(self.fold_data_type(dt)),
             Secret(secret) => {
                 let item_name = self.fold_item_name(secret);
@@ -45,7 +45,7 @@ use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::role_id::RoleId;
 use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
 use mz_sql_parser::ast::{
-    AlterSourceAddSubsourceOption, ConnectionOptionName, QualifiedReplica,
+    AlterSourceAddSubsourceOption, ColumnDef, ConnectionOptionName, QualifiedReplica,
     TransactionIsolationLevel, TransactionMode, UnresolvedItemName, Value, WithOptionValue,
 };
 use mz_storage_types::connections::inline::ReferencedConnection;
@@ -118,6 +118,7 @@ pub enum Plan {
     CreateSource(CreateSourcePlan),
     CreateSources(Vec<CreateSourcePlanBundle>),
     CreateSecret(CreateSecretPlan),
+    CreateSequence(CreateSequencePlan),
     CreateSink(CreateSinkPlan),
     CreateTable(CreateTablePlan),
     CreateView(CreateViewPlan),
@@ -126,6 +127,7 @@ pub enum Plan {
     CreateType(CreateTypePlan),
     Comment(CommentPlan),
     DiscardTemp,
+    DiscardSequences,
     DiscardAll,
     DropObjects(DropObjectsPlan),
     DropOwned(DropOwnedPlan),
@@ -243,6 +245,7 @@ impl Plan {
             StatementKind::CreateRole => &[PlanKind::CreateRole],
             StatementKind::CreateSchema => &[PlanKind::CreateSchema],
             StatementKind::CreateSecret => &[PlanKind::CreateSecret],
+            StatementKind::CreateSequence => &[PlanKind::CreateSequence],
             StatementKind::CreateSink => &[PlanKind::CreateSink],
             StatementKind::CreateSource
             | StatementKind::CreateSubsource
@@ -253,7 +256,12 @@ impl Plan {
             StatementKind::Deallocate => &[PlanKind::Deallocate],
             StatementKind::Declare => &[PlanKind::Declare],
             StatementKind::Delete => &[PlanKind::ReadThenWrite],
-            StatementKind::Discard => &[PlanKind::DiscardAll, PlanKind::DiscardTemp],
+            StatementKind::Merge => &[PlanKind::ReadThenWrite],
+            StatementKind::Discard => &[
+                PlanKind::DiscardAll,
+                PlanKind::DiscardTemp,
+                PlanKind::DiscardSequences,
+            ],
             StatementKind::DropObjects => &[PlanKind::DropObjects],
             StatementKind::DropOwned => &[PlanKind::DropOwned],
             StatementKind::Execute => &[PlanKind::Execute],
@@ -303,6 +311,7 @@ impl Plan {
             Plan::CreateSource(_) => "create source",
             Plan::CreateSources(_) => "create source",
             Plan::CreateSecret(_) => "create secret",
+            Plan::CreateSequence(_) => "create sequence",
             Plan::CreateSink(_) => "create sink",
             Plan::CreateTable(_) => "create table",
             Plan::CreateView(_) => "create view",
@@ -311,6 +320,7 @@ impl Plan {
             Plan::CreateType(_) => "create type",
             Plan::Comment(_) => "comment",
             Plan::DiscardTemp => "discard temp",
+            Plan::DiscardSequences => "discard sequences",
             Plan::DiscardAll => "discard all",
             Plan::DropObjects(plan) => match plan.object_type {
                 ObjectType::Table => "drop table",
@@ -324,6 +334,7 @@ impl Plan {
                 ObjectType::Cluster => "drop clusters",
                 ObjectType::ClusterReplica => "drop cluster replicas",
                 ObjectType::Secret => "drop secret",
+                ObjectType::Sequence => "drop sequence",
                 ObjectType::Connection => "drop connection",
                 ObjectType::Database => "drop database",
                 ObjectType::Schema => "drop schema",
@@ -363,6 +374,7 @@ impl Plan {
                 ObjectType::Cluster => "alter cluster",
                 ObjectType::ClusterReplica => "alter cluster replica",
                 ObjectType::Secret => "alter secret",
+                ObjectType::Sequence => "alter sequence",
                 ObjectType::Connection => "alter connection",
                 ObjectType::Database => "alter database",
                 ObjectType::Schema => "alter schema",
@@ -396,6 +408,7 @@ impl Plan {
                 ObjectType::Cluster => "alter cluster owner",
                 ObjectType::ClusterReplica => "alter cluster replica owner",
                 ObjectType::Secret => "alter secret owner",
+                ObjectType::Sequence => "alter sequence owner",
                 ObjectType::Connection => "alter connection owner",
                 ObjectType::Database => "alter database owner",
                 ObjectType::Schema => "alter schema owner",
@@ -406,8 +419,10 @@ impl Plan {
             Plan::Close(_) => "close",
             Plan::ReadThenWrite(plan) => match plan.kind {
                 MutationKind::Insert => "insert into select",
+                MutationKind::Upsert => "insert on conflict",
                 MutationKind::Update => "update",
                 MutationKind::Delete => "delete",
+                MutationKind::Merge => "merge",
             },
             Plan::Prepare(_) => "prepare",
             Plan::Execute(_) => "execute",
@@ -603,6 +618,13 @@ pub struct CreateSecretPlan {
     pub if_not_exists: bool,
 }
 
+#[derive(Debug)]
+pub struct CreateSequencePlan {
+    pub name: QualifiedItemName,
+    pub sequence: Sequence,
+    pub if_not_exists: bool,
+}
+
 #[derive(Debug)]
 pub struct CreateSinkPlan {
     pub name: QualifiedItemName,
@@ -794,10 +816,25 @@ pub struct ShowColumnsPlan {
 #[derive(Debug)]
 pub struct CopyFromPlan {
     pub id: GlobalId,
+    pub source: CopyFromSource,
     pub columns: Vec<usize>,
     pub params: CopyFormatParams<'static>,
 }
 
+#[derive(Debug)]
+pub enum CopyFromSource {
+    /// Copying from a file local to the user, transmitted via pgwire.
+    Stdin,
+    /// A remote resource, e.g. S3.
+    Url {
+        /// The scalar expression to be resolved to get the source uri.
+        url: HirScalarExpr,
+        connection: mz_storage_types::connections::Connection<ReferencedConnection>,
+        /// The ID of the connection.
+        connection_id: GlobalId,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct CopyToPlan {
     /// The select query plan whose data will be copied to destination uri.
@@ -948,12 +985,18 @@ pub struct SendDiffsPlan {
     pub kind: MutationKind,
     pub returning: Vec<(Row, NonZeroUsize)>,
     pub max_result_size: u64,
+    /// The number of affected rows, if it cannot be derived from `updates`.
+    pub affected_rows: Option<usize>,
 }
 
 #[derive(Debug)]
 pub struct InsertPlan {
     pub id: GlobalId,
     pub values: HirRelationExpr,
+    /// The defaults of the omitted columns that call `nextval`, by column
+    /// index. They are evaluated for every row when it is written, in place
+    /// of the `NULL` the row holds for the column.
+    pub sequence_defaults: BTreeMap<usize, mz_expr::MirScalarExpr>,
     pub returning: Vec<mz_expr::MirScalarExpr>,
 }
 
@@ -964,6 +1007,8 @@ pub struct ReadThenWritePlan {
     pub finishing: RowSetFinishing,
     pub assignments: BTreeMap<usize, mz_expr::MirScalarExpr>,
     pub kind: MutationKind,
+    /// See [`InsertPlan::sequence_defaults`].
+    pub sequence_defaults: BTreeMap<usize, mz_expr::MirScalarExpr>,
     pub returning: Vec<mz_expr::MirScalarExpr>,
 }
 
@@ -1017,6 +1062,18 @@ pub enum AlterSourceAction {
         subsources: Vec<CreateSourcePlanBundle>,
         options: Vec<AlterSourceAddSubsourceOption<Aug>>,
     },
+    RefreshSchema {
+        /// The hex-encoded details of the source, describing the refreshed
+        /// upstream tables.
+        details: String,
+        /// The new columns of each refreshed subsource.
+        subsources: BTreeMap<GlobalId, Vec<ColumnDef<Aug>>>,
+    },
+    ResetTo {
+        /// The offset of each partition of the topic at which the source
+        /// resumes reading.
+        start_offsets: Vec<i64>,
+    },
 }
 
 #[derive(Debug)]
@@ -1248,10 +1305,38 @@ pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
+    /// The expressions that compute the table's generated columns, by column
+    /// index.
+    pub generated_columns: BTreeMap<usize, Expr<Aug>>,
+    pub checks: Vec<TableCheck>,
+    /// The table's identity columns, by column index.
+    pub identity_columns: BTreeMap<usize, IdentityColumn>,
+    /// The keys of the table that were declared `UNIQUE NULLS NOT DISTINCT`.
+    pub nulls_not_distinct_keys: Vec<Vec<usize>>,
     pub temporary: bool,
     pub compaction_window: Option<CompactionWindow>,
 }
 
+/// A `CHECK` constraint on the rows of a table.
+#[derive(Clone, Debug, Serialize)]
+pub struct TableCheck {
+    /// The name of the constraint.
+    pub name: String,
+    /// The condition, in terms of the table's columns. Rows for which it is
+    /// false violate the constraint.
+    pub expr: MirScalarExpr,
+}
+
+/// A `GENERATED ... AS IDENTITY` column of a table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityColumn {
+    /// Whether the column is `GENERATED ALWAYS`, i.e., rejects explicitly
+    /// provided values.
+    pub always: bool,
+    /// The options of the column's implicit sequence.
+    pub options: SequenceOptions,
+}
+
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
@@ -1399,6 +1484,67 @@ pub struct Secret {
     pub secret_as: MirScalarExpr,
 }
 
+#[derive(Clone, Debug)]
+pub struct Sequence {
+    pub create_sql: String,
+    pub options: SequenceOptions,
+}
+
+/// The options that determine the values a sequence produces.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceOptions {
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub start: i64,
+    /// The number of values to reserve durably at a time.
+    pub cache: u64,
+    pub cycle: bool,
+}
+
+impl SequenceOptions {
+    /// Returns the `n`th value (counting from zero) that the sequence
+    /// produces, or `None` if the sequence is exhausted by then.
+    pub fn nth_value(&self, n: u64) -> Option<i64> {
+        let n = i128::from(n);
+        let increment = i128::from(self.increment);
+        let (min, max, start) = (
+            i128::from(self.min_value),
+            i128::from(self.max_value),
+            i128::from(self.start),
+        );
+        // The number of values produced before reaching the limit for the first
+        // time, and on every lap after cycling back to the other end.
+        let (first_lap, lap) = if increment > 0 {
+            ((max - start) / increment + 1, (max - min) / increment + 1)
+        } else {
+            ((start - min) / -increment + 1, (max - min) / -increment + 1)
+        };
+        let value = if n < first_lap {
+            start + n * increment
+        } else if self.cycle {
+            let offset = (n - first_lap) % lap;
+            if increment > 0 {
+                min + offset * increment
+            } else {
+                max + offset * increment
+            }
+        } else {
+            return None;
+        };
+        Some(i64::try_from(value).expect("value within bounds"))
+    }
+
+    /// Returns the limit that the sequence reaches when it is exhausted.
+    pub fn limit(&self) -> i64 {
+        if self.increment > 0 {
+            self.max_value
+        } else {
+            self.min_value
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Sink {
     pub create_sql: String,
@@ -1517,8 +1663,11 @@ impl QueryWhen {
 #[derive(Debug, Copy, Clone)]
 pub enum MutationKind {
     Insert,
+    /// An `INSERT ... ON CONFLICT DO UPDATE`.
+    Upsert,
     Update,
     Delete,
+    Merge,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
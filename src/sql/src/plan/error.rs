@@ -30,6 +30,7 @@ use mz_repr::{strconv, ColumnName, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{IdentError, UnresolvedItemName};
 use mz_sql_parser::parser::{ParserError, ParserStatementError};
+use mz_sql_server_util::SqlServerError;
 
 use crate::catalog::{
     CatalogError, CatalogItemType, ErrorMessageObjectDescription, SystemObjectType,
@@ -40,8 +41,10 @@ use crate::plan::scope::ScopeItem;
 use crate::plan::typeconv::CastContext;
 use crate::plan::ObjectType;
 use crate::pure::error::{
-    CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
+    CsrPurificationError, FileSourcePurificationError, KafkaSinkPurificationError,
+    KafkaSourcePurificationError, KinesisSourcePurificationError,
     LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    SqlServerSourcePurificationError,
 };
 use crate::session::vars::VarError;
 
@@ -151,6 +154,9 @@ pub enum PlanError {
     MySqlConnectionErr {
         cause: Arc<MySqlError>,
     },
+    SqlServerConnectionErr {
+        cause: Arc<SqlServerError>,
+    },
     SubsourceNameConflict {
         name: UnresolvedItemName,
         upstream_references: Vec<UnresolvedItemName>,
@@ -227,6 +233,9 @@ pub enum PlanError {
     LoadGeneratorSourcePurification(LoadGeneratorSourcePurificationError),
     CsrPurification(CsrPurificationError),
     MySqlSourcePurification(MySqlSourcePurificationError),
+    SqlServerSourcePurification(SqlServerSourcePurificationError),
+    FileSourcePurification(FileSourcePurificationError),
+    KinesisSourcePurification(KinesisSourcePurificationError),
     MissingName(CatalogItemType),
     InvalidRefreshAt,
     InvalidRefreshEveryAlignedTo,
@@ -298,6 +307,9 @@ impl PlanError {
             Self::InternalFunctionCall => Some("This function is for the internal use of the database system and cannot be called directly.".into()),
             Self::PgSourcePurification(e) => e.detail(),
             Self::MySqlSourcePurification(e) => e.detail(),
+            Self::SqlServerSourcePurification(e) => e.detail(),
+            Self::FileSourcePurification(e) => e.detail(),
+            Self::KinesisSourcePurification(e) => e.detail(),
             Self::KafkaSourcePurification(e) => e.detail(),
             Self::LoadGeneratorSourcePurification(e) => e.detail(),
             Self::CsrPurification(e) => e.detail(),
@@ -399,6 +411,9 @@ impl PlanError {
             Self::LoadGeneratorSourcePurification(e) => e.hint(),
             Self::CsrPurification(e) => e.hint(),
             Self::KafkaSinkPurification(e) => e.hint(),
+            Self::FileSourcePurification(e) => e.hint(),
+            Self::KinesisSourcePurification(e) => e.hint(),
+            Self::SqlServerSourcePurification(e) => e.hint(),
             Self::UnknownColumn { table, similar, .. } => {
                 let suffix = "Make sure to surround case sensitive names in double quotes.";
                 match &similar[..] {
@@ -562,6 +577,9 @@ impl fmt::Display for PlanError {
             Self::MySqlConnectionErr { cause } => {
                 write!(f, "failed to connect to MySQL database: {}", cause)
             }
+            Self::SqlServerConnectionErr { cause } => {
+                write!(f, "failed to connect to SQL Server database: {}", cause)
+            }
             Self::SubsourceNameConflict {
                 name , upstream_references: _,
             } => {
@@ -662,6 +680,9 @@ impl fmt::Display for PlanError {
             Self::KafkaSinkPurification(e) => write!(f, "KAFKA sink validation: {}", e),
             Self::CsrPurification(e) => write!(f, "CONFLUENT SCHEMA REGISTRY validation: {}", e),
             Self::MySqlSourcePurification(e) => write!(f, "MYSQL source validation: {}", e),
+            Self::SqlServerSourcePurification(e) => write!(f, "SQL SERVER source validation: {}", e),
+            Self::FileSourcePurification(e) => write!(f, "file source validation: {}", e),
+            Self::KinesisSourcePurification(e) => write!(f, "KINESIS source validation: {}", e),
             Self::MangedReplicaName(name) => {
                 write!(f, "{name} is reserved for replicas of managed clusters")
             }
@@ -801,6 +822,12 @@ impl From<MySqlError> for PlanError {
     }
 }
 
+impl From<SqlServerError> for PlanError {
+    fn from(e: SqlServerError) -> PlanError {
+        PlanError::SqlServerConnectionErr { cause: Arc::new(e) }
+    }
+}
+
 impl From<VarError> for PlanError {
     fn from(e: VarError) -> Self {
         PlanError::VarError(e)
@@ -843,6 +870,24 @@ impl From<MySqlSourcePurificationError> for PlanError {
     }
 }
 
+impl From<SqlServerSourcePurificationError> for PlanError {
+    fn from(e: SqlServerSourcePurificationError) -> Self {
+        PlanError::SqlServerSourcePurification(e)
+    }
+}
+
+impl From<FileSourcePurificationError> for PlanError {
+    fn from(e: FileSourcePurificationError) -> Self {
+        PlanError::FileSourcePurification(e)
+    }
+}
+
+impl From<KinesisSourcePurificationError> for PlanError {
+    fn from(e: KinesisSourcePurificationError) -> Self {
+        PlanError::KinesisSourcePurification(e)
+    }
+}
+
 impl From<IdentError> for PlanError {
     fn from(e: IdentError) -> Self {
         PlanError::InvalidIdent(e)
@@ -13,6 +13,7 @@
 //! representation via a call to lower().
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
 use std::fmt::{Display, Formatter};
 use std::{fmt, mem};
 
@@ -1937,6 +1938,150 @@ impl HirRelationExpr {
         })?;
         Ok(contains)
     }
+
+    /// Calls `f` on each call to a sequence function, i.e., `nextval` or
+    /// `currval`, in the expression, in a deterministic order, allowing `f` to
+    /// replace the call.
+    pub fn visit_sequence_calls_mut<F, E>(&mut self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&mut HirScalarExpr) -> Result<(), E>,
+    {
+        #[allow(deprecated)]
+        self.visit_scalar_expressions_mut(0, &mut |e: &mut HirScalarExpr, _: usize| {
+            e.visit_recursively_mut(0, &mut |_: usize, e: &mut HirScalarExpr| {
+                if let HirScalarExpr::CallUnmaterializable(
+                    UnmaterializableFunc::NextVal(_) | UnmaterializableFunc::CurrVal(_),
+                ) = e
+                {
+                    f(e)?;
+                }
+                Ok(())
+            })
+        })
+    }
+
+    /// Returns the sequences whose functions the expression calls.
+    pub fn sequence_ids(&self) -> BTreeSet<GlobalId> {
+        let mut ids = BTreeSet::new();
+        #[allow(deprecated)]
+        let _ = self.visit_scalar_expressions(0, &mut |e: &HirScalarExpr, _: usize| {
+            e.visit_recursively(0, &mut |_: usize, e: &HirScalarExpr| {
+                if let HirScalarExpr::CallUnmaterializable(
+                    UnmaterializableFunc::NextVal(id) | UnmaterializableFunc::CurrVal(id),
+                ) = e
+                {
+                    ids.insert(*id);
+                }
+                Ok::<_, Infallible>(())
+            })
+        });
+        ids
+    }
+
+    /// Reports whether the expression calls `nextval` in a place where the
+    /// call may be evaluated more than once, e.g., for every row of a relation
+    /// with more than one row.
+    ///
+    /// The analysis is conservative: it only considers calls evaluated once if
+    /// the relations they are evaluated for evidently have at most one row.
+    pub fn calls_nextval_repeatedly(&self) -> bool {
+        self.calls_nextval_repeatedly_inner(true)
+    }
+
+    /// Like [`HirRelationExpr::calls_nextval_repeatedly`], where `once`
+    /// indicates whether the expression itself is evaluated at most once.
+    fn calls_nextval_repeatedly_inner(&self, once: bool) -> bool {
+        use HirRelationExpr::*;
+        match self {
+            Constant { .. } | Get { .. } => false,
+            LetRec { bindings, body, .. } => {
+                bindings
+                    .iter()
+                    .any(|(_, _, value, _)| value.calls_nextval_repeatedly_inner(false))
+                    || body.calls_nextval_repeatedly_inner(once)
+            }
+            Let { value, body, .. } => {
+                value.calls_nextval_repeatedly_inner(once)
+                    || body.calls_nextval_repeatedly_inner(once)
+            }
+            Project { input, .. } | Distinct { input } | Negate { input } | Threshold { input } => {
+                input.calls_nextval_repeatedly_inner(once)
+            }
+            Map {
+                input,
+                scalars: exprs,
+            }
+            | Filter {
+                input,
+                predicates: exprs,
+            } => {
+                let once_per_row = once && input.at_most_one_row();
+                input.calls_nextval_repeatedly_inner(once)
+                    || exprs
+                        .iter()
+                        .any(|expr| expr.calls_nextval_repeatedly(once_per_row))
+            }
+            CallTable { exprs, .. } => exprs.iter().any(|expr| expr.calls_nextval_repeatedly(once)),
+            Join {
+                left, right, on, ..
+            } => {
+                let once_per_row = once && left.at_most_one_row() && right.at_most_one_row();
+                left.calls_nextval_repeatedly_inner(once)
+                    || right.calls_nextval_repeatedly_inner(once)
+                    || on.calls_nextval_repeatedly(once_per_row)
+            }
+            Reduce {
+                input, aggregates, ..
+            } => {
+                let once_per_row = once && input.at_most_one_row();
+                input.calls_nextval_repeatedly_inner(once)
+                    || aggregates
+                        .iter()
+                        .any(|aggregate| aggregate.expr.calls_nextval_repeatedly(once_per_row))
+            }
+            TopK { input, limit, .. } => {
+                input.calls_nextval_repeatedly_inner(once)
+                    || limit
+                        .as_ref()
+                        .map_or(false, |limit| limit.calls_nextval_repeatedly(once))
+            }
+            Union { base, inputs } => {
+                base.calls_nextval_repeatedly_inner(once)
+                    || inputs
+                        .iter()
+                        .any(|input| input.calls_nextval_repeatedly_inner(once))
+            }
+        }
+    }
+
+    /// Reports whether the expression evidently evaluates to at most one row.
+    fn at_most_one_row(&self) -> bool {
+        use HirRelationExpr::*;
+        match self {
+            Constant { rows, .. } => rows.len() <= 1,
+            CallTable {
+                func: TableFunc::Wrap { width, .. },
+                exprs,
+            } => exprs.len() <= *width,
+            Project { input, .. }
+            | Map { input, .. }
+            | Filter { input, .. }
+            | Distinct { input }
+            | TopK { input, .. } => input.at_most_one_row(),
+            Reduce {
+                input, group_key, ..
+            } => group_key.is_empty() || input.at_most_one_row(),
+            Join {
+                left, right, kind, ..
+            } => {
+                !matches!(kind, JoinKind::FullOuter)
+                    && left.at_most_one_row()
+                    && right.at_most_one_row()
+            }
+            Let { body, .. } => body.at_most_one_row(),
+            _ => false,
+        }
+    }
 }
 
 impl CollectionPlan for HirRelationExpr {
@@ -2664,6 +2809,38 @@ impl HirScalarExpr {
         contains
     }
 
+    /// Whether the expression contains a call to `nextval`.
+    pub fn contains_nextval(&self) -> bool {
+        let mut contains = false;
+        #[allow(deprecated)]
+        self.visit_post_nolimit(&mut |e| {
+            if let Self::CallUnmaterializable(UnmaterializableFunc::NextVal(_)) = e {
+                contains = true;
+            }
+        });
+        contains
+    }
+
+    /// Reports whether the expression calls `nextval` in a place where the
+    /// call may be evaluated more than once, where `once` indicates whether
+    /// the expression itself is evaluated at most once. See
+    /// [`HirRelationExpr::calls_nextval_repeatedly`].
+    fn calls_nextval_repeatedly(&self, once: bool) -> bool {
+        match self {
+            HirScalarExpr::CallUnmaterializable(UnmaterializableFunc::NextVal(_)) => !once,
+            HirScalarExpr::Exists(expr) | HirScalarExpr::Select(expr) => {
+                expr.calls_nextval_repeatedly_inner(once)
+            }
+            _ => {
+                let mut calls = false;
+                self.visit_children(|expr: &HirScalarExpr| {
+                    calls = calls || expr.calls_nextval_repeatedly(once)
+                });
+                calls
+            }
+        }
+    }
+
     /// Constructs a column reference in the current scope.
     pub fn column(index: usize) -> HirScalarExpr {
         HirScalarExpr::Column(ColumnRef {
@@ -60,15 +60,16 @@ use mz_sql_parser::ast::{
     visit, AsOf, Assignment, AstInfo, CreateWebhookSourceBody, CreateWebhookSourceCheck,
     CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, DeleteStatement, Distinct,
     Expr, Function, FunctionArgs, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join,
-    JoinConstraint, JoinOperator, Limit, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName,
-    OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr, SetOperator,
-    ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableWithJoins, UnresolvedItemName,
+    JoinConstraint, JoinOperator, Limit, MergeAction, MergeStatement, MutRecBlock,
+    MutRecBlockOption, MutRecBlockOptionName, OnConflict, OnConflictAction, OrderByExpr, Query,
+    Select, SelectItem, SelectOption, SelectOptionName, SetExpr, SetOperator, ShowStatement,
+    SubscriptPosition, TableAlias, TableFactor, TableWithJoins, UnresolvedItemName,
     UpdateStatement, Value, Values, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
 };
 use mz_sql_parser::ident;
 use uuid::Uuid;
 
-use crate::catalog::{CatalogItemType, CatalogType, SessionCatalog};
+use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, SessionCatalog};
 use crate::func::{self, Func, FuncSpec};
 use crate::names::{
     Aug, FullItemName, PartialItemName, ResolvedDataType, ResolvedItemName, SchemaSpecifier,
@@ -197,16 +198,28 @@ fn try_push_projection_order_by(
     }
 }
 
+/// Plans the rows that an `INSERT` into `table_name` inserts.
+///
+/// If `ordinals` is set, the rows carry an additional trailing `int8` column
+/// that numbers the rows of a `VALUES` clause in the order they are listed.
+/// Rows of other sources all have the ordinal `0`.
+///
+/// The defaults of omitted columns that call `nextval` must produce a value
+/// for every row, so they are not planned as part of the rows. Instead, the
+/// rows hold `NULL` in these columns, and the defaults are returned by column
+/// index, to be evaluated for every row when it is written.
 pub fn plan_insert_query(
     scx: &StatementContext,
     table_name: ResolvedItemName,
     columns: Vec<Ident>,
     source: InsertSource<Aug>,
     returning: Vec<SelectItem<Aug>>,
+    ordinals: bool,
 ) -> Result<
     (
         GlobalId,
         HirRelationExpr,
+        BTreeMap<usize, HirScalarExpr>,
         PlannedRootQuery<Vec<HirScalarExpr>>,
     ),
     PlanError,
@@ -289,20 +302,21 @@ pub fn plan_insert_query(
                     offset: None,
                 } if ctes.is_empty() && order_by.is_empty() => {
                     let names: Vec<_> = ordering.iter().map(|i| desc.get_name(*i)).collect();
-                    plan_values_insert(&qcx, &names, &source_types, &values)?
+                    plan_values_insert(&qcx, &names, &source_types, &values, ordinals)?
                 }
                 _ => {
                     let (expr, _scope) = plan_nested_query(&mut qcx, &query)?;
-                    expr
+                    map_ordinal(expr, ordinals)
                 }
             }
         }
-        InsertSource::DefaultValues => {
-            HirRelationExpr::constant(vec![vec![]], RelationType::empty())
-        }
+        InsertSource::DefaultValues => map_ordinal(
+            HirRelationExpr::constant(vec![vec![]], RelationType::empty()),
+            ordinals,
+        ),
     };
 
-    let expr_arity = expr.arity();
+    let expr_arity = expr.arity() - usize::from(ordinals);
 
     // Validate that the arity of the source query is at most the size of declared columns or the
     // size of the table if none are declared
@@ -323,9 +337,22 @@ pub fn plan_insert_query(
     source_types.truncate(expr_arity);
     ordering.truncate(expr_arity);
 
+    let generated = plan_generated_columns(scx, table, &desc)?;
+    if let Some(idx) = ordering
+        .iter()
+        .find(|idx| is_default_only(table, &generated, **idx))
+    {
+        sql_bail!(
+            "cannot insert a non-DEFAULT value into column {}",
+            desc.get_name(*idx).as_str().quoted()
+        );
+    }
+
     // Ensure the types of the source query match the types of the target table,
     // installing assignment casts where necessary and possible.
-    let expr = cast_relation(&qcx, CastContext::Assignment, expr, source_types).map_err(|e| {
+    let ordinal_type = ordinals.then_some(&ScalarType::Int64);
+    let target_types = source_types.into_iter().chain(ordinal_type);
+    let expr = cast_relation(&qcx, CastContext::Assignment, expr, target_types).map_err(|e| {
         sql_err!(
             "column {} is of type {} but expression is of type {}",
             desc.get_name(ordering[e.column]).as_str().quoted(),
@@ -337,6 +364,7 @@ pub fn plan_insert_query(
     // Fill in any omitted columns and rearrange into correct order
     let mut map_exprs = vec![];
     let mut project_key = Vec::with_capacity(desc.arity());
+    let mut sequence_defaults = BTreeMap::new();
 
     // Maps from table column index to position in the source query
     let col_to_source: BTreeMap<_, _> = ordering.iter().enumerate().map(|(a, b)| (b, a)).collect();
@@ -346,11 +374,18 @@ pub fn plan_insert_query(
         if let Some(src_idx) = col_to_source.get(&col_idx) {
             project_key.push(*src_idx);
         } else {
-            let hir = plan_default_expr(scx, &default, &col_typ.scalar_type)?;
-            project_key.push(expr_arity + map_exprs.len());
+            let mut hir = plan_default_expr(scx, &default, &col_typ.scalar_type)?;
+            if hir.contains_nextval() {
+                let null = HirScalarExpr::literal_null(col_typ.scalar_type.clone());
+                sequence_defaults.insert(col_idx, mem::replace(&mut hir, null));
+            }
+            project_key.push(expr_arity + usize::from(ordinals) + map_exprs.len());
             map_exprs.push(hir);
         }
     }
+    if ordinals {
+        project_key.push(expr_arity);
+    }
 
     let returning = {
         let (scope, typ) = if let ResolvedItemName::Item { full_name, .. } = table_name {
@@ -405,11 +440,228 @@ pub fn plan_insert_query(
 
     Ok((
         table.id(),
-        expr.map(map_exprs).project(project_key),
+        map_generated_columns(expr.map(map_exprs).project(project_key), generated),
+        sequence_defaults,
         returning,
     ))
 }
 
+/// Plans the `ON CONFLICT` clause of an `INSERT` into `table_name` whose rows
+/// to insert, as planned by [`plan_insert_query`] with ordinals, are
+/// `excluded`, and whose columns with the defaults `sequence_defaults` are
+/// filled in when the rows are written.
+///
+/// Conflicts are found by left joining the rows to insert with the rows of the
+/// table on the columns of the conflicting unique keys. For `DO NOTHING`, the
+/// selection produces the rows to insert that do not conflict, keeping only
+/// the first of the rows that conflict with each other. For `DO
+/// UPDATE`, it produces rows of the form `[existing, excluded, conflicted]`,
+/// where `existing` is the conflicting row of the table, if any, and
+/// `conflicted` is `true` if `existing` is to be updated with the assignments
+/// and `NULL` if `excluded` is to be inserted instead.
+pub fn plan_insert_on_conflict(
+    scx: &StatementContext,
+    table_name: ResolvedItemName,
+    excluded: HirRelationExpr,
+    sequence_defaults: &BTreeMap<usize, HirScalarExpr>,
+    OnConflict { target, action }: OnConflict<Aug>,
+) -> Result<ReadThenWritePlan, PlanError> {
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let table = scx.get_item_by_resolved_name(&table_name)?;
+    let id = table.id();
+    let desc = table.desc(&scx.catalog.resolve_full_name(table.name()))?;
+    let arity = desc.arity();
+
+    // Determine the unique keys whose conflicts are handled.
+    let mut target_columns = BTreeSet::new();
+    for column in target {
+        let column = normalize::column_name(column);
+        match desc.get_by_name(&column) {
+            Some((idx, _)) => {
+                target_columns.insert(idx);
+            }
+            None => sql_bail!("column {} does not exist", column.as_str().quoted()),
+        }
+    }
+    let keys: Vec<&Vec<usize>> = if target_columns.is_empty() {
+        if let OnConflictAction::DoUpdate { .. } = action {
+            sql_bail!("ON CONFLICT DO UPDATE requires inference specification or constraint name");
+        }
+        desc.typ().keys.iter().collect()
+    } else {
+        match desc
+            .typ()
+            .keys
+            .iter()
+            .find(|key| key.iter().copied().collect::<BTreeSet<_>>() == target_columns)
+        {
+            Some(key) => vec![key],
+            None => sql_bail!(
+                "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+            ),
+        }
+    };
+
+    // The `NULL` values of identity columns and of columns whose default calls
+    // `nextval` are filled in with new values when the rows are written, so
+    // they never conflict.
+    let filled: BTreeSet<usize> = table
+        .table_identity_columns()
+        .into_iter()
+        .flat_map(|identity_columns| identity_columns.keys())
+        .chain(sequence_defaults.keys())
+        .copied()
+        .collect();
+
+    // Rows conflict if they agree on all columns of any of the keys. As when
+    // enforcing keys, NULLs are considered equal.
+    let on = keys
+        .iter()
+        .map(|key| {
+            HirScalarExpr::variadic_and(
+                key.iter()
+                    .map(|idx| {
+                        let excluded = HirScalarExpr::column(*idx);
+                        let existing = HirScalarExpr::column(arity + 1 + *idx);
+                        let eq = excluded
+                            .clone()
+                            .call_binary(existing.clone(), BinaryFunc::Eq);
+                        if desc.typ().column_types[*idx].nullable && !filled.contains(idx) {
+                            eq.or(excluded.call_is_null().and(existing.call_is_null()))
+                        } else {
+                            eq
+                        }
+                    })
+                    .collect(),
+            )
+        })
+        .reduce(HirScalarExpr::or)
+        .unwrap_or_else(HirScalarExpr::literal_false);
+
+    let (get, table_scope) = qcx.resolve_table_name(table_name)?;
+    let rows = excluded.join(
+        get.map(vec![HirScalarExpr::literal_true()]),
+        on,
+        JoinKind::LeftOuter,
+    );
+    let conflicted = HirScalarExpr::column(2 * arity + 1);
+
+    let (selection, assignments, project) = match action {
+        OnConflictAction::DoNothing => {
+            // Of the rows that conflict with each other on a key, only the one
+            // with the lowest ordinal is inserted, as if the rows were inserted
+            // one after the other. With several keys, a row that conflicts
+            // with an earlier row is skipped even if that row is itself
+            // skipped because of a conflict on another key. Keys with filled
+            // in columns are left to be enforced when the rows are written.
+            let mut selection = rows.filter(vec![conflicted.call_is_null()]);
+            for key in keys
+                .into_iter()
+                .filter(|key| !key.iter().any(|idx| filled.contains(idx)))
+            {
+                selection = selection.top_k(
+                    key.clone(),
+                    vec![ColumnOrder {
+                        column: arity,
+                        desc: false,
+                        nulls_last: false,
+                    }],
+                    Some(HirScalarExpr::literal(Datum::Int64(1), ScalarType::Int64)),
+                    0,
+                    None,
+                );
+            }
+            (selection, BTreeMap::new(), (0..arity).collect())
+        }
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => {
+            let rows = rows.project(
+                (arity + 1..2 * arity + 1)
+                    .chain(0..arity)
+                    .chain(iter::once(2 * arity + 1))
+                    .collect(),
+            );
+            let excluded_name = PartialItemName {
+                database: None,
+                schema: None,
+                item: "excluded".into(),
+            };
+            let scope =
+                table_scope.product(Scope::from_source(Some(excluded_name), desc.iter_names()))?;
+            let mut relation_type = qcx.relation_type(&rows);
+            relation_type.column_types.truncate(2 * arity);
+
+            let mut sets = BTreeMap::new();
+            for Assignment { id, value } in assignments {
+                let name = normalize::column_name(id);
+                match desc.get_by_name(&name) {
+                    Some((idx, typ)) => {
+                        let ecx = &ExprContext {
+                            qcx: &qcx,
+                            name: "SET clause",
+                            scope: &scope,
+                            relation_type: &relation_type,
+                            allow_aggregates: false,
+                            allow_subqueries: false,
+                            allow_parameters: true,
+                            allow_windows: false,
+                        };
+                        let expr = plan_expr(ecx, &value)?.cast_to(
+                            ecx,
+                            CastContext::Assignment,
+                            &typ.scalar_type,
+                        )?;
+
+                        if sets.insert(idx, expr).is_some() {
+                            sql_bail!("column {} set twice", name)
+                        }
+                    }
+                    None => sql_bail!("unknown column {}", name),
+                };
+            }
+
+            plan_generated_assignments(scx, table, &desc, &mut sets)?;
+
+            // Conflicting rows that do not satisfy the WHERE clause are
+            // neither updated nor inserted.
+            let rows = match selection {
+                Some(expr) => {
+                    let ecx = &ExprContext {
+                        qcx: &qcx,
+                        name: "WHERE clause",
+                        scope: &scope,
+                        relation_type: &relation_type,
+                        allow_aggregates: false,
+                        allow_subqueries: true,
+                        allow_parameters: true,
+                        allow_windows: false,
+                    };
+                    let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
+                    rows.filter(vec![conflicted.call_is_null().or(expr)])
+                }
+                None => rows,
+            };
+            (rows, sets, (0..=2 * arity).collect())
+        }
+    };
+
+    let finishing = RowSetFinishing {
+        order_by: vec![],
+        limit: None,
+        offset: 0,
+        project,
+    };
+
+    Ok(ReadThenWritePlan {
+        id,
+        selection,
+        finishing,
+        assignments,
+    })
+}
+
 pub fn plan_copy_item(
     scx: &StatementContext,
     item_name: ResolvedItemName,
@@ -475,8 +727,8 @@ pub fn plan_copy_from(
         );
     }
 
-    let _ = table
-        .table_details()
+    let generated = table
+        .table_generated_columns()
         .expect("attempted to insert into non-table");
 
     if table.id().is_system() {
@@ -485,7 +737,42 @@ pub fn plan_copy_from(
             table_name.full_name_str()
         );
     }
+
+    // Generated columns are computed from the others, so they are left out
+    // when no columns are specified, as in PostgreSQL.
+    let columns = if columns.is_empty() && !generated.is_empty() {
+        let desc = table.desc(&scx.catalog.resolve_full_name(table.name()))?;
+        desc.iter_names()
+            .enumerate()
+            .filter(|(idx, _)| !generated.contains_key(idx))
+            .map(|(_, name)| Ident::new_unchecked(name.as_str()))
+            .collect()
+    } else {
+        columns
+    };
     let (id, desc, ordering) = plan_copy_item(scx, table_name, columns)?;
+    if let Some(idx) = ordering.iter().position(|idx| generated.contains_key(idx)) {
+        sql_bail!(
+            "column {} is a generated column",
+            desc.get_name(idx).as_str().quoted()
+        );
+    }
+
+    // Only `INSERT` evaluates defaults that call `nextval` for every row.
+    let table_desc = table.desc(&scx.catalog.resolve_full_name(table.name()))?;
+    let defaults = table
+        .table_details()
+        .expect("attempted to insert into non-table");
+    for (idx, (default, typ)) in defaults.iter().zip_eq(table_desc.iter_types()).enumerate() {
+        if ordering.contains(&idx) {
+            continue;
+        }
+        let mut default = default.clone();
+        transform_ast::transform(scx, &mut default)?;
+        if plan_default_expr(scx, &default, &typ.scalar_type)?.contains_nextval() {
+            bail_unsupported!("COPY FROM into columns whose default calls nextval");
+        }
+    }
 
     Ok((id, desc, ordering))
 }
@@ -557,7 +844,11 @@ pub fn plan_copy_from_rows(
         }
     }
 
-    Ok(expr.map(map_exprs).project(project_key))
+    let generated = plan_generated_columns(&scx, table, &desc)?;
+    Ok(map_generated_columns(
+        expr.map(map_exprs).project(project_key),
+        generated,
+    ))
 }
 
 /// Common information used for DELETE, UPDATE, and INSERT INTO ... SELECT plans.
@@ -608,6 +899,324 @@ pub fn plan_update_query(
     )
 }
 
+/// Plans a `MERGE` as a read of the rows of the source left joined with the
+/// rows of the target they match.
+///
+/// The selection produces rows of the form `[existing, new, retract, insert]`
+/// for every source row to which one of the clauses applies, where `existing`
+/// is the matched row of the target, if any, and `new` is the row to replace
+/// it with or to insert. `retract` and `insert` indicate which of the two rows
+/// are to be written.
+pub fn plan_merge_query(
+    scx: &StatementContext,
+    mut merge_stmt: MergeStatement<Aug>,
+) -> Result<ReadThenWritePlan, PlanError> {
+    transform_ast::transform(scx, &mut merge_stmt)?;
+    let MergeStatement {
+        table_name,
+        alias,
+        source,
+        on,
+        clauses,
+    } = merge_stmt;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+
+    // Get global ID.
+    let id = match table_name {
+        ResolvedItemName::Item { id, .. } => id,
+        _ => sql_bail!("cannot mutate non-user table"),
+    };
+
+    // Perform checks on item with given ID.
+    let item = scx.get_item(&id);
+    if item.item_type() != CatalogItemType::Table {
+        sql_bail!(
+            "cannot mutate {} '{}'",
+            item.item_type(),
+            table_name.full_name_str()
+        );
+    }
+    if id.is_system() {
+        sql_bail!(
+            "cannot mutate system table '{}'",
+            table_name.full_name_str()
+        );
+    }
+
+    let desc = item.desc(&scx.catalog.resolve_full_name(item.name()))?;
+    let arity = desc.arity();
+    let mut defaults = item
+        .table_details()
+        .expect("attempted to merge into non-table")
+        .to_vec();
+    for default in &mut defaults {
+        transform_ast::transform(scx, default)?;
+    }
+    let generated = plan_generated_columns(scx, item, &desc)?;
+
+    let (target, target_scope) = qcx.resolve_table_name(table_name)?;
+    let target_scope = plan_table_alias(target_scope, alias.as_ref())?;
+    let (source, source_scope) = plan_table_factor(&qcx, &source)?;
+    let source_arity = source.arity();
+    let scope = source_scope.clone().product(target_scope)?;
+    let mut relation_type = qcx.relation_type(&source);
+    relation_type
+        .column_types
+        .extend(qcx.relation_type(&target).column_types);
+
+    let on = {
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "ON clause",
+            scope: &scope,
+            relation_type: &relation_type,
+            allow_aggregates: false,
+            allow_subqueries: true,
+            allow_parameters: true,
+            allow_windows: false,
+        };
+        plan_expr(ecx, &on)?.type_as(ecx, &ScalarType::Bool)?
+    };
+    let rows = source.join(
+        target.map(vec![HirScalarExpr::literal_true()]),
+        on,
+        JoinKind::LeftOuter,
+    );
+    let matched = HirScalarExpr::column(source_arity + arity);
+
+    // The target's columns are NULL in source rows that do not match.
+    for typ in &mut relation_type.column_types[source_arity..] {
+        typ.nullable = true;
+    }
+
+    // The index of the first clause that applies to each row, and the
+    // expressions that each clause produces for the new row and the `retract`
+    // and `insert` flags.
+    let mut clause_idx = HirScalarExpr::literal_null(ScalarType::Int32);
+    let mut new_values = vec![vec![]; arity];
+    let mut retracts = vec![];
+    let mut inserts = vec![];
+    let clause = HirScalarExpr::column(source_arity + arity + 1);
+    for (i, merge_clause) in clauses.into_iter().enumerate().rev() {
+        let i_expr = HirScalarExpr::literal(
+            Datum::Int32(i32::try_from(i).expect("too many MERGE clauses")),
+            ScalarType::Int32,
+        );
+        let is_clause = clause.clone().call_binary(i_expr.clone(), BinaryFunc::Eq);
+
+        // The target row is only visible to WHEN MATCHED clauses.
+        let clause_scope = if merge_clause.matched {
+            &scope
+        } else {
+            &source_scope
+        };
+        let mut applies = if merge_clause.matched {
+            matched.clone().call_is_null().not()
+        } else {
+            matched.clone().call_is_null()
+        };
+        if let Some(condition) = merge_clause.condition {
+            let ecx = &ExprContext {
+                qcx: &qcx,
+                name: "WHEN clause",
+                scope: clause_scope,
+                relation_type: &relation_type,
+                allow_aggregates: false,
+                allow_subqueries: false,
+                allow_parameters: true,
+                allow_windows: false,
+            };
+            let condition = plan_expr(ecx, &condition)?.type_as(ecx, &ScalarType::Bool)?;
+            applies = applies.and(condition);
+        }
+        clause_idx = HirScalarExpr::If {
+            cond: Box::new(applies),
+            then: Box::new(i_expr),
+            els: Box::new(clause_idx),
+        };
+
+        match merge_clause.action {
+            MergeAction::Update { assignments } => {
+                let mut sets = BTreeMap::new();
+                for Assignment { id, value } in assignments {
+                    let name = normalize::column_name(id);
+                    match desc.get_by_name(&name) {
+                        Some((idx, typ)) => {
+                            let ecx = &ExprContext {
+                                qcx: &qcx,
+                                name: "SET clause",
+                                scope: clause_scope,
+                                relation_type: &relation_type,
+                                allow_aggregates: false,
+                                allow_subqueries: false,
+                                allow_parameters: true,
+                                allow_windows: false,
+                            };
+                            let expr = plan_expr(ecx, &value)?.cast_to(
+                                ecx,
+                                CastContext::Assignment,
+                                &typ.scalar_type,
+                            )?;
+
+                            if sets.insert(idx, expr).is_some() {
+                                sql_bail!("column {} set twice", name)
+                            }
+                        }
+                        None => sql_bail!("unknown column {}", name),
+                    };
+                }
+                if let Some(idx) = sets
+                    .keys()
+                    .find(|idx| is_default_only(item, &generated, **idx))
+                {
+                    sql_bail!(
+                        "column {} can only be updated to DEFAULT",
+                        desc.get_name(*idx).as_str().quoted()
+                    );
+                }
+                let mut values: Vec<_> = (0..arity)
+                    .map(|idx| {
+                        sets.remove(&idx)
+                            .unwrap_or_else(|| HirScalarExpr::column(source_arity + idx))
+                    })
+                    .collect();
+                apply_generated_columns(&generated, &mut values);
+                for (clause_values, value) in new_values.iter_mut().zip_eq(values) {
+                    clause_values.push((is_clause.clone(), value));
+                }
+                retracts.push(is_clause.clone());
+                inserts.push(is_clause);
+            }
+            MergeAction::Delete => retracts.push(is_clause),
+            MergeAction::Insert { columns, values } => {
+                let columns: Vec<_> = columns.into_iter().map(normalize::column_name).collect();
+                let mut ordering = Vec::with_capacity(columns.len());
+                if columns.is_empty() {
+                    ordering.extend(0..values.len());
+                } else {
+                    for c in &columns {
+                        match desc.get_by_name(c) {
+                            Some((idx, _)) => ordering.push(idx),
+                            None => sql_bail!(
+                                "column {} of relation {} does not exist",
+                                c.as_str().quoted(),
+                                item.name().item.as_str().quoted()
+                            ),
+                        }
+                    }
+                    if let Some(dup) = columns.iter().duplicates().next() {
+                        sql_bail!("column {} specified more than once", dup.as_str().quoted());
+                    }
+                }
+                if values.len() > ordering.len() || values.len() > arity {
+                    sql_bail!("INSERT has more expressions than target columns");
+                }
+                if !values.is_empty() && values.len() < ordering.len() {
+                    sql_bail!("INSERT has more target columns than expressions");
+                }
+                if let Some(idx) = ordering
+                    .iter()
+                    .find(|idx| is_default_only(item, &generated, **idx))
+                {
+                    sql_bail!(
+                        "cannot insert a non-DEFAULT value into column {}",
+                        desc.get_name(*idx).as_str().quoted()
+                    );
+                }
+
+                let mut planned = BTreeMap::new();
+                for (idx, value) in ordering.into_iter().zip(values) {
+                    let ecx = &ExprContext {
+                        qcx: &qcx,
+                        name: "VALUES",
+                        scope: clause_scope,
+                        relation_type: &relation_type,
+                        allow_aggregates: false,
+                        allow_subqueries: false,
+                        allow_parameters: true,
+                        allow_windows: false,
+                    };
+                    let typ = &desc.typ().column_types[idx].scalar_type;
+                    let expr =
+                        plan_expr(ecx, &value)?.cast_to(ecx, CastContext::Assignment, typ)?;
+                    planned.insert(idx, expr);
+                }
+                let mut values = Vec::with_capacity(arity);
+                for idx in 0..arity {
+                    let value = match planned.remove(&idx) {
+                        Some(value) => value,
+                        None => plan_default_expr(
+                            scx,
+                            &defaults[idx],
+                            &desc.typ().column_types[idx].scalar_type,
+                        )?,
+                    };
+                    values.push(value);
+                }
+                apply_generated_columns(&generated, &mut values);
+                for (clause_values, value) in new_values.iter_mut().zip_eq(values) {
+                    clause_values.push((is_clause.clone(), value));
+                }
+                inserts.push(is_clause);
+            }
+            MergeAction::DoNothing => {}
+        }
+    }
+
+    // Selects the expression of the clause that applies to each row.
+    let select = |exprs: Vec<(HirScalarExpr, HirScalarExpr)>, default: HirScalarExpr| {
+        exprs
+            .into_iter()
+            .fold(default, |els, (cond, then)| HirScalarExpr::If {
+                cond: Box::new(cond),
+                then: Box::new(then),
+                els: Box::new(els),
+            })
+    };
+    let mut map_exprs = Vec::with_capacity(arity + 2);
+    for (values, typ) in new_values.into_iter().zip_eq(desc.iter_types()) {
+        map_exprs.push(select(
+            values,
+            HirScalarExpr::literal_null(typ.scalar_type.clone()),
+        ));
+    }
+    for flags in [retracts, inserts] {
+        let flags = flags
+            .into_iter()
+            .map(|cond| (cond, HirScalarExpr::literal_true()))
+            .collect();
+        map_exprs.push(select(flags, HirScalarExpr::literal_false()));
+    }
+
+    let new_start = source_arity + arity + 2;
+    let retract = HirScalarExpr::column(new_start + arity);
+    let insert = HirScalarExpr::column(new_start + arity + 1);
+    let selection = rows
+        .map(vec![clause_idx])
+        .map(map_exprs)
+        .filter(vec![retract.or(insert)])
+        .project(
+            (source_arity..source_arity + arity)
+                .chain(new_start..new_start + arity + 2)
+                .collect(),
+        );
+
+    let finishing = RowSetFinishing {
+        order_by: vec![],
+        limit: None,
+        offset: 0,
+        project: (0..2 * arity + 2).collect(),
+    };
+
+    Ok(ReadThenWritePlan {
+        id,
+        selection,
+        finishing,
+        assignments: BTreeMap::new(),
+    })
+}
+
 pub fn plan_mutation_query_inner(
     qcx: QueryContext,
     table_name: ResolvedItemName,
@@ -692,6 +1301,10 @@ pub fn plan_mutation_query_inner(
             None => sql_bail!("unknown column {}", name),
         };
     }
+    // Deletes have no assignments.
+    if !sets.is_empty() {
+        plan_generated_assignments(qcx.scx, item, &desc, &mut sets)?;
+    }
 
     let finishing = RowSetFinishing {
         order_by: vec![],
@@ -1136,6 +1749,164 @@ pub fn plan_default_expr(
     Ok(hir)
 }
 
+/// Plans the generation expression of a column of type `target_ty` of a table
+/// described by `desc`. The expression refers to the table's columns by
+/// index.
+pub fn plan_generated_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    expr: &Expr<Aug>,
+    target_ty: &ScalarType,
+) -> Result<HirScalarExpr, PlanError> {
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let scope = Scope::from_source(None, desc.iter_names());
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "generation expression",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let hir = plan_expr(ecx, expr)?.cast_to(ecx, CastContext::Assignment, target_ty)?;
+    Ok(hir)
+}
+
+/// Plans the condition of a `CHECK` constraint on a table described by `desc`.
+/// The condition refers to the table's columns by index.
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    expr: &Expr<Aug>,
+) -> Result<HirScalarExpr, PlanError> {
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let scope = Scope::from_source(None, desc.iter_names());
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let hir = plan_expr(ecx, expr)?.type_as(ecx, &ScalarType::Bool)?;
+    Ok(hir)
+}
+
+/// Plans the generation expressions of the generated columns of `table`, which
+/// is described by `desc`, by column index.
+fn plan_generated_columns(
+    scx: &StatementContext,
+    table: &dyn CatalogItem,
+    desc: &RelationDesc,
+) -> Result<BTreeMap<usize, HirScalarExpr>, PlanError> {
+    let mut generated = BTreeMap::new();
+    for (idx, expr) in table
+        .table_generated_columns()
+        .expect("attempted to write to non-table")
+    {
+        let mut expr = expr.clone();
+        transform_ast::transform(scx, &mut expr)?;
+        let typ = &desc.typ().column_types[*idx].scalar_type;
+        generated.insert(*idx, plan_generated_expr(scx, desc, &expr, typ)?);
+    }
+    Ok(generated)
+}
+
+/// Reports whether column `idx` of `table`, whose generated columns are
+/// `generated`, can only be assigned `DEFAULT`, as is the case for generated
+/// columns and `GENERATED ALWAYS` identity columns.
+fn is_default_only(
+    table: &dyn CatalogItem,
+    generated: &BTreeMap<usize, HirScalarExpr>,
+    idx: usize,
+) -> bool {
+    generated.contains_key(&idx)
+        || table
+            .table_identity_columns()
+            .and_then(|identity_columns| identity_columns.get(&idx))
+            .map_or(false, |identity| identity.always)
+}
+
+/// Replaces the values of the `generated` columns of `rows`, which have the
+/// columns of the table, with the results of their generation expressions.
+fn map_generated_columns(
+    rows: HirRelationExpr,
+    generated: BTreeMap<usize, HirScalarExpr>,
+) -> HirRelationExpr {
+    if generated.is_empty() {
+        return rows;
+    }
+    let arity = rows.arity();
+    let mut project: Vec<_> = (0..arity).collect();
+    for (i, idx) in generated.keys().enumerate() {
+        project[*idx] = arity + i;
+    }
+    rows.map(generated.into_values().collect()).project(project)
+}
+
+/// Adds the assignments that recompute the generated columns of `table`, which
+/// is described by `desc`, to `sets`, the assignments of an update of rows
+/// whose first columns are the table's.
+fn plan_generated_assignments(
+    scx: &StatementContext,
+    table: &dyn CatalogItem,
+    desc: &RelationDesc,
+    sets: &mut BTreeMap<usize, HirScalarExpr>,
+) -> Result<(), PlanError> {
+    let generated = plan_generated_columns(scx, table, desc)?;
+    if let Some(idx) = sets
+        .keys()
+        .find(|idx| is_default_only(table, &generated, **idx))
+    {
+        sql_bail!(
+            "column {} can only be updated to DEFAULT",
+            desc.get_name(*idx).as_str().quoted()
+        );
+    }
+    if generated.is_empty() {
+        return Ok(());
+    }
+    let mut values: Vec<_> = (0..desc.arity())
+        .map(|idx| {
+            sets.get(&idx)
+                .cloned()
+                .unwrap_or_else(|| HirScalarExpr::column(idx))
+        })
+        .collect();
+    apply_generated_columns(&generated, &mut values);
+    for idx in generated.keys() {
+        sets.insert(*idx, values[*idx].clone());
+    }
+    Ok(())
+}
+
+/// Replaces the new values of the `generated` columns in `values`, which holds
+/// an expression for the new value of each of the table's columns, with their
+/// generation expressions applied to the new values of the other columns.
+fn apply_generated_columns(
+    generated: &BTreeMap<usize, HirScalarExpr>,
+    values: &mut [HirScalarExpr],
+) {
+    for (idx, expr) in generated {
+        let mut expr = expr.clone();
+        // Generation expressions cannot contain subqueries, so all of their
+        // column references are to the table's columns.
+        #[allow(deprecated)]
+        let _ = expr.visit_recursively_mut(0, &mut |_, e: &mut HirScalarExpr| -> Result<(), ()> {
+            if let HirScalarExpr::Column(ColumnRef { level: 0, column }) = e {
+                *e = values[*column].clone();
+            }
+            Ok(())
+        });
+        values[*idx] = expr;
+    }
+}
+
 pub fn plan_params<'a>(
     scx: &'a StatementContext,
     params: Vec<Expr<Aug>>,
@@ -1916,6 +2687,7 @@ fn plan_values_insert(
     target_names: &[&ColumnName],
     target_types: &[&ScalarType],
     values: &[Vec<Expr<Aug>>],
+    ordinals: bool,
 ) -> Result<HirRelationExpr, PlanError> {
     assert!(!values.is_empty());
 
@@ -1936,7 +2708,7 @@ fn plan_values_insert(
 
     let mut exprs = vec![];
     let mut types = vec![];
-    for row in values {
+    for (ordinal, row) in values.iter().enumerate() {
         if row.len() > target_names.len() {
             sql_bail!("INSERT has more expressions than target columns");
         }
@@ -1961,17 +2733,40 @@ fn plan_values_insert(
             }
             exprs.push(val);
         }
+        if ordinals {
+            let ordinal = i64::try_from(ordinal).expect("too many rows");
+            exprs.push(HirScalarExpr::literal(
+                Datum::Int64(ordinal),
+                ScalarType::Int64,
+            ));
+        }
+    }
+    if ordinals {
+        types.push(ScalarType::Int64.nullable(false));
     }
 
     Ok(HirRelationExpr::CallTable {
         func: mz_expr::TableFunc::Wrap {
-            width: values[0].len(),
+            width: values[0].len() + usize::from(ordinals),
             types,
         },
         exprs,
     })
 }
 
+/// Adds a trailing ordinal column of `0` to `expr` if `ordinals` is set. See
+/// [`plan_insert_query`].
+fn map_ordinal(expr: HirRelationExpr, ordinals: bool) -> HirRelationExpr {
+    if ordinals {
+        expr.map(vec![HirScalarExpr::literal(
+            Datum::Int64(0),
+            ScalarType::Int64,
+        )])
+    } else {
+        expr
+    }
+}
+
 fn plan_join_identity() -> (HirRelationExpr, Scope) {
     let typ = RelationType::new(vec![]);
     let expr = HirRelationExpr::constant(vec![vec![]], typ);
@@ -607,6 +607,7 @@ pub fn plan_alter_default_privileges(
         ObjectType::Sink | ObjectType::ClusterReplica | ObjectType::Role | ObjectType::Func => {
             sql_bail!("{object_type}S do not have privileges")
         }
+        ObjectType::Sequence => bail_unsupported!("ALTER DEFAULT PRIVILEGES ON SEQUENCES"),
         ObjectType::Cluster | ObjectType::Database
             if matches!(
                 target_objects,
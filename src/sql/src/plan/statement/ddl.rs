@@ -23,8 +23,11 @@ use mz_controller_types::{
     is_cluster_size_v2, ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL,
 };
 use mz_expr::refresh_schedule::{RefreshEvery, RefreshSchedule};
-use mz_expr::{CollectionPlan, UnmaterializableFunc};
+use mz_expr::visit::Visit;
+use mz_expr::{CollectionPlan, MirScalarExpr, UnmaterializableFunc};
 use mz_interchange::avro::{AvroSchemaGenerator, AvroSchemaOptions, DocTarget};
+use mz_interchange::protobuf::{DecodedDescriptors, ProtobufEncoder};
+use mz_interchange::text_binary::{BytesEncoder, TextEncoder};
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::collections::HashSet;
 use mz_ore::soft_panic_or_log;
@@ -52,38 +55,45 @@ use mz_sql_parser::ast::{
     CreateClusterReplicaStatement, CreateClusterStatement, CreateConnectionOption,
     CreateConnectionOptionName, CreateConnectionStatement, CreateConnectionType,
     CreateDatabaseStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
-    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
-    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
+    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSequenceStatement,
+    CreateSinkConnection, CreateSinkOption, CreateSinkOptionName, CreateSinkStatement,
+    CreateSourceConnection, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
     CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
     CreateTableStatement, CreateTypeAs, CreateTypeListOption, CreateTypeListOptionName,
     CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
     CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
     CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format, Ident,
-    IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
-    LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
+    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, FileSourceOption,
+    FileSourceOptionName, Format, FormatSpecifier, Ident, IfExistsBehavior, IndexOption,
+    IndexOptionName, KafkaSinkConfigOption, KeyConstraint, KinesisSourceOption,
+    KinesisSourceOptionName, LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
     MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
     PgConfigOptionName, ProtobufSchema, QualifiedReplica, RefreshAtOptionValue,
     RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
-    ReplicaOptionName, RoleAttribute, SetRoleVar, SourceIncludeMetadata, Statement,
-    TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName, UnresolvedItemName,
-    UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition, WithOptionValue,
+    ReplicaOptionName, RoleAttribute, SequenceOption, SetRoleVar, SourceIncludeMetadata,
+    SqlServerConfigOption, SqlServerConfigOptionName, Statement, TableConstraint, TableOption,
+    TableOptionName, UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName,
+    UnresolvedSchemaName, Value, ViewDefinition, WithOptionValue,
 };
 use mz_sql_parser::ident;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
 use mz_storage_types::connections::Connection;
 use mz_storage_types::sinks::{
-    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, SinkEnvelope, StorageSinkConnection,
+    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType, SinkEnvelope,
+    StorageSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
-    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding,
-    RegexEncoding, SourceDataEncoding,
+    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, DecodeErrorPolicy,
+    JsonEncoding, JsonFraming, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
 };
 use mz_storage_types::sources::envelope::{
     KeyEnvelope, SourceEnvelope, UnplannedSourceEnvelope, UpsertStyle,
 };
-use mz_storage_types::sources::kafka::{KafkaMetadataKind, KafkaSourceConnection};
+use mz_storage_types::sources::file::{
+    FileFormat, FileSourceConnection, FileSourceDetails, FileStore, ProtoFileSourceDetails,
+};
+use mz_storage_types::sources::kafka::{KafkaMetadataKind, KafkaSourceConnection, KAFKA_MAX_EPOCH};
+use mz_storage_types::sources::kinesis::KinesisSourceConnection;
 use mz_storage_types::sources::load_generator::{
     KeyValueLoadGenerator, LoadGenerator, LoadGeneratorSourceConnection,
     LOAD_GENERATOR_KEY_VALUE_OFFSET_DEFAULT,
@@ -95,6 +105,9 @@ use mz_storage_types::sources::postgres::{
     CastType, PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails,
 };
+use mz_storage_types::sources::sql_server::{
+    ProtoSqlServerSourceDetails, SqlServerSourceConnection, SqlServerSourceDetails,
+};
 use mz_storage_types::sources::{GenericSourceConnection, SourceConnection, SourceDesc, Timeline};
 use prost::Message;
 
@@ -128,11 +141,12 @@ use crate::plan::{
     CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan,
     CreateClusterPlan, CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
     CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
-    DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
-    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, Sink, Source, Table, Type,
-    VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
+    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSequencePlan, CreateSinkPlan,
+    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc,
+    DropObjectsPlan, DropOwnedPlan, FullItemName, HirScalarExpr, IdentityColumn, Index, Ingestion,
+    MaterializedView, Params, Plan, PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig,
+    Secret, Sequence, SequenceOptions, Sink, Source, Table, TableCheck, Type, VariableValue, View,
+    WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
 };
 use crate::session::vars;
 use crate::session::vars::{
@@ -238,17 +252,24 @@ pub fn plan_create_table(
         sql_bail!("column {} specified more than once", dup.as_str().quoted());
     }
 
+    let item_name = normalize::unresolved_item_name(name.to_owned())?.item;
+
     // Build initial relation type that handles declared data types
     // and NOT NULL constraints.
     let mut column_types = Vec::with_capacity(columns.len());
     let mut defaults = Vec::with_capacity(columns.len());
+    let mut generated_columns = BTreeMap::new();
+    let mut identity_columns = BTreeMap::new();
+    let mut checks = Vec::new();
     let mut keys = Vec::new();
+    let mut nulls_not_distinct_keys = Vec::new();
 
     for (i, c) in columns.into_iter().enumerate() {
         let aug_data_type = &c.data_type;
         let ty = query::scalar_type_from_sql(scx, aug_data_type)?;
         let mut nullable = true;
         let mut default = Expr::null();
+        let mut has_default = false;
         for option in &c.options {
             match &option.option {
                 ColumnOption::NotNull => nullable = false,
@@ -259,6 +280,7 @@ pub fn plan_create_table(
                     transform_ast::transform(scx, &mut expr)?;
                     let _ = query::plan_default_expr(scx, &expr, &ty)?;
                     default = expr.clone();
+                    has_default = true;
                 }
                 ColumnOption::Unique { is_primary } => {
                     keys.push(vec![i]);
@@ -266,15 +288,88 @@ pub fn plan_create_table(
                         nullable = false;
                     }
                 }
+                ColumnOption::Check(expr) => {
+                    let name = match &option.name {
+                        Some(name) => normalize::ident(name.clone()),
+                        None => format!("{}_{}_check", item_name, names[i].as_str()),
+                    };
+                    checks.push((name, expr.clone()));
+                }
+                ColumnOption::Generated(expr) => {
+                    if generated_columns.insert(i, expr.clone()).is_some() {
+                        sql_bail!(
+                            "multiple generation clauses specified for column {} of table {}",
+                            names[i].as_str().quoted(),
+                            item_name.quoted()
+                        );
+                    }
+                }
+                ColumnOption::Identity { always, options } => {
+                    if options.iter().any(|o| matches!(o, SequenceOption::As(_))) {
+                        sql_bail!("identity column type must be specified as the column type");
+                    }
+                    let options = plan_sequence_options(options, &ty)?;
+                    let identity = IdentityColumn {
+                        always: *always,
+                        options,
+                    };
+                    if identity_columns.insert(i, identity).is_some() {
+                        sql_bail!(
+                            "multiple identity specifications for column {} of table {}",
+                            names[i].as_str().quoted(),
+                            item_name.quoted()
+                        );
+                    }
+                    // Identity columns are filled in when their row is
+                    // written, so they never hold NULL.
+                    nullable = false;
+                }
                 other => {
                     bail_unsupported!(format!("CREATE TABLE with column constraint: {}", other))
                 }
             }
         }
+        if has_default && generated_columns.contains_key(&i) {
+            sql_bail!(
+                "both default and generation expression specified for column {} of table {}",
+                names[i].as_str().quoted(),
+                item_name.quoted()
+            );
+        }
+        if identity_columns.contains_key(&i) {
+            if has_default {
+                sql_bail!(
+                    "both default and identity specified for column {} of table {}",
+                    names[i].as_str().quoted(),
+                    item_name.quoted()
+                );
+            }
+            if generated_columns.contains_key(&i) {
+                sql_bail!(
+                    "both identity and generation expression specified for column {} of table {}",
+                    names[i].as_str().quoted(),
+                    item_name.quoted()
+                );
+            }
+        }
         column_types.push(ty.nullable(nullable));
         defaults.push(default);
     }
 
+    for constraint in constraints {
+        if let TableConstraint::Check {
+            name: constraint_name,
+            expr,
+        } = constraint
+        {
+            let name = match constraint_name {
+                Some(name) => normalize::ident(name.clone()),
+                None => format!("{}_check", item_name),
+            };
+            checks.push((name, expr.clone()));
+        }
+    }
+
     let mut seen_primary = false;
     'c: for constraint in constraints {
         match constraint {
@@ -321,6 +416,9 @@ pub fn plan_create_table(
                 if *is_primary {
                     keys.insert(0, key);
                 } else {
+                    if *nulls_not_distinct {
+                        nulls_not_distinct_keys.push(key.clone());
+                    }
                     keys.push(key);
                 }
             }
@@ -329,17 +427,14 @@ pub fn plan_create_table(
                 // them with feature flags for sqllogictest's sake.
                 scx.require_feature_flag(&vars::ENABLE_TABLE_FOREIGN_KEY)?
             }
-            TableConstraint::Check { .. } => {
-                // Check constraints are not presently enforced. We allow them
-                // with feature flags for sqllogictest's sake.
-                scx.require_feature_flag(&vars::ENABLE_TABLE_CHECK_CONSTRAINT)?
-            }
+            // Collected with the column constraints above.
+            TableConstraint::Check { .. } => {}
         }
     }
 
     if !keys.is_empty() {
-        // Unique constraints are not presently enforced. We allow them with feature flags for
-        // sqllogictest's sake.
+        // Unique constraints are enforced when writing to the table, which requires keeping the
+        // values of its keys in memory, so they remain behind a feature flag.
         scx.require_feature_flag(&vars::ENABLE_TABLE_KEYS)?
     }
 
@@ -369,6 +464,43 @@ pub fn plan_create_table(
 
     let desc = RelationDesc::new(typ, names);
 
+    for expr in generated_columns.values_mut() {
+        transform_ast::transform(scx, expr)?;
+    }
+    for (idx, expr) in &generated_columns {
+        let typ = &desc.typ().column_types[*idx].scalar_type;
+        let expr = query::plan_generated_expr(scx, &desc, expr, typ)?.lower_uncorrelated()?;
+        // Generated columns are computed when their row is written, so they
+        // must not depend on when that happens.
+        if expr.contains_unmaterializable() {
+            sql_bail!("generation expression cannot call unmaterializable functions");
+        }
+        if let Some(column) = expr
+            .support()
+            .into_iter()
+            .find(|column| generated_columns.contains_key(column))
+        {
+            sql_bail!(
+                "cannot use generated column {} in column generation expression",
+                desc.get_name(column).as_str().quoted()
+            );
+        }
+    }
+
+    let checks = checks
+        .into_iter()
+        .map(|(name, mut expr)| {
+            transform_ast::transform(scx, &mut expr)?;
+            let expr = query::plan_check_expr(scx, &desc, &expr)?.lower_uncorrelated()?;
+            // Rows are checked when they are written, so the condition must
+            // not depend on when that happens.
+            if expr.contains_unmaterializable() {
+                sql_bail!("CHECK constraint cannot call unmaterializable functions");
+            }
+            Ok(TableCheck { name, expr })
+        })
+        .collect::<Result<_, PlanError>>()?;
+
     let create_sql = normalize::create_statement(scx, Statement::CreateTable(stmt.clone()))?;
 
     let options = plan_table_options(scx, with_options.clone())?;
@@ -385,6 +517,10 @@ pub fn plan_create_table(
         create_sql,
         desc,
         defaults,
+        generated_columns,
+        checks,
+        identity_columns,
+        nulls_not_distinct_keys,
         temporary,
         compaction_window,
     };
@@ -421,23 +557,124 @@ generate_extracted_config!(
     (IgnoreKeys, bool),
     (Timeline, String),
     (TimestampInterval, Duration),
-    (RetainHistory, Duration)
+    (RetainHistory, Duration),
+    (OnDecodeError, String)
 );
 
 generate_extracted_config!(
     PgConfigOption,
     (Details, String),
     (Publication, String),
-    (TextColumns, Vec::<UnresolvedItemName>, Default(vec![]))
+    (TextColumns, Vec::<UnresolvedItemName>, Default(vec![])),
+    (
+        RetractOnTruncate,
+        Vec::<UnresolvedItemName>,
+        Default(vec![])
+    ),
+    (UpsertTables, Vec::<UnresolvedItemName>, Default(vec![])),
+    (IncludeColumns, Vec::<UnresolvedItemName>, Default(vec![])),
+    (
+        RowFilters,
+        Vec::<(UnresolvedItemName, Expr<Aug>)>,
+        Default(vec![])
+    )
 );
 
 generate_extracted_config!(
     MySqlConfigOption,
     (Details, String),
     (TextColumns, Vec::<UnresolvedItemName>, Default(vec![])),
-    (IgnoreColumns, Vec::<UnresolvedItemName>, Default(vec![]))
+    (IgnoreColumns, Vec::<UnresolvedItemName>, Default(vec![])),
+    (
+        RetractOnTruncate,
+        Vec::<UnresolvedItemName>,
+        Default(vec![])
+    )
 );
 
+generate_extracted_config!(SqlServerConfigOption, (Details, String));
+
+generate_extracted_config!(
+    FileSourceOption,
+    (Bucket, String),
+    (Path, String),
+    (Prefix, String, Default(String::new())),
+    (Details, String)
+);
+
+generate_extracted_config!(KinesisSourceOption, (Stream, String));
+
+/// Plans the connection of a Kinesis source that reads the stream named by the
+/// `STREAM` option via the AWS connection `connection`.
+pub(crate) fn plan_kinesis_source_connection(
+    scx: &StatementContext,
+    connection: &ResolvedItemName,
+    options: &[KinesisSourceOption<Aug>],
+) -> Result<KinesisSourceConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_KINESIS_SOURCES)?;
+    let connection_item = scx.get_item_by_resolved_name(connection)?;
+    if !matches!(connection_item.connection()?, Connection::Aws(_)) {
+        sql_bail!(
+            "{} is not an AWS connection",
+            scx.catalog.resolve_full_name(connection_item.name())
+        )
+    }
+
+    let KinesisSourceOptionExtracted { stream, seen: _ } = options.to_vec().try_into()?;
+    let Some(stream_name) = stream else {
+        sql_bail!("STREAM option is required");
+    };
+
+    Ok(KinesisSourceConnection {
+        connection_id: connection_item.id(),
+        connection: connection_item.id(),
+        stream_name,
+    })
+}
+
+/// Plans the store that a file source reads from, which is an S3 bucket when
+/// `connection` is present and the local filesystem otherwise.
+pub(crate) fn plan_file_store(
+    scx: &StatementContext,
+    connection: Option<&ResolvedItemName>,
+    bucket: Option<String>,
+    path: Option<String>,
+) -> Result<FileStore<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_FILE_SOURCES)?;
+    match connection {
+        Some(connection) => {
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            if !matches!(connection_item.connection()?, Connection::Aws(_)) {
+                sql_bail!(
+                    "{} is not an AWS connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                )
+            }
+            if path.is_some() {
+                sql_bail!("PATH is not supported with S3 CONNECTION");
+            }
+            let Some(bucket) = bucket else {
+                sql_bail!("BUCKET option is required");
+            };
+            Ok(FileStore::S3 {
+                connection_id: connection_item.id(),
+                connection: connection_item.id(),
+                bucket,
+            })
+        }
+        None => {
+            scx.require_feature_flag(&vars::ENABLE_LOCAL_FILE_SOURCES)?;
+            if bucket.is_some() {
+                sql_bail!("BUCKET is not supported with LOCAL FILES");
+            }
+            let Some(path) = path else {
+                sql_bail!("PATH option is required");
+            };
+            Ok(FileStore::Local { path })
+        }
+    }
+}
+
 pub fn plan_create_webhook_source(
     scx: &StatementContext,
     mut stmt: CreateWebhookSourceStatement<Aug>,
@@ -477,7 +714,7 @@ pub fn plan_create_webhook_source(
 
     let body_format = match body_format {
         Format::Bytes => WebhookBodyFormat::Bytes,
-        Format::Json { array } => WebhookBodyFormat::Json { array },
+        Format::Json { array, .. } => WebhookBodyFormat::Json { array },
         Format::Text => WebhookBodyFormat::Text,
         // TODO(parkmycar): Make an issue to support more types, or change this to NeverSupported.
         ty => {
@@ -624,6 +861,7 @@ pub fn plan_create_source(
     let allowed_with_options = vec![
         CreateSourceOptionName::TimestampInterval,
         CreateSourceOptionName::RetainHistory,
+        CreateSourceOptionName::OnDecodeError,
     ];
     if let Some(op) = with_options
         .iter()
@@ -671,13 +909,19 @@ pub fn plan_create_source(
             let KafkaSourceConfigOptionExtracted {
                 group_id_prefix,
                 topic,
+                topic_pattern,
                 topic_metadata_refresh_interval,
                 start_timestamp: _, // purified into `start_offset`
                 start_offset,
+                start_epoch,
                 seen: _,
             }: KafkaSourceConfigOptionExtracted = options.clone().try_into()?;
 
-            let topic = topic.expect("validated exists during purification");
+            let (topic, topic_is_pattern) = match (topic, topic_pattern) {
+                (Some(topic), None) => (topic, false),
+                (None, Some(topic_pattern)) => (topic_pattern, true),
+                _ => unreachable!("validated exactly one exists during purification"),
+            };
 
             let mut start_offsets = BTreeMap::new();
             if let Some(offsets) = start_offset {
@@ -693,6 +937,10 @@ pub fn plan_create_source(
                 sql_bail!("START OFFSET is not supported with ENVELOPE {}", envelope)
             }
 
+            if start_epoch > KAFKA_MAX_EPOCH {
+                sql_bail!("START EPOCH cannot be greater than {}", KAFKA_MAX_EPOCH);
+            }
+
             if topic_metadata_refresh_interval > Duration::from_secs(60 * 60) {
                 // This is a librdkafka-enforced restriction that, if violated,
                 // would result in a runtime error for the source.
@@ -735,6 +983,13 @@ pub fn plan_create_source(
                         };
                         Some((name, KafkaMetadataKind::Offset))
                     }
+                    SourceIncludeMetadata::Topic { alias } => {
+                        let name = match alias {
+                            Some(name) => name.to_string(),
+                            None => "topic".to_owned(),
+                        };
+                        Some((name, KafkaMetadataKind::Topic))
+                    }
                     SourceIncludeMetadata::Headers { alias } => {
                         let name = match alias {
                             Some(name) => name.to_string(),
@@ -764,7 +1019,9 @@ pub fn plan_create_source(
                 connection: connection_item.id(),
                 connection_id: connection_item.id(),
                 topic,
+                topic_is_pattern,
                 start_offsets,
+                start_epoch,
                 group_id_prefix,
                 topic_metadata_refresh_interval,
                 metadata_columns,
@@ -788,6 +1045,10 @@ pub fn plan_create_source(
                 details,
                 publication,
                 text_columns,
+                retract_on_truncate,
+                upsert_tables,
+                include_columns,
+                row_filters,
                 seen: _,
             } = options.clone().try_into()?;
 
@@ -812,10 +1073,8 @@ pub fn plan_create_source(
 
             let publication_catalog = crate::catalog::SubsourceCatalog(tables_by_name);
 
-            let mut text_cols: BTreeMap<Oid, BTreeSet<String>> = BTreeMap::new();
-
-            // Look up the referenced text_columns in the publication_catalog.
-            for name in text_columns {
+            // Look up a referenced column in the publication_catalog.
+            let resolve_column = |name: UnresolvedItemName| {
                 let (qual, col) = match name.0.split_last().expect("must have at least one element")
                 {
                     (col, qual) => (UnresolvedItemName(qual.to_vec()), col.as_str().to_string()),
@@ -834,10 +1093,60 @@ pub fn plan_create_source(
                     "validated column exists in table during purification"
                 );
 
-                text_cols
-                    .entry(Oid(table_desc.oid))
-                    .or_default()
-                    .insert(col);
+                (Oid(table_desc.oid), col)
+            };
+
+            let mut text_cols: BTreeMap<Oid, BTreeSet<String>> = BTreeMap::new();
+            for name in text_columns {
+                let (oid, col) = resolve_column(name);
+                text_cols.entry(oid).or_default().insert(col);
+            }
+
+            // Tables referenced by INCLUDE COLUMNS only ingest the listed
+            // columns.
+            let mut include_cols: BTreeMap<Oid, BTreeSet<String>> = BTreeMap::new();
+            for name in include_columns {
+                let (oid, col) = resolve_column(name);
+                include_cols.entry(oid).or_default().insert(col);
+            }
+
+            // Look up the tables referenced by ROW FILTERS.
+            let mut row_filters_by_oid = BTreeMap::new();
+            for (name, filter) in row_filters {
+                let (_name, table_desc) = publication_catalog
+                    .resolve(name)
+                    .expect("known to exist from purification");
+                row_filters_by_oid.insert(Oid(table_desc.oid), filter);
+            }
+
+            // Look up the tables referenced by RETRACT ON TRUNCATE and record
+            // their output indexes.
+            let mut retract_on_truncate_outputs = BTreeSet::new();
+            for name in retract_on_truncate {
+                let (_name, table_desc) = publication_catalog
+                    .resolve(name)
+                    .expect("known to exist from purification");
+                let idx = details
+                    .tables
+                    .iter()
+                    .position(|table| table.oid == table_desc.oid)
+                    .expect("table known to exist");
+                retract_on_truncate_outputs.insert(idx + 1);
+            }
+
+            // Look up the tables referenced by UPSERT TABLES and record their
+            // output indexes.
+            let mut upsert_table_outputs = BTreeSet::new();
+            for name in upsert_tables {
+                let (_name, table_desc) = publication_catalog
+                    .resolve(name)
+                    .expect("known to exist from purification");
+                let idx = details
+                    .tables
+                    .iter()
+                    .position(|table| table.oid == table_desc.oid)
+                    .expect("table known to exist");
+                upsert_table_outputs.insert(idx + 1);
             }
 
             // Here we will generate the cast expressions required to convert the text encoded
@@ -845,6 +1154,7 @@ pub fn plan_create_source(
             // The postgres source reader will then eval each of those on the incoming rows based
             // on the target table
             let mut table_casts = BTreeMap::new();
+            let mut table_filters = BTreeMap::new();
 
             for (i, table) in details.tables.iter().enumerate() {
                 // First, construct an expression context where the expression is evaluated on an
@@ -875,10 +1185,18 @@ pub fn plan_create_source(
                     allow_windows: false,
                 };
 
+                let include_cols = include_cols.get(&Oid(table.oid));
+
                 // Then, for each column we will generate a MirRelationExpr that extracts the nth
                 // column and casts it to the appropriate target type
                 let mut column_casts = vec![];
+                // The types of the cast columns, against which the table's row
+                // filter is planned.
+                let mut cast_column_types = vec![];
                 for (i, column) in table.columns.iter().enumerate() {
+                    // Columns left out of INCLUDE COLUMNS are still cast, as
+                    // the row filter may refer to them, but are not ingested.
+                    let excluded = include_cols.is_some_and(|names| !names.contains(&column.name));
                     let (cast_type, ty) = match text_cols.get(&Oid(table.oid)) {
                         // Treat the column as text if it was referenced in
                         // `TEXT COLUMNS`. This is the only place we need to
@@ -899,8 +1217,14 @@ pub fn plan_create_source(
                                 // user will consume., i.e. expect this table to
                                 // be filtered out of table casts.
                                 Err(_) => {
+                                    let cast_type = if excluded {
+                                        CastType::Excluded
+                                    } else {
+                                        CastType::Natural
+                                    };
+                                    cast_column_types.push(ScalarType::String.nullable(true));
                                     column_casts.push((
-                                        CastType::Natural,
+                                        cast_type,
                                         HirScalarExpr::CallVariadic {
                                             func: mz_expr::VariadicFunc::ErrorIfNull,
                                             exprs: vec![
@@ -926,16 +1250,33 @@ pub fn plan_create_source(
                         }
                     };
 
+                    let cast_type = if excluded {
+                        CastType::Excluded
+                    } else {
+                        cast_type
+                    };
+
                     let data_type = scx.resolve_type(ty)?;
                     let scalar_type = query::scalar_type_from_sql(scx, &data_type)?;
+                    cast_column_types.push(scalar_type.clone().nullable(column.nullable));
 
                     let col_expr = HirScalarExpr::Column(ColumnRef {
                         level: 0,
                         column: i,
                     });
 
-                    let cast_expr =
-                        plan_cast(&cast_ecx, CastContext::Explicit, col_expr, &scalar_type)?;
+                    // PostgreSQL enforces the maximum length of `varchar`
+                    // columns itself, so a longer value means that the column
+                    // was widened upstream. Casting such values fails rather
+                    // than truncating them, which lets the source ingest them
+                    // in full until the schema of the subsource is refreshed.
+                    let ccx = match scalar_type {
+                        ScalarType::VarChar {
+                            max_length: Some(_),
+                        } => CastContext::Assignment,
+                        _ => CastContext::Explicit,
+                    };
+                    let cast_expr = plan_cast(&cast_ecx, ccx, col_expr, &scalar_type)?;
 
                     let cast = if column.nullable {
                         cast_expr
@@ -993,6 +1334,47 @@ pub fn plan_create_source(
 
                     column_casts.push((cast_type, mir_cast));
                 }
+
+                if let Some(filter) = row_filters_by_oid.get(&Oid(table.oid)) {
+                    let mut filter = filter.clone();
+                    transform_ast::transform(scx, &mut filter)?;
+                    let scope = Scope::from_source(
+                        None,
+                        table.columns.iter().map(|column| column.name.as_str()),
+                    );
+                    let filter_ecx = ExprContext {
+                        qcx: &cast_qcx,
+                        name: "ROW FILTERS",
+                        scope: &scope,
+                        relation_type: &RelationType {
+                            column_types: cast_column_types,
+                            keys: vec![],
+                        },
+                        allow_aggregates: false,
+                        allow_subqueries: false,
+                        allow_parameters: false,
+                        allow_windows: false,
+                    };
+                    let mut filter = plan_expr(&filter_ecx, &filter)?
+                        .type_as(&filter_ecx, &ScalarType::Bool)?
+                        .lower_uncorrelated()?;
+                    // The filter is evaluated once per row by the source, so it
+                    // must produce the same result no matter when it is
+                    // evaluated.
+                    if filter.contains_unmaterializable() {
+                        sql_bail!("ROW FILTERS expressions cannot call unmaterializable functions");
+                    }
+                    // The source evaluates the filter on the text encoded row,
+                    // so we replace the references to the columns with their
+                    // casts.
+                    filter.visit_mut_post(&mut |expr: &mut MirScalarExpr| {
+                        if let MirScalarExpr::Column(col) = expr {
+                            *expr = column_casts[*col].1.clone();
+                        }
+                    })?;
+                    table_filters.insert(i + 1, filter);
+                }
+
                 let r = table_casts.insert(i + 1, column_casts);
                 assert!(r.is_none(), "cannot have table defined multiple times");
             }
@@ -1005,8 +1387,11 @@ pub fn plan_create_source(
                     connection: connection_item.id(),
                     connection_id: connection_item.id(),
                     table_casts,
+                    table_filters,
                     publication: publication.expect("validated exists during purification"),
                     publication_details,
+                    retract_on_truncate: retract_on_truncate_outputs,
+                    upsert_tables: upsert_table_outputs,
                 });
 
             connection
@@ -1027,6 +1412,7 @@ pub fn plan_create_source(
                 details,
                 text_columns,
                 ignore_columns,
+                retract_on_truncate,
                 seen: _,
             } = options.clone().try_into()?;
 
@@ -1046,6 +1432,14 @@ pub fn plan_create_source(
                 .into_iter()
                 .map(|name| name.try_into().map_err(|e| sql_err!("{}", e)))
                 .collect::<Result<Vec<_>, _>>()?;
+            let retract_on_truncate = retract_on_truncate
+                .iter()
+                .map(|name| {
+                    details
+                        .output_idx_for_name(name)
+                        .ok_or_else(|| sql_err!("internal error: unknown table {}", name))
+                })
+                .collect::<Result<BTreeSet<_>, _>>()?;
 
             let connection =
                 GenericSourceConnection::<ReferencedConnection>::from(MySqlSourceConnection {
@@ -1054,10 +1448,122 @@ pub fn plan_create_source(
                     details,
                     text_columns,
                     ignore_columns,
+                    retract_on_truncate,
                 });
 
             connection
         }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_SQL_SERVER_SOURCE)?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            match connection_item.connection()? {
+                Connection::SqlServer(connection) => connection,
+                _ => sql_bail!(
+                    "{} is not a SQL Server connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                ),
+            };
+            let SqlServerConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: SQL Server source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoSqlServerSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                SqlServerSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            let connection =
+                GenericSourceConnection::<ReferencedConnection>::from(SqlServerSourceConnection {
+                    connection: connection_item.id(),
+                    connection_id: connection_item.id(),
+                    details,
+                });
+
+            connection
+        }
+        CreateSourceConnection::S3 {
+            connection: _,
+            options,
+        }
+        | CreateSourceConnection::LocalFiles { options } => {
+            let connection = match connection {
+                CreateSourceConnection::S3 { connection, .. } => Some(connection),
+                _ => None,
+            };
+            let FileSourceOptionExtracted {
+                bucket,
+                path,
+                prefix,
+                details,
+                seen: _,
+            } = options.clone().try_into()?;
+
+            let store = plan_file_store(scx, connection, bucket, path)?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: file source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoFileSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details = FileSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            if !matches!(envelope, ast::SourceEnvelope::None) {
+                bail_unsupported!(format!("ENVELOPE {} with file sources", envelope));
+            }
+
+            let format = match format {
+                Some(FormatSpecifier::Bare(format)) => format,
+                Some(FormatSpecifier::KeyValue { .. }) => {
+                    sql_bail!("KEY FORMAT is not supported by file sources")
+                }
+                None => sql_bail!("file sources require a FORMAT"),
+            };
+            let format = match format {
+                Format::Parquet => FileFormat::Parquet {
+                    desc: details.parquet_desc.ok_or_else(|| {
+                        sql_err!("internal error: Parquet file source missing schema")
+                    })?,
+                },
+                Format::Csv { columns, .. } => FileFormat::Csv {
+                    skip_header: matches!(columns, CsvColumns::Header { .. }),
+                },
+                // A JSON array may span many lines, so each object must hold
+                // exactly one array.
+                Format::Json { array: true, .. } | Format::Bytes => FileFormat::Object,
+                Format::Json { .. } | Format::Text | Format::Regex(_) => FileFormat::Lines,
+                Format::Avro(_) | Format::Protobuf(_) => {
+                    bail_unsupported!(format!("FORMAT {} with file sources", format))
+                }
+            };
+
+            GenericSourceConnection::from(FileSourceConnection::<ReferencedConnection> {
+                store,
+                prefix,
+                format,
+            })
+        }
+        CreateSourceConnection::Kinesis {
+            connection,
+            options,
+        } => {
+            let connection = plan_kinesis_source_connection(scx, connection, options)?;
+
+            if !matches!(envelope, ast::SourceEnvelope::None) {
+                bail_unsupported!(format!("ENVELOPE {} with Kinesis sources", envelope));
+            }
+            if let Some(FormatSpecifier::KeyValue { .. }) = format {
+                sql_bail!("KEY FORMAT is not supported by Kinesis sources");
+            }
+
+            GenericSourceConnection::from(connection)
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let (load_generator, _available_subsources) =
                 load_generator_ast_to_generator(scx, generator, options, include_metadata)?;
@@ -1082,10 +1588,14 @@ pub fn plan_create_source(
         timestamp_interval,
         ignore_keys,
         retain_history,
+        on_decode_error,
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
 
-    let encoding = match format {
+    let is_file_source = matches!(external_connection, GenericSourceConnection::File(_));
+    let mut encoding = match format {
+        // Parquet objects are decoded by the source itself.
+        Some(FormatSpecifier::Bare(Format::Parquet)) if is_file_source => None,
         Some(format) => Some(get_encoding(scx, format, &envelope)?),
         None => None,
     };
@@ -1134,6 +1644,20 @@ pub fn plan_create_source(
         ),
     };
 
+    // File sources skip the header line of every object themselves, so the
+    // header names only determine the columns of the source.
+    if is_file_source {
+        if let Some(SourceDataEncoding {
+            value: DataEncoding::Csv(CsvEncoding { columns, .. }),
+            ..
+        }) = &mut encoding
+        {
+            if let ColumnSpec::Header { names } = columns {
+                *columns = ColumnSpec::Count(names.len());
+            }
+        }
+    }
+
     // KEY VALUE load generators are the only UPSERT source that
     // has no encoding but defaults to `INCLUDE KEY`.
     //
@@ -1220,7 +1744,7 @@ pub fn plan_create_source(
             scx.require_feature_flag(&vars::ENABLE_ENVELOPE_MATERIALIZE)?;
             //TODO check that key envelope is not set
             match format {
-                Some(CreateSourceFormat::Bare(Format::Avro(_))) => {}
+                Some(FormatSpecifier::Bare(Format::Avro(_))) => {}
                 _ => bail_unsupported!("non-Avro-encoded ENVELOPE MATERIALIZE"),
             }
             UnplannedSourceEnvelope::CdcV2
@@ -1297,11 +1821,33 @@ pub fn plan_create_source(
         None => scx.catalog.config().timestamp_interval,
     };
 
+    let decode_error_policy = match on_decode_error {
+        None => DecodeErrorPolicy::Error,
+        Some(policy) => {
+            if encoding.is_none() {
+                sql_bail!("ON DECODE ERROR requires a FORMAT that is decoded by Materialize");
+            }
+            if matches!(envelope, SourceEnvelope::CdcV2) {
+                bail_unsupported!("ON DECODE ERROR with ENVELOPE MATERIALIZE");
+            }
+            match policy.to_lowercase().as_str() {
+                "error" => DecodeErrorPolicy::Error,
+                "skip" => DecodeErrorPolicy::Skip,
+                "dead_letter" => DecodeErrorPolicy::DeadLetter,
+                _ => sql_bail!(
+                    "invalid ON DECODE ERROR {}: must be one of 'error', 'skip', or 'dead_letter'",
+                    policy.quoted()
+                ),
+            }
+        }
+    };
+
     let source_desc = SourceDesc::<ReferencedConnection> {
         connection: external_connection,
         encoding,
         envelope: envelope.clone(),
         timestamp_interval,
+        decode_error_policy,
     };
 
     let progress_subsource = match progress_subsource {
@@ -1803,12 +2349,15 @@ fn typecheck_debezium(value_desc: &RelationDesc) -> Result<(Option<usize>, usize
 
 fn get_encoding(
     scx: &StatementContext,
-    format: &CreateSourceFormat<Aug>,
+    format: &FormatSpecifier<Aug>,
     envelope: &ast::SourceEnvelope,
 ) -> Result<SourceDataEncoding<ReferencedConnection>, PlanError> {
     let encoding = match format {
-        CreateSourceFormat::Bare(format) => get_encoding_inner(scx, format)?,
-        CreateSourceFormat::KeyValue { key, value } => {
+        FormatSpecifier::Bare(format) => get_encoding_inner(scx, format)?,
+        FormatSpecifier::KeyValue { key, value } => {
+            if let Format::Json { array: true, .. } | Format::Json { lines: true, .. } = key {
+                bail_unsupported!(format!("KEY FORMAT {}", key));
+            }
             let key = {
                 let encoding = get_encoding_inner(scx, key)?;
                 Some(encoding.key.unwrap_or(encoding.value))
@@ -1818,6 +2367,20 @@ fn get_encoding(
         }
     };
 
+    // A framed JSON value decodes to any number of rows, none of which can be
+    // associated with a unique key or offset.
+    if !matches!(envelope, ast::SourceEnvelope::None) {
+        match &encoding.value {
+            DataEncoding::Json(JsonEncoding {
+                framing: JsonFraming::Array,
+            }) => sql_bail!("FORMAT JSON ARRAY is only supported with ENVELOPE NONE"),
+            DataEncoding::Json(JsonEncoding {
+                framing: JsonFraming::Lines,
+            }) => sql_bail!("FORMAT JSON LINES is only supported with ENVELOPE NONE"),
+            _ => {}
+        }
+    }
+
     let requires_keyvalue = matches!(
         envelope,
         ast::SourceEnvelope::Debezium | ast::SourceEnvelope::Upsert
@@ -2043,9 +2606,22 @@ fn get_encoding_inner(
                     .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?,
             })
         }
-        Format::Json { array: false } => DataEncoding::Json,
-        Format::Json { array: true } => bail_unsupported!("JSON ARRAY format in sources"),
+        Format::Json {
+            csr_connection: Some(_),
+            ..
+        } => bail_unsupported!("FORMAT JSON USING CONFLUENT SCHEMA REGISTRY in sources"),
+        Format::Json { array, lines, .. } => {
+            let framing = match (array, lines) {
+                (true, _) => JsonFraming::Array,
+                (false, true) => JsonFraming::Lines,
+                (false, false) => JsonFraming::Single,
+            };
+            DataEncoding::Json(JsonEncoding { framing })
+        }
         Format::Text => DataEncoding::Text,
+        Format::Parquet => {
+            sql_bail!("FORMAT PARQUET is only supported by S3 sources")
+        }
     };
     Ok(SourceDataEncoding { key: None, value })
 }
@@ -2091,7 +2667,7 @@ fn get_unnamed_key_envelope(
     //
     // Otherwise it gets the names of the columns in the type
     let is_composite = match key {
-        Some(DataEncoding::Bytes | DataEncoding::Json | DataEncoding::Text) => false,
+        Some(DataEncoding::Bytes | DataEncoding::Json(_) | DataEncoding::Text) => false,
         Some(
             DataEncoding::Avro(_)
             | DataEncoding::Csv(_)
@@ -2151,6 +2727,12 @@ pub fn plan_view(
 
     expr.bind_parameters(params)?;
 
+    // Sequence functions are only evaluated by the statements that call them
+    // directly.
+    if !expr.sequence_ids().is_empty() {
+        sql_bail!("views cannot call sequence functions");
+    }
+
     let name = if temporary {
         scx.allocate_temporary_qualified_name(normalize::unresolved_item_name(name.to_owned())?)?
     } else {
@@ -2740,6 +3322,9 @@ pub fn plan_create_sink(
 
     let CreateSinkOptionExtracted { snapshot, seen: _ } = with_options.try_into()?;
 
+    let StorageSinkConnection::Kafka(KafkaSinkConnection { format, .. }) = &connection_builder;
+    add_json_schema_options(&mut stmt.format, format);
+
     // WITH SNAPSHOT defaults to true
     let with_snapshot = snapshot.unwrap_or(true);
 
@@ -2763,6 +3348,54 @@ pub fn plan_create_sink(
     }))
 }
 
+/// Records the JSON Schemas that a sink publishes to a schema registry as
+/// options of the sink's `FORMAT JSON` clauses, so that they are exposed by
+/// `SHOW CREATE SINK` and stay fixed for the lifetime of the sink.
+fn add_json_schema_options(
+    format_specifier: &mut Option<FormatSpecifier<Aug>>,
+    format: &KafkaSinkFormat<ReferencedConnection>,
+) {
+    let key_schema = match &format.key_format {
+        Some(KafkaSinkFormatType::JsonSchema { schema, .. }) => Some(schema),
+        _ => None,
+    };
+    let value_schema = match &format.value_format {
+        KafkaSinkFormatType::JsonSchema { schema, .. } => Some(schema),
+        _ => None,
+    };
+    let formats = match format_specifier {
+        Some(FormatSpecifier::Bare(format)) => vec![(format, key_schema, value_schema)],
+        Some(FormatSpecifier::KeyValue { key, value }) => {
+            vec![(key, key_schema, None), (value, None, value_schema)]
+        }
+        None => vec![],
+    };
+    for (format, key_schema, value_schema) in formats {
+        let Format::Json {
+            csr_connection: Some(CsrConnection { options, .. }),
+            ..
+        } = format
+        else {
+            continue;
+        };
+        for (name, schema) in [
+            (CsrConfigOptionName::JsonKeySchema, key_schema),
+            (CsrConfigOptionName::JsonValueSchema, value_schema),
+        ] {
+            let Some(schema) = schema else {
+                continue;
+            };
+            if options.iter().any(|o| o.name == name) {
+                continue;
+            }
+            options.push(CsrConfigOption {
+                name,
+                value: Some(WithOptionValue::Value(Value::String(schema.clone()))),
+            });
+        }
+    }
+}
+
 fn key_constraint_err(desc: &RelationDesc, user_keys: &[ColumnName]) -> PlanError {
     let user_keys = user_keys.iter().map(|column| column.as_str()).join(", ");
 
@@ -2795,6 +3428,8 @@ pub struct CsrConfigOptionExtracted {
     pub(crate) null_defaults: bool,
     pub(crate) value_doc_options: BTreeMap<DocTarget, String>,
     pub(crate) key_doc_options: BTreeMap<DocTarget, String>,
+    pub(crate) json_key_schema: Option<String>,
+    pub(crate) json_value_schema: Option<String>,
 }
 
 impl std::convert::TryFrom<Vec<CsrConfigOption<Aug>>> for CsrConfigOptionExtracted {
@@ -2826,6 +3461,14 @@ impl std::convert::TryFrom<Vec<CsrConfigOption<Aug>>> for CsrConfigOptionExtract
                     extracted.null_defaults =
                         <bool>::try_from_value(option.value).map_err(better_error)?;
                 }
+                CsrConfigOptionName::JsonKeySchema => {
+                    extracted.json_key_schema =
+                        <Option<String>>::try_from_value(option.value).map_err(better_error)?;
+                }
+                CsrConfigOptionName::JsonValueSchema => {
+                    extracted.json_value_schema =
+                        <Option<String>>::try_from_value(option.value).map_err(better_error)?;
+                }
                 CsrConfigOptionName::AvroDocOn(doc_on) => {
                     let value = String::try_from_value(option.value.ok_or_else(|| {
                         PlanError::InvalidOptionValue {
@@ -2880,7 +3523,7 @@ fn kafka_sink_builder(
     scx: &StatementContext,
     connection: ResolvedItemName,
     options: Vec<KafkaSinkConfigOption<Aug>>,
-    format: Option<Format<Aug>>,
+    format: Option<FormatSpecifier<Aug>>,
     relation_key_indices: Option<Vec<usize>>,
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     headers_index: Option<usize>,
@@ -2902,7 +3545,9 @@ fn kafka_sink_builder(
     let KafkaSinkConfigOptionExtracted {
         topic,
         compression_type,
+        partition_by,
         progress_group_id_prefix,
+        tombstones,
         transactional_id_prefix,
         legacy_ids,
         seen: _,
@@ -2926,95 +3571,364 @@ fn kafka_sink_builder(
 
     let topic_name = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
 
-    let format = match format {
-        Some(Format::Avro(AvroSchema::Csr {
-            csr_connection:
-                CsrConnectionAvro {
-                    connection:
-                        CsrConnection {
-                            connection,
-                            options,
-                        },
-                    seed,
-                    key_strategy,
-                    value_strategy,
-                },
-        })) => {
-            if seed.is_some() {
-                sql_bail!("SEED option does not make sense with sinks");
+    let partition_by = match partition_by {
+        Some(mut partition_by) => {
+            scx.require_feature_flag(&vars::ENABLE_KAFKA_SINK_PARTITION_BY)?;
+            transform_ast::transform(scx, &mut partition_by)?;
+            let scope = Scope::from_source(None, value_desc.iter_names());
+            let ecx = &ExprContext {
+                qcx: &QueryContext::root(scx, QueryLifetime::OneShot),
+                name: "PARTITION BY",
+                scope: &scope,
+                relation_type: value_desc.typ(),
+                allow_aggregates: false,
+                allow_subqueries: false,
+                allow_parameters: false,
+                allow_windows: false,
+            };
+            let expr = plan_expr(ecx, &partition_by)?
+                .cast_to(ecx, CastContext::Assignment, &ScalarType::UInt64)?
+                .lower_uncorrelated()?;
+            // The expression is evaluated once per message by the sink, so it
+            // must produce the same result no matter when it is evaluated.
+            if expr.contains_unmaterializable() {
+                sql_bail!("PARTITION BY expression cannot call unmaterializable functions");
             }
-            if key_strategy.is_some() {
-                sql_bail!("KEY STRATEGY option does not make sense with sinks");
+            Some(expr)
+        }
+        None => None,
+    };
+
+    let tombstones = match (envelope, tombstones) {
+        (SinkEnvelope::Debezium, Some(_)) => {
+            sql_bail!("TOMBSTONES option is not supported with ENVELOPE DEBEZIUM")
+        }
+        (_, Some(tombstones)) => {
+            scx.require_feature_flag(&vars::ENABLE_KAFKA_SINK_TOMBSTONES_OPTION)?;
+            tombstones
+        }
+        (_, None) => true,
+    };
+
+    let resolve_csr_connection = |connection: &ResolvedItemName| -> Result<GlobalId, PlanError> {
+        let item = scx.get_item_by_resolved_name(connection)?;
+        match item.connection()? {
+            Connection::Csr(_) => Ok(item.id()),
+            _ => {
+                sql_bail!(
+                    "{} is not a schema registry connection",
+                    scx.catalog
+                        .resolve_full_name(item.name())
+                        .to_string()
+                        .quoted()
+                )
             }
-            if value_strategy.is_some() {
-                sql_bail!("VALUE STRATEGY option does not make sense with sinks");
+        }
+    };
+
+    let map_format = |format: Format<Aug>,
+                      desc: &RelationDesc,
+                      is_key: bool,
+                      bare: bool|
+     -> Result<KafkaSinkFormatType<ReferencedConnection>, PlanError> {
+        match format {
+            Format::Avro(AvroSchema::Csr {
+                csr_connection:
+                    CsrConnectionAvro {
+                        connection:
+                            CsrConnection {
+                                connection,
+                                options,
+                            },
+                        seed,
+                        key_strategy,
+                        value_strategy,
+                    },
+            }) => {
+                if seed.is_some() {
+                    sql_bail!("SEED option does not make sense with sinks");
+                }
+                if key_strategy.is_some() {
+                    sql_bail!("KEY STRATEGY option does not make sense with sinks");
+                }
+                if value_strategy.is_some() {
+                    sql_bail!("VALUE STRATEGY option does not make sense with sinks");
+                }
+
+                let csr_connection = resolve_csr_connection(&connection)?;
+                let CsrConfigOptionExtracted {
+                    avro_key_fullname,
+                    avro_value_fullname,
+                    null_defaults,
+                    key_doc_options,
+                    value_doc_options,
+                    json_key_schema,
+                    json_value_schema,
+                    ..
+                } = options.try_into()?;
+
+                if json_key_schema.is_some() || json_value_schema.is_some() {
+                    sql_bail!("JSON KEY SCHEMA and JSON VALUE SCHEMA options require FORMAT JSON");
+                }
+
+                if key_desc_and_indices.is_none() && avro_key_fullname.is_some() {
+                    sql_bail!("Cannot specify AVRO KEY FULLNAME without a corresponding KEY field");
+                }
+
+                if key_desc_and_indices.is_some()
+                    && (avro_key_fullname.is_some() ^ avro_value_fullname.is_some())
+                {
+                    sql_bail!("Must specify both AVRO KEY FULLNAME and AVRO VALUE FULLNAME when specifying generated schema names");
+                }
+
+                let options = AvroSchemaOptions {
+                    avro_key_fullname,
+                    avro_value_fullname,
+                    set_null_defaults: null_defaults,
+                    is_debezium: matches!(envelope, SinkEnvelope::Debezium),
+                    sink_from: Some(sink_from),
+                    value_doc_options,
+                    key_doc_options,
+                };
+
+                let schema_generator = AvroSchemaGenerator::new(
+                    key_desc_and_indices
+                        .as_ref()
+                        .map(|(desc, _indices)| desc.clone()),
+                    value_desc.clone(),
+                    options,
+                )?;
+                let schema = if is_key {
+                    schema_generator
+                        .key_writer_schema()
+                        .expect("key schema is generated when the sink has a key")
+                        .to_string()
+                } else {
+                    schema_generator.value_writer_schema().to_string()
+                };
+
+                Ok(KafkaSinkFormatType::Avro {
+                    schema,
+                    csr_connection,
+                })
             }
+            Format::Avro(AvroSchema::InlineSchema {
+                schema: ast::Schema { schema },
+                with_options,
+            }) => {
+                if !with_options.is_empty() {
+                    sql_bail!("CONFLUENT WIRE FORMAT option does not make sense with sinks");
+                }
 
-            let item = scx.get_item_by_resolved_name(&connection)?;
-            let csr_connection = match item.connection()? {
-                Connection::Csr(_) => item.id(),
-                _ => {
+                // The schema is never published anywhere, so consumers must be
+                // able to decode messages using exactly the provided schema.
+                // Derive the writer schema from the relation and require that
+                // it matches. A bare `FORMAT` only provides the value schema,
+                // so in that case the key schema is always the generated one.
+                let provided_schema = mz_interchange::avro::parse_schema(&schema)
+                    .map_err(|e| sql_err!("invalid Avro schema: {}", e))?;
+                let provided_fullname = provided_schema
+                    .top_node()
+                    .name
+                    .map(|name| name.human_name());
+                let options = if is_key {
+                    AvroSchemaOptions {
+                        avro_key_fullname: if bare { None } else { provided_fullname },
+                        ..Default::default()
+                    }
+                } else {
+                    AvroSchemaOptions {
+                        avro_value_fullname: provided_fullname,
+                        is_debezium: matches!(envelope, SinkEnvelope::Debezium),
+                        ..Default::default()
+                    }
+                };
+                let schema_generator = AvroSchemaGenerator::new(
+                    key_desc_and_indices
+                        .as_ref()
+                        .map(|(desc, _indices)| desc.clone()),
+                    value_desc.clone(),
+                    options,
+                )?;
+                let schema = if is_key {
+                    schema_generator
+                        .key_writer_schema()
+                        .expect("key schema is generated when the sink has a key")
+                        .clone()
+                } else {
+                    schema_generator.value_writer_schema().clone()
+                };
+                if !(is_key && bare) && schema.canonical_form() != provided_schema.canonical_form()
+                {
                     sql_bail!(
-                        "{} is not a schema registry connection",
+                        "provided Avro {} schema does not match the schema of {}; expected: {}",
+                        if is_key { "key" } else { "value" },
                         scx.catalog
-                            .resolve_full_name(item.name())
+                            .resolve_full_name(scx.get_item(&sink_from).name())
                             .to_string()
-                            .quoted()
+                            .quoted(),
+                        schema.to_string()
+                    );
+                }
+
+                Ok(KafkaSinkFormatType::AvroInline {
+                    schema: schema.to_string(),
+                })
+            }
+            Format::Json { array: true, .. } => bail_unsupported!("JSON ARRAY format in sinks"),
+            Format::Json { lines: true, .. } => bail_unsupported!("JSON LINES format in sinks"),
+            Format::Json {
+                array: false,
+                lines: false,
+                csr_connection: None,
+            } => Ok(KafkaSinkFormatType::Json),
+            Format::Json {
+                array: false,
+                lines: false,
+                csr_connection:
+                    Some(CsrConnection {
+                        connection,
+                        options,
+                    }),
+            } => {
+                let csr_connection = resolve_csr_connection(&connection)?;
+                let CsrConfigOptionExtracted {
+                    json_key_schema,
+                    json_value_schema,
+                    seen,
+                    ..
+                } = options.try_into()?;
+
+                if let Some(name) = seen.iter().find(|name| {
+                    !matches!(
+                        name,
+                        CsrConfigOptionName::JsonKeySchema | CsrConfigOptionName::JsonValueSchema
                     )
+                }) {
+                    sql_bail!("{} option requires FORMAT AVRO", name);
                 }
-            };
-            let CsrConfigOptionExtracted {
-                avro_key_fullname,
-                avro_value_fullname,
-                null_defaults,
-                key_doc_options,
-                value_doc_options,
-                ..
-            } = options.try_into()?;
+                if key_desc_and_indices.is_none() && json_key_schema.is_some() {
+                    sql_bail!("Cannot specify JSON KEY SCHEMA without a corresponding KEY field");
+                }
+
+                // The generated schemas are recorded in the `create_sql` of
+                // the sink (see `plan_create_sink`), so that the schema of an
+                // existing sink does not change if the way schemas are
+                // generated does.
+                let provided_schema = if is_key {
+                    json_key_schema
+                } else {
+                    json_value_schema
+                };
+                let generated_schema = mz_interchange::json::build_json_schema(
+                    desc.clone(),
+                    !is_key && matches!(envelope, SinkEnvelope::Debezium),
+                );
+                let schema = match provided_schema {
+                    // Consumers decode messages using the published schema, so
+                    // a provided schema must describe exactly the messages the
+                    // sink writes. Only its formatting may differ from the
+                    // generated one.
+                    Some(schema) => {
+                        let parsed: serde_json::Value = serde_json::from_str(&schema)
+                            .map_err(|e| sql_err!("invalid JSON Schema: {}", e))?;
+                        if !parsed.is_object() {
+                            sql_bail!("invalid JSON Schema: must be a JSON object");
+                        }
+                        if parsed != generated_schema {
+                            sql_bail!(
+                                "provided JSON {} schema does not match the schema of {}; expected: {}",
+                                if is_key { "key" } else { "value" },
+                                scx.catalog
+                                    .resolve_full_name(scx.get_item(&sink_from).name())
+                                    .to_string()
+                                    .quoted(),
+                                generated_schema
+                            );
+                        }
+                        schema
+                    }
+                    None => generated_schema.to_string(),
+                };
 
-            if key_desc_and_indices.is_none() && avro_key_fullname.is_some() {
-                sql_bail!("Cannot specify AVRO KEY FULLNAME without a corresponding KEY field");
+                Ok(KafkaSinkFormatType::JsonSchema {
+                    schema,
+                    csr_connection,
+                })
             }
+            Format::Protobuf(ProtobufSchema::InlineSchema {
+                message_name,
+                schema: ast::Schema { schema },
+            }) => {
+                if is_key && bare {
+                    sql_bail!(
+                        "FORMAT PROTOBUF cannot be used for the key of a sink; \
+                        specify KEY FORMAT and VALUE FORMAT instead"
+                    );
+                }
+
+                let descriptors = strconv::parse_bytes(&schema)?;
+                let decoded = DecodedDescriptors::from_bytes(&descriptors, message_name.clone())?;
+                // Constructing the encoder checks the message against the relation.
+                ProtobufEncoder::new(
+                    decoded,
+                    desc.clone(),
+                    !is_key && matches!(envelope, SinkEnvelope::Debezium),
+                )?;
 
-            if key_desc_and_indices.is_some()
-                && (avro_key_fullname.is_some() ^ avro_value_fullname.is_some())
+                Ok(KafkaSinkFormatType::Protobuf {
+                    descriptors,
+                    message_name,
+                })
+            }
+            // The Debezium envelope wraps the value in a record of `before`
+            // and `after` fields, which cannot be written as a single text or
+            // bytes value.
+            Format::Text | Format::Bytes
+                if !is_key && matches!(envelope, SinkEnvelope::Debezium) =>
             {
-                sql_bail!("Must specify both AVRO KEY FULLNAME and AVRO VALUE FULLNAME when specifying generated schema names");
+                sql_bail!(
+                    "FORMAT {} cannot be used for the value of a sink with ENVELOPE DEBEZIUM",
+                    if matches!(format, Format::Text) {
+                        "TEXT"
+                    } else {
+                        "BYTES"
+                    }
+                )
+            }
+            Format::Text => {
+                TextEncoder::new(desc.clone())?;
+                Ok(KafkaSinkFormatType::Text)
             }
+            Format::Bytes => {
+                BytesEncoder::new(desc.clone())?;
+                Ok(KafkaSinkFormatType::Bytes)
+            }
+            format => bail_unsupported!(format!("sink format {:?}", format)),
+        }
+    };
 
-            let options = AvroSchemaOptions {
-                avro_key_fullname,
-                avro_value_fullname,
-                set_null_defaults: null_defaults,
-                is_debezium: matches!(envelope, SinkEnvelope::Debezium),
-                sink_from: Some(sink_from),
-                value_doc_options,
-                key_doc_options,
+    let (key_format, value_format) = match format {
+        Some(FormatSpecifier::Bare(format)) => {
+            let key_format = match &key_desc_and_indices {
+                Some((desc, _indices)) => Some(map_format(format.clone(), desc, true, true)?),
+                None => None,
             };
-
-            let schema_generator = AvroSchemaGenerator::new(
-                key_desc_and_indices
-                    .as_ref()
-                    .map(|(desc, _indices)| desc.clone()),
-                value_desc.clone(),
-                options,
-            )?;
-            let value_schema = schema_generator.value_writer_schema().to_string();
-            let key_schema = schema_generator
-                .key_writer_schema()
-                .map(|key_schema| key_schema.to_string());
-
-            KafkaSinkFormat::Avro {
-                key_schema,
-                value_schema,
-                csr_connection,
-            }
+            (key_format, map_format(format, &value_desc, false, true)?)
+        }
+        Some(FormatSpecifier::KeyValue { key, value }) => {
+            let key_format = match &key_desc_and_indices {
+                Some((desc, _indices)) => Some(map_format(key, desc, true, false)?),
+                None => sql_bail!("KEY FORMAT specified without a KEY"),
+            };
+            (key_format, map_format(value, &value_desc, false, false)?)
         }
-        Some(Format::Json { array: false }) => KafkaSinkFormat::Json,
-        Some(Format::Json { array: true }) => bail_unsupported!("JSON ARRAY format in sinks"),
-        Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
     };
+    let format = KafkaSinkFormat {
+        key_format,
+        value_format,
+    };
 
     Ok(StorageSinkConnection::Kafka(KafkaSinkConnection {
         connection_id,
@@ -3028,6 +3942,8 @@ fn kafka_sink_builder(
         compression_type,
         progress_group_id,
         transactional_id,
+        partition_by,
+        tombstones,
     }))
 }
 
@@ -3906,6 +4822,127 @@ pub fn plan_create_secret(
     }))
 }
 
+pub fn describe_create_sequence(
+    _: &StatementContext,
+    _: CreateSequenceStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_sequence(
+    scx: &StatementContext,
+    stmt: CreateSequenceStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let create_sql = normalize::create_statement(scx, Statement::CreateSequence(stmt.clone()))?;
+    let CreateSequenceStatement {
+        name,
+        if_not_exists,
+        options,
+    } = stmt;
+
+    let mut ty = ScalarType::Int64;
+    for option in &options {
+        if let SequenceOption::As(data_type) = option {
+            ty = query::scalar_type_from_sql(scx, data_type)?;
+        }
+    }
+    let options = plan_sequence_options(&options, &ty)?;
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
+    let full_name = scx.catalog.resolve_full_name(&name);
+    let partial_name = PartialItemName::from(full_name.clone());
+    // Like tables, sequences conflict with types of the same name.
+    if let (false, Ok(item)) = (
+        if_not_exists,
+        scx.catalog.resolve_item_or_type(&partial_name),
+    ) {
+        return Err(PlanError::ItemAlreadyExists {
+            name: full_name.to_string(),
+            item_type: item.item_type(),
+        });
+    }
+
+    Ok(Plan::CreateSequence(CreateSequencePlan {
+        name,
+        sequence: Sequence {
+            create_sql,
+            options,
+        },
+        if_not_exists,
+    }))
+}
+
+/// The number of values of a sequence that are reserved durably at a time,
+/// unless it specifies `CACHE`. As in PostgreSQL, values are not cached by
+/// default, so that sequences don't have gaps after restarts.
+const DEFAULT_SEQUENCE_CACHE: u64 = 1;
+
+/// Plans the options of a sequence whose values are of type `ty`, which is
+/// either specified by its `AS` option or is the type of its identity column.
+fn plan_sequence_options(
+    options: &[SequenceOption<Aug>],
+    ty: &ScalarType,
+) -> Result<SequenceOptions, PlanError> {
+    let (type_min, type_max, type_name) = match ty {
+        ScalarType::Int16 => (i64::from(i16::MIN), i64::from(i16::MAX), "smallint"),
+        ScalarType::Int32 => (i64::from(i32::MIN), i64::from(i32::MAX), "integer"),
+        ScalarType::Int64 => (i64::MIN, i64::MAX, "bigint"),
+        _ => sql_bail!("sequence type must be smallint, integer, or bigint"),
+    };
+
+    let mut seen = BTreeSet::new();
+    let (mut increment, mut min_value, mut max_value) = (1, None, None);
+    let (mut start, mut cache, mut cycle) = (None, DEFAULT_SEQUENCE_CACHE, false);
+    for option in options {
+        if !seen.insert(std::mem::discriminant(option)) {
+            sql_bail!("conflicting or redundant options");
+        }
+        match option {
+            SequenceOption::As(_) => {}
+            SequenceOption::IncrementBy(n) => increment = *n,
+            SequenceOption::MinValue(n) => min_value = *n,
+            SequenceOption::MaxValue(n) => max_value = *n,
+            SequenceOption::StartWith(n) => start = Some(*n),
+            SequenceOption::Cache(n) => cache = *n,
+            SequenceOption::Cycle(b) => cycle = *b,
+        }
+    }
+
+    if increment == 0 {
+        sql_bail!("INCREMENT must not be zero");
+    }
+    let min_value = min_value.unwrap_or(if increment > 0 { 1 } else { type_min });
+    let max_value = max_value.unwrap_or(if increment > 0 { type_max } else { -1 });
+    if min_value < type_min || min_value > type_max {
+        sql_bail!("MINVALUE ({min_value}) is out of range for sequence data type {type_name}");
+    }
+    if max_value < type_min || max_value > type_max {
+        sql_bail!("MAXVALUE ({max_value}) is out of range for sequence data type {type_name}");
+    }
+    if min_value >= max_value {
+        sql_bail!("MINVALUE ({min_value}) must be less than MAXVALUE ({max_value})");
+    }
+    let start = start.unwrap_or(if increment > 0 { min_value } else { max_value });
+    if start < min_value {
+        sql_bail!("START value ({start}) cannot be less than MINVALUE ({min_value})");
+    }
+    if start > max_value {
+        sql_bail!("START value ({start}) cannot be greater than MAXVALUE ({max_value})");
+    }
+    if cache == 0 {
+        sql_bail!("CACHE (0) must be greater than zero");
+    }
+
+    Ok(SequenceOptions {
+        increment,
+        min_value,
+        max_value,
+        start,
+        cache,
+        cycle,
+    })
+}
+
 pub fn describe_create_connection(
     _: &StatementContext,
     _: CreateConnectionStatement<Aug>,
@@ -3934,6 +4971,8 @@ pub fn plan_create_connection(
         scx.require_feature_flag(&vars::ENABLE_AWS_CONNECTION)?;
     } else if let Connection::MySql(_) = &connection {
         scx.require_feature_flag(&vars::ENABLE_MYSQL_SOURCE)?;
+    } else if let Connection::SqlServer(_) = &connection {
+        scx.require_feature_flag(&vars::ENABLE_SQL_SERVER_SOURCE)?;
     }
     let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
 
@@ -4225,6 +5264,7 @@ fn dependency_prevents_drop(object_type: ObjectType, dep: &dyn CatalogItem) -> b
             | CatalogItemType::Sink
             | CatalogItemType::Type
             | CatalogItemType::Secret
+            | CatalogItemType::Sequence
             | CatalogItemType::Connection => true,
             CatalogItemType::Index => false,
         },
@@ -4914,6 +5954,7 @@ pub fn plan_alter_object_rename(
             | ObjectType::Index
             | ObjectType::Sink
             | ObjectType::Secret
+            | ObjectType::Sequence
             | ObjectType::Connection,
             UnresolvedObjectName::Item(name),
         ) => plan_alter_item_rename(scx, object_type, name, to_item_name, if_exists),
@@ -5439,6 +6480,7 @@ pub fn plan_alter_connection(
         Connection::Postgres(_) => CreateConnectionType::Postgres,
         Connection::Ssh(_) => CreateConnectionType::Ssh,
         Connection::MySql(_) => CreateConnectionType::MySql,
+        Connection::SqlServer(_) => CreateConnectionType::SqlServer,
     };
 
     // Collect all options irrespective of action taken on them.
@@ -5641,6 +6683,12 @@ pub fn plan_alter_source(
         AlterSourceAction::AddSubsources { .. } => {
             unreachable!("ALTER SOURCE...ADD SUBSOURCE must be purified")
         }
+        AlterSourceAction::RefreshSchema => {
+            unreachable!("ALTER SOURCE...REFRESH SCHEMA must be purified")
+        }
+        AlterSourceAction::ResetTo { .. } => {
+            unreachable!("ALTER SOURCE...RESET TO must be purified")
+        }
     };
 }
 
@@ -26,7 +26,8 @@ use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{
     AwsPrivatelink, AwsPrivatelinkConnection, CsrConnection, CsrConnectionHttpAuth,
     KafkaConnection, KafkaSaslConfig, KafkaTlsConfig, MySqlConnection, MySqlSslMode,
-    PostgresConnection, SshConnection, SshTunnel, StringOrSecret, TlsIdentity, Tunnel,
+    PostgresConnection, SqlServerConnection, SqlServerEncryptionMode, SshConnection, SshTunnel,
+    StringOrSecret, TlsIdentity, Tunnel,
 };
 
 use crate::names::Aug;
@@ -147,6 +148,16 @@ pub(super) fn validate_options_per_connection_type(
             SslMode,
             User,
         ],
+        CreateConnectionType::SqlServer => &[
+            AwsPrivatelink,
+            Database,
+            Host,
+            Password,
+            Port,
+            SshTunnel,
+            SslMode,
+            User,
+        ],
     };
 
     for o in permitted_options {
@@ -429,6 +440,44 @@ impl ConnectionOptionExtracted {
                         .ok_or_else(|| sql_err!("USER option is required"))?,
                 })
             }
+            CreateConnectionType::SqlServer => {
+                scx.require_feature_flag(&crate::session::vars::ENABLE_SQL_SERVER_SOURCE)?;
+
+                let encryption = match self
+                    .ssl_mode
+                    .map(|f| f.to_uppercase())
+                    .as_ref()
+                    .map(|m| m.as_str())
+                {
+                    None | Some("DISABLED") => SqlServerEncryptionMode::Disabled,
+                    Some("REQUIRED") => SqlServerEncryptionMode::Required,
+                    Some("VERIFY") => SqlServerEncryptionMode::Verify,
+                    Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+                };
+
+                if let Some(privatelink) = self.aws_privatelink.as_ref() {
+                    if privatelink.port.is_some() {
+                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
+                    }
+                }
+                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
+
+                Connection::SqlServer(SqlServerConnection {
+                    database: self
+                        .database
+                        .ok_or_else(|| sql_err!("DATABASE option is required"))?,
+                    password: self.password.map(|password| password.into()),
+                    host: self
+                        .host
+                        .ok_or_else(|| sql_err!("HOST option is required"))?,
+                    port: self.port.unwrap_or(1433_u16),
+                    tunnel,
+                    encryption,
+                    user: self
+                        .user
+                        .ok_or_else(|| sql_err!("USER option is required"))?,
+                })
+            }
         };
 
         Ok(connection)
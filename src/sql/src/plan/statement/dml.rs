@@ -18,7 +18,7 @@ use std::collections::BTreeMap;
 use itertools::Itertools;
 
 use mz_arrow_util::builder::ArrowBuilder;
-use mz_expr::{MirRelationExpr, RowSetFinishing};
+use mz_expr::{MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
@@ -34,16 +34,16 @@ use mz_sql_parser::ast::{
 };
 use mz_sql_parser::ident;
 use mz_storage_types::sinks::{
-    KafkaSinkConnection, KafkaSinkFormat, S3SinkFormat, StorageSinkConnection,
+    KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType, S3SinkFormat, StorageSinkConnection,
     MAX_S3_SINK_FILE_SIZE, MIN_S3_SINK_FILE_SIZE,
 };
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AstInfo, CopyDirection, CopyOption, CopyOptionName, CopyRelation, CopyStatement, CopyTarget,
-    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement, Query,
-    SelectStatement, SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement,
-    UpdateStatement,
+    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement,
+    MergeStatement, OnConflictAction, Query, SelectStatement, SubscribeOption, SubscribeOptionName,
+    SubscribeRelation, SubscribeStatement, UpdateStatement,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{Aug, ResolvedItemName};
@@ -57,8 +57,9 @@ use crate::plan::{
     ExplainSinkSchemaPlan, ExplainTimestampPlan,
 };
 use crate::plan::{
-    query, CopyFormat, CopyFromPlan, ExplainPlanPlan, InsertPlan, MutationKind, Params, Plan,
-    PlanError, QueryContext, ReadThenWritePlan, SelectPlan, SubscribeFrom, SubscribePlan,
+    query, CopyFormat, CopyFromPlan, CopyFromSource, ExplainPlanPlan, HirScalarExpr, InsertPlan,
+    MutationKind, Params, Plan, PlanError, QueryContext, ReadThenWritePlan, SelectPlan,
+    SubscribeFrom, SubscribePlan,
 };
 use crate::session::vars;
 
@@ -74,10 +75,21 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (_, expr, sequence_defaults, returning) = query::plan_insert_query(
+        scx,
+        table_name.clone(),
+        columns,
+        source,
+        returning,
+        on_conflict.is_some(),
+    )?;
+    if let Some(on_conflict) = on_conflict {
+        query::plan_insert_on_conflict(scx, table_name, expr, &sequence_defaults, on_conflict)?;
+    }
     let desc = if returning.expr.is_empty() {
         None
     } else {
@@ -92,24 +104,54 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
-    let (id, mut expr, returning) =
-        query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (id, mut expr, sequence_defaults, returning) = query::plan_insert_query(
+        scx,
+        table_name.clone(),
+        columns,
+        source,
+        returning,
+        on_conflict.is_some(),
+    )?;
     expr.bind_parameters(params)?;
     let returning = returning
         .expr
         .into_iter()
         .map(|expr| expr.lower_uncorrelated())
         .collect::<Result<Vec<_>, _>>()?;
-
-    Ok(Plan::Insert(InsertPlan {
-        id,
-        values: expr,
-        returning,
-    }))
+    let lowered_sequence_defaults = sequence_defaults
+        .iter()
+        .map(|(idx, expr)| Ok((*idx, expr.clone().lower_uncorrelated()?)))
+        .collect::<Result<BTreeMap<_, _>, PlanError>>()?;
+
+    match on_conflict {
+        None => Ok(Plan::Insert(InsertPlan {
+            id,
+            values: expr,
+            sequence_defaults: lowered_sequence_defaults,
+            returning,
+        })),
+        // Inserts with an `ON CONFLICT` clause need to read the table to find
+        // the conflicting rows.
+        Some(on_conflict) => {
+            let kind = match on_conflict.action {
+                OnConflictAction::DoNothing => MutationKind::Insert,
+                OnConflictAction::DoUpdate { .. } => MutationKind::Upsert,
+            };
+            let rtw_plan = query::plan_insert_on_conflict(
+                scx,
+                table_name,
+                expr,
+                &sequence_defaults,
+                on_conflict,
+            )?;
+            plan_read_then_write(kind, params, rtw_plan, lowered_sequence_defaults, returning)
+        }
+    }
 }
 
 pub fn describe_delete(
@@ -126,7 +168,13 @@ pub fn plan_delete(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_delete_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Delete, params, rtw_plan)
+    plan_read_then_write(
+        MutationKind::Delete,
+        params,
+        rtw_plan,
+        BTreeMap::new(),
+        Vec::new(),
+    )
 }
 
 pub fn describe_update(
@@ -143,7 +191,36 @@ pub fn plan_update(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_update_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Update, params, rtw_plan)
+    plan_read_then_write(
+        MutationKind::Update,
+        params,
+        rtw_plan,
+        BTreeMap::new(),
+        Vec::new(),
+    )
+}
+
+pub fn describe_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    query::plan_merge_query(scx, stmt)?;
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    let rtw_plan = query::plan_merge_query(scx, stmt)?;
+    plan_read_then_write(
+        MutationKind::Merge,
+        params,
+        rtw_plan,
+        BTreeMap::new(),
+        Vec::new(),
+    )
 }
 
 pub fn plan_read_then_write(
@@ -155,6 +232,8 @@ pub fn plan_read_then_write(
         finishing,
         assignments,
     }: query::ReadThenWritePlan,
+    sequence_defaults: BTreeMap<usize, MirScalarExpr>,
+    returning: Vec<MirScalarExpr>,
 ) -> Result<Plan, PlanError> {
     selection.bind_parameters(params)?;
     let mut assignments_outer = BTreeMap::new();
@@ -170,7 +249,8 @@ pub fn plan_read_then_write(
         finishing,
         assignments: assignments_outer,
         kind,
-        returning: Vec::new(),
+        sequence_defaults,
+        returning,
     }))
 }
 
@@ -594,18 +674,25 @@ pub fn plan_explain_schema(
         Plan::CreateSink(CreateSinkPlan { sink, .. }) => match sink.connection {
             StorageSinkConnection::Kafka(KafkaSinkConnection {
                 format:
-                    KafkaSinkFormat::Avro {
-                        key_schema,
-                        value_schema,
-                        ..
+                    KafkaSinkFormat {
+                        key_format,
+                        value_format,
                     },
                 ..
             }) => {
-                let schema = match schema_for {
+                let format = match schema_for {
                     ExplainSinkSchemaFor::Key => {
-                        key_schema.ok_or_else(|| sql_err!("CREATE SINK does not have a key"))?
+                        key_format.ok_or_else(|| sql_err!("CREATE SINK does not have a key"))?
                     }
-                    ExplainSinkSchemaFor::Value => value_schema,
+                    ExplainSinkSchemaFor::Value => value_format,
+                };
+                let schema = match format {
+                    KafkaSinkFormatType::Avro { schema, .. }
+                    | KafkaSinkFormatType::AvroInline { schema }
+                    | KafkaSinkFormatType::JsonSchema { schema, .. } => schema,
+                    _ => bail_unsupported!(
+                        "EXPLAIN SCHEMA is only available for Kafka sinks with Avro or JSON schemas"
+                    ),
                 };
 
                 Ok(Plan::ExplainSinkSchema(ExplainSinkSchemaPlan {
@@ -613,9 +700,6 @@ pub fn plan_explain_schema(
                     json_schema: schema,
                 }))
             }
-            _ => bail_unsupported!(
-                "EXPLAIN SCHEMA is only available for Kafka sinks with Avro schemas"
-            ),
         },
         _ => unreachable!("plan_create_sink returns a CreateSinkPlan"),
     }
@@ -983,6 +1067,30 @@ pub fn describe_copy(
     .with_is_copy())
 }
 
+/// Plans the scalar expression that resolves to the uri of a `COPY ... TO` or
+/// `COPY ... FROM` target.
+fn plan_copy_url_expr(
+    scx: &StatementContext,
+    expr: &Expr<Aug>,
+    name: &str,
+) -> Result<HirScalarExpr, PlanError> {
+    let mut expr = expr.clone();
+    transform_ast::transform(scx, &mut expr)?;
+    let relation_type = RelationDesc::empty();
+    let ecx = &ExprContext {
+        qcx: &QueryContext::root(scx, QueryLifetime::OneShot),
+        name,
+        scope: &Scope::empty(),
+        relation_type: relation_type.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+
+    plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::String)
+}
+
 fn plan_copy_to_expr(
     scx: &StatementContext,
     select_plan: SelectPlan,
@@ -1017,22 +1125,7 @@ fn plan_copy_to_expr(
         CopyFormat::Text => bail_unsupported!("FORMAT TEXT"),
     };
 
-    // Converting the to expr to a HirScalarExpr
-    let mut to_expr = to.clone();
-    transform_ast::transform(scx, &mut to_expr)?;
-    let relation_type = RelationDesc::empty();
-    let ecx = &ExprContext {
-        qcx: &QueryContext::root(scx, QueryLifetime::OneShot),
-        name: "COPY TO target",
-        scope: &Scope::empty(),
-        relation_type: relation_type.typ(),
-        allow_aggregates: false,
-        allow_subqueries: false,
-        allow_parameters: false,
-        allow_windows: false,
-    };
-
-    let to = plan_expr(ecx, &to_expr)?.type_as(ecx, &ScalarType::String)?;
+    let to = plan_copy_url_expr(scx, to, "COPY TO target")?;
 
     if options.max_file_size.as_bytes() < MIN_S3_SINK_FILE_SIZE.as_bytes() {
         sql_bail!(
@@ -1062,6 +1155,7 @@ fn plan_copy_from(
     scx: &StatementContext,
     table_name: ResolvedItemName,
     columns: Vec<Ident>,
+    from: Option<&Expr<Aug>>,
     format: CopyFormat,
     options: CopyOptionExtracted,
 ) -> Result<Plan, PlanError> {
@@ -1072,6 +1166,13 @@ fn plan_copy_from(
         }
     }
 
+    fn only_available_with_text_or_csv<T>(option: Option<T>, param: &str) -> Result<(), PlanError> {
+        match option {
+            Some(_) => sql_bail!("cannot specify {} in BINARY mode", param),
+            None => Ok(()),
+        }
+    }
+
     fn extract_byte_param_value(
         v: Option<String>,
         param_name: &str,
@@ -1111,13 +1212,48 @@ fn plan_copy_from(
                 .map_err(|e| sql_err!("{}", e))?,
             )
         }
-        CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
-        CopyFormat::Parquet => bail_unsupported!("FORMAT PARQUET"),
+        CopyFormat::Binary => {
+            only_available_with_text_or_csv(options.delimiter, "DELIMITER")?;
+            only_available_with_text_or_csv(options.null, "NULL")?;
+            only_available_with_csv(options.quote, "quote")?;
+            only_available_with_csv(options.escape, "escape")?;
+            only_available_with_csv(options.header, "HEADER")?;
+            CopyFormatParams::Binary
+        }
+        CopyFormat::Parquet if from.is_some() => CopyFormatParams::Parquet,
+        CopyFormat::Parquet => bail_unsupported!("FORMAT PARQUET with COPY FROM STDIN"),
+    };
+
+    let source = match from {
+        None => {
+            if options.aws_connection.is_some() {
+                sql_bail!("COPY FROM STDIN does not support AWS CONNECTION option");
+            }
+            CopyFromSource::Stdin
+        }
+        Some(from) => {
+            let connection_id = match options.aws_connection {
+                Some(conn_id) => GlobalId::from(conn_id),
+                None => sql_bail!("AWS CONNECTION is required for COPY ... FROM <expr>"),
+            };
+            let connection = scx.get_item(&connection_id).connection()?;
+            match connection {
+                mz_storage_types::connections::Connection::Aws(_) => {}
+                _ => sql_bail!("only AWS CONNECTION is supported for COPY ... FROM <expr>"),
+            }
+            let url = plan_copy_url_expr(scx, from, "COPY FROM source")?;
+            CopyFromSource::Url {
+                url,
+                connection: connection.to_owned(),
+                connection_id,
+            }
+        }
     };
 
     let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
     Ok(Plan::CopyFrom(CopyFromPlan {
         id,
+        source,
         columns,
         params,
     }))
@@ -1172,10 +1308,20 @@ pub fn plan_copy(
         },
         (CopyDirection::From, CopyTarget::Stdin) => match relation {
             CopyRelation::Named { name, columns } => {
-                plan_copy_from(scx, name, columns, format, options)
+                plan_copy_from(scx, name, columns, None, format, options)
             }
             _ => sql_bail!("COPY FROM {} not supported", target),
         },
+        (CopyDirection::From, CopyTarget::Expr(from_expr)) => {
+            scx.require_feature_flag(&vars::ENABLE_COPY_FROM_REMOTE)?;
+
+            match relation {
+                CopyRelation::Named { name, columns } => {
+                    plan_copy_from(scx, name, columns, Some(from_expr), format, options)
+                }
+                _ => sql_bail!("COPY {} {} not supported", direction, target),
+            }
+        }
         (CopyDirection::To, CopyTarget::Expr(to_expr)) => {
             scx.require_feature_flag(&vars::ENABLE_COPY_TO_EXPR)?;
 
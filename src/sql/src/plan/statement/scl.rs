@@ -159,7 +159,7 @@ pub fn plan_discard(
     match target {
         DiscardTarget::All => Ok(Plan::DiscardAll),
         DiscardTarget::Temp => Ok(Plan::DiscardTemp),
-        DiscardTarget::Sequences => bail_unsupported!("DISCARD SEQUENCES"),
+        DiscardTarget::Sequences => Ok(Plan::DiscardSequences),
         DiscardTarget::Plans => bail_unsupported!("DISCARD PLANS"),
     }
 }
@@ -578,6 +578,7 @@ pub fn show_columns<'a>(
         | ty @ CatalogItemType::Index
         | ty @ CatalogItemType::Func
         | ty @ CatalogItemType::Secret
+        | ty @ CatalogItemType::Sequence
         | ty @ CatalogItemType::Type
         | ty @ CatalogItemType::Sink => {
             sql_bail!("{full_name} is a {ty} and so does not have columns");
@@ -902,7 +903,10 @@ fn humanize_sql_for_show_create(
     id: GlobalId,
     sql: &str,
 ) -> Result<String, PlanError> {
-    use mz_sql_parser::ast::{CreateSourceConnection, MySqlConfigOptionName, PgConfigOptionName};
+    use mz_sql_parser::ast::{
+        CreateSourceConnection, FileSourceOptionName, MySqlConfigOptionName, PgConfigOptionName,
+        SqlServerConfigOptionName,
+    };
 
     let parsed = parse::parse(sql)?.into_element().ast;
     let (mut resolved, _) = names::resolve(catalog, parsed)?;
@@ -1014,8 +1018,18 @@ fn humanize_sql_for_show_create(
                         }
                     });
                 }
+                CreateSourceConnection::SqlServer { options, .. } => {
+                    // Drop details, which does not rountrip.
+                    options.retain(|o| o.name != SqlServerConfigOptionName::Details);
+                }
+                CreateSourceConnection::S3 { options, .. }
+                | CreateSourceConnection::LocalFiles { options } => {
+                    // Drop details, which does not rountrip.
+                    options.retain(|o| o.name != FileSourceOptionName::Details);
+                }
                 CreateSourceConnection::Kafka { .. }
-                | CreateSourceConnection::LoadGenerator { .. } => {}
+                | CreateSourceConnection::LoadGenerator { .. }
+                | CreateSourceConnection::Kinesis { .. } => {}
             }
 
             // If this source has any references, reconstruct them.
@@ -1025,6 +1039,8 @@ fn humanize_sql_for_show_create(
                     .map(|(reference, name)| CreateSourceSubsource {
                         reference,
                         subsource: Some(name),
+                        columns: vec![],
+                        filter: None,
                     })
                     .collect();
                 subsources.sort();
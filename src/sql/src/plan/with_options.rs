@@ -15,7 +15,7 @@ use mz_repr::adt::interval::Interval;
 use mz_repr::bytes::ByteSize;
 use mz_repr::{strconv, GlobalId};
 use mz_sql_parser::ast::{
-    ClusterScheduleOptionValue, ConnectionDefaultAwsPrivatelink, Ident, KafkaBroker,
+    ClusterScheduleOptionValue, ConnectionDefaultAwsPrivatelink, Expr, Ident, KafkaBroker,
     RefreshOptionValue, ReplicaDefinition,
 };
 use mz_storage_types::connections::StringOrSecret;
@@ -469,6 +469,7 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
             }
             WithOptionValue::RetainHistoryFor(v) => V::try_from_value(v),
             WithOptionValue::Sequence(_)
+            | WithOptionValue::Expr(_)
             | WithOptionValue::Item(_)
             | WithOptionValue::UnresolvedItemName(_)
             | WithOptionValue::Secret(_)
@@ -484,6 +485,7 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
                     WithOptionValue::Value(_) => unreachable!(),
                     WithOptionValue::RetainHistoryFor(_) => unreachable!(),
                     WithOptionValue::Sequence(_) => "sequences",
+                    WithOptionValue::Expr(_) => "expressions",
                     WithOptionValue::Item(_) => "object references",
                     WithOptionValue::UnresolvedItemName(_) => "object names",
                     WithOptionValue::Secret(_) => "secrets",
@@ -619,3 +621,52 @@ impl TryFromValue<WithOptionValue<Aug>> for ClusterScheduleOptionValue {
         "cluster schedule option value".to_string()
     }
 }
+
+impl TryFromValue<WithOptionValue<Aug>> for Expr<Aug> {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        if let WithOptionValue::Expr(e) = v {
+            Ok(e)
+        } else {
+            sql_bail!("cannot use value `{}` as an expression", v)
+        }
+    }
+
+    fn name() -> String {
+        "expression".to_string()
+    }
+}
+
+impl ImpliedValue for Expr<Aug> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an expression")
+    }
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for (UnresolvedItemName, Expr<Aug>) {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Sequence(values) => match <[_; 2]>::try_from(values) {
+                Ok([name, expr]) => Ok((
+                    UnresolvedItemName::try_from_value(name)?,
+                    Expr::<Aug>::try_from_value(expr)?,
+                )),
+                Err(values) => sql_bail!(
+                    "cannot use value `{}` for {}",
+                    WithOptionValue::Sequence(values),
+                    Self::name()
+                ),
+            },
+            _ => sql_bail!("cannot use value `{}` for {}", v, Self::name()),
+        }
+    }
+
+    fn name() -> String {
+        "item and expression pair".to_string()
+    }
+}
+
+impl ImpliedValue for (UnresolvedItemName, Expr<Aug>) {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an item and an expression")
+    }
+}
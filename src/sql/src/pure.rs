@@ -22,65 +22,78 @@ use itertools::Itertools;
 use mz_ccsr::{Client, GetByIdError, GetBySubjectError, Schema as CcsrSchema};
 use mz_controller_types::ClusterId;
 use mz_kafka_util::client::MzClientContext;
+use mz_ore::collections::CollectionExt;
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
 use mz_ore::iter::IteratorExt;
 use mz_ore::str::StrExt;
 use mz_postgres_util::replication::WalLevel;
 use mz_proto::RustType;
-use mz_repr::{strconv, Timestamp};
+use mz_repr::{strconv, RelationDesc, Timestamp};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit::{visit_function, Visit};
 use mz_sql_parser::ast::visit_mut::{visit_expr_mut, VisitMut};
 use mz_sql_parser::ast::{
     AlterSourceAction, AlterSourceAddSubsourceOptionName, AlterSourceStatement, AvroDocOn,
     CreateMaterializedViewStatement, CreateSinkConnection, CreateSinkStatement,
-    CreateSubsourceOption, CreateSubsourceOptionName, CsrConfigOption, CsrConfigOptionName,
-    CsrConnection, CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
-    KafkaSourceConfigOptionName, MaterializedViewOption, MaterializedViewOptionName,
-    MySqlConfigOption, MySqlConfigOptionName, PgConfigOption, PgConfigOptionName, RawItemName,
-    ReaderSchemaSelectionStrategy, RefreshAtOptionValue, RefreshEveryOptionValue,
-    RefreshOptionValue, SourceEnvelope, Statement, UnresolvedItemName,
+    CreateSourceOptionName, CreateSubsourceOption, CreateSubsourceOptionName, CsrConfigOption,
+    CsrConfigOptionName, CsrConnection, CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema,
+    DeferredItemName, DocOnIdentifier, DocOnSchema, Expr, Function, FunctionArgs, Ident,
+    KafkaSourceConfigOption, KafkaSourceConfigOptionName, MaterializedViewOption,
+    MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
+    PgConfigOptionName, RawItemName, ReaderSchemaSelectionStrategy, RefreshAtOptionValue,
+    RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope, SourceResetPosition,
+    SqlServerConfigOption, SqlServerConfigOptionName, Statement, UnresolvedItemName,
 };
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::connections::Connection;
 use mz_storage_types::errors::ContextCreationError;
-use mz_storage_types::sources::mysql::MySqlSourceDetails;
-use mz_storage_types::sources::postgres::PostgresSourcePublicationDetails;
-use mz_storage_types::sources::{GenericSourceConnection, SourceConnection};
+use mz_storage_types::sources::encoding::{dead_letter_desc, DEAD_LETTER_REFERENCE};
+use mz_storage_types::sources::mysql::{
+    MySqlBinlogDetails, MySqlFlavor, MySqlSourceConnection, MySqlSourceDetails,
+};
+use mz_storage_types::sources::postgres::{CastType, PostgresSourcePublicationDetails};
+use mz_storage_types::sources::sql_server::SqlServerSourceDetails;
+use mz_storage_types::sources::{GenericSourceConnection, SourceConnection, SourceDesc};
 use prost::Message;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
 use protobuf_native::MessageLite;
 use rdkafka::admin::AdminClient;
+use regex::Regex;
 use uuid::Uuid;
 
 use crate::ast::{
-    AvroSchema, CreateSourceConnection, CreateSourceFormat, CreateSourceStatement,
-    CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf,
-    Format, ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
+    AvroSchema, ColumnDef, CreateSourceConnection, CreateSourceStatement, CreateSourceSubsource,
+    CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf, Format, FormatSpecifier,
+    ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
 };
-use crate::catalog::{CatalogItemType, SessionCatalog, SubsourceCatalog};
+use crate::catalog::{CatalogItem, CatalogItemType, SessionCatalog, SubsourceCatalog};
 use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
 use crate::names::{
     Aug, FullItemName, PartialItemName, ResolvedColumnName, ResolvedDataType, ResolvedIds,
     ResolvedItemName,
 };
 use crate::plan::error::PlanError;
-use crate::plan::statement::ddl::load_generator_ast_to_generator;
+use crate::plan::statement::ddl::{
+    load_generator_ast_to_generator, plan_kinesis_source_connection,
+};
 use crate::plan::StatementContext;
 use crate::{kafka_util, normalize};
 
 use self::error::{
-    CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
+    CsrPurificationError, FileSourcePurificationError, KafkaSinkPurificationError,
+    KafkaSourcePurificationError, KinesisSourcePurificationError,
     LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    SqlServerSourcePurificationError,
 };
 use self::mysql::MYSQL_DATABASE_FAKE_NAME;
 
 pub(crate) mod error;
+mod file;
 mod mysql;
 mod postgres;
+mod sql_server;
 
 pub(crate) struct RequestedSubsource<'a, T> {
     upstream_name: UnresolvedItemName,
@@ -89,13 +102,22 @@ pub(crate) struct RequestedSubsource<'a, T> {
 }
 
 fn subsource_gen<'a, T>(
-    selected_subsources: &mut Vec<CreateSourceSubsource>,
+    selected_subsources: &mut Vec<CreateSourceSubsource<Aug>>,
     catalog: &SubsourceCatalog<&'a T>,
     source_name: &UnresolvedItemName,
 ) -> Result<Vec<RequestedSubsource<'a, T>>, PlanError> {
     let mut validated_requested_subsources = vec![];
 
     for subsource in selected_subsources {
+        // Only `CREATE SOURCE ... FROM POSTGRES` consumes these, before
+        // generating its subsources.
+        if !subsource.columns.is_empty() || subsource.filter.is_some() {
+            bail_unsupported!(format!(
+                "column lists and row filters for {} in this statement",
+                subsource.reference
+            ));
+        }
+
         let subsource_name = match &subsource.subsource {
             Some(name) => {
                 let partial = normalize::unresolved_item_name(name.clone())?;
@@ -143,6 +165,34 @@ fn subsource_name_gen(
     Ok(UnresolvedItemName::from(partial))
 }
 
+/// Generates an unused name for a subsource that accompanies the source named
+/// `source_name` by appending `suffix` to it, e.g. its progress subsource.
+fn generate_companion_subsource_name(
+    scx: &StatementContext,
+    source_name: &UnresolvedItemName,
+    suffix: &str,
+) -> Result<UnresolvedItemName, PlanError> {
+    let (item, prefix) = source_name.0.split_last().unwrap();
+    let item_name = Ident::try_generate_name(item.to_string(), suffix, |candidate| {
+        let mut suggested_name = prefix.to_vec();
+        suggested_name.push(candidate.clone());
+
+        let partial = normalize::unresolved_item_name(UnresolvedItemName(suggested_name))?;
+        let qualified = scx.allocate_qualified_name(partial)?;
+        let item_exists = scx.catalog.get_item_by_name(&qualified).is_some();
+        let type_exists = scx.catalog.get_type_by_name(&qualified).is_some();
+        Ok::<_, PlanError>(!item_exists && !type_exists)
+    })?;
+
+    let mut full_name = prefix.to_vec();
+    full_name.push(item_name);
+    let full_name = normalize::unresolved_item_name(UnresolvedItemName(full_name))?;
+    let qualified_name = scx.allocate_qualified_name(full_name)?;
+    let full_name = scx.catalog.resolve_full_name(&qualified_name);
+
+    Ok(UnresolvedItemName::from(full_name.clone()))
+}
+
 /// Validates the requested subsources do not have name conflicts with each other
 /// and that the same upstream table is not referenced multiple times.
 fn validate_subsource_names<T>(
@@ -223,6 +273,20 @@ pub enum PurifiedStatement {
         options: Vec<AlterSourceAddSubsourceOption<Aug>>,
         create_subsource_stmts: Vec<CreateSubsourceStatement<Aug>>,
     },
+    PurifiedAlterSourceRefreshSchema {
+        altered_id: mz_repr::GlobalId,
+        /// The hex-encoded details of the source, describing the refreshed
+        /// upstream tables.
+        details: String,
+        /// The new columns of each subsource whose upstream table changed.
+        subsources: BTreeMap<mz_repr::GlobalId, Vec<ColumnDef<Aug>>>,
+    },
+    PurifiedAlterSourceResetTo {
+        altered_id: mz_repr::GlobalId,
+        /// The offset of each partition of the topic at which the source
+        /// should resume reading.
+        start_offsets: Vec<i64>,
+    },
     PurifiedCreateSink(CreateSinkStatement<Aug>),
 }
 
@@ -250,7 +314,7 @@ pub async fn purify_statement(
             )
         }
         Statement::AlterSource(stmt) => (
-            purify_alter_source(catalog, stmt, storage_configuration).await,
+            purify_alter_source(catalog, now, stmt, storage_configuration).await,
             None,
         ),
         Statement::CreateSink(stmt) => {
@@ -272,21 +336,37 @@ pub(crate) fn add_materialize_comments(
     // updating avro format with comments so that they are frozen in the `create_sql`
     let from_id = stmt.from.item_id();
     let from = catalog.get_item(from_id);
-    let object_ids = from.references().0.clone().into_iter().chain_one(from.id());
+    let object_ids: Vec<_> = from
+        .references()
+        .0
+        .clone()
+        .into_iter()
+        .chain_one(from.id())
+        .collect();
+
+    let formats = match &mut stmt.format {
+        Some(FormatSpecifier::Bare(format)) => vec![format],
+        Some(FormatSpecifier::KeyValue { key, value }) => vec![key, value],
+        None => vec![],
+    };
 
     // add comments to the avro doc comments
-    if let Some(Format::Avro(AvroSchema::Csr {
-        csr_connection:
-            CsrConnectionAvro {
-                connection:
-                    CsrConnection {
-                        connection: _,
-                        options,
-                    },
-                ..
-            },
-    })) = &mut stmt.format
-    {
+    for format in formats {
+        let Format::Avro(AvroSchema::Csr {
+            csr_connection:
+                CsrConnectionAvro {
+                    connection:
+                        CsrConnection {
+                            connection: _,
+                            options,
+                        },
+                    ..
+                },
+        }) = format
+        else {
+            continue;
+        };
+
         let user_provided_comments = &options
             .iter()
             .filter_map(|CsrConfigOption { name, .. }| match name {
@@ -296,7 +376,8 @@ pub(crate) fn add_materialize_comments(
             .collect::<BTreeSet<_>>();
 
         // Adding existing comments if not already provided by user
-        for object_id in object_ids {
+        for object_id in &object_ids {
+            let object_id = *object_id;
             let item = catalog.get_item(&object_id);
             let full_name = catalog.resolve_full_name(item.name());
             let full_resolved_name = ResolvedItemName::Item {
@@ -436,14 +517,23 @@ async fn purify_create_sink(
         }
     }
 
-    if let Some(format) = format {
+    let formats = match format {
+        Some(FormatSpecifier::Bare(format)) => vec![format],
+        Some(FormatSpecifier::KeyValue { key, value }) => vec![key, value],
+        None => vec![],
+    };
+    for format in formats {
         match format {
             Format::Avro(AvroSchema::Csr {
                 csr_connection: CsrConnectionAvro { connection, .. },
             })
             | Format::Protobuf(ProtobufSchema::Csr {
                 csr_connection: CsrConnectionProtobuf { connection, .. },
-            }) => {
+            })
+            | Format::Json {
+                csr_connection: Some(connection),
+                ..
+            } => {
                 let connection = {
                     let scx = StatementContext::new(None, &catalog);
                     let item = scx.get_item_by_resolved_name(&connection.connection)?;
@@ -471,10 +561,14 @@ async fn purify_create_sink(
             Format::Avro(AvroSchema::InlineSchema { .. })
             | Format::Bytes
             | Format::Csv { .. }
-            | Format::Json { .. }
+            | Format::Json {
+                csr_connection: None,
+                ..
+            }
             | Format::Protobuf(ProtobufSchema::InlineSchema { .. })
             | Format::Regex(..)
-            | Format::Text => {}
+            | Format::Text
+            | Format::Parquet => {}
         }
     }
 
@@ -495,6 +589,7 @@ async fn purify_create_source(
         include_metadata,
         referenced_subsources,
         progress_subsource,
+        with_options,
         ..
     } = &mut create_source_stmt;
 
@@ -505,6 +600,14 @@ async fn purify_create_source(
     let mut create_subsource_stmts = vec![];
 
     let progress_desc = match &connection {
+        // Sources with topic patterns track the progress of each topic.
+        CreateSourceConnection::Kafka { options, .. }
+            if options
+                .iter()
+                .any(|o| o.name == KafkaSourceConfigOptionName::TopicPattern) =>
+        {
+            &mz_storage_types::sources::kafka::KAFKA_TOPIC_PATTERN_PROGRESS_DESC
+        }
         CreateSourceConnection::Kafka { .. } => {
             &mz_storage_types::sources::kafka::KAFKA_PROGRESS_DESC
         }
@@ -514,9 +617,18 @@ async fn purify_create_source(
         CreateSourceConnection::MySql { .. } => {
             &mz_storage_types::sources::mysql::MYSQL_PROGRESS_DESC
         }
+        CreateSourceConnection::SqlServer { .. } => {
+            &mz_storage_types::sources::sql_server::SQL_SERVER_PROGRESS_DESC
+        }
         CreateSourceConnection::LoadGenerator { .. } => {
             &mz_storage_types::sources::load_generator::LOAD_GEN_PROGRESS_DESC
         }
+        CreateSourceConnection::S3 { .. } | CreateSourceConnection::LocalFiles { .. } => {
+            &mz_storage_types::sources::file::FILE_PROGRESS_DESC
+        }
+        CreateSourceConnection::Kinesis { .. } => {
+            &mz_storage_types::sources::kinesis::KINESIS_PROGRESS_DESC
+        }
     };
 
     match connection {
@@ -548,9 +660,29 @@ async fn purify_create_source(
             let extracted_options: KafkaSourceConfigOptionExtracted =
                 base_with_options.clone().try_into()?;
 
-            let topic = extracted_options
-                .topic
-                .ok_or(KafkaSourcePurificationError::ConnectionMissingTopic)?;
+            // The epoch is only ever advanced by `ALTER SOURCE...RESET TO`.
+            if extracted_options
+                .seen
+                .contains(&KafkaSourceConfigOptionName::StartEpoch)
+            {
+                Err(KafkaSourcePurificationError::UserSpecifiedStartEpoch)?;
+            }
+
+            let (topic, topic_is_pattern) =
+                match (extracted_options.topic, extracted_options.topic_pattern) {
+                    (Some(topic), None) => (topic, false),
+                    (None, Some(topic_pattern)) => {
+                        if let Err(e) = Regex::new(&topic_pattern) {
+                            Err(KafkaSourcePurificationError::InvalidTopicPattern(
+                                topic_pattern,
+                                e.to_string(),
+                            ))?
+                        }
+                        (topic_pattern, true)
+                    }
+                    (Some(_), Some(_)) => Err(KafkaSourcePurificationError::TopicAndTopicPattern)?,
+                    (None, None) => Err(KafkaSourcePurificationError::ConnectionMissingTopic)?,
+                };
 
             let consumer = connection
                 .create_with_context(
@@ -572,6 +704,10 @@ async fn purify_create_source(
                 extracted_options.start_offset,
                 extracted_options.start_timestamp,
             ) {
+                (None, None) if topic_is_pattern => {
+                    // The topics that match the pattern are discovered while the source runs, so
+                    // none of them need to exist yet.
+                }
                 (None, None) => {
                     // Validate that the topic at least exists.
                     kafka_util::ensure_topic_exists(
@@ -587,6 +723,12 @@ async fn purify_create_source(
                 (Some(_), Some(_)) => {
                     sql_bail!("cannot specify START TIMESTAMP and START OFFSET at same time")
                 }
+                (Some(_), None) if topic_is_pattern => Err(
+                    KafkaSourcePurificationError::UnsupportedWithTopicPattern("START OFFSET"),
+                )?,
+                (None, Some(_)) if topic_is_pattern => Err(
+                    KafkaSourcePurificationError::UnsupportedWithTopicPattern("START TIMESTAMP"),
+                )?,
                 (Some(start_offsets), None) => {
                     // Validate the start offsets.
                     kafka_util::validate_start_offsets(
@@ -650,6 +792,10 @@ async fn purify_create_source(
             let crate::plan::statement::PgConfigOptionExtracted {
                 publication,
                 mut text_columns,
+                retract_on_truncate,
+                upsert_tables,
+                mut include_columns,
+                mut row_filters,
                 details,
                 ..
             } = options.clone().try_into()?;
@@ -782,6 +928,20 @@ async fn purify_create_source(
                     }
                 }
                 ReferencedSubsources::SubsetTables(subsources) => {
+                    // Column lists and row filters are folded into the `INCLUDE
+                    // COLUMNS` and `ROW FILTERS` options, which persist them
+                    // once the references are removed from the statement.
+                    for subsource in subsources.iter_mut() {
+                        for column in subsource.columns.drain(..) {
+                            let mut name = subsource.reference.clone();
+                            name.0.push(column);
+                            include_columns.push(name);
+                        }
+                        if let Some(filter) = subsource.filter.take() {
+                            row_filters.push((subsource.reference.clone(), filter));
+                        }
+                    }
+
                     // The user manually selected a subset of upstream tables so we need to
                     // validate that the names actually exist and are not ambiguous
                     validated_requested_subsources.extend(subsource_gen(
@@ -801,14 +961,122 @@ async fn purify_create_source(
 
             validate_subsource_names(&validated_requested_subsources)?;
 
+            let retract_on_truncate = postgres::normalize_retract_on_truncate(
+                &publication_catalog,
+                &validated_requested_subsources,
+                retract_on_truncate,
+                &PgConfigOptionName::RetractOnTruncate.to_ast_string(),
+            )?;
+            let upsert_tables = postgres::normalize_upsert_tables(
+                &publication_catalog,
+                &validated_requested_subsources,
+                upsert_tables,
+                &PgConfigOptionName::UpsertTables.to_ast_string(),
+            )?;
+
+            // Tables ingested by primary key don't know the contents they
+            // would have to retract on truncation.
+            let overlapping: Vec<_> = retract_on_truncate
+                .iter()
+                .filter(|name| upsert_tables.contains(name))
+                .cloned()
+                .collect();
+            if !overlapping.is_empty() {
+                Err(PgSourcePurificationError::UpsertTablesRetractOnTruncate {
+                    items: overlapping,
+                })?;
+            }
+
+            let include_cols_dict = postgres::generate_include_columns(
+                &publication_catalog,
+                &validated_requested_subsources,
+                &mut include_columns,
+                &PgConfigOptionName::IncludeColumns.to_ast_string(),
+            )?;
+            let row_filters = postgres::normalize_row_filters(
+                &publication_catalog,
+                &validated_requested_subsources,
+                row_filters,
+                &PgConfigOptionName::RowFilters.to_ast_string(),
+            )?;
+
+            // Tables ingested by primary key must retain all of their rows and
+            // key columns.
+            let filtered: Vec<_> = validated_requested_subsources
+                .iter()
+                .filter(|subsource| {
+                    upsert_tables.contains(&subsource.upstream_name)
+                        && (include_cols_dict.contains_key(&subsource.table.oid)
+                            || row_filters
+                                .iter()
+                                .any(|(table, _)| table == &subsource.upstream_name))
+                })
+                .map(|subsource| subsource.upstream_name.clone())
+                .collect();
+            if !filtered.is_empty() {
+                Err(PgSourcePurificationError::UpsertTablesWithFilters { items: filtered })?;
+            }
+
+            // Normalize options to contain fully qualified values, adding them
+            // if they were specified in the `FOR TABLES` clause.
+            options.retain(|option| {
+                !matches!(
+                    option.name,
+                    PgConfigOptionName::IncludeColumns | PgConfigOptionName::RowFilters
+                )
+            });
+            if !include_columns.is_empty() {
+                options.push(PgConfigOption {
+                    name: PgConfigOptionName::IncludeColumns,
+                    value: Some(WithOptionValue::Sequence(
+                        include_columns
+                            .into_iter()
+                            .map(WithOptionValue::UnresolvedItemName)
+                            .collect(),
+                    )),
+                });
+            }
+            if !row_filters.is_empty() {
+                options.push(PgConfigOption {
+                    name: PgConfigOptionName::RowFilters,
+                    value: Some(WithOptionValue::Sequence(
+                        row_filters
+                            .into_iter()
+                            .map(|(table, filter)| {
+                                WithOptionValue::Sequence(vec![
+                                    WithOptionValue::UnresolvedItemName(table),
+                                    WithOptionValue::Expr(filter),
+                                ])
+                            })
+                            .collect(),
+                    )),
+                });
+            }
+
+            for (option_name, tables) in [
+                (PgConfigOptionName::RetractOnTruncate, &retract_on_truncate),
+                (PgConfigOptionName::UpsertTables, &upsert_tables),
+            ] {
+                if let Some(option) = options.iter_mut().find(|option| option.name == option_name) {
+                    option.value = Some(WithOptionValue::Sequence(
+                        tables
+                            .iter()
+                            .cloned()
+                            .map(WithOptionValue::UnresolvedItemName)
+                            .collect(),
+                    ));
+                }
+            }
+
             postgres::validate_requested_subsources_privileges(
                 &config,
                 &validated_requested_subsources,
+                &upsert_tables,
                 &storage_configuration.connection_context.ssh_tunnel_manager,
             )
             .await?;
 
-            let text_cols_dict = postgres::generate_text_columns(
+            let text_cols_dict = postgres::generate_column_refs(
                 &publication_catalog,
                 &mut text_columns,
                 &PgConfigOptionName::TextColumns.to_ast_string(),
@@ -834,6 +1102,7 @@ async fn purify_create_source(
                 None,
                 validated_requested_subsources,
                 text_cols_dict,
+                &include_cols_dict,
                 &publication_tables,
             )?;
 
@@ -886,6 +1155,7 @@ async fn purify_create_source(
                 details,
                 text_columns,
                 ignore_columns,
+                retract_on_truncate,
                 seen: _,
             } = options.clone().try_into()?;
 
@@ -908,18 +1178,42 @@ async fn purify_create_source(
                 )
                 .await?;
 
-            // Check if the MySQL database is configured to allow row-based consistent GTID replication
+            // MariaDB servers always assign GTIDs, in a format of their own. MySQL servers
+            // without GTIDs are replicated from by binlog position instead.
+            let flavor = if mz_mysql_util::is_mariadb(&mut conn).await? {
+                MySqlFlavor::MariaDb
+            } else {
+                MySqlFlavor::MySql
+            };
+            let gtids_enabled = match flavor {
+                MySqlFlavor::MariaDb => true,
+                MySqlFlavor::MySql => mz_mysql_util::gtids_enabled(&mut conn).await?,
+            };
+
+            // Check if the MySQL database is configured to allow row-based consistent replication
             let mut replication_errors = vec![];
             for error in [
-                mz_mysql_util::ensure_gtid_consistency(&mut conn)
-                    .await
-                    .err(),
+                match (flavor, gtids_enabled) {
+                    (MySqlFlavor::MariaDb, _) => {
+                        mz_mysql_util::ensure_mariadb_gtid_consistency(&mut conn)
+                            .await
+                            .err()
+                    }
+                    (MySqlFlavor::MySql, true) => mz_mysql_util::ensure_gtid_consistency(&mut conn)
+                        .await
+                        .err(),
+                    (MySqlFlavor::MySql, false) => None,
+                },
                 mz_mysql_util::ensure_full_row_binlog_format(&mut conn)
                     .await
                     .err(),
-                mz_mysql_util::ensure_replication_commit_order(&mut conn)
-                    .await
-                    .err(),
+                // MariaDB replicas always commit transactions in the order of their primary.
+                match flavor {
+                    MySqlFlavor::MySql => mz_mysql_util::ensure_replication_commit_order(&mut conn)
+                        .await
+                        .err(),
+                    MySqlFlavor::MariaDb => None,
+                },
             ] {
                 match error {
                     Some(mz_mysql_util::MySqlError::InvalidSystemSetting {
@@ -985,24 +1279,7 @@ async fn purify_create_source(
                 &ignore_cols_map,
             )
             .await
-            .map_err(|err| match err {
-                mz_mysql_util::MySqlError::UnsupportedDataTypes { columns } => {
-                    PlanError::from(MySqlSourcePurificationError::UnrecognizedTypes {
-                        cols: columns
-                            .into_iter()
-                            .map(|c| (c.qualified_table_name, c.column_name, c.column_type))
-                            .collect(),
-                    })
-                }
-                mz_mysql_util::MySqlError::DuplicatedColumnNames {
-                    qualified_table_name,
-                    columns,
-                } => PlanError::from(MySqlSourcePurificationError::DuplicatedColumnNames(
-                    qualified_table_name,
-                    columns,
-                )),
-                _ => err.into(),
-            })?;
+            .map_err(mysql::schema_info_error)?;
 
             if tables.is_empty() {
                 Err(MySqlSourcePurificationError::EmptyDatabase)?;
@@ -1110,6 +1387,24 @@ async fn purify_create_source(
             )
             .await?;
 
+            // Normalize the RETRACT ON TRUNCATE option to contain fully qualified values.
+            let retract_on_truncate = mysql::normalize_retract_on_truncate(
+                &mysql_catalog,
+                &validated_requested_subsources,
+                retract_on_truncate,
+            )?;
+            if let Some(option) = options
+                .iter_mut()
+                .find(|option| option.name == MySqlConfigOptionName::RetractOnTruncate)
+            {
+                option.value = Some(WithOptionValue::Sequence(
+                    retract_on_truncate
+                        .into_iter()
+                        .map(WithOptionValue::UnresolvedItemName)
+                        .collect(),
+                ));
+            }
+
             let new_subsources =
                 mysql::generate_targeted_subsources(&scx, validated_requested_subsources)?;
 
@@ -1118,11 +1413,39 @@ async fn purify_create_source(
             // part of the `CREATE SOURCE` statement in the catalog.
             *referenced_subsources = None;
 
-            // Retrieve the current @gtid_executed value of the server to mark as the effective
-            // initial snapshot point such that we can ensure consistency if the initial source
-            // snapshot is broken up over multiple points in time.
-            let initial_gtid_set =
-                mz_mysql_util::query_sys_var(&mut conn, "global.gtid_executed").await?;
+            // Retrieve the current @gtid_executed value of the server (@gtid_binlog_state for
+            // MariaDB), or its current binlog position if it doesn't use GTIDs, to mark as the
+            // effective initial snapshot point such that we can ensure consistency if the initial
+            // source snapshot is broken up over multiple points in time.
+            let (initial_gtid_set, binlog) = if gtids_enabled {
+                let initial_gtid_set =
+                    mz_mysql_util::query_sys_var(&mut conn, flavor.gtid_set_variable()).await?;
+                // Fail early if we cannot parse the GTID set of the server.
+                flavor.gtid_set_frontier(&initial_gtid_set).map_err(|e| {
+                    mz_mysql_util::MySqlError::Generic(anyhow!("unsupported gtid set: {e}"))
+                })?;
+                (initial_gtid_set, None)
+            } else {
+                let server_uuid =
+                    mz_mysql_util::query_sys_var(&mut conn, "global.server_uuid").await?;
+                let server_uuid = Uuid::parse_str(&server_uuid).map_err(|e| {
+                    mz_mysql_util::MySqlError::Generic(anyhow!(
+                        "invalid server_uuid {server_uuid}: {e}"
+                    ))
+                })?;
+                let (initial_file, initial_position) =
+                    mz_mysql_util::query_binlog_position(&mut conn).await?;
+                let binlog = MySqlBinlogDetails {
+                    server_uuid,
+                    initial_file,
+                    initial_position,
+                };
+                // Fail early if we cannot represent the binlog position as a timestamp.
+                binlog.initial_frontier().map_err(|e| {
+                    mz_mysql_util::MySqlError::Generic(anyhow!("unsupported binlog position: {e}"))
+                })?;
+                (String::new(), Some(binlog))
+            };
 
             // Remove any old detail references
             options
@@ -1130,6 +1453,8 @@ async fn purify_create_source(
             let details = MySqlSourceDetails {
                 tables,
                 initial_gtid_set,
+                binlog,
+                flavor,
             };
 
             create_subsource_stmts.extend(new_subsources);
@@ -1141,6 +1466,255 @@ async fn purify_create_source(
                 )))),
             })
         }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &catalog);
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::SqlServer(connection) => {
+                    connection.clone().into_inline_connection(&catalog)
+                }
+                _ => Err(SqlServerSourcePurificationError::NotSqlServerConnection(
+                    scx.catalog.resolve_full_name(connection_item.name()),
+                ))?,
+            };
+            let crate::plan::statement::ddl::SqlServerConfigOptionExtracted { details, seen: _ } =
+                options.clone().try_into()?;
+
+            if details.is_some() {
+                Err(SqlServerSourcePurificationError::UserSpecifiedDetails)?;
+            }
+
+            let config = connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+
+            let mut conn = config
+                .connect(
+                    "sql server purification",
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+
+            // Check if the database is configured for change data capture and
+            // allows the snapshot isolation we use to snapshot tables.
+            let mut settings_errors = vec![];
+            for error in [
+                mz_sql_server_util::ensure_cdc_enabled(&mut conn)
+                    .await
+                    .err(),
+                mz_sql_server_util::ensure_snapshot_isolation_enabled(&mut conn)
+                    .await
+                    .err(),
+            ] {
+                match error {
+                    Some(mz_sql_server_util::SqlServerError::InvalidSystemSetting {
+                        setting,
+                        expected,
+                        actual,
+                    }) => {
+                        settings_errors.push((setting, expected, actual));
+                    }
+                    Some(err) => Err(err)?,
+                    None => (),
+                }
+            }
+            if !settings_errors.is_empty() {
+                Err(SqlServerSourcePurificationError::SystemSettingsError(
+                    settings_errors,
+                ))?;
+            }
+
+            // Tables may be referenced with any level of qualification, so
+            // unless the request is restricted to specific schemas we need all
+            // CDC-enabled tables to resolve the references.
+            let table_schema_request = match referenced_subsources
+                .as_ref()
+                .ok_or(SqlServerSourcePurificationError::RequiresReferencedSubsources)?
+            {
+                ReferencedSubsources::SubsetSchemas(schemas) => {
+                    mz_sql_server_util::SchemaRequest::Schemas(
+                        schemas.iter().map(|s| s.as_str()).collect(),
+                    )
+                }
+                ReferencedSubsources::All | ReferencedSubsources::SubsetTables(_) => {
+                    mz_sql_server_util::SchemaRequest::All
+                }
+            };
+
+            let tables = mz_sql_server_util::schema_info(&mut conn, &table_schema_request)
+                .await
+                .map_err(|err| match err {
+                    mz_sql_server_util::SqlServerError::UnsupportedDataTypes { columns } => {
+                        PlanError::from(SqlServerSourcePurificationError::UnrecognizedTypes {
+                            cols: columns
+                                .into_iter()
+                                .map(|c| (c.qualified_table_name, c.column_name, c.column_type))
+                                .collect(),
+                        })
+                    }
+                    _ => err.into(),
+                })?;
+
+            if tables.is_empty() {
+                Err(SqlServerSourcePurificationError::EmptyDatabase)?;
+            }
+
+            let sql_server_catalog =
+                sql_server::derive_catalog_from_tables(&connection.database, &tables)?;
+
+            let mut validated_requested_subsources = vec![];
+            match referenced_subsources
+                .as_mut()
+                .ok_or(SqlServerSourcePurificationError::RequiresReferencedSubsources)?
+            {
+                ReferencedSubsources::All => {
+                    for table in &tables {
+                        let upstream_name =
+                            sql_server::sql_server_upstream_name(&connection.database, table)?;
+                        let subsource_name = subsource_name_gen(source_name, &table.name)?;
+                        validated_requested_subsources.push(RequestedSubsource {
+                            upstream_name,
+                            subsource_name,
+                            table,
+                        });
+                    }
+                }
+                ReferencedSubsources::SubsetSchemas(schemas) => {
+                    let available_schemas: BTreeSet<_> =
+                        tables.iter().map(|t| t.schema_name.as_str()).collect();
+                    let requested_schemas: BTreeSet<_> =
+                        schemas.iter().map(|s| s.as_str()).collect();
+                    let missing_schemas: Vec<_> = requested_schemas
+                        .difference(&available_schemas)
+                        .map(|s| s.to_string())
+                        .collect();
+                    if !missing_schemas.is_empty() {
+                        Err(SqlServerSourcePurificationError::NoTablesFoundForSchemas(
+                            missing_schemas,
+                        ))?;
+                    }
+
+                    for table in &tables {
+                        let upstream_name =
+                            sql_server::sql_server_upstream_name(&connection.database, table)?;
+                        let subsource_name = subsource_name_gen(source_name, &table.name)?;
+                        validated_requested_subsources.push(RequestedSubsource {
+                            upstream_name,
+                            subsource_name,
+                            table,
+                        });
+                    }
+                }
+                ReferencedSubsources::SubsetTables(subsources) => {
+                    // The user manually selected a subset of upstream tables so we need to
+                    // validate that the names actually exist and are not ambiguous
+                    validated_requested_subsources =
+                        subsource_gen(subsources, &sql_server_catalog, source_name)?;
+                }
+            }
+
+            if validated_requested_subsources.is_empty() {
+                sql_bail!(
+                    "[internal error]: SQL Server source must ingest at least one table, but {} matched none",
+                    referenced_subsources.as_ref().unwrap().to_ast_string()
+                );
+            }
+
+            validate_subsource_names(&validated_requested_subsources)?;
+
+            let new_subsources =
+                sql_server::generate_targeted_subsources(&scx, validated_requested_subsources)?;
+
+            // Now that we know which subsources to create alongside this
+            // statement, remove the references so it is not canonicalized as
+            // part of the `CREATE SOURCE` statement in the catalog.
+            *referenced_subsources = None;
+
+            // Retrieve the end of the transaction log to mark as the effective
+            // initial snapshot point such that we can ensure consistency if the
+            // initial source snapshot is broken up over multiple points in
+            // time.
+            let initial_lsn = mz_sql_server_util::get_log_end_lsn(&mut conn).await?;
+
+            // Remove any old detail references
+            options.retain(|SqlServerConfigOption { name, .. }| {
+                name != &SqlServerConfigOptionName::Details
+            });
+            let details = SqlServerSourceDetails {
+                database: connection.database.clone(),
+                tables,
+                initial_lsn,
+            };
+
+            create_subsource_stmts.extend(new_subsources);
+
+            options.push(SqlServerConfigOption {
+                name: SqlServerConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
+        CreateSourceConnection::S3 {
+            connection,
+            options,
+        } => {
+            if let Some(referenced_subsources) = referenced_subsources {
+                Err(FileSourcePurificationError::ReferencedSubsources(
+                    referenced_subsources.clone(),
+                ))?;
+            }
+
+            let scx = StatementContext::new(None, &catalog);
+            file::purify_file_source(
+                &scx,
+                Some(&*connection),
+                options,
+                format,
+                storage_configuration,
+            )
+            .await?;
+        }
+        CreateSourceConnection::LocalFiles { options } => {
+            if let Some(referenced_subsources) = referenced_subsources {
+                Err(FileSourcePurificationError::ReferencedSubsources(
+                    referenced_subsources.clone(),
+                ))?;
+            }
+
+            let scx = StatementContext::new(None, &catalog);
+            file::purify_file_source(&scx, None, options, format, storage_configuration).await?;
+        }
+        CreateSourceConnection::Kinesis {
+            connection,
+            options,
+        } => {
+            if let Some(referenced_subsources) = referenced_subsources {
+                Err(KinesisSourcePurificationError::ReferencedSubsources(
+                    referenced_subsources.clone(),
+                ))?;
+            }
+
+            let scx = StatementContext::new(None, &catalog);
+            let connection = plan_kinesis_source_connection(&scx, connection, options)?
+                .into_inline_connection(scx.catalog);
+            connection
+                .validate(&storage_configuration.connection_context, InTask::No)
+                .await
+                .map_err(|e| {
+                    KinesisSourcePurificationError::StreamError(
+                        connection.stream_name.clone(),
+                        e.display_with_causes().to_string(),
+                    )
+                })?;
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let scx = StatementContext::new(None, &catalog);
 
@@ -1206,38 +1780,48 @@ async fn purify_create_source(
         }
     }
 
-    // Generate progress subsource
-
     // Create the targeted AST node for the original CREATE SOURCE statement
     let scx = StatementContext::new(None, &catalog);
 
+    // Generate dead-letter subsource, which receives the records that fail to
+    // decode instead of the source's primary output.
+    let dead_letter = with_options.iter().any(|option| {
+        option.name == CreateSourceOptionName::OnDecodeError
+            && matches!(
+                &option.value,
+                Some(WithOptionValue::Value(Value::String(policy)))
+                    if policy.eq_ignore_ascii_case("dead_letter")
+            )
+    });
+    if dead_letter {
+        let (columns, constraints) = scx
+            .relation_desc_into_table_defs(&dead_letter_desc(RelationDesc::clone(progress_desc)))?;
+        create_subsource_stmts.push(CreateSubsourceStatement {
+            name: generate_companion_subsource_name(&scx, source_name, "_dead_letter")?,
+            columns,
+            // We don't know the primary source's `GlobalId` yet; fill it in
+            // once we generate it.
+            of_source: None,
+            constraints,
+            if_not_exists: false,
+            with_options: vec![CreateSubsourceOption {
+                name: CreateSubsourceOptionName::ExternalReference,
+                value: Some(WithOptionValue::UnresolvedItemName(
+                    UnresolvedItemName::unqualified(Ident::new_unchecked(DEAD_LETTER_REFERENCE)),
+                )),
+            }],
+        });
+    }
+
+    // Generate progress subsource
+
     // Take name from input or generate name
     let name = match progress_subsource {
         Some(name) => match name {
             DeferredItemName::Deferred(name) => name.clone(),
             DeferredItemName::Named(_) => unreachable!("already checked for this value"),
         },
-        None => {
-            let (item, prefix) = source_name.0.split_last().unwrap();
-            let item_name = Ident::try_generate_name(item.to_string(), "_progress", |candidate| {
-                let mut suggested_name = prefix.to_vec();
-                suggested_name.push(candidate.clone());
-
-                let partial = normalize::unresolved_item_name(UnresolvedItemName(suggested_name))?;
-                let qualified = scx.allocate_qualified_name(partial)?;
-                let item_exists = scx.catalog.get_item_by_name(&qualified).is_some();
-                let type_exists = scx.catalog.get_type_by_name(&qualified).is_some();
-                Ok::<_, PlanError>(!item_exists && !type_exists)
-            })?;
-
-            let mut full_name = prefix.to_vec();
-            full_name.push(item_name);
-            let full_name = normalize::unresolved_item_name(UnresolvedItemName(full_name))?;
-            let qualified_name = scx.allocate_qualified_name(full_name)?;
-            let full_name = scx.catalog.resolve_full_name(&qualified_name);
-
-            UnresolvedItemName::from(full_name.clone())
-        }
+        None => generate_companion_subsource_name(&scx, source_name, "_progress")?,
     };
 
     let (columns, constraints) = scx.relation_desc_into_table_defs(progress_desc)?;
@@ -1280,6 +1864,7 @@ async fn purify_create_source(
 /// handling `ALTER SOURCE` statements.
 async fn purify_alter_source(
     catalog: impl SessionCatalog,
+    now: u64,
     stmt: AlterSourceStatement<Aug>,
     storage_configuration: &StorageConfiguration,
 ) -> Result<PurifiedStatement, PlanError> {
@@ -1315,6 +1900,23 @@ async fn purify_alter_source(
         }
     };
 
+    if let AlterSourceAction::RefreshSchema = action {
+        return purify_alter_source_refresh_schema(&scx, item, desc, storage_configuration).await;
+    }
+
+    if let AlterSourceAction::ResetTo { position, value } = action {
+        return purify_alter_source_reset_to(
+            &scx,
+            item,
+            desc,
+            now,
+            position,
+            value,
+            storage_configuration,
+        )
+        .await;
+    }
+
     let name = item.name();
     let full_name = scx.catalog.resolve_full_name(name);
     let resolved_source_name = ResolvedItemName::Item {
@@ -1407,11 +2009,12 @@ async fn purify_alter_source(
     postgres::validate_requested_subsources_privileges(
         &config,
         &validated_requested_subsources,
+        &[],
         &storage_configuration.connection_context.ssh_tunnel_manager,
     )
     .await?;
 
-    let text_cols_dict = postgres::generate_text_columns(
+    let text_cols_dict = postgres::generate_column_refs(
         &publication_catalog,
         // In addition to using the `text_columns` values here, we also fully
         // normalize the objects to which they refer.
@@ -1440,6 +2043,7 @@ async fn purify_alter_source(
         Some(resolved_source_name),
         validated_requested_subsources,
         text_cols_dict,
+        &BTreeMap::new(),
         &new_publication_tables,
     )?;
 
@@ -1488,14 +2092,376 @@ async fn purify_alter_source(
     })
 }
 
+/// Equivalent to `purify_alter_source` but for `ALTER SOURCE...REFRESH SCHEMA`.
+///
+/// Compares the upstream tables of the source's subsources to their current
+/// definitions, and produces the source's details and the columns of each
+/// subsource that reflect the additive changes made to them. Any other change
+/// is an error.
+async fn purify_alter_source_refresh_schema(
+    scx: &StatementContext<'_>,
+    item: &dyn CatalogItem,
+    desc: SourceDesc,
+    storage_configuration: &StorageConfiguration,
+) -> Result<PurifiedStatement, PlanError> {
+    // The subsources of the source, along with the upstream table they refer to.
+    let references: Vec<_> = item
+        .used_by()
+        .iter()
+        .filter_map(|id| {
+            let subsource = scx.catalog.get_item(id);
+            subsource
+                .subsource_details()
+                .map(|(_, reference)| (subsource, reference.clone()))
+        })
+        .collect();
+
+    let subsource_name = |subsource: &dyn CatalogItem| {
+        UnresolvedItemName::from(scx.catalog.resolve_full_name(subsource.name()))
+    };
+
+    let mut subsources = BTreeMap::new();
+    let mut incompatible_tables = vec![];
+
+    let details = match desc.connection {
+        GenericSourceConnection::Postgres(pg_source_connection) => {
+            let config = pg_source_connection
+                .connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+
+            let publication_tables = mz_postgres_util::publication_info(
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+                &config,
+                &pg_source_connection.publication,
+            )
+            .await?;
+
+            let mut details = pg_source_connection.publication_details;
+            for (subsource, reference) in references {
+                let Some(output_index) = details.output_idx_for_name(&reference) else {
+                    sql_bail!("internal error: {} missing from source details", reference);
+                };
+                let cur_table = &details.tables[output_index - 1];
+
+                let casts = &pg_source_connection.table_casts[&output_index];
+
+                // Columns ingested as text may change their type arbitrarily.
+                let text_columns: Vec<_> = cur_table
+                    .columns
+                    .iter()
+                    .zip(casts.iter())
+                    .filter(|(_, (cast_type, _))| *cast_type == CastType::Text)
+                    .map(|(column, _)| column)
+                    .collect();
+                let text_col_nums = text_columns.iter().map(|c| c.col_num).collect();
+
+                let Some(new_table) = publication_tables.iter().find(|t| t.oid == cur_table.oid)
+                else {
+                    incompatible_tables.push((
+                        reference.to_ast_string(),
+                        "table was dropped or removed from the publication".to_string(),
+                    ));
+                    continue;
+                };
+                if let Err(err) = cur_table.determine_compatibility(new_table, &text_col_nums) {
+                    incompatible_tables.push((reference.to_ast_string(), err.to_string()));
+                    continue;
+                }
+                if cur_table.additive_changes(new_table).is_empty() {
+                    continue;
+                }
+
+                // Keys are not refreshed, as they describe the data we already
+                // ingested, and rows that predate the new columns contain
+                // `NULL` in them.
+                let mut table = new_table.clone();
+                table.keys = cur_table.keys.clone();
+                for column in table.columns.iter_mut().skip(cur_table.columns.len()) {
+                    column.nullable = true;
+                }
+
+                let text_cols_dict = BTreeMap::from([(
+                    table.oid,
+                    text_columns.iter().map(|c| c.name.clone()).collect(),
+                )]);
+                // Tables that are only partially ingested don't ingest the new
+                // columns either, which matches how `INCLUDE COLUMNS` is
+                // planned.
+                let mut include_cols_dict = BTreeMap::new();
+                if casts
+                    .iter()
+                    .any(|(cast_type, _)| *cast_type == CastType::Excluded)
+                {
+                    include_cols_dict.insert(
+                        table.oid,
+                        cur_table
+                            .columns
+                            .iter()
+                            .zip(casts.iter())
+                            .filter(|(_, (cast_type, _))| *cast_type != CastType::Excluded)
+                            .map(|(column, _)| column.name.clone())
+                            .collect(),
+                    );
+                }
+                let (mut stmts, _) = postgres::generate_targeted_subsources(
+                    scx,
+                    None,
+                    vec![RequestedSubsource {
+                        upstream_name: reference,
+                        subsource_name: subsource_name(subsource),
+                        table: &table,
+                    }],
+                    text_cols_dict,
+                    &include_cols_dict,
+                    &publication_tables,
+                )?;
+                let stmt = stmts.into_element();
+                subsources.insert(subsource.id(), stmt.columns);
+
+                details.tables[output_index - 1] = table;
+            }
+
+            if !incompatible_tables.is_empty() {
+                Err(PgSourcePurificationError::IncompatibleSchemaChanges {
+                    tables: incompatible_tables,
+                })?;
+            }
+
+            details.into_proto().encode_to_vec()
+        }
+        GenericSourceConnection::MySql(mysql_source_connection) => {
+            let MySqlSourceConnection {
+                connection,
+                mut details,
+                text_columns,
+                ignore_columns,
+                ..
+            } = mysql_source_connection;
+
+            let config = connection
+                .config(
+                    &storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                    InTask::No,
+                )
+                .await?;
+
+            let mut conn = config
+                .connect(
+                    "mysql purification",
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+
+            let mut output_indexes = vec![];
+            for (subsource, reference) in references {
+                let Some(output_index) = details.output_idx_for_name(&reference) else {
+                    sql_bail!("internal error: {} missing from source details", reference);
+                };
+                output_indexes.push((subsource, reference, output_index));
+            }
+
+            let text_cols_map = mysql::map_source_column_refs(&text_columns);
+            let ignore_cols_map = mysql::map_source_column_refs(&ignore_columns);
+            let tables = mz_mysql_util::schema_info(
+                &mut *conn,
+                &mz_mysql_util::SchemaRequest::Tables(
+                    output_indexes
+                        .iter()
+                        .map(|(_, _, output_index)| {
+                            let table = &details.tables[output_index - 1];
+                            (table.schema_name.as_str(), table.name.as_str())
+                        })
+                        .collect(),
+                ),
+                &text_cols_map,
+                &ignore_cols_map,
+            )
+            .await
+            .map_err(mysql::schema_info_error)?;
+
+            for (subsource, reference, output_index) in output_indexes {
+                let cur_table = &details.tables[output_index - 1];
+
+                let Some(new_table) = tables
+                    .iter()
+                    .find(|t| t.schema_name == cur_table.schema_name && t.name == cur_table.name)
+                else {
+                    incompatible_tables
+                        .push((reference.to_ast_string(), "table was dropped".to_string()));
+                    continue;
+                };
+                if let Err(err) = cur_table.determine_compatibility(new_table) {
+                    incompatible_tables.push((reference.to_ast_string(), err.to_string()));
+                    continue;
+                }
+                if cur_table.additive_changes(new_table).is_empty() {
+                    continue;
+                }
+
+                // Keys are not refreshed, as they describe the data we already
+                // ingested, and rows that predate the new columns contain
+                // `NULL` in them.
+                let mut table = new_table.clone();
+                table.keys = cur_table.keys.clone();
+                for column in table.columns.iter_mut().skip(cur_table.columns.len()) {
+                    if let Some(column_type) = &mut column.column_type {
+                        column_type.nullable = true;
+                    }
+                }
+
+                let stmts = mysql::generate_targeted_subsources(
+                    scx,
+                    vec![RequestedSubsource {
+                        upstream_name: reference,
+                        subsource_name: subsource_name(subsource),
+                        table: &table,
+                    }],
+                )?;
+                let stmt = stmts.into_element();
+                subsources.insert(subsource.id(), stmt.columns);
+
+                details.tables[output_index - 1] = table;
+            }
+
+            if !incompatible_tables.is_empty() {
+                Err(MySqlSourcePurificationError::IncompatibleSchemaChanges {
+                    tables: incompatible_tables,
+                })?;
+            }
+
+            details.into_proto().encode_to_vec()
+        }
+        connection => sql_bail!(
+            "{} is a {} source, which does not support ALTER SOURCE...REFRESH SCHEMA.",
+            scx.catalog.minimal_qualification(item.name()),
+            connection.name()
+        ),
+    };
+
+    Ok(PurifiedStatement::PurifiedAlterSourceRefreshSchema {
+        altered_id: item.id(),
+        details: hex::encode(details),
+        subsources,
+    })
+}
+
+/// Equivalent to `purify_alter_source` but for `ALTER SOURCE...RESET TO`.
+///
+/// Translates the requested position into the offset of each partition of the
+/// source's topic at which it should resume reading.
+async fn purify_alter_source_reset_to(
+    scx: &StatementContext<'_>,
+    item: &dyn CatalogItem,
+    desc: SourceDesc,
+    now: u64,
+    position: SourceResetPosition,
+    value: WithOptionValue<Aug>,
+    storage_configuration: &StorageConfiguration,
+) -> Result<PurifiedStatement, PlanError> {
+    let kafka_connection = match desc.connection {
+        GenericSourceConnection::Kafka(kafka_connection) => kafka_connection,
+        connection => sql_bail!(
+            "{} is a {} source, which does not support ALTER SOURCE...RESET TO.",
+            scx.catalog.minimal_qualification(item.name()),
+            connection.name()
+        ),
+    };
+
+    if kafka_connection.topic_is_pattern {
+        Err(KafkaSourcePurificationError::UnsupportedWithTopicPattern(
+            "ALTER SOURCE...RESET TO",
+        ))?;
+    }
+
+    // The progress statements of this envelope describe the entire topic, so
+    // it cannot start reading from anywhere but its beginning.
+    if matches!(
+        desc.envelope,
+        mz_storage_types::sources::envelope::SourceEnvelope::CdcV2
+    ) {
+        sql_bail!("ALTER SOURCE...RESET TO is not supported with ENVELOPE MATERIALIZE");
+    }
+
+    let consumer = kafka_connection
+        .connection
+        .create_with_context(
+            storage_configuration,
+            MzClientContext::default(),
+            &BTreeMap::new(),
+            InTask::No,
+        )
+        .await
+        .map_err(|e| {
+            // anyhow doesn't support Clone, so not trivial to move into PlanError
+            KafkaSourcePurificationError::KafkaConsumerError(e.display_with_causes().to_string())
+        })?;
+    let consumer = Arc::new(consumer);
+
+    let fetch_metadata_timeout = storage_configuration
+        .parameters
+        .kafka_timeout_config
+        .fetch_metadata_timeout;
+
+    let name = match position {
+        SourceResetPosition::Offset => KafkaSourceConfigOptionName::StartOffset,
+        SourceResetPosition::Timestamp => KafkaSourceConfigOptionName::StartTimestamp,
+    };
+    let extracted_options: KafkaSourceConfigOptionExtracted = vec![KafkaSourceConfigOption {
+        name,
+        value: Some(value),
+    }]
+    .try_into()?;
+
+    let start_offsets = match (
+        extracted_options.start_offset,
+        extracted_options.start_timestamp,
+    ) {
+        (Some(start_offsets), None) => {
+            if start_offsets.iter().any(|offset| *offset < 0) {
+                sql_bail!("RESET TO OFFSET must be a nonnegative integer");
+            }
+            kafka_util::validate_start_offsets(
+                Arc::clone(&consumer),
+                &kafka_connection.topic,
+                start_offsets.clone(),
+                fetch_metadata_timeout,
+            )
+            .await?;
+            start_offsets
+        }
+        (None, Some(time_offset)) => {
+            kafka_util::lookup_start_offsets(
+                Arc::clone(&consumer),
+                &kafka_connection.topic,
+                time_offset,
+                now,
+                fetch_metadata_timeout,
+            )
+            .await?
+        }
+        _ => unreachable!("exactly one option was provided"),
+    };
+
+    Ok(PurifiedStatement::PurifiedAlterSourceResetTo {
+        altered_id: item.id(),
+        start_offsets,
+    })
+}
+
 async fn purify_source_format(
     catalog: &dyn SessionCatalog,
-    format: &mut Option<CreateSourceFormat<Aug>>,
+    format: &mut Option<FormatSpecifier<Aug>>,
     connection: &mut CreateSourceConnection<Aug>,
     envelope: &Option<SourceEnvelope>,
     storage_configuration: &StorageConfiguration,
 ) -> Result<(), PlanError> {
-    if matches!(format, Some(CreateSourceFormat::KeyValue { .. }))
+    if matches!(format, Some(FormatSpecifier::KeyValue { .. }))
         && !matches!(connection, CreateSourceConnection::Kafka { .. })
     {
         sql_bail!("Kafka sources are the only source type that can provide KEY/VALUE formats")
@@ -1503,7 +2469,7 @@ async fn purify_source_format(
 
     match format.as_mut() {
         None => {}
-        Some(CreateSourceFormat::Bare(format)) => {
+        Some(FormatSpecifier::Bare(format)) => {
             purify_source_format_single(
                 catalog,
                 format,
@@ -1514,7 +2480,7 @@ async fn purify_source_format(
             .await?;
         }
 
-        Some(CreateSourceFormat::KeyValue { key, value: val }) => {
+        Some(FormatSpecifier::KeyValue { key, value: val }) => {
             purify_source_format_single(catalog, key, connection, envelope, storage_configuration)
                 .await?;
             purify_source_format_single(catalog, val, connection, envelope, storage_configuration)
@@ -1562,7 +2528,8 @@ async fn purify_source_format_single(
         | Format::Regex(_)
         | Format::Json { .. }
         | Format::Text
-        | Format::Csv { .. } => (),
+        | Format::Csv { .. }
+        | Format::Parquet => (),
     }
     Ok(())
 }
@@ -1579,7 +2546,14 @@ async fn purify_csr_connection_proto(
             .clone()
             .try_into()
             .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
+        // Schemas are looked up by subjects named after the topic, which a pattern doesn't
+        // determine.
+        match topic {
+            Some(topic) => topic,
+            None => {
+                sql_bail!("CONFLUENT SCHEMA REGISTRY formats are not supported with TOPIC PATTERN")
+            }
+        }
     } else {
         sql_bail!("Confluent Schema Registry is only supported with Kafka sources")
     };
@@ -1634,7 +2608,14 @@ async fn purify_csr_connection_avro(
             .clone()
             .try_into()
             .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
+        // Schemas are looked up by subjects named after the topic, which a pattern doesn't
+        // determine.
+        match topic {
+            Some(topic) => topic,
+            None => {
+                sql_bail!("CONFLUENT SCHEMA REGISTRY formats are not supported with TOPIC PATTERN")
+            }
+        }
     } else {
         sql_bail!("Confluent Schema Registry is only supported with Kafka sources")
     };
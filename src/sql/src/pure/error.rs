@@ -15,7 +15,7 @@ use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{ReferencedSubsources, UnresolvedItemName};
 use mz_storage_types::errors::{ContextCreationError, CsrConnectError};
 
-use crate::names::{FullItemName, PartialItemName};
+use crate::names::{Aug, FullItemName, PartialItemName};
 
 /// Logical errors detectable during purification for a POSTGRES SOURCE.
 #[derive(Debug, Clone, thiserror::Error)]
@@ -39,6 +39,20 @@ pub enum PgSourcePurificationError {
     NotTablesWReplicaIdentityFull { items: Vec<String> },
     #[error("TEXT COLUMNS refers to table not currently being added")]
     DanglingTextColumns { items: Vec<PartialItemName> },
+    #[error("RETRACT ON TRUNCATE refers to tables not currently being added")]
+    DanglingRetractOnTruncate { items: Vec<UnresolvedItemName> },
+    #[error("UPSERT TABLES refers to tables not currently being added")]
+    DanglingUpsertTables { items: Vec<UnresolvedItemName> },
+    #[error("UPSERT TABLES refers to tables without a primary key")]
+    UpsertTablesWithoutPrimaryKey { items: Vec<UnresolvedItemName> },
+    #[error("tables cannot be listed in both UPSERT TABLES and RETRACT ON TRUNCATE")]
+    UpsertTablesRetractOnTruncate { items: Vec<UnresolvedItemName> },
+    #[error("INCLUDE COLUMNS refers to tables not currently being added")]
+    DanglingIncludeColumns { items: Vec<UnresolvedItemName> },
+    #[error("ROW FILTERS refers to tables not currently being added")]
+    DanglingRowFilters { items: Vec<UnresolvedItemName> },
+    #[error("tables listed in UPSERT TABLES cannot have column lists or row filters")]
+    UpsertTablesWithFilters { items: Vec<UnresolvedItemName> },
     #[error("referenced tables use unsupported types")]
     UnrecognizedTypes { cols: Vec<(String, Oid)> },
     #[error("{0} is not a POSTGRES CONNECTION")]
@@ -53,6 +67,8 @@ pub enum PgSourcePurificationError {
     },
     #[error("replication disabled on server")]
     ReplicationDisabled,
+    #[error("referenced tables were altered in ways that cannot be refreshed")]
+    IncompatibleSchemaChanges { tables: Vec<(String, String)> },
 }
 
 impl PgSourcePurificationError {
@@ -62,6 +78,19 @@ impl PgSourcePurificationError {
                 "the following tables are referenced but not added: {}",
                 itertools::join(items, ", ")
             )),
+            Self::DanglingRetractOnTruncate { items }
+            | Self::DanglingUpsertTables { items }
+            | Self::DanglingIncludeColumns { items }
+            | Self::DanglingRowFilters { items } => Some(format!(
+                "the following tables are referenced but not added: {}",
+                itertools::join(items, ", ")
+            )),
+            Self::UpsertTablesWithoutPrimaryKey { items }
+            | Self::UpsertTablesRetractOnTruncate { items }
+            | Self::UpsertTablesWithFilters { items } => Some(format!(
+                "referenced tables: {}",
+                itertools::join(items, ", ")
+            )),
             Self::DatabaseMissingFilteredSchemas {
                 database: _,
                 schemas,
@@ -95,6 +124,15 @@ impl PgSourcePurificationError {
                 count,
                 if *count == 1 { "" } else { "s" }
             )),
+            Self::IncompatibleSchemaChanges { tables } => Some(format!(
+                "the following tables were altered:\n{}",
+                itertools::join(
+                    tables
+                        .iter()
+                        .map(|(table, err)| format!("{}: {}", table, err)),
+                    "\n"
+                )
+            )),
             _ => None,
         }
     }
@@ -119,6 +157,16 @@ impl PgSourcePurificationError {
                 "you might be able to wait for other sources to finish snapshotting and try again".into()
             ),
             Self::ReplicationDisabled => Some("set max_wal_senders to a value > 0".into()),
+            Self::NotTablesWReplicaIdentityFull { .. } => Some(
+                "Use ALTER TABLE ... REPLICA IDENTITY FULL on the tables, or list tables that have \
+                a primary key in the UPSERT TABLES option."
+                    .into(),
+            ),
+            Self::IncompatibleSchemaChanges { .. } => Some(
+                "Only added columns and widened varchar columns can be refreshed. Drop the \
+                affected subsources and add them again with ALTER SOURCE ... ADD SUBSOURCE."
+                    .into(),
+            ),
             _ => None,
         }
     }
@@ -128,9 +176,17 @@ impl PgSourcePurificationError {
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum KafkaSourcePurificationError {
     #[error("{} is only valid for multi-output sources", .0.to_ast_string())]
-    ReferencedSubsources(ReferencedSubsources),
+    ReferencedSubsources(ReferencedSubsources<Aug>),
     #[error("KAFKA CONNECTION without TOPIC")]
     ConnectionMissingTopic,
+    #[error("cannot specify TOPIC and TOPIC PATTERN at same time")]
+    TopicAndTopicPattern,
+    #[error("invalid TOPIC PATTERN '{0}'")]
+    InvalidTopicPattern(String, String),
+    #[error("{0} is not supported with TOPIC PATTERN")]
+    UnsupportedWithTopicPattern(&'static str),
+    #[error("START EPOCH cannot be specified directly")]
+    UserSpecifiedStartEpoch,
     #[error("{0} is not a KAFKA CONNECTION")]
     NotKafkaConnection(FullItemName),
     #[error("failed to create and connect Kafka consumer")]
@@ -141,12 +197,19 @@ impl KafkaSourcePurificationError {
     pub fn detail(&self) -> Option<String> {
         match self {
             Self::KafkaConsumerError(e) => Some(e.clone()),
+            Self::InvalidTopicPattern(_, e) => Some(e.clone()),
             _ => None,
         }
     }
 
     pub fn hint(&self) -> Option<String> {
-        None
+        match self {
+            Self::UserSpecifiedStartEpoch => Some(
+                "Use ALTER SOURCE...RESET TO to move an existing source to a new start position."
+                    .into(),
+            ),
+            _ => None,
+        }
     }
 }
 
@@ -228,6 +291,79 @@ impl CsrPurificationError {
     }
 }
 
+/// Logical errors detectable during purification for a SQL SERVER SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SqlServerSourcePurificationError {
+    #[error("CREATE SOURCE specifies DETAILS option")]
+    UserSpecifiedDetails,
+    #[error("{0} is not a SQL SERVER CONNECTION")]
+    NotSqlServerConnection(FullItemName),
+    #[error("Invalid SQL Server system settings")]
+    SystemSettingsError(Vec<(String, String, String)>),
+    #[error("referenced tables use unsupported types")]
+    UnrecognizedTypes { cols: Vec<(String, String, String)> },
+    #[error("No CDC-enabled tables found")]
+    EmptyDatabase,
+    #[error("missing TABLES specification")]
+    RequiresReferencedSubsources,
+    #[error("No CDC-enabled tables found in referenced schemas")]
+    NoTablesFoundForSchemas(Vec<String>),
+}
+
+impl SqlServerSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::SystemSettingsError(settings) => Some(format!(
+                "Invalid SQL Server system settings: {}",
+                itertools::join(
+                    settings.iter().map(|(setting, expected, actual)| format!(
+                        "{}: expected {}, got {}",
+                        setting, expected, actual
+                    )),
+                    "; "
+                )
+            )),
+            Self::UnrecognizedTypes { cols } => Some(format!(
+                "the following columns contain unsupported types:\n{}",
+                itertools::join(
+                    cols.into_iter().map(|(table, column, data_type)| format!(
+                        "'{}' for {}.{}",
+                        data_type, column, table
+                    )),
+                    "\n"
+                )
+            )),
+            Self::NoTablesFoundForSchemas(schemas) => Some(format!(
+                "missing schemas: {}",
+                itertools::join(schemas.iter(), ", ")
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UserSpecifiedDetails => Some(
+                "If trying to use the output of SHOW CREATE SOURCE, remove the DETAILS option."
+                    .into(),
+            ),
+            Self::SystemSettingsError(_) => Some(
+                "Enable change data capture and snapshot isolation on the SQL Server database."
+                    .into(),
+            ),
+            Self::RequiresReferencedSubsources => {
+                Some("provide a FOR TABLES (..), FOR SCHEMAS (..), or FOR ALL TABLES clause".into())
+            }
+            Self::EmptyDatabase => Some(
+                "No tables were found to replicate. Enable change data capture on the intended \
+                tables using sys.sp_cdc_enable_table."
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
 /// Logical errors detectable during purification for a MySQL SOURCE.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum MySqlSourcePurificationError {
@@ -245,6 +381,8 @@ pub enum MySqlSourcePurificationError {
     DuplicatedColumnNames(String, Vec<String>),
     #[error("TEXT COLUMNS refers to columns not currently being added")]
     DanglingTextColumns { items: Vec<UnresolvedItemName> },
+    #[error("RETRACT ON TRUNCATE refers to tables not currently being added")]
+    DanglingRetractOnTruncate { items: Vec<UnresolvedItemName> },
     #[error("Invalid MySQL table reference: {0}")]
     InvalidTableReference(String),
     #[error("No tables found")]
@@ -253,6 +391,8 @@ pub enum MySqlSourcePurificationError {
     RequiresReferencedSubsources,
     #[error("No tables found in referenced schemas")]
     NoTablesFoundForSchemas(Vec<String>),
+    #[error("referenced tables were altered in ways that cannot be refreshed")]
+    IncompatibleSchemaChanges { tables: Vec<(String, String)> },
 }
 
 impl MySqlSourcePurificationError {
@@ -271,6 +411,10 @@ impl MySqlSourcePurificationError {
                 "the following columns are referenced but not added: {}",
                 itertools::join(items, ", ")
             )),
+            Self::DanglingRetractOnTruncate { items } => Some(format!(
+                "the following tables are referenced but not added: {}",
+                itertools::join(items, ", ")
+            )),
             Self::ReplicationSettingsError(settings) => Some(format!(
                 "Invalid MySQL system replication settings: {}",
                 itertools::join(
@@ -295,6 +439,15 @@ impl MySqlSourcePurificationError {
                 "missing schemas: {}",
                 itertools::join(schemas.iter(), ", ")
             )),
+            Self::IncompatibleSchemaChanges { tables } => Some(format!(
+                "the following tables were altered:\n{}",
+                itertools::join(
+                    tables
+                        .iter()
+                        .map(|(table, err)| format!("{}: {}", table, err)),
+                    "\n"
+                )
+            )),
             _ => None,
         }
     }
@@ -324,6 +477,87 @@ impl MySqlSourcePurificationError {
                 the user does not have privileges on the intended tables."
                     .into(),
             ),
+            Self::IncompatibleSchemaChanges { .. } => Some(
+                "Only added columns and widened varchar columns can be refreshed. Drop the \
+                source and create it again."
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Logical errors detectable during purification for a KINESIS SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum KinesisSourcePurificationError {
+    #[error("{} is only valid for multi-output sources", .0.to_ast_string())]
+    ReferencedSubsources(ReferencedSubsources<Aug>),
+    #[error("failed to access stream {0}")]
+    StreamError(String, String),
+}
+
+impl KinesisSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::StreamError(_, e) => Some(e.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Logical errors detectable during purification for an S3 or LOCAL FILES SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum FileSourcePurificationError {
+    #[error("{} is only valid for multi-output sources", .0.to_ast_string())]
+    ReferencedSubsources(ReferencedSubsources<Aug>),
+    #[error("CREATE SOURCE specifies DETAILS option")]
+    UserSpecifiedDetails,
+    #[error("{0} is not an AWS CONNECTION")]
+    NotAwsConnection(FullItemName),
+    #[error("failed to access objects")]
+    ClientError(String),
+    #[error("no objects found")]
+    NoObjects,
+    #[error("failed to read the schema of {key}")]
+    InvalidSchema { key: String, error: String },
+    #[error("FORMAT PARQUET is not supported by LOCAL FILES sources")]
+    LocalParquet,
+    #[error("FORMAT CSV WITH HEADER requires column names in LOCAL FILES sources")]
+    LocalCsvHeader,
+}
+
+impl FileSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::ClientError(e) => Some(e.clone()),
+            Self::InvalidSchema { error, .. } => Some(error.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UserSpecifiedDetails => Some(
+                "If trying to use the output of SHOW CREATE SOURCE, remove the DETAILS option."
+                    .into(),
+            ),
+            Self::NoObjects => Some(
+                "The schema of the source is determined from the first object, so at least \
+                one object must exist under the prefix when the source is created."
+                    .into(),
+            ),
+            Self::LocalParquet => Some(
+                "The schema of a Parquet source is read from its objects when the source is \
+                created, which is only possible for S3 sources."
+                    .into(),
+            ),
+            Self::LocalCsvHeader => {
+                Some("Name the columns explicitly, e.g. FORMAT CSV WITH HEADER (a, b).".into())
+            }
             _ => None,
         }
     }
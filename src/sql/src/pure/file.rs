@@ -0,0 +1,169 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! File source utilities for SQL purification.
+
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_proto::RustType;
+use mz_sql_parser::ast::{
+    CsvColumns, FileSourceOption, FileSourceOptionName, Format, FormatSpecifier, Ident, Value,
+    WithOptionValue,
+};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::connections::inline::IntoInlineConnection;
+use mz_storage_types::connections::Connection;
+use mz_storage_types::sources::file::{FileSourceDetails, FileStore};
+use prost::Message;
+
+use crate::names::{Aug, ResolvedItemName};
+use crate::plan::statement::ddl::{plan_file_store, FileSourceOptionExtracted};
+use crate::plan::{PlanError, StatementContext};
+use crate::pure::FileSourcePurificationError;
+
+/// Validates that the objects of a file source can be accessed and records
+/// what is learned from them in the statement.
+///
+/// The schema of a `FORMAT PARQUET` source is read from the first object under
+/// the prefix and recorded in the `DETAILS` option. Likewise, the column names
+/// of a `FORMAT CSV WITH HEADER` source that does not name its columns are read
+/// from the header of the first object.
+///
+/// The objects of a `LOCAL FILES` source live on the filesystem of the cluster
+/// replica rather than of this process, so they are not accessed here, and
+/// formats that require reading them are rejected.
+pub(super) async fn purify_file_source(
+    scx: &StatementContext<'_>,
+    connection: Option<&ResolvedItemName>,
+    options: &mut Vec<FileSourceOption<Aug>>,
+    format: &mut Option<FormatSpecifier<Aug>>,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), PlanError> {
+    if let Some(connection) = connection {
+        let item = scx.get_item_by_resolved_name(connection)?;
+        if !matches!(item.connection()?, Connection::Aws(_)) {
+            Err(FileSourcePurificationError::NotAwsConnection(
+                scx.catalog.resolve_full_name(item.name()),
+            ))?;
+        }
+    }
+
+    let FileSourceOptionExtracted {
+        bucket,
+        path,
+        prefix,
+        details,
+        seen: _,
+    } = options.clone().try_into()?;
+
+    if details.is_some() {
+        Err(FileSourcePurificationError::UserSpecifiedDetails)?;
+    }
+
+    let store = plan_file_store(scx, connection, bucket, path)?.into_inline_connection(scx.catalog);
+    if matches!(store, FileStore::Local { .. }) {
+        match format {
+            Some(FormatSpecifier::Bare(Format::Parquet)) => {
+                Err(FileSourcePurificationError::LocalParquet)?
+            }
+            Some(FormatSpecifier::Bare(Format::Csv {
+                columns: CsvColumns::Header { names },
+                ..
+            })) if names.is_empty() => Err(FileSourcePurificationError::LocalCsvHeader)?,
+            _ => {}
+        }
+        push_details(options, FileSourceDetails::default());
+        return Ok(());
+    }
+
+    let client = store
+        .client(&storage_configuration.connection_context, InTask::No)
+        .await
+        .map_err(|e| {
+            FileSourcePurificationError::ClientError(e.display_with_causes().to_string())
+        })?;
+    let keys = client.list_objects(&prefix).await.map_err(|e| {
+        FileSourcePurificationError::ClientError(e.display_with_causes().to_string())
+    })?;
+
+    let mut details = FileSourceDetails::default();
+    match format {
+        Some(FormatSpecifier::Bare(Format::Parquet)) => {
+            let (key, contents) = first_object(&client, &keys).await?;
+            let desc = mz_arrow_util::reader::parquet_desc(contents).map_err(|e| {
+                FileSourcePurificationError::InvalidSchema {
+                    key,
+                    error: e.display_with_causes().to_string(),
+                }
+            })?;
+            details.parquet_desc = Some(desc);
+        }
+        Some(FormatSpecifier::Bare(Format::Csv {
+            columns: CsvColumns::Header { names },
+            delimiter,
+        })) if names.is_empty() => {
+            let (key, contents) = first_object(&client, &keys).await?;
+            let header = contents
+                .split(|b| *b == b'\n')
+                .next()
+                .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+                .unwrap_or_default();
+            let header = std::str::from_utf8(header).map_err(|e| {
+                FileSourcePurificationError::InvalidSchema {
+                    key: key.clone(),
+                    error: e.to_string(),
+                }
+            })?;
+            *names = header
+                .split(*delimiter)
+                .map(|name| {
+                    Ident::new(name).map_err(|e| FileSourcePurificationError::InvalidSchema {
+                        key: key.clone(),
+                        error: e.to_string(),
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        _ => {}
+    }
+
+    push_details(options, details);
+
+    Ok(())
+}
+
+/// Records `details` in the `DETAILS` option of a file source.
+fn push_details(options: &mut Vec<FileSourceOption<Aug>>, details: FileSourceDetails) {
+    options.push(FileSourceOption {
+        name: FileSourceOptionName::Details,
+        value: Some(WithOptionValue::Value(Value::String(hex::encode(
+            details.into_proto().encode_to_vec(),
+        )))),
+    });
+}
+
+/// Fetches the first object under the prefix, whose contents determine the
+/// schema of the source.
+async fn first_object(
+    client: &mz_storage_types::sources::file::FileStoreClient,
+    keys: &[String],
+) -> Result<(String, bytes::Bytes), PlanError> {
+    for key in keys {
+        let contents = client.get_object(key).await.map_err(|e| {
+            FileSourcePurificationError::ClientError(e.display_with_causes().to_string())
+        })?;
+        // Skip objects that were deleted since they were listed, as well as
+        // empty objects, which the source ignores.
+        match contents {
+            Some(contents) if !contents.is_empty() => return Ok((key.clone(), contents)),
+            _ => {}
+        }
+    }
+    Err(FileSourcePurificationError::NoObjects.into())
+}
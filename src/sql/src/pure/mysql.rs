@@ -18,6 +18,7 @@ use mz_sql_parser::ast::{
     ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement, Ident,
     IdentError, WithOptionValue,
 };
+use mz_storage_types::sources::mysql::MySqlColumnRef;
 
 use crate::catalog::SubsourceCatalog;
 use crate::names::Aug;
@@ -181,6 +182,47 @@ pub(super) fn map_column_refs<'a>(
     Ok(table_to_cols)
 }
 
+/// Map the column references of an existing source to a map of table references to column names.
+pub(super) fn map_source_column_refs(
+    cols: &[MySqlColumnRef],
+) -> BTreeMap<QualifiedTableRef<'_>, BTreeSet<&str>> {
+    let mut table_to_cols = BTreeMap::new();
+    for col in cols.iter() {
+        let key = mz_mysql_util::QualifiedTableRef {
+            schema_name: col.schema_name.as_str(),
+            table_name: col.table_name.as_str(),
+        };
+        table_to_cols
+            .entry(key)
+            .or_insert_with(BTreeSet::new)
+            .insert(col.column_name.as_str());
+    }
+    table_to_cols
+}
+
+/// Converts the errors of [`mz_mysql_util::schema_info`] into the purification
+/// errors that describe them.
+pub(super) fn schema_info_error(err: MySqlError) -> PlanError {
+    match err {
+        MySqlError::UnsupportedDataTypes { columns } => {
+            PlanError::from(MySqlSourcePurificationError::UnrecognizedTypes {
+                cols: columns
+                    .into_iter()
+                    .map(|c| (c.qualified_table_name, c.column_name, c.column_type))
+                    .collect(),
+            })
+        }
+        MySqlError::DuplicatedColumnNames {
+            qualified_table_name,
+            columns,
+        } => PlanError::from(MySqlSourcePurificationError::DuplicatedColumnNames(
+            qualified_table_name,
+            columns,
+        )),
+        _ => err.into(),
+    }
+}
+
 /// Normalize column references to a sorted, deduplicated options list of column names.
 pub(super) fn normalize_column_refs<'a>(
     cols: Vec<UnresolvedItemName>,
@@ -209,6 +251,47 @@ pub(super) fn normalize_column_refs<'a>(
     Ok(seq)
 }
 
+/// Resolves the tables referenced by the `RETRACT ON TRUNCATE` option to their
+/// fully qualified names, returning them sorted and deduplicated.
+///
+/// All referenced tables must be among the `requested_subsources`.
+pub(super) fn normalize_retract_on_truncate(
+    catalog: &SubsourceCatalog<&MySqlTableDesc>,
+    requested_subsources: &[RequestedSubsource<'_, MySqlTableDesc>],
+    tables: Vec<UnresolvedItemName>,
+) -> Result<Vec<UnresolvedItemName>, PlanError> {
+    let requested: BTreeSet<_> = requested_subsources
+        .iter()
+        .map(|subsource| &subsource.upstream_name)
+        .collect();
+
+    let mut normalized = vec![];
+    let mut dangling = vec![];
+    for name in tables {
+        let (_, desc) = catalog
+            .resolve(name)
+            .map_err(|e| PlanError::InvalidOptionValue {
+                option_name: MySqlConfigOptionName::RetractOnTruncate.to_ast_string(),
+                err: Box::new(e),
+            })?;
+        let upstream_name = mysql_upstream_name(desc)?;
+
+        if requested.contains(&upstream_name) {
+            normalized.push(upstream_name);
+        } else {
+            dangling.push(upstream_name);
+        }
+    }
+
+    if !dangling.is_empty() {
+        Err(MySqlSourcePurificationError::DanglingRetractOnTruncate { items: dangling })?;
+    }
+
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
 pub(super) async fn validate_requested_subsources_privileges(
     requested_subsources: &[RequestedSubsource<'_, MySqlTableDesc>],
     conn: &mut mz_mysql_util::MySqlConn,
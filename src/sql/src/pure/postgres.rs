@@ -11,14 +11,15 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
+use itertools::Itertools;
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::Config;
 use mz_repr::adt::system::Oid;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::UnresolvedItemName;
 use mz_sql_parser::ast::{
-    ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement, Ident,
-    WithOptionValue,
+    ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement, Expr,
+    Ident, WithOptionValue,
 };
 use mz_ssh_util::tunnel_manager::SshTunnelManager;
 
@@ -49,9 +50,12 @@ pub(super) fn derive_catalog_from_publication_tables<'a>(
     Ok(SubsourceCatalog(tables_by_name))
 }
 
+/// Validates that we can ingest the `requested_subsources`. Tables listed in
+/// `upsert_tables` may use `REPLICA IDENTITY DEFAULT` in addition to `FULL`.
 pub(super) async fn validate_requested_subsources_privileges(
     config: &Config,
     requested_subsources: &[RequestedSubsource<'_, PostgresTableDesc>],
+    upsert_tables: &[UnresolvedItemName],
     ssh_tunnel_manager: &SshTunnelManager,
 ) -> Result<(), PlanError> {
     // Ensure that we have select permissions on all tables; we have to do this before we
@@ -75,24 +79,32 @@ pub(super) async fn validate_requested_subsources_privileges(
         .map(|RequestedSubsource { table, .. }| table.oid)
         .collect();
 
-    replica_identity::check_replica_identity_full(config, oids, ssh_tunnel_manager).await?;
+    let upsert_oids: Vec<_> = requested_subsources
+        .iter()
+        .filter(|subsource| upsert_tables.contains(&subsource.upstream_name))
+        .map(|RequestedSubsource { table, .. }| table.oid)
+        .collect();
+
+    replica_identity::check_replica_identity_full(config, oids, upsert_oids, ssh_tunnel_manager)
+        .await?;
 
     Ok(())
 }
 
-/// Generate a mapping of `Oid`s to column names that should be ingested as text
-/// (rather than their type in the upstream database).
+/// Generate a mapping of `Oid`s to the names of the columns referenced by
+/// `column_refs`, e.g. the columns that should be ingested as text (rather than
+/// their type in the upstream database).
 ///
-/// Additionally, modify `text_columns` so that they contain database-qualified
+/// Additionally, modify `column_refs` so that they contain database-qualified
 /// references to the columns.
-pub(super) fn generate_text_columns(
+pub(super) fn generate_column_refs(
     publication_catalog: &SubsourceCatalog<&PostgresTableDesc>,
-    text_columns: &mut [UnresolvedItemName],
+    column_refs: &mut [UnresolvedItemName],
     option_name: &str,
 ) -> Result<BTreeMap<u32, BTreeSet<String>>, PlanError> {
-    let mut text_cols_dict: BTreeMap<u32, BTreeSet<String>> = BTreeMap::new();
+    let mut column_refs_dict: BTreeMap<u32, BTreeSet<String>> = BTreeMap::new();
 
-    for name in text_columns {
+    for name in column_refs {
         let (qual, col) = match name.0.split_last().expect("must have at least one element") {
             (col, qual) if qual.is_empty() => {
                 return Err(PlanError::InvalidOptionValue {
@@ -143,7 +155,7 @@ pub(super) fn generate_text_columns(
         fully_qualified_name.0.push(col_ident);
         *name = fully_qualified_name;
 
-        let new = text_cols_dict
+        let new = column_refs_dict
             .entry(desc.oid)
             .or_default()
             .insert(col.as_str().to_string());
@@ -156,7 +168,217 @@ pub(super) fn generate_text_columns(
         }
     }
 
-    Ok(text_cols_dict)
+    Ok(column_refs_dict)
+}
+
+/// Generate a mapping of `Oid`s to the names of the columns that should be
+/// ingested from the tables referenced by `include_columns`, which are the only
+/// tables that are not ingested in full.
+///
+/// Additionally, modify `include_columns` so that they contain sorted,
+/// database-qualified references to the columns. The references to tables
+/// whose columns are all included are removed, as they are ingested in full.
+pub(super) fn generate_include_columns(
+    publication_catalog: &SubsourceCatalog<&PostgresTableDesc>,
+    requested_subsources: &[RequestedSubsource<'_, PostgresTableDesc>],
+    include_columns: &mut Vec<UnresolvedItemName>,
+    option_name: &str,
+) -> Result<BTreeMap<u32, BTreeSet<String>>, PlanError> {
+    let mut include_cols_dict =
+        generate_column_refs(publication_catalog, include_columns, option_name)?;
+
+    // The table of each column reference.
+    let table_of =
+        |name: &UnresolvedItemName| UnresolvedItemName(name.0[..name.0.len() - 1].to_vec());
+
+    let requested_oids: BTreeSet<_> = requested_subsources
+        .iter()
+        .map(|subsource| subsource.table.oid)
+        .collect();
+    let mut dangling: Vec<_> = include_columns
+        .iter()
+        .map(table_of)
+        .filter(|table| {
+            let (_, desc) = publication_catalog
+                .resolve(table.clone())
+                .expect("resolved when generating column references");
+            !requested_oids.contains(&desc.oid)
+        })
+        .unique()
+        .collect();
+    if !dangling.is_empty() {
+        dangling.sort();
+        Err(PgSourcePurificationError::DanglingIncludeColumns { items: dangling })?;
+    }
+
+    let mut complete_tables = BTreeSet::new();
+    for RequestedSubsource {
+        upstream_name,
+        table,
+        ..
+    } in requested_subsources
+    {
+        if let Some(names) = include_cols_dict.get(&table.oid) {
+            if names.len() == table.columns.len() {
+                include_cols_dict.remove(&table.oid);
+                complete_tables.insert(upstream_name.clone());
+            }
+        }
+    }
+
+    include_columns.retain(|name| !complete_tables.contains(&table_of(name)));
+    include_columns.sort();
+
+    Ok(include_cols_dict)
+}
+
+/// Resolves the tables referenced by the `ROW FILTERS` option to their fully
+/// qualified names, returning the filters sorted by table.
+///
+/// All referenced tables must be among the `requested_subsources`, and each
+/// may have at most one filter.
+pub(super) fn normalize_row_filters(
+    publication_catalog: &SubsourceCatalog<&PostgresTableDesc>,
+    requested_subsources: &[RequestedSubsource<'_, PostgresTableDesc>],
+    row_filters: Vec<(UnresolvedItemName, Expr<Aug>)>,
+    option_name: &str,
+) -> Result<Vec<(UnresolvedItemName, Expr<Aug>)>, PlanError> {
+    let (tables, filters): (Vec<_>, Vec<_>) = row_filters.into_iter().unzip();
+    let (resolved, dangling) = resolve_requested_tables(
+        publication_catalog,
+        requested_subsources,
+        tables,
+        option_name,
+    )?;
+
+    if !dangling.is_empty() {
+        Err(PgSourcePurificationError::DanglingRowFilters { items: dangling })?;
+    }
+
+    let mut normalized: Vec<_> = resolved
+        .into_iter()
+        .map(|(name, _)| name)
+        .zip_eq(filters)
+        .collect();
+
+    if let Some(name) = normalized.iter().map(|(name, _)| name).duplicates().next() {
+        return Err(PlanError::InvalidOptionValue {
+            option_name: option_name.to_string(),
+            err: Box::new(PlanError::UnexpectedDuplicateReference { name: name.clone() }),
+        });
+    }
+
+    normalized.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(normalized)
+}
+
+/// Resolves the tables referenced by the `RETRACT ON TRUNCATE` option to their
+/// fully qualified names, returning them sorted and deduplicated.
+///
+/// All referenced tables must be among the `requested_subsources`.
+pub(super) fn normalize_retract_on_truncate(
+    publication_catalog: &SubsourceCatalog<&PostgresTableDesc>,
+    requested_subsources: &[RequestedSubsource<'_, PostgresTableDesc>],
+    tables: Vec<UnresolvedItemName>,
+    option_name: &str,
+) -> Result<Vec<UnresolvedItemName>, PlanError> {
+    let (resolved, dangling) = resolve_requested_tables(
+        publication_catalog,
+        requested_subsources,
+        tables,
+        option_name,
+    )?;
+
+    if !dangling.is_empty() {
+        Err(PgSourcePurificationError::DanglingRetractOnTruncate { items: dangling })?;
+    }
+
+    let mut normalized: Vec<_> = resolved.into_iter().map(|(name, _)| name).collect();
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
+/// Resolves the tables referenced by the `UPSERT TABLES` option to their fully
+/// qualified names, returning them sorted and deduplicated.
+///
+/// All referenced tables must be among the `requested_subsources` and have a
+/// primary key.
+pub(super) fn normalize_upsert_tables(
+    publication_catalog: &SubsourceCatalog<&PostgresTableDesc>,
+    requested_subsources: &[RequestedSubsource<'_, PostgresTableDesc>],
+    tables: Vec<UnresolvedItemName>,
+    option_name: &str,
+) -> Result<Vec<UnresolvedItemName>, PlanError> {
+    let (resolved, dangling) = resolve_requested_tables(
+        publication_catalog,
+        requested_subsources,
+        tables,
+        option_name,
+    )?;
+
+    if !dangling.is_empty() {
+        Err(PgSourcePurificationError::DanglingUpsertTables { items: dangling })?;
+    }
+
+    let mut without_primary_key: Vec<_> = resolved
+        .iter()
+        .filter(|(_, desc)| !desc.keys.iter().any(|key| key.is_primary))
+        .map(|(name, _)| name.clone())
+        .collect();
+    if !without_primary_key.is_empty() {
+        without_primary_key.sort();
+        without_primary_key.dedup();
+        Err(PgSourcePurificationError::UpsertTablesWithoutPrimaryKey {
+            items: without_primary_key,
+        })?;
+    }
+
+    let mut normalized: Vec<_> = resolved.into_iter().map(|(name, _)| name).collect();
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
+/// Resolves `tables` against the `publication_catalog`, splitting them into
+/// the tables that are among the `requested_subsources` and the fully
+/// qualified names of those that are not.
+fn resolve_requested_tables<'a>(
+    publication_catalog: &SubsourceCatalog<&'a PostgresTableDesc>,
+    requested_subsources: &[RequestedSubsource<'_, PostgresTableDesc>],
+    tables: Vec<UnresolvedItemName>,
+    option_name: &str,
+) -> Result<
+    (
+        Vec<(UnresolvedItemName, &'a PostgresTableDesc)>,
+        Vec<UnresolvedItemName>,
+    ),
+    PlanError,
+> {
+    let requested_oids: BTreeSet<_> = requested_subsources
+        .iter()
+        .map(|subsource| subsource.table.oid)
+        .collect();
+
+    let mut resolved = vec![];
+    let mut dangling = vec![];
+    for name in tables {
+        let (fully_qualified_name, desc) =
+            publication_catalog
+                .resolve(name)
+                .map_err(|e| PlanError::InvalidOptionValue {
+                    option_name: option_name.to_string(),
+                    err: Box::new(e),
+                })?;
+
+        if requested_oids.contains(&desc.oid) {
+            resolved.push((fully_qualified_name, *desc));
+        } else {
+            dangling.push(fully_qualified_name);
+        }
+    }
+
+    Ok((resolved, dangling))
 }
 
 pub(crate) fn generate_targeted_subsources(
@@ -164,6 +386,7 @@ pub(crate) fn generate_targeted_subsources(
     source_name: Option<ResolvedItemName>,
     validated_requested_subsources: Vec<RequestedSubsource<'_, PostgresTableDesc>>,
     mut text_cols_dict: BTreeMap<u32, BTreeSet<String>>,
+    include_cols_dict: &BTreeMap<u32, BTreeSet<String>>,
     publication_tables: &[PostgresTableDesc],
 ) -> Result<
     (
@@ -192,7 +415,13 @@ pub(crate) fn generate_targeted_subsources(
         // Figure out the schema of the subsource
         let mut columns = vec![];
         let text_cols_dict = text_cols_dict.remove(&table.oid);
+        let include_cols = include_cols_dict.get(&table.oid);
+        let is_included = |name: &String| include_cols.map_or(true, |names| names.contains(name));
         for c in table.columns.iter() {
+            // Columns that are not ingested don't need a supported type.
+            if !is_included(&c.name) {
+                continue;
+            }
             let name = Ident::new(c.name.clone())?;
             let ty = match &text_cols_dict {
                 Some(names) if names.contains(&c.name) => mz_pgrepr::Type::Text,
@@ -229,20 +458,22 @@ pub(crate) fn generate_targeted_subsources(
         }
 
         let mut constraints = vec![];
-        for key in table.keys.clone() {
+        'keys: for key in table.keys.clone() {
             let mut key_columns = vec![];
 
             for col_num in key.cols {
-                let ident = Ident::new(
-                    table
-                        .columns
-                        .iter()
-                        .find(|col| col.col_num == col_num)
-                        .expect("key exists as column")
-                        .name
-                        .clone(),
-                )?;
-                key_columns.push(ident);
+                let name = &table
+                    .columns
+                    .iter()
+                    .find(|col| col.col_num == col_num)
+                    .expect("key exists as column")
+                    .name;
+                // Keys are only known to hold if all of their columns are
+                // ingested.
+                if !is_included(name) {
+                    continue 'keys;
+                }
+                key_columns.push(Ident::new(name.clone())?);
             }
 
             let constraint = mz_sql_parser::ast::TableConstraint::Unique {
@@ -484,25 +715,31 @@ mod replica_identity {
     use crate::plan::PlanError;
     use crate::pure::PgSourcePurificationError;
 
-    /// Ensures that all provided OIDs are tables with `REPLICA IDENTITY FULL`.
+    /// Ensures that all provided OIDs are tables with `REPLICA IDENTITY FULL`,
+    /// except for those in `upsert_oids`, which may also use `REPLICA IDENTITY
+    /// DEFAULT`.
     pub async fn check_replica_identity_full(
         config: &Config,
         oids: Vec<Oid>,
+        upsert_oids: Vec<Oid>,
         ssh_tunnel_manager: &SshTunnelManager,
     ) -> Result<(), PlanError> {
         let client = config
             .connect("check_replica_identity_full", ssh_tunnel_manager)
             .await?;
 
-        let oids_len = oids.len();
-
-        let oids = Array::from_parts(
-            oids,
-            vec![Dimension {
-                len: i32::try_from(oids_len).expect("fewer than i32::MAX schemas"),
-                lower_bound: 0,
-            }],
-        );
+        let to_array = |oids: Vec<Oid>| {
+            let oids_len = oids.len();
+            Array::from_parts(
+                oids,
+                vec![Dimension {
+                    len: i32::try_from(oids_len).expect("fewer than i32::MAX schemas"),
+                    lower_bound: 0,
+                }],
+            )
+        };
+        let oids = to_array(oids);
+        let upsert_oids = to_array(upsert_oids);
 
         let mut invalid_replica_identity = client
             .query(
@@ -513,8 +750,12 @@ mod replica_identity {
                 (SELECT unnest($1::OID[]) AS oid) AS input
                 LEFT JOIN pg_class ON input.oid = pg_class.oid
             WHERE
-                relreplident != 'f' OR relreplident IS NULL;",
-                &[&oids],
+                relreplident IS NULL
+                OR (
+                    relreplident != 'f'
+                    AND NOT (relreplident = 'd' AND input.oid = ANY($2::OID[]))
+                );",
+                &[&oids, &upsert_oids],
             )
             .await
             .map_err(PostgresError::from)?
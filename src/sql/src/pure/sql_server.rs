@@ -0,0 +1,114 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server utilities for SQL purification.
+
+use std::collections::BTreeMap;
+
+use mz_sql_parser::ast::{
+    ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement, Ident,
+    IdentError, UnresolvedItemName, WithOptionValue,
+};
+use mz_sql_server_util::SqlServerTableDesc;
+
+use crate::catalog::SubsourceCatalog;
+use crate::names::Aug;
+use crate::plan::{PlanError, StatementContext};
+
+use super::RequestedSubsource;
+
+/// Returns the fully qualified `database.schema.table` name of `table`.
+pub(super) fn sql_server_upstream_name(
+    database: &str,
+    table: &SqlServerTableDesc,
+) -> Result<UnresolvedItemName, IdentError> {
+    Ok(UnresolvedItemName::qualified(&[
+        Ident::new(database)?,
+        Ident::new(&table.schema_name)?,
+        Ident::new(&table.name)?,
+    ]))
+}
+
+pub(super) fn derive_catalog_from_tables<'a>(
+    database: &'a str,
+    tables: &'a [SqlServerTableDesc],
+) -> Result<SubsourceCatalog<&'a SqlServerTableDesc>, PlanError> {
+    // An index from table name -> schema name -> database name -> SqlServerTableDesc
+    let mut tables_by_name = BTreeMap::new();
+    for table in tables.iter() {
+        tables_by_name
+            .entry(table.name.clone())
+            .or_insert_with(BTreeMap::new)
+            .entry(table.schema_name.clone())
+            .or_insert_with(BTreeMap::new)
+            .entry(database.to_string())
+            .or_insert(table);
+    }
+
+    Ok(SubsourceCatalog(tables_by_name))
+}
+
+pub(super) fn generate_targeted_subsources(
+    scx: &StatementContext,
+    validated_requested_subsources: Vec<RequestedSubsource<SqlServerTableDesc>>,
+) -> Result<Vec<CreateSubsourceStatement<Aug>>, PlanError> {
+    let mut subsources = vec![];
+
+    // Now that we have an explicit list of validated requested subsources we can create them
+    for RequestedSubsource {
+        upstream_name,
+        subsource_name,
+        table,
+    } in validated_requested_subsources.into_iter()
+    {
+        // Figure out the schema of the subsource
+        let mut columns = vec![];
+        for c in table.columns.iter() {
+            let Some(column_type) = &c.column_type else {
+                continue;
+            };
+            let name = Ident::new(&c.name)?;
+
+            let ty = mz_pgrepr::Type::from(&column_type.scalar_type);
+            let data_type = scx.resolve_type(ty)?;
+            let mut col_options = vec![];
+
+            if !column_type.nullable {
+                col_options.push(mz_sql_parser::ast::ColumnOptionDef {
+                    name: None,
+                    option: mz_sql_parser::ast::ColumnOption::NotNull,
+                });
+            }
+            columns.push(ColumnDef {
+                name,
+                data_type,
+                collation: None,
+                options: col_options,
+            });
+        }
+
+        // Create the subsource statement
+        let subsource = CreateSubsourceStatement {
+            name: subsource_name,
+            columns,
+            // We don't know the primary source's `GlobalId` yet; fill it in
+            // once we generate it.
+            of_source: None,
+            constraints: vec![],
+            if_not_exists: false,
+            with_options: vec![CreateSubsourceOption {
+                name: CreateSubsourceOptionName::ExternalReference,
+                value: Some(WithOptionValue::UnresolvedItemName(upstream_name)),
+            }],
+        };
+        subsources.push(subsource);
+    }
+
+    Ok(subsources)
+}
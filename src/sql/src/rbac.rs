@@ -13,7 +13,7 @@ use std::iter;
 use itertools::Itertools;
 use maplit::btreeset;
 use mz_controller_types::ClusterId;
-use mz_expr::CollectionPlan;
+use mz_expr::{CollectionPlan, MirScalarExpr, UnmaterializableFunc};
 use mz_ore::str::StrExt;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
 use mz_repr::role_id::RoleId;
@@ -506,6 +506,19 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
+        Plan::CreateSequence(plan::CreateSequencePlan {
+            name,
+            sequence: _,
+            if_not_exists: _,
+        }) => RbacRequirements {
+            privileges: vec![(
+                SystemObjectId::Object(name.qualifiers.clone().into()),
+                AclMode::CREATE,
+                role_id,
+            )],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::CreateSink(plan::CreateSinkPlan {
             name,
             sink,
@@ -736,8 +749,11 @@ fn generate_rbac_requirements(
             finishing: _,
             copy_to: _,
         }) => {
-            let mut privileges =
-                generate_read_privileges(catalog, source.depends_on().into_iter(), role_id);
+            let mut privileges = generate_read_privileges(
+                catalog,
+                source.depends_on().into_iter().chain(source.sequence_ids()),
+                role_id,
+            );
             if let Some(privilege) = generate_cluster_usage_privileges(
                 source.as_const().is_some(),
                 target_cluster_id,
@@ -775,19 +791,30 @@ fn generate_rbac_requirements(
         }
         Plan::CopyFrom(plan::CopyFromPlan {
             id,
+            source,
             columns: _,
             params: _,
-        }) => RbacRequirements {
-            privileges: vec![
+        }) => {
+            let mut privileges = vec![
                 (
                     SystemObjectId::Object(catalog.get_item(id).name().qualifiers.clone().into()),
                     AclMode::USAGE,
                     role_id,
                 ),
                 (SystemObjectId::Object(id.into()), AclMode::INSERT, role_id),
-            ],
-            ..Default::default()
-        },
+            ];
+            if let plan::CopyFromSource::Url { connection_id, .. } = source {
+                privileges.push((
+                    SystemObjectId::Object(connection_id.into()),
+                    AclMode::USAGE,
+                    role_id,
+                ));
+            }
+            RbacRequirements {
+                privileges,
+                ..Default::default()
+            }
+        }
         Plan::CopyTo(plan::CopyToPlan {
             select_plan,
             desc: _,
@@ -883,6 +910,7 @@ fn generate_rbac_requirements(
         Plan::Insert(plan::InsertPlan {
             id,
             values,
+            sequence_defaults,
             returning,
         }) => {
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
@@ -906,9 +934,13 @@ fn generate_rbac_requirements(
                 seen.insert((id.into(), role_id));
             }
 
+            let mut sequence_ids = values.sequence_ids();
+            sequence_ids.extend(mir_sequence_ids(
+                sequence_defaults.values().chain(returning.iter()),
+            ));
             privileges.extend_from_slice(&generate_read_privileges_inner(
                 catalog,
-                values.depends_on().into_iter(),
+                values.depends_on().into_iter().chain(sequence_ids),
                 role_id,
                 &mut seen,
             ));
@@ -1138,12 +1170,17 @@ fn generate_rbac_requirements(
             finishing: _,
             assignments,
             kind,
+            sequence_defaults,
             returning,
         }) => {
             let acl_mode = match kind {
                 MutationKind::Insert => AclMode::INSERT,
+                MutationKind::Upsert => AclMode::INSERT.union(AclMode::UPDATE),
                 MutationKind::Update => AclMode::UPDATE,
                 MutationKind::Delete => AclMode::DELETE,
+                MutationKind::Merge => AclMode::INSERT
+                    .union(AclMode::UPDATE)
+                    .union(AclMode::DELETE),
             };
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             let mut privileges = vec![
@@ -1173,9 +1210,16 @@ fn generate_rbac_requirements(
             //  PostgreSQL doesn't always do this.
             //  As a concrete example, we require SELECT and UPDATE privileges to execute
             //  `UPDATE t SET a = 42;`, while PostgreSQL only requires UPDATE privileges.
+            let mut sequence_ids = selection.sequence_ids();
+            sequence_ids.extend(mir_sequence_ids(
+                assignments
+                    .values()
+                    .chain(sequence_defaults.values())
+                    .chain(returning.iter()),
+            ));
             privileges.extend_from_slice(&generate_read_privileges_inner(
                 catalog,
-                selection.depends_on().into_iter(),
+                selection.depends_on().into_iter().chain(sequence_ids),
                 role_id,
                 &mut seen,
             ));
@@ -1346,6 +1390,7 @@ fn generate_rbac_requirements(
             }
         }
         Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::EmptyQuery
         | Plan::ShowAllVariables
@@ -1482,6 +1527,22 @@ fn generate_required_source_privileges(
     privileges
 }
 
+/// Returns the sequences whose functions `exprs` call.
+fn mir_sequence_ids<'a>(exprs: impl Iterator<Item = &'a MirScalarExpr>) -> BTreeSet<GlobalId> {
+    let mut ids = BTreeSet::new();
+    for expr in exprs {
+        expr.visit_pre(|e| {
+            if let MirScalarExpr::CallUnmaterializable(
+                UnmaterializableFunc::NextVal(id) | UnmaterializableFunc::CurrVal(id),
+            ) = e
+            {
+                ids.insert(*id);
+            }
+        });
+    }
+    ids
+}
+
 /// Generates all the privileges required to execute a read that includes the objects in `ids`.
 ///
 /// Not only do we need to validate that `role_id` has read privileges on all relations in `ids`,
@@ -1521,7 +1582,10 @@ fn generate_read_privileges_inner(
                 CatalogItemType::Table | CatalogItemType::Source => {
                     privileges.push((SystemObjectId::Object(id.into()), AclMode::SELECT, role_id));
                 }
-                CatalogItemType::Type | CatalogItemType::Secret | CatalogItemType::Connection => {
+                CatalogItemType::Type
+                | CatalogItemType::Secret
+                | CatalogItemType::Sequence
+                | CatalogItemType::Connection => {
                     privileges.push((SystemObjectId::Object(id.into()), AclMode::USAGE, role_id));
                 }
                 CatalogItemType::Sink | CatalogItemType::Index | CatalogItemType::Func => {}
@@ -1635,6 +1699,7 @@ pub const fn all_object_privileges(object_type: SystemObjectType) -> AclMode {
         SystemObjectType::Object(ObjectType::Cluster) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::ClusterReplica) => EMPTY_ACL_MODE,
         SystemObjectType::Object(ObjectType::Secret) => AclMode::USAGE,
+        SystemObjectType::Object(ObjectType::Sequence) => AclMode::USAGE,
         SystemObjectType::Object(ObjectType::Connection) => AclMode::USAGE,
         SystemObjectType::Object(ObjectType::Database) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::Schema) => USAGE_CREATE_ACL_MODE,
@@ -1664,6 +1729,7 @@ const fn default_builtin_object_acl_mode(object_type: ObjectType) -> AclMode {
         | ObjectType::Cluster
         | ObjectType::ClusterReplica
         | ObjectType::Secret
+        | ObjectType::Sequence
         | ObjectType::Connection
         | ObjectType::Database
         | ObjectType::Func => AclMode::empty(),
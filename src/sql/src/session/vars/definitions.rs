@@ -540,7 +540,7 @@ pub static MAX_COPY_FROM_SIZE: VarDefinition = VarDefinition::new(
     "max_copy_from_size",
     // 1 GiB, this limit is noted in the docs, if you change it make sure to update our docs.
     value!(u32; 1_073_741_824),
-    "The maximum size in bytes we buffer for COPY FROM statements (Materialize).",
+    "The maximum size in bytes we buffer for COPY FROM statements, and of each chunk of rows that COPY FROM a url inserts (Materialize).",
     false,
 );
 
@@ -1818,13 +1818,6 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
-    {
-        name: enable_table_check_constraint,
-        desc: "CREATE TABLE with a check constraint",
-        default: false,
-        internal: true,
-        enable_for_item_parsing: true,
-    },
     {
         name: enable_table_foreign_key,
         desc: "CREATE TABLE with a foreign key",
@@ -1972,6 +1965,34 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_sql_server_source,
+        desc: "Create a SQL Server connection or source",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_file_sources,
+        desc: "CREATE SOURCE ... FROM S3 CONNECTION",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_kinesis_sources,
+        desc: "CREATE SOURCE ... FROM KINESIS CONNECTION",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_local_file_sources,
+        desc: "CREATE SOURCE ... FROM LOCAL FILES, which reads the local filesystem of the cluster replica",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_load_generator_key_value,
         desc: "Create a LOAD GENERATOR KEY VALUE",
@@ -2050,6 +2071,13 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_copy_from_remote,
+        desc: "COPY ... FROM 's3://...'",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_session_timelines,
         desc: "strong session serializable isolation levels",
@@ -2085,6 +2113,20 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_kafka_sink_partition_by,
+        desc: "PARTITION BY option for Kafka sinks",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_kafka_sink_tombstones_option,
+        desc: "TOMBSTONES option for Kafka sinks",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
 );
 
 impl From<&super::SystemVars> for OptimizerFeatures {
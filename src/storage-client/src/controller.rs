@@ -405,6 +405,17 @@ pub trait StorageController: Debug {
         source_desc: SourceDesc,
     ) -> Result<(), StorageError<Self::Timestamp>>;
 
+    /// Alters the identified collection to use the provided [`SourceDesc`] and
+    /// each of the identified exports of the ingestion to use the correlated
+    /// [`RelationDesc`], then re-runs the ingestion so that the changes take
+    /// effect.
+    async fn alter_ingestion_export_descs(
+        &mut self,
+        ingestion_id: GlobalId,
+        source_desc: SourceDesc,
+        export_descs: BTreeMap<GlobalId, RelationDesc>,
+    ) -> Result<(), StorageError<Self::Timestamp>>;
+
     /// Alters each identified collection to use the correlated [`GenericSourceConnection`].
     async fn alter_ingestion_connections(
         &mut self,
@@ -273,43 +273,21 @@ pub async fn ensure_kafka_topic(
     .with_context(|| format!("Error creating topic {} for sink", topic))
 }
 
-/// Publish value and optional key schemas for a given topic.
+/// Publish a schema under the given subject.
 ///
 /// TODO(benesch): do we need to delete the Kafka topic if publishing the
 /// schema fails?
-pub async fn publish_kafka_schemas(
+pub async fn publish_kafka_schema(
     ccsr: mz_ccsr::Client,
-    topic: String,
-    key_schema: Option<String>,
-    key_schema_type: Option<mz_ccsr::SchemaType>,
-    value_schema: &str,
-    value_schema_type: mz_ccsr::SchemaType,
-) -> Result<(Option<i32>, i32), anyhow::Error> {
-    let value_schema_id = ccsr
-        .publish_schema(
-            &format!("{}-value", topic),
-            value_schema,
-            value_schema_type,
-            &[],
-        )
-        .await
-        .context("unable to publish value schema to registry in kafka sink")?;
-
-    let key_schema_id = if let Some(key_schema) = key_schema {
-        let key_schema_type =
-            key_schema_type.ok_or_else(|| anyhow!("expected schema type for key schema"))?;
-        Some(
-            async move {
-                ccsr.publish_schema(&format!("{}-key", topic), &key_schema, key_schema_type, &[])
-                    .await
-            }
-            .run_in_task(|| "publish_kafka_schemas".to_string())
+    subject: String,
+    schema: String,
+    schema_type: mz_ccsr::SchemaType,
+) -> Result<i32, anyhow::Error> {
+    let schema_id = async move {
+        ccsr.publish_schema(&subject, &schema, schema_type, &[])
             .await
-            .context("unable to publish key schema to registry in kafka sink")?,
-        )
-    } else {
-        None
-    };
-
-    Ok((key_schema_id, value_schema_id))
+    }
+    .run_in_task(|| "publish_kafka_schema".to_string())
+    .await?;
+    Ok(schema_id)
 }
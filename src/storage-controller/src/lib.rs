@@ -1155,6 +1155,48 @@ where
         Ok(())
     }
 
+    async fn alter_ingestion_export_descs(
+        &mut self,
+        ingestion_id: GlobalId,
+        source_desc: SourceDesc,
+        export_descs: BTreeMap<GlobalId, RelationDesc>,
+    ) -> Result<(), StorageError<Self::Timestamp>> {
+        // Validate first, to avoid corrupting state.
+        for export_id in export_descs.keys() {
+            match &self.collection(*export_id)?.description.data_source {
+                DataSource::IngestionExport {
+                    ingestion_id: export_ingestion_id,
+                    ..
+                } if *export_ingestion_id == ingestion_id => {}
+                o => {
+                    tracing::warn!(
+                        "{export_id} not DataSource::IngestionExport of {ingestion_id} but {o:#?}",
+                    );
+                    Err(StorageError::IdentifierInvalid(*export_id))?
+                }
+            }
+        }
+
+        self.alter_ingestion_source_desc(ingestion_id, source_desc)
+            .await?;
+
+        for (export_id, desc) in export_descs {
+            let collection = self
+                .collections
+                .get_mut(&export_id)
+                .expect("validated exists");
+            collection.description.desc = desc.clone();
+            collection.collection_metadata.relation_desc = desc;
+            tracing::debug!("altered {export_id}'s RelationDesc");
+        }
+
+        // Unlike `alter_ingestion_source_desc`, no new subsources are created
+        // that would run the ingestion for us, so we have to do it ourselves.
+        self.run_ingestion(ingestion_id)?;
+
+        Ok(())
+    }
+
     async fn alter_ingestion_connections(
         &mut self,
         source_connections: BTreeMap<GlobalId, GenericSourceConnection<InlinedConnection>>,
@@ -287,6 +287,7 @@ where
     let cfg = persist_clients.cfg().clone();
     let name = source_id.to_string();
     let desc = metadata.relation_desc.clone();
+    let arity = desc.arity();
     let filter_plan = map_filter_project.as_ref().map(|p| (*p).clone());
 
     let desc_transformer = match flow_control {
@@ -353,7 +354,7 @@ where
         listen_sleep,
         start_signal,
     );
-    let rows = decode_and_mfp(cfg, &fetched, &name, until, map_filter_project);
+    let rows = decode_and_mfp(cfg, &fetched, &name, arity, until, map_filter_project);
     (rows, token)
 }
 
@@ -380,10 +381,15 @@ fn filter_may_match(
     result.may_contain(Datum::True) || result.may_fail()
 }
 
+/// Decodes the rows of the fetched parts and applies `map_filter_project` to them.
+///
+/// Rows with fewer than `arity` columns were written before nullable columns were added to the
+/// schema of the collection and are padded with nulls.
 pub fn decode_and_mfp<G>(
     cfg: PersistConfig,
     fetched: &Stream<G, FetchedBlob<SourceData, (), Timestamp, Diff>>,
     name: &str,
+    arity: usize,
     until: Antichain<Timestamp>,
     mut map_filter_project: Option<&mut MfpPlan>,
 ) -> Stream<G, (Result<Row, DataflowError>, G::Timestamp, Diff)>
@@ -443,6 +449,7 @@ where
                     &name,
                     start_time,
                     yield_fn,
+                    arity,
                     &until,
                     map_filter_project.as_ref(),
                     &mut datum_vec,
@@ -462,6 +469,19 @@ where
     updates_stream
 }
 
+/// Pads `row` with nulls until it has `arity` columns.
+fn pad_row(row: Row, arity: usize) -> Row {
+    let len = row.iter().count();
+    if len >= arity {
+        return row;
+    }
+    let mut padded = Row::default();
+    let mut packer = padded.packer();
+    packer.extend(row.iter());
+    packer.extend(std::iter::repeat(Datum::Null).take(arity - len));
+    padded
+}
+
 /// Pending work to read from fetched parts
 struct PendingWork {
     /// The time at which the work should happen.
@@ -499,6 +519,7 @@ impl PendingWork {
         name: &str,
         start_time: Instant,
         yield_fn: YFn,
+        arity: usize,
         until: &Antichain<Timestamp>,
         map_filter_project: Option<&MfpPlan>,
         datum_vec: &mut DatumVec,
@@ -544,6 +565,9 @@ impl PendingWork {
                         *work += 1;
                         let arena = mz_repr::RowArena::new();
                         let mut datums_local = datum_vec.borrow_with(&row);
+                        if datums_local.len() < arity {
+                            datums_local.resize(arity, Datum::Null);
+                        }
                         for result in mfp.evaluate(
                             &mut datums_local,
                             &arena,
@@ -599,6 +623,7 @@ impl PendingWork {
                         drop(datums_local);
                         row_buf.replace(SourceData(Ok(row)));
                     } else {
+                        let row = pad_row(row, arity);
                         let mut emit_time = *self.capability.time();
                         emit_time.0 = time;
                         output.give_at(&self.capability, (Ok(row), emit_time, diff));
@@ -250,6 +250,7 @@ pub enum Connection<C: ConnectionAccess = InlinedConnection> {
     Aws(AwsConnection),
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<C>),
+    SqlServer(SqlServerConnection<C>),
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
@@ -264,6 +265,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
             Connection::Aws(aws) => Connection::Aws(aws),
             Connection::AwsPrivatelink(awspl) => Connection::AwsPrivatelink(awspl),
             Connection::MySql(mysql) => Connection::MySql(mysql.into_inline_connection(r)),
+            Connection::SqlServer(sql_server) => {
+                Connection::SqlServer(sql_server.into_inline_connection(r))
+            }
         }
     }
 }
@@ -279,6 +283,7 @@ impl<C: ConnectionAccess> Connection<C> {
             Connection::Aws(conn) => conn.validate_by_default(),
             Connection::AwsPrivatelink(conn) => conn.validate_by_default(),
             Connection::MySql(conn) => conn.validate_by_default(),
+            Connection::SqlServer(conn) => conn.validate_by_default(),
         }
     }
 }
@@ -298,6 +303,7 @@ impl Connection<InlinedConnection> {
             Connection::Aws(conn) => conn.validate(id, storage_configuration).await?,
             Connection::AwsPrivatelink(conn) => conn.validate(id, storage_configuration).await?,
             Connection::MySql(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::SqlServer(conn) => conn.validate(id, storage_configuration).await?,
         }
         Ok(())
     }
@@ -323,6 +329,20 @@ impl Connection<InlinedConnection> {
         }
     }
 
+    pub fn unwrap_sql_server(self) -> <InlinedConnection as ConnectionAccess>::SqlServer {
+        match self {
+            Self::SqlServer(conn) => conn,
+            o => unreachable!("{o:?} is not a SQL Server connection"),
+        }
+    }
+
+    pub fn unwrap_aws(self) -> <InlinedConnection as ConnectionAccess>::Aws {
+        match self {
+            Self::Aws(conn) => conn,
+            o => unreachable!("{o:?} is not an AWS connection"),
+        }
+    }
+
     pub fn unwrap_ssh(self) -> <InlinedConnection as ConnectionAccess>::Ssh {
         match self {
             Self::Ssh(conn) => conn,
@@ -375,6 +395,7 @@ impl<C: ConnectionAccess> AlterCompatible for Connection<C> {
             (Self::Kafka(s), Self::Kafka(o)) => s.alter_compatible(id, o),
             (Self::Postgres(s), Self::Postgres(o)) => s.alter_compatible(id, o),
             (Self::MySql(s), Self::MySql(o)) => s.alter_compatible(id, o),
+            (Self::SqlServer(s), Self::SqlServer(o)) => s.alter_compatible(id, o),
             _ => {
                 tracing::warn!(
                     "Connection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
@@ -1888,6 +1909,276 @@ impl<C: ConnectionAccess> AlterCompatible for MySqlConnection<C> {
     }
 }
 
+/// Specifies how to encrypt the connection to a SQL Server.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SqlServerEncryptionMode {
+    /// Do not encrypt the connection.
+    Disabled,
+    /// Encrypt the connection, but do not verify the server's certificate.
+    Required,
+    /// Encrypt the connection and verify the server's certificate and identity.
+    Verify,
+}
+
+impl RustType<i32> for SqlServerEncryptionMode {
+    fn into_proto(&self) -> i32 {
+        match self {
+            SqlServerEncryptionMode::Disabled => ProtoSqlServerEncryptionMode::Disabled.into(),
+            SqlServerEncryptionMode::Required => ProtoSqlServerEncryptionMode::Required.into(),
+            SqlServerEncryptionMode::Verify => ProtoSqlServerEncryptionMode::Verify.into(),
+        }
+    }
+
+    fn from_proto(proto: i32) -> Result<Self, TryFromProtoError> {
+        Ok(match ProtoSqlServerEncryptionMode::from_i32(proto) {
+            Some(ProtoSqlServerEncryptionMode::Disabled) => SqlServerEncryptionMode::Disabled,
+            Some(ProtoSqlServerEncryptionMode::Required) => SqlServerEncryptionMode::Required,
+            Some(ProtoSqlServerEncryptionMode::Verify) => SqlServerEncryptionMode::Verify,
+            None => {
+                return Err(TryFromProtoError::UnknownEnumVariant(
+                    "encryption".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+pub fn any_sql_server_encryption_mode() -> impl Strategy<Value = SqlServerEncryptionMode> {
+    proptest::sample::select(vec![
+        SqlServerEncryptionMode::Disabled,
+        SqlServerEncryptionMode::Required,
+        SqlServerEncryptionMode::Verify,
+    ])
+}
+
+/// A connection to a SQL Server database.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The name of the database to connect to.
+    pub database: String,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// A tunnel through which to route traffic.
+    pub tunnel: Tunnel<C>,
+    /// Whether to encrypt the connection and verify the server's certificate.
+    #[proptest(strategy = "any_sql_server_encryption_mode()")]
+    pub encryption: SqlServerEncryptionMode,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<SqlServerConnection, R>
+    for SqlServerConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> SqlServerConnection {
+        let SqlServerConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel,
+            encryption,
+        } = self;
+
+        SqlServerConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel: tunnel.into_inline_connection(r),
+            encryption,
+        }
+    }
+}
+
+impl<C: ConnectionAccess> SqlServerConnection<C> {
+    fn validate_by_default(&self) -> bool {
+        true
+    }
+}
+
+impl SqlServerConnection<InlinedConnection> {
+    pub async fn config(
+        &self,
+        secrets_reader: &Arc<dyn mz_secrets::SecretsReader>,
+        storage_configuration: &StorageConfiguration,
+        in_task: InTask,
+    ) -> Result<mz_sql_server_util::Config, anyhow::Error> {
+        let mut config = tiberius::Config::new();
+        config.host(&self.host);
+        config.port(self.port);
+        config.database(&self.database);
+
+        let user = self.user.get_string(in_task, secrets_reader).await?;
+        let password = match self.password {
+            Some(password) => {
+                secrets_reader
+                    .read_string_in_task_if(in_task, password)
+                    .await?
+            }
+            None => String::new(),
+        };
+        config.authentication(tiberius::AuthMethod::sql_server(user, password));
+
+        match self.encryption {
+            SqlServerEncryptionMode::Disabled => {
+                config.encryption(tiberius::EncryptionLevel::NotSupported)
+            }
+            SqlServerEncryptionMode::Required => {
+                config.encryption(tiberius::EncryptionLevel::Required);
+                config.trust_cert();
+            }
+            SqlServerEncryptionMode::Verify => {
+                config.encryption(tiberius::EncryptionLevel::Required)
+            }
+        }
+
+        let tunnel = match &self.tunnel {
+            Tunnel::Direct => {
+                // Ensure any host we connect to is resolved to an external address.
+                let resolved = resolve_address(
+                    &self.host,
+                    ENFORCE_EXTERNAL_ADDRESSES.get(storage_configuration.config_set()),
+                )
+                .await?;
+                mz_sql_server_util::TunnelConfig::Direct {
+                    resolved_ips: Some(resolved),
+                }
+            }
+            Tunnel::Ssh(SshTunnel {
+                connection_id,
+                connection,
+            }) => {
+                let secret = secrets_reader
+                    .read_in_task_if(in_task, *connection_id)
+                    .await?;
+                let key_pair = SshKeyPair::from_bytes(&secret)?;
+                // Ensure any ssh-bastion host we connect to is resolved to an external address.
+                let resolved = resolve_address(
+                    &connection.host,
+                    ENFORCE_EXTERNAL_ADDRESSES.get(storage_configuration.config_set()),
+                )
+                .await?;
+                mz_sql_server_util::TunnelConfig::Ssh {
+                    config: SshTunnelConfig {
+                        host: resolved
+                            .iter()
+                            .map(|a| a.to_string())
+                            .collect::<BTreeSet<_>>(),
+                        port: connection.port,
+                        user: connection.user.clone(),
+                        key_pair,
+                    },
+                }
+            }
+            Tunnel::AwsPrivatelink(connection) => {
+                assert!(connection.port.is_none());
+                mz_sql_server_util::TunnelConfig::AwsPrivatelink {
+                    connection_id: connection.connection_id,
+                }
+            }
+        };
+
+        Ok(mz_sql_server_util::Config::new(
+            config,
+            self.host.clone(),
+            self.port,
+            tunnel,
+            storage_configuration.parameters.ssh_timeout_config,
+            in_task,
+        ))
+    }
+
+    async fn validate(
+        &self,
+        _id: GlobalId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        let config = self
+            .config(
+                &storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+                // We are in a normal tokio context during validation, already.
+                InTask::No,
+            )
+            .await?;
+        let conn = config
+            .connect(
+                "connection validation",
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+            )
+            .await?;
+        conn.close().await?;
+        Ok(())
+    }
+}
+
+impl RustType<ProtoSqlServerConnection> for SqlServerConnection {
+    fn into_proto(&self) -> ProtoSqlServerConnection {
+        ProtoSqlServerConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            encryption: self.encryption.into_proto(),
+            tunnel: Some(self.tunnel.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoSqlServerConnection::user")?,
+            password: proto.password.into_rust()?,
+            tunnel: proto
+                .tunnel
+                .into_rust_if_some("ProtoSqlServerConnection::tunnel")?,
+            encryption: proto.encryption.into_rust()?,
+        })
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for SqlServerConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        let SqlServerConnection {
+            tunnel,
+            // All non-tunnel options may change arbitrarily
+            host: _,
+            port: _,
+            database: _,
+            user: _,
+            password: _,
+            encryption: _,
+        } = self;
+
+        let compatibility_checks = [(tunnel.alter_compatible(id, &other.tunnel).is_ok(), "tunnel")];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "SqlServerConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A connection to a SSH tunnel.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SshConnection {
@@ -102,6 +102,24 @@ pub trait ConnectionAccess:
         + Serialize
         + for<'a> Deserialize<'a>
         + AlterCompatible;
+    type Aws: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>
+        + AlterCompatible;
+    type SqlServer: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>
+        + AlterCompatible;
 }
 
 /// Expresses that the struct contains references to connections. Use a
@@ -116,6 +134,8 @@ impl ConnectionAccess for ReferencedConnection {
     type Ssh = GlobalId;
     type Csr = GlobalId;
     type MySql = GlobalId;
+    type Aws = GlobalId;
+    type SqlServer = GlobalId;
 }
 
 /// Expresses that the struct contains an inlined definition of a connection.
@@ -128,4 +148,6 @@ impl ConnectionAccess for InlinedConnection {
     type Ssh = super::SshConnection;
     type Csr = super::CsrConnection;
     type MySql = super::MySqlConnection;
+    type Aws = super::aws::AwsConnection;
+    type SqlServer = super::SqlServerConnection;
 }
@@ -94,6 +94,33 @@ pub const STORAGE_ROCKSDB_CLEANUP_TRIES: Config<usize> = Config::new(
     "How many times to try to cleanup old RocksDB DB's on disk before giving up.",
 );
 
+/// The number of blocks of a PostgreSQL table that a single chunk of its snapshot reads. The
+/// chunks of a snapshot are distributed across workers. A value of 0 disables chunking.
+pub const PG_SNAPSHOT_CHUNK_BLOCKS: Config<usize> = Config::new(
+    "storage_pg_snapshot_chunk_blocks",
+    8192,
+    "The number of blocks of a PostgreSQL table that a single chunk of its snapshot reads.",
+);
+
+/// The number of bytes of a chunk of a PostgreSQL snapshot that are buffered in memory so that the
+/// chunk can be retried. Chunks that are larger are streamed once they exceed this size, and are
+/// no longer retried if they fail afterwards.
+pub const PG_SNAPSHOT_CHUNK_BUFFER_BYTES: Config<usize> = Config::new(
+    "storage_pg_snapshot_chunk_buffer_bytes",
+    128 * 1024 * 1024,
+    "The number of bytes of a chunk of a PostgreSQL snapshot that are buffered in memory so that \
+        the chunk can be retried.",
+);
+
+/// How many times to retry reading a chunk of a PostgreSQL snapshot before restarting the
+/// snapshot.
+pub const PG_SNAPSHOT_CHUNK_RETRIES: Config<usize> = Config::new(
+    "storage_pg_snapshot_chunk_retries",
+    3,
+    "How many times to retry reading a chunk of a PostgreSQL snapshot before restarting the \
+        snapshot.",
+);
+
 /// Adds the full set of all storage `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -104,4 +131,7 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&STORAGE_UPSERT_PREVENT_SNAPSHOT_BUFFERING)
         .add(&STORAGE_UPSERT_MAX_SNAPSHOT_BATCH_BUFFERING)
         .add(&STORAGE_ROCKSDB_CLEANUP_TRIES)
+        .add(&PG_SNAPSHOT_CHUNK_BLOCKS)
+        .add(&PG_SNAPSHOT_CHUNK_BUFFER_BYTES)
+        .add(&PG_SNAPSHOT_CHUNK_RETRIES)
 }
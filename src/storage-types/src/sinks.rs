@@ -13,6 +13,7 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 
 use mz_dyncfg::ConfigSet;
+use mz_expr::MirScalarExpr;
 use mz_persist_types::ShardId;
 use mz_pgcopy::CopyFormatParams;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
@@ -391,6 +392,14 @@ pub struct KafkaSinkConnection<C: ConnectionAccess = InlinedConnection> {
     pub compression_type: KafkaSinkCompressionType,
     pub progress_group_id: KafkaIdStyle,
     pub transactional_id: KafkaIdStyle,
+    /// An expression over the columns of `value_desc` that determines the
+    /// partition of each message. If absent, messages are partitioned by the
+    /// hash of their key.
+    pub partition_by: Option<MirScalarExpr>,
+    /// Whether deletions are emitted as tombstones. If false, deletions are
+    /// instead emitted as the deleted value with a `materialize-deleted`
+    /// header. Only meaningful for the upsert envelope.
+    pub tombstones: bool,
 }
 
 impl KafkaSinkConnection {
@@ -481,6 +490,8 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
             compression_type,
             progress_group_id,
             transactional_id,
+            partition_by,
+            tombstones,
         } = self;
 
         let compatibility_checks = [
@@ -513,6 +524,8 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
                 transactional_id == &other.transactional_id,
                 "transactional_id",
             ),
+            (partition_by == &other.partition_by, "partition_by"),
+            (tombstones == &other.tombstones, "tombstones"),
         ];
         for (compatible, field) in compatibility_checks {
             if !compatible {
@@ -546,6 +559,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             compression_type,
             progress_group_id,
             transactional_id,
+            partition_by,
+            tombstones,
         } = self;
         KafkaSinkConnection {
             connection_id,
@@ -559,6 +574,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             compression_type,
             progress_group_id,
             transactional_id,
+            partition_by,
+            tombstones,
         }
     }
 }
@@ -620,6 +637,8 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
             }),
             progress_group_id: Some(self.progress_group_id.into_proto()),
             transactional_id: Some(self.transactional_id.into_proto()),
+            partition_by: self.partition_by.into_proto(),
+            tombstones: self.tombstones,
         }
     }
 
@@ -660,25 +679,150 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
             transactional_id: proto
                 .transactional_id
                 .into_rust_if_some("ProtoKafkaSinkConnectionV2::transactional_id")?,
+            partition_by: proto.partition_by.into_rust()?,
+            tombstones: proto.tombstones,
         })
     }
 }
 
+/// The formats of the keys and values of the messages written by a Kafka sink.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub enum KafkaSinkFormat<C: ConnectionAccess = InlinedConnection> {
+pub struct KafkaSinkFormat<C: ConnectionAccess = InlinedConnection> {
+    /// The format of message keys. Present if and only if the sink has a key.
+    pub key_format: Option<KafkaSinkFormatType<C>>,
+    /// The format of message values.
+    pub value_format: KafkaSinkFormatType<C>,
+}
+
+impl<C: ConnectionAccess> KafkaSinkFormat<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let KafkaSinkFormat {
+            key_format,
+            value_format,
+        } = self;
+
+        let compatibility_checks = [
+            (
+                match (key_format, &other.key_format) {
+                    (Some(s), Some(o)) => s.alter_compatible(id, o).is_ok(),
+                    (s, o) => s == o,
+                },
+                "key_format",
+            ),
+            (
+                value_format
+                    .alter_compatible(id, &other.value_format)
+                    .is_ok(),
+                "value_format",
+            ),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "KafkaSinkFormat incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormat, R>
+    for KafkaSinkFormat<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> KafkaSinkFormat {
+        KafkaSinkFormat {
+            key_format: self
+                .key_format
+                .map(|key_format| key_format.into_inline_connection(&r)),
+            value_format: self.value_format.into_inline_connection(r),
+        }
+    }
+}
+
+impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
+    fn into_proto(&self) -> ProtoKafkaSinkFormat {
+        ProtoKafkaSinkFormat {
+            key_format: self.key_format.into_proto(),
+            value_format: Some(self.value_format.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkFormat) -> Result<Self, TryFromProtoError> {
+        Ok(KafkaSinkFormat {
+            key_format: proto.key_format.into_rust()?,
+            value_format: proto
+                .value_format
+                .into_rust_if_some("ProtoKafkaSinkFormat::value_format")?,
+        })
+    }
+}
+
+/// The format of either the keys or the values of the messages written by a
+/// Kafka sink.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkFormatType<C: ConnectionAccess = InlinedConnection> {
+    /// Avro with a schema published to a schema registry.
     Avro {
-        key_schema: Option<String>,
-        value_schema: String,
+        schema: String,
         csr_connection: C::Csr,
     },
+    /// Avro without a schema registry. Messages are encoded as plain Avro
+    /// datums, without the Confluent wire format header.
+    AvroInline {
+        schema: String,
+    },
     Json,
+    /// JSON with a JSON Schema published to a schema registry.
+    JsonSchema {
+        schema: String,
+        csr_connection: C::Csr,
+    },
+    /// Protobuf using a message from a user-provided `FileDescriptorSet`.
+    Protobuf {
+        /// The encoded `FileDescriptorSet`.
+        descriptors: Vec<u8>,
+        /// The fully qualified name of the message to encode rows as.
+        message_name: String,
+    },
+    /// The Postgres text representation of a single column.
+    Text,
+    /// The raw bytes of a single `bytea` column.
+    Bytes,
 }
 
-impl<C: ConnectionAccess> KafkaSinkFormat<C> {
+impl<C: ConnectionAccess> KafkaSinkFormatType<C> {
     pub fn get_format_name(&self) -> &str {
         match self {
-            Self::Avro { .. } => "avro",
-            Self::Json => "json",
+            Self::Avro { .. } | Self::AvroInline { .. } => "avro",
+            Self::Json | Self::JsonSchema { .. } => "json",
+            Self::Protobuf { .. } => "protobuf",
+            Self::Text => "text",
+            Self::Bytes => "bytes",
+        }
+    }
+
+    /// Returns the schema registry connection the format publishes its schema
+    /// to, if any.
+    pub fn csr_connection(&self) -> Option<&C::Csr> {
+        match self {
+            Self::Avro { csr_connection, .. } | Self::JsonSchema { csr_connection, .. } => {
+                Some(csr_connection)
+            }
+            Self::AvroInline { .. }
+            | Self::Json
+            | Self::Protobuf { .. }
+            | Self::Text
+            | Self::Bytes => None,
         }
     }
 
@@ -690,19 +834,26 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
         match (self, other) {
             (
                 Self::Avro {
-                    key_schema,
-                    value_schema,
+                    schema,
                     csr_connection,
                 },
                 Self::Avro {
-                    key_schema: other_key_schema,
-                    value_schema: other_value_schema,
+                    schema: other_schema,
+                    csr_connection: other_csr_connection,
+                },
+            )
+            | (
+                Self::JsonSchema {
+                    schema,
+                    csr_connection,
+                },
+                Self::JsonSchema {
+                    schema: other_schema,
                     csr_connection: other_csr_connection,
                 },
             ) => {
                 let compatibility_checks = [
-                    (key_schema == other_key_schema, "key_schema"),
-                    (value_schema == other_value_schema, "value_schema"),
+                    (schema == other_schema, "schema"),
                     (
                         csr_connection
                             .alter_compatible(id, other_csr_connection)
@@ -713,7 +864,7 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                 for (compatible, field) in compatibility_checks {
                     if !compatible {
                         tracing::warn!(
-                            "KafkaSinkAvroFormatState::Avro incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                            "KafkaSinkFormatType incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
                             self,
                             other
                         );
@@ -725,7 +876,7 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
             (s, o) => {
                 if s != o {
                     tracing::warn!(
-                        "KafkaSinkFormat incompatible\nself:\n{:#?}\n\nother:{:#?}",
+                        "KafkaSinkFormatType incompatible\nself:\n{:#?}\n\nother:{:#?}",
                         s,
                         o
                     );
@@ -738,59 +889,109 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
     }
 }
 
-impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormat, R>
-    for KafkaSinkFormat<ReferencedConnection>
+impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormatType, R>
+    for KafkaSinkFormatType<ReferencedConnection>
 {
-    fn into_inline_connection(self, r: R) -> KafkaSinkFormat {
+    fn into_inline_connection(self, r: R) -> KafkaSinkFormatType {
         match self {
             Self::Avro {
-                key_schema,
-                value_schema,
+                schema,
                 csr_connection,
-            } => KafkaSinkFormat::Avro {
-                key_schema,
-                value_schema,
+            } => KafkaSinkFormatType::Avro {
+                schema,
                 csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
             },
-            Self::Json => KafkaSinkFormat::Json,
+            Self::AvroInline { schema } => KafkaSinkFormatType::AvroInline { schema },
+            Self::Json => KafkaSinkFormatType::Json,
+            Self::JsonSchema {
+                schema,
+                csr_connection,
+            } => KafkaSinkFormatType::JsonSchema {
+                schema,
+                csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
+            },
+            Self::Protobuf {
+                descriptors,
+                message_name,
+            } => KafkaSinkFormatType::Protobuf {
+                descriptors,
+                message_name,
+            },
+            Self::Text => KafkaSinkFormatType::Text,
+            Self::Bytes => KafkaSinkFormatType::Bytes,
         }
     }
 }
 
-impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
-    fn into_proto(&self) -> ProtoKafkaSinkFormat {
-        use proto_kafka_sink_format::Kind;
-        ProtoKafkaSinkFormat {
+impl RustType<ProtoKafkaSinkFormatType> for KafkaSinkFormatType {
+    fn into_proto(&self) -> ProtoKafkaSinkFormatType {
+        use proto_kafka_sink_format_type::Kind;
+        ProtoKafkaSinkFormatType {
             kind: Some(match self {
                 Self::Avro {
-                    key_schema,
-                    value_schema,
+                    schema,
                     csr_connection,
-                } => Kind::Avro(proto_kafka_sink_format::ProtoKafkaSinkAvroFormat {
-                    key_schema: key_schema.clone(),
-                    value_schema: value_schema.clone(),
+                } => Kind::Avro(proto_kafka_sink_format_type::ProtoKafkaSinkAvroFormat {
+                    schema: schema.clone(),
                     csr_connection: Some(csr_connection.into_proto()),
                 }),
+                Self::AvroInline { schema } => Kind::AvroInline(
+                    proto_kafka_sink_format_type::ProtoKafkaSinkAvroInlineFormat {
+                        schema: schema.clone(),
+                    },
+                ),
                 Self::Json => Kind::Json(()),
+                Self::JsonSchema {
+                    schema,
+                    csr_connection,
+                } => Kind::JsonSchema(
+                    proto_kafka_sink_format_type::ProtoKafkaSinkJsonSchemaFormat {
+                        schema: schema.clone(),
+                        csr_connection: Some(csr_connection.into_proto()),
+                    },
+                ),
+                Self::Protobuf {
+                    descriptors,
+                    message_name,
+                } => Kind::Protobuf(proto_kafka_sink_format_type::ProtoKafkaSinkProtobufFormat {
+                    descriptors: descriptors.clone(),
+                    message_name: message_name.clone(),
+                }),
+                Self::Text => Kind::Text(()),
+                Self::Bytes => Kind::Bytes(()),
             }),
         }
     }
 
-    fn from_proto(proto: ProtoKafkaSinkFormat) -> Result<Self, TryFromProtoError> {
-        use proto_kafka_sink_format::Kind;
+    fn from_proto(proto: ProtoKafkaSinkFormatType) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_format_type::Kind;
         let kind = proto
             .kind
-            .ok_or_else(|| TryFromProtoError::missing_field("ProtoKafkaSinkFormat::kind"))?;
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoKafkaSinkFormatType::kind"))?;
 
         Ok(match kind {
             Kind::Avro(proto) => Self::Avro {
-                key_schema: proto.key_schema,
-                value_schema: proto.value_schema,
+                schema: proto.schema,
                 csr_connection: proto
                     .csr_connection
                     .into_rust_if_some("ProtoKafkaSinkAvroFormat::csr_connection")?,
             },
+            Kind::AvroInline(proto) => Self::AvroInline {
+                schema: proto.schema,
+            },
             Kind::Json(()) => Self::Json,
+            Kind::JsonSchema(proto) => Self::JsonSchema {
+                schema: proto.schema,
+                csr_connection: proto
+                    .csr_connection
+                    .into_rust_if_some("ProtoKafkaSinkJsonSchemaFormat::csr_connection")?,
+            },
+            Kind::Protobuf(proto) => Self::Protobuf {
+                descriptors: proto.descriptors,
+                message_name: proto.message_name,
+            },
+            Kind::Text(()) => Self::Text,
+            Kind::Bytes(()) => Self::Bytes,
         })
     }
 }
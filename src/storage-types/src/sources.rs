@@ -55,16 +55,22 @@ use crate::AlterCompatible;
 
 pub mod encoding;
 pub mod envelope;
+pub mod file;
 pub mod kafka;
+pub mod kinesis;
 pub mod load_generator;
 pub mod mysql;
 pub mod postgres;
+pub mod sql_server;
 
 pub use crate::sources::envelope::SourceEnvelope;
+pub use crate::sources::file::FileSourceConnection;
 pub use crate::sources::kafka::KafkaSourceConnection;
+pub use crate::sources::kinesis::KinesisSourceConnection;
 pub use crate::sources::load_generator::LoadGeneratorSourceConnection;
 pub use crate::sources::mysql::MySqlSourceConnection;
 pub use crate::sources::postgres::PostgresSourceConnection;
+pub use crate::sources::sql_server::SqlServerSourceConnection;
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.sources.rs"));
 
@@ -688,6 +694,7 @@ pub struct SourceDesc<C: ConnectionAccess = InlinedConnection> {
     pub encoding: Option<encoding::SourceDataEncoding<C>>,
     pub envelope: SourceEnvelope,
     pub timestamp_interval: Duration,
+    pub decode_error_policy: encoding::DecodeErrorPolicy,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
@@ -699,6 +706,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
             encoding,
             envelope,
             timestamp_interval,
+            decode_error_policy,
         } = self;
 
         SourceDesc {
@@ -706,6 +714,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
             encoding: encoding.map(|e| e.into_inline_connection(r)),
             envelope,
             timestamp_interval,
+            decode_error_policy,
         }
     }
 }
@@ -717,6 +726,7 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             encoding: self.encoding.into_proto(),
             envelope: Some(self.envelope.into_proto()),
             timestamp_interval: Some(self.timestamp_interval.into_proto()),
+            decode_error_policy: self.decode_error_policy.into_proto(),
         }
     }
 
@@ -732,6 +742,7 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             timestamp_interval: proto
                 .timestamp_interval
                 .into_rust_if_some("ProtoSourceDesc::timestamp_interval")?,
+            decode_error_policy: proto.decode_error_policy.into_rust()?,
         })
     }
 }
@@ -755,11 +766,19 @@ impl<C: ConnectionAccess> SourceDesc<C> {
                 connection: GenericSourceConnection::MySql(_),
                 ..
             } => false,
+            // SQL Server can produce retractions (deletes)
+            SourceDesc {
+                connection: GenericSourceConnection::SqlServer(_),
+                ..
+            } => false,
             // Upsert and CdcV2 may produce retractions.
             SourceDesc {
                 envelope: SourceEnvelope::Upsert(_) | SourceEnvelope::CdcV2,
                 connection:
-                    GenericSourceConnection::Kafka(_) | GenericSourceConnection::LoadGenerator(_),
+                    GenericSourceConnection::Kafka(_)
+                    | GenericSourceConnection::LoadGenerator(_)
+                    | GenericSourceConnection::File(_)
+                    | GenericSourceConnection::Kinesis(_),
                 ..
             } => false,
             // Loadgen can produce retractions (deletes)
@@ -793,6 +812,7 @@ impl<C: ConnectionAccess> AlterCompatible for SourceDesc<C> {
             encoding,
             envelope,
             timestamp_interval,
+            decode_error_policy,
         } = &self;
 
         let compatibility_checks = [
@@ -812,6 +832,10 @@ impl<C: ConnectionAccess> AlterCompatible for SourceDesc<C> {
                 timestamp_interval == &other.timestamp_interval,
                 "timestamp_interval",
             ),
+            (
+                decode_error_policy == &other.decode_error_policy,
+                "decode_error_policy",
+            ),
         ];
 
         for (compatible, field) in compatibility_checks {
@@ -836,6 +860,9 @@ pub enum GenericSourceConnection<C: ConnectionAccess = InlinedConnection> {
     Postgres(PostgresSourceConnection<C>),
     MySql(MySqlSourceConnection<C>),
     LoadGenerator(LoadGeneratorSourceConnection),
+    File(FileSourceConnection<C>),
+    Kinesis(KinesisSourceConnection<C>),
+    SqlServer(SqlServerSourceConnection<C>),
 }
 
 impl<C: ConnectionAccess> From<KafkaSourceConnection<C>> for GenericSourceConnection<C> {
@@ -862,6 +889,24 @@ impl<C: ConnectionAccess> From<LoadGeneratorSourceConnection> for GenericSourceC
     }
 }
 
+impl<C: ConnectionAccess> From<FileSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: FileSourceConnection<C>) -> Self {
+        Self::File(conn)
+    }
+}
+
+impl<C: ConnectionAccess> From<KinesisSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: KinesisSourceConnection<C>) -> Self {
+        Self::Kinesis(conn)
+    }
+}
+
+impl<C: ConnectionAccess> From<SqlServerSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: SqlServerSourceConnection<C>) -> Self {
+        Self::SqlServer(conn)
+    }
+}
+
 impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
     for GenericSourceConnection<ReferencedConnection>
 {
@@ -879,6 +924,15 @@ impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
             GenericSourceConnection::LoadGenerator(lg) => {
                 GenericSourceConnection::LoadGenerator(lg)
             }
+            GenericSourceConnection::File(file) => {
+                GenericSourceConnection::File(file.into_inline_connection(r))
+            }
+            GenericSourceConnection::Kinesis(kinesis) => {
+                GenericSourceConnection::Kinesis(kinesis.into_inline_connection(r))
+            }
+            GenericSourceConnection::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_inline_connection(r))
+            }
         }
     }
 }
@@ -890,6 +944,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.name(),
             Self::MySql(conn) => conn.name(),
             Self::LoadGenerator(conn) => conn.name(),
+            Self::File(conn) => conn.name(),
+            Self::Kinesis(conn) => conn.name(),
+            Self::SqlServer(conn) => conn.name(),
         }
     }
 
@@ -899,6 +956,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.upstream_name(),
             Self::MySql(conn) => conn.upstream_name(),
             Self::LoadGenerator(conn) => conn.upstream_name(),
+            Self::File(conn) => conn.upstream_name(),
+            Self::Kinesis(conn) => conn.upstream_name(),
+            Self::SqlServer(conn) => conn.upstream_name(),
         }
     }
 
@@ -908,6 +968,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.key_desc(),
             Self::MySql(conn) => conn.key_desc(),
             Self::LoadGenerator(conn) => conn.key_desc(),
+            Self::File(conn) => conn.key_desc(),
+            Self::Kinesis(conn) => conn.key_desc(),
+            Self::SqlServer(conn) => conn.key_desc(),
         }
     }
 
@@ -917,6 +980,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.value_desc(),
             Self::MySql(conn) => conn.value_desc(),
             Self::LoadGenerator(conn) => conn.value_desc(),
+            Self::File(conn) => conn.value_desc(),
+            Self::Kinesis(conn) => conn.value_desc(),
+            Self::SqlServer(conn) => conn.value_desc(),
         }
     }
 
@@ -926,6 +992,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.timestamp_desc(),
             Self::MySql(conn) => conn.timestamp_desc(),
             Self::LoadGenerator(conn) => conn.timestamp_desc(),
+            Self::File(conn) => conn.timestamp_desc(),
+            Self::Kinesis(conn) => conn.timestamp_desc(),
+            Self::SqlServer(conn) => conn.timestamp_desc(),
         }
     }
 
@@ -935,6 +1004,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.connection_id(),
             Self::MySql(conn) => conn.connection_id(),
             Self::LoadGenerator(conn) => conn.connection_id(),
+            Self::File(conn) => conn.connection_id(),
+            Self::Kinesis(conn) => conn.connection_id(),
+            Self::SqlServer(conn) => conn.connection_id(),
         }
     }
 
@@ -944,6 +1016,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.metadata_columns(),
             Self::MySql(conn) => conn.metadata_columns(),
             Self::LoadGenerator(conn) => conn.metadata_columns(),
+            Self::File(conn) => conn.metadata_columns(),
+            Self::Kinesis(conn) => conn.metadata_columns(),
+            Self::SqlServer(conn) => conn.metadata_columns(),
         }
     }
 
@@ -953,6 +1028,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.output_idx_for_name(name),
             Self::MySql(conn) => conn.output_idx_for_name(name),
             Self::LoadGenerator(conn) => conn.output_idx_for_name(name),
+            Self::File(conn) => conn.output_idx_for_name(name),
+            Self::Kinesis(conn) => conn.output_idx_for_name(name),
+            Self::SqlServer(conn) => conn.output_idx_for_name(name),
         }
     }
 }
@@ -969,6 +1047,9 @@ impl<C: ConnectionAccess> crate::AlterCompatible for GenericSourceConnection<C>
             (Self::LoadGenerator(conn), Self::LoadGenerator(other)) => {
                 conn.alter_compatible(id, other)
             }
+            (Self::File(conn), Self::File(other)) => conn.alter_compatible(id, other),
+            (Self::Kinesis(conn), Self::Kinesis(other)) => conn.alter_compatible(id, other),
+            (Self::SqlServer(conn), Self::SqlServer(other)) => conn.alter_compatible(id, other),
             _ => Err(AlterError { id }),
         };
 
@@ -997,6 +1078,11 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
                 GenericSourceConnection::LoadGenerator(loadgen) => {
                     Kind::Loadgen(loadgen.into_proto())
                 }
+                GenericSourceConnection::File(file) => Kind::File(file.into_proto()),
+                GenericSourceConnection::Kinesis(kinesis) => Kind::Kinesis(kinesis.into_proto()),
+                GenericSourceConnection::SqlServer(sql_server) => {
+                    Kind::SqlServer(sql_server.into_proto())
+                }
             }),
         }
     }
@@ -1011,6 +1097,11 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
             Kind::Postgres(postgres) => GenericSourceConnection::Postgres(postgres.into_rust()?),
             Kind::Mysql(mysql) => GenericSourceConnection::MySql(mysql.into_rust()?),
             Kind::Loadgen(loadgen) => GenericSourceConnection::LoadGenerator(loadgen.into_rust()?),
+            Kind::File(file) => GenericSourceConnection::File(file.into_rust()?),
+            Kind::Kinesis(kinesis) => GenericSourceConnection::Kinesis(kinesis.into_rust()?),
+            Kind::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_rust()?)
+            }
         })
     }
 }
@@ -1133,8 +1224,11 @@ impl PartEncoder<SourceData> for SourceDataEncoder {
             Ok(row) => {
                 self.ok_validity.push(true);
                 self.ok.inc_len();
-                for (encoder, datum) in self.ok.col_encoders().iter_mut().zip(row.iter()) {
-                    encoder.encode(datum);
+                // Rows written before nullable columns were added to the
+                // schema lack their trailing datums, which we encode as nulls.
+                let mut datums = row.iter();
+                for encoder in self.ok.col_encoders().iter_mut() {
+                    encoder.encode(datums.next().unwrap_or(Datum::Null));
                 }
                 ColumnPush::<Option<Vec<u8>>>::push(self.err.as_mut(), None);
             }
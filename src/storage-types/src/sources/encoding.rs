@@ -14,6 +14,7 @@ use mz_interchange::{avro, protobuf};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::regex::any_regex;
 use mz_repr::{ColumnType, GlobalId, RelationDesc, ScalarType};
+use mz_sql_parser::ast::UnresolvedItemName;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
@@ -110,6 +111,71 @@ impl<C: ConnectionAccess> AlterCompatible for SourceDataEncoding<C> {
     }
 }
 
+/// What to do with the records of a source that fail to decode.
+#[derive(Arbitrary, Copy, Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum DecodeErrorPolicy {
+    /// Ingest the error into the source's error collection, which makes the
+    /// queries that read the source fail.
+    #[default]
+    Error,
+    /// Drop the record.
+    Skip,
+    /// Drop the record, and write its raw bytes and error into the source's
+    /// dead-letter subsource instead.
+    DeadLetter,
+}
+
+impl RustType<i32> for DecodeErrorPolicy {
+    fn into_proto(&self) -> i32 {
+        match self {
+            DecodeErrorPolicy::Error => ProtoDecodeErrorPolicy::Error.into(),
+            DecodeErrorPolicy::Skip => ProtoDecodeErrorPolicy::Skip.into(),
+            DecodeErrorPolicy::DeadLetter => ProtoDecodeErrorPolicy::DeadLetter.into(),
+        }
+    }
+
+    fn from_proto(proto: i32) -> Result<Self, TryFromProtoError> {
+        Ok(match ProtoDecodeErrorPolicy::from_i32(proto) {
+            Some(ProtoDecodeErrorPolicy::Error) => DecodeErrorPolicy::Error,
+            Some(ProtoDecodeErrorPolicy::Skip) => DecodeErrorPolicy::Skip,
+            Some(ProtoDecodeErrorPolicy::DeadLetter) => DecodeErrorPolicy::DeadLetter,
+            None => {
+                return Err(TryFromProtoError::UnknownEnumVariant(
+                    "decode_error_policy".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+/// The external reference of the dead-letter subsource of a source.
+pub const DEAD_LETTER_REFERENCE: &str = "dead_letter";
+
+/// The output index of the dead-letter subsource. Only sources with a single
+/// output decode their data, so the index after the primary output is always
+/// free.
+pub const DEAD_LETTER_OUTPUT_INDEX: usize = 1;
+
+/// Returns the output index of the dead-letter subsource if `name` refers to
+/// it.
+pub fn dead_letter_output_idx(name: &UnresolvedItemName) -> Option<usize> {
+    match &name.0[..] {
+        [name] if name.as_str() == DEAD_LETTER_REFERENCE => Some(DEAD_LETTER_OUTPUT_INDEX),
+        _ => None,
+    }
+}
+
+/// Returns the description of the dead-letter subsource of a source, whose
+/// records carry their position in the source as described by
+/// `timestamp_desc`.
+pub fn dead_letter_desc(timestamp_desc: RelationDesc) -> RelationDesc {
+    RelationDesc::empty()
+        .with_column("key", ScalarType::Bytes.nullable(true))
+        .with_column("value", ScalarType::Bytes.nullable(true))
+        .with_column("error", ScalarType::String.nullable(false))
+        .concat(timestamp_desc)
+}
+
 /// A description of how each row should be decoded, from a string of bytes to a sequence of
 /// Differential updates.
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -119,7 +185,7 @@ pub enum DataEncoding<C: ConnectionAccess = InlinedConnection> {
     Csv(CsvEncoding),
     Regex(RegexEncoding),
     Bytes,
-    Json,
+    Json(JsonEncoding),
     Text,
 }
 
@@ -133,7 +199,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<DataEncoding, R>
             Self::Csv(conn) => DataEncoding::Csv(conn),
             Self::Regex(conn) => DataEncoding::Regex(conn),
             Self::Bytes => DataEncoding::Bytes,
-            Self::Json => DataEncoding::Json,
+            Self::Json(conn) => DataEncoding::Json(conn),
             Self::Text => DataEncoding::Text,
         }
     }
@@ -150,7 +216,7 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
                 DataEncoding::Regex(e) => Kind::Regex(e.into_proto()),
                 DataEncoding::Bytes => Kind::Bytes(()),
                 DataEncoding::Text => Kind::Text(()),
-                DataEncoding::Json => Kind::Json(()),
+                DataEncoding::Json(e) => Kind::Json(e.into_proto()),
             }),
         }
     }
@@ -167,7 +233,7 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
             Kind::Regex(e) => DataEncoding::Regex(e.into_rust()?),
             Kind::Bytes(()) => DataEncoding::Bytes,
             Kind::Text(()) => DataEncoding::Text,
-            Kind::Json(()) => DataEncoding::Json,
+            Kind::Json(e) => DataEncoding::Json(e.into_rust()?),
         })
     }
 }
@@ -189,7 +255,7 @@ impl<C: ConnectionAccess> DataEncoding<C> {
             Self::Csv(_) => "csv",
             Self::Regex(_) => "regex",
             Self::Bytes => "bytes",
-            Self::Json => "json",
+            Self::Json(_) => "json",
             Self::Text => "text",
         }
     }
@@ -202,7 +268,7 @@ impl<C: ConnectionAccess> DataEncoding<C> {
             Self::Bytes => {
                 RelationDesc::empty().with_column("data", ScalarType::Bytes.nullable(false))
             }
-            Self::Json => {
+            Self::Json(_) => {
                 RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
             }
             Self::Avro(AvroEncoding { schema, .. }) => {
@@ -255,7 +321,7 @@ impl<C: ConnectionAccess> DataEncoding<C> {
     pub fn op_name(&self) -> &'static str {
         match self {
             Self::Bytes => "Bytes",
-            Self::Json => "Json",
+            Self::Json(_) => "Json",
             Self::Avro(_) => "Avro",
             Self::Protobuf(_) => "Protobuf",
             Self::Regex { .. } => "Regex",
@@ -404,6 +470,58 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
     }
 }
 
+/// Encoding in JSON format.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct JsonEncoding {
+    pub framing: JsonFraming,
+}
+
+impl RustType<ProtoJsonEncoding> for JsonEncoding {
+    fn into_proto(&self) -> ProtoJsonEncoding {
+        ProtoJsonEncoding {
+            framing: self.framing.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(JsonEncoding {
+            framing: proto.framing.into_rust()?,
+        })
+    }
+}
+
+/// How JSON values are laid out within a single record.
+#[derive(Arbitrary, Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum JsonFraming {
+    /// Each record contains exactly one JSON value.
+    Single,
+    /// Each record contains a JSON array, each element of which is decoded
+    /// as its own row.
+    Array,
+    /// Each record contains zero or more newline-delimited JSON values, each
+    /// of which is decoded as its own row.
+    Lines,
+}
+
+impl RustType<i32> for JsonFraming {
+    fn into_proto(&self) -> i32 {
+        match self {
+            JsonFraming::Single => ProtoJsonFraming::Single.into(),
+            JsonFraming::Array => ProtoJsonFraming::Array.into(),
+            JsonFraming::Lines => ProtoJsonFraming::Lines.into(),
+        }
+    }
+
+    fn from_proto(proto: i32) -> Result<Self, TryFromProtoError> {
+        Ok(match ProtoJsonFraming::from_i32(proto) {
+            Some(ProtoJsonFraming::Single) => JsonFraming::Single,
+            Some(ProtoJsonFraming::Array) => JsonFraming::Array,
+            Some(ProtoJsonFraming::Lines) => JsonFraming::Lines,
+            None => return Err(TryFromProtoError::UnknownEnumVariant("framing".to_string())),
+        })
+    }
+}
+
 /// Arguments necessary to define how to decode from CSV format
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct CsvEncoding {
@@ -0,0 +1,540 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to sources that ingest files from an object store or a local
+//! directory.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::{Bytes, BytesMut};
+use mz_ore::future::InTask;
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, Row, ScalarType};
+use mz_sql_parser::ast::UnresolvedItemName;
+use mz_timely_util::order::Partitioned;
+use once_cell::sync::Lazy;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncReadExt;
+
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::connections::ConnectionContext;
+use crate::controller::AlterError;
+use crate::sources::kafka::{BoundKind, RangeBound};
+use crate::sources::{encoding, MzOffset, SourceConnection, SourceTimestamp};
+use crate::AlterCompatible;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.sources.file.rs"
+));
+
+/// A source that ingests every object stored under a prefix of a [`FileStore`].
+///
+/// Objects are ingested in key order, each exactly once. New objects that
+/// appear under the prefix after the source is created are ingested as they
+/// are discovered.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub store: FileStore<C>,
+    /// Only objects whose keys start with this prefix are ingested.
+    pub prefix: String,
+    pub format: FileFormat,
+}
+
+/// The location of the objects ingested by a [`FileSourceConnection`].
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileStore<C: ConnectionAccess = InlinedConnection> {
+    /// An S3 bucket, accessed via an AWS connection.
+    S3 {
+        connection_id: GlobalId,
+        connection: C::Aws,
+        bucket: String,
+    },
+    /// A directory on the local filesystem of the cluster replica. Object keys
+    /// are the `/`-separated paths of the files relative to the directory.
+    Local { path: String },
+}
+
+/// How the contents of each object are split into records.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileFormat {
+    /// Each line of an object is a record, which is decoded by the source's
+    /// encoding.
+    Lines,
+    /// Each CSV record of an object is a record, which is decoded by the
+    /// source's encoding. Unlike with [`FileFormat::Lines`], newlines within
+    /// quoted fields do not end a record.
+    Csv {
+        /// Whether to skip the first record of each object, which contains a
+        /// header rather than data.
+        skip_header: bool,
+    },
+    /// Each object is a single record, which is decoded by the source's
+    /// encoding.
+    Object,
+    /// Each object is a Parquet file whose rows are decoded into `desc`.
+    Parquet { desc: RelationDesc },
+}
+
+/// The details of a file source determined during purification.
+#[derive(Arbitrary, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSourceDetails {
+    /// The schema of the Parquet files, if the source ingests Parquet files.
+    pub parquet_desc: Option<RelationDesc>,
+}
+
+impl RustType<ProtoFileSourceDetails> for FileSourceDetails {
+    fn into_proto(&self) -> ProtoFileSourceDetails {
+        ProtoFileSourceDetails {
+            parquet_desc: self.parquet_desc.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(FileSourceDetails {
+            parquet_desc: proto.parquet_desc.into_rust()?,
+        })
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<FileSourceConnection, R>
+    for FileSourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> FileSourceConnection {
+        let FileSourceConnection {
+            store,
+            prefix,
+            format,
+        } = self;
+
+        FileSourceConnection {
+            store: store.into_inline_connection(r),
+            prefix,
+            format,
+        }
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<FileStore, R> for FileStore<ReferencedConnection> {
+    fn into_inline_connection(self, r: R) -> FileStore {
+        match self {
+            FileStore::S3 {
+                connection_id,
+                connection,
+                bucket,
+            } => FileStore::S3 {
+                connection_id,
+                connection: r.resolve_connection(connection).unwrap_aws(),
+                bucket,
+            },
+            FileStore::Local { path } => FileStore::Local { path },
+        }
+    }
+}
+
+/// The progress of a file source is tracked per object key. An ingested object
+/// is recorded as a singleton range containing its key at offset 1, while the
+/// gaps between ingested objects are ranges whose bounds are exclusive and
+/// `NULL` for infinity.
+pub static FILE_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("lower", ScalarType::String.nullable(true))
+        .with_column("upper", ScalarType::String.nullable(true))
+        .with_column("offset", ScalarType::UInt64.nullable(false))
+});
+
+impl<C: ConnectionAccess> SourceConnection for FileSourceConnection<C> {
+    fn name(&self) -> &'static str {
+        match self.store {
+            FileStore::S3 { .. } => "s3",
+            FileStore::Local { .. } => "local-files",
+        }
+    }
+
+    fn upstream_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn value_desc(&self) -> RelationDesc {
+        match &self.format {
+            FileFormat::Lines | FileFormat::Csv { .. } | FileFormat::Object => {
+                RelationDesc::empty().with_column("data", ScalarType::Bytes.nullable(false))
+            }
+            FileFormat::Parquet { desc } => desc.clone(),
+        }
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        FILE_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<GlobalId> {
+        match &self.store {
+            FileStore::S3 { connection_id, .. } => Some(*connection_id),
+            FileStore::Local { .. } => None,
+        }
+    }
+
+    fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+        vec![]
+    }
+
+    fn output_idx_for_name(&self, name: &UnresolvedItemName) -> Option<usize> {
+        encoding::dead_letter_output_idx(name)
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for FileSourceConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let FileSourceConnection {
+            store,
+            prefix,
+            format,
+        } = self;
+
+        let compatibility_checks = [
+            (
+                match (store, &other.store) {
+                    (
+                        FileStore::S3 {
+                            connection_id,
+                            connection,
+                            bucket,
+                        },
+                        FileStore::S3 {
+                            connection_id: other_connection_id,
+                            connection: other_connection,
+                            bucket: other_bucket,
+                        },
+                    ) => {
+                        connection_id == other_connection_id
+                            && connection.alter_compatible(id, other_connection).is_ok()
+                            && bucket == other_bucket
+                    }
+                    (s, o) => s == o,
+                },
+                "store",
+            ),
+            (prefix == &other.prefix, "prefix"),
+            (format == &other.format, "format"),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "FileSourceConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RustType<ProtoFileSourceConnection> for FileSourceConnection {
+    fn into_proto(&self) -> ProtoFileSourceConnection {
+        ProtoFileSourceConnection {
+            store: Some(self.store.into_proto()),
+            prefix: self.prefix.clone(),
+            format: Some(self.format.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(FileSourceConnection {
+            store: proto
+                .store
+                .into_rust_if_some("ProtoFileSourceConnection::store")?,
+            prefix: proto.prefix,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoFileSourceConnection::format")?,
+        })
+    }
+}
+
+impl RustType<ProtoFileStore> for FileStore {
+    fn into_proto(&self) -> ProtoFileStore {
+        use proto_file_store::{Kind, ProtoS3};
+        ProtoFileStore {
+            kind: Some(match self {
+                FileStore::S3 {
+                    connection_id,
+                    connection,
+                    bucket,
+                } => Kind::S3(ProtoS3 {
+                    connection_id: Some(connection_id.into_proto()),
+                    connection: Some(connection.into_proto()),
+                    bucket: bucket.clone(),
+                }),
+                FileStore::Local { path } => Kind::LocalPath(path.clone()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileStore) -> Result<Self, TryFromProtoError> {
+        use proto_file_store::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoFileStore::kind"))?;
+        Ok(match kind {
+            Kind::S3(s3) => FileStore::S3 {
+                connection_id: s3
+                    .connection_id
+                    .into_rust_if_some("ProtoS3::connection_id")?,
+                connection: s3.connection.into_rust_if_some("ProtoS3::connection")?,
+                bucket: s3.bucket,
+            },
+            Kind::LocalPath(path) => FileStore::Local { path },
+        })
+    }
+}
+
+impl RustType<ProtoFileFormat> for FileFormat {
+    fn into_proto(&self) -> ProtoFileFormat {
+        use proto_file_format::Kind;
+        ProtoFileFormat {
+            kind: Some(match self {
+                FileFormat::Lines => Kind::Lines(()),
+                FileFormat::Csv { skip_header } => Kind::CsvSkipHeader(*skip_header),
+                FileFormat::Object => Kind::Object(()),
+                FileFormat::Parquet { desc } => Kind::Parquet(desc.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoFileFormat) -> Result<Self, TryFromProtoError> {
+        use proto_file_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoFileFormat::kind"))?;
+        Ok(match kind {
+            Kind::Lines(()) => FileFormat::Lines,
+            Kind::CsvSkipHeader(skip_header) => FileFormat::Csv { skip_header },
+            Kind::Object(()) => FileFormat::Object,
+            Kind::Parquet(desc) => FileFormat::Parquet {
+                desc: desc.into_rust()?,
+            },
+        })
+    }
+}
+
+impl FileStore<InlinedConnection> {
+    /// Returns a client that can list and fetch the objects in this store.
+    pub async fn client(
+        &self,
+        connection_context: &ConnectionContext,
+        in_task: InTask,
+    ) -> Result<FileStoreClient, anyhow::Error> {
+        match self {
+            FileStore::S3 {
+                connection_id,
+                connection,
+                bucket,
+            } => {
+                let sdk_config = connection
+                    .load_sdk_config(connection_context, *connection_id, in_task)
+                    .await?;
+                Ok(FileStoreClient::S3 {
+                    client: mz_aws_util::s3::new_client(&sdk_config),
+                    bucket: bucket.clone(),
+                })
+            }
+            FileStore::Local { path } => Ok(FileStoreClient::Local {
+                path: PathBuf::from(path),
+            }),
+        }
+    }
+}
+
+/// A client for the objects in a [`FileStore`].
+#[derive(Debug)]
+pub enum FileStoreClient {
+    S3 {
+        client: aws_sdk_s3::Client,
+        bucket: String,
+    },
+    Local {
+        path: PathBuf,
+    },
+}
+
+impl FileStoreClient {
+    /// Returns the keys of all objects that start with `prefix`, in ascending
+    /// order.
+    pub async fn list_objects(&self, prefix: &str) -> Result<Vec<String>, anyhow::Error> {
+        let mut keys = match self {
+            FileStoreClient::S3 { client, bucket } => {
+                mz_aws_util::s3::list_all_objects(client, bucket, prefix).await?
+            }
+            FileStoreClient::Local { path } => {
+                let mut keys = vec![];
+                list_local_files(path, "", &mut keys)
+                    .await
+                    .with_context(|| format!("listing {}", path.display()))?;
+                keys.retain(|key| key.starts_with(prefix));
+                keys
+            }
+        };
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Returns the contents of the object at `key`, or `None` if it no longer
+    /// exists.
+    pub async fn get_object(&self, key: &str) -> Result<Option<Bytes>, anyhow::Error> {
+        match self {
+            FileStoreClient::S3 { client, bucket } => {
+                mz_aws_util::s3::get_object(client, bucket, key).await
+            }
+            FileStoreClient::Local { path } => match tokio::fs::read(path.join(key)).await {
+                Ok(contents) => Ok(Some(Bytes::from(contents))),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).with_context(|| format!("reading {}", key)),
+            },
+        }
+    }
+
+    /// Like [`FileStoreClient::get_object`], but returns a reader that yields
+    /// the contents of the object in chunks rather than reading it into
+    /// memory.
+    pub async fn open_object(&self, key: &str) -> Result<Option<ObjectReader>, anyhow::Error> {
+        match self {
+            FileStoreClient::S3 { client, bucket } => {
+                let body = mz_aws_util::s3::get_object_stream(client, bucket, key).await?;
+                Ok(body.map(ObjectReader::S3))
+            }
+            FileStoreClient::Local { path } => match tokio::fs::File::open(path.join(key)).await {
+                Ok(file) => Ok(Some(ObjectReader::Local(file))),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).with_context(|| format!("opening {}", key)),
+            },
+        }
+    }
+}
+
+/// The size of the chunks in which an [`ObjectReader`] reads local files.
+const LOCAL_CHUNK_SIZE: usize = 1 << 20;
+
+/// Reads the contents of an object in a [`FileStore`] in chunks.
+#[derive(Debug)]
+pub enum ObjectReader {
+    S3(ByteStream),
+    Local(tokio::fs::File),
+}
+
+impl ObjectReader {
+    /// Returns the next chunk of the object, or `None` once the whole object
+    /// has been read.
+    pub async fn next_chunk(&mut self) -> Result<Option<Bytes>, anyhow::Error> {
+        match self {
+            ObjectReader::S3(body) => Ok(body.next().await.transpose()?),
+            ObjectReader::Local(file) => {
+                let mut buf = BytesMut::with_capacity(LOCAL_CHUNK_SIZE);
+                let n = file.read_buf(&mut buf).await?;
+                Ok((n > 0).then(|| buf.freeze()))
+            }
+        }
+    }
+}
+
+/// Appends the `/`-separated paths, relative to the root of the walk, of all
+/// files below `dir` to `keys`. `relative` is the path of `dir` itself.
+async fn list_local_files(
+    dir: &Path,
+    relative: &str,
+    keys: &mut Vec<String>,
+) -> Result<(), std::io::Error> {
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let key = if relative.is_empty() {
+            name
+        } else {
+            format!("{relative}/{name}")
+        };
+        if entry.file_type().await?.is_dir() {
+            Box::pin(list_local_files(&entry.path(), &key, keys)).await?;
+        } else {
+            keys.push(key);
+        }
+    }
+    Ok(())
+}
+
+impl SourceTimestamp for Partitioned<RangeBound<String>, MzOffset> {
+    fn encode_row(&self) -> Row {
+        let interval = self.interval();
+        let (lower, upper) = match interval.singleton() {
+            Some(RangeBound::Elem(key, BoundKind::At)) => (Datum::String(key), Datum::String(key)),
+            Some(bound) => unreachable!("invalid singleton {bound:?}"),
+            None => {
+                let lower = match &interval.lower {
+                    RangeBound::NegInfinity => Datum::Null,
+                    RangeBound::Elem(key, BoundKind::After) => Datum::String(key),
+                    lower => unreachable!("invalid lower bound {lower:?}"),
+                };
+                let upper = match &interval.upper {
+                    RangeBound::PosInfinity => Datum::Null,
+                    RangeBound::Elem(key, BoundKind::Before) => Datum::String(key),
+                    upper => unreachable!("invalid upper bound {upper:?}"),
+                };
+                (lower, upper)
+            }
+        };
+        Row::pack([lower, upper, Datum::UInt64(self.timestamp().offset)])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        match (datums.next(), datums.next(), datums.next(), datums.next()) {
+            (Some(lower), Some(upper), Some(Datum::UInt64(offset)), None) => {
+                let lower = (!lower.is_null()).then(|| lower.unwrap_str().to_owned());
+                let upper = (!upper.is_null()).then(|| upper.unwrap_str().to_owned());
+                match (lower, upper) {
+                    // Gaps between objects never start and end at the same key,
+                    // so equal bounds always describe a single object.
+                    (Some(lower), Some(upper)) if lower == upper => {
+                        Partitioned::new_singleton(RangeBound::exact(lower), MzOffset::from(offset))
+                    }
+                    (lower, upper) => {
+                        let lower = match lower {
+                            Some(key) => RangeBound::after(key),
+                            None => RangeBound::NegInfinity,
+                        };
+                        let upper = match upper {
+                            Some(key) => RangeBound::before(key),
+                            None => RangeBound::PosInfinity,
+                        };
+                        Partitioned::new_range(lower, upper, MzOffset::from(offset))
+                    }
+                }
+            }
+            invalid_binding => unreachable!("invalid binding {:?}", invalid_binding),
+        }
+    }
+}
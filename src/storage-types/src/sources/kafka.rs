@@ -30,7 +30,7 @@ use crate::connections::inline::{
 };
 use crate::connections::{ConnectionContext, KafkaConnection};
 use crate::controller::AlterError;
-use crate::sources::{MzOffset, SourceConnection, SourceTimestamp};
+use crate::sources::{encoding, MzOffset, SourceConnection, SourceTimestamp};
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -42,9 +42,15 @@ pub struct KafkaSourceConnection<C: ConnectionAccess = InlinedConnection> {
     pub connection: C::Kafka,
     pub connection_id: GlobalId,
     pub topic: String,
+    /// Whether `topic` is a regular expression that selects the topics to consume from, rather
+    /// than the name of a single topic.
+    pub topic_is_pattern: bool,
     // Map from partition -> starting offset
     #[proptest(strategy = "proptest::collection::btree_map(any::<i32>(), any::<i64>(), 0..4)")]
     pub start_offsets: BTreeMap<i32, i64>,
+    /// The ingestion epoch of the source, which `ALTER SOURCE ... RESET TO` advances whenever it
+    /// moves the source to new `start_offsets`. See [`epoch_offset`].
+    pub start_epoch: u64,
     pub group_id_prefix: Option<String>,
     #[proptest(strategy = "proptest::collection::vec(any::<(String, KafkaMetadataKind)>(), 0..4)")]
     pub metadata_columns: Vec<(String, KafkaMetadataKind)>,
@@ -59,7 +65,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSourceConnection, R>
             connection,
             connection_id,
             topic,
+            topic_is_pattern,
             start_offsets,
+            start_epoch,
             group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval,
@@ -68,7 +76,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSourceConnection, R>
             connection: r.resolve_connection(connection).unwrap_kafka(),
             connection_id,
             topic,
+            topic_is_pattern,
             start_offsets,
+            start_epoch,
             group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval,
@@ -76,6 +86,57 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSourceConnection, R>
     }
 }
 
+/// The number of low bits of the offsets in the frontier of a Kafka source that hold the Kafka
+/// offset. The remaining high bits hold the ingestion epoch of the source, so that its frontier
+/// keeps advancing even when `ALTER SOURCE ... RESET TO` moves the source back in its topics.
+const KAFKA_EPOCH_OFFSET_BITS: u32 = 48;
+
+/// The largest ingestion epoch of a Kafka source.
+pub const KAFKA_MAX_EPOCH: u64 = (1 << (u64::BITS - KAFKA_EPOCH_OFFSET_BITS)) - 1;
+
+/// Returns the frontier offset of Kafka offset `offset` in ingestion epoch `epoch`.
+///
+/// Sources that were never reset are in epoch 0, in which frontier offsets are Kafka offsets.
+pub fn epoch_offset(epoch: u64, offset: u64) -> MzOffset {
+    assert!(
+        epoch <= KAFKA_MAX_EPOCH,
+        "kafka source epoch {epoch} out of range"
+    );
+    assert!(
+        offset >> KAFKA_EPOCH_OFFSET_BITS == 0,
+        "kafka offset {offset} out of range"
+    );
+    MzOffset::from((epoch << KAFKA_EPOCH_OFFSET_BITS) | offset)
+}
+
+/// Returns the Kafka offset of frontier offset `offset`, if it belongs to ingestion epoch
+/// `epoch`.
+pub fn offset_in_epoch(epoch: u64, offset: MzOffset) -> Option<u64> {
+    let (offset_epoch, offset) = split_epoch_offset(offset);
+    (offset_epoch == epoch).then_some(offset)
+}
+
+/// Splits frontier offset `offset` into its ingestion epoch and its Kafka offset.
+fn split_epoch_offset(offset: MzOffset) -> (u64, u64) {
+    let mask = (1 << KAFKA_EPOCH_OFFSET_BITS) - 1;
+    (
+        offset.offset >> KAFKA_EPOCH_OFFSET_BITS,
+        offset.offset & mask,
+    )
+}
+
+/// Returns the frontier offset encoded by the `offset` and `epoch` columns of a progress row.
+///
+/// Rows written before the progress collection had an `epoch` column lack it, and belong to
+/// epoch 0.
+fn decode_epoch_offset(offset: u64, epoch: Option<Datum>) -> MzOffset {
+    match epoch {
+        None | Some(Datum::Null) => MzOffset::from(offset),
+        Some(Datum::UInt64(epoch)) => epoch_offset(epoch, offset),
+        Some(epoch) => unreachable!("invalid epoch {epoch:?}"),
+    }
+}
+
 pub static KAFKA_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
     RelationDesc::empty()
         .with_column(
@@ -86,6 +147,17 @@ pub static KAFKA_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
             .nullable(false),
         )
         .with_column("offset", ScalarType::UInt64.nullable(true))
+        .with_column("epoch", ScalarType::UInt64.nullable(true))
+});
+
+pub static KAFKA_TOPIC_PATTERN_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("lower_topic", ScalarType::String.nullable(true))
+        .with_column("lower_partition", ScalarType::Int32.nullable(true))
+        .with_column("upper_topic", ScalarType::String.nullable(true))
+        .with_column("upper_partition", ScalarType::Int32.nullable(true))
+        .with_column("offset", ScalarType::UInt64.nullable(true))
+        .with_column("epoch", ScalarType::UInt64.nullable(true))
 });
 
 impl KafkaSourceConnection {
@@ -138,7 +210,11 @@ impl<C: ConnectionAccess> SourceConnection for KafkaSourceConnection<C> {
     }
 
     fn timestamp_desc(&self) -> RelationDesc {
-        KAFKA_PROGRESS_DESC.clone()
+        if self.topic_is_pattern {
+            KAFKA_TOPIC_PATTERN_PROGRESS_DESC.clone()
+        } else {
+            KAFKA_PROGRESS_DESC.clone()
+        }
     }
 
     fn connection_id(&self) -> Option<GlobalId> {
@@ -150,6 +226,7 @@ impl<C: ConnectionAccess> SourceConnection for KafkaSourceConnection<C> {
             .iter()
             .map(|(name, kind)| {
                 let typ = match kind {
+                    KafkaMetadataKind::Topic => ScalarType::String.nullable(false),
                     KafkaMetadataKind::Partition => ScalarType::Int32.nullable(false),
                     KafkaMetadataKind::Offset => ScalarType::UInt64.nullable(false),
                     KafkaMetadataKind::Timestamp => {
@@ -190,8 +267,10 @@ impl<C: ConnectionAccess> SourceConnection for KafkaSourceConnection<C> {
             .collect()
     }
 
-    fn output_idx_for_name(&self, _name: &mz_sql_parser::ast::UnresolvedItemName) -> Option<usize> {
-        None
+    fn output_idx_for_name(&self, name: &mz_sql_parser::ast::UnresolvedItemName) -> Option<usize> {
+        // The only exported output besides the primary one contains the
+        // records that failed to decode.
+        encoding::dead_letter_output_idx(name)
     }
 }
 
@@ -205,7 +284,9 @@ impl<C: ConnectionAccess> crate::AlterCompatible for KafkaSourceConnection<C> {
             connection,
             connection_id,
             topic,
+            topic_is_pattern,
             start_offsets,
+            start_epoch,
             group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval,
@@ -218,7 +299,17 @@ impl<C: ConnectionAccess> crate::AlterCompatible for KafkaSourceConnection<C> {
             ),
             (connection_id == &other.connection_id, "connection_id"),
             (topic == &other.topic, "topic"),
-            (start_offsets == &other.start_offsets, "start_offsets"),
+            (
+                topic_is_pattern == &other.topic_is_pattern,
+                "topic_is_pattern",
+            ),
+            // The start offsets can only change along with the epoch, which is how the ingestion
+            // learns to ignore the offsets it reached before.
+            (
+                start_offsets == &other.start_offsets || start_epoch < &other.start_epoch,
+                "start_offsets",
+            ),
+            (start_epoch <= &other.start_epoch, "start_epoch"),
             (group_id_prefix == &other.group_id_prefix, "group_id_prefix"),
             (
                 metadata_columns == &other.metadata_columns,
@@ -252,7 +343,9 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection<InlinedConne
             connection: Some(self.connection.into_proto()),
             connection_id: Some(self.connection_id.into_proto()),
             topic: self.topic.clone(),
+            topic_is_pattern: self.topic_is_pattern,
             start_offsets: self.start_offsets.clone(),
+            start_epoch: self.start_epoch,
             group_id_prefix: self.group_id_prefix.clone(),
             metadata_columns: self
                 .metadata_columns
@@ -283,7 +376,9 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection<InlinedConne
                 .connection_id
                 .into_rust_if_some("ProtoKafkaSourceConnection::connection_id")?,
             topic: proto.topic,
+            topic_is_pattern: proto.topic_is_pattern,
             start_offsets: proto.start_offsets,
+            start_epoch: proto.start_epoch,
             group_id_prefix: proto.group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval: proto
@@ -370,7 +465,7 @@ impl<P> Extrema for RangeBound<P> {
 impl SourceTimestamp for Partitioned<RangeBound<i32>, MzOffset> {
     fn encode_row(&self) -> Row {
         use mz_repr::adt::range;
-        let mut row = Row::with_capacity(2);
+        let mut row = Row::with_capacity(3);
         let mut packer = row.packer();
 
         let to_numeric = |p: i32| Datum::from(OrderedDecimal(Numeric::from(p)));
@@ -396,15 +491,18 @@ impl SourceTimestamp for Partitioned<RangeBound<i32>, MzOffset> {
             ))))
             .expect("pushing range must not generate errors");
 
-        packer.push(Datum::UInt64(self.timestamp().offset));
+        let (epoch, offset) = split_epoch_offset(*self.timestamp());
+        packer.push(Datum::UInt64(offset));
+        packer.push(Datum::UInt64(epoch));
         row
     }
 
     fn decode_row(row: &Row) -> Self {
         let mut datums = row.iter();
 
-        match (datums.next(), datums.next(), datums.next()) {
-            (Some(Datum::Range(range)), Some(Datum::UInt64(offset)), None) => {
+        match (datums.next(), datums.next(), datums.next(), datums.next()) {
+            (Some(Datum::Range(range)), Some(Datum::UInt64(offset)), epoch, None) => {
+                let offset = decode_epoch_offset(offset, epoch);
                 let mut range = range.into_bounds(|b| b.datum());
                 //XXX: why do we have to canonicalize on read?
                 range.canonicalize().expect("ranges must be valid");
@@ -422,10 +520,7 @@ impl SourceTimestamp for Partitioned<RangeBound<i32>, MzOffset> {
                 match (range.lower.inclusive, range.upper.inclusive) {
                     (true, true) => {
                         assert_eq!(lower, upper);
-                        Partitioned::new_singleton(
-                            RangeBound::exact(lower.unwrap()),
-                            MzOffset::from(offset),
-                        )
+                        Partitioned::new_singleton(RangeBound::exact(lower.unwrap()), offset)
                     }
                     (false, false) => {
                         let lower = match lower {
@@ -436,7 +531,7 @@ impl SourceTimestamp for Partitioned<RangeBound<i32>, MzOffset> {
                             Some(pid) => RangeBound::before(pid),
                             None => RangeBound::PosInfinity,
                         };
-                        Partitioned::new_range(lower, upper, MzOffset::from(offset))
+                        Partitioned::new_range(lower, upper, offset)
                     }
                     _ => panic!("invalid timestamp"),
                 }
@@ -446,9 +541,106 @@ impl SourceTimestamp for Partitioned<RangeBound<i32>, MzOffset> {
     }
 }
 
+/// A partition of one of the topics consumed by a Kafka source with a topic pattern.
+///
+/// Partitions are ordered by topic first, so the partitions of a newly discovered topic can sort
+/// before the ones that are already known.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct KafkaTopicPartition {
+    pub topic: String,
+    pub partition: i32,
+}
+
+impl fmt::Display for KafkaTopicPartition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.topic, self.partition)
+    }
+}
+
+impl SourceTimestamp for Partitioned<RangeBound<KafkaTopicPartition>, MzOffset> {
+    fn encode_row(&self) -> Row {
+        let interval = self.interval();
+        let (lower, upper) = match interval.singleton() {
+            Some(RangeBound::Elem(tp, BoundKind::At)) => (Some(tp), Some(tp)),
+            Some(bound) => unreachable!("invalid singleton {bound:?}"),
+            None => {
+                let lower = match &interval.lower {
+                    RangeBound::NegInfinity => None,
+                    RangeBound::Elem(tp, BoundKind::After) => Some(tp),
+                    lower => unreachable!("invalid lower bound {lower:?}"),
+                };
+                let upper = match &interval.upper {
+                    RangeBound::PosInfinity => None,
+                    RangeBound::Elem(tp, BoundKind::Before) => Some(tp),
+                    upper => unreachable!("invalid upper bound {upper:?}"),
+                };
+                (lower, upper)
+            }
+        };
+        let topic = |tp: Option<&KafkaTopicPartition>| match tp {
+            Some(tp) => Datum::String(&tp.topic),
+            None => Datum::Null,
+        };
+        let partition = |tp: Option<&KafkaTopicPartition>| match tp {
+            Some(tp) => Datum::Int32(tp.partition),
+            None => Datum::Null,
+        };
+        let (epoch, offset) = split_epoch_offset(*self.timestamp());
+        Row::pack([
+            topic(lower),
+            partition(lower),
+            topic(upper),
+            partition(upper),
+            Datum::UInt64(offset),
+            Datum::UInt64(epoch),
+        ])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let datums: Vec<_> = row.iter().collect();
+        let bound = |topic: Datum, partition: Datum| match (topic, partition) {
+            (Datum::String(topic), Datum::Int32(partition)) => Some(KafkaTopicPartition {
+                topic: topic.to_owned(),
+                partition,
+            }),
+            (Datum::Null, Datum::Null) => None,
+            invalid_bound => unreachable!("invalid bound {:?}", invalid_bound),
+        };
+        match datums[..] {
+            [lower_topic, lower_partition, upper_topic, upper_partition, Datum::UInt64(offset), ref epoch @ ..]
+                if epoch.len() <= 1 =>
+            {
+                let offset = decode_epoch_offset(offset, epoch.first().copied());
+                let lower = bound(lower_topic, lower_partition);
+                let upper = bound(upper_topic, upper_partition);
+                match (lower, upper) {
+                    // Gaps between partitions never start and end at the same partition, so equal
+                    // bounds always describe a single partition.
+                    (Some(lower), Some(upper)) if lower == upper => {
+                        Partitioned::new_singleton(RangeBound::exact(lower), offset)
+                    }
+                    (lower, upper) => {
+                        let lower = match lower {
+                            Some(tp) => RangeBound::after(tp),
+                            None => RangeBound::NegInfinity,
+                        };
+                        let upper = match upper {
+                            Some(tp) => RangeBound::before(tp),
+                            None => RangeBound::PosInfinity,
+                        };
+                        Partitioned::new_range(lower, upper, offset)
+                    }
+                }
+            }
+            _ => unreachable!("invalid binding {:?}", row),
+        }
+    }
+}
+
 /// Which piece of metadata a column corresponds to
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum KafkaMetadataKind {
+    Topic,
     Partition,
     Offset,
     Timestamp,
@@ -461,6 +653,7 @@ impl RustType<ProtoKafkaMetadataKind> for KafkaMetadataKind {
         use proto_kafka_metadata_kind::Kind;
         ProtoKafkaMetadataKind {
             kind: Some(match self {
+                KafkaMetadataKind::Topic => Kind::Topic(()),
                 KafkaMetadataKind::Partition => Kind::Partition(()),
                 KafkaMetadataKind::Offset => Kind::Offset(()),
                 KafkaMetadataKind::Timestamp => Kind::Timestamp(()),
@@ -479,6 +672,7 @@ impl RustType<ProtoKafkaMetadataKind> for KafkaMetadataKind {
             .kind
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoKafkaMetadataKind::kind"))?;
         Ok(match kind {
+            Kind::Topic(()) => KafkaMetadataKind::Topic,
             Kind::Partition(()) => KafkaMetadataKind::Partition,
             Kind::Offset(()) => KafkaMetadataKind::Offset,
             Kind::Timestamp(()) => KafkaMetadataKind::Timestamp,
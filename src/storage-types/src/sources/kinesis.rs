@@ -0,0 +1,397 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to Kinesis sources.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use mz_ore::future::InTask;
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, Row, ScalarType};
+use mz_sql_parser::ast::UnresolvedItemName;
+use mz_timely_util::order::Partitioned;
+use once_cell::sync::Lazy;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::{PathSummary, Refines, Timestamp};
+
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::connections::ConnectionContext;
+use crate::controller::AlterError;
+use crate::sources::kafka::{BoundKind, RangeBound};
+use crate::sources::{encoding, SourceConnection, SourceTimestamp};
+use crate::AlterCompatible;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.sources.kinesis.rs"
+));
+
+/// A source that ingests the records of every shard of a Kinesis data stream.
+///
+/// Shards are discovered as the stream is resharded. The records of a child
+/// shard are only ingested once its parent shards have been fully ingested,
+/// so records with the same partition key are ingested in order.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KinesisSourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: GlobalId,
+    pub connection: C::Aws,
+    pub stream_name: String,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<KinesisSourceConnection, R>
+    for KinesisSourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> KinesisSourceConnection {
+        let KinesisSourceConnection {
+            connection_id,
+            connection,
+            stream_name,
+        } = self;
+
+        KinesisSourceConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_aws(),
+            stream_name,
+        }
+    }
+}
+
+/// The progress of a Kinesis source is tracked per shard ID. An ingested shard
+/// is recorded as a singleton range containing its ID at the sequence number
+/// of the last record ingested from it, while the gaps between known shards
+/// are ranges whose bounds are exclusive and `NULL` for infinity. The sequence
+/// number is `NULL` for shards from which no record has been ingested yet, and
+/// for the gaps.
+pub static KINESIS_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("lower", ScalarType::String.nullable(true))
+        .with_column("upper", ScalarType::String.nullable(true))
+        .with_column("sequence_number", ScalarType::String.nullable(true))
+});
+
+/// The position of a Kinesis source within a shard.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum KinesisPosition {
+    /// The start of the shard, before any of its records.
+    Start,
+    /// The position right after the record with the given sequence number.
+    ///
+    /// Sequence numbers are decimal numbers of up to 128 digits without
+    /// leading zeros, and increase within a shard.
+    After(String),
+}
+
+impl Ord for KinesisPosition {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (KinesisPosition::Start, KinesisPosition::Start) => Ordering::Equal,
+            (KinesisPosition::Start, KinesisPosition::After(_)) => Ordering::Less,
+            (KinesisPosition::After(_), KinesisPosition::Start) => Ordering::Greater,
+            // Without leading zeros, longer sequence numbers are larger ones.
+            (KinesisPosition::After(a), KinesisPosition::After(b)) => {
+                a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+            }
+        }
+    }
+}
+
+impl PartialOrd for KinesisPosition {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for KinesisPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KinesisPosition::Start => write!(f, "Start"),
+            KinesisPosition::After(sequence_number) => write!(f, "After({sequence_number})"),
+        }
+    }
+}
+
+impl Timestamp for KinesisPosition {
+    // No need to describe complex summaries
+    type Summary = ();
+
+    fn minimum() -> Self {
+        KinesisPosition::Start
+    }
+}
+
+impl TotalOrder for KinesisPosition {}
+
+impl PartialOrder for KinesisPosition {
+    fn less_equal(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl PathSummary<KinesisPosition> for () {
+    fn results_in(&self, src: &KinesisPosition) -> Option<KinesisPosition> {
+        Some(src.clone())
+    }
+
+    fn followed_by(&self, _other: &Self) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Refines<()> for KinesisPosition {
+    fn to_inner(_other: ()) -> Self {
+        Self::minimum()
+    }
+
+    fn to_outer(self) -> () {}
+
+    fn summarize(_path: Self::Summary) -> <() as Timestamp>::Summary {}
+}
+
+/// The timestamp of a Kinesis source, which is partitioned by shard ID.
+///
+/// The records of a shard are ingested at the position right after the record
+/// that precedes them, so the frontier of a shard is the position after the
+/// last record that was ingested from it. A restarted source resumes reading
+/// every shard right after that record.
+pub type KinesisTimestamp = Partitioned<RangeBound<String>, KinesisPosition>;
+
+impl SourceTimestamp for KinesisTimestamp {
+    fn encode_row(&self) -> Row {
+        let interval = self.interval();
+        let (lower, upper) = match interval.singleton() {
+            Some(RangeBound::Elem(shard_id, BoundKind::At)) => {
+                (Datum::String(shard_id), Datum::String(shard_id))
+            }
+            Some(bound) => unreachable!("invalid singleton {bound:?}"),
+            None => {
+                let lower = match &interval.lower {
+                    RangeBound::NegInfinity => Datum::Null,
+                    RangeBound::Elem(shard_id, BoundKind::After) => Datum::String(shard_id),
+                    lower => unreachable!("invalid lower bound {lower:?}"),
+                };
+                let upper = match &interval.upper {
+                    RangeBound::PosInfinity => Datum::Null,
+                    RangeBound::Elem(shard_id, BoundKind::Before) => Datum::String(shard_id),
+                    upper => unreachable!("invalid upper bound {upper:?}"),
+                };
+                (lower, upper)
+            }
+        };
+        let position = match self.timestamp() {
+            KinesisPosition::Start => Datum::Null,
+            KinesisPosition::After(sequence_number) => Datum::String(sequence_number),
+        };
+        Row::pack([lower, upper, position])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        match (datums.next(), datums.next(), datums.next(), datums.next()) {
+            (Some(lower), Some(upper), Some(position), None) => {
+                let lower = (!lower.is_null()).then(|| lower.unwrap_str().to_owned());
+                let upper = (!upper.is_null()).then(|| upper.unwrap_str().to_owned());
+                let position = match position {
+                    Datum::Null => KinesisPosition::Start,
+                    position => KinesisPosition::After(position.unwrap_str().to_owned()),
+                };
+                match (lower, upper) {
+                    // Gaps between shards never start and end at the same ID,
+                    // so equal bounds always describe a single shard.
+                    (Some(lower), Some(upper)) if lower == upper => {
+                        Partitioned::new_singleton(RangeBound::exact(lower), position)
+                    }
+                    (lower, upper) => {
+                        let lower = match lower {
+                            Some(shard_id) => RangeBound::after(shard_id),
+                            None => RangeBound::NegInfinity,
+                        };
+                        let upper = match upper {
+                            Some(shard_id) => RangeBound::before(shard_id),
+                            None => RangeBound::PosInfinity,
+                        };
+                        Partitioned::new_range(lower, upper, position)
+                    }
+                }
+            }
+            invalid_binding => unreachable!("invalid binding {:?}", invalid_binding),
+        }
+    }
+}
+
+impl<C: ConnectionAccess> SourceConnection for KinesisSourceConnection<C> {
+    fn name(&self) -> &'static str {
+        "kinesis"
+    }
+
+    fn upstream_name(&self) -> Option<&str> {
+        Some(self.stream_name.as_str())
+    }
+
+    fn key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn value_desc(&self) -> RelationDesc {
+        RelationDesc::empty().with_column("data", ScalarType::Bytes.nullable(false))
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        KINESIS_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<GlobalId> {
+        Some(self.connection_id)
+    }
+
+    fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+        vec![]
+    }
+
+    fn output_idx_for_name(&self, name: &UnresolvedItemName) -> Option<usize> {
+        encoding::dead_letter_output_idx(name)
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for KinesisSourceConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let KinesisSourceConnection {
+            connection_id,
+            connection,
+            stream_name,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                connection.alter_compatible(id, &other.connection).is_ok(),
+                "connection",
+            ),
+            (stream_name == &other.stream_name, "stream_name"),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "KinesisSourceConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RustType<ProtoKinesisSourceConnection> for KinesisSourceConnection {
+    fn into_proto(&self) -> ProtoKinesisSourceConnection {
+        ProtoKinesisSourceConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            stream_name: self.stream_name.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoKinesisSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(KinesisSourceConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoKinesisSourceConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoKinesisSourceConnection::connection")?,
+            stream_name: proto.stream_name,
+        })
+    }
+}
+
+impl KinesisSourceConnection<InlinedConnection> {
+    /// Returns a client for the Kinesis API of the source's AWS connection.
+    pub async fn client(
+        &self,
+        connection_context: &ConnectionContext,
+        in_task: InTask,
+    ) -> Result<aws_sdk_kinesis::Client, anyhow::Error> {
+        let sdk_config = self
+            .connection
+            .load_sdk_config(connection_context, self.connection_id, in_task)
+            .await?;
+        Ok(mz_aws_util::kinesis::new_client(&sdk_config))
+    }
+
+    /// Validates that the stream of the source exists and that its shards can
+    /// be listed with the source's AWS connection.
+    pub async fn validate(
+        &self,
+        connection_context: &ConnectionContext,
+        in_task: InTask,
+    ) -> Result<(), anyhow::Error> {
+        let client = self.client(connection_context, in_task).await?;
+        mz_aws_util::kinesis::list_shards(&client, &self.stream_name).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_position_order() {
+        let after = |s: &str| KinesisPosition::After(s.to_string());
+        assert!(KinesisPosition::Start < after("1"));
+        assert!(after("9") < after("10"));
+        assert!(
+            after("49590338271490256608559692538361571095921575989136588898")
+                < after("49590338271490256608559692540925702759324208523137515618")
+        );
+        assert_eq!(after("123"), after("123"));
+    }
+
+    #[mz_ore::test]
+    fn test_timestamp_roundtrip() {
+        let timestamps = [
+            KinesisTimestamp::new_range(
+                RangeBound::NegInfinity,
+                RangeBound::before("shardId-000000000001".to_string()),
+                KinesisPosition::Start,
+            ),
+            KinesisTimestamp::new_singleton(
+                RangeBound::exact("shardId-000000000001".to_string()),
+                KinesisPosition::Start,
+            ),
+            KinesisTimestamp::new_singleton(
+                RangeBound::exact("shardId-000000000002".to_string()),
+                KinesisPosition::After(
+                    "49590338271490256608559692538361571095921575989136588898".to_string(),
+                ),
+            ),
+            KinesisTimestamp::new_range(
+                RangeBound::after("shardId-000000000002".to_string()),
+                RangeBound::PosInfinity,
+                KinesisPosition::Start,
+            ),
+        ];
+        for ts in timestamps {
+            assert_eq!(KinesisTimestamp::decode_row(&ts.encode_row()), ts);
+        }
+    }
+}
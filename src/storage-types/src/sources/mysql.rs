@@ -9,6 +9,7 @@
 
 //! Types related to mysql sources
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::io;
 use std::num::NonZeroU64;
@@ -104,6 +105,9 @@ pub struct MySqlSourceConnection<C: ConnectionAccess = InlinedConnection> {
     pub details: MySqlSourceDetails,
     pub text_columns: Vec<MySqlColumnRef>,
     pub ignore_columns: Vec<MySqlColumnRef>,
+    /// The output indexes of the tables whose current contents are retracted,
+    /// rather than permanently errored, when they are truncated upstream.
+    pub retract_on_truncate: BTreeSet<usize>,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<MySqlSourceConnection, R>
@@ -116,6 +120,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<MySqlSourceConnection, R>
             details,
             text_columns,
             ignore_columns,
+            retract_on_truncate,
         } = self;
 
         MySqlSourceConnection {
@@ -124,6 +129,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<MySqlSourceConnection, R>
             details,
             text_columns,
             ignore_columns,
+            retract_on_truncate,
         }
     }
 }
@@ -183,6 +189,7 @@ impl<C: ConnectionAccess> AlterCompatible for MySqlSourceConnection<C> {
             details,
             text_columns,
             ignore_columns,
+            retract_on_truncate,
         } = self;
 
         let compatibility_checks = [
@@ -191,9 +198,16 @@ impl<C: ConnectionAccess> AlterCompatible for MySqlSourceConnection<C> {
                 connection.alter_compatible(id, &other.connection).is_ok(),
                 "connection",
             ),
-            (details == &other.details, "details"),
+            (
+                details.alter_compatible(id, &other.details).is_ok(),
+                "details",
+            ),
             (text_columns == &other.text_columns, "text_columns"),
             (ignore_columns == &other.ignore_columns, "ignore_columns"),
+            (
+                retract_on_truncate == &other.retract_on_truncate,
+                "retract_on_truncate",
+            ),
         ];
 
         for (compatible, field) in compatibility_checks {
@@ -220,6 +234,12 @@ impl RustType<ProtoMySqlSourceConnection> for MySqlSourceConnection {
             details: Some(self.details.into_proto()),
             text_columns: self.text_columns.into_proto(),
             ignore_columns: self.ignore_columns.into_proto(),
+            retract_on_truncate: self
+                .retract_on_truncate
+                .iter()
+                .copied()
+                .map(mz_ore::cast::usize_to_u64)
+                .collect(),
         }
     }
 
@@ -236,6 +256,11 @@ impl RustType<ProtoMySqlSourceConnection> for MySqlSourceConnection {
                 .into_rust_if_some("ProtoMySqlSourceConnection::details")?,
             text_columns: proto.text_columns.into_rust()?,
             ignore_columns: proto.ignore_columns.into_rust()?,
+            retract_on_truncate: proto
+                .retract_on_truncate
+                .into_iter()
+                .map(mz_ore::cast::u64_to_usize)
+                .collect(),
         })
     }
 }
@@ -251,6 +276,11 @@ pub struct MySqlSourceDetails {
     /// one or more tables before the initial snapshot of all tables is complete.
     #[proptest(strategy = "any_gtidset()")]
     pub initial_gtid_set: String,
+    /// Set for sources that replicate from servers without GTIDs, which track their progress by
+    /// position in the server's binary log instead. `initial_gtid_set` is empty for such sources.
+    pub binlog: Option<MySqlBinlogDetails>,
+    /// The flavor of the upstream server, which determines the format of `initial_gtid_set`.
+    pub flavor: MySqlFlavor,
 }
 
 impl MySqlSourceDetails {
@@ -267,15 +297,228 @@ impl MySqlSourceDetails {
     }
 }
 
+/// The binary log coordinates of a source that replicates from a MySQL server without GTIDs.
+///
+/// Such sources represent a position in the binary log as a [`GtidPartition`] for the server's
+/// `@@server_uuid`, whose transaction id holds the sequence number of the binlog file in its upper
+/// 32 bits and the offset within that file in its lower 32 bits. Positions are therefore totally
+/// ordered in the same way as the transaction ids of a single GTID source, which lets these
+/// sources reuse the reclocking, resumption and rewind logic of GTID-based sources.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MySqlBinlogDetails {
+    /// The `@@server_uuid` of the server whose binary log the source reads.
+    #[proptest(strategy = "mz_proto::any_uuid()")]
+    pub server_uuid: Uuid,
+    /// The binlog file containing the effective snapshot point, e.g. `mysql-bin.000003`.
+    pub initial_file: String,
+    /// The offset of the effective snapshot point within `initial_file`. This serves the same
+    /// purpose as `initial_gtid_set` does for GTID-based sources.
+    pub initial_position: u64,
+}
+
+impl MySqlBinlogDetails {
+    /// Returns the frontier beyond the effective snapshot point of the source.
+    pub fn initial_frontier(&self) -> Result<Antichain<GtidPartition>, io::Error> {
+        self.frontier(&self.initial_file, self.initial_position)
+    }
+
+    /// Returns the frontier of all binlog events at or after `position` in `file`.
+    pub fn frontier(
+        &self,
+        file: &str,
+        position: u64,
+    ) -> Result<Antichain<GtidPartition>, io::Error> {
+        let mut frontier = Antichain::from_elem(self.timestamp(file, position)?);
+        if let Some(gap_upper) = self.server_uuid.backward_checked(1) {
+            frontier.insert(GtidPartition::new_range(
+                Uuid::nil(),
+                gap_upper,
+                GtidState::Absent,
+            ));
+        }
+        if let Some(gap_lower) = self.server_uuid.forward_checked(1) {
+            frontier.insert(GtidPartition::new_range(
+                gap_lower,
+                Uuid::max(),
+                GtidState::Absent,
+            ));
+        }
+        Ok(frontier)
+    }
+
+    /// Returns the timestamp of the binlog event at `position` in `file`.
+    pub fn timestamp(&self, file: &str, position: u64) -> Result<GtidPartition, io::Error> {
+        Ok(GtidPartition::new_singleton(
+            self.server_uuid,
+            GtidState::Active(self.packed_position(file, position)?),
+        ))
+    }
+
+    /// Packs `position` in `file` into the transaction id of the server's partition.
+    pub fn packed_position(&self, file: &str, position: u64) -> Result<NonZeroU64, io::Error> {
+        let (_, sequence) = split_binlog_file_name(file)?;
+        let position = u32::try_from(position).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("binlog position out of range: {}:{}", file, position),
+            )
+        })?;
+        let packed = (u64::from(sequence) << 32) | u64::from(position);
+        NonZeroU64::new(packed).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid binlog position: {}:{}", file, position),
+            )
+        })
+    }
+
+    /// Returns the binlog file and the offset within it that correspond to `state`, the
+    /// timestamp of the server's partition.
+    pub fn file_position(&self, state: NonZeroU64) -> Result<(String, u64), io::Error> {
+        let (basename, _) = split_binlog_file_name(&self.initial_file)?;
+        let sequence = state.get() >> 32;
+        let position = state.get() & u64::from(u32::MAX);
+        Ok((format!("{}.{:06}", basename, sequence), position))
+    }
+}
+
+/// Splits a binlog file name like `mysql-bin.000003` into its base name and sequence number.
+fn split_binlog_file_name(file: &str) -> Result<(&str, u32), io::Error> {
+    file.rsplit_once('.')
+        .and_then(|(basename, sequence)| Some((basename, sequence.parse().ok()?)))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid binlog file name: {}", file),
+            )
+        })
+}
+
+impl RustType<ProtoMySqlBinlogDetails> for MySqlBinlogDetails {
+    fn into_proto(&self) -> ProtoMySqlBinlogDetails {
+        ProtoMySqlBinlogDetails {
+            server_uuid: Some(self.server_uuid.into_proto()),
+            initial_file: self.initial_file.clone(),
+            initial_position: self.initial_position,
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlBinlogDetails) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlBinlogDetails {
+            server_uuid: proto
+                .server_uuid
+                .into_rust_if_some("ProtoMySqlBinlogDetails::server_uuid")?,
+            initial_file: proto.initial_file,
+            initial_position: proto.initial_position,
+        })
+    }
+}
+
+/// The flavor of the server a MySQL source replicates from.
+///
+/// MariaDB speaks the MySQL protocol, but identifies transactions by GTIDs of the form
+/// `domain-server-sequence` and reports them in different system variables than MySQL does.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub enum MySqlFlavor {
+    MySql,
+    MariaDb,
+}
+
+impl MySqlFlavor {
+    /// The system variable that holds the set of GTIDs the server has committed.
+    pub fn gtid_set_variable(&self) -> &'static str {
+        match self {
+            MySqlFlavor::MySql => "global.gtid_executed",
+            MySqlFlavor::MariaDb => "global.gtid_binlog_state",
+        }
+    }
+
+    /// Returns the frontier of all future GTIDs that are not contained in `gtid_set_str`, a
+    /// value of [`Self::gtid_set_variable`].
+    pub fn gtid_set_frontier(
+        &self,
+        gtid_set_str: &str,
+    ) -> Result<Antichain<GtidPartition>, io::Error> {
+        match self {
+            MySqlFlavor::MySql => gtid_set_frontier(gtid_set_str),
+            MySqlFlavor::MariaDb => mariadb_gtid_set_frontier(gtid_set_str),
+        }
+    }
+}
+
+impl RustType<i32> for MySqlFlavor {
+    fn into_proto(&self) -> i32 {
+        match self {
+            MySqlFlavor::MySql => ProtoMySqlFlavor::MySql.into(),
+            MySqlFlavor::MariaDb => ProtoMySqlFlavor::MariaDb.into(),
+        }
+    }
+
+    fn from_proto(proto: i32) -> Result<Self, TryFromProtoError> {
+        Ok(match ProtoMySqlFlavor::from_i32(proto) {
+            Some(ProtoMySqlFlavor::MySql) => MySqlFlavor::MySql,
+            Some(ProtoMySqlFlavor::MariaDb) => MySqlFlavor::MariaDb,
+            None => return Err(TryFromProtoError::UnknownEnumVariant("flavor".to_string())),
+        })
+    }
+}
+
 fn any_gtidset() -> impl Strategy<Value = String> {
     any::<(u128, u64)>().prop_map(|(uuid, tx_id)| format!("{}:{}", Uuid::from_u128(uuid), tx_id))
 }
 
+impl AlterCompatible for MySqlSourceDetails {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        let MySqlSourceDetails {
+            tables,
+            initial_gtid_set,
+            binlog,
+            flavor,
+        } = self;
+
+        // The tables of the source can only change by having their schemas
+        // refreshed, which must not change their position or make them
+        // incompatible with the data we have already ingested.
+        let compatibility_checks = [
+            (
+                initial_gtid_set == &other.initial_gtid_set,
+                "initial_gtid_set",
+            ),
+            (binlog == &other.binlog, "binlog"),
+            (flavor == &other.flavor, "flavor"),
+            (
+                tables.len() == other.tables.len()
+                    && tables
+                        .iter()
+                        .zip(other.tables.iter())
+                        .all(|(table, other)| table.determine_compatibility(other).is_ok()),
+                "tables",
+            ),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "MySqlSourceDetails incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl RustType<ProtoMySqlSourceDetails> for MySqlSourceDetails {
     fn into_proto(&self) -> ProtoMySqlSourceDetails {
         ProtoMySqlSourceDetails {
             tables: self.tables.iter().map(|t| t.into_proto()).collect(),
             initial_gtid_set: self.initial_gtid_set.clone(),
+            binlog: self.binlog.into_proto(),
+            flavor: self.flavor.into_proto(),
         }
     }
 
@@ -287,6 +530,8 @@ impl RustType<ProtoMySqlSourceDetails> for MySqlSourceDetails {
                 .map(mz_mysql_util::MySqlTableDesc::from_proto)
                 .collect::<Result<_, _>>()?,
             initial_gtid_set: proto.initial_gtid_set,
+            binlog: proto.binlog.into_rust()?,
+            flavor: MySqlFlavor::from_proto(proto.flavor)?,
         })
     }
 }
@@ -530,6 +775,140 @@ pub fn gtid_set_frontier(gtid_set_str: &str) -> Result<Antichain<GtidPartition>,
     Ok(partitions)
 }
 
+/// A MariaDB GTID, e.g. `0-1-42`.
+///
+/// Sequence numbers are only ordered within a replication domain, and are only guaranteed to be
+/// consecutive for the transactions a single server commits in that domain. Each pair of domain
+/// and server id is therefore tracked as its own partition of [`GtidPartition`], identified by
+/// the UUID returned by [`MariaDbGtid::partition`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct MariaDbGtid {
+    pub domain_id: u32,
+    pub server_id: u32,
+    pub seq_no: u64,
+}
+
+impl MariaDbGtid {
+    /// Returns the UUID of the partition for this GTID's domain and server.
+    pub fn partition(&self) -> Uuid {
+        Uuid::from_u128((u128::from(self.domain_id) << 32) | u128::from(self.server_id))
+    }
+
+    /// Returns the GTID with sequence number `seq_no` in the partition identified by `uuid`, or
+    /// `None` if `uuid` does not identify a MariaDB partition.
+    pub fn from_partition(uuid: &Uuid, seq_no: u64) -> Option<Self> {
+        let value = uuid.as_u128();
+        let domain_id = u32::try_from(value >> 32).ok()?;
+        let server_id = u32::try_from(value & u128::from(u32::MAX)).expect("masked");
+        Some(MariaDbGtid {
+            domain_id,
+            server_id,
+            seq_no,
+        })
+    }
+
+    /// Returns the timestamp of the transaction identified by this GTID.
+    pub fn timestamp(&self) -> Result<GtidPartition, io::Error> {
+        let seq_no = NonZeroU64::new(self.seq_no).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid sequence number in mariadb gtid: {}", self),
+            )
+        })?;
+        Ok(GtidPartition::new_singleton(
+            self.partition(),
+            GtidState::Active(seq_no),
+        ))
+    }
+}
+
+impl fmt::Display for MariaDbGtid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.domain_id, self.server_id, self.seq_no)
+    }
+}
+
+impl std::str::FromStr for MariaDbGtid {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid mariadb gtid: {}", s),
+            )
+        };
+        let mut parts = s.trim().split('-');
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(domain_id), Some(server_id), Some(seq_no), None) => Ok(MariaDbGtid {
+                domain_id: domain_id.parse().map_err(|_| invalid())?,
+                server_id: server_id.parse().map_err(|_| invalid())?,
+                seq_no: seq_no.parse().map_err(|_| invalid())?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Parses a MariaDB GTID list (e.g. from @@gtid_binlog_state), which contains the last GTID
+/// committed by each server in each replication domain.
+///
+/// Returns the frontier of all future GTIDs that are not contained in the provided list, with the
+/// same shape as the frontiers returned by [`gtid_set_frontier`].
+pub fn mariadb_gtid_set_frontier(gtid_list: &str) -> Result<Antichain<GtidPartition>, io::Error> {
+    let mut gtids = BTreeMap::new();
+    for gtid_str in gtid_list.split(',') {
+        if gtid_str.trim().is_empty() {
+            continue;
+        }
+        let gtid: MariaDbGtid = gtid_str.parse()?;
+        if gtids.insert(gtid.partition(), gtid.seq_no).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "duplicate domain and server in mariadb gtid list: {}",
+                    gtid_list
+                ),
+            ));
+        }
+    }
+
+    let mut partitions = Antichain::new();
+    let mut gap_lower = Some(Uuid::nil());
+    for (uuid, seq_no) in gtids {
+        if let Some(gap_upper) = uuid.backward_checked(1) {
+            let gap_lower = gap_lower.expect("uuids are in order");
+            if gap_upper >= gap_lower {
+                partitions.insert(GtidPartition::new_range(
+                    gap_lower,
+                    gap_upper,
+                    GtidState::Absent,
+                ));
+            }
+        }
+        gap_lower = uuid.forward_checked(1);
+        let next = seq_no
+            .checked_add(1)
+            .and_then(NonZeroU64::new)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("mariadb gtid sequence number out of range: {}", seq_no),
+                )
+            })?;
+        partitions.insert(GtidPartition::new_singleton(uuid, GtidState::Active(next)));
+    }
+    if let Some(gap_lower) = gap_lower {
+        partitions.insert(GtidPartition::new_range(
+            gap_lower,
+            Uuid::max(),
+            GtidState::Absent,
+        ));
+    }
+
+    Ok(partitions)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -627,4 +1006,118 @@ mod tests {
             ))
         );
     }
+
+    #[mz_ore::test]
+    fn test_binlog_position_frontier() {
+        let details = MySqlBinlogDetails {
+            server_uuid: Uuid::parse_str("14c1b43a-eb64-11eb-8a9a-0242ac130002").unwrap(),
+            initial_file: "mysql-bin.000003".into(),
+            initial_position: 157,
+        };
+        let result = details.initial_frontier().unwrap();
+        assert_eq!(
+            result,
+            Antichain::from_iter(vec![
+                GtidPartition::new_range(
+                    Uuid::nil(),
+                    Uuid::parse_str("14c1b43a-eb64-11eb-8a9a-0242ac130001").unwrap(),
+                    GtidState::Absent,
+                ),
+                GtidPartition::new_singleton(
+                    details.server_uuid,
+                    GtidState::Active(NonZeroU64::new((3 << 32) | 157).unwrap()),
+                ),
+                GtidPartition::new_range(
+                    Uuid::parse_str("14c1b43a-eb64-11eb-8a9a-0242ac130003").unwrap(),
+                    Uuid::max(),
+                    GtidState::Absent,
+                ),
+            ])
+        );
+
+        // Positions in later files are beyond all positions in earlier files.
+        let earlier = details.timestamp("mysql-bin.000003", 1_000_000).unwrap();
+        let later = details.timestamp("mysql-bin.000004", 4).unwrap();
+        assert!(earlier.less_than(&later));
+
+        match later.timestamp() {
+            GtidState::Active(state) => assert_eq!(
+                details.file_position(*state).unwrap(),
+                ("mysql-bin.000004".into(), 4)
+            ),
+            GtidState::Absent => panic!("expected an active timestamp"),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_binlog_position_invalid() {
+        let details = MySqlBinlogDetails {
+            server_uuid: Uuid::nil(),
+            initial_file: "mysql-bin.000003".into(),
+            initial_position: 157,
+        };
+        assert!(details.timestamp("mysql-bin", 4).is_err());
+        assert!(details.timestamp("mysql-bin.000003", u64::MAX).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_mariadb_gtid_set_frontier() {
+        let result = mariadb_gtid_set_frontier("0-2-7, 0-1-12,1-1-3").unwrap();
+        let partition = |domain_id, server_id| {
+            MariaDbGtid {
+                domain_id,
+                server_id,
+                seq_no: 0,
+            }
+            .partition()
+        };
+        assert_eq!(
+            result,
+            Antichain::from_iter(vec![
+                GtidPartition::new_range(Uuid::nil(), Uuid::from_u128(0), GtidState::Absent,),
+                GtidPartition::new_singleton(
+                    partition(0, 1),
+                    GtidState::Active(NonZeroU64::new(13).unwrap()),
+                ),
+                GtidPartition::new_singleton(
+                    partition(0, 2),
+                    GtidState::Active(NonZeroU64::new(8).unwrap()),
+                ),
+                GtidPartition::new_range(
+                    Uuid::from_u128(3),
+                    Uuid::from_u128(1 << 32),
+                    GtidState::Absent,
+                ),
+                GtidPartition::new_singleton(
+                    partition(1, 1),
+                    GtidState::Active(NonZeroU64::new(4).unwrap()),
+                ),
+                GtidPartition::new_range(
+                    Uuid::from_u128((1 << 32) | 2),
+                    Uuid::max(),
+                    GtidState::Absent,
+                ),
+            ])
+        );
+
+        let gtid = MariaDbGtid::from_partition(&partition(1, 1), 4).unwrap();
+        assert_eq!(gtid.to_string(), "1-1-4");
+        assert_eq!(
+            mariadb_gtid_set_frontier("").unwrap(),
+            Antichain::from_elem(GtidPartition::new_range(
+                Uuid::nil(),
+                Uuid::max(),
+                GtidState::Absent,
+            ))
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_mariadb_gtid_set_frontier_invalid() {
+        assert!(mariadb_gtid_set_frontier("0-1").is_err());
+        assert!(mariadb_gtid_set_frontier("0-1-2-3").is_err());
+        assert!(mariadb_gtid_set_frontier("0-1-x").is_err());
+        assert!(mariadb_gtid_set_frontier("0-1-2,0-1-3").is_err());
+        assert!(mariadb_gtid_set_frontier("14c1b43a-eb64-11eb-8a9a-0242ac130002:1").is_err());
+    }
 }
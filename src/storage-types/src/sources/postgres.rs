@@ -9,7 +9,7 @@
 
 //! Types related to postgres sources
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use itertools::Itertools;
 use mz_expr::MirScalarExpr;
@@ -45,8 +45,20 @@ pub struct PostgresSourceConnection<C: ConnectionAccess = InlinedConnection> {
         strategy = "proptest::collection::btree_map(any::<usize>(), proptest::collection::vec((any::<CastType>(), any::<MirScalarExpr>()), 0..4), 0..4)"
     )]
     pub table_casts: BTreeMap<usize, Vec<(CastType, MirScalarExpr)>>,
+    /// The predicates that the text encoded rows must satisfy to be ingested,
+    /// keyed by their position in the source.
+    #[proptest(
+        strategy = "proptest::collection::btree_map(any::<usize>(), any::<MirScalarExpr>(), 0..4)"
+    )]
+    pub table_filters: BTreeMap<usize, MirScalarExpr>,
     pub publication: String,
     pub publication_details: PostgresSourcePublicationDetails,
+    /// The output indexes of the tables whose current contents are retracted,
+    /// rather than permanently errored, when they are truncated upstream.
+    pub retract_on_truncate: BTreeSet<usize>,
+    /// The output indexes of the tables whose changes are applied by primary
+    /// key, which lets them be ingested without `REPLICA IDENTITY FULL`.
+    pub upsert_tables: BTreeSet<usize>,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
@@ -57,16 +69,22 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
             connection_id,
             connection,
             table_casts,
+            table_filters,
             publication,
             publication_details,
+            retract_on_truncate,
+            upsert_tables,
         } = self;
 
         PostgresSourceConnection {
             connection_id,
             connection: r.resolve_connection(connection).unwrap_pg(),
             table_casts,
+            table_filters,
             publication,
             publication_details,
+            retract_on_truncate,
+            upsert_tables,
         }
     }
 }
@@ -128,8 +146,14 @@ impl<C: ConnectionAccess> AlterCompatible for PostgresSourceConnection<C> {
             // then the subsource itself understands how to cast its data from
             // the source.
             table_casts: _,
+            // Like table casts, these are keyed by output index.
+            table_filters: _,
             publication,
             publication_details,
+            // Like table casts, these are keyed by output index and change
+            // whenever subsources are added.
+            retract_on_truncate: _,
+            upsert_tables: _,
         } = self;
 
         let compatibility_checks = [
@@ -169,6 +193,9 @@ pub enum CastType {
     Natural,
     /// This cast was generated with the `TEXT COLUMNS` option.
     Text,
+    /// This column is not ingested because it was left out of the `INCLUDE
+    /// COLUMNS` option; its cast is only evaluated by the table's row filter.
+    Excluded,
 }
 
 impl RustType<proto_postgres_source_connection::ProtoCastType> for CastType {
@@ -178,6 +205,7 @@ impl RustType<proto_postgres_source_connection::ProtoCastType> for CastType {
             kind: Some(match self {
                 CastType::Natural => Natural(()),
                 CastType::Text => Text(()),
+                CastType::Excluded => Excluded(()),
             }),
         }
     }
@@ -189,6 +217,7 @@ impl RustType<proto_postgres_source_connection::ProtoCastType> for CastType {
         Ok(match proto.kind {
             Some(Natural(())) => CastType::Natural,
             Some(Text(())) => CastType::Text,
+            Some(Excluded(())) => CastType::Excluded,
             None => {
                 return Err(TryFromProtoError::missing_field(
                     "ProtoWindowFrameUnits::kind",
@@ -200,7 +229,7 @@ impl RustType<proto_postgres_source_connection::ProtoCastType> for CastType {
 
 impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
     fn into_proto(&self) -> ProtoPostgresSourceConnection {
-        use proto_postgres_source_connection::ProtoPostgresTableCast;
+        use proto_postgres_source_connection::{ProtoPostgresTableCast, ProtoPostgresTableFilter};
         let mut table_casts = Vec::with_capacity(self.table_casts.len());
         let mut table_cast_pos = Vec::with_capacity(self.table_casts.len());
         for (pos, table_cast_inner) in self.table_casts.iter() {
@@ -226,6 +255,26 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
             details: Some(self.publication_details.into_proto()),
             table_casts,
             table_cast_pos,
+            table_filters: self
+                .table_filters
+                .iter()
+                .map(|(pos, filter)| ProtoPostgresTableFilter {
+                    output_index: mz_ore::cast::usize_to_u64(*pos),
+                    filter: Some(filter.into_proto()),
+                })
+                .collect(),
+            retract_on_truncate: self
+                .retract_on_truncate
+                .iter()
+                .copied()
+                .map(mz_ore::cast::usize_to_u64)
+                .collect(),
+            upsert_tables: self
+                .upsert_tables
+                .iter()
+                .copied()
+                .map(mz_ore::cast::usize_to_u64)
+                .collect(),
         }
     }
 
@@ -260,6 +309,16 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
             table_casts.insert(mz_ore::cast::u64_to_usize(pos), column_casts);
         }
 
+        let mut table_filters = BTreeMap::new();
+        for table_filter in proto.table_filters {
+            table_filters.insert(
+                mz_ore::cast::u64_to_usize(table_filter.output_index),
+                table_filter
+                    .filter
+                    .into_rust_if_some("ProtoPostgresTableFilter::filter")?,
+            );
+        }
+
         Ok(PostgresSourceConnection {
             connection: proto
                 .connection
@@ -272,6 +331,17 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
                 .details
                 .into_rust_if_some("ProtoPostgresSourceConnection::details")?,
             table_casts,
+            table_filters,
+            retract_on_truncate: proto
+                .retract_on_truncate
+                .into_iter()
+                .map(mz_ore::cast::u64_to_usize)
+                .collect(),
+            upsert_tables: proto
+                .upsert_tables
+                .into_iter()
+                .map(mz_ore::cast::u64_to_usize)
+                .collect(),
         })
     }
 }
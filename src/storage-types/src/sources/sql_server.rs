@@ -0,0 +1,232 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to SQL Server sources
+
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, Row, ScalarType};
+use mz_sql_parser::ast::UnresolvedItemName;
+use mz_sql_server_util::Lsn;
+use once_cell::sync::Lazy;
+use proptest::prelude::any;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::controller::AlterError;
+use crate::sources::{SourceConnection, SourceTimestamp};
+use crate::AlterCompatible;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.sources.sql_server.rs"
+));
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerSourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: GlobalId,
+    pub connection: C::SqlServer,
+    pub details: SqlServerSourceDetails,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<SqlServerSourceConnection, R>
+    for SqlServerSourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> SqlServerSourceConnection {
+        let SqlServerSourceConnection {
+            connection_id,
+            connection,
+            details,
+        } = self;
+
+        SqlServerSourceConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_sql_server(),
+            details,
+        }
+    }
+}
+
+pub static SQL_SERVER_PROGRESS_DESC: Lazy<RelationDesc> =
+    Lazy::new(|| RelationDesc::empty().with_column("lsn", ScalarType::Bytes.nullable(false)));
+
+impl<C: ConnectionAccess> SourceConnection for SqlServerSourceConnection<C> {
+    fn name(&self) -> &'static str {
+        "sql-server"
+    }
+
+    fn upstream_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn value_desc(&self) -> RelationDesc {
+        // The SQL Server source only outputs data to its subsources. The catalog object
+        // representing the source itself is just an empty relation with no columns
+        RelationDesc::empty()
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        SQL_SERVER_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<GlobalId> {
+        Some(self.connection_id)
+    }
+
+    fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+        vec![]
+    }
+
+    fn output_idx_for_name(&self, name: &UnresolvedItemName) -> Option<usize> {
+        self.details.output_idx_for_name(name)
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for SqlServerSourceConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let SqlServerSourceConnection {
+            connection_id,
+            connection,
+            details,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                connection.alter_compatible(id, &other.connection).is_ok(),
+                "connection",
+            ),
+            (details == &other.details, "details"),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "SqlServerSourceConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(AlterError { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RustType<ProtoSqlServerSourceConnection> for SqlServerSourceConnection {
+    fn into_proto(&self) -> ProtoSqlServerSourceConnection {
+        ProtoSqlServerSourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            details: Some(self.details.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerSourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoSqlServerSourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoSqlServerSourceConnection::connection_id")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoSqlServerSourceConnection::details")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerSourceDetails {
+    /// The name of the upstream database.
+    pub database: String,
+    #[proptest(
+        strategy = "proptest::collection::vec(any::<mz_sql_server_util::SqlServerTableDesc>(), 0..4)"
+    )]
+    pub tables: Vec<mz_sql_server_util::SqlServerTableDesc>,
+    /// The end of the transaction log at the time the source was created. This
+    /// is used as the effective snapshot point, to ensure consistency if the
+    /// source is interrupted but commits one or more tables before the initial
+    /// snapshot of all tables is complete.
+    pub initial_lsn: Lsn,
+}
+
+impl SqlServerSourceDetails {
+    pub fn output_idx_for_name(&self, name: &UnresolvedItemName) -> Option<usize> {
+        let (database, schema_name, name) = match &name.0[..] {
+            [database, schema_name, name] => {
+                (database.as_str(), schema_name.as_str(), name.as_str())
+            }
+            _ => return None,
+        };
+
+        if database != self.database {
+            return None;
+        }
+
+        self.tables
+            .iter()
+            .position(|t| t.schema_name == schema_name && t.name == name)
+            .map(|idx| idx + 1)
+    }
+}
+
+impl RustType<ProtoSqlServerSourceDetails> for SqlServerSourceDetails {
+    fn into_proto(&self) -> ProtoSqlServerSourceDetails {
+        ProtoSqlServerSourceDetails {
+            database: self.database.clone(),
+            tables: self.tables.iter().map(|t| t.into_proto()).collect(),
+            initial_lsn: Some(self.initial_lsn.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerSourceDetails {
+            database: proto.database,
+            tables: proto
+                .tables
+                .into_iter()
+                .map(mz_sql_server_util::SqlServerTableDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+            initial_lsn: proto
+                .initial_lsn
+                .into_rust_if_some("ProtoSqlServerSourceDetails::initial_lsn")?,
+        })
+    }
+}
+
+impl SourceTimestamp for Lsn {
+    fn encode_row(&self) -> Row {
+        Row::pack([Datum::Bytes(&self.as_bytes())])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        match (datums.next(), datums.next()) {
+            (Some(Datum::Bytes(bytes)), None) => {
+                Lsn::from_bytes(bytes).unwrap_or_else(|e| panic!("invalid row {row:?}: {e}"))
+            }
+            _ => panic!("invalid row {row:?}"),
+        }
+    }
+}
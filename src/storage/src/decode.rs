@@ -25,7 +25,10 @@ use mz_ore::future::InTask;
 use mz_repr::{Datum, Diff, Row};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{CsrConnectError, DecodeError, DecodeErrorKind};
-use mz_storage_types::sources::encoding::{AvroEncoding, DataEncoding, RegexEncoding};
+use mz_storage_types::sources::encoding::{
+    AvroEncoding, DataEncoding, DecodeErrorPolicy, JsonEncoding, JsonFraming, RegexEncoding,
+};
+use mz_storage_types::sources::SourceTimestamp;
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
@@ -337,7 +340,7 @@ async fn get_decoder(
         }
         DataEncoding::Text
         | DataEncoding::Bytes
-        | DataEncoding::Json
+        | DataEncoding::Json(_)
         | DataEncoding::Protobuf(_)
         | DataEncoding::Regex(_) => {
             let after_delimiting = match encoding {
@@ -351,7 +354,7 @@ async fn get_decoder(
                     ))
                 }
                 DataEncoding::Bytes => PreDelimitedFormat::Bytes,
-                DataEncoding::Json => PreDelimitedFormat::Json,
+                DataEncoding::Json(_) => PreDelimitedFormat::Json,
                 DataEncoding::Text => PreDelimitedFormat::Text,
                 _ => unreachable!(),
             };
@@ -405,9 +408,48 @@ async fn decode_delimited(
     }))
 }
 
+/// Decodes an already delimited record that may contain several JSON values,
+/// as described by `framing`, pushing one result per value into `values`.
+async fn decode_delimited_framed(
+    decoder: &mut DataDecoder,
+    framing: JsonFraming,
+    buf: &[u8],
+    values: &mut Vec<Result<Option<Row>, DecodeError>>,
+) -> Result<(), CsrConnectError> {
+    match framing {
+        JsonFraming::Single => values.push(decode_delimited(decoder, buf).await?),
+        JsonFraming::Lines => {
+            for line in buf.split(|&byte| byte == b'\n') {
+                // Blank lines, including the one following a trailing
+                // newline, do not contain a value.
+                if line.iter().all(|byte| byte.is_ascii_whitespace()) {
+                    continue;
+                }
+                values.push(decode_delimited(decoder, line).await?);
+            }
+        }
+        // Like the webhook source's `JSON ARRAY` format, a value that is not
+        // an array is decoded as a single row.
+        JsonFraming::Array => match decode_delimited(decoder, buf).await? {
+            Ok(Some(row)) => match row.unpack_first() {
+                Datum::List(elements) => values.extend(
+                    elements
+                        .iter()
+                        .map(|element| Ok(Some(Row::pack_slice(&[element])))),
+                ),
+                _ => values.push(Ok(Some(row))),
+            },
+            result => values.push(result),
+        },
+    }
+    Ok(())
+}
+
 /// Decode already delimited records of data.
 ///
-/// Precondition: each record in the stream has at most one key and at most one value.
+/// Precondition: each record in the stream has at most one key and at most one value,
+/// except for JSON values with `ARRAY` or `LINES` framing, which may decode to any number
+/// of values that all share the record's key and metadata.
 /// This function is useful mainly for decoding data from systems like Kafka,
 /// that have already separated the stream into records/messages/etc. before we
 /// decode them.
@@ -417,15 +459,23 @@ async fn decode_delimited(
 /// often lets us, for example, detect when Avro decoding has gone off the rails
 /// (which is not always possible otherwise, since often gibberish strings can be interpreted as Avro,
 ///  so the only signal is how many bytes you managed to decode).
-pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
+///
+/// Records that fail to decode are handled according to `decode_error_policy`.
+/// With [`DecodeErrorPolicy::DeadLetter`], they are dropped from the decoded
+/// collection, and their raw key and value, the decoding error and their position
+/// in the source are emitted as rows of the returned dead-letter collection
+/// instead, which is otherwise empty.
+pub fn render_decode_delimited<G: Scope, FromTime: SourceTimestamp>(
     input: &Collection<G, SourceOutput<FromTime>, Diff>,
     key_encoding: Option<DataEncoding>,
     value_encoding: DataEncoding,
+    decode_error_policy: DecodeErrorPolicy,
     debug_name: String,
     metrics: DecodeMetricDefs,
     storage_configuration: StorageConfiguration,
 ) -> (
     Collection<G, DecodeResult<FromTime>, Diff>,
+    Collection<G, Row, Diff>,
     Stream<G, HealthStatusMessage>,
 ) {
     let op_name = format!(
@@ -436,16 +486,25 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
             .unwrap_or(""),
         value_encoding.op_name()
     );
+    let value_framing = match &value_encoding {
+        DataEncoding::Json(JsonEncoding { framing }) => *framing,
+        _ => JsonFraming::Single,
+    };
     let dist = |(x, _, _): &(SourceOutput<FromTime>, _, _)| x.value.hashed();
 
     let mut builder = AsyncOperatorBuilder::new(op_name, input.scope());
 
     let (mut output_handle, output) = builder.new_output();
-    let mut input = builder.new_input_for(&input.inner, Exchange::new(dist), &output_handle);
+    let (mut dead_letter_handle, dead_letters) = builder.new_output();
+    let mut input = builder.new_input_for_many(
+        &input.inner,
+        Exchange::new(dist),
+        [&output_handle, &dead_letter_handle],
+    );
 
     let (_, transient_errors) = builder.build_fallible(move |caps| {
         Box::pin(async move {
-            let [cap_set]: &mut [_; 1] = caps.try_into().unwrap();
+            let [cap_set, dead_letter_cap_set]: &mut [_; 2] = caps.try_into().unwrap();
 
             let mut key_decoder = match key_encoding {
                 Some(encoding) => Some(
@@ -471,10 +530,13 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
             .await?;
 
             let mut output_container = Vec::new();
+            let mut dead_letter_container = Vec::new();
+            let mut values = Vec::new();
+            let mut row_buf = Row::default();
 
             while let Some(event) = input.next().await {
                 match event {
-                    AsyncEvent::Data(cap, data) => {
+                    AsyncEvent::Data([cap, dead_letter_cap], data) => {
                         let mut n_errors = 0;
                         let mut n_successes = 0;
                         for (output, ts, diff) in data.iter() {
@@ -491,27 +553,63 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
                                 None => None,
                             };
 
-                            let value = match output.value.unpack_first() {
-                                Datum::Bytes(buf) => {
-                                    decode_delimited(&mut value_decoder, buf).await?.transpose()
-                                }
+                            let value_buf = match output.value.unpack_first() {
+                                Datum::Bytes(buf) => Some(buf),
                                 Datum::Null => None,
                                 d => unreachable!("invalid datum: {d}"),
                             };
-
-                            if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
-                                n_errors += 1;
-                            } else if matches!(&value, Some(Ok(_))) {
-                                n_successes += 1;
+                            match value_buf {
+                                Some(buf) => {
+                                    decode_delimited_framed(
+                                        &mut value_decoder,
+                                        value_framing,
+                                        buf,
+                                        &mut values,
+                                    )
+                                    .await?
+                                }
+                                None => values.push(Ok(None)),
                             }
 
-                            let result = DecodeResult {
-                                key,
-                                value,
-                                metadata: output.metadata.clone(),
-                                from_time: output.from_time.clone(),
-                            };
-                            output_container.push((result, ts.clone(), *diff));
+                            for value in values.drain(..) {
+                                let value = value.transpose();
+                                // Like the envelopes, prefer the value's error
+                                // over the key's.
+                                let err = match (&key, &value) {
+                                    (_, Some(Err(err))) | (Some(Err(err)), _) => Some(err),
+                                    _ => None,
+                                };
+                                if let Some(err) = err {
+                                    n_errors += 1;
+                                    match decode_error_policy {
+                                        DecodeErrorPolicy::Error => {}
+                                        DecodeErrorPolicy::Skip => continue,
+                                        DecodeErrorPolicy::DeadLetter => {
+                                            let mut packer = row_buf.packer();
+                                            packer.push(Datum::from(key_buf));
+                                            packer.push(Datum::from(value_buf));
+                                            packer.push(Datum::String(&err.kind.to_string()));
+                                            packer.extend_by_row(&output.from_time.encode_row());
+                                            dead_letter_container.push((
+                                                row_buf.clone(),
+                                                ts.clone(),
+                                                *diff,
+                                            ));
+                                            continue;
+                                        }
+                                    }
+                                } else if matches!(&value, Some(Ok(_))) {
+                                    n_successes += 1;
+                                }
+
+                                let result = DecodeResult {
+                                    key: key.clone(),
+                                    value,
+                                    metadata: output.metadata.clone(),
+                                    from_time: output.from_time.clone(),
+                                };
+                                output_container.push((result, ts.clone(), *diff));
+                            }
                         }
 
                         // Matching historical practice, we only log metrics on the value decoder.
@@ -525,8 +623,14 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
                         output_handle
                             .give_container(&cap, &mut output_container)
                             .await;
+                        dead_letter_handle
+                            .give_container(&dead_letter_cap, &mut dead_letter_container)
+                            .await;
+                    }
+                    AsyncEvent::Progress(frontier) => {
+                        cap_set.downgrade(frontier.iter());
+                        dead_letter_cap_set.downgrade(frontier.iter());
                     }
-                    AsyncEvent::Progress(frontier) => cap_set.downgrade(frontier.iter()),
                 }
             }
 
@@ -547,5 +651,5 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
         }
     });
 
-    (output.as_collection(), health)
+    (output.as_collection(), dead_letters.as_collection(), health)
 }
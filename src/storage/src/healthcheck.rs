@@ -53,6 +53,11 @@ pub enum StatusNamespace {
     Kafka,
     Postgres,
     MySql,
+    File,
+    Kinesis,
+    SqlServer,
+    /// Changes to the schema of upstream tables that require attention.
+    UpstreamSchema,
     Ssh,
     Upsert,
     Decode,
@@ -73,6 +78,10 @@ impl fmt::Display for StatusNamespace {
             Kafka => write!(f, "kafka"),
             Postgres => write!(f, "postgres"),
             MySql => write!(f, "mysql"),
+            File => write!(f, "file"),
+            Kinesis => write!(f, "kinesis"),
+            SqlServer => write!(f, "sql-server"),
+            UpstreamSchema => write!(f, "upstream-schema"),
             Ssh => write!(f, "ssh"),
             Upsert => write!(f, "upsert"),
             Decode => write!(f, "decode"),
@@ -135,9 +144,14 @@ impl PerWorkerHealthStatus {
                     }
                     HealthStatusUpdate::Running => {
                         if !ns.is_sidechannel() {
-                            output_status = OverallStatus::Running;
+                            output_status = OverallStatus::Running {
+                                hints: BTreeSet::new(),
+                            };
                         }
                     }
+                    HealthStatusUpdate::Notice { hint } => {
+                        hints.insert(hint.to_string());
+                    }
                 }
             }
         }
@@ -150,6 +164,11 @@ impl PerWorkerHealthStatus {
                 hints,
                 namespaced_errors,
             }
+        } else if let OverallStatus::Running {
+            hints: running_hints,
+        } = &mut output_status
+        {
+            *running_hints = hints;
         }
 
         output_status
@@ -159,7 +178,9 @@ impl PerWorkerHealthStatus {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum OverallStatus {
     Starting,
-    Running,
+    Running {
+        hints: BTreeSet<String>,
+    },
     Stalled {
         error: String,
         hints: BTreeSet<String>,
@@ -174,7 +195,7 @@ impl OverallStatus {
     /// The user-readable error string, if there is one.
     pub(crate) fn error(&self) -> Option<&str> {
         match self {
-            OverallStatus::Starting | OverallStatus::Running => None,
+            OverallStatus::Starting | OverallStatus::Running { .. } => None,
             OverallStatus::Stalled { error, .. } | OverallStatus::Ceased { error, .. } => {
                 Some(error)
             }
@@ -184,7 +205,9 @@ impl OverallStatus {
     /// A set of namespaced errors, if there are any.
     pub(crate) fn errors(&self) -> Option<&BTreeMap<StatusNamespace, String>> {
         match self {
-            OverallStatus::Starting | OverallStatus::Running | OverallStatus::Ceased { .. } => None,
+            OverallStatus::Starting
+            | OverallStatus::Running { .. }
+            | OverallStatus::Ceased { .. } => None,
             OverallStatus::Stalled {
                 namespaced_errors, ..
             } => Some(namespaced_errors),
@@ -194,10 +217,10 @@ impl OverallStatus {
     /// A set of hints, if there are any.
     pub(crate) fn hints(&self) -> BTreeSet<String> {
         match self {
-            OverallStatus::Starting | OverallStatus::Running | OverallStatus::Ceased { .. } => {
-                BTreeSet::new()
+            OverallStatus::Starting | OverallStatus::Ceased { .. } => BTreeSet::new(),
+            OverallStatus::Running { hints } | OverallStatus::Stalled { hints, .. } => {
+                hints.clone()
             }
-            OverallStatus::Stalled { hints, .. } => hints.clone(),
         }
     }
 }
@@ -206,7 +229,7 @@ impl<'a> From<&'a OverallStatus> for Status {
     fn from(val: &'a OverallStatus) -> Self {
         match val {
             OverallStatus::Starting => Status::Starting,
-            OverallStatus::Running => Status::Running,
+            OverallStatus::Running { .. } => Status::Running,
             OverallStatus::Stalled { .. } => Status::Stalled,
             OverallStatus::Ceased { .. } => Status::Ceased,
         }
@@ -532,6 +555,11 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum HealthStatusUpdate {
     Running,
+    /// The object is healthy but requires the attention of the user, which is
+    /// described by `hint`.
+    Notice {
+        hint: String,
+    },
     Stalled {
         error: String,
         hint: Option<String>,
@@ -548,6 +576,11 @@ impl HealthStatusUpdate {
         HealthStatusUpdate::Running
     }
 
+    /// Generates a notice [`HealthStatusUpdate`] with `hint`.
+    pub(crate) fn notice(hint: String) -> Self {
+        HealthStatusUpdate::Notice { hint }
+    }
+
     /// Generates a non-halting [`HealthStatusUpdate`] with `update`.
     pub(crate) fn stalled(error: String, hint: Option<String>) -> Self {
         HealthStatusUpdate::Stalled {
@@ -576,6 +609,7 @@ impl HealthStatusUpdate {
     pub(crate) fn should_halt(&self) -> bool {
         match self {
             HealthStatusUpdate::Running |
+            HealthStatusUpdate::Notice { .. } |
             // HealthStatusUpdate::Ceased should never halt because it can occur
             // at the subsource level and should not cause the entire dataflow
             // to halt. Instead, the dataflow itself should handle shutting
@@ -968,6 +1002,78 @@ mod tests {
         );
     }
 
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    fn test_health_operator_notices() {
+        use Step::*;
+
+        health_operator_runner(
+            2,
+            1,
+            true,
+            vec![
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Starting,
+                    ..Default::default()
+                }]),
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::Generator,
+                    input_index: 0,
+                    update: HealthStatusUpdate::running(),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Running,
+                    ..Default::default()
+                }]),
+                // Notices are reported as hints without affecting the status.
+                Update(TestUpdate {
+                    worker_id: 1,
+                    namespace: StatusNamespace::UpstreamSchema,
+                    input_index: 0,
+                    update: HealthStatusUpdate::notice("hint1".to_string()),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Running,
+                    hint: Some("hint1".to_string()),
+                    ..Default::default()
+                }]),
+                // Updates in other namespaces don't clear notices.
+                Update(TestUpdate {
+                    worker_id: 1,
+                    namespace: StatusNamespace::Generator,
+                    input_index: 0,
+                    update: HealthStatusUpdate::stalled(
+                        "uhoh".to_string(),
+                        Some("hint2".to_string()),
+                    ),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Stalled,
+                    error: Some("generator: uhoh".to_string()),
+                    errors: Some("generator: uhoh".to_string()),
+                    hint: Some("hint1, hint2".to_string()),
+                }]),
+                Update(TestUpdate {
+                    worker_id: 1,
+                    namespace: StatusNamespace::Generator,
+                    input_index: 0,
+                    update: HealthStatusUpdate::running(),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Running,
+                    hint: Some("hint1".to_string()),
+                    ..Default::default()
+                }]),
+            ],
+        );
+    }
+
     // The below is ALL test infrastructure for the above
 
     use timely::dataflow::operators::exchange::Exchange;
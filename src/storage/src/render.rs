@@ -212,6 +212,7 @@ use timely::progress::Antichain;
 use timely::worker::Worker as TimelyWorker;
 
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::KafkaTopicPatternSource;
 use crate::storage_state::StorageState;
 
 mod persist_sink;
@@ -249,6 +250,20 @@ pub fn build_ingestion_dataflow<A: Allocate>(
 
             let connection = description.desc.connection.clone();
             let (mut outputs, source_health, source_tokens) = match connection {
+                GenericSourceConnection::Kafka(c) if c.topic_is_pattern => {
+                    crate::render::sources::render_source(
+                        mz_scope,
+                        &debug_name,
+                        primary_source_id,
+                        KafkaTopicPatternSource(c),
+                        description.clone(),
+                        as_of.clone(),
+                        resume_uppers.clone(),
+                        source_resume_uppers,
+                        &feedback,
+                        storage_state,
+                    )
+                }
                 GenericSourceConnection::Kafka(c) => crate::render::sources::render_source(
                     mz_scope,
                     &debug_name,
@@ -297,6 +312,42 @@ pub fn build_ingestion_dataflow<A: Allocate>(
                     &feedback,
                     storage_state,
                 ),
+                GenericSourceConnection::File(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    primary_source_id,
+                    c,
+                    description.clone(),
+                    as_of.clone(),
+                    resume_uppers.clone(),
+                    source_resume_uppers,
+                    &feedback,
+                    storage_state,
+                ),
+                GenericSourceConnection::Kinesis(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    primary_source_id,
+                    c,
+                    description.clone(),
+                    as_of.clone(),
+                    resume_uppers.clone(),
+                    source_resume_uppers,
+                    &feedback,
+                    storage_state,
+                ),
+                GenericSourceConnection::SqlServer(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    primary_source_id,
+                    c,
+                    description.clone(),
+                    as_of.clone(),
+                    resume_uppers.clone(),
+                    source_resume_uppers,
+                    &feedback,
+                    storage_state,
+                ),
             };
             tokens.extend(source_tokens);
 
@@ -9,14 +9,13 @@
 
 //! Logic related to the creation of dataflow sinks.
 
-use std::cell::RefCell;
-use std::rc::Rc;
 use std::sync::Arc;
 
 use differential_dataflow::operators::arrange::Arrange;
 use differential_dataflow::trace::implementations::ord_neu::ColValSpine;
 use differential_dataflow::{AsCollection, Collection, Hashable};
-use mz_interchange::envelopes::{combine_at_timestamp, dbz_format};
+use mz_interchange::avro::DiffPair;
+use mz_interchange::envelopes::combine_at_timestamp;
 use mz_persist_client::operators::shard_source::SnapshotMode;
 use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
 use mz_storage_operators::persist_source;
@@ -87,7 +86,7 @@ fn apply_sink_envelope<G>(
     sink: &StorageSinkDesc<MetadataFilled, mz_repr::Timestamp>,
     sink_render: &Box<dyn SinkRender<G>>,
     collection: Collection<G, Row, Diff>,
-) -> Collection<G, (Option<Row>, Option<Row>), Diff>
+) -> Collection<G, (Option<Row>, DiffPair<Row>), Diff>
 where
     G: Scope<Timestamp = Timestamp>,
 {
@@ -152,67 +151,38 @@ where
         }
     }
 
-    // Apply the envelope.
-    // * "Debezium" consolidates the stream, sorts it by time, and produces DiffPairs from it.
-    //   It then renders those as Avro.
-    // * Upsert" does the same, except at the last step, it renders the diff pair in upsert format.
-    //   (As part of doing so, it asserts that there are not multiple conflicting values at the same timestamp)
-    let collection = match sink.envelope {
-        SinkEnvelope::Debezium => {
-            // Allow access to `arrange_named` because we cannot access Mz's wrapper from here.
-            // TODO(#17413): Revisit with cluster unification.
-            #[allow(clippy::disallowed_methods)]
-            let combined = combine_at_timestamp(
-                keyed.arrange_named::<ColValSpine<_, _, _, _>>("Arrange Debezium"),
-            );
-
-            // if there is no user-specified key, remove the synthetic
-            // distribution key again
-            let user_key_indices = sink_render.get_key_indices();
-            let combined = if user_key_indices.is_some() {
-                combined
-            } else {
-                combined.map(|(_key, value)| (None, value))
-            };
-
-            // This has to be an `Rc<RefCell<...>>` because the inner closure (passed to `Iterator::map`) references it, and it might outlive the outer closure.
-            let row_buf = Rc::new(RefCell::new(Row::default()));
-            let from_id = sink.from;
-            let collection = combined.flat_map(move |(mut k, v)| {
-                warn_on_dups(&v, sink_id, from_id);
-                let max_idx = v.len() - 1;
-                let row_buf = Rc::clone(&row_buf);
-                v.into_iter().enumerate().map(move |(idx, dp)| {
-                    let k = if idx == max_idx { k.take() } else { k.clone() };
-                    let mut row_buf = row_buf.borrow_mut();
-                    dbz_format(&mut row_buf.packer(), dp);
-                    (k, Some(row_buf.clone()))
-                })
-            });
-            collection
-        }
-        SinkEnvelope::Upsert => {
-            // Allow access to `arrange_named` because we cannot access Mz's wrapper from here.
-            // TODO(#17413): Revisit with cluster unification.
-            #[allow(clippy::disallowed_methods)]
-            let combined = combine_at_timestamp(
-                keyed.arrange_named::<ColValSpine<_, _, _, _>>("Arrange Upsert"),
-            );
-
-            let from_id = sink.from;
-            let collection = combined.flat_map(move |(mut k, v)| {
-                warn_on_dups(&v, sink_id, from_id);
-                let max_idx = v.len() - 1;
-                v.into_iter().enumerate().map(move |(idx, dp)| {
-                    let k = if idx == max_idx { k.take() } else { k.clone() };
-                    (k, dp.after)
-                })
-            });
-            collection
-        }
+    // Consolidate the stream, sort it by time, and produce `DiffPair`s from it. The sink then
+    // renders each diff pair according to its envelope.
+    // * "Debezium" renders both the before and after state of each diff pair.
+    // * "Upsert" renders only the after state, or a deletion if there is none. (As part of
+    //   doing so, we warn if there are multiple conflicting values at the same timestamp)
+    let name = match sink.envelope {
+        SinkEnvelope::Debezium => "Arrange Debezium",
+        SinkEnvelope::Upsert => "Arrange Upsert",
+    };
+    // Allow access to `arrange_named` because we cannot access Mz's wrapper from here.
+    // TODO(#17413): Revisit with cluster unification.
+    #[allow(clippy::disallowed_methods)]
+    let combined = combine_at_timestamp(keyed.arrange_named::<ColValSpine<_, _, _, _>>(name));
+
+    // if there is no user-specified key, remove the synthetic
+    // distribution key again
+    let user_key_indices = sink_render.get_key_indices();
+    let combined = if user_key_indices.is_some() {
+        combined
+    } else {
+        combined.map(|(_key, value)| (None, value))
     };
 
-    collection
+    let from_id = sink.from;
+    combined.flat_map(move |(mut k, v)| {
+        warn_on_dups(&v, sink_id, from_id);
+        let max_idx = v.len() - 1;
+        v.into_iter().enumerate().map(move |(idx, dp)| {
+            let k = if idx == max_idx { k.take() } else { k.clone() };
+            (k, dp)
+        })
+    })
 }
 
 /// A type that can be rendered as a dataflow sink.
@@ -232,7 +202,7 @@ where
         storage_state: &mut StorageState,
         sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
         sink_id: GlobalId,
-        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        sinked_collection: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
         err_collection: Collection<G, DataflowError, Diff>,
     ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>);
 }
@@ -19,6 +19,7 @@ use differential_dataflow::{collection, AsCollection, Collection, Hashable};
 use mz_ore::cast::CastLossy;
 use mz_persist_client::operators::shard_source::SnapshotMode;
 use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker};
+use mz_sql_parser::ast::UnresolvedItemName;
 use mz_storage_operators::persist_source;
 use mz_storage_operators::persist_source::Subtime;
 use mz_storage_types::controller::CollectionMetadata;
@@ -27,6 +28,7 @@ use mz_storage_types::errors::{
     DataflowError, DecodeError, EnvelopeError, UpsertError, UpsertNullKeyError, UpsertValueError,
 };
 use mz_storage_types::parameters::StorageMaxInflightBytesConfig;
+use mz_storage_types::sources::encoding::{DecodeErrorPolicy, DEAD_LETTER_REFERENCE};
 use mz_storage_types::sources::envelope::{KeyEnvelope, NoneEnvelope, UpsertEnvelope, UpsertStyle};
 use mz_storage_types::sources::*;
 use mz_timely_util::builder_async::PressOnDropButton;
@@ -118,6 +120,11 @@ where
         // This might quite a large clone, but its just during rendering
         config: storage_state.storage_configuration.clone(),
         remap_collection_id: description.remap_collection_id.clone(),
+        instance_context: storage_state.instance_context.clone(),
+        rocksdb_tuning_config: storage_state
+            .dataflow_parameters
+            .upsert_rocksdb_tuning_config
+            .clone(),
     };
 
     // A set of channels (1 per worker) used to signal rehydration being finished
@@ -143,14 +150,33 @@ where
 
     needed_tokens.extend(source_tokens);
 
+    // The dead-letter subsource is not produced by the source itself but by
+    // decoding its primary output.
+    let dead_letter_output = match description.desc.decode_error_policy {
+        DecodeErrorPolicy::DeadLetter => description
+            .desc
+            .connection
+            .output_idx_for_name(&UnresolvedItemName::unqualified(DEAD_LETTER_REFERENCE)),
+        DecodeErrorPolicy::Error | DecodeErrorPolicy::Skip => None,
+    };
+
     let mut outputs = vec![];
-    for (ok_source, err_source) in streams {
+    let mut dead_letters = vec![];
+    for (output_index, (ok_source, err_source)) in streams.into_iter().enumerate() {
+        if Some(output_index) == dead_letter_output {
+            outputs.push((
+                Collection::empty(scope),
+                err_source.map(DataflowError::from),
+            ));
+            continue;
+        }
+
         // All sources should push their various error streams into this vector,
         // whose contents will be concatenated and inserted along the collection.
         // All subsources include the non-definite errors of the ingestion
         let error_collections = vec![err_source.map(DataflowError::from)];
 
-        let (ok, err, extra_tokens, health_stream) = render_source_stream(
+        let (ok, err, dead_letter, extra_tokens, health_stream) = render_source_stream(
             scope,
             dataflow_debug_name,
             id,
@@ -163,14 +189,23 @@ where
         );
         needed_tokens.extend(extra_tokens);
         outputs.push((ok, err));
+        dead_letters.push(dead_letter);
 
         health = health.concat(&health_stream.leave());
     }
+    if let Some(output_index) = dead_letter_output {
+        if let Some((ok, _)) = outputs.get_mut(output_index) {
+            *ok = collection::concatenate(scope, dead_letters);
+        }
+    }
     (outputs, health, needed_tokens)
 }
 
 /// Completes the rendering of a particular source stream by applying decoding and envelope
 /// processing as necessary
+///
+/// Besides the rendered collections, returns the collection of the stream's records that
+/// failed to decode if they are dead-lettered.
 fn render_source_stream<G, FromTime>(
     scope: &mut G,
     dataflow_debug_name: &String,
@@ -184,12 +219,13 @@ fn render_source_stream<G, FromTime>(
 ) -> (
     Collection<G, Row, Diff>,
     Collection<G, DataflowError, Diff>,
+    Collection<G, Row, Diff>,
     Vec<PressOnDropButton>,
     Stream<G, HealthStatusMessage>,
 )
 where
     G: Scope<Timestamp = mz_repr::Timestamp>,
-    FromTime: Timestamp,
+    FromTime: SourceTimestamp,
 {
     let mut needed_tokens = vec![];
 
@@ -198,9 +234,10 @@ where
         envelope,
         connection: _,
         timestamp_interval: _,
+        decode_error_policy,
     } = description.desc;
 
-    let (decoded_stream, decode_health) = match encoding {
+    let (decoded_stream, dead_letters, decode_health) = match encoding {
         None => (
             ok_source.map(|r| DecodeResult {
                 // This is safe because the current set of sources produce
@@ -215,12 +252,14 @@ where
                 metadata: r.metadata,
                 from_time: r.from_time,
             }),
+            Collection::empty(scope),
             empty(scope),
         ),
         Some(encoding) => render_decode_delimited(
             &ok_source,
             encoding.key,
             encoding.value,
+            decode_error_policy,
             dataflow_debug_name.clone(),
             storage_state.metrics.decode_defs.clone(),
             storage_state.storage_configuration.clone(),
@@ -444,7 +483,13 @@ where
     };
 
     // Return the collections and any needed tokens.
-    (collection, err_collection, needed_tokens, health)
+    (
+        collection,
+        err_collection,
+        dead_letters,
+        needed_tokens,
+        health,
+    )
 }
 
 // Returns the maximum limit of inflight bytes for backpressure based on given config
@@ -39,7 +39,9 @@
 //! One part of the dataflow deals with encoding the rows that we read from persist. There isn't
 //! anything surprizing here, it is *almost* just a `Collection::map` with the exception of an
 //! initialization step that makes sure the schemas are published to the Schema Registry. After
-//! that step the operator just encodes each batch it receives record by record.
+//! that step the operator just encodes each batch it receives record by record. Records that
+//! cannot be encoded are passed along as errors, which the sink operator reports as a definite
+//! error instead of committing the transaction that would contain them.
 //!
 //! # Sinking
 //!
@@ -68,6 +70,11 @@
 //! Both the encoding operator and the sinking operator can produce a transient error that is wired
 //! up with our health monitoring and will trigger a restart of the sink dataflow.
 //!
+//! Errors that would recur after a restart, like a record that cannot be encoded or a data topic
+//! whose partition count no longer matches the one pinned by a `PARTITION BY` sink, are definite
+//! instead. The sinking operator reports them as a stalled status and stops, without restarting
+//! the dataflow.
+//!
 //! [1]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#message-reliability
 
 use std::cell::RefCell;
@@ -75,30 +82,35 @@ use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context};
 use differential_dataflow::{AsCollection, Collection, Hashable};
 use maplit::btreemap;
-use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator, AvroSchemaOptions};
+use mz_expr::MirScalarExpr;
+use mz_interchange::avro::{AvroEncoder, DiffPair};
 use mz_interchange::encode::Encode;
+use mz_interchange::envelopes::dbz_format;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::{DecodedDescriptors, ProtobufEncoder};
+use mz_interchange::text_binary::{BytesEncoder, TextEncoder};
 use mz_kafka_util::client::{
     GetPartitionsError, MzClientContext, TimeoutConfig, TunnelingClientContext,
+    DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL,
 };
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
-use mz_ore::task;
 use mz_ore::vec::VecExt;
-use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_ore::{soft_assert_or_log, task};
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, RelationDesc, Row, RowArena, Timestamp};
 use mz_storage_client::sink::progress_key::ProgressKey;
 use mz_storage_client::sink::{TopicCleanupPolicy, TopicConfig};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{ContextCreationError, ContextCreationErrorExt, DataflowError};
 use mz_storage_types::sinks::{
-    KafkaSinkConnection, KafkaSinkFormat, MetadataFilled, SinkEnvelope, StorageSinkDesc,
+    KafkaSinkConnection, KafkaSinkFormatType, MetadataFilled, SinkEnvelope, StorageSinkDesc,
 };
 use mz_timely_util::antichain::AntichainExt;
 use mz_timely_util::builder_async::{
@@ -146,7 +158,7 @@ impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for KafkaSinkConnection {
         storage_state: &mut StorageState,
         sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
         sink_id: GlobalId,
-        input: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        input: Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
         // TODO(benesch): errors should stream out through the sink,
         // if we figure out a protocol for that.
         _err_collection: Collection<G, DataflowError, Diff>,
@@ -221,6 +233,15 @@ struct TransactionalProducer {
     socket_timeout: Duration,
     /// The maximum duration of a transaction.
     transaction_timeout: Duration,
+    /// The Prometheus metrics of this sink.
+    metrics: Arc<KafkaSinkMetrics>,
+    /// The number of partitions of the data topic, as of the last metadata refresh.
+    partition_count: u64,
+    /// The time of the last refresh of `partition_count`.
+    partition_count_refreshed_at: Instant,
+    /// The number of partitions that messages are assigned to by their hash, fixed when a
+    /// `PARTITION BY` sink first starts and recorded in its progress records.
+    pinned_partition_count: Option<u64>,
 }
 
 impl TransactionalProducer {
@@ -282,7 +303,10 @@ impl TransactionalProducer {
 
         let stats_receiver = ctx.subscribe_statistics();
         let task_name = format!("kafka_sink_metrics_collector:{sink_id}");
-        task::spawn(|| &task_name, collect_statistics(stats_receiver, metrics));
+        task::spawn(
+            || &task_name,
+            collect_statistics(stats_receiver, Arc::clone(&metrics)),
+        );
 
         let producer: BaseProducer<_> = connection
             .connection
@@ -305,6 +329,10 @@ impl TransactionalProducer {
             staged_bytes: 0,
             socket_timeout: timeout_config.socket_timeout,
             transaction_timeout: timeout_config.transaction_timeout,
+            metrics,
+            partition_count: 0,
+            partition_count_refreshed_at: Instant::now(),
+            pinned_partition_count: None,
         };
 
         let timeout = timeout_config.socket_timeout;
@@ -336,8 +364,50 @@ impl TransactionalProducer {
             .await
     }
 
-    async fn begin_transaction(&mut self) -> Result<(), ContextCreationError> {
-        self.spawn_blocking(|p| p.begin_transaction()).await
+    /// Fetches the number of partitions of the data topic, which must exist, and checks it
+    /// against the pinned partition count, if any.
+    async fn refresh_partition_count(&mut self) -> Result<(), SinkError> {
+        let meta = self.fetch_metadata().await?;
+        match meta.topics().iter().find(|t| t.name() == self.data_topic) {
+            Some(topic) if !topic.partitions().is_empty() => {
+                let partition_count = u64::cast_from(topic.partitions().len());
+                self.metrics.partition_count.set(partition_count);
+                self.partition_count = partition_count;
+                self.partition_count_refreshed_at = Instant::now();
+                self.check_partition_count()
+            }
+            _ => Err(ContextCreationError::from(anyhow!("sink data topic is missing")).into()),
+        }
+    }
+
+    /// Pins the number of partitions that messages are assigned to by their hash to
+    /// `partition_count`.
+    fn pin_partition_count(&mut self, partition_count: u64) -> Result<(), SinkError> {
+        self.pinned_partition_count = Some(partition_count);
+        self.check_partition_count()
+    }
+
+    /// Checks that the data topic still has the pinned number of partitions. Messages are
+    /// assigned to partitions by their hash modulo the partition count, so writing with a
+    /// different count would move messages with the same hash to a different partition.
+    fn check_partition_count(&self) -> Result<(), SinkError> {
+        match self.pinned_partition_count {
+            Some(pinned) if pinned != self.partition_count => Err(SinkError::Definite(format!(
+                "sink data topic {} has {} partitions, but the sink assigns messages to \
+                 partitions based on the {pinned} partitions it had when the sink started",
+                self.data_topic, self.partition_count,
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    async fn begin_transaction(&mut self) -> Result<(), SinkError> {
+        // Periodically check the partition count of the data topic so that the sink stops if
+        // partitions are added while it is running.
+        if self.partition_count_refreshed_at.elapsed() >= DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL {
+            self.refresh_partition_count().await?;
+        }
+        Ok(self.spawn_blocking(|p| p.begin_transaction()).await?)
     }
 
     /// Synchronously puts the provided message to librdkafka's send queue. This method only
@@ -372,12 +442,18 @@ impl TransactionalProducer {
                 value: header.value.as_ref(),
             });
         }
+        // Messages only have a hash if the partition count is pinned, and
+        // transactions don't begin while the topic has a different count.
+        let partition = message.hash.map(|hash| {
+            let partition = hash % self.partition_count;
+            i32::try_from(partition).expect("partition count fits in an i32")
+        });
         let record = BaseRecord {
             topic: &self.data_topic,
             key: message.key.as_ref(),
             payload: message.value.as_ref(),
             headers: Some(headers),
-            partition: None,
+            partition,
             timestamp: None,
             delivery_opaque: (),
         };
@@ -417,6 +493,7 @@ impl TransactionalProducer {
     ) -> Result<(), ContextCreationError> {
         let progress = ProgressRecord {
             frontier: upper.into(),
+            partition_count: self.pinned_partition_count,
         };
         let payload = serde_json::to_vec(&progress).expect("infallible");
         let record = BaseRecord::to(&self.progress_topic)
@@ -524,6 +601,9 @@ struct KafkaMessage {
     value: Option<Vec<u8>>,
     /// Message headers.
     headers: Vec<KafkaHeader>,
+    /// The hash that determines the partition of the message, if the sink has a `PARTITION BY`
+    /// expression. If absent, the partition is chosen by hashing the message key.
+    hash: Option<u64>,
 }
 
 /// A header to attach to a Kafka message.
@@ -542,7 +622,7 @@ struct KafkaHeader {
 /// Updates are sent in ascending timestamp order.
 fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     name: String,
-    input: &Collection<G, KafkaMessage, Diff>,
+    input: &Collection<G, Result<KafkaMessage, String>, Diff>,
     sink_id: GlobalId,
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
@@ -568,7 +648,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             }
 
             fail::fail_point!("kafka_sink_creation_error", |_| Err(
-                ContextCreationError::Other(anyhow::anyhow!("synthetic error"))
+                ContextCreationError::Other(anyhow::anyhow!("synthetic error")).into()
             ));
 
             let metrics = Arc::new(metrics);
@@ -591,8 +671,8 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             )
             .await?;
 
-            let resume_upper = match resume_upper {
-                Some(upper) => upper,
+            let (resume_upper, recorded_partition_count) = match resume_upper {
+                Some(resume) => resume,
                 None => {
                     mz_storage_client::sink::ensure_kafka_topic(
                         &connection,
@@ -609,18 +689,17 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                         },
                     )
                     .await?;
-                    Antichain::from_elem(Timestamp::minimum())
+                    (Antichain::from_elem(Timestamp::minimum()), None)
                 }
             };
 
             // At this point the topic must exist and so we can query for its metadata.
-            let meta = producer.fetch_metadata().await?;
-            match meta.topics().iter().find(|t| t.name() == &connection.topic) {
-                Some(topic) => {
-                    let partition_count = u64::cast_from(topic.partitions().len());
-                    metrics.partition_count.set(partition_count);
-                }
-                None => return Err(anyhow!("sink data topic is missing").into()),
+            producer.refresh_partition_count().await?;
+            // Pin the partition count the first time a `PARTITION BY` sink starts, so that
+            // messages with the same hash are always written to the same partition.
+            if connection.partition_by.is_some() {
+                let partition_count = recorded_partition_count.unwrap_or(producer.partition_count);
+                producer.pin_partition_count(partition_count)?;
             }
 
             // The input has overcompacted if
@@ -639,7 +718,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                 // Materialize backup/restore we log an error so that it appears on Sentry but
                 // leaves the rest of the objects in the cluster unaffected.
                 error!("{err}");
-                return Err(anyhow!("{err}").into());
+                return Err(ContextCreationError::Other(anyhow!("{err}")).into());
             }
 
             info!(
@@ -681,6 +760,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                                         producer.begin_transaction().await?;
                                         transaction_begun = true;
                                     }
+                                    let message = message.map_err(SinkError::Definite)?;
                                     producer.send(&message, time, diff).await?;
                                 }
                                 Ordering::Greater => continue,
@@ -723,6 +803,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                         );
                         extra_updates.sort_unstable_by(|a, b| a.1.cmp(&b.1));
                         for (message, time, diff) in extra_updates.drain(..) {
+                            let message = message.map_err(SinkError::Definite)?;
                             producer.send(&message, time, diff).await?;
                         }
 
@@ -741,7 +822,17 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
         })
     });
 
-    let statuses = errors.map(|error: Rc<ContextCreationError>| {
+    let statuses = errors.map(|error: Rc<SinkError>| {
+        let error = match &*error {
+            SinkError::Transient(error) => error,
+            SinkError::Definite(error) => {
+                return HealthStatusMessage {
+                    index: 0,
+                    update: HealthStatusUpdate::stalled(error.clone(), None),
+                    namespace: StatusNamespace::Kafka,
+                };
+            }
+        };
         let hint = match *error {
             ContextCreationError::KafkaError(KafkaError::Transaction(ref e)) => {
                 if e.is_retriable() && e.code() == RDKafkaErrorCode::OperationTimedOut {
@@ -770,8 +861,24 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     (statuses, button.press_on_drop())
 }
 
+/// An error that stops the sink operator.
+#[derive(Debug)]
+enum SinkError {
+    /// An error that might not recur, which restarts the sink dataflow.
+    Transient(ContextCreationError),
+    /// An error that would recur if the sink dataflow restarted, like a record that cannot be
+    /// encoded. It stalls the sink without restarting the dataflow.
+    Definite(String),
+}
+
+impl From<ContextCreationError> for SinkError {
+    fn from(err: ContextCreationError) -> Self {
+        SinkError::Transient(err)
+    }
+}
+
 /// Determines the latest progress record from the specified topic for the given
-/// progress key.
+/// progress key, returning its upper and pinned partition count.
 ///
 /// IMPORTANT: to achieve exactly once guarantees, the producer that will resume
 /// production at the returned timestamp *must* have called `init_transactions`
@@ -781,7 +888,7 @@ async fn determine_sink_resume_upper(
     connection: &KafkaSinkConnection,
     storage_configuration: &StorageConfiguration,
     metrics: Arc<KafkaSinkMetrics>,
-) -> Result<Option<Antichain<Timestamp>>, ContextCreationError> {
+) -> Result<Option<(Antichain<Timestamp>, Option<u64>)>, ContextCreationError> {
     // ****************************** WARNING ******************************
     // Be VERY careful when editing the code in this function. It is very easy
     // to accidentally introduce a correctness or liveness bug when refactoring
@@ -1012,6 +1119,7 @@ async fn determine_sink_resume_upper(
         // beyond the high water mark. To make this invariant easy to check, do
         // not use `break` in the body of the loop.
         let mut last_upper = None;
+        let mut last_partition_count = None;
         while get_position()? < hi {
             let message = match progress_client_read_committed.poll(progress_record_fetch_timeout) {
                 Some(Ok(message)) => message,
@@ -1039,7 +1147,7 @@ async fn determine_sink_resume_upper(
             let Some(payload) = message.payload() else {
                 continue
             };
-            let upper = parse_progress_record(payload)?;
+            let (upper, partition_count) = parse_progress_record(payload)?;
 
             match last_upper {
                 Some(last_upper) if !PartialOrder::less_equal(&last_upper, &upper) => {
@@ -1050,12 +1158,13 @@ async fn determine_sink_resume_upper(
                 }
                 _ => last_upper = Some(upper),
             }
+            last_partition_count = partition_count;
         }
 
         // If we get here, we are assured that we've read all messages up to
         // the high water mark, and therefore `last_timestamp` contains the
         // most recent timestamp for the sink under consideration.
-        Ok(last_upper)
+        Ok(last_upper.map(|upper| (upper, last_partition_count)))
     }).await.unwrap().check_ssh_status(&ctx);
     // Express interest to the computation until after we've received its result
     drop(parent_token);
@@ -1090,19 +1199,25 @@ where
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ProgressRecord {
     pub frontier: Vec<Timestamp>,
+    /// The number of partitions that a `PARTITION BY` sink assigns messages to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_count: Option<u64>,
 }
 
-fn parse_progress_record(payload: &[u8]) -> Result<Antichain<Timestamp>, anyhow::Error> {
+/// Parses a progress record into the upper frontier and pinned partition count it describes.
+fn parse_progress_record(
+    payload: &[u8],
+) -> Result<(Antichain<Timestamp>, Option<u64>), anyhow::Error> {
     Ok(match serde_json::from_slice::<ProgressRecord>(payload) {
-        Ok(progress) => Antichain::from(progress.frontier),
+        Ok(progress) => (Antichain::from(progress.frontier), progress.partition_count),
         // If we fail to deserialize we might be reading a legacy progress record
         Err(_) => match serde_json::from_slice::<LegacyProgressRecord>(payload) {
             Ok(LegacyProgressRecord {
                 timestamp: Some(Some(time)),
-            }) => Antichain::from_elem(time.step_forward()),
+            }) => (Antichain::from_elem(time.step_forward()), None),
             Ok(LegacyProgressRecord {
                 timestamp: Some(None),
-            }) => Antichain::new(),
+            }) => (Antichain::new(), None),
             _ => match std::str::from_utf8(payload) {
                 Ok(payload) => bail!("invalid progress record: {payload}"),
                 Err(_) => bail!("invalid progress record bytes: {payload:?}"),
@@ -1116,12 +1231,12 @@ fn parse_progress_record(payload: &[u8]) -> Result<Antichain<Timestamp>, anyhow:
 /// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
 fn encode_collection<G: Scope>(
     name: String,
-    input: &Collection<G, (Option<Row>, Option<Row>), Diff>,
+    input: &Collection<G, (Option<Row>, DiffPair<Row>), Diff>,
     envelope: SinkEnvelope,
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
 ) -> (
-    Collection<G, KafkaMessage, Diff>,
+    Collection<G, Result<KafkaMessage, String>, Diff>,
     Stream<G, HealthStatusMessage>,
     PressOnDropButton,
 ) {
@@ -1133,56 +1248,31 @@ fn encode_collection<G: Scope>(
     let (button, errors) = builder.build_fallible(move |caps| {
         Box::pin(async move {
             let [capset]: &mut [_; 1] = caps.try_into().unwrap();
-            let key_desc = connection
-                .key_desc_and_indices
-                .as_ref()
-                .map(|(desc, _indices)| desc.clone());
-            let value_desc = connection.value_desc;
-
-            let encoder: Box<dyn Encode> = match connection.format {
-                KafkaSinkFormat::Avro {
-                    key_schema,
-                    value_schema,
-                    csr_connection,
-                } => {
-                    // Ensure that schemas are registered with the schema registry.
-                    //
-                    // Note that where this lies in the rendering cycle means that we will publish the
-                    // schemas each time the sink is rendered.
-                    let ccsr = csr_connection
-                        .connect(&storage_configuration, InTask::Yes)
-                        .await?;
-                    let (key_schema_id, value_schema_id) =
-                        mz_storage_client::sink::publish_kafka_schemas(
-                            ccsr,
-                            connection.topic.clone(),
-                            key_schema,
-                            Some(mz_ccsr::SchemaType::Avro),
-                            &value_schema,
-                            mz_ccsr::SchemaType::Avro,
-                        )
-                        .await
-                        .context("error publishing kafka schemas for sink")?;
-
-                    let options = AvroSchemaOptions {
-                        is_debezium: matches!(envelope, SinkEnvelope::Debezium),
-                        ..Default::default()
-                    };
-
-                    let schema_generator = AvroSchemaGenerator::new(key_desc, value_desc, options)
-                        .expect("avro schema validated");
-                    Box::new(AvroEncoder::new(
-                        schema_generator,
-                        key_schema_id,
-                        value_schema_id,
-                    ))
-                }
-                KafkaSinkFormat::Json => Box::new(JsonEncoder::new(
-                    key_desc,
-                    value_desc,
-                    matches!(envelope, SinkEnvelope::Debezium),
-                )),
+            let debezium = matches!(envelope, SinkEnvelope::Debezium);
+            let key_encoder = match (
+                connection.format.key_format,
+                &connection.key_desc_and_indices,
+            ) {
+                (Some(format), Some((desc, _indices))) => Some(
+                    build_encoder(
+                        format,
+                        desc.clone(),
+                        false,
+                        format!("{}-key", connection.topic),
+                        &storage_configuration,
+                    )
+                    .await?,
+                ),
+                _ => None,
             };
+            let value_encoder = build_encoder(
+                connection.format.value_format,
+                connection.value_desc,
+                debezium,
+                format!("{}-value", connection.topic),
+                &storage_configuration,
+            )
+            .await?;
 
             // !IMPORTANT!
             // Correctness of this operator relies on no fallible operations happening after this
@@ -1191,20 +1281,62 @@ fn encode_collection<G: Scope>(
             // TODO(petrosagg): Make the fallible async operator safe
             *capset = CapabilitySet::new();
 
+            let mut row_buf = Row::default();
+            let mut datums = DatumVec::new();
+
             while let Some(event) = input.next().await {
                 if let Event::Data(cap, rows) = event {
-                    for ((key, value), time, diff) in rows {
-                        let headers = match (connection.headers_index, &value) {
+                    for ((key, dp), time, diff) in rows {
+                        // The partition is determined by the latest state of the row, or by
+                        // the deleted row in the case of a deletion.
+                        let hash = match &connection.partition_by {
+                            Some(partition_by) => {
+                                let row = dp.after.as_ref().or(dp.before.as_ref());
+                                let row = row.expect("diff pair is never empty");
+                                Some(evaluate_partition_by(
+                                    partition_by,
+                                    &datums.borrow_with(row),
+                                ))
+                            }
+                            None => None,
+                        };
+                        let (value, deleted) = match envelope {
+                            SinkEnvelope::Debezium => {
+                                dbz_format(&mut row_buf.packer(), dp);
+                                (Some(row_buf.clone()), false)
+                            }
+                            SinkEnvelope::Upsert => match (dp.after, connection.tombstones) {
+                                (Some(after), _) => (Some(after), false),
+                                (None, true) => (None, false),
+                                (None, false) => (dp.before, true),
+                            },
+                        };
+                        let mut headers = match (connection.headers_index, &value) {
                             (Some(i), Some(v)) => encode_headers(v.iter().nth(i).unwrap()),
                             _ => vec![],
                         };
-                        let key = key.map(|key| encoder.encode_key_unchecked(key));
-                        let value = value.map(|value| encoder.encode_value_unchecked(value));
-                        let message = KafkaMessage {
-                            key,
-                            value,
-                            headers,
+                        if deleted {
+                            headers.push(KafkaHeader {
+                                key: "materialize-deleted".into(),
+                                value: Some(b"true".to_vec()),
+                            });
+                        }
+                        let key = match (key, &key_encoder) {
+                            (Some(key), Some(encoder)) => Some(encoder.encode_unchecked(key)),
+                            _ => None,
                         };
+                        let value = value.map(|value| value_encoder.encode_unchecked(value));
+                        // Encoding and partitioning errors cannot fail this operator, see
+                        // above, so they are forwarded to the sink operator instead.
+                        let message = (|| {
+                            Ok(KafkaMessage {
+                                key: key.transpose()?,
+                                value: value.transpose()?,
+                                headers,
+                                hash: hash.transpose()?,
+                            })
+                        })()
+                        .map_err(|e: anyhow::Error| e.display_with_causes().to_string());
                         output.give(&cap, (message, time, diff)).await;
                     }
                 }
@@ -1222,6 +1354,95 @@ fn encode_collection<G: Scope>(
     (stream.as_collection(), statuses, button.press_on_drop())
 }
 
+/// Evaluates the `PARTITION BY` expression of a sink against the datums of a
+/// row.
+///
+/// Expressions that evaluate to `NULL` map to zero, so that the corresponding
+/// messages are sent to the first partition. Evaluation errors are returned, so
+/// that they can be reported as errors of the sink.
+fn evaluate_partition_by(
+    partition_by: &MirScalarExpr,
+    row: &[Datum],
+) -> Result<u64, anyhow::Error> {
+    let temp_storage = RowArena::new();
+    match partition_by.eval(row, &temp_storage) {
+        Ok(Datum::UInt64(u)) => Ok(u),
+        Ok(datum) => {
+            soft_assert_or_log!(datum.is_null(), "unexpected partition_by result: {datum:?}");
+            Ok(0)
+        }
+        Err(e) => Err(anyhow!("evaluating PARTITION BY expression: {e}")),
+    }
+}
+
+/// Builds the encoder for one side (key or value) of a Kafka message.
+///
+/// If the format uses a schema registry, the schema is published under
+/// `subject`. Note that where this lies in the rendering cycle means that we
+/// will publish the schemas each time the sink is rendered.
+async fn build_encoder(
+    format: KafkaSinkFormatType,
+    desc: RelationDesc,
+    debezium: bool,
+    subject: String,
+    storage_configuration: &StorageConfiguration,
+) -> Result<Box<dyn Encode>, anyhow::Error> {
+    let encoder: Box<dyn Encode> = match format {
+        KafkaSinkFormatType::Avro {
+            schema,
+            csr_connection,
+        } => {
+            let ccsr = csr_connection
+                .connect(storage_configuration, InTask::Yes)
+                .await?;
+            let schema_id = mz_storage_client::sink::publish_kafka_schema(
+                ccsr,
+                subject.clone(),
+                schema.clone(),
+                mz_ccsr::SchemaType::Avro,
+            )
+            .await
+            .with_context(|| {
+                format!("unable to publish schema {subject} to registry in kafka sink")
+            })?;
+            Box::new(AvroEncoder::new(desc, debezium, &schema, Some(schema_id))?)
+        }
+        KafkaSinkFormatType::AvroInline { schema } => {
+            Box::new(AvroEncoder::new(desc, debezium, &schema, None)?)
+        }
+        KafkaSinkFormatType::Json => Box::new(JsonEncoder::new(desc, debezium, None)),
+        KafkaSinkFormatType::JsonSchema {
+            schema,
+            csr_connection,
+        } => {
+            let ccsr = csr_connection
+                .connect(storage_configuration, InTask::Yes)
+                .await?;
+            let schema_id = mz_storage_client::sink::publish_kafka_schema(
+                ccsr,
+                subject.clone(),
+                schema,
+                mz_ccsr::SchemaType::Json,
+            )
+            .await
+            .with_context(|| {
+                format!("unable to publish schema {subject} to registry in kafka sink")
+            })?;
+            Box::new(JsonEncoder::new(desc, debezium, Some(schema_id)))
+        }
+        KafkaSinkFormatType::Protobuf {
+            descriptors,
+            message_name,
+        } => {
+            let descriptors = DecodedDescriptors::from_bytes(&descriptors, message_name)?;
+            Box::new(ProtobufEncoder::new(descriptors, desc, debezium)?)
+        }
+        KafkaSinkFormatType::Text => Box::new(TextEncoder::new(desc)?),
+        KafkaSinkFormatType::Bytes => Box::new(BytesEncoder::new(desc)?),
+    };
+    Ok(encoder)
+}
+
 fn encode_headers(datum: Datum) -> Vec<KafkaHeader> {
     let mut out = vec![];
     if datum.is_null() {
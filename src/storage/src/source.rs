@@ -27,14 +27,18 @@
 
 use crate::source::types::{SourceMessage, SourceReaderError};
 
+mod file;
 pub mod generator;
 mod kafka;
+mod kinesis;
 mod mysql;
 mod postgres;
 pub(crate) mod reclock;
 mod source_reader_pipeline;
+mod sql_server;
 mod statistics;
+mod truncation;
 pub mod types;
 
-pub use kafka::KafkaSourceReader;
+pub use kafka::{KafkaSourceReader, KafkaTopicPatternSource};
 pub use source_reader_pipeline::{create_raw_source, RawSourceCreationConfig};
@@ -0,0 +1,462 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that ingests the objects stored under a prefix of an S3 bucket or
+//! a local directory.
+//!
+//! The timestamp of the source is a partitioned offset whose partitions are
+//! object keys. Every object is ingested at offset 0 of its own singleton
+//! partition, after which its capability is downgraded to offset 1 and held
+//! forever. The frontier of the source therefore records every object that has
+//! been ingested, and the ranges between them are held at offset 0 until a
+//! new object is discovered there. This makes ingestion incremental: on restart
+//! only objects that are not yet part of the resume upper are ingested.
+//!
+//! Objects are read in chunks and split into records as they are read, except
+//! for formats whose records span whole objects. An object that cannot be read
+//! or decoded stalls the source, and is retried after the next listing while
+//! the remaining objects continue to be ingested.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+use bytes::Bytes;
+use differential_dataflow::{AsCollection, Collection};
+use futures::StreamExt;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_repr::{Datum, Diff, Row};
+use mz_storage_types::sources::file::{FileFormat, FileSourceConnection};
+use mz_storage_types::sources::kafka::RangeBound;
+use mz_storage_types::sources::{MzOffset, SourceTimestamp};
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use mz_timely_util::order::Partitioned;
+use timely::dataflow::operators::Capability;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+use tracing::info;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
+use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+
+type FileTimestamp = Partitioned<RangeBound<String>, MzOffset>;
+
+/// The capabilities held for a part of the key space that has not yet been
+/// ingested.
+struct PendingCapability {
+    data: Capability<FileTimestamp>,
+    progress: Capability<FileTimestamp>,
+}
+
+impl PendingCapability {
+    fn delayed(&self, ts: &FileTimestamp) -> Self {
+        PendingCapability {
+            data: self.data.delayed(ts),
+            progress: self.progress.delayed(ts),
+        }
+    }
+}
+
+impl SourceRender for FileSourceConnection {
+    type Time = FileTimestamp;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::File;
+
+    fn render<G: Scope<Timestamp = FileTimestamp>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<FileTimestamp>> + 'static,
+        start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        let mut builder = AsyncOperatorBuilder::new(config.name.clone(), scope.clone());
+
+        let (mut data_output, stream) = builder.new_output();
+        let (_progress_output, progress_stream) = builder.new_output();
+        let (mut health_output, health_stream) = builder.new_output();
+        let (mut stats_output, stats_stream) = builder.new_output();
+
+        let button = builder.build(move |caps| async move {
+            let [data_cap, progress_cap, health_cap, stats_cap]: [_; 4] = caps.try_into().unwrap();
+
+            if !config.responsible_for(()) {
+                // Emit 0, to mark this worker as having started up correctly.
+                stats_output
+                    .give(
+                        &stats_cap,
+                        ProgressStatisticsUpdate::SteadyState {
+                            offset_known: 0,
+                            offset_committed: 0,
+                        },
+                    )
+                    .await;
+                return;
+            }
+
+            let resume_upper = Antichain::from_iter(
+                config.source_resume_uppers[&config.id]
+                    .iter()
+                    .map(FileTimestamp::decode_row),
+            );
+
+            // The parts of the key space that have not been ingested yet, keyed
+            // by their lower bound. Objects whose ingestion was interrupted
+            // show up here as singletons at offset 0.
+            let mut pending = BTreeMap::new();
+            // The capabilities that record ingested objects in the frontier.
+            let mut ingested = vec![];
+            // The number of records emitted for objects whose ingestion failed
+            // part way through.
+            let mut partial: BTreeMap<String, usize> = BTreeMap::new();
+            let initial_cap = PendingCapability {
+                data: data_cap,
+                progress: progress_cap,
+            };
+            for ts in resume_upper.elements() {
+                match ts.interval().singleton() {
+                    Some(_) if ts.timestamp().offset > 0 => {
+                        ingested.push(initial_cap.progress.delayed(ts));
+                    }
+                    _ => {
+                        pending.insert(ts.interval().lower.clone(), initial_cap.delayed(ts));
+                    }
+                }
+            }
+            drop(initial_cap);
+
+            info!(
+                source_id = config.id.to_string(),
+                worker_id = config.worker_id,
+                num_workers = config.worker_count,
+                "instantiating file source reader with {} ingested objects",
+                ingested.len(),
+            );
+
+            let client = match self
+                .store
+                .client(&config.config.connection_context, InTask::Yes)
+                .await
+            {
+                Ok(client) => client,
+                Err(err) => {
+                    let update = HealthStatusUpdate::halting(
+                        format!("failed to create client: {}", err.display_with_causes()),
+                        None,
+                    );
+                    health_output
+                        .give(
+                            &health_cap,
+                            HealthStatusMessage {
+                                index: 0,
+                                namespace: Self::STATUS_NAMESPACE.clone(),
+                                update,
+                            },
+                        )
+                        .await;
+                    // IMPORTANT: wedge forever until the `SuspendAndRestart` is processed.
+                    // Returning would incorrectly present to the remap operator as progress to the
+                    // empty frontier which would be incorrectly recorded to the remap shard.
+                    std::future::pending::<()>().await;
+                    unreachable!("pending future never returns");
+                }
+            };
+
+            // Note that we wait for this AFTER we downgrade to the source `resume_upper`. This
+            // allows downstream operators (namely, the `reclock_operator`) to downgrade to the
+            // `resume_upper`, which is necessary for this basic form of backpressure to work.
+            start_signal.await;
+
+            let mut resume_uppers = std::pin::pin!(resume_uppers);
+            let mut offset_committed = 0;
+            let mut last_status = None;
+            let mut ticker = tokio::time::interval(config.timestamp_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                let keys = match client.list_objects(&self.prefix).await {
+                    Ok(keys) => keys,
+                    Err(err) => {
+                        let update = HealthStatusUpdate::stalled(
+                            format!("failed to list objects: {}", err.display_with_causes()),
+                            None,
+                        );
+                        health_output
+                            .give(
+                                &health_cap,
+                                HealthStatusMessage {
+                                    index: 0,
+                                    namespace: Self::STATUS_NAMESPACE.clone(),
+                                    update: update.clone(),
+                                },
+                            )
+                            .await;
+                        last_status = Some(update);
+                        ticker.tick().await;
+                        continue;
+                    }
+                };
+                let offset_known = u64::cast_from(keys.len());
+
+                let mut status = HealthStatusUpdate::running();
+                for key in keys {
+                    let exact = RangeBound::exact(key.clone());
+                    let lower = match pending.range(..=exact.clone()).next_back() {
+                        Some((lower, cap)) if cap.data.time().interval().contains(&exact) => {
+                            lower.clone()
+                        }
+                        // The object has already been ingested.
+                        _ => continue,
+                    };
+                    let cap = pending.remove(&lower).expect("known to exist");
+
+                    // Split the pending range around the object, unless the
+                    // object's ingestion was previously interrupted.
+                    let interval = cap.data.time().interval();
+                    if interval.singleton().is_none() {
+                        let before = Partitioned::new_range(
+                            interval.lower.clone(),
+                            RangeBound::before(key.clone()),
+                            MzOffset::from(0),
+                        );
+                        let after = Partitioned::new_range(
+                            RangeBound::after(key.clone()),
+                            interval.upper.clone(),
+                            MzOffset::from(0),
+                        );
+                        pending.insert(before.interval().lower.clone(), cap.delayed(&before));
+                        pending.insert(after.interval().lower.clone(), cap.delayed(&after));
+                    }
+                    let ts = Partitioned::new_singleton(exact.clone(), MzOffset::from(0));
+                    let object_cap = cap.delayed(&ts);
+                    drop(cap);
+
+                    // Records that were emitted before a previous attempt to
+                    // ingest the object failed are not emitted again.
+                    let skip = partial.remove(&key).unwrap_or(0);
+                    let mut emitted = 0;
+                    let result: Result<(), anyhow::Error> = async {
+                        // Objects that were deleted after being listed are
+                        // ingested as if they were empty.
+                        let Some(mut reader) = client.open_object(&key).await? else {
+                            return Ok(());
+                        };
+                        let mut framer = RecordFramer::new(&self.format);
+                        loop {
+                            let messages = match reader.next_chunk().await? {
+                                Some(chunk) => framer.push(&chunk),
+                                None => break,
+                            };
+                            for message in messages {
+                                emitted += 1;
+                                if emitted > skip {
+                                    data_output
+                                        .give(&object_cap.data, ((0, Ok(message)), ts.clone(), 1))
+                                        .await;
+                                }
+                            }
+                        }
+                        for message in framer.finish()? {
+                            emitted += 1;
+                            if emitted > skip {
+                                data_output
+                                    .give(&object_cap.data, ((0, Ok(message)), ts.clone(), 1))
+                                    .await;
+                            }
+                        }
+                        Ok(())
+                    }
+                    .await;
+                    if let Err(err) = result {
+                        // Retry the object after the next listing. Its records
+                        // are not committed until it has been ingested in full.
+                        status = HealthStatusUpdate::stalled(
+                            format!(
+                                "failed to ingest object {}: {}",
+                                key,
+                                err.display_with_causes()
+                            ),
+                            None,
+                        );
+                        partial.insert(key, skip.max(emitted));
+                        pending.insert(exact, object_cap);
+                        continue;
+                    }
+
+                    let ingested_ts = Partitioned::new_singleton(exact, MzOffset::from(1));
+                    ingested.push(object_cap.progress.delayed(&ingested_ts));
+                }
+
+                if last_status.as_ref() != Some(&status) {
+                    health_output
+                        .give(
+                            &health_cap,
+                            HealthStatusMessage {
+                                index: 0,
+                                namespace: Self::STATUS_NAMESPACE.clone(),
+                                update: status.clone(),
+                            },
+                        )
+                        .await;
+                    last_status = Some(status);
+                }
+
+                // Wait for the next listing, while keeping track of the objects
+                // whose ingestion has been committed.
+                let mut tick = std::pin::pin!(ticker.tick());
+                loop {
+                    tokio::select! {
+                        _ = &mut tick => break,
+                        Some(frontier) = resume_uppers.next() => {
+                            offset_committed = u64::cast_from(
+                                frontier
+                                    .iter()
+                                    .filter(|ts| {
+                                        ts.interval().singleton().is_some()
+                                            && ts.timestamp().offset > 0
+                                    })
+                                    .count(),
+                            );
+                        }
+                    }
+                }
+                stats_output
+                    .give(
+                        &stats_cap,
+                        ProgressStatisticsUpdate::SteadyState {
+                            offset_known,
+                            offset_committed,
+                        },
+                    )
+                    .await;
+            }
+        });
+
+        (
+            stream.as_collection(),
+            Some(progress_stream),
+            health_stream,
+            stats_stream,
+            vec![button.press_on_drop()],
+        )
+    }
+}
+
+/// Splits the contents of an object, which are read in chunks, into the
+/// messages of the source.
+///
+/// Records of line-based formats are emitted as soon as they are complete, while
+/// formats whose records span whole objects buffer the object in memory.
+struct RecordFramer<'a> {
+    format: &'a FileFormat,
+    /// The bytes that have not yet been emitted as part of a record.
+    buf: Vec<u8>,
+    /// The number of bytes of `buf` that have been scanned for the end of a
+    /// record.
+    scanned: usize,
+    /// Whether the scan position is within a quoted CSV field.
+    in_quotes: bool,
+    /// Whether the next record is the header of the object.
+    at_header: bool,
+}
+
+impl<'a> RecordFramer<'a> {
+    fn new(format: &'a FileFormat) -> Self {
+        RecordFramer {
+            format,
+            buf: vec![],
+            scanned: 0,
+            in_quotes: false,
+            at_header: matches!(format, FileFormat::Csv { skip_header: true }),
+        }
+    }
+
+    /// Adds the next chunk of the object, returning the records that it
+    /// completes.
+    fn push(&mut self, chunk: &[u8]) -> Vec<SourceMessage> {
+        self.buf.extend_from_slice(chunk);
+        let quoted = match self.format {
+            FileFormat::Lines => false,
+            FileFormat::Csv { .. } => true,
+            FileFormat::Object | FileFormat::Parquet { .. } => return vec![],
+        };
+        let mut messages = vec![];
+        let mut start = 0;
+        for i in self.scanned..self.buf.len() {
+            match self.buf[i] {
+                // Escaped quotes within a quoted field toggle the state twice.
+                b'"' if quoted => self.in_quotes = !self.in_quotes,
+                b'\n' if !self.in_quotes => {
+                    let record = &self.buf[start..i];
+                    start = i + 1;
+                    if std::mem::take(&mut self.at_header) {
+                        continue;
+                    }
+                    messages.extend(line_message(record));
+                }
+                _ => {}
+            }
+        }
+        self.buf.drain(..start);
+        self.scanned = self.buf.len();
+        messages
+    }
+
+    /// Returns the records that remain once the whole object has been read.
+    fn finish(self) -> Result<Vec<SourceMessage>, anyhow::Error> {
+        // Empty objects, like the sentinels that `COPY ... TO` writes while it
+        // is in progress, contain no data in any format.
+        if self.buf.is_empty() {
+            return Ok(vec![]);
+        }
+        match self.format {
+            // A final record need not be terminated by a newline.
+            FileFormat::Lines | FileFormat::Csv { .. } => {
+                if self.at_header {
+                    return Ok(vec![]);
+                }
+                Ok(line_message(&self.buf).into_iter().collect())
+            }
+            FileFormat::Object => Ok(vec![SourceMessage {
+                key: Row::pack([Datum::Null]),
+                value: Row::pack([Datum::Bytes(&self.buf)]),
+                metadata: Row::default(),
+            }]),
+            FileFormat::Parquet { desc } => {
+                let rows = mz_arrow_util::reader::read_parquet(Bytes::from(self.buf), desc)?;
+                Ok(rows
+                    .into_iter()
+                    .map(|value| SourceMessage {
+                        key: Row::default(),
+                        value,
+                        metadata: Row::default(),
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+/// Returns the message for a record of a line-based format, or `None` if the
+/// record is empty.
+fn line_message(line: &[u8]) -> Option<SourceMessage> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    (!line.is_empty()).then(|| SourceMessage {
+        key: Row::pack([Datum::Null]),
+        value: Row::pack([Datum::Bytes(line)]),
+        metadata: Row::default(),
+    })
+}
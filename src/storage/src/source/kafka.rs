@@ -7,9 +7,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::Infallible;
+use std::fmt;
+use std::hash::Hash;
 use std::str::{self};
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -20,16 +21,24 @@ use chrono::{DateTime, NaiveDateTime};
 use differential_dataflow::{AsCollection, Collection};
 use futures::StreamExt;
 use maplit::btreemap;
-use mz_kafka_util::client::{get_partitions, MzClientContext, PartitionId, TunnelingClientContext};
+use mz_kafka_util::client::{
+    get_partitions, get_topics, GetPartitionsError, MzClientContext, PartitionId,
+    TunnelingClientContext,
+};
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
 use mz_ore::thread::{JoinHandleExt, UnparkOnDropHandle};
 use mz_repr::adt::timestamp::CheckedTimestamp;
-use mz_repr::{adt::jsonb::Jsonb, Datum, Diff, GlobalId, Row};
+use mz_repr::{adt::jsonb::Jsonb, Datum, Diff, GlobalId, RelationDesc, Row};
 use mz_ssh_util::tunnel::SshTunnelStatus;
+use mz_storage_types::controller::AlterError;
 use mz_storage_types::errors::ContextCreationError;
-use mz_storage_types::sources::kafka::{KafkaMetadataKind, KafkaSourceConnection, RangeBound};
-use mz_storage_types::sources::{MzOffset, SourceTimestamp};
+use mz_storage_types::sources::kafka::{
+    epoch_offset, offset_in_epoch, KafkaMetadataKind, KafkaSourceConnection, KafkaTopicPartition,
+    RangeBound,
+};
+use mz_storage_types::sources::{MzOffset, SourceConnection, SourceTimestamp};
+use mz_storage_types::AlterCompatible;
 use mz_timely_util::antichain::AntichainExt;
 use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
 use mz_timely_util::order::Partitioned;
@@ -41,16 +50,19 @@ use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::statistics::Statistics;
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientContext, Message, TopicPartitionList};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use timely::dataflow::operators::Capability;
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 use timely::progress::Timestamp;
-use timely::PartialOrder;
 use tokio::sync::Notify;
 use tracing::{error, info, trace, warn};
 
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::metrics::source::kafka::KafkaSourceMetrics;
+use crate::metrics::StorageMetrics;
 use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
 use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
 
@@ -60,9 +72,119 @@ struct HealthStatus {
     ssh: Option<HealthStatusUpdate>,
 }
 
+/// The partition identifier by which the frontier of a Kafka source is partitioned.
+pub trait KafkaPartitionKey:
+    Clone
+    + Ord
+    + Hash
+    + fmt::Debug
+    + fmt::Display
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + 'static
+{
+    /// Whether partitions are only ever discovered after all the known ones. The frontier of
+    /// such sources doesn't need to keep the gaps between the known partitions open.
+    const ORDERED_DISCOVERY: bool;
+
+    /// Identifies partition `pid` of `topic`.
+    fn new(topic: &str, pid: PartitionId) -> Self;
+
+    /// Returns the topic of this partition, given the topic of the source.
+    fn topic<'a>(&'a self, source_topic: &'a str) -> &'a str;
+
+    /// Returns the id of this partition within its topic.
+    fn pid(&self) -> PartitionId;
+}
+
+impl KafkaPartitionKey for PartitionId {
+    const ORDERED_DISCOVERY: bool = true;
+
+    fn new(_topic: &str, pid: PartitionId) -> Self {
+        pid
+    }
+
+    fn topic<'a>(&'a self, source_topic: &'a str) -> &'a str {
+        source_topic
+    }
+
+    fn pid(&self) -> PartitionId {
+        *self
+    }
+}
+
+impl KafkaPartitionKey for KafkaTopicPartition {
+    const ORDERED_DISCOVERY: bool = false;
+
+    fn new(topic: &str, pid: PartitionId) -> Self {
+        KafkaTopicPartition {
+            topic: topic.to_owned(),
+            partition: pid,
+        }
+    }
+
+    fn topic<'a>(&'a self, _source_topic: &'a str) -> &'a str {
+        &self.topic
+    }
+
+    fn pid(&self) -> PartitionId {
+        self.partition
+    }
+}
+
+/// A Kafka source that consumes from all the topics that match a pattern.
+///
+/// The frontier of these sources is partitioned by topic and partition, whereas the frontier of
+/// single-topic sources is partitioned by partition only and must stay so, as it is recorded in
+/// their remap shards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KafkaTopicPatternSource(pub KafkaSourceConnection);
+
+impl AlterCompatible for KafkaTopicPatternSource {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), AlterError> {
+        self.0.alter_compatible(id, &other.0)
+    }
+}
+
+impl SourceConnection for KafkaTopicPatternSource {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn upstream_name(&self) -> Option<&str> {
+        self.0.upstream_name()
+    }
+
+    fn key_desc(&self) -> RelationDesc {
+        self.0.key_desc()
+    }
+
+    fn value_desc(&self) -> RelationDesc {
+        self.0.value_desc()
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        self.0.timestamp_desc()
+    }
+
+    fn connection_id(&self) -> Option<GlobalId> {
+        self.0.connection_id()
+    }
+
+    fn metadata_columns(&self) -> Vec<(&str, mz_repr::ColumnType)> {
+        self.0.metadata_columns()
+    }
+
+    fn output_idx_for_name(&self, name: &mz_sql_parser::ast::UnresolvedItemName) -> Option<usize> {
+        self.0.output_idx_for_name(name)
+    }
+}
+
 /// Contains all information necessary to ingest data from Kafka
-pub struct KafkaSourceReader {
-    /// Name of the topic on which this source is backed on
+pub struct KafkaSourceReader<P: KafkaPartitionKey> {
+    /// Name of the topic on which this source is backed on, or the pattern of the topics
     topic_name: String,
     /// Name of the source (will have format kafka-source-id)
     source_name: String,
@@ -71,7 +193,7 @@ pub struct KafkaSourceReader {
     /// Kafka consumer for this source
     consumer: Arc<BaseConsumer<TunnelingClientContext<GlueConsumerContext>>>,
     /// List of consumers. A consumer should be assigned per partition to guarantee fairness
-    partition_consumers: Vec<PartitionConsumer>,
+    partition_consumers: Vec<PartitionConsumer<P>>,
     /// Worker ID
     worker_id: usize,
     /// Total count of workers
@@ -79,28 +201,32 @@ pub struct KafkaSourceReader {
     /// The most recently read offset for each partition known to this source
     /// reader. An offset of -1 indicates that no prior message has been read
     /// for the given partition.
-    last_offsets: BTreeMap<PartitionId, i64>,
+    last_offsets: BTreeMap<P, i64>,
     /// The offset to start reading from for each partition.
-    start_offsets: BTreeMap<PartitionId, i64>,
+    start_offsets: BTreeMap<P, i64>,
+    /// The ingestion epoch of the source, which the offsets of its frontier belong to.
+    start_epoch: u64,
     /// Channel to receive Kafka statistics JSON blobs from the stats callback.
     stats_rx: crossbeam_channel::Receiver<Jsonb>,
     /// Progress statistics as collected from the `resume_uppers` stream and the partition metadata
     /// thread.
     progress_statistics: Arc<Mutex<PartialProgressStatistics>>,
     /// The last partition info we received. For each partition we also fetch the high watermark.
-    partition_info: Arc<Mutex<Option<BTreeMap<PartitionId, WatermarkOffsets>>>>,
+    partition_info: Arc<Mutex<Option<BTreeMap<P, WatermarkOffsets>>>>,
     /// A handle to the spawned metadata thread
     // Drop order is important here, we want the thread to be unparked after the `partition_info`
     // Arc has been dropped, so that the unpacked thread notices it and exits immediately
     _metadata_thread_handle: UnparkOnDropHandle<()>,
-    /// A handle to the partition specific metrics
-    partition_metrics: KafkaSourceMetrics,
+    /// Handles to the partition specific metrics of each topic
+    partition_metrics: BTreeMap<String, KafkaSourceMetrics>,
+    /// The storage metrics, used to register the metrics of newly discovered topics
+    storage_metrics: StorageMetrics,
     /// The metadata columns requested by the user
     metadata_columns: Vec<KafkaMetadataKind>,
     /// The latest status detected by the metadata refresh thread.
     health_status: Arc<Mutex<HealthStatus>>,
     /// Per partition capabilities used to produce messages
-    partition_capabilities: BTreeMap<PartitionId, PartitionCapability>,
+    partition_capabilities: BTreeMap<P, PartitionCapability<P>>,
 }
 
 /// A partially-filled version of `ProgressStatisticsUpdate`. This allows us to
@@ -111,11 +237,11 @@ struct PartialProgressStatistics {
     offset_committed: Option<u64>,
 }
 
-struct PartitionCapability {
+struct PartitionCapability<P> {
     /// The capability of the data produced
-    data: Capability<Partitioned<RangeBound<PartitionId>, MzOffset>>,
+    data: Capability<Partitioned<RangeBound<P>, MzOffset>>,
     /// The capability of the progress stream
-    progress: Capability<Partitioned<RangeBound<PartitionId>, MzOffset>>,
+    progress: Capability<Partitioned<RangeBound<P>, MzOffset>>,
 }
 
 /// Represents the low and high watermark offsets of a Kafka partition.
@@ -132,11 +258,13 @@ struct WatermarkOffsets {
 
 /// Processes `resume_uppers` stream updates, committing them upstream and
 /// storing them in the `progress_statistics` to be emitted later.
-pub struct KafkaResumeUpperProcessor {
+pub struct KafkaResumeUpperProcessor<P> {
     config: RawSourceCreationConfig,
     topic_name: String,
     consumer: Arc<BaseConsumer<TunnelingClientContext<GlueConsumerContext>>>,
     progress_statistics: Arc<Mutex<PartialProgressStatistics>>,
+    start_epoch: u64,
+    _partition: std::marker::PhantomData<P>,
 }
 
 impl SourceRender for KafkaSourceConnection {
@@ -162,6 +290,56 @@ impl SourceRender for KafkaSourceConnection {
         Stream<G, ProgressStatisticsUpdate>,
         Vec<PressOnDropButton>,
     ) {
+        self.render_reader(scope, config, resume_uppers, start_signal)
+    }
+}
+
+impl SourceRender for KafkaTopicPatternSource {
+    type Time = Partitioned<RangeBound<KafkaTopicPartition>, MzOffset>;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::Kafka;
+
+    fn render<G: Scope<Timestamp = Partitioned<RangeBound<KafkaTopicPartition>, MzOffset>>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<
+                Item = Antichain<Partitioned<RangeBound<KafkaTopicPartition>, MzOffset>>,
+            > + 'static,
+        start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        self.0
+            .render_reader(scope, config, resume_uppers, start_signal)
+    }
+}
+
+impl KafkaSourceConnection {
+    /// Renders a Kafka source whose frontier is partitioned by `P`.
+    fn render_reader<G, P>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<Partitioned<RangeBound<P>, MzOffset>>>
+            + 'static,
+        start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    )
+    where
+        G: Scope<Timestamp = Partitioned<RangeBound<P>, MzOffset>>,
+        P: KafkaPartitionKey,
+        Partitioned<RangeBound<P>, MzOffset>: SourceTimestamp,
+    {
         let mut builder = AsyncOperatorBuilder::new(config.name.clone(), scope.clone());
 
         let (mut data_output, stream) = builder.new_output();
@@ -170,8 +348,7 @@ impl SourceRender for KafkaSourceConnection {
         let (mut stats_output, stats_stream) = builder.new_output();
 
         let button = builder.build(move |caps| async move {
-            let [mut data_cap, mut progress_cap, health_cap, stats_cap]: [_; 4] =
-                caps.try_into().unwrap();
+            let [data_cap, progress_cap, health_cap, stats_cap]: [_; 4] = caps.try_into().unwrap();
 
             let client_id = self.client_id(
                 config.config.config_set(),
@@ -182,8 +359,10 @@ impl SourceRender for KafkaSourceConnection {
             let KafkaSourceConnection {
                 connection,
                 topic,
+                topic_is_pattern,
                 topic_metadata_refresh_interval,
                 start_offsets,
+                start_epoch,
                 metadata_columns,
                 // Exhaustive match protects against forgetting to apply an
                 // option. Ignored fields are justified below.
@@ -191,20 +370,28 @@ impl SourceRender for KafkaSourceConnection {
                 group_id_prefix: _, // used above via `self.group_id`
             } = self;
 
+            // Topic patterns match whole topic names, like the pattern subscriptions of the Java
+            // consumer.
+            let pattern = topic_is_pattern.then(|| {
+                Regex::new(&format!("^(?:{topic})$")).expect("validated during purification")
+            });
+
             // Start offsets is a map from partition to the next offset to read from.
-            let mut start_offsets: BTreeMap<_, i64> = start_offsets
+            let mut start_offsets: BTreeMap<P, i64> = start_offsets
                 .clone()
                 .into_iter()
+                .map(|(pid, offset)| (P::new(&topic, pid), offset))
                 .filter(|(pid, _offset)| config.responsible_for(pid))
-                .map(|(k, v)| (k, v))
                 .collect();
 
             let mut partition_capabilities = BTreeMap::new();
-            let mut max_pid = None;
+            // All the partitions known to the source, including the ones that other workers are
+            // responsible for.
+            let mut known_partitions = BTreeSet::new();
             let resume_upper = Antichain::from_iter(
                 config.source_resume_uppers[&config.id]
                     .iter()
-                    .map(Partitioned::<RangeBound<PartitionId>, MzOffset>::decode_row),
+                    .map(Partitioned::<RangeBound<P>, MzOffset>::decode_row),
             );
 
             // Whether or not this instance of the dataflow is performing a snapshot.
@@ -213,35 +400,62 @@ impl SourceRender for KafkaSourceConnection {
             for ts in resume_upper.elements() {
                 if let Some(pid) = ts.interval().singleton() {
                     let pid = pid.unwrap_exact();
-                    max_pid = std::cmp::max(max_pid, Some(*pid));
+                    known_partitions.insert(pid.clone());
                     if config.responsible_for(pid) {
-                        let restored_offset = i64::try_from(ts.timestamp().offset)
-                            .expect("restored kafka offsets must fit into i64");
-                        if let Some(start_offset) = start_offsets.get_mut(pid) {
-                            *start_offset = std::cmp::max(restored_offset, *start_offset);
-                        } else {
-                            start_offsets.insert(*pid, restored_offset);
+                        // The offsets reached in earlier epochs are superseded by the start
+                        // offsets of the current one.
+                        if let Some(restored_offset) = offset_in_epoch(start_epoch, *ts.timestamp())
+                        {
+                            let restored_offset = i64::try_from(restored_offset)
+                                .expect("restored kafka offsets must fit into i64");
+                            if let Some(start_offset) = start_offsets.get_mut(pid) {
+                                *start_offset = std::cmp::max(restored_offset, *start_offset);
+                            } else {
+                                start_offsets.insert(pid.clone(), restored_offset);
+                            }
                         }
 
                         let part_ts = Partitioned::new_singleton(
-                            RangeBound::exact(*pid),
+                            RangeBound::exact(pid.clone()),
                             ts.timestamp().clone(),
                         );
                         let part_cap = PartitionCapability {
                             data: data_cap.delayed(&part_ts),
                             progress: progress_cap.delayed(&part_ts),
                         };
-                        partition_capabilities.insert(*pid, part_cap);
+                        partition_capabilities.insert(pid.clone(), part_cap);
                     }
                 }
             }
-            let lower = max_pid
-                .map(RangeBound::after)
-                .unwrap_or(RangeBound::NegInfinity);
+
+            // The capabilities of the ranges of partitions that are not known yet, keyed by the
+            // lower bound of each range. Newly discovered partitions take their capabilities from
+            // the range that contains them.
+            let mut gap_capabilities = BTreeMap::new();
+            let mut lower = RangeBound::NegInfinity;
+            for pid in known_partitions.iter() {
+                if !P::ORDERED_DISCOVERY {
+                    let gap_ts = Partitioned::new_range(
+                        lower.clone(),
+                        RangeBound::before(pid.clone()),
+                        MzOffset::from(0),
+                    );
+                    let gap_cap = PartitionCapability {
+                        data: data_cap.delayed(&gap_ts),
+                        progress: progress_cap.delayed(&gap_ts),
+                    };
+                    gap_capabilities.insert(lower, gap_cap);
+                }
+                lower = RangeBound::after(pid.clone());
+            }
             let future_ts =
-                Partitioned::new_range(lower, RangeBound::PosInfinity, MzOffset::from(0));
-            data_cap.downgrade(&future_ts);
-            progress_cap.downgrade(&future_ts);
+                Partitioned::new_range(lower.clone(), RangeBound::PosInfinity, MzOffset::from(0));
+            let future_cap = PartitionCapability {
+                data: data_cap.delayed(&future_ts),
+                progress: progress_cap.delayed(&future_ts),
+            };
+            gap_capabilities.insert(lower, future_cap);
+            drop((data_cap, progress_cap));
 
             info!(
                 source_id = config.id.to_string(),
@@ -339,6 +553,7 @@ impl SourceRender for KafkaSourceConnection {
             let metadata_thread_handle = {
                 let partition_info = Arc::downgrade(&partition_info);
                 let topic = topic.clone();
+                let pattern = pattern.clone();
                 let consumer = Arc::clone(&consumer);
 
                 // We want a fairly low ceiling on our polling frequency, since we rely
@@ -367,6 +582,7 @@ impl SourceRender for KafkaSourceConnection {
                             let result = fetch_partition_info(
                                 consumer.client(),
                                 &topic,
+                                pattern.as_ref(),
                                 config
                                     .config
                                     .parameters
@@ -432,8 +648,6 @@ impl SourceRender for KafkaSourceConnection {
                     .unwrap()
                     .unpark_on_drop()
             };
-            let partition_ids = start_offsets.keys().copied().collect();
-
             let offset_commit_metrics = config.metrics.get_offset_commit_metrics(config.id);
 
             let mut reader = KafkaSourceReader {
@@ -446,6 +660,7 @@ impl SourceRender for KafkaSourceConnection {
                 worker_count: config.worker_count,
                 last_offsets: BTreeMap::new(),
                 start_offsets,
+                start_epoch,
                 stats_rx,
                 progress_statistics: Default::default(),
                 partition_info,
@@ -454,11 +669,8 @@ impl SourceRender for KafkaSourceConnection {
                     .map(|(_name, kind)| kind)
                     .collect(),
                 _metadata_thread_handle: metadata_thread_handle,
-                partition_metrics: config.metrics.get_kafka_source_metrics(
-                    partition_ids,
-                    topic.clone(),
-                    config.id,
-                ),
+                partition_metrics: BTreeMap::new(),
+                storage_metrics: config.metrics.clone(),
                 health_status,
                 partition_capabilities,
             };
@@ -468,6 +680,8 @@ impl SourceRender for KafkaSourceConnection {
                 topic_name: topic.clone(),
                 consumer,
                 progress_statistics: Arc::clone(&reader.progress_statistics),
+                start_epoch,
+                _partition: std::marker::PhantomData,
             };
 
             // Seed the progress metrics with `0` if we are snapshotting.
@@ -509,42 +723,55 @@ impl SourceRender for KafkaSourceConnection {
             };
             tokio::pin!(resume_uppers_process_loop);
 
-            let mut prev_pid_info: Option<BTreeMap<PartitionId, WatermarkOffsets>> = None;
+            let mut prev_pid_info: Option<BTreeMap<P, WatermarkOffsets>> = None;
             let mut snapshot_total = None;
 
             loop {
                 let partition_info = reader.partition_info.lock().unwrap().take();
                 if let Some(partitions) = partition_info {
-                    let max_pid = partitions.keys().last().cloned();
-                    let lower = max_pid
-                        .map(RangeBound::after)
-                        .unwrap_or(RangeBound::NegInfinity);
-                    let future_ts =
-                        Partitioned::new_range(lower, RangeBound::PosInfinity, MzOffset::from(0));
-
                     // Topics are identified by name but it's possible that a user recreates a
                     // topic with the same name but different configuration. Ideally we'd want to
                     // catch all of these cases and immediately error out the source, since the
                     // data is effectively gone. Unfortunately this is not possible without
                     // something like KIP-516 so we're left with heuristics.
                     //
-                    // The first heuristic is whether the reported number of partitions went down
-                    if !PartialOrder::less_equal(data_cap.time(), &future_ts) {
-                        let prev_pid_count = prev_pid_info.map(|info| info.len()).unwrap_or(0);
-                        let pid_count = partitions.len();
-                        let err = SourceReaderError::other_definite(anyhow!(
-                            "topic was recreated: partition \
-                                     count regressed from {prev_pid_count} to {pid_count}"
-                        ));
-                        let time = data_cap.time().clone();
-                        data_output.give(&data_cap, ((0, Err(err)), time, 1)).await;
-                        return;
+                    // The first heuristic is whether the reported number of partitions of a topic
+                    // went down. Topics that are no longer reported at all have been deleted or,
+                    // for topic patterns, no longer match and are left alone.
+                    let mut known_counts: BTreeMap<&str, (usize, PartitionId)> = BTreeMap::new();
+                    for pid in known_partitions.iter() {
+                        let entry = known_counts.entry(pid.topic(&topic)).or_default();
+                        entry.0 += 1;
+                        entry.1 = std::cmp::max(entry.1, pid.pid());
+                    }
+                    let mut counts: BTreeMap<&str, (usize, PartitionId)> = BTreeMap::new();
+                    for pid in partitions.keys() {
+                        let entry = counts.entry(pid.topic(&topic)).or_default();
+                        entry.0 += 1;
+                        entry.1 = std::cmp::max(entry.1, pid.pid());
+                    }
+                    for (partition_topic, (prev_pid_count, prev_max_pid)) in known_counts {
+                        let Some((pid_count, max_pid)) = counts.get(partition_topic) else {
+                            continue;
+                        };
+                        if *max_pid < prev_max_pid {
+                            let err = SourceReaderError::other_definite(anyhow!(
+                                "topic was recreated: partition \
+                                             count regressed from {prev_pid_count} to {pid_count}"
+                            ));
+                            let cap = &gap_capabilities.values().next_back().unwrap().data;
+                            let time = cap.time().clone();
+                            data_output.give(cap, ((0, Err(err)), time, 1)).await;
+                            return;
+                        }
                     }
 
                     // The second heuristic is whether the high watermark regressed
                     if let Some(prev_pid_info) = prev_pid_info {
                         for (pid, prev_watermarks) in prev_pid_info {
-                            let watermarks = &partitions[&pid];
+                            let Some(watermarks) = partitions.get(&pid) else {
+                                continue;
+                            };
                             if !(prev_watermarks.high <= watermarks.high) {
                                 let err = SourceReaderError::other_definite(anyhow!(
                                     "topic was recreated: high watermark of \
@@ -552,47 +779,88 @@ impl SourceRender for KafkaSourceConnection {
                                     prev_watermarks.high,
                                     watermarks.high
                                 ));
-                                let time = data_cap.time().clone();
-                                data_output.give(&data_cap, ((0, Err(err)), time, 1)).await;
+                                let cap = &gap_capabilities.values().next_back().unwrap().data;
+                                let time = cap.time().clone();
+                                data_output.give(cap, ((0, Err(err)), time, 1)).await;
                                 return;
                             }
                         }
                     }
 
+                    // Carve the newly discovered partitions out of the ranges of unknown
+                    // partitions that contain them.
+                    for pid in partitions.keys() {
+                        if known_partitions.contains(pid) {
+                            continue;
+                        }
+                        let exact = RangeBound::exact(pid.clone());
+                        let Some((lower, gap_cap)) = gap_capabilities
+                            .range(..=exact.clone())
+                            .next_back()
+                            .map(|(lower, gap_cap)| (lower.clone(), gap_cap))
+                        else {
+                            continue;
+                        };
+                        if gap_cap.data.time().interval().upper <= exact {
+                            continue;
+                        }
+                        let gap_cap = gap_capabilities.remove(&lower).unwrap();
+                        let upper = gap_cap.data.time().interval().upper.clone();
+                        let split = |lower: RangeBound<P>, upper: RangeBound<P>| {
+                            let gap_ts = Partitioned::new_range(lower, upper, MzOffset::from(0));
+                            PartitionCapability {
+                                data: gap_cap.data.delayed(&gap_ts),
+                                progress: gap_cap.progress.delayed(&gap_ts),
+                            }
+                        };
+                        if !P::ORDERED_DISCOVERY {
+                            let before = split(lower.clone(), RangeBound::before(pid.clone()));
+                            gap_capabilities.insert(lower, before);
+                        }
+                        let after = split(RangeBound::after(pid.clone()), upper);
+                        gap_capabilities.insert(RangeBound::after(pid.clone()), after);
+                        known_partitions.insert(pid.clone());
+
+                        if config.responsible_for(pid) {
+                            let watermarks = &partitions[pid];
+                            let start_offset = match reader.start_offsets.get(pid) {
+                                Some(&offset) => offset.try_into().unwrap(),
+                                None => 0u64,
+                            };
+                            let start_offset = std::cmp::max(start_offset, watermarks.low);
+                            let part_since_ts = Partitioned::new_singleton(
+                                RangeBound::exact(pid.clone()),
+                                epoch_offset(start_epoch, start_offset),
+                            );
+                            let part_upper_ts = Partitioned::new_singleton(
+                                RangeBound::exact(pid.clone()),
+                                epoch_offset(start_epoch, watermarks.high),
+                            );
+
+                            // This is the moment at which we have discovered a new partition
+                            // and we need to make sure we produce its initial snapshot at a,
+                            // single timestamp so that the source transitions from no data
+                            // from this partition to all the data of this partition. We do
+                            // this by initializing the data capability to the starting offset
+                            // and, importantly, the progress capability directly to the high
+                            // watermark. This jump of the progress capability ensures that
+                            // everything until the high watermark will be reclocked to a
+                            // single point.
+                            reader.partition_capabilities.insert(
+                                pid.clone(),
+                                PartitionCapability {
+                                    data: gap_cap.data.delayed(&part_since_ts),
+                                    progress: gap_cap.progress.delayed(&part_upper_ts),
+                                },
+                            );
+                        }
+                    }
+
                     let mut upstream_stat = 0;
-                    for (&pid, watermarks) in &partitions {
+                    for (pid, watermarks) in &partitions {
                         if config.responsible_for(pid) {
                             upstream_stat += watermarks.high;
                             reader.ensure_partition(pid);
-                            if let Entry::Vacant(entry) = reader.partition_capabilities.entry(pid) {
-                                let start_offset = match reader.start_offsets.get(&pid) {
-                                    Some(&offset) => offset.try_into().unwrap(),
-                                    None => 0u64,
-                                };
-                                let start_offset = std::cmp::max(start_offset, watermarks.low);
-                                let part_since_ts = Partitioned::new_singleton(
-                                    RangeBound::exact(pid),
-                                    MzOffset::from(start_offset),
-                                );
-                                let part_upper_ts = Partitioned::new_singleton(
-                                    RangeBound::exact(pid),
-                                    MzOffset::from(watermarks.high),
-                                );
-
-                                // This is the moment at which we have discovered a new partition
-                                // and we need to make sure we produce its initial snapshot at a,
-                                // single timestamp so that the source transitions from no data
-                                // from this partition to all the data of this partition. We do
-                                // this by initializing the data capability to the starting offset
-                                // and, importantly, the progress capability directly to the high
-                                // watermark. This jump of the progress capability ensures that
-                                // everything until the high watermark will be reclocked to a
-                                // single point.
-                                entry.insert(PartitionCapability {
-                                    data: data_cap.delayed(&part_since_ts),
-                                    progress: progress_cap.delayed(&part_upper_ts),
-                                });
-                            }
                         }
                     }
 
@@ -610,8 +878,6 @@ impl SourceRender for KafkaSourceConnection {
                         .lock()
                         .expect("poisoned")
                         .offset_known = Some(upstream_stat);
-                    data_cap.downgrade(&future_ts);
-                    progress_cap.downgrade(&future_ts);
                     prev_pid_info = Some(partitions);
                 }
 
@@ -678,7 +944,7 @@ impl SourceRender for KafkaSourceConnection {
                                 let pid = consumer.pid();
                                 let last_offset = reader
                                     .last_offsets
-                                    .get(&pid)
+                                    .get(pid)
                                     .expect("partition known to be installed");
 
                                 let status = HealthStatusUpdate::stalled(
@@ -708,17 +974,19 @@ impl SourceRender for KafkaSourceConnection {
                 reader.partition_consumers = consumers;
 
                 let positions = reader.consumer.position().unwrap();
-                let topic_positions = positions.elements_for_topic(&reader.topic_name);
                 let mut snapshot_staged = 0;
 
-                for position in topic_positions {
+                for position in positions.elements() {
                     // The offset begins in the `Offset::Invalid` state in which case we simply
                     // skip this partition.
                     if let Offset::Offset(offset) = position.offset() {
-                        let pid = position.partition();
-                        let upper_offset = MzOffset::from(u64::try_from(offset).unwrap());
-                        let upper =
-                            Partitioned::new_singleton(RangeBound::exact(pid), upper_offset);
+                        let pid = P::new(position.topic(), position.partition());
+                        let upper_offset =
+                            epoch_offset(start_epoch, u64::try_from(offset).unwrap());
+                        let upper = Partitioned::new_singleton(
+                            RangeBound::exact(pid.clone()),
+                            upper_offset,
+                        );
 
                         let part_cap = reader.partition_capabilities.get_mut(&pid).unwrap();
                         part_cap.data.downgrade(&upper);
@@ -838,10 +1106,10 @@ impl SourceRender for KafkaSourceConnection {
     }
 }
 
-impl KafkaResumeUpperProcessor {
+impl<P: KafkaPartitionKey> KafkaResumeUpperProcessor<P> {
     async fn process_frontier(
         &self,
-        frontier: Antichain<Partitioned<RangeBound<PartitionId>, MzOffset>>,
+        frontier: Antichain<Partitioned<RangeBound<P>, MzOffset>>,
     ) -> Result<(), anyhow::Error> {
         use rdkafka::consumer::CommitMode;
 
@@ -851,14 +1119,19 @@ impl KafkaResumeUpperProcessor {
         for ts in frontier.iter() {
             if let Some(pid) = ts.interval().singleton() {
                 let pid = pid.unwrap_exact();
+                // Partitions whose frontier is still in an earlier epoch have not made any
+                // progress in the current one.
+                let Some(offset) = offset_in_epoch(self.start_epoch, *ts.timestamp()) else {
+                    continue;
+                };
                 if self.config.responsible_for(pid) {
-                    offsets.push((pid.clone(), *ts.timestamp()));
+                    offsets.push((pid.clone(), offset));
 
                     // Note that we do not subtract 1 from the frontier. Imagine
                     // that frontier is 2 for this pid. That means we have
                     // full processed offset 0 and offset 1, which means we have
                     // processed _2_ offsets.
-                    progress_stat += ts.timestamp().offset;
+                    progress_stat += offset;
                 }
             }
         }
@@ -871,8 +1144,8 @@ impl KafkaResumeUpperProcessor {
             let mut tpl = TopicPartitionList::new();
             for (pid, offset) in offsets {
                 let offset_to_commit =
-                    Offset::Offset(offset.offset.try_into().expect("offset to be vald i64"));
-                tpl.add_partition_offset(&self.topic_name, pid, offset_to_commit)
+                    Offset::Offset(offset.try_into().expect("offset to be vald i64"));
+                tpl.add_partition_offset(pid.topic(&self.topic_name), pid.pid(), offset_to_commit)
                     .expect("offset known to be valid");
             }
             let consumer = Arc::clone(&self.consumer);
@@ -886,44 +1159,53 @@ impl KafkaResumeUpperProcessor {
     }
 }
 
-impl KafkaSourceReader {
+impl<P: KafkaPartitionKey> KafkaSourceReader<P> {
     /// Ensures that a partition queue for `pid` exists.
-    fn ensure_partition(&mut self, pid: PartitionId) {
-        if self.last_offsets.contains_key(&pid) {
+    fn ensure_partition(&mut self, pid: &P) {
+        if self.last_offsets.contains_key(pid) {
             return;
         }
 
-        let start_offset = self.start_offsets.get(&pid).copied().unwrap_or(0);
-        self.create_partition_queue(pid, Offset::Offset(start_offset));
+        let topic = pid.topic(&self.topic_name);
+        if !self.partition_metrics.contains_key(topic) {
+            let metrics =
+                self.storage_metrics
+                    .get_kafka_source_metrics(vec![], topic.to_owned(), self.id);
+            self.partition_metrics.insert(topic.to_owned(), metrics);
+        }
+
+        let start_offset = self.start_offsets.get(pid).copied().unwrap_or(0);
+        self.create_partition_queue(pid.clone(), Offset::Offset(start_offset));
 
-        let prev = self.last_offsets.insert(pid, start_offset - 1);
+        let prev = self.last_offsets.insert(pid.clone(), start_offset - 1);
 
         assert!(prev.is_none());
     }
 
     /// Creates a new partition queue for `partition_id`.
-    fn create_partition_queue(&mut self, partition_id: PartitionId, initial_offset: Offset) {
+    fn create_partition_queue(&mut self, partition_id: P, initial_offset: Offset) {
+        let topic = partition_id.topic(&self.topic_name);
         info!(
             source_id = self.id.to_string(),
             worker_id = self.worker_id,
             num_workers = self.worker_count,
             "activating Kafka queue for topic {}, partition {}",
-            self.topic_name,
-            partition_id,
+            topic,
+            partition_id.pid(),
         );
 
         // Collect old partition assignments
         let tpl = self.consumer.assignment().unwrap();
         // Create list from assignments
         let mut partition_list = TopicPartitionList::new();
-        for partition in tpl.elements_for_topic(&self.topic_name) {
+        for partition in tpl.elements() {
             partition_list
                 .add_partition_offset(partition.topic(), partition.partition(), partition.offset())
                 .expect("offset known to be valid");
         }
         // Add new partition
         partition_list
-            .add_partition_offset(&self.topic_name, partition_id, initial_offset)
+            .add_partition_offset(topic, partition_id.pid(), initial_offset)
             .expect("offset known to be valid");
         self.consumer
             .assign(&partition_list)
@@ -935,7 +1217,7 @@ impl KafkaSourceReader {
         for pc in &mut self.partition_consumers {
             pc.partition_queue = self
                 .consumer
-                .split_partition_queue(&self.topic_name, pc.pid)
+                .split_partition_queue(pc.pid.topic(&self.topic_name), pc.pid.pid())
                 .expect("partition known to be valid");
             pc.partition_queue.set_nonempty_callback({
                 let context = Arc::clone(&context);
@@ -945,7 +1227,7 @@ impl KafkaSourceReader {
 
         let mut partition_queue = self
             .consumer
-            .split_partition_queue(&self.topic_name, partition_id)
+            .split_partition_queue(topic, partition_id.pid())
             .expect("partition known to be valid");
         partition_queue.set_nonempty_callback(move || context.inner().activate());
         self.partition_consumers.push(PartitionConsumer::new(
@@ -954,11 +1236,7 @@ impl KafkaSourceReader {
             self.metadata_columns.clone(),
         ));
         assert_eq!(
-            self.consumer
-                .assignment()
-                .unwrap()
-                .elements_for_topic(&self.topic_name)
-                .len(),
+            self.consumer.assignment().unwrap().count(),
             self.partition_consumers.len()
         );
     }
@@ -966,10 +1244,11 @@ impl KafkaSourceReader {
     /// Fast-forward consumer to specified Kafka Offset. Prints a warning if failed to do so
     /// Assumption: if offset does not exist (for instance, because of compaction), will seek
     /// to the next available offset
-    fn fast_forward_consumer(&self, pid: PartitionId, next_offset: i64) {
+    fn fast_forward_consumer(&self, pid: &P, next_offset: i64) {
+        let topic = pid.topic(&self.topic_name);
         let res = self.consumer.seek(
-            &self.topic_name,
-            pid,
+            topic,
+            pid.pid(),
             Offset::Offset(next_offset),
             Duration::from_secs(1),
         );
@@ -977,7 +1256,7 @@ impl KafkaSourceReader {
             Ok(_) => {
                 let res = self.consumer.position().unwrap_or_default().to_topic_map();
                 let position = res
-                    .get(&(self.topic_name.clone(), pid))
+                    .get(&(topic.to_owned(), pid.pid()))
                     .and_then(|p| match p {
                         Offset::Offset(o) => Some(o),
                         _ => None,
@@ -1034,15 +1313,16 @@ impl KafkaSourceReader {
         while let Ok(stats) = self.stats_rx.try_recv() {
             match serde_json::from_str::<Statistics>(&stats.to_string()) {
                 Ok(statistics) => {
-                    let topic = statistics.topics.get(&self.topic_name);
-                    match topic {
-                        Some(topic) => {
-                            for (id, partition) in &topic.partitions {
-                                self.partition_metrics
-                                    .set_offset_max(*id, partition.hi_offset);
+                    for (topic_name, partition_metrics) in &mut self.partition_metrics {
+                        let topic = statistics.topics.get(topic_name);
+                        match topic {
+                            Some(topic) => {
+                                for (id, partition) in &topic.partitions {
+                                    partition_metrics.set_offset_max(*id, partition.hi_offset);
+                                }
                             }
+                            None => error!("No stats found for topic: {}", topic_name),
                         }
-                        None => error!("No stats found for topic: {}", &self.topic_name),
                     }
                 }
                 Err(e) => {
@@ -1057,10 +1337,10 @@ impl KafkaSourceReader {
     fn handle_message(
         &mut self,
         message: Result<SourceMessage, KafkaHeaderParseError>,
-        (partition, offset): (PartitionId, MzOffset),
+        (partition, offset): (P, MzOffset),
     ) -> Option<(
         Result<SourceMessage, KafkaHeaderParseError>,
-        Partitioned<RangeBound<PartitionId>, MzOffset>,
+        Partitioned<RangeBound<P>, MzOffset>,
         Diff,
     )> {
         // Offsets are guaranteed to be 1) monotonically increasing *unless* there is
@@ -1092,13 +1372,13 @@ impl KafkaSourceReader {
                 source {} (reading topic {}, partition {}) \
                 received offset {} expected offset {:?}",
                 self.source_name,
-                self.topic_name,
-                partition,
+                partition.topic(&self.topic_name),
+                partition.pid(),
                 offset.offset,
                 last_offset + 1,
             );
             // Seek to the *next* offset that we have not yet processed
-            self.fast_forward_consumer(partition, last_offset + 1);
+            self.fast_forward_consumer(&partition, last_offset + 1);
             // We explicitly should not consume the message as we have already processed it
             // However, we make sure to activate the source to make sure that we get a chance
             // to read from this consumer again (even if no new data arrives)
@@ -1106,20 +1386,21 @@ impl KafkaSourceReader {
         } else {
             *last_offset_ref = offset_as_i64;
 
-            let ts = Partitioned::new_singleton(RangeBound::exact(partition), offset);
+            let ts = Partitioned::new_singleton(
+                RangeBound::exact(partition),
+                epoch_offset(self.start_epoch, offset.offset),
+            );
             Some((message, ts, 1))
         }
     }
 }
 
-fn construct_source_message(
+fn construct_source_message<P: KafkaPartitionKey>(
     msg: &BorrowedMessage<'_>,
     metadata_columns: &[KafkaMetadataKind],
-) -> (
-    Result<SourceMessage, KafkaHeaderParseError>,
-    (PartitionId, MzOffset),
-) {
+) -> (Result<SourceMessage, KafkaHeaderParseError>, (P, MzOffset)) {
     let pid = msg.partition();
+    let key = P::new(msg.topic(), pid);
     let Ok(offset) = u64::try_from(msg.offset()) else {
         panic!(
             "got negative offset ({}) from otherwise non-error'd kafka message",
@@ -1131,6 +1412,7 @@ fn construct_source_message(
     let mut packer = metadata.packer();
     for kind in metadata_columns {
         match kind {
+            KafkaMetadataKind::Topic => packer.push(Datum::String(msg.topic())),
             KafkaMetadataKind::Partition => packer.push(Datum::from(pid)),
             KafkaMetadataKind::Offset => packer.push(Datum::UInt64(offset)),
             KafkaMetadataKind::Timestamp => {
@@ -1177,7 +1459,7 @@ fn construct_source_message(
                         match d {
                             Ok(d) => packer.push(d),
                             //abort with a definite error when the header is not found or cannot be parsed correctly
-                            Err(err) => return (Err(err), (pid, offset.into())),
+                            Err(err) => return (Err(err), (key, offset.into())),
                         }
                     }
                     None => packer.push(Datum::Null),
@@ -1218,24 +1500,24 @@ fn construct_source_message(
             value,
             metadata,
         }),
-        (pid, offset.into()),
+        (key, offset.into()),
     )
 }
 
 /// Wrapper around a partition containing the underlying consumer
-struct PartitionConsumer {
+struct PartitionConsumer<P> {
     /// the partition id with which this consumer is associated
-    pid: PartitionId,
+    pid: P,
     /// The underlying Kafka partition queue
     partition_queue: PartitionQueue<TunnelingClientContext<GlueConsumerContext>>,
     /// Additional metadata columns requested by the user
     metadata_columns: Vec<KafkaMetadataKind>,
 }
 
-impl PartitionConsumer {
+impl<P: KafkaPartitionKey> PartitionConsumer<P> {
     /// Creates a new partition consumer from underlying Kafka consumer
     fn new(
-        pid: PartitionId,
+        pid: P,
         partition_queue: PartitionQueue<TunnelingClientContext<GlueConsumerContext>>,
         metadata_columns: Vec<KafkaMetadataKind>,
     ) -> Self {
@@ -1254,13 +1536,8 @@ impl PartitionConsumer {
     /// The inner `Option` represents if there is a message to process.
     fn get_next_message(
         &mut self,
-    ) -> Result<
-        Option<(
-            Result<SourceMessage, KafkaHeaderParseError>,
-            (PartitionId, MzOffset),
-        )>,
-        KafkaError,
-    > {
+    ) -> Result<Option<(Result<SourceMessage, KafkaHeaderParseError>, (P, MzOffset))>, KafkaError>
+    {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
                 let (msg, ts) = construct_source_message(&msg, &self.metadata_columns);
@@ -1273,8 +1550,8 @@ impl PartitionConsumer {
     }
 
     /// Return the partition id for this PartitionConsumer
-    fn pid(&self) -> PartitionId {
-        self.pid
+    fn pid(&self) -> &P {
+        &self.pid
     }
 }
 
@@ -1411,23 +1688,39 @@ mod tests {
     }
 }
 
-/// Fetches the list of partitions and their corresponding high watermark
-fn fetch_partition_info<C: ClientContext>(
+/// Fetches the list of partitions and their corresponding high watermark, either of `topic` or,
+/// if a `pattern` is given, of all the topics that match it.
+fn fetch_partition_info<C: ClientContext, P: KafkaPartitionKey>(
     client: &Client<C>,
     topic: &str,
+    pattern: Option<&Regex>,
     fetch_timeout: Duration,
-) -> Result<BTreeMap<PartitionId, WatermarkOffsets>, anyhow::Error> {
-    let pids = get_partitions(client, topic, fetch_timeout)?;
+) -> Result<BTreeMap<P, WatermarkOffsets>, anyhow::Error> {
+    let topics = match pattern {
+        Some(pattern) => get_topics(client, fetch_timeout)?
+            .into_iter()
+            .filter(|topic| pattern.is_match(topic))
+            .collect(),
+        None => vec![topic.to_owned()],
+    };
 
     let mut result = BTreeMap::new();
 
-    for pid in pids {
-        let (low, high) = client.fetch_watermarks(topic, pid, fetch_timeout)?;
-        let watermarks = WatermarkOffsets {
-            low: low.try_into().expect("invalid negative offset"),
-            high: high.try_into().expect("invalid negative offset"),
+    for topic in topics {
+        let pids = match get_partitions(client, &topic, fetch_timeout) {
+            Ok(pids) => pids,
+            // Topics that match a pattern can be deleted after they are listed.
+            Err(GetPartitionsError::TopicDoesNotExist) if pattern.is_some() => continue,
+            Err(err) => return Err(err.into()),
         };
-        result.insert(pid, watermarks);
+        for pid in pids {
+            let (low, high) = client.fetch_watermarks(&topic, pid, fetch_timeout)?;
+            let watermarks = WatermarkOffsets {
+                low: low.try_into().expect("invalid negative offset"),
+                high: high.try_into().expect("invalid negative offset"),
+            };
+            result.insert(P::new(&topic, pid), watermarks);
+        }
     }
     Ok(result)
 }
@@ -0,0 +1,517 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that ingests the records of a Kinesis data stream.
+//!
+//! The timestamp of the source is a partitioned position whose partitions are
+//! shard IDs. The position of a shard is right after the sequence number of
+//! the last record that has been ingested from it, so the frontier of the
+//! source records how far every open shard has been read, while the ranges
+//! between known shards are held at the start until a new shard is discovered
+//! there. Once a shard has been closed by a resharding and all of its records
+//! have been ingested, its capability is dropped, which removes it from the
+//! frontier for good.
+//!
+//! On restart, every open shard is read with an iterator that starts right
+//! after the sequence number in the resume upper, or at the trim horizon of
+//! shards from which no record had been ingested. This requires that the
+//! stream retains records until they have been ingested by the source.
+//!
+//! The records of a child shard are only ingested once all of its parent
+//! shards have been closed and fully ingested, which preserves the order of
+//! records that share a partition key across reshardings.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::convert::Infallible;
+use std::time::Duration;
+
+use aws_sdk_kinesis::types::ShardIteratorType;
+use differential_dataflow::{AsCollection, Collection};
+use futures::StreamExt;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::future::InTask;
+use mz_repr::{Datum, Diff, Row};
+use mz_storage_types::sources::kafka::RangeBound;
+use mz_storage_types::sources::kinesis::{
+    KinesisPosition, KinesisSourceConnection, KinesisTimestamp,
+};
+use mz_storage_types::sources::SourceTimestamp;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use mz_timely_util::order::Partitioned;
+use timely::dataflow::operators::Capability;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+use tracing::info;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
+use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+
+/// How long to wait before polling a shard again while it is behind the tip of
+/// the stream. Kinesis allows at most five `GetRecords` calls per second and
+/// shard.
+const CATCH_UP_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The capabilities held for a part of the shard ID space.
+struct PendingCapability {
+    data: Capability<KinesisTimestamp>,
+    progress: Capability<KinesisTimestamp>,
+}
+
+impl PendingCapability {
+    fn delayed(&self, ts: &KinesisTimestamp) -> Self {
+        PendingCapability {
+            data: self.data.delayed(ts),
+            progress: self.progress.delayed(ts),
+        }
+    }
+
+    fn downgrade(&mut self, ts: &KinesisTimestamp) {
+        self.data.downgrade(ts);
+        self.progress.downgrade(ts);
+    }
+}
+
+/// The state of an open shard that is being ingested.
+struct ShardReader {
+    cap: PendingCapability,
+    /// The position after the last record ingested from the shard.
+    position: KinesisPosition,
+    /// The iterator to read the next records of the shard with, if one has
+    /// been obtained.
+    iterator: Option<String>,
+    /// The IDs of the shards that must be fully ingested before this shard.
+    parents: Vec<String>,
+}
+
+/// The statistics of a shard that has been read by this reader.
+#[derive(Default)]
+struct ShardStats {
+    /// The number of records ingested from the shard by this reader.
+    ingested: u64,
+    /// The number of those records whose ingestion has been committed.
+    committed: u64,
+    /// The positions that the shard has been read up to whose ingestion has
+    /// not been committed yet, with the value of `ingested` at each of them.
+    uncommitted: VecDeque<(KinesisPosition, u64)>,
+}
+
+impl ShardStats {
+    /// Records that the shard has been read up to `position`, ingesting
+    /// `count` more records.
+    fn ingest(&mut self, position: &KinesisPosition, count: u64) {
+        if count > 0 {
+            self.ingested += count;
+            self.uncommitted
+                .push_back((position.clone(), self.ingested));
+        }
+    }
+
+    /// Records that the ingestion of the shard has been committed up to
+    /// `position`, or entirely if `position` is `None`.
+    fn commit(&mut self, position: Option<&KinesisPosition>) {
+        while let Some((uncommitted, ingested)) = self.uncommitted.front() {
+            if position.map_or(false, |position| uncommitted > position) {
+                break;
+            }
+            self.committed = *ingested;
+            self.uncommitted.pop_front();
+        }
+    }
+}
+
+impl SourceRender for KinesisSourceConnection {
+    type Time = KinesisTimestamp;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::Kinesis;
+
+    fn render<G: Scope<Timestamp = KinesisTimestamp>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<KinesisTimestamp>> + 'static,
+        start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        let mut builder = AsyncOperatorBuilder::new(config.name.clone(), scope.clone());
+
+        let (mut data_output, stream) = builder.new_output();
+        let (_progress_output, progress_stream) = builder.new_output();
+        let (mut health_output, health_stream) = builder.new_output();
+        let (mut stats_output, stats_stream) = builder.new_output();
+
+        let button = builder.build(move |caps| async move {
+            let [data_cap, progress_cap, health_cap, stats_cap]: [_; 4] = caps.try_into().unwrap();
+
+            if !config.responsible_for(()) {
+                // Emit 0, to mark this worker as having started up correctly.
+                stats_output
+                    .give(
+                        &stats_cap,
+                        ProgressStatisticsUpdate::SteadyState {
+                            offset_known: 0,
+                            offset_committed: 0,
+                        },
+                    )
+                    .await;
+                return;
+            }
+
+            let resume_upper = Antichain::from_iter(
+                config.source_resume_uppers[&config.id]
+                    .iter()
+                    .map(KinesisTimestamp::decode_row),
+            );
+
+            // The parts of the shard ID space in which no shard has been
+            // discovered yet, keyed by their lower bound.
+            let mut pending = BTreeMap::new();
+            // The open shards, keyed by their ID.
+            let mut shards = BTreeMap::new();
+            let initial_cap = PendingCapability {
+                data: data_cap,
+                progress: progress_cap,
+            };
+            for ts in resume_upper.elements() {
+                match ts.interval().singleton() {
+                    Some(RangeBound::Elem(shard_id, _)) => {
+                        let reader = ShardReader {
+                            cap: initial_cap.delayed(ts),
+                            position: ts.timestamp().clone(),
+                            iterator: None,
+                            parents: vec![],
+                        };
+                        shards.insert(shard_id.clone(), reader);
+                    }
+                    Some(bound) => unreachable!("invalid singleton {bound:?}"),
+                    None => {
+                        pending.insert(ts.interval().lower.clone(), initial_cap.delayed(ts));
+                    }
+                }
+            }
+            drop(initial_cap);
+
+            info!(
+                source_id = config.id.to_string(),
+                worker_id = config.worker_id,
+                num_workers = config.worker_count,
+                "instantiating kinesis source reader for stream {} with {} open shards",
+                self.stream_name,
+                shards.len(),
+            );
+
+            let client = match self
+                .client(&config.config.connection_context, InTask::Yes)
+                .await
+            {
+                Ok(client) => client,
+                Err(err) => {
+                    let update = HealthStatusUpdate::halting(
+                        format!("failed to create client: {}", err.display_with_causes()),
+                        None,
+                    );
+                    health_output
+                        .give(
+                            &health_cap,
+                            HealthStatusMessage {
+                                index: 0,
+                                namespace: Self::STATUS_NAMESPACE.clone(),
+                                update,
+                            },
+                        )
+                        .await;
+                    // IMPORTANT: wedge forever until the `SuspendAndRestart` is processed.
+                    // Returning would incorrectly present to the remap operator as progress to the
+                    // empty frontier which would be incorrectly recorded to the remap shard.
+                    std::future::pending::<()>().await;
+                    unreachable!("pending future never returns");
+                }
+            };
+
+            // Note that we wait for this AFTER we downgrade to the source `resume_upper`. This
+            // allows downstream operators (namely, the `reclock_operator`) to downgrade to the
+            // `resume_upper`, which is necessary for this basic form of backpressure to work.
+            start_signal.await;
+
+            let mut resume_uppers = std::pin::pin!(resume_uppers);
+            // The statistics of every shard seen by this reader, including
+            // shards that have since been fully ingested.
+            let mut shard_stats: BTreeMap<String, ShardStats> = shards
+                .keys()
+                .map(|shard_id| (shard_id.clone(), ShardStats::default()))
+                .collect();
+            let mut last_status = None;
+            let mut ticker = tokio::time::interval(config.timestamp_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut list_shards = true;
+            // Whether the shards have been listed at least once, which is
+            // required to know the parents of the shards we resumed.
+            let mut listed = false;
+
+            loop {
+                let mut status = HealthStatusUpdate::running();
+
+                if list_shards {
+                    match mz_aws_util::kinesis::list_shards(&client, &self.stream_name).await {
+                        Ok(shard_list) => {
+                            listed = true;
+                            for shard in shard_list {
+                                let shard_id = shard.shard_id().to_owned();
+                                let parents: Vec<_> = shard
+                                    .parent_shard_id()
+                                    .into_iter()
+                                    .chain(shard.adjacent_parent_shard_id())
+                                    .map(|id| id.to_owned())
+                                    .collect();
+                                if let Some(reader) = shards.get_mut(&shard_id) {
+                                    reader.parents = parents;
+                                    continue;
+                                }
+
+                                let exact = RangeBound::exact(shard_id.clone());
+                                let lower = match pending.range(..=exact.clone()).next_back() {
+                                    Some((lower, cap))
+                                        if cap.data.time().interval().contains(&exact) =>
+                                    {
+                                        lower.clone()
+                                    }
+                                    // The shard has already been fully ingested.
+                                    _ => continue,
+                                };
+                                let cap = pending.remove(&lower).expect("known to exist");
+
+                                // Split the pending range around the new shard.
+                                let interval = cap.data.time().interval();
+                                let before = Partitioned::new_range(
+                                    interval.lower.clone(),
+                                    RangeBound::before(shard_id.clone()),
+                                    KinesisPosition::Start,
+                                );
+                                let after = Partitioned::new_range(
+                                    RangeBound::after(shard_id.clone()),
+                                    interval.upper.clone(),
+                                    KinesisPosition::Start,
+                                );
+                                pending
+                                    .insert(before.interval().lower.clone(), cap.delayed(&before));
+                                pending.insert(after.interval().lower.clone(), cap.delayed(&after));
+
+                                let ts = Partitioned::new_singleton(exact, KinesisPosition::Start);
+                                let reader = ShardReader {
+                                    cap: cap.delayed(&ts),
+                                    position: KinesisPosition::Start,
+                                    iterator: None,
+                                    parents,
+                                };
+                                shard_stats.insert(shard_id.clone(), ShardStats::default());
+                                shards.insert(shard_id, reader);
+                            }
+                        }
+                        Err(err) => {
+                            status = HealthStatusUpdate::stalled(
+                                format!("failed to list shards: {}", err.display_with_causes()),
+                                None,
+                            );
+                        }
+                    }
+                }
+
+                // Shards are only read once their parents have been fully
+                // ingested. Parents that are not open either have been
+                // ingested already or have aged out of the stream.
+                let open: BTreeSet<_> = shards.keys().cloned().collect();
+                let mut behind = false;
+                let mut finished = vec![];
+                for (shard_id, reader) in shards.iter_mut() {
+                    if !listed || reader.parents.iter().any(|parent| open.contains(parent)) {
+                        continue;
+                    }
+
+                    let iterator = match &reader.iterator {
+                        Some(iterator) => iterator.clone(),
+                        None => {
+                            let req = client
+                                .get_shard_iterator()
+                                .stream_name(&self.stream_name)
+                                .shard_id(shard_id);
+                            let req = match &reader.position {
+                                KinesisPosition::Start => {
+                                    req.shard_iterator_type(ShardIteratorType::TrimHorizon)
+                                }
+                                KinesisPosition::After(sequence_number) => req
+                                    .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+                                    .starting_sequence_number(sequence_number),
+                            };
+                            match req.send().await.map(|res| res.shard_iterator) {
+                                Ok(Some(iterator)) => iterator,
+                                Ok(None) => {
+                                    status = HealthStatusUpdate::stalled(
+                                        format!("no shard iterator returned for shard {shard_id}"),
+                                        None,
+                                    );
+                                    continue;
+                                }
+                                Err(err) => {
+                                    status = HealthStatusUpdate::stalled(
+                                        format!(
+                                            "failed to get shard iterator for shard {}: {}",
+                                            shard_id,
+                                            err.display_with_causes()
+                                        ),
+                                        None,
+                                    );
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+
+                    let res = match client.get_records().shard_iterator(iterator).send().await {
+                        Ok(res) => res,
+                        Err(err) => {
+                            // Obtain a fresh iterator on the next attempt, as
+                            // the current one may have expired.
+                            status = HealthStatusUpdate::stalled(
+                                format!(
+                                    "failed to get records from shard {}: {}",
+                                    shard_id,
+                                    err.display_with_causes()
+                                ),
+                                None,
+                            );
+                            reader.iterator = None;
+                            continue;
+                        }
+                    };
+
+                    for record in res.records() {
+                        let ts = Partitioned::new_singleton(
+                            RangeBound::exact(shard_id.clone()),
+                            reader.position.clone(),
+                        );
+                        let message = SourceMessage {
+                            key: Row::pack([Datum::Null]),
+                            value: Row::pack([Datum::Bytes(record.data().as_ref())]),
+                            metadata: Row::default(),
+                        };
+                        data_output
+                            .give(&reader.cap.data, ((0, Ok(message)), ts, 1))
+                            .await;
+                        reader.position =
+                            KinesisPosition::After(record.sequence_number().to_owned());
+                    }
+                    let ts = Partitioned::new_singleton(
+                        RangeBound::exact(shard_id.clone()),
+                        reader.position.clone(),
+                    );
+                    reader.cap.downgrade(&ts);
+                    shard_stats
+                        .get_mut(shard_id)
+                        .expect("known shard")
+                        .ingest(&reader.position, u64::cast_from(res.records().len()));
+
+                    match res.next_shard_iterator {
+                        Some(iterator) => {
+                            reader.iterator = Some(iterator);
+                            behind |= res.millis_behind_latest.unwrap_or(0) > 0;
+                        }
+                        // The shard has been closed and all of its records
+                        // have been ingested.
+                        None => finished.push(shard_id.clone()),
+                    }
+                }
+                for shard_id in finished {
+                    info!(
+                        source_id = config.id.to_string(),
+                        "finished ingesting closed kinesis shard {shard_id}"
+                    );
+                    shards.remove(&shard_id);
+                }
+
+                if last_status.as_ref() != Some(&status) {
+                    health_output
+                        .give(
+                            &health_cap,
+                            HealthStatusMessage {
+                                index: 0,
+                                namespace: Self::STATUS_NAMESPACE.clone(),
+                                update: status.clone(),
+                            },
+                        )
+                        .await;
+                    last_status = Some(status);
+                }
+
+                // Wait for the next poll, while keeping track of the records
+                // whose ingestion has been committed. Shards that are behind
+                // the tip of the stream are polled again sooner, but the shard
+                // list is only refreshed on every tick.
+                let mut tick = std::pin::pin!(ticker.tick());
+                let mut catch_up = std::pin::pin!(tokio::time::sleep(CATCH_UP_INTERVAL));
+                loop {
+                    tokio::select! {
+                        _ = &mut tick => {
+                            list_shards = true;
+                            break;
+                        }
+                        _ = &mut catch_up, if behind => {
+                            list_shards = false;
+                            break;
+                        }
+                        Some(frontier) = resume_uppers.next() => {
+                            commit_stats(&mut shard_stats, &frontier);
+                        }
+                    }
+                }
+                stats_output
+                    .give(
+                        &stats_cap,
+                        ProgressStatisticsUpdate::SteadyState {
+                            offset_known: shard_stats.values().map(|s| s.ingested).sum(),
+                            offset_committed: shard_stats.values().map(|s| s.committed).sum(),
+                        },
+                    )
+                    .await;
+            }
+        });
+
+        (
+            stream.as_collection(),
+            Some(progress_stream),
+            health_stream,
+            stats_stream,
+            vec![button.press_on_drop()],
+        )
+    }
+}
+
+/// Records in `shard_stats` which records have been committed according to
+/// `frontier`.
+///
+/// Shards that are no longer covered by the frontier have been fully ingested,
+/// so all of their records count as committed.
+fn commit_stats(
+    shard_stats: &mut BTreeMap<String, ShardStats>,
+    frontier: &Antichain<KinesisTimestamp>,
+) {
+    for (shard_id, stats) in shard_stats.iter_mut() {
+        let exact = RangeBound::exact(shard_id.clone());
+        match frontier.iter().find(|ts| ts.interval().contains(&exact)) {
+            Some(ts) if ts.interval().singleton().is_some() => stats.commit(Some(ts.timestamp())),
+            Some(_) => {}
+            None => stats.commit(None),
+        }
+    }
+}
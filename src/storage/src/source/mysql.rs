@@ -22,6 +22,24 @@
 //! the replication operator containing the GTID-set based frontier which will be used to
 //! ensure that the requested portion of the replication stream is subtracted from the snapshot.
 //!
+//! # Servers without GTIDs
+//!
+//! Sources that replicate from servers with `gtid_mode` turned off track their progress by
+//! position in the server's binary log instead. These positions are represented as timestamps of
+//! a single GTID partition for the server's `@@server_uuid`, see
+//! [`mz_storage_types::sources::mysql::MySqlBinlogDetails`], so that both operators can treat
+//! them exactly like GTID sets. The differences are confined to how the current position of the
+//! server is read and how the replication stream is requested and interpreted.
+//!
+//! # MariaDB
+//!
+//! MariaDB identifies transactions by GTIDs of the form `domain-server-sequence`. Each pair of
+//! domain and server id is mapped to a partition of [`GtidPartition`], see
+//! [`mz_storage_types::sources::mysql::MariaDbGtid`], whose timestamps are the sequence numbers of
+//! the transactions. The current GTID set of the server is read from `@@gtid_binlog_state`
+//! instead of `@@gtid_executed`, and the replication stream is requested and interpreted using
+//! MariaDB's own protocol extensions.
+//!
 //! See the [snapshot] module for more information.
 //!
 //! # Replication
@@ -66,20 +84,20 @@ use timely::progress::Antichain;
 use uuid::Uuid;
 
 use mz_mysql_util::{
-    ensure_full_row_binlog_format, ensure_gtid_consistency, ensure_replication_commit_order,
-    MySqlError, MySqlTableDesc,
+    ensure_full_row_binlog_format, ensure_gtid_consistency, ensure_mariadb_gtid_consistency,
+    ensure_replication_commit_order, query_sys_var, MySqlError, MySqlTableDesc,
 };
 use mz_ore::error::ErrorExt;
 use mz_repr::{Diff, Row};
 use mz_storage_types::errors::SourceErrorDetails;
-use mz_storage_types::sources::mysql::{GtidPartition, GtidState};
+use mz_storage_types::sources::mysql::{GtidPartition, GtidState, MySqlFlavor, MySqlSourceDetails};
 use mz_storage_types::sources::{MySqlSourceConnection, SourceTimestamp};
 use mz_timely_util::builder_async::{AsyncOutputHandle, PressOnDropButton};
 use mz_timely_util::order::Extrema;
 
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
-use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+use crate::source::{truncation, RawSourceCreationConfig, SourceMessage, SourceReaderError};
 
 mod replication;
 mod schemas;
@@ -150,14 +168,22 @@ impl SourceRender for MySqlSourceConnection {
                 metrics.snapshot_metrics.clone(),
             );
 
-        let (repl_updates, uppers, repl_err, repl_token) = replication::render(
-            scope.clone(),
-            config.clone(),
-            self.clone(),
-            subsource_resume_uppers,
-            table_info,
-            &rewinds,
-            metrics,
+        let (repl_updates, truncates, schema_changes, uppers, repl_err, repl_token) =
+            replication::render(
+                scope.clone(),
+                config.clone(),
+                self.clone(),
+                subsource_resume_uppers,
+                table_info,
+                &rewinds,
+                metrics,
+            );
+
+        let (updates, truncation_token) = truncation::render(
+            &config,
+            &snapshot_updates.concat(&repl_updates),
+            &truncates,
+            self.retract_on_truncate.clone(),
         );
 
         let (stats_stream, stats_err, stats_token) =
@@ -165,7 +191,7 @@ impl SourceRender for MySqlSourceConnection {
 
         let stats_stream = stats_stream.concat(&snapshot_stats);
 
-        let updates = snapshot_updates.concat(&repl_updates).map(|(output, res)| {
+        let updates = updates.map(|(output, res)| {
             let res = res.map(|row| SourceMessage {
                 key: Row::default(),
                 value: row,
@@ -204,15 +230,19 @@ impl SourceRender for MySqlSourceConnection {
                     update,
                 }
             });
-        let health = health_init.concat(&health_errs);
-
-        (
-            updates,
-            Some(uppers),
-            health,
-            stats_stream,
-            vec![snapshot_token, repl_token, stats_token],
-        )
+        // Additive schema changes don't interrupt the ingestion but are reported on the affected
+        // subsource so that users know to refresh its schema.
+        let health_notices = schema_changes.map(|(output_index, hint)| HealthStatusMessage {
+            index: output_index,
+            namespace: StatusNamespace::UpstreamSchema,
+            update: HealthStatusUpdate::notice(hint),
+        });
+        let health = health_init.concat(&health_errs).concat(&health_notices);
+
+        let mut tokens = vec![snapshot_token, repl_token, stats_token];
+        tokens.extend(truncation_token);
+
+        (updates, Some(uppers), health, stats_stream, tokens)
     }
 }
 
@@ -258,6 +288,8 @@ pub enum DefiniteError {
     UnsupportedGtidState(String),
     #[error("received out of order gtids for source {0} at transaction-id {1}")]
     BinlogGtidMonotonicityViolation(String, GtidState),
+    #[error("received a binlog position from the server that violates our requirements: {0}")]
+    UnsupportedBinlogPosition(String),
     #[error("mysql server does not have the binlog available at the requested gtid set")]
     BinlogNotAvailable,
     #[error("mysql server binlog frontier at {0} is beyond required frontier {1}")]
@@ -340,10 +372,30 @@ async fn return_definite_error(
     ()
 }
 
-async fn validate_mysql_repl_settings(conn: &mut mysql_async::Conn) -> Result<(), MySqlError> {
-    ensure_gtid_consistency(conn).await?;
+async fn validate_mysql_repl_settings(
+    conn: &mut mysql_async::Conn,
+    details: &MySqlSourceDetails,
+) -> Result<(), MySqlError> {
+    match (details.flavor, &details.binlog) {
+        (MySqlFlavor::MariaDb, _) => ensure_mariadb_gtid_consistency(conn).await?,
+        // Binlog positions are only meaningful on the server that they were obtained from.
+        (MySqlFlavor::MySql, Some(binlog)) => {
+            let server_uuid = query_sys_var(conn, "global.server_uuid").await?;
+            if !server_uuid.eq_ignore_ascii_case(&binlog.server_uuid.to_string()) {
+                return Err(MySqlError::InvalidSystemSetting {
+                    setting: "server_uuid".to_string(),
+                    expected: binlog.server_uuid.to_string(),
+                    actual: server_uuid,
+                });
+            }
+        }
+        (MySqlFlavor::MySql, None) => ensure_gtid_consistency(conn).await?,
+    }
     ensure_full_row_binlog_format(conn).await?;
-    ensure_replication_commit_order(conn).await?;
+    // MariaDB replicas always commit transactions in the order of their primary.
+    if details.flavor == MySqlFlavor::MySql {
+        ensure_replication_commit_order(conn).await?;
+    }
 
     Ok(())
 }
@@ -23,6 +23,15 @@
 //! This allows us to easily advance the frontier each time we see a new GTID on the replication
 //! stream.
 //!
+//! Sources that replicate from servers without GTIDs use the same representation, with the binlog
+//! position of each transaction packed into the transaction id of a single partition for the
+//! server's `@@server_uuid`. The frontier is only advanced to positions right after the commit of
+//! a transaction, since these are the positions the replication stream can be resumed from.
+//!
+//! Sources that replicate from MariaDB servers track one partition per replication domain and
+//! server id, see [`mz_storage_types::sources::mysql::MariaDbGtid`]. MariaDB announces the GTID of
+//! each transaction with an event type of its own, which is handled like MySQL's GTID events.
+//!
 //! # Resumption
 //!
 //! When the dataflow is resumed, the MySQL replication stream is started from the GTID frontier
@@ -66,7 +75,9 @@ use mz_mysql_util::{
 use mz_ore::cast::CastFrom;
 use mz_ore::result::ResultExt;
 use mz_repr::{Diff, GlobalId, Row};
-use mz_storage_types::sources::mysql::{gtid_set_frontier, GtidPartition, GtidState};
+use mz_storage_types::sources::mysql::{
+    gtid_set_frontier, GtidPartition, GtidState, MariaDbGtid, MySqlFlavor, MySqlSourceDetails,
+};
 use mz_storage_types::sources::MySqlSourceConnection;
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
@@ -95,6 +106,15 @@ static REPL_READER: &str = "reader";
 /// TODO(roshan): Add user-facing documentation for this
 static REPLICATION_SERVER_ID_OFFSET: u32 = 524000;
 
+/// The binlog event types that only exist on MariaDB servers start at this value.
+const MARIADB_ANNOTATE_ROWS_EVENT: u8 = 160;
+/// The MariaDB binlog event that announces the GTID of the subsequent transaction.
+const MARIADB_GTID_EVENT: u8 = 162;
+/// The range of MariaDB binlog event types that hold compressed query and rows events.
+const MARIADB_COMPRESSED_EVENTS: std::ops::RangeInclusive<u8> = 165..=171;
+/// The replication capability that lets MariaDB servers send GTID events to the replica.
+const MARIADB_SLAVE_CAPABILITY_GTID: u8 = 4;
+
 /// Renders the replication dataflow. See the module documentation for more
 /// information.
 pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
@@ -107,6 +127,8 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
     metrics: MySqlSourceMetrics,
 ) -> (
     Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, (usize, GtidPartition)>,
+    Stream<G, (usize, String)>,
     Stream<G, Infallible>,
     Stream<G, ReplicationError>,
     PressOnDropButton,
@@ -119,6 +141,10 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
     let (upper_output, upper_stream) = builder.new_output();
     // Captures DefiniteErrors that affect the entire source, including all subsources
     let (mut definite_error_handle, definite_errors) = builder.new_output();
+    // Markers for the truncation of tables that are retracted on truncation.
+    let (mut truncate_output, truncate_stream) = builder.new_output();
+    // Descriptions of the additive schema changes of upstream tables.
+    let (mut schema_change_output, schema_change_stream) = builder.new_output();
     let mut rewind_input = builder.new_input_for_many(
         rewind_stream,
         Exchange::new(move |_| repl_reader_id),
@@ -135,7 +161,7 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
     let (button, transient_errors) = builder.build_fallible(move |caps| {
         Box::pin(async move {
             let (id, worker_id) = (config.id, config.worker_id);
-            let [data_cap_set, upper_cap_set, definite_error_cap_set]: &mut [_; 3] =
+            let [data_cap_set, upper_cap_set, definite_error_cap_set, truncate_cap_set, schema_change_cap_set]: &mut [_; 5] =
                 caps.try_into().unwrap();
 
             // Only run the replication reader on the worker responsible for it.
@@ -161,8 +187,15 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
 
             // Get the set of GTIDs that have been purged from the binlogs. The assumption is that this
             // represents the frontier of possible GTIDs that exist in the binlog, that we can start
-            // replicating from.
-            let binlog_purged_set = query_sys_var(&mut conn, "global.gtid_purged").await?;
+            // replicating from. Servers without GTIDs have no equivalent, so for those a purged
+            // resumption point is only detected when requesting the replication stream.
+            // MariaDB servers don't expose the GTIDs purged from their binlogs either.
+            let binlog_purged_set = match (connection.details.flavor, &connection.details.binlog) {
+                (MySqlFlavor::MySql, None) => {
+                    query_sys_var(&mut conn, "global.gtid_purged").await?
+                }
+                _ => String::new(),
+            };
             let binlog_frontier = match gtid_set_frontier(&binlog_purged_set) {
                 Ok(frontier) => frontier,
                 Err(err) => {
@@ -206,7 +239,13 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                         // resume the replication stream from this consistent point all updates
                         // will be rewound appropriately, such that the effective snapshot point
                         // is consistent across all tables.
-                        gtid_set_frontier(&connection.details.initial_gtid_set)?
+                        match &connection.details.binlog {
+                            Some(binlog) => binlog.initial_frontier()?,
+                            None => connection
+                                .details
+                                .flavor
+                                .gtid_set_frontier(&connection.details.initial_gtid_set)?,
+                        }
                     }
                     upper => upper,
                 };
@@ -230,6 +269,8 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
 
             data_cap_set.downgrade(&*resume_upper);
             upper_cap_set.downgrade(&*resume_upper);
+            truncate_cap_set.downgrade(&*resume_upper);
+            schema_change_cap_set.downgrade(&*resume_upper);
             trace!(%id, "timely-{worker_id} replication reader started at {:?}", resume_upper);
 
             let mut rewinds = BTreeMap::new();
@@ -264,7 +305,7 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
             // We don't use _conn_tunnel_handle here, but need to keep it around to ensure that the
             // SSH tunnel is not dropped until the replication stream is dropped.
             let (binlog_stream, _conn_tunnel_handle) =
-                match raw_stream(&config, conn, &resume_upper).await? {
+                match raw_stream(&config, conn, &connection.details, &resume_upper).await? {
                     Ok(stream) => stream,
                     // If the replication stream cannot be obtained in a definite way there is
                     // nothing else to do. These errors are not retractable.
@@ -297,18 +338,59 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                 &metrics,
                 &connection.text_columns,
                 &connection.ignore_columns,
+                &connection.retract_on_truncate,
                 &mut data_output,
                 data_cap_set,
                 upper_cap_set,
+                &mut truncate_output,
+                truncate_cap_set,
+                &mut schema_change_output,
+                schema_change_cap_set,
                 rewinds,
             );
 
             let mut active_tx: Option<(Uuid, NonZeroU64)> = None;
+            // The binlog coordinates of sources that replicate from servers without GTIDs, and
+            // the binlog file that the current event belongs to.
+            let binlog = connection.details.binlog.as_ref();
+            let mut binlog_file: Option<String> = None;
 
             while let Some(event) = repl_context.stream.next().await {
                 use mysql_async::binlog::events::*;
                 let event = event?;
-                let event_data = event.read_data()?;
+                // The position right after this event in `binlog_file`.
+                let event_end = u64::from(event.header().log_pos());
+                let event_size = u64::from(event.header().event_size());
+                // MariaDB specific events are not understood by the replication client, so we
+                // interpret the ones we care about ourselves.
+                let (gtid, event_data) = if connection.details.flavor == MySqlFlavor::MariaDb
+                    && event.header().event_type().0 >= MARIADB_ANNOTATE_ROWS_EVENT
+                {
+                    match mariadb_gtid(&event) {
+                        Ok(gtid) => (gtid, None),
+                        Err(err) => {
+                            return Ok(return_definite_error(
+                                err,
+                                &output_indexes,
+                                &mut data_output,
+                                data_cap_set,
+                                &mut definite_error_handle,
+                                definite_error_cap_set,
+                            )
+                            .await);
+                        }
+                    }
+                } else {
+                    let event_data = event.read_data()?;
+                    let gtid = match &event_data {
+                        Some(EventData::GtidEvent(event)) => Some((
+                            Uuid::from_bytes(event.sid()),
+                            NonZeroU64::new(event.gno()).unwrap(),
+                        )),
+                        _ => None,
+                    };
+                    (gtid, event_data)
+                };
                 metrics.total.inc();
 
                 match event_data {
@@ -317,12 +399,15 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                         // all events for the current GTID and we can advance the frontier beyond.
                         let (source_id, tx_id) = active_tx.take().expect("unexpected xid event");
 
-                        // Increment the transaction-id to the next GTID we should see from this source-id
-                        let next_tx_id = tx_id.checked_add(1).unwrap();
-                        let next_gtid =
-                            GtidPartition::new_singleton(source_id, GtidState::Active(next_tx_id));
-
-                        if let Err(err) = data_partitions.advance_frontier(next_gtid) {
+                        let advanced = next_timestamp(
+                            &connection.details,
+                            binlog_file.as_deref(),
+                            source_id,
+                            tx_id,
+                            event_end,
+                        )
+                        .and_then(|next_gtid| data_partitions.advance_frontier(next_gtid));
+                        if let Err(err) = advanced {
                             return Ok(return_definite_error(
                                 err,
                                 &output_indexes,
@@ -336,10 +421,10 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                         let new_upper = data_partitions.frontier();
                         repl_context.downgrade_data_cap_set("xid_event", new_upper);
                     }
-                    // We receive a GtidEvent that tells us the GTID of the incoming RowsEvents (and other events)
-                    Some(EventData::GtidEvent(event)) => {
-                        let source_id = Uuid::from_bytes(event.sid());
-                        let tx_id = NonZeroU64::new(event.gno()).unwrap();
+                    // We receive a GtidEvent, or MariaDB's equivalent, that tells us the GTID of the
+                    // incoming RowsEvents (and other events)
+                    _ if gtid.is_some() => {
+                        let (source_id, tx_id) = gtid.expect("known to be Some");
 
                         // We are potentially about to ingest a big transaction that we don't want
                         // to store in memory. For this reason we are immediately downgrading our
@@ -367,6 +452,37 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                         // Store the information of the active transaction for the subsequent events
                         active_tx = Some((source_id, tx_id));
                     }
+                    // Servers without GTIDs precede every transaction with an anonymous GTID
+                    // event, whose position we use as the timestamp of the transaction. Unlike
+                    // above we can't advance the progress frontier until the transaction commits,
+                    // since every frontier must be a position that replication can resume from.
+                    Some(EventData::AnonymousGtidEvent(_)) if binlog.is_some() => {
+                        let binlog = binlog.expect("known to be Some");
+                        let file = binlog_file
+                            .as_deref()
+                            .expect("binlog stream starts with a rotate event");
+                        let event_start = event_end.saturating_sub(event_size);
+                        match binlog.packed_position(file, event_start) {
+                            Ok(tx_id) => active_tx = Some((binlog.server_uuid, tx_id)),
+                            Err(err) => {
+                                let err = DefiniteError::UnsupportedBinlogPosition(err.to_string());
+                                return Ok(return_definite_error(
+                                    err,
+                                    &output_indexes,
+                                    &mut data_output,
+                                    data_cap_set,
+                                    &mut definite_error_handle,
+                                    definite_error_cap_set,
+                                )
+                                .await);
+                            }
+                        }
+                    }
+                    // Rotate events announce the binlog file of the subsequent events, including
+                    // a fake one at the start of the stream.
+                    Some(EventData::RotateEvent(event)) => {
+                        binlog_file = Some(event.name().into_owned());
+                    }
                     Some(EventData::RowsEvent(data)) => {
                         let (source_id, tx_id) = active_tx
                             .clone()
@@ -405,14 +521,15 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
 
                         if should_advance {
                             active_tx = None;
-                            // Increment the transaction-id to the next GTID we should see from this source-id
-                            let next_tx_id = tx_id.checked_add(1).unwrap();
-                            let next_gtid = GtidPartition::new_singleton(
+                            let advanced = next_timestamp(
+                                &connection.details,
+                                binlog_file.as_deref(),
                                 source_id,
-                                GtidState::Active(next_tx_id),
-                            );
-
-                            if let Err(err) = data_partitions.advance_frontier(next_gtid) {
+                                tx_id,
+                                event_end,
+                            )
+                            .and_then(|next_gtid| data_partitions.advance_frontier(next_gtid));
+                            if let Err(err) = advanced {
                                 return Ok(return_definite_error(
                                     err,
                                     &output_indexes,
@@ -447,21 +564,97 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
 
     (
         replication_updates,
+        truncate_stream,
+        schema_change_stream,
         upper_stream,
         errors,
         button.press_on_drop(),
     )
 }
 
+/// Returns the timestamp right after the transaction `tx_id` of `source_id`, which was completed
+/// by the binlog event that ends at `event_end` in `binlog_file`.
+///
+/// For servers with GTIDs this is the next transaction id of the same source. For servers without
+/// GTIDs this is the position right after the event, which is where replication has to resume
+/// from to not see the transaction again.
+fn next_timestamp(
+    details: &MySqlSourceDetails,
+    binlog_file: Option<&str>,
+    source_id: Uuid,
+    tx_id: NonZeroU64,
+    event_end: u64,
+) -> Result<GtidPartition, DefiniteError> {
+    match &details.binlog {
+        Some(binlog) => {
+            let file = binlog_file.expect("binlog stream starts with a rotate event");
+            binlog
+                .timestamp(file, event_end)
+                .map_err(|err| DefiniteError::UnsupportedBinlogPosition(err.to_string()))
+        }
+        None => {
+            // Increment the transaction-id to the next GTID we should see from this source-id
+            let next_tx_id = tx_id.checked_add(1).unwrap();
+            Ok(GtidPartition::new_singleton(
+                source_id,
+                GtidState::Active(next_tx_id),
+            ))
+        }
+    }
+}
+
+/// Returns the GTID announced by `event`, a MariaDB specific binlog event, or `None` if it does not
+/// announce a GTID.
+fn mariadb_gtid(
+    event: &mysql_async::binlog::events::Event,
+) -> Result<Option<(Uuid, NonZeroU64)>, DefiniteError> {
+    let event_type = event.header().event_type().0;
+    if MARIADB_COMPRESSED_EVENTS.contains(&event_type) {
+        return Err(DefiniteError::ServerConfigurationError(
+            "compressed binlog events are not supported".to_string(),
+        ));
+    }
+    if event_type != MARIADB_GTID_EVENT {
+        return Ok(None);
+    }
+
+    // The event starts with the sequence number and the replication domain of the GTID, while
+    // the server id is taken from the event header.
+    let data = event.data();
+    let gtid = match (data.get(0..8), data.get(8..12)) {
+        (Some(seq_no), Some(domain_id)) => MariaDbGtid {
+            domain_id: u32::from_le_bytes(domain_id.try_into().expect("known length")),
+            server_id: event.header().server_id(),
+            seq_no: u64::from_le_bytes(seq_no.try_into().expect("known length")),
+        },
+        _ => {
+            return Err(DefiniteError::UnsupportedGtidState(format!(
+                "truncated mariadb gtid event: {:?}",
+                data
+            )))
+        }
+    };
+    match NonZeroU64::new(gtid.seq_no) {
+        Some(seq_no) => Ok(Some((gtid.partition(), seq_no))),
+        None => Err(DefiniteError::UnsupportedGtidState(format!(
+            "invalid mariadb gtid: {}",
+            gtid
+        ))),
+    }
+}
+
 /// Produces the replication stream from the MySQL server. This will return all transactions
-/// whose GTIDs were not present in the GTID UUIDs referenced in the `resume_uppper` partitions.
+/// whose GTIDs were not present in the GTID UUIDs referenced in the `resume_uppper` partitions,
+/// or, for servers without GTIDs, all transactions at or after the binlog position of the
+/// server's partition.
 async fn raw_stream<'a>(
     config: &RawSourceCreationConfig,
     mut conn: MySqlConn,
+    details: &MySqlSourceDetails,
     resume_upper: &Antichain<GtidPartition>,
 ) -> Result<Result<(BinlogStream, Option<ManagedSshTunnelHandle>), DefiniteError>, TransientError> {
-    // Verify the MySQL system settings are correct for consistent row-based replication using GTIDs
-    match validate_mysql_repl_settings(&mut conn).await {
+    // Verify the MySQL system settings are correct for consistent row-based replication
+    match validate_mysql_repl_settings(&mut conn, details).await {
         Err(err @ MySqlError::InvalidSystemSetting { .. }) => {
             return Ok(Err(DefiniteError::ServerConfigurationError(
                 err.to_string(),
@@ -524,24 +717,97 @@ async fn raw_stream<'a>(
         _ => REPLICATION_SERVER_ID_OFFSET,
     };
 
-    trace!(
-        "requesting replication stream with seen_gtids: {seen_gtids:?} \
-         and server_id: {server_id:?}"
-    );
+    let request = match (details.flavor, &details.binlog) {
+        (MySqlFlavor::MariaDb, _) => {
+            // MariaDB servers are asked for the transactions after the last GTID we have seen in
+            // each replication domain. Sequence numbers are increasing across all servers of a
+            // domain, so the partition with the highest one is the latest position in the domain.
+            let mut connect_state: BTreeMap<u32, MariaDbGtid> = BTreeMap::new();
+            for partition in resume_upper.iter() {
+                let (uuid, next_seq_no) =
+                    match (partition.interval().singleton(), partition.timestamp()) {
+                        (Some(uuid), GtidState::Active(next_seq_no)) => (uuid, next_seq_no),
+                        _ => continue,
+                    };
+                let gtid = match MariaDbGtid::from_partition(uuid, next_seq_no.get() - 1) {
+                    Some(gtid) if gtid.seq_no > 0 => gtid,
+                    Some(_) => continue,
+                    None => {
+                        return Ok(Err(DefiniteError::UnsupportedGtidState(format!(
+                            "not a mariadb gtid partition: {}",
+                            uuid
+                        ))))
+                    }
+                };
+                let latest = connect_state.entry(gtid.domain_id).or_insert(gtid);
+                if latest.seq_no < gtid.seq_no {
+                    *latest = gtid;
+                }
+            }
+            let connect_state = connect_state.values().join(",");
+            conn.query_drop(format!(
+                "SET @mariadb_slave_capability = {MARIADB_SLAVE_CAPABILITY_GTID}"
+            ))
+            .await?;
+            conn.query_drop(format!("SET @slave_connect_state = '{connect_state}'"))
+                .await?;
+            conn.query_drop("SET @slave_gtid_strict_mode = 1").await?;
+            trace!(
+                "requesting replication stream with connect_state: {connect_state} \
+                 and server_id: {server_id:?}"
+            );
+            // The binlog file and position of the request are ignored in favor of the connect
+            // state.
+            BinlogStreamRequest::new(server_id)
+        }
+        (MySqlFlavor::MySql, Some(binlog)) => {
+            let position = resume_upper.iter().find_map(|partition| {
+                match (partition.interval().singleton(), partition.timestamp()) {
+                    (Some(uuid), GtidState::Active(position)) if uuid == &binlog.server_uuid => {
+                        Some(*position)
+                    }
+                    _ => None,
+                }
+            });
+            let (file, position) = match position.map(|position| binlog.file_position(position)) {
+                Some(Ok(file_position)) => file_position,
+                Some(Err(err)) => {
+                    return Ok(Err(DefiniteError::UnsupportedBinlogPosition(
+                        err.to_string(),
+                    )))
+                }
+                None => {
+                    return Ok(Err(DefiniteError::UnsupportedBinlogPosition(format!(
+                        "no binlog position for server {} in {:?}",
+                        binlog.server_uuid, resume_upper
+                    ))))
+                }
+            };
+            trace!(
+                "requesting replication stream at binlog position {file}:{position} \
+                 and server_id: {server_id:?}"
+            );
+            BinlogStreamRequest::new(server_id)
+                .with_filename(file.into_bytes())
+                .with_pos(position)
+        }
+        (MySqlFlavor::MySql, None) => {
+            trace!(
+                "requesting replication stream with seen_gtids: {seen_gtids:?} \
+                 and server_id: {server_id:?}"
+            );
+            BinlogStreamRequest::new(server_id)
+                .with_gtid()
+                .with_gtid_set(seen_gtids)
+        }
+    };
 
     // We need to transform the connection into a BinlogStream (which takes the `Conn` by value),
     // but to avoid dropping any active SSH tunnel used by the connection we need to preserve the
     // tunnel handle and return it
     let (inner_conn, conn_tunnel_handle) = conn.take();
 
-    let repl_stream = match inner_conn
-        .get_binlog_stream(
-            BinlogStreamRequest::new(server_id)
-                .with_gtid()
-                .with_gtid_set(seen_gtids),
-        )
-        .await
-    {
+    let repl_stream = match inner_conn.get_binlog_stream(request).await {
         Ok(stream) => stream,
         Err(mysql_async::Error::Server(ref server_err))
             if server_err.code == ER_SOURCE_FATAL_ERROR_READING_BINLOG_CODE =>
@@ -35,6 +35,7 @@ pub(super) struct ReplContext<'a> {
     pub(super) metrics: &'a MySqlSourceMetrics,
     pub(super) text_columns: &'a Vec<MySqlColumnRef>,
     pub(super) ignore_columns: &'a Vec<MySqlColumnRef>,
+    pub(super) retract_on_truncate: &'a BTreeSet<usize>,
     pub(super) data_output: &'a mut AsyncOutputHandle<
         GtidPartition,
         Vec<((usize, Result<Row, DefiniteError>), GtidPartition, i64)>,
@@ -42,6 +43,18 @@ pub(super) struct ReplContext<'a> {
     >,
     pub(super) data_cap_set: &'a mut CapabilitySet<GtidPartition>,
     pub(super) upper_cap_set: &'a mut CapabilitySet<GtidPartition>,
+    pub(super) truncate_output: &'a mut AsyncOutputHandle<
+        GtidPartition,
+        Vec<(usize, GtidPartition)>,
+        Tee<GtidPartition, Vec<(usize, GtidPartition)>>,
+    >,
+    pub(super) truncate_cap_set: &'a mut CapabilitySet<GtidPartition>,
+    pub(super) schema_change_output: &'a mut AsyncOutputHandle<
+        GtidPartition,
+        Vec<(usize, String)>,
+        Tee<GtidPartition, Vec<(usize, String)>>,
+    >,
+    pub(super) schema_change_cap_set: &'a mut CapabilitySet<GtidPartition>,
     // Owned values:
     pub(super) rewinds: BTreeMap<MySqlTableName, ([Capability<GtidPartition>; 2], RewindRequest)>,
     pub(super) errored_tables: BTreeSet<MySqlTableName>,
@@ -56,6 +69,7 @@ impl<'a> ReplContext<'a> {
         metrics: &'a MySqlSourceMetrics,
         text_columns: &'a Vec<MySqlColumnRef>,
         ignore_columns: &'a Vec<MySqlColumnRef>,
+        retract_on_truncate: &'a BTreeSet<usize>,
         data_output: &'a mut AsyncOutputHandle<
             GtidPartition,
             Vec<((usize, Result<Row, DefiniteError>), GtidPartition, i64)>,
@@ -63,6 +77,18 @@ impl<'a> ReplContext<'a> {
         >,
         data_cap_set: &'a mut CapabilitySet<GtidPartition>,
         upper_cap_set: &'a mut CapabilitySet<GtidPartition>,
+        truncate_output: &'a mut AsyncOutputHandle<
+            GtidPartition,
+            Vec<(usize, GtidPartition)>,
+            Tee<GtidPartition, Vec<(usize, GtidPartition)>>,
+        >,
+        truncate_cap_set: &'a mut CapabilitySet<GtidPartition>,
+        schema_change_output: &'a mut AsyncOutputHandle<
+            GtidPartition,
+            Vec<(usize, String)>,
+            Tee<GtidPartition, Vec<(usize, String)>>,
+        >,
+        schema_change_cap_set: &'a mut CapabilitySet<GtidPartition>,
         rewinds: BTreeMap<MySqlTableName, ([Capability<GtidPartition>; 2], RewindRequest)>,
     ) -> Self {
         Self {
@@ -73,9 +99,14 @@ impl<'a> ReplContext<'a> {
             metrics,
             text_columns,
             ignore_columns,
+            retract_on_truncate,
             data_output,
             data_cap_set,
             upper_cap_set,
+            truncate_output,
+            truncate_cap_set,
+            schema_change_output,
+            schema_change_cap_set,
             rewinds,
             errored_tables: BTreeSet::new(),
         }
@@ -93,6 +124,8 @@ impl<'a> ReplContext<'a> {
         trace!(%id, "timely-{worker_id} [{reason}] advancing data frontier to {new_upper:?}");
 
         self.data_cap_set.downgrade(&*new_upper);
+        self.truncate_cap_set.downgrade(&*new_upper);
+        self.schema_change_cap_set.downgrade(&*new_upper);
 
         self.metrics.gtid_txids.set(
             new_upper
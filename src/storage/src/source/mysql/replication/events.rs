@@ -16,7 +16,7 @@ use mz_mysql_util::{pack_mysql_row, MySqlError};
 use mz_repr::Row;
 use mz_storage_types::sources::mysql::GtidPartition;
 
-use super::super::schemas::verify_schemas;
+use super::super::schemas::{verify_schema_changes, verify_schemas};
 use super::super::{DefiniteError, MySqlTableName, TransientError};
 use super::context::ReplContext;
 
@@ -87,7 +87,7 @@ pub(super) async fn handle_query_event(
                         &ctx.config.config.connection_context.ssh_tunnel_manager,
                     )
                     .await?;
-                if let Some((err_table, err)) = verify_schemas(
+                let (verified_table, result) = verify_schema_changes(
                     &mut *conn,
                     &[(&table, table_desc)],
                     ctx.text_columns,
@@ -96,15 +96,37 @@ pub(super) async fn handle_query_event(
                 .await?
                 .into_iter()
                 .next()
-                {
-                    assert_eq!(err_table, &table, "Unexpected table verification error");
-                    trace!(%id, "timely-{worker_id} DDL change \
-                           verification error for {table:?}: {err:?}");
-                    let gtid_cap = ctx.data_cap_set.delayed(new_gtid);
-                    ctx.data_output
-                        .give(&gtid_cap, ((*output_index, Err(err)), new_gtid.clone(), 1))
-                        .await;
-                    ctx.errored_tables.insert(table.clone());
+                .expect("one result per table");
+                assert_eq!(
+                    verified_table, &table,
+                    "Unexpected table verification result"
+                );
+                match result {
+                    Ok(changes) if changes.is_empty() => {}
+                    // The table evolved in a way that we can continue to ingest, but the schema
+                    // of the subsource needs to be refreshed for the changes to become visible.
+                    Ok(changes) => {
+                        trace!(%id, "timely-{worker_id} DDL change \
+                               additive changes for {table:?}: {changes:?}");
+                        let hint = format!(
+                            "upstream table {table} has changed: {}; use ALTER SOURCE ... \
+                             REFRESH SCHEMA to ingest the changes",
+                            changes.join(", ")
+                        );
+                        let schema_change_cap = ctx.schema_change_cap_set.delayed(new_gtid);
+                        ctx.schema_change_output
+                            .give(&schema_change_cap, (*output_index, hint))
+                            .await;
+                    }
+                    Err(err) => {
+                        trace!(%id, "timely-{worker_id} DDL change \
+                               verification error for {table:?}: {err:?}");
+                        let gtid_cap = ctx.data_cap_set.delayed(new_gtid);
+                        ctx.data_output
+                            .give(&gtid_cap, ((*output_index, Err(err)), new_gtid.clone(), 1))
+                            .await;
+                        ctx.errored_tables.insert(table.clone());
+                    }
                 }
             }
         }
@@ -161,22 +183,32 @@ pub(super) async fn handle_query_event(
             if ctx.table_info.contains_key(&table) {
                 trace!(%id, "timely-{worker_id} TRUNCATE detected \
                        for {table:?}");
-                if let Some((output_index, _)) = ctx.table_info.get(&table) {
-                    let gtid_cap = ctx.data_cap_set.delayed(new_gtid);
-                    ctx.data_output
-                        .give(
-                            &gtid_cap,
-                            (
+                match ctx.table_info.get(&table) {
+                    // The contents of the table are retracted by the truncation operator.
+                    Some((output_index, _)) if ctx.retract_on_truncate.contains(output_index) => {
+                        let truncate_cap = ctx.truncate_cap_set.delayed(new_gtid);
+                        ctx.truncate_output
+                            .give(&truncate_cap, (*output_index, new_gtid.clone()))
+                            .await;
+                    }
+                    Some((output_index, _)) => {
+                        let gtid_cap = ctx.data_cap_set.delayed(new_gtid);
+                        ctx.data_output
+                            .give(
+                                &gtid_cap,
                                 (
-                                    *output_index,
-                                    Err(DefiniteError::TableTruncated(table.to_string())),
+                                    (
+                                        *output_index,
+                                        Err(DefiniteError::TableTruncated(table.to_string())),
+                                    ),
+                                    new_gtid.clone(),
+                                    1,
                                 ),
-                                new_gtid.clone(),
-                                1,
-                            ),
-                        )
-                        .await;
-                    ctx.errored_tables.insert(table);
+                            )
+                            .await;
+                        ctx.errored_tables.insert(table);
+                    }
+                    None => {}
                 }
             }
         }
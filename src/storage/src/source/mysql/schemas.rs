@@ -25,6 +25,27 @@ pub(super) async fn verify_schemas<'a, Q>(
     text_columns: &Vec<MySqlColumnRef>,
     ignore_columns: &Vec<MySqlColumnRef>,
 ) -> Result<Vec<(&'a MySqlTableName, DefiniteError)>, MySqlError>
+where
+    Q: Queryable,
+{
+    Ok(
+        verify_schema_changes(conn, expected, text_columns, ignore_columns)
+            .await?
+            .into_iter()
+            .filter_map(|(table, result)| result.err().map(|err| (table, err)))
+            .collect(),
+    )
+}
+
+/// Like [`verify_schemas`], but returns the result of the verification for every table. The
+/// result of a table with a compatible schema describes the additive changes that were made to
+/// it, see [`MySqlTableDesc::additive_changes`].
+pub(super) async fn verify_schema_changes<'a, Q>(
+    conn: &mut Q,
+    expected: &[(&'a MySqlTableName, &MySqlTableDesc)],
+    text_columns: &Vec<MySqlColumnRef>,
+    ignore_columns: &Vec<MySqlColumnRef>,
+) -> Result<Vec<(&'a MySqlTableName, Result<Vec<String>, DefiniteError>)>, MySqlError>
 where
     Q: Queryable,
 {
@@ -55,12 +76,10 @@ where
 
     Ok(expected
         .into_iter()
-        .filter_map(|(table, desc)| {
-            if let Err(err) = verify_schema(table, desc, &cur_schemas) {
-                Some((*table, err))
-            } else {
-                None
-            }
+        .map(|(table, desc)| {
+            let result = verify_schema(table, desc, &cur_schemas)
+                .map(|()| desc.additive_changes(&cur_schemas[*table]));
+            (*table, result)
         })
         .collect())
 }
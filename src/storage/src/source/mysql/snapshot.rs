@@ -19,7 +19,8 @@
 //! point in time. This must be true even in the presence of restarts or partially committed
 //! snapshots. The consistent point that the snapshot must happen at is discovered and durably
 //! recorded during planning of the source and is exposed to this ingestion dataflow via the
-//! `initial_gtid_set` field in `MySqlSourceDetails`.
+//! `initial_gtid_set` field in `MySqlSourceDetails`, or its `binlog` field for servers without
+//! GTIDs.
 //!
 //! Unfortunately MySQL does not provide an API to perform a transaction at a specific point in
 //! time. Instead, MySQL allows us to perform a snapshot of a table and let us know at which point
@@ -37,9 +38,9 @@
 //! worker initiates a connection to the server and acquires a table lock on all the tables that
 //! have been assigned to it. By doing so we establish a moment in time where we know no writes are
 //! happening to the tables we are interested in. After the locks are taken each worker reads the
-//! current upper frontier (`snapshot_upper`) using the `@@gtid_executed` system variable. This
-//! frontier establishes an upper bound on any possible write to the tables of interest until the
-//! lock is released.
+//! current upper frontier (`snapshot_upper`) using the `@@gtid_executed` system variable
+//! (`@@gtid_binlog_state` on MariaDB), or the current binlog position for servers without GTIDs. This frontier establishes an upper bound on
+//! any possible write to the tables of interest until the lock is released.
 //!
 //! Each worker now starts a transaction via a new connection with 'REPEATABLE READ' and
 //! 'CONSISTENT SNAPSHOT' semantics. Due to linearizability we know that this transaction's view of
@@ -97,12 +98,15 @@ use timely::dataflow::{Scope, Stream};
 use timely::progress::{Antichain, Timestamp};
 use tracing::{error, trace};
 
-use mz_mysql_util::{pack_mysql_row, query_sys_var, MySqlError, MySqlTableDesc, ER_NO_SUCH_TABLE};
+use mz_mysql_util::{
+    pack_mysql_row, query_binlog_position, query_sys_var, MySqlError, MySqlTableDesc,
+    ER_NO_SUCH_TABLE,
+};
 use mz_ore::future::InTask;
 use mz_ore::metrics::MetricsFutureExt;
 use mz_ore::result::ResultExt;
 use mz_repr::{Diff, GlobalId, Row};
-use mz_storage_types::sources::mysql::{gtid_set_frontier, GtidPartition};
+use mz_storage_types::sources::mysql::GtidPartition;
 use mz_storage_types::sources::MySqlSourceConnection;
 use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
 
@@ -273,13 +277,26 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                 };
 
                 // Record the frontier of future GTIDs based on the executed GTID set at the start
-                // of the snapshot
-                let snapshot_gtid_set =
-                    query_sys_var(&mut lock_conn, "global.gtid_executed").await?;
-                let snapshot_gtid_frontier = match gtid_set_frontier(&snapshot_gtid_set) {
+                // of the snapshot, or of future binlog events if the server doesn't use GTIDs
+                let snapshot_gtid_frontier = match &connection.details.binlog {
+                    Some(binlog) => {
+                        let (file, position) = query_binlog_position(&mut lock_conn).await?;
+                        binlog.frontier(&file, position).map_err(|err| {
+                            DefiniteError::UnsupportedBinlogPosition(err.to_string())
+                        })
+                    }
+                    None => {
+                        let flavor = connection.details.flavor;
+                        let snapshot_gtid_set =
+                            query_sys_var(&mut lock_conn, flavor.gtid_set_variable()).await?;
+                        flavor
+                            .gtid_set_frontier(&snapshot_gtid_set)
+                            .map_err(|err| DefiniteError::UnsupportedGtidState(err.to_string()))
+                    }
+                };
+                let snapshot_gtid_frontier = match snapshot_gtid_frontier {
                     Ok(frontier) => frontier,
                     Err(err) => {
-                        let err = DefiniteError::UnsupportedGtidState(err.to_string());
                         // If we received a GTID Set with non-consecutive intervals this breaks all
                         // our assumptions, so there is nothing else we can do.
                         return Ok(return_definite_error(
@@ -305,8 +322,8 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                     )
                     .await?;
 
-                // Verify the MySQL system settings are correct for consistent row-based replication using GTIDs
-                match validate_mysql_repl_settings(&mut conn).await {
+                // Verify the MySQL system settings are correct for consistent row-based replication
+                match validate_mysql_repl_settings(&mut conn, &connection.details).await {
                     Err(err @ MySqlError::InvalidSystemSetting { .. }) => {
                         return Ok(return_definite_error(
                             DefiniteError::ServerConfigurationError(err.to_string()),
@@ -14,9 +14,9 @@ use timely::dataflow::operators::Map;
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 
-use mz_mysql_util::query_sys_var;
+use mz_mysql_util::{query_binlog_position, query_sys_var};
 use mz_ore::future::InTask;
-use mz_storage_types::sources::mysql::{gtid_set_frontier, GtidPartition, GtidState};
+use mz_storage_types::sources::mysql::{GtidPartition, GtidState};
 use mz_storage_types::sources::MySqlSourceConnection;
 use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
 
@@ -92,9 +92,19 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
                 loop {
                     interval.tick().await;
 
-                    let gtid_executed =
-                        query_sys_var(&mut stats_conn, "global.gtid_executed").await?;
-                    match gtid_set_frontier(&gtid_executed) {
+                    let upstream_frontier = match &connection.details.binlog {
+                        Some(binlog) => {
+                            let (file, position) = query_binlog_position(&mut stats_conn).await?;
+                            binlog.frontier(&file, position)
+                        }
+                        None => {
+                            let flavor = connection.details.flavor;
+                            let gtid_executed =
+                                query_sys_var(&mut stats_conn, flavor.gtid_set_variable()).await?;
+                            flavor.gtid_set_frontier(&gtid_executed)
+                        }
+                    };
+                    match upstream_frontier {
                         Ok(frontier) => yield Ok(frontier),
                         Err(err) => {
                             // We don't translate this into a definite error like in snapshotting, but we will
@@ -145,7 +155,9 @@ pub(crate) fn render<G: Scope<Timestamp = GtidPartition>>(
 }
 
 /// Aggregate a mysql frontier into single number representing the
-/// _number of transactions_ it represents.
+/// _number of transactions_ it represents. For sources that track binlog
+/// positions this is the packed binlog position instead, which still grows
+/// as the source makes progress.
 fn aggregate_mysql_frontier(frontier: Antichain<GtidPartition>) -> u64 {
     let mut progress_stat = 0;
     for ts in frontier.iter() {
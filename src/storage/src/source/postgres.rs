@@ -13,8 +13,10 @@
 //! # Snapshot
 //!
 //! One part of the dataflow deals with snapshotting the tables involved in the ingestion. Each
-//! table that needs a snapshot is assigned to a specific worker which performs a `COPY` query
-//! and distributes the raw COPY bytes to all workers to decode the text encoded rows.
+//! table that needs a snapshot is split into chunks that are assigned to all workers, which
+//! perform `COPY` queries and distribute the raw COPY bytes to all workers to decode the text
+//! encoded rows. Failed chunks are retried, but a snapshot that is interrupted by a restart is
+//! started over.
 //!
 //! For all tables that ended up being snapshotted the snapshot reader also emits a rewind request
 //! to the replication reader which will ensure that the requested portion of the replication
@@ -30,6 +32,14 @@
 //!
 //! See the [replication] module for more information on the replication strategy.
 //!
+//! # Upsert tables
+//!
+//! The snapshot and replication changes of tables listed in `UPSERT TABLES` are applied by
+//! primary key in a separate operator, which produces the retractions that the replication stream
+//! of such tables doesn't carry.
+//!
+//! See the [upsert] module for more information.
+//!
 //! # Error handling
 //!
 //! There are two kinds of errors that can happen during ingestion that are represented as two
@@ -84,9 +94,9 @@ use std::convert::Infallible;
 use std::rc::Rc;
 use std::time::Duration;
 
-use differential_dataflow::Collection;
+use differential_dataflow::{AsCollection, Collection};
 use itertools::Itertools as _;
-use mz_expr::{EvalError, MirScalarExpr};
+use mz_expr::{EvalError, MirScalarExpr, UnaryFunc};
 use mz_ore::error::ErrorExt;
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::{simple_query_opt, PostgresError};
@@ -99,7 +109,7 @@ use mz_storage_types::sources::{
 };
 use mz_timely_util::builder_async::PressOnDropButton;
 use serde::{Deserialize, Serialize};
-use timely::dataflow::operators::{Concat, Map, ToStream};
+use timely::dataflow::operators::{Concat, Map, OkErr, ToStream};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 use tokio_postgres::error::SqlState;
@@ -107,11 +117,13 @@ use tokio_postgres::types::PgLsn;
 use tokio_postgres::Client;
 
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::postgres::upsert::UpsertChange;
 use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
-use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+use crate::source::{truncation, RawSourceCreationConfig, SourceMessage, SourceReaderError};
 
 mod replication;
 mod snapshot;
+mod upsert;
 
 impl SourceRender for PostgresSourceConnection {
     type Time = MzOffset;
@@ -163,10 +175,11 @@ impl SourceRender for PostgresSourceConnection {
             let table_idx = *output_index - 1;
             let desc = self.publication_details.tables[table_idx].clone();
 
-            // Tables are indexed by their native index, but table casts are
-            // indexed by the output index.
+            // Tables are indexed by their native index, but table casts and
+            // filters are indexed by the output index.
             let casts = self.table_casts[output_index].clone();
-            table_info.insert(desc.oid, (*output_index, desc.clone(), casts.clone()));
+            let filter = self.table_filters.get(output_index).cloned();
+            table_info.insert(desc.oid, (*output_index, desc.clone(), casts, filter));
         }
 
         let metrics = config.metrics.get_postgres_source_metrics(config.id);
@@ -181,20 +194,77 @@ impl SourceRender for PostgresSourceConnection {
                 metrics.snapshot_metrics.clone(),
             );
 
-        let (repl_updates, uppers, stats_stream, repl_err, repl_token) = replication::render(
+        let retract_on_truncate = self.retract_on_truncate.clone();
+
+        // The primary key columns of the tables whose changes are applied by primary key.
+        let upsert_key_indices: BTreeMap<_, _> = table_info
+            .values()
+            .filter(|(output_index, _, _, _)| self.upsert_tables.contains(output_index))
+            .map(|(output_index, desc, _, _)| {
+                let key_indices =
+                    upsert::primary_key_indices(desc).expect("upsert tables have a primary key");
+                (*output_index, key_indices)
+            })
+            .collect();
+
+        // The snapshots of upsert tables are applied by the upsert operator too.
+        let snapshot_key_indices = upsert_key_indices.clone();
+        let (snapshot_updates, snapshot_upserts) =
+            snapshot_updates
+                .inner
+                .ok_err(move |((output_index, event), time, diff)| match event {
+                    Ok(row) if snapshot_key_indices.contains_key(&output_index) => {
+                        let change = UpsertChange::Upsert {
+                            seq: 0,
+                            prev: None,
+                            row,
+                            unchanged_toast: vec![],
+                        };
+                        Err(((output_index, change), time, diff))
+                    }
+                    event => Ok(((output_index, event), time, diff)),
+                });
+        let snapshot_updates = snapshot_updates.as_collection();
+
+        let (
+            repl_updates,
+            repl_upserts,
+            truncates,
+            schema_changes,
+            uppers,
+            stats_stream,
+            repl_err,
+            repl_token,
+        ) = replication::render(
             scope.clone(),
-            config,
+            config.clone(),
             self,
             subsource_resume_uppers,
             table_info,
             &rewinds,
             resume_uppers,
             metrics,
+            upsert_key_indices.clone(),
         );
 
         let stats_stream = stats_stream.concat(&snapshot_stats);
 
-        let updates = snapshot_updates.concat(&repl_updates).map(|(output, res)| {
+        let (upsert_updates, upsert_err, upsert_token) = upsert::render(
+            &config,
+            &snapshot_upserts.concat(&repl_upserts),
+            upsert_key_indices,
+        );
+
+        let (updates, truncation_token) = truncation::render(
+            &config,
+            &snapshot_updates
+                .concat(&repl_updates)
+                .concat(&upsert_updates),
+            &truncates,
+            retract_on_truncate,
+        );
+
+        let updates = updates.map(|(output, res)| {
             let res = res.map(|row| SourceMessage {
                 key: Row::default(),
                 value: row,
@@ -213,40 +283,49 @@ impl SourceRender for PostgresSourceConnection {
         // N.B. Note that we don't check ssh tunnel statuses here. We could, but immediately on
         // restart we are going to set the status to an ssh error correctly, so we don't do this
         // extra work.
-        let errs = snapshot_err.concat(&repl_err).map(move |err| {
-            // This update will cause the dataflow to restart
-            let err_string = err.display_with_causes().to_string();
-            let update = HealthStatusUpdate::halting(err_string.clone(), None);
-
-            let namespace = match err {
-                ReplicationError::Transient(err)
-                    if matches!(
-                        &*err,
-                        TransientError::PostgresError(PostgresError::Ssh(_))
-                            | TransientError::PostgresError(PostgresError::SshIo(_))
-                    ) =>
-                {
-                    StatusNamespace::Ssh
+        let errs = snapshot_err
+            .concat(&repl_err)
+            .concat(&upsert_err)
+            .map(move |err| {
+                // This update will cause the dataflow to restart
+                let err_string = err.display_with_causes().to_string();
+                let update = HealthStatusUpdate::halting(err_string.clone(), None);
+
+                let namespace = match err {
+                    ReplicationError::Transient(err)
+                        if matches!(
+                            &*err,
+                            TransientError::PostgresError(PostgresError::Ssh(_))
+                                | TransientError::PostgresError(PostgresError::SshIo(_))
+                        ) =>
+                    {
+                        StatusNamespace::Ssh
+                    }
+                    _ => Self::STATUS_NAMESPACE,
+                };
+
+                HealthStatusMessage {
+                    index: 0,
+                    namespace: namespace.clone(),
+                    update,
                 }
-                _ => Self::STATUS_NAMESPACE,
-            };
+            });
 
-            HealthStatusMessage {
-                index: 0,
-                namespace: namespace.clone(),
-                update,
-            }
+        // Additive schema changes don't interrupt the ingestion but are reported on the affected
+        // subsource so that users know to refresh its schema.
+        let notices = schema_changes.map(|(output_index, hint)| HealthStatusMessage {
+            index: output_index,
+            namespace: StatusNamespace::UpstreamSchema,
+            update: HealthStatusUpdate::notice(hint),
         });
 
-        let health = init.concat(&errs);
+        let health = init.concat(&errs).concat(&notices);
 
-        (
-            updates,
-            Some(uppers),
-            health,
-            stats_stream,
-            vec![snapshot_token, repl_token],
-        )
+        let mut tokens = vec![snapshot_token, repl_token];
+        tokens.extend(upsert_token);
+        tokens.extend(truncation_token);
+
+        (updates, Some(uppers), health, stats_stream, tokens)
     }
 }
 
@@ -313,6 +392,8 @@ pub enum DefiniteError {
     MissingToast,
     #[error("old row missing from replication stream. Did you forget to set REPLICA IDENTITY to FULL for your table?")]
     DefaultReplicaIdentity,
+    #[error("TOASTed value missing because the previous version of the row is unknown")]
+    MissingUpsertToast,
     #[error("incompatible schema change: {0}")]
     // TODO: proper error variants for all the expected schema violations
     IncompatibleSchema(String),
@@ -320,6 +401,8 @@ pub enum DefiniteError {
     InvalidUTF8(Vec<u8>),
     #[error("failed to cast raw column: {0}")]
     CastError(#[source] EvalError),
+    #[error("failed to evaluate row filter: {0}")]
+    RowFilterError(#[source] EvalError),
 }
 
 impl From<DefiniteError> for SourceReaderError {
@@ -403,7 +486,7 @@ fn verify_schema(
         .zip_eq(casts.iter())
         .flat_map(|(col, (cast_type, _))| match cast_type {
             CastType::Text => Some(col.col_num),
-            CastType::Natural => None,
+            CastType::Natural | CastType::Excluded => None,
         })
         .collect();
 
@@ -418,18 +501,72 @@ fn cast_row(
     casts: &[(CastType, MirScalarExpr)],
     datums: &[Datum<'_>],
     row: &mut Row,
+) -> Result<(), DefiniteError> {
+    cast_row_columns(casts, datums, |_| true, row)
+}
+
+/// Like [`cast_row`], but only casts the columns for which `include` returns true and sets the
+/// others to NULL. This is used for rows whose other columns are not known.
+fn cast_row_columns(
+    casts: &[(CastType, MirScalarExpr)],
+    datums: &[Datum<'_>],
+    include: impl Fn(usize) -> bool,
+    row: &mut Row,
 ) -> Result<(), DefiniteError> {
     let arena = mz_repr::RowArena::new();
     let mut packer = row.packer();
-    for (_, column_cast) in casts {
-        let datum = column_cast
-            .eval(datums, &arena)
-            .map_err(DefiniteError::CastError)?;
+    for (idx, (cast_type, column_cast)) in casts.iter().enumerate() {
+        // Columns left out of `INCLUDE COLUMNS` are not part of the output.
+        if *cast_type == CastType::Excluded {
+            continue;
+        }
+        if !include(idx) {
+            packer.push(Datum::Null);
+            continue;
+        }
+        let datum = match column_cast.eval(datums, &arena) {
+            // PostgreSQL enforces the maximum length of `varchar` columns, so the value of a
+            // column is only too long if the column was widened upstream. We ingest such values
+            // in full until the schema of the subsource is refreshed.
+            Err(EvalError::StringValueTooLong { .. }) => {
+                let column_cast = unbounded_varchar_cast(column_cast);
+                let datum = column_cast
+                    .eval(datums, &arena)
+                    .map_err(DefiniteError::CastError)?;
+                packer.push(datum);
+                continue;
+            }
+            datum => datum.map_err(DefiniteError::CastError)?,
+        };
         packer.push(datum);
     }
     Ok(())
 }
 
+/// Removes the maximum length from the `varchar` casts in `cast`.
+fn unbounded_varchar_cast(cast: &MirScalarExpr) -> MirScalarExpr {
+    let mut cast = cast.clone();
+    cast.visit_pre_mut(|expr| {
+        if let MirScalarExpr::CallUnary {
+            func: UnaryFunc::CastStringToVarChar(func),
+            ..
+        } = expr
+        {
+            func.length = None;
+        }
+    });
+    cast
+}
+
+/// Evaluates the row filter of a table on a text row, returning whether the row should be ingested.
+fn filter_row(filter: &MirScalarExpr, datums: &[Datum<'_>]) -> Result<bool, DefiniteError> {
+    let arena = mz_repr::RowArena::new();
+    let datum = filter
+        .eval(datums, &arena)
+        .map_err(DefiniteError::RowFilterError)?;
+    Ok(datum == Datum::True)
+}
+
 /// Converts raw bytes that are expected to be UTF8 encoded into a `Datum::String`
 fn decode_utf8_text(bytes: &[u8]) -> Result<Datum<'_>, DefiniteError> {
     match std::str::from_utf8(bytes) {
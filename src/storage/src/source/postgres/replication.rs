@@ -68,8 +68,16 @@
 //!
 //! [1]: https://www.postgresql.org/docs/15/protocol-replication.html#PROTOCOL-REPLICATION-START-REPLICATION
 //! [2]: https://www.postgresql.org/message-id/CAFPTHDZS9O9WG02EfayBd6oONzK%2BqfUxS6AbVLJ7W%2BKECza2gg%40mail.gmail.com
+//!
+//! # Upsert tables
+//!
+//! Updates and deletes of tables with `REPLICA IDENTITY DEFAULT` only carry the primary key of the
+//! old row, if anything, so they can't be turned into retractions here. The changes of tables
+//! listed in `UPSERT TABLES` are instead emitted as [`UpsertChange`]s, which the [upsert](super::upsert)
+//! operator applies by primary key. Since these changes can't be negated they are not part of
+//! snapshot rewinds and changes that are already reflected in the snapshot are dropped instead.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::Infallible;
 use std::pin::pin;
 use std::rc::Rc;
@@ -105,7 +113,7 @@ use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::Exchange;
 use timely::dataflow::channels::pushers::Tee;
 use timely::dataflow::operators::Capability;
-use timely::dataflow::operators::{Concat, Map};
+use timely::dataflow::operators::{Concat, Map, OkErr};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 use tokio_postgres::error::SqlState;
@@ -115,6 +123,7 @@ use tokio_postgres::Client;
 use tracing::{error, trace};
 
 use crate::metrics::source::postgres::PgSourceMetrics;
+use crate::source::postgres::upsert::UpsertChange;
 use crate::source::postgres::verify_schema;
 use crate::source::postgres::{DefiniteError, ReplicationError, TransientError};
 use crate::source::types::ProgressStatisticsUpdate;
@@ -144,18 +153,39 @@ pub(crate) struct RewindRequest {
     pub(crate) snapshot_lsn: MzOffset,
 }
 
+/// A change to an upstream table as read from the replication stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum RawChange {
+    /// A row that is inserted or retracted according to the diff of the change.
+    Row(Vec<Option<Bytes>>),
+    /// A change to a table that is applied by primary key.
+    Upsert(UpsertChange<Vec<Option<Bytes>>>),
+}
+
 /// Renders the replication dataflow. See the module documentation for more information.
 pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     scope: G,
     config: RawSourceCreationConfig,
     connection: PostgresSourceConnection,
     subsource_resume_uppers: BTreeMap<GlobalId, Antichain<MzOffset>>,
-    table_info: BTreeMap<u32, (usize, PostgresTableDesc, Vec<(CastType, MirScalarExpr)>)>,
+    table_info: BTreeMap<
+        u32,
+        (
+            usize,
+            PostgresTableDesc,
+            Vec<(CastType, MirScalarExpr)>,
+            Option<MirScalarExpr>,
+        ),
+    >,
     rewind_stream: &Stream<G, RewindRequest>,
     committed_uppers: impl futures::Stream<Item = Antichain<MzOffset>> + 'static,
     metrics: PgSourceMetrics,
+    upsert_key_indices: BTreeMap<usize, Vec<usize>>,
 ) -> (
     Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, ((usize, UpsertChange<Row>), MzOffset, Diff)>,
+    Stream<G, (usize, MzOffset)>,
+    Stream<G, (usize, String)>,
     Stream<G, Infallible>,
     Stream<G, ProgressStatisticsUpdate>,
     Stream<G, ReplicationError>,
@@ -170,6 +200,10 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     let (mut definite_error_handle, definite_errors) = builder.new_output();
 
     let (mut stats_output, stats_stream) = builder.new_output();
+    // Markers for the truncation of tables that are retracted on truncation.
+    let (mut truncate_output, truncate_stream) = builder.new_output();
+    // Descriptions of the additive schema changes of upstream tables.
+    let (mut schema_change_output, schema_change_stream) = builder.new_output();
 
     let mut rewind_input = builder.new_input_for_many(
         rewind_stream,
@@ -179,12 +213,18 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
 
     metrics.tables.set(u64::cast_from(table_info.len()));
 
+    let upsert_tables: BTreeSet<u32> = table_info
+        .iter()
+        .filter(|(_, (output_index, _, _, _))| upsert_key_indices.contains_key(output_index))
+        .map(|(oid, _)| *oid)
+        .collect();
+
     let reader_table_info = table_info.clone();
     let (button, transient_errors) = builder.build_fallible(move |caps| {
         let table_info = reader_table_info;
         Box::pin(async move {
             let (id, worker_id) = (config.id, config.worker_id);
-            let [data_cap_set, upper_cap_set, definite_error_cap_set, stats_cap]: &mut [_; 4] =
+            let [data_cap_set, upper_cap_set, definite_error_cap_set, stats_cap, truncate_cap_set, schema_change_cap_set]: &mut [_; 6] =
                 caps.try_into().unwrap();
 
             if !config.responsible_for("slot") {
@@ -233,6 +273,8 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
             };
             data_cap_set.downgrade([&resume_lsn]);
             upper_cap_set.downgrade([&resume_lsn]);
+            truncate_cap_set.downgrade([&resume_lsn]);
+            schema_change_cap_set.downgrade([&resume_lsn]);
             trace!(%id, "timely-{worker_id} replication \
                    reader started lsn={}", resume_lsn);
 
@@ -314,6 +356,8 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
             let mut stream = pin!(stream.peekable());
 
             let mut errored = HashSet::new();
+            let mut truncated = BTreeSet::new();
+            let mut schema_changes = BTreeMap::new();
             while let Some(event) = stream.as_mut().next().await {
                 use LogicalReplicationMessage::*;
                 use ReplicationMessage::*;
@@ -323,49 +367,76 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                         Begin(begin) => {
                             let commit_lsn = MzOffset::from(begin.final_lsn());
 
-                            let mut tx = pin!(extract_transaction(
-                                stream.by_ref(),
-                                commit_lsn,
-                                &table_info,
-                                &connection_config,
-                                &config.config.connection_context.ssh_tunnel_manager,
-                                &metrics,
-                                &connection.publication,
-                                &mut errored
-                            ));
-
-                            trace!(
-                                %id,
-                                "timely-{worker_id} extracting transaction \
-                                    at {commit_lsn}"
-                            );
-                            assert!(
-                                new_upper <= commit_lsn,
-                                "new_upper={new_upper} tx_lsn={commit_lsn}",
-                            );
-                            new_upper = commit_lsn + 1;
-                            // We are about to ingest a transaction which has the possiblity to be
-                            // very big and we certainly don't want to hold the data in memory. For
-                            // this reason we eagerly downgrade the upper capability in order for
-                            // the reclocking machinery to mint a binding that includes
-                            // this transaction and therefore be able to pass the data of the
-                            // transaction through as we stream it.
-                            upper_cap_set.downgrade([&new_upper]);
-                            while let Some((oid, event, diff)) = tx.try_next().await? {
-                                if !table_info.contains_key(&oid) {
-                                    continue;
-                                }
+                            {
+                                let mut tx = pin!(extract_transaction(
+                                    stream.by_ref(),
+                                    commit_lsn,
+                                    &table_info,
+                                    &connection_config,
+                                    &config.config.connection_context.ssh_tunnel_manager,
+                                    &metrics,
+                                    &connection.publication,
+                                    &connection.retract_on_truncate,
+                                    &upsert_tables,
+                                    &mut errored,
+                                    &mut truncated,
+                                    &mut schema_changes,
+                                ));
+
+                                trace!(
+                                    %id,
+                                    "timely-{worker_id} extracting transaction \
+                                        at {commit_lsn}"
+                                );
+                                assert!(
+                                    new_upper <= commit_lsn,
+                                    "new_upper={new_upper} tx_lsn={commit_lsn}",
+                                );
+                                new_upper = commit_lsn + 1;
+                                // We are about to ingest a transaction which has the possiblity to
+                                // be very big and we certainly don't want to hold the data in
+                                // memory. For this reason we eagerly downgrade the upper capability
+                                // in order for the reclocking machinery to mint a binding that
+                                // includes this transaction and therefore be able to pass the data
+                                // of the transaction through as we stream it.
+                                upper_cap_set.downgrade([&new_upper]);
+                                while let Some((oid, event, diff)) = tx.try_next().await? {
+                                    if !table_info.contains_key(&oid) {
+                                        continue;
+                                    }
 
-                                let data = (oid, event);
-                                if let Some((rewind_caps, req)) = rewinds.get(&oid) {
-                                    let [data_cap, _upper_cap] = rewind_caps;
-                                    if commit_lsn <= req.snapshot_lsn {
-                                        let update = (data.clone(), MzOffset::from(0), -diff);
-                                        data_output.give(data_cap, update).await;
+                                    let data = (oid, event);
+                                    if let Some((rewind_caps, req)) = rewinds.get(&oid) {
+                                        let [data_cap, _upper_cap] = rewind_caps;
+                                        if commit_lsn <= req.snapshot_lsn {
+                                            // Upsert changes can't be negated, but the snapshot
+                                            // already reflects them.
+                                            if matches!(data.1, Ok(RawChange::Upsert(_))) {
+                                                continue;
+                                            }
+                                            let update = (data.clone(), MzOffset::from(0), -diff);
+                                            data_output.give(data_cap, update).await;
+                                        }
                                     }
+                                    data_output
+                                        .give(&data_cap_set[0], (data, commit_lsn, diff))
+                                        .await;
                                 }
-                                data_output
-                                    .give(&data_cap_set[0], (data, commit_lsn, diff))
+                            }
+
+                            // Mark the truncation of tables whose contents should be retracted,
+                            // which happens once the whole transaction has been processed.
+                            for oid in std::mem::take(&mut truncated) {
+                                let (output_index, _, _, _) = &table_info[&oid];
+                                let marker = (*output_index, commit_lsn);
+                                truncate_output.give(&truncate_cap_set[0], marker).await;
+                            }
+
+                            // Report the additive schema changes that the transaction revealed.
+                            for (oid, hint) in std::mem::take(&mut schema_changes) {
+                                let (output_index, _, _, _) = &table_info[&oid];
+                                schema_change_output
+                                    .give(&schema_change_cap_set[0], (*output_index, hint))
                                     .await;
                             }
                         }
@@ -388,6 +459,8 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 if will_yield {
                     upper_cap_set.downgrade([&new_upper]);
                     data_cap_set.downgrade([&new_upper]);
+                    truncate_cap_set.downgrade([&new_upper]);
+                    schema_change_cap_set.downgrade([&new_upper]);
                     rewinds.retain(|_, (_, req)| data_cap_set[0].time() <= &req.snapshot_lsn);
                 }
             }
@@ -396,34 +469,55 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
         })
     });
 
-    // Distribute the raw slot data to all workers and turn it into a collection
-    let raw_collection = data_stream.distribute().as_collection();
+    // Distribute the raw slot data to all workers
+    let raw_updates = data_stream.distribute();
 
-    // We now process the slot updates and apply the cast expressions
+    // We now process the slot updates and apply the cast expressions, splitting off the changes
+    // of upsert tables.
     let mut final_row = Row::default();
     let mut datum_vec = DatumVec::new();
-    let replication_updates = raw_collection
-        .map(move |(oid, event)| {
-            let (output_index, _, casts) = &table_info[&oid];
-            let event = event.and_then(|row| {
-                let mut datums = datum_vec.borrow();
-                for col in row.iter() {
-                    let datum = col.as_deref().map(super::decode_utf8_text).transpose()?;
-                    datums.push(datum.unwrap_or(Datum::Null));
+    let (replication_updates, upsert_changes) =
+        raw_updates.ok_err(move |((oid, event), time, diff)| {
+            let (output_index, _, casts, filter) = &table_info[&oid];
+            let event = match event {
+                Ok(RawChange::Row(row)) => {
+                    let filter = filter.as_ref();
+                    match decode_filtered_row(&row, casts, filter, &mut datum_vec, &mut final_row) {
+                        Ok(Some(row)) => Ok(row),
+                        // Rows rejected by the row filter of the table are not ingested.
+                        Ok(None) => return Ok(None),
+                        Err(err) => Err(err),
+                    }
                 }
-                super::cast_row(casts, &datums, &mut final_row)?;
-                Ok(final_row.clone())
-            });
-
-            (*output_index, event.err_into())
-        })
-        .inner
+                Ok(RawChange::Upsert(change)) => {
+                    let key_indices = &upsert_key_indices[output_index];
+                    match decode_upsert_change(
+                        change,
+                        key_indices,
+                        casts,
+                        &mut datum_vec,
+                        &mut final_row,
+                    ) {
+                        Ok(change) => return Err(((*output_index, change), time, diff)),
+                        // Changes that can't be decoded surface as errors of the table.
+                        Err(err) => Err(err),
+                    }
+                }
+                Err(err) => Err(err),
+            };
+            Ok(Some(((*output_index, event.err_into()), time, diff)))
+        });
+    let replication_updates = replication_updates
+        .flat_map(|update| update)
         .as_collection();
 
     let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
 
     (
         replication_updates,
+        upsert_changes,
+        truncate_stream,
+        schema_change_stream,
         upper_stream,
         stats_stream,
         errors,
@@ -431,6 +525,83 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     )
 }
 
+/// Decodes a raw row and casts the columns for which `include` returns true into their target
+/// types, setting the other columns to NULL.
+fn decode_row(
+    row: &[Option<Bytes>],
+    casts: &[(CastType, MirScalarExpr)],
+    include: impl Fn(usize) -> bool,
+    datum_vec: &mut DatumVec,
+    final_row: &mut Row,
+) -> Result<Row, DefiniteError> {
+    let mut datums = datum_vec.borrow();
+    for col in row.iter() {
+        let datum = col.as_deref().map(super::decode_utf8_text).transpose()?;
+        datums.push(datum.unwrap_or(Datum::Null));
+    }
+    super::cast_row_columns(casts, &datums, include, final_row)?;
+    Ok(final_row.clone())
+}
+
+/// Decodes a raw row and, if it passes the row filter of its table, casts it into its target types.
+/// Returns `None` for rows that the filter rejects.
+fn decode_filtered_row(
+    row: &[Option<Bytes>],
+    casts: &[(CastType, MirScalarExpr)],
+    filter: Option<&MirScalarExpr>,
+    datum_vec: &mut DatumVec,
+    final_row: &mut Row,
+) -> Result<Option<Row>, DefiniteError> {
+    let mut datums = datum_vec.borrow();
+    for col in row.iter() {
+        let datum = col.as_deref().map(super::decode_utf8_text).transpose()?;
+        datums.push(datum.unwrap_or(Datum::Null));
+    }
+    if let Some(filter) = filter {
+        if !super::filter_row(filter, &datums)? {
+            return Ok(None);
+        }
+    }
+    super::cast_row(casts, &datums, final_row)?;
+    Ok(Some(final_row.clone()))
+}
+
+/// Decodes a raw upsert change, only casting the columns whose values the change carries.
+fn decode_upsert_change(
+    change: UpsertChange<Vec<Option<Bytes>>>,
+    key_indices: &[usize],
+    casts: &[(CastType, MirScalarExpr)],
+    datum_vec: &mut DatumVec,
+    final_row: &mut Row,
+) -> Result<UpsertChange<Row>, DefiniteError> {
+    let is_key = |idx: usize| key_indices.contains(&idx);
+    match change {
+        UpsertChange::Upsert {
+            seq,
+            prev,
+            row,
+            unchanged_toast,
+        } => {
+            let prev = match prev {
+                Some(prev) => Some(decode_row(&prev, casts, is_key, datum_vec, final_row)?),
+                None => None,
+            };
+            let is_known = |idx: usize| !unchanged_toast.contains(&idx);
+            let row = decode_row(&row, casts, is_known, datum_vec, final_row)?;
+            Ok(UpsertChange::Upsert {
+                seq,
+                prev,
+                row,
+                unchanged_toast,
+            })
+        }
+        UpsertChange::Delete { seq, key } => {
+            let key = decode_row(&key, casts, is_key, datum_vec, final_row)?;
+            Ok(UpsertChange::Delete { seq, key })
+        }
+    }
+}
+
 /// Produces the logical replication stream while taking care of regularly sending standby
 /// keepalive messages with the provided `uppers` stream.
 ///
@@ -604,20 +775,42 @@ fn extract_transaction<'a>(
     stream: impl AsyncStream<Item = Result<ReplicationMessage<LogicalReplicationMessage>, TransientError>>
         + 'a,
     commit_lsn: MzOffset,
-    table_info: &'a BTreeMap<u32, (usize, PostgresTableDesc, Vec<(CastType, MirScalarExpr)>)>,
+    table_info: &'a BTreeMap<
+        u32,
+        (
+            usize,
+            PostgresTableDesc,
+            Vec<(CastType, MirScalarExpr)>,
+            Option<MirScalarExpr>,
+        ),
+    >,
     connection_config: &'a mz_postgres_util::Config,
     ssh_tunnel_manager: &'a SshTunnelManager,
     metrics: &'a PgSourceMetrics,
     publication: &'a str,
+    retract_on_truncate: &'a BTreeSet<usize>,
+    upsert_tables: &'a BTreeSet<u32>,
     errored_tables: &'a mut HashSet<u32>,
-) -> impl AsyncStream<
-    Item = Result<(u32, Result<Vec<Option<Bytes>>, DefiniteError>, Diff), TransientError>,
-> + 'a {
+    truncated_tables: &'a mut BTreeSet<u32>,
+    schema_changes: &'a mut BTreeMap<u32, String>,
+) -> impl AsyncStream<Item = Result<(u32, Result<RawChange, DefiniteError>, Diff), TransientError>> + 'a
+{
     use LogicalReplicationMessage::*;
     async_stream::try_stream!({
         let mut stream = pin!(stream);
         metrics.transactions.inc();
         metrics.lsn.set(commit_lsn.offset);
+        // The rows of the tables that are retracted on truncation that this transaction has
+        // produced so far, which need to be retracted if the table is truncated later in the
+        // same transaction.
+        let retractable: BTreeSet<u32> = table_info
+            .iter()
+            .filter(|(_, (output_index, _, _, _))| retract_on_truncate.contains(output_index))
+            .map(|(oid, _)| *oid)
+            .collect();
+        let mut tx_rows: BTreeMap<u32, Vec<(Vec<Option<Bytes>>, Diff)>> = BTreeMap::new();
+        // The position of the next change to an upsert table in this transaction.
+        let mut seq = 0u64;
         while let Some(event) = stream.try_next().await? {
             // We can ignore keepalive messages while processing a transaction because the
             // commit_lsn will drive progress.
@@ -632,10 +825,56 @@ fn extract_transaction<'a>(
                 Update(body) if errored_tables.contains(&body.rel_id()) => continue,
                 Delete(body) if errored_tables.contains(&body.rel_id()) => continue,
                 Relation(body) if errored_tables.contains(&body.rel_id()) => continue,
+                Insert(body) if upsert_tables.contains(&body.rel_id()) => {
+                    metrics.inserts.inc();
+                    let (row, unchanged_toast) = unpack_partial_tuple(body.tuple().tuple_data());
+                    let change = UpsertChange::Upsert {
+                        seq,
+                        prev: None,
+                        row,
+                        unchanged_toast,
+                    };
+                    seq += 1;
+                    yield (body.rel_id(), Ok(RawChange::Upsert(change)), 1);
+                }
+                Update(body) if upsert_tables.contains(&body.rel_id()) => {
+                    metrics.updates.inc();
+                    // The old tuple is only present if the primary key changed, or if the table
+                    // uses `REPLICA IDENTITY FULL`.
+                    let prev = body
+                        .old_tuple()
+                        .or(body.key_tuple())
+                        .map(|tuple| unpack_partial_tuple(tuple.tuple_data()).0);
+                    let (row, unchanged_toast) =
+                        unpack_partial_tuple(body.new_tuple().tuple_data());
+                    let change = UpsertChange::Upsert {
+                        seq,
+                        prev,
+                        row,
+                        unchanged_toast,
+                    };
+                    seq += 1;
+                    yield (body.rel_id(), Ok(RawChange::Upsert(change)), 1);
+                }
+                Delete(body) if upsert_tables.contains(&body.rel_id()) => {
+                    match body.old_tuple().or(body.key_tuple()) {
+                        Some(tuple) => {
+                            metrics.deletes.inc();
+                            let (key, _) = unpack_partial_tuple(tuple.tuple_data());
+                            let change = UpsertChange::Delete { seq, key };
+                            seq += 1;
+                            yield (body.rel_id(), Ok(RawChange::Upsert(change)), 1);
+                        }
+                        None => {
+                            yield (body.rel_id(), Err(DefiniteError::DefaultReplicaIdentity), 1);
+                        }
+                    }
+                }
                 Insert(body) => {
                     metrics.inserts.inc();
                     let row = unpack_tuple(body.tuple().tuple_data());
-                    yield (body.rel_id(), row, 1);
+                    track_row(&retractable, &mut tx_rows, body.rel_id(), &row, 1);
+                    yield (body.rel_id(), row.map(RawChange::Row), 1);
                 }
                 Update(body) => match body.old_tuple() {
                     Some(old_tuple) => {
@@ -648,9 +887,11 @@ fn extract_transaction<'a>(
                                     _ => new,
                                 });
                         let old_row = unpack_tuple(old_tuple.tuple_data());
-                        yield (body.rel_id(), old_row, -1);
+                        track_row(&retractable, &mut tx_rows, body.rel_id(), &old_row, -1);
+                        yield (body.rel_id(), old_row.map(RawChange::Row), -1);
                         let new_row = unpack_tuple(new_tuple);
-                        yield (body.rel_id(), new_row, 1);
+                        track_row(&retractable, &mut tx_rows, body.rel_id(), &new_row, 1);
+                        yield (body.rel_id(), new_row.map(RawChange::Row), 1);
                     }
                     None => {
                         yield (body.rel_id(), Err(DefiniteError::DefaultReplicaIdentity), 1);
@@ -660,7 +901,8 @@ fn extract_transaction<'a>(
                     Some(old_tuple) => {
                         metrics.deletes.inc();
                         let row = unpack_tuple(old_tuple.tuple_data());
-                        yield (body.rel_id(), row, -1);
+                        track_row(&retractable, &mut tx_rows, body.rel_id(), &row, -1);
+                        yield (body.rel_id(), row.map(RawChange::Row), -1);
                     }
                     None => {
                         yield (body.rel_id(), Err(DefiniteError::DefaultReplicaIdentity), 1);
@@ -668,7 +910,7 @@ fn extract_transaction<'a>(
                 },
                 Relation(body) => {
                     let rel_id = body.rel_id();
-                    if let Some((_, expected_desc, casts)) = table_info.get(&rel_id) {
+                    if let Some((_, expected_desc, casts, _)) = table_info.get(&rel_id) {
                         // Because the replication stream doesn't include columns' attnums, we need
                         // to check the current local schema against the current remote schema to
                         // ensure e.g. we haven't received a schema update with the same terminal
@@ -681,11 +923,28 @@ fn extract_transaction<'a>(
                         .await?;
                         let upstream_info = upstream_info.into_iter().map(|t| (t.oid, t)).collect();
 
-                        if let Err(err) =
-                            verify_schema(rel_id, expected_desc, &upstream_info, casts)
-                        {
-                            errored_tables.insert(rel_id);
-                            yield (rel_id, Err(err), 1);
+                        match verify_schema(rel_id, expected_desc, &upstream_info, casts) {
+                            Ok(()) => {
+                                // The table might have evolved in a way that we can continue to
+                                // ingest but that requires the schema of the subsource to be
+                                // refreshed for the new data to become visible.
+                                let current_desc = &upstream_info[&rel_id];
+                                let changes = expected_desc.additive_changes(current_desc);
+                                if !changes.is_empty() {
+                                    let hint = format!(
+                                        "upstream table {}.{} has changed: {}; use ALTER SOURCE \
+                                         ... REFRESH SCHEMA to ingest the changes",
+                                        expected_desc.namespace,
+                                        expected_desc.name,
+                                        changes.join(", ")
+                                    );
+                                    schema_changes.insert(rel_id, hint);
+                                }
+                            }
+                            Err(err) => {
+                                errored_tables.insert(rel_id);
+                                yield (rel_id, Err(err), 1);
+                            }
                         }
 
                         // Error any dropped tables.
@@ -702,7 +961,18 @@ fn extract_transaction<'a>(
                 }
                 Truncate(body) => {
                     for &rel_id in body.rel_ids() {
-                        if errored_tables.insert(rel_id) {
+                        if errored_tables.contains(&rel_id) {
+                            continue;
+                        }
+                        if retractable.contains(&rel_id) {
+                            // Retract the rows this transaction produced before the truncation.
+                            // Everything else is retracted by the truncation operator.
+                            for (row, diff) in tx_rows.remove(&rel_id).unwrap_or_default() {
+                                yield (rel_id, Ok(RawChange::Row(row)), -diff);
+                            }
+                            truncated_tables.insert(rel_id);
+                        } else {
+                            errored_tables.insert(rel_id);
                             yield (rel_id, Err(DefiniteError::TableTruncated), 1);
                         }
                     }
@@ -726,6 +996,21 @@ fn extract_transaction<'a>(
     })
 }
 
+/// Records a row produced by a transaction for a table that is retracted on truncation.
+fn track_row(
+    retractable: &BTreeSet<u32>,
+    tx_rows: &mut BTreeMap<u32, Vec<(Vec<Option<Bytes>>, Diff)>>,
+    rel_id: u32,
+    row: &Result<Vec<Option<Bytes>>, DefiniteError>,
+    diff: Diff,
+) {
+    if let Ok(row) = row {
+        if retractable.contains(&rel_id) {
+            tx_rows.entry(rel_id).or_default().push((row.clone(), diff));
+        }
+    }
+}
+
 /// Unpacks an iterator of TupleData into a list of nullable bytes or an error if this can't be
 /// done.
 fn unpack_tuple<'a, I>(tuple_data: I) -> Result<Vec<Option<Bytes>>, DefiniteError>
@@ -746,6 +1031,30 @@ where
     Ok(row)
 }
 
+/// Unpacks an iterator of TupleData into a list of nullable bytes, along with the positions of the
+/// unchanged TOASTed values, which are unpacked as NULL.
+fn unpack_partial_tuple<'a, I>(tuple_data: I) -> (Vec<Option<Bytes>>, Vec<usize>)
+where
+    I: IntoIterator<Item = &'a TupleData>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let iter = tuple_data.into_iter();
+    let mut row = Vec::with_capacity(iter.len());
+    let mut unchanged_toast = vec![];
+    for (idx, data) in iter.enumerate() {
+        let datum = match data {
+            TupleData::Text(bytes) => Some(bytes.clone()),
+            TupleData::Null => None,
+            TupleData::UnchangedToast => {
+                unchanged_toast.push(idx);
+                None
+            }
+        };
+        row.push(datum);
+    }
+    (row, unchanged_toast)
+}
+
 /// Ensures the publication exists on the server. It returns an outer transient error in case of
 /// connection issues and an inner definite error if the publication is dropped.
 async fn ensure_publication_exists(
@@ -12,8 +12,8 @@
 //! # Snapshot reading
 //!
 //! Depending on the resumption LSNs the table reader decides which tables need to be snapshotted
-//! and performs a simple `COPY` query on them in order to get a snapshot. There are a few subtle
-//! points about this operation, described in the following sections.
+//! and performs `COPY` queries on them in order to get a snapshot. There are a few subtle points
+//! about this operation, described in the following sections.
 //!
 //! ## Consistent LSN point for snapshot transactions
 //!
@@ -54,18 +54,49 @@
 //!
 //! During this phase the follower workers are simply waiting to hear on the feedback edge,
 //! effectively synchronizing with the leader. Once all workers have received the snapshot
-//! information they can all start to perform their assigned COPY queries.
+//! information they all open a SQL session that enters the exported snapshot and start to perform
+//! their assigned COPY queries. The leader does the same, so that the session holding the
+//! exported transaction never runs a COPY query that could fail and abort it.
 //!
 //! The leader and follower steps described above are accomplished by the [`export_snapshot`] and
 //! [`use_snapshot`] functions respectively.
 //!
+//! ## Chunking
+//!
+//! Reading a large table with a single `COPY` query would leave all but one worker idle and would
+//! require starting over if the query fails. Before exporting the snapshot information the leader
+//! therefore splits every table into chunks of consecutive blocks, which are read with queries
+//! that filter on the `ctid` of the rows. The chunks are broadcast along with the snapshot
+//! information, so that all workers agree on them, and are distributed among all workers.
+//!
+//! A worker buffers the rows of a chunk until the chunk has been read completely. If reading a
+//! chunk fails the worker opens a new session, enters the exported snapshot again, and reads the
+//! chunk from the start, without affecting the chunks that have already been read. Only once a
+//! chunk has failed repeatedly, or if the session holding the exported transaction fails, is the
+//! whole snapshot started over.
+//!
+//! The buffer is limited by the number of bytes it holds rather than by the number of blocks of a
+//! chunk, since values stored out of line by TOAST don't count towards the blocks of a table. A
+//! chunk that exceeds the buffer is streamed from that point on, and so is a table that is read
+//! in a single chunk. A failure while streaming restarts the whole snapshot.
+//!
+//! Chunks only make failures cheaper to retry, they do not make snapshots resumable. If the
+//! dataflow restarts before the snapshot has been committed, every chunk is read again in a new
+//! snapshot. Resuming from the last completed chunk would require reading the remaining chunks at
+//! the LSN of the completed ones, but the exported snapshot does not outlive the transaction that
+//! exported it. Chunks read at different LSNs cannot be rewound separately either, since the
+//! rewind requests described below apply to whole tables and rows move between ctids as they
+//! are updated.
+//!
+//! Efficiently scanning ranges of ctids requires PostgreSQL 14 or later. Tables are read in a
+//! single chunk on earlier versions.
+//!
 //! ## Coordinated transaction COMMIT
 //!
-//! When follower workers are done with snapshotting they commit their transaction, close their
-//! session, and then drop their snapshot feedback capability. When the leader worker is done with
-//! snapshotting it drops its snapshot feedback capability and waits until it observes the
-//! snapshot input advancing to the empty frontier. This allows the leader to COMMIT its
-//! transaction last, which is the transaction that exported the snapshot.
+//! When workers are done with snapshotting they commit the transaction of their reading session,
+//! close it, and then drop their snapshot feedback capability. The leader additionally waits until
+//! it observes the snapshot input advancing to the empty frontier. This allows the leader to
+//! COMMIT its exporting transaction last, which is the transaction that exported the snapshot.
 //!
 //! It's unclear if this is strictly necessary, but having the frontiers made it easy enough that I
 //! added the synchronization.
@@ -106,9 +137,9 @@
 //!
 //! # Snapshot decoding
 //!
-//! The expectation is that tables will most likely be skewed on the number of rows they contain so
-//! while a `COPY` query for any given table runs on a single worker the decoding of the COPY
-//! stream is distributed to all workers.
+//! The expectation is that tables will most likely be skewed on the number of rows they contain,
+//! and on PostgreSQL versions without chunking a `COPY` query for any given table runs on a
+//! single worker, so the decoding of the COPY stream is distributed to all workers.
 //!
 //!
 //! ```text
@@ -142,25 +173,28 @@ use anyhow::bail;
 use differential_dataflow::{AsCollection, Collection};
 use futures::TryStreamExt;
 use mz_expr::MirScalarExpr;
+use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
 use mz_ore::result::ResultExt;
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::{simple_query_opt, PostgresError};
 use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row};
 use mz_sql_parser::ast::{display::AstDisplay, Ident};
+use mz_storage_types::dyncfgs;
 use mz_storage_types::sources::postgres::CastType;
 use mz_storage_types::sources::{MzOffset, PostgresSourceConnection};
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
 use mz_timely_util::operator::StreamExt as TimelyStreamExt;
+use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::{Broadcast, CapabilitySet, Concat, ConnectLoop, Feedback, Map};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::{Antichain, Timestamp};
 use tokio_postgres::types::{Oid, PgLsn};
-use tokio_postgres::Client;
-use tracing::{error, trace};
+use tokio_postgres::{Client, CopyOutStream};
+use tracing::{trace, warn};
 
 use crate::metrics::source::postgres::PgSnapshotMetrics;
 use crate::source::postgres::replication::RewindRequest;
@@ -175,7 +209,15 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     config: RawSourceCreationConfig,
     connection: PostgresSourceConnection,
     subsource_resume_uppers: BTreeMap<GlobalId, Antichain<MzOffset>>,
-    table_info: BTreeMap<u32, (usize, PostgresTableDesc, Vec<(CastType, MirScalarExpr)>)>,
+    table_info: BTreeMap<
+        u32,
+        (
+            usize,
+            PostgresTableDesc,
+            Vec<(CastType, MirScalarExpr)>,
+            Option<MirScalarExpr>,
+        ),
+    >,
     metrics: PgSnapshotMetrics,
 ) -> (
     Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
@@ -222,19 +264,28 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
         })
         .collect();
 
-    // A filtered table info containing only the tables that this worker should snapshot.
-    let reader_snapshot_table_info: BTreeMap<_, _> = table_info
+    // The tables that need to be snapshot by all workers.
+    let snapshot_table_info: BTreeMap<_, _> = table_info
         .iter()
-        .filter(|(oid, (output_index, _, _))| {
+        .filter(|(_, (output_index, _, _, _))| {
             mz_ore::soft_assert_or_log!(
                 *output_index != 0,
                 "primary collection should not be represented in table info"
             );
-            exports_to_snapshot.contains(output_index) && config.responsible_for(oid)
+            exports_to_snapshot.contains(output_index)
         })
         .map(|(k, v)| (*k, v.clone()))
         .collect();
 
+    // A filtered table info containing only the tables that this worker is responsible for. The
+    // responsible worker emits the rewind requests, size estimates and errors of a table, while
+    // its chunks are read by all workers.
+    let reader_snapshot_table_info: BTreeMap<_, _> = snapshot_table_info
+        .iter()
+        .filter(|(oid, _)| config.responsible_for(oid))
+        .map(|(k, v)| (*k, v.clone()))
+        .collect();
+
     let (button, transient_errors) = builder.build_fallible(move |caps| {
         Box::pin(async move {
             let id = config.id;
@@ -274,7 +325,8 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 .await?;
             let task_name = format!("timely-{worker_id} PG snapshotter");
 
-            let client = if is_snapshot_leader {
+            // The session of the snapshot leader that holds the exported snapshot alive.
+            let export_client = if is_snapshot_leader {
                 let client = connection_config
                     .connect_replication(&config.config.connection_context.ssh_tunnel_manager)
                     .await?;
@@ -284,7 +336,16 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 super::ensure_replication_slot(&client, &connection.publication_details.slot)
                     .await?;
 
-                let snapshot_info = export_snapshot(&client).await?;
+                let (snapshot_id, snapshot_lsn) = export_snapshot(&client).await?;
+                let chunk_blocks =
+                    dyncfgs::PG_SNAPSHOT_CHUNK_BLOCKS.get(config.config.config_set());
+                let chunks =
+                    plan_chunks(&client, snapshot_table_info.keys().copied(), chunk_blocks).await?;
+                let snapshot_info = SnapshotInfo {
+                    id: snapshot_id,
+                    lsn: snapshot_lsn,
+                    chunks,
+                };
                 trace!(
                     %id,
                     "timely-{worker_id} exporting snapshot info {snapshot_info:?}");
@@ -292,30 +353,12 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                     .give(&snapshot_cap_set[0], snapshot_info)
                     .await;
 
-                client
+                Some(client)
             } else {
-                // Only the snapshot leader needs a replication connection.
-                connection_config
-                    .connect(
-                        &task_name,
-                        &config.config.connection_context.ssh_tunnel_manager,
-                    )
-                    .await?
+                None
             };
 
-            // Configure statement_timeout based on param. We want to be able to
-            // override the server value here in case it's set too low,
-            // respective to the size of the data we need to copy.
-            set_statement_timeout(
-                &client,
-                config
-                    .config
-                    .parameters
-                    .pg_source_snapshot_statement_timeout,
-            )
-            .await?;
-
-            let (snapshot, snapshot_lsn) = loop {
+            let snapshot = loop {
                 match snapshot_input.next().await {
                     Some(AsyncEvent::Data(_, mut data)) => {
                         break data.pop().expect("snapshot sent above")
@@ -327,11 +370,14 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                     ),
                 }
             };
-            // Snapshot leader is already in identified transaction but all other workers need to enter it.
-            if !is_snapshot_leader {
-                trace!(%id, "timely-{worker_id} using snapshot id {snapshot:?}");
-                use_snapshot(&client, &snapshot).await?;
-            }
+            let snapshot_lsn = snapshot.lsn;
+
+            // All workers, including the leader, read through a separate session that enters the
+            // exported snapshot, so that a failed read does not end the exported transaction.
+            trace!(%id, "timely-{worker_id} using snapshot id {:?}", snapshot.id);
+            let mut client =
+                connect_snapshot_reader(&config, &connection_config, &task_name, &snapshot.id)
+                    .await?;
 
             // We have established a snapshot LSN so we can broadcast the rewind requests
             for &oid in reader_snapshot_table_info.keys() {
@@ -380,16 +426,7 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
 
             let worker_tables = reader_snapshot_table_info
                 .iter()
-                .map(|(_, (_, desc, _))| {
-                    (
-                        format!(
-                            "{}.{}",
-                            Ident::new_unchecked(desc.namespace.clone()).to_ast_string(),
-                            Ident::new_unchecked(desc.name.clone()).to_ast_string()
-                        ),
-                        desc.oid.clone(),
-                    )
-                })
+                .map(|(_, (_, desc, _))| (table_name(desc), desc.oid.clone()))
                 .collect();
 
             let snapshot_total =
@@ -405,63 +442,119 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 )
                 .await;
 
+            let max_retries = dyncfgs::PG_SNAPSHOT_CHUNK_RETRIES.get(config.config.config_set());
+            let buffer_limit =
+                dyncfgs::PG_SNAPSHOT_CHUNK_BUFFER_BYTES.get(config.config.config_set());
             let mut snapshot_staged = 0;
-            for (&oid, (_, expected_desc, casts)) in reader_snapshot_table_info.iter() {
+            for (&oid, (_, expected_desc, casts, _)) in snapshot_table_info.iter() {
                 let desc = match verify_schema(oid, expected_desc, &upstream_info, casts) {
                     Ok(()) => expected_desc,
-                    Err(err) => {
+                    // Only the responsible worker reports the error, the others skip the table.
+                    Err(err) if reader_snapshot_table_info.contains_key(&oid) => {
                         raw_handle
                             .give(&data_cap_set[0], ((oid, Err(err)), MzOffset::minimum(), 1))
                             .await;
                         continue;
                     }
+                    Err(_) => continue,
                 };
 
-                trace!(
-                    %id,
-                    "timely-{worker_id} snapshotting table {:?}({oid}) @ {snapshot_lsn}",
-                    desc.name
-                );
-
-                // To handle quoted/keyword names, we can use `Ident`'s AST printing, which
-                // emulate's PG's rules for name formatting.
-                let query = format!(
-                    "COPY {}.{} TO STDOUT (FORMAT TEXT, DELIMITER '\t')",
-                    Ident::new_unchecked(desc.namespace.clone()).to_ast_string(),
-                    Ident::new_unchecked(desc.name.clone()).to_ast_string(),
-                );
-                let mut stream = pin!(client.copy_out_simple(&query).await?);
-
-                while let Some(bytes) = stream.try_next().await? {
-                    raw_handle
-                        .give(&data_cap_set[0], ((oid, Ok(bytes)), MzOffset::minimum(), 1))
+                let chunks = &snapshot.chunks[&oid];
+                for (idx, &start) in chunks.iter().enumerate() {
+                    if !config.responsible_for((oid, idx)) {
+                        continue;
+                    }
+                    let end = chunks.get(idx + 1).copied();
+
+                    trace!(
+                        %id,
+                        "timely-{worker_id} snapshotting chunk {idx} of table {:?}({oid}) \
+                         @ {snapshot_lsn}",
+                        desc.name
+                    );
+
+                    // Only bounded chunks are buffered, a table that is read in a single chunk
+                    // is streamed and can't be retried.
+                    let mut retryable = start > 0 || end.is_some();
+                    let mut retries = 0;
+                    loop {
+                        let mut buffer = vec![];
+                        let mut buffered_bytes = 0;
+                        let result = async {
+                            let mut stream = pin!(read_chunk(&client, desc, start, end).await?);
+                            loop {
+                                let next = stream.try_next().await?;
+                                let done = next.is_none();
+                                if let Some(bytes) = next {
+                                    buffered_bytes += bytes.len();
+                                    buffer.push(bytes);
+                                }
+                                if !done {
+                                    if retryable && buffered_bytes <= buffer_limit {
+                                        continue;
+                                    }
+                                    retryable = false;
+                                }
+                                for bytes in buffer.drain(..) {
+                                    raw_handle
+                                        .give(
+                                            &data_cap_set[0],
+                                            ((oid, Ok(bytes)), MzOffset::minimum(), 1),
+                                        )
+                                        .await;
+                                    snapshot_staged += 1;
+                                    // TODO(guswynn): does this 1000 need to be configurable?
+                                    if snapshot_staged % 1000 == 0 {
+                                        stats_output
+                                            .give(
+                                                &stats_cap[0],
+                                                ProgressStatisticsUpdate::Snapshot {
+                                                    records_known: snapshot_total
+                                                        .max(snapshot_staged),
+                                                    records_staged: snapshot_staged,
+                                                },
+                                            )
+                                            .await;
+                                    }
+                                }
+                                if done {
+                                    return Ok::<_, TransientError>(());
+                                }
+                            }
+                        }
                         .await;
-                    snapshot_staged += 1;
-                    // TODO(guswynn): does this 1000 need to be configurable?
-                    if snapshot_staged % 1000 == 0 {
-                        stats_output
-                            .give(
-                                &stats_cap[0],
-                                ProgressStatisticsUpdate::Snapshot {
-                                    records_known: snapshot_total,
-                                    records_staged: snapshot_staged,
-                                },
-                            )
-                            .await;
+
+                        match result {
+                            Ok(()) => break,
+                            Err(err) if retryable && retries < max_retries => {
+                                retries += 1;
+                                warn!(
+                                    %id,
+                                    "timely-{worker_id} retrying chunk {idx} of table {oid} \
+                                     after error: {}",
+                                    err.display_with_causes()
+                                );
+                                client = connect_snapshot_reader(
+                                    &config,
+                                    &connection_config,
+                                    &task_name,
+                                    &snapshot.id,
+                                )
+                                .await?;
+                            }
+                            Err(err) => return Err(err),
+                        }
                     }
                 }
             }
 
-            if snapshot_staged < snapshot_total {
-                error!(%id, "timely-{worker_id} snapshot size {snapshot_total} is somehow
-                                 bigger than records staged {snapshot_staged}");
-                snapshot_staged = snapshot_total;
-            }
+            // The chunks of the tables this worker estimated the size of might have been read by
+            // other workers, so the estimate is replaced by what this worker actually read.
             stats_output
                 .give(
                     &stats_cap[0],
                     ProgressStatisticsUpdate::Snapshot {
-                        records_known: snapshot_total,
+                        records_known: snapshot_staged,
                         records_staged: snapshot_staged,
                     },
                 )
@@ -472,20 +565,22 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 TransientError::SyntheticError
             ));
 
+            trace!(%id, "timely-{worker_id} comitting COPY transaction");
+            client.simple_query("COMMIT").await?;
+            drop(client);
+
             // The exporting worker should wait for all the other workers to commit before dropping
             // its client since this is what holds the exported transaction alive.
-            if is_snapshot_leader {
-                trace!(%id, "timely-{worker_id} waiting for all workers to finish");
-                *snapshot_cap_set = CapabilitySet::new();
-                while snapshot_input.next().await.is_some() {}
-                trace!(%id, "timely-{worker_id} (leader) comitting COPY transaction");
-                client.simple_query("COMMIT").await?;
-            } else {
-                trace!(%id, "timely-{worker_id} comitting COPY transaction");
-                client.simple_query("COMMIT").await?;
-                *snapshot_cap_set = CapabilitySet::new();
+            match export_client {
+                Some(export_client) => {
+                    trace!(%id, "timely-{worker_id} waiting for all workers to finish");
+                    *snapshot_cap_set = CapabilitySet::new();
+                    while snapshot_input.next().await.is_some() {}
+                    trace!(%id, "timely-{worker_id} (leader) comitting exported transaction");
+                    export_client.simple_query("COMMIT").await?;
+                }
+                None => *snapshot_cap_set = CapabilitySet::new(),
             }
-            drop(client);
             Ok(())
         })
     });
@@ -493,21 +588,29 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     // Distribute the raw COPY data to all workers and turn it into a collection
     let raw_collection = raw_data.distribute().as_collection();
 
-    // We now decode the COPY protocol and apply the cast expressions
+    // We now decode the COPY protocol and apply the row filters and cast expressions
     let mut text_row = Row::default();
     let mut final_row = Row::default();
     let mut datum_vec = DatumVec::new();
-    let snapshot_updates = raw_collection.map(move |(oid, event)| {
-        let (output_index, _, casts) = &table_info[&oid];
+    let snapshot_updates = raw_collection.flat_map(move |(oid, event)| {
+        let (output_index, _, casts, filter) = &table_info[&oid];
 
         let event = event.and_then(|bytes| {
             decode_copy_row(&bytes, casts.len(), &mut text_row)?;
             let datums = datum_vec.borrow_with(&text_row);
+            // Rows rejected by the row filter of the table are not ingested.
+            if let Some(filter) = filter {
+                if !super::filter_row(filter, &datums)? {
+                    return Ok(None);
+                }
+            }
             super::cast_row(casts, &datums, &mut final_row)?;
-            Ok(final_row.clone())
+            Ok(Some(final_row.clone()))
         });
 
-        (*output_index, event.err_into())
+        event
+            .transpose()
+            .map(|event| (*output_index, event.err_into()))
     });
 
     let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
@@ -521,6 +624,120 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     )
 }
 
+/// The snapshot that the snapshot leader exports to all workers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SnapshotInfo {
+    /// The identifier of the exported snapshot.
+    id: String,
+    /// The LSN of the consistent point of the snapshot.
+    lsn: MzOffset,
+    /// The first block of every chunk of each table that is snapshotted. The last chunk of a table
+    /// extends to the end of the table.
+    chunks: BTreeMap<u32, Vec<u64>>,
+}
+
+/// Splits the tables with the given OIDs into chunks of `chunk_blocks` blocks and returns the first
+/// block of every chunk. Tables are read in a single chunk if chunking is disabled or if the
+/// server can't scan ranges of ctids efficiently, which is only possible since PostgreSQL 14.
+async fn plan_chunks(
+    client: &Client,
+    oids: impl Iterator<Item = u32>,
+    chunk_blocks: usize,
+) -> Result<BTreeMap<u32, Vec<u64>>, TransientError> {
+    let row = simple_query_opt(client, "SHOW server_version_num;")
+        .await?
+        .unwrap();
+    let version: u32 = row.get("server_version_num").unwrap().parse().unwrap();
+    let chunked = chunk_blocks > 0 && version >= 140000;
+
+    let mut chunks = BTreeMap::new();
+    for oid in oids {
+        let mut starts = vec![0];
+        if chunked {
+            // The size of a relation is not subject to the snapshot, but the rows that are visible
+            // to it can't be in blocks past the current end of the relation. The last chunk is
+            // unbounded regardless.
+            let query = format!(
+                "SELECT coalesce(pg_relation_size({oid}), 0) / \
+                    current_setting('block_size')::int8 AS blocks;"
+            );
+            let row = simple_query_opt(client, &query).await?.unwrap();
+            let blocks: u64 = row.get("blocks").unwrap().parse().unwrap();
+            starts.extend((0..blocks).step_by(chunk_blocks).skip(1));
+        }
+        chunks.insert(oid, starts);
+    }
+    Ok(chunks)
+}
+
+/// Opens a SQL session that reads from the exported `snapshot`.
+async fn connect_snapshot_reader(
+    config: &RawSourceCreationConfig,
+    connection_config: &mz_postgres_util::Config,
+    task_name: &str,
+    snapshot: &str,
+) -> Result<Client, TransientError> {
+    let client = connection_config
+        .connect(
+            task_name,
+            &config.config.connection_context.ssh_tunnel_manager,
+        )
+        .await?;
+
+    // Configure statement_timeout based on param. We want to be able to
+    // override the server value here in case it's set too low,
+    // respective to the size of the data we need to copy.
+    set_statement_timeout(
+        &client,
+        config
+            .config
+            .parameters
+            .pg_source_snapshot_statement_timeout,
+    )
+    .await?;
+
+    use_snapshot(&client, snapshot).await?;
+    Ok(client)
+}
+
+/// Starts a COPY query that reads the raw rows of the chunk of `desc` that starts at block `start`
+/// and ends before block `end`, if any.
+async fn read_chunk(
+    client: &Client,
+    desc: &PostgresTableDesc,
+    start: u64,
+    end: Option<u64>,
+) -> Result<CopyOutStream, TransientError> {
+    fail::fail_point!("pg_snapshot_chunk_failure", |_| Err(
+        TransientError::SyntheticError
+    ));
+
+    let table = table_name(desc);
+    let query = match (start, end) {
+        (0, None) => format!("COPY {table} TO STDOUT (FORMAT TEXT, DELIMITER '\t')"),
+        (start, None) => format!(
+            "COPY (SELECT * FROM {table} WHERE ctid >= '({start},0)') \
+             TO STDOUT (FORMAT TEXT, DELIMITER '\t')"
+        ),
+        (start, Some(end)) => format!(
+            "COPY (SELECT * FROM {table} WHERE ctid >= '({start},0)' AND ctid < '({end},0)') \
+             TO STDOUT (FORMAT TEXT, DELIMITER '\t')"
+        ),
+    };
+    Ok(client.copy_out_simple(&query).await?)
+}
+
+/// Returns the quoted, schema-qualified name of `desc`.
+fn table_name(desc: &PostgresTableDesc) -> String {
+    // To handle quoted/keyword names, we can use `Ident`'s AST printing, which
+    // emulate's PG's rules for name formatting.
+    format!(
+        "{}.{}",
+        Ident::new_unchecked(desc.namespace.clone()).to_ast_string(),
+        Ident::new_unchecked(desc.name.clone()).to_ast_string()
+    )
+}
+
 /// Starts a read-only transaction on the SQL session of `client` at a consistent LSN point by
 /// creating a temporary replication slot. Returns a snapshot identifier that can be imported in
 /// other SQL session and the LSN of the consistent point.
@@ -0,0 +1,545 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the operator that applies the changes of tables listed in `UPSERT TABLES`.
+//!
+//! Tables that use `REPLICA IDENTITY DEFAULT` only report the primary key of the rows that are
+//! updated or deleted, and omit the values of TOASTed columns that an update didn't change. The
+//! replication reader therefore can't produce retractions for these tables and instead emits
+//! [`UpsertChange`]s, which this operator applies to a copy of the table's contents keyed by
+//! primary key. The retractions are then produced from the previous versions of the rows found in
+//! that copy.
+//!
+//! ```text
+//!   snapshot and
+//!   replication
+//!   changes
+//!      │
+//!   ┏━━v━━━━━━━━━━━┓
+//!   ┃    upsert    ┃ (exchanged by output index)
+//!   ┗━━━━━━┯━━━━━━━┛
+//!          │ updates
+//!          v
+//! ```
+//!
+//! # State
+//!
+//! The contents of the tables are kept in the same state backends as the upsert envelope, which
+//! spill to RocksDB when the replica has scratch space. The state of all the tables a worker is
+//! responsible for is shared, with the output index being part of every key.
+//!
+//! Snapshot changes are applied as they arrive. The changes of a transaction are buffered in
+//! memory until the input frontier has advanced past the transaction's commit LSN, at which point
+//! they are applied in the order they happened upstream.
+//!
+//! When the dataflow restarts the state is rehydrated from the subsource's persist shard as of its
+//! resume upper. Changes at source timestamps that are not beyond the subsource's resume upper are
+//! already reflected in the shard and are therefore dropped.
+//!
+//! # Consistency
+//!
+//! Snapshots of other tables are rewound to the minimum timestamp by retracting the replicated
+//! changes that happened before the snapshot was taken. Changes to upsert tables can't be
+//! retracted without knowing the rows they replaced, so they are dropped instead. As a result the
+//! contents of the subsource at timestamps between the minimum timestamp and the snapshot LSN
+//! correspond to the snapshot rather than to the state of the upstream table at that timestamp.
+//! The contents after the snapshot LSN are always correct.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use differential_dataflow::{AsCollection, Collection};
+use indexmap::IndexMap;
+use mz_ore::cast::CastFrom;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_postgres_util::desc::PostgresTableDesc;
+use mz_repr::{Datum, Diff, Row};
+use mz_storage_types::dyncfgs;
+use mz_storage_types::sources::{MzOffset, SourceData, SourceTimestamp};
+use mz_timely_util::builder_async::{
+    Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use serde::{Deserialize, Serialize};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::operator::empty;
+use timely::dataflow::operators::{CapabilitySet, Map};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::trace;
+
+use crate::metrics::upsert::UpsertMetrics;
+use crate::source::postgres::{DefiniteError, ReplicationError, TransientError};
+use crate::source::types::SourceReaderError;
+use crate::source::RawSourceCreationConfig;
+use crate::upsert::autospill::{AutoSpillBackend, RocksDBParams};
+use crate::upsert::memory::InMemoryHashMap;
+use crate::upsert::types::{
+    upsert_bincode_opts, PutValue, StateValue, UpsertState, UpsertStateBackend, UpsertValueAndSize,
+    Value, ValueMetadata,
+};
+use crate::upsert::UpsertKey;
+
+/// A change to a table whose changes are applied by primary key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum UpsertChange<R> {
+    /// An insertion of `row`, or an update to it.
+    Upsert {
+        /// The position of the change in its transaction.
+        seq: u64,
+        /// The previous version of the row, if the update changed its primary key. Only the
+        /// primary key columns are known.
+        prev: Option<R>,
+        /// The new version of the row.
+        row: R,
+        /// The positions of the columns of `row` that are unchanged from the previous version of
+        /// the row and must be filled in from it.
+        unchanged_toast: Vec<usize>,
+    },
+    /// A deletion of the row with the primary key of `key`. Only the primary key columns are
+    /// known.
+    Delete {
+        /// The position of the change in its transaction.
+        seq: u64,
+        key: R,
+    },
+}
+
+impl<R> UpsertChange<R> {
+    fn seq(&self) -> u64 {
+        match self {
+            UpsertChange::Upsert { seq, .. } | UpsertChange::Delete { seq, .. } => *seq,
+        }
+    }
+
+    /// The rows whose primary keys identify the rows affected by this change.
+    fn key_rows(&self) -> impl Iterator<Item = &R> {
+        let (first, second) = match self {
+            UpsertChange::Upsert { prev, row, .. } => (row, prev.as_ref()),
+            UpsertChange::Delete { key, .. } => (key, None),
+        };
+        std::iter::once(first).chain(second)
+    }
+}
+
+/// Returns the positions of the primary key columns of `desc`, if it has a primary key.
+pub(crate) fn primary_key_indices(desc: &PostgresTableDesc) -> Option<Vec<usize>> {
+    let key = desc.keys.iter().find(|key| key.is_primary)?;
+    key.cols
+        .iter()
+        .map(|col_num| desc.columns.iter().position(|col| col.col_num == *col_num))
+        .collect()
+}
+
+/// Renders the upsert operator for the outputs in `key_indices`, which maps each output to the
+/// positions of its primary key columns.
+pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
+    config: &RawSourceCreationConfig,
+    changes: &Stream<G, ((usize, UpsertChange<Row>), MzOffset, Diff)>,
+    key_indices: BTreeMap<usize, Vec<usize>>,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, ReplicationError>,
+    Option<PressOnDropButton>,
+) {
+    if key_indices.is_empty() {
+        let scope = changes.scope();
+        return (Collection::new(empty(&scope)), empty(&scope), None);
+    }
+
+    let upsert_metrics = config
+        .metrics
+        .get_upsert_metrics(config.id, config.worker_id, None);
+    let rocksdb_cleanup_tries =
+        dyncfgs::STORAGE_ROCKSDB_CLEANUP_TRIES.get(config.config.config_set());
+    let auto_spill_config = &config.config.parameters.upsert_auto_spill_config;
+
+    let Some(scratch_directory) = config.instance_context.scratch_directory.as_ref() else {
+        tracing::info!(
+            "timely-{} rendering {} with memory-backed upsert table state",
+            config.worker_id,
+            config.id
+        );
+        return render_inner(config, changes, key_indices, upsert_metrics, || async {
+            InMemoryHashMap::default()
+        });
+    };
+
+    let tuning = config.rocksdb_tuning_config.clone();
+    tracing::info!(
+        ?tuning,
+        ?auto_spill_config,
+        "timely-{} rendering {} with rocksdb-backed upsert table state",
+        config.worker_id,
+        config.id
+    );
+    let rocksdb_shared_metrics = Arc::clone(&upsert_metrics.rocksdb_shared);
+    let rocksdb_instance_metrics = Arc::clone(&upsert_metrics.rocksdb_instance_metrics);
+    let rocksdb_in_use_metric = Arc::clone(&upsert_metrics.rocksdb_autospill_in_use);
+    let rocksdb_dir = scratch_directory
+        .join("storage")
+        .join("upsert")
+        .join(config.id.to_string())
+        .join(config.worker_id.to_string());
+    let env = config.instance_context.rocksdb_env.clone();
+
+    if auto_spill_config.allow_spilling_to_disk {
+        let spill_threshold = auto_spill_config.spill_to_disk_threshold_bytes;
+        render_inner(
+            config,
+            changes,
+            key_indices,
+            upsert_metrics,
+            move || async move {
+                AutoSpillBackend::new(
+                    RocksDBParams {
+                        instance_path: rocksdb_dir,
+                        env,
+                        tuning_config: tuning,
+                        shared_metrics: rocksdb_shared_metrics,
+                        instance_metrics: rocksdb_instance_metrics,
+                        cleanup_tries: rocksdb_cleanup_tries,
+                    },
+                    spill_threshold,
+                    rocksdb_in_use_metric,
+                )
+            },
+        )
+    } else {
+        render_inner(
+            config,
+            changes,
+            key_indices,
+            upsert_metrics,
+            move || async move {
+                crate::upsert::rocksdb::RocksDB::new(
+                    mz_rocksdb::RocksDBInstance::new(
+                        &rocksdb_dir,
+                        mz_rocksdb::InstanceOptions::defaults_with_env(env, rocksdb_cleanup_tries),
+                        tuning,
+                        rocksdb_shared_metrics,
+                        rocksdb_instance_metrics,
+                        upsert_bincode_opts(),
+                    )
+                    .await
+                    .unwrap(),
+                )
+            },
+        )
+    }
+}
+
+fn render_inner<G, F, Fut, US>(
+    config: &RawSourceCreationConfig,
+    changes: &Stream<G, ((usize, UpsertChange<Row>), MzOffset, Diff)>,
+    key_indices: BTreeMap<usize, Vec<usize>>,
+    upsert_metrics: UpsertMetrics,
+    state: F,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, ReplicationError>,
+    Option<PressOnDropButton>,
+)
+where
+    G: Scope<Timestamp = MzOffset>,
+    F: FnOnce() -> Fut + 'static,
+    Fut: Future<Output = US>,
+    US: UpsertStateBackend<()>,
+{
+    let mut builder =
+        AsyncOperatorBuilder::new(format!("PgUpsertTables({})", config.id), changes.scope());
+
+    let (mut output_handle, output) = builder.new_output();
+
+    let router = config.clone();
+    let mut input = builder.new_input_for(
+        changes,
+        Exchange::new(move |((output_index, _), _, _): &((usize, _), _, _)| {
+            u64::cast_from(router.responsible_worker(*output_index))
+        }),
+        &output_handle,
+    );
+
+    // The outputs this worker is responsible for, along with the export that they correspond to.
+    let exports: BTreeMap<_, _> = config
+        .source_exports
+        .iter()
+        .filter(|(_, export)| {
+            key_indices.contains_key(&export.output_index)
+                && config.responsible_for(export.output_index)
+        })
+        .map(|(id, export)| (export.output_index, (*id, export.storage_metadata.clone())))
+        .collect();
+
+    let config = config.clone();
+    let (button, errors) = builder.build_fallible(move |caps| {
+        Box::pin(async move {
+            let [cap_set]: &mut [_; 1] = caps.try_into().unwrap();
+
+            let (id, worker_id) = (config.id, config.worker_id);
+
+            let mut state = UpsertState::<_, ()>::new(
+                state().await,
+                Arc::clone(&upsert_metrics.shared),
+                &upsert_metrics,
+                config.source_statistics.clone(),
+                config
+                    .config
+                    .parameters
+                    .shrink_upsert_unused_buffers_by_ratio,
+            );
+
+            // The upper of each output in the source timestamp domain. Changes that are not beyond
+            // it are already reflected in the persisted contents.
+            let mut source_uppers = BTreeMap::new();
+
+            for (output_index, (export_id, metadata)) in exports {
+                let source_upper: Antichain<MzOffset> = config
+                    .source_resume_uppers
+                    .get(&export_id)
+                    .map(|upper| upper.iter().map(MzOffset::decode_row).collect())
+                    .unwrap_or_else(|| Antichain::from_elem(MzOffset::minimum()));
+                source_uppers.insert(output_index, source_upper);
+
+                let upper = match config.resume_uppers.get(&export_id) {
+                    Some(upper) => upper,
+                    None => continue,
+                };
+                let as_of = match upper.as_option().and_then(|ts| ts.step_back()) {
+                    Some(as_of) => as_of,
+                    // The output is either empty or will never receive updates again.
+                    None => continue,
+                };
+
+                let persist_client = config
+                    .persist_clients
+                    .open(metadata.persist_location.clone())
+                    .await
+                    .expect("error creating persist client");
+                let mut read_handle = persist_client
+                    .open_leased_reader::<SourceData, (), mz_repr::Timestamp, Diff>(
+                        metadata.data_shard,
+                        Arc::new(metadata.relation_desc.clone()),
+                        Arc::new(UnitSchema),
+                        Diagnostics {
+                            shard_name: export_id.to_string(),
+                            handle_purpose: format!("upsert table state for {}", id),
+                        },
+                        false,
+                    )
+                    .await
+                    .expect("shard unavailable");
+                let snapshot = read_handle
+                    .snapshot_and_fetch(Antichain::from_elem(as_of))
+                    .await
+                    .expect("resume upper is beyond the since");
+
+                let output_key_indices = &key_indices[&output_index];
+                let mut updates = vec![];
+                for ((key, _val), _ts, diff) in snapshot {
+                    if let Ok(SourceData(Ok(row))) = key {
+                        let key = upsert_key(output_index, output_key_indices, &row);
+                        updates.push((key, Ok(row), diff));
+                    }
+                }
+                read_handle.expire().await;
+                trace!(%id, "timely-{worker_id} rehydrating {} updates of output \
+                             {output_index} as of {as_of}", updates.len());
+                state
+                    .merge_snapshot_chunk(updates.into_iter(), false)
+                    .await?;
+            }
+            state
+                .merge_snapshot_chunk(Vec::new().into_iter(), true)
+                .await?;
+
+            let is_replayed = |output_index: usize, time: &MzOffset| {
+                source_uppers
+                    .get(&output_index)
+                    .map_or(false, |upper| !upper.less_equal(time))
+            };
+
+            // Changes that are waiting for the frontier to advance past their time.
+            let mut pending: BTreeMap<MzOffset, Vec<(usize, UpsertChange<Row>)>> = BTreeMap::new();
+            let mut updates = vec![];
+
+            while let Some(event) = input.next().await {
+                match event {
+                    AsyncEvent::Data(cap, data) => {
+                        // Snapshot changes never conflict with each other and can be applied
+                        // right away.
+                        let mut snapshot = vec![];
+                        for ((output_index, change), time, _diff) in data {
+                            if is_replayed(output_index, &time) {
+                                continue;
+                            }
+                            if time == MzOffset::minimum() {
+                                snapshot.push((output_index, change));
+                            } else {
+                                pending
+                                    .entry(time)
+                                    .or_default()
+                                    .push((output_index, change));
+                            }
+                        }
+                        if !snapshot.is_empty() {
+                            apply_changes(&mut state, &key_indices, snapshot, &mut updates).await?;
+                            for (update, diff) in updates.drain(..) {
+                                output_handle
+                                    .give(&cap, (update, MzOffset::minimum(), diff))
+                                    .await;
+                            }
+                        }
+                    }
+                    AsyncEvent::Progress(frontier) => {
+                        while let Some(entry) = pending.first_entry() {
+                            if frontier.less_equal(entry.key()) {
+                                break;
+                            }
+                            let (time, changes) = entry.remove_entry();
+                            trace!(%id, "timely-{worker_id} applying {} changes at {time}",
+                                   changes.len());
+                            apply_changes(&mut state, &key_indices, changes, &mut updates).await?;
+                            let cap = cap_set.delayed(&time);
+                            for (update, diff) in updates.drain(..) {
+                                output_handle.give(&cap, (update, time, diff)).await;
+                            }
+                        }
+                        cap_set.downgrade(frontier.iter());
+                    }
+                }
+            }
+            Ok::<_, TransientError>(())
+        })
+    });
+
+    (
+        output.as_collection(),
+        errors.map(ReplicationError::from),
+        Some(button.press_on_drop()),
+    )
+}
+
+/// Applies `changes` to `state` in the order they happened upstream and records the resulting
+/// updates in `updates`.
+async fn apply_changes<US: UpsertStateBackend<()>>(
+    state: &mut UpsertState<'_, US, ()>,
+    key_indices: &BTreeMap<usize, Vec<usize>>,
+    mut changes: Vec<(usize, UpsertChange<Row>)>,
+    updates: &mut Vec<((usize, Result<Row, SourceReaderError>), Diff)>,
+) -> Result<(), anyhow::Error> {
+    changes.sort_by_key(|(_, change)| change.seq());
+
+    // Read the current value of every key the changes refer to, so that they can be applied with
+    // a single `multi_get` and `multi_put`.
+    let mut values: IndexMap<UpsertKey, UpsertValueAndSize<()>> = IndexMap::new();
+    for (output_index, change) in &changes {
+        for row in change.key_rows() {
+            let key = upsert_key(*output_index, &key_indices[output_index], row);
+            values.entry(key).or_default();
+        }
+    }
+    let keys: Vec<_> = values.keys().copied().collect();
+    state.multi_get(keys, values.values_mut()).await?;
+    let bincode_opts = upsert_bincode_opts();
+    for value in values.values_mut() {
+        if let Some(value) = value.value.as_mut() {
+            value.ensure_decoded(bincode_opts);
+        }
+    }
+
+    for (output_index, change) in changes {
+        let output_key_indices = &key_indices[&output_index];
+        match change {
+            UpsertChange::Upsert {
+                prev,
+                mut row,
+                unchanged_toast,
+                ..
+            } => {
+                let key = upsert_key(output_index, output_key_indices, &row);
+                let prev_key = prev.map_or(key, |prev| {
+                    upsert_key(output_index, output_key_indices, &prev)
+                });
+                let old_row = take_row(&mut values[&prev_key]);
+                if !unchanged_toast.is_empty() {
+                    match &old_row {
+                        Some(old_row) => row = fill_unchanged(&row, old_row, &unchanged_toast),
+                        None => {
+                            let err = SourceReaderError::from(DefiniteError::MissingUpsertToast);
+                            updates.push(((output_index, Err(err)), 1));
+                            continue;
+                        }
+                    }
+                }
+                if let Some(old_row) = old_row {
+                    updates.push(((output_index, Ok(old_row)), -1));
+                }
+                if prev_key != key {
+                    if let Some(replaced) = take_row(&mut values[&key]) {
+                        updates.push(((output_index, Ok(replaced)), -1));
+                    }
+                }
+                updates.push(((output_index, Ok(row.clone())), 1));
+                values[&key].value = Some(StateValue::value(Ok(row), ()));
+            }
+            UpsertChange::Delete { key, .. } => {
+                let key = upsert_key(output_index, output_key_indices, &key);
+                if let Some(old_row) = take_row(&mut values[&key]) {
+                    updates.push(((output_index, Ok(old_row)), -1));
+                }
+            }
+        }
+    }
+
+    state
+        .multi_put(values.into_iter().map(|(key, value)| {
+            (
+                key,
+                PutValue {
+                    value: value.value.map(|value| value.into_decoded()),
+                    previous_value_metadata: value.metadata.map(|metadata| ValueMetadata {
+                        size: metadata.size.try_into().expect("less than i64 size"),
+                        is_tombstone: metadata.is_tombstone,
+                    }),
+                },
+            )
+        }))
+        .await
+}
+
+/// The key of `row` in the state, which includes the output index as the state is shared by all
+/// outputs.
+fn upsert_key(output_index: usize, key_indices: &[usize], row: &Row) -> UpsertKey {
+    let datums = row.unpack();
+    let key = std::iter::once(Datum::from(u64::cast_from(output_index)))
+        .chain(key_indices.iter().map(|idx| datums[*idx]));
+    UpsertKey::from_iter(Ok(key))
+}
+
+/// Removes the row stored in `value`, if any, leaving its key deleted.
+fn take_row(value: &mut UpsertValueAndSize<()>) -> Option<Row> {
+    match value.value.take()?.into_decoded() {
+        Value::Value(Ok(row), ()) => Some(row),
+        _ => None,
+    }
+}
+
+/// Replaces the columns of `row` at the `unchanged` positions with the ones of `old_row`.
+fn fill_unchanged(row: &Row, old_row: &Row, unchanged: &[usize]) -> Row {
+    let mut filled = Row::default();
+    filled.packer().extend(
+        row.iter()
+            .zip(old_row.iter())
+            .enumerate()
+            .map(|(idx, (new, old))| if unchanged.contains(&idx) { old } else { new }),
+    );
+    filled
+}
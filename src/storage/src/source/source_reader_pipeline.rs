@@ -77,6 +77,7 @@ use crate::metrics::StorageMetrics;
 use crate::source::reclock::{ReclockBatch, ReclockFollower, ReclockOperator};
 use crate::source::types::{SourceMessage, SourceOutput, SourceReaderError, SourceRender};
 use crate::statistics::SourceStatistics;
+use crate::storage_state::StorageInstanceContext;
 
 /// Shared configuration information for all source types. This is used in the
 /// `create_raw_source` functions, which produce raw sources.
@@ -123,6 +124,10 @@ pub struct RawSourceCreationConfig {
     pub config: StorageConfiguration,
     /// The ID of this source remap/progress collection.
     pub remap_collection_id: GlobalId,
+    /// Context about the storage instance this source is running on, e.g. its scratch directory.
+    pub instance_context: StorageInstanceContext,
+    /// The tuning configuration of the RocksDB instances that sources keep state in.
+    pub rocksdb_tuning_config: mz_rocksdb::RocksDBConfig,
 }
 
 impl RawSourceCreationConfig {
@@ -446,6 +451,8 @@ where
         shared_remap_upper,
         config: _,
         remap_collection_id,
+        instance_context: _,
+        rocksdb_tuning_config: _,
     } = config;
 
     let chosen_worker = usize::cast_from(id.hashed() % u64::cast_from(worker_count));
@@ -605,6 +612,8 @@ where
         shared_remap_upper: _,
         config: _,
         remap_collection_id: _,
+        instance_context: _,
+        rocksdb_tuning_config: _,
     } = config;
 
     // TODO(guswynn): expose function
@@ -0,0 +1,300 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of a [`SqlServerSourceConnection`].
+//!
+//! This dataflow is split into Snapshot and Replication operators, following
+//! the same structure as the MySQL source.
+//!
+//! # Snapshot
+//!
+//! The snapshot operator is responsible for taking a consistent snapshot of
+//! the tables involved in the ingestion. Each table that needs to be
+//! snapshotted is assigned to a specific worker, which reads all of its rows
+//! in a `SNAPSHOT` isolation transaction.
+//!
+//! For all tables that are snapshotted the snapshot operator also emits a
+//! rewind request to the replication operator containing the LSN at which the
+//! snapshot was taken, which is used to subtract the overlapping portion of
+//! the change data from the snapshot.
+//!
+//! See the [snapshot] module for more information.
+//!
+//! # Replication
+//!
+//! The replication operator is responsible for reading the change tables that
+//! SQL Server's change data capture (CDC) populates, which happens from a
+//! single worker.
+//!
+//! See the [replication] module for more information.
+//!
+//! # Error handling
+//!
+//! As with the MySQL source, [`DefiniteError`]s are errors that are specific
+//! to the data of a collection and end up in its error collection, whereas
+//! [`TransientError`]s are any other errors, e.g. connection failures, which
+//! are reported to the source status and restart the dataflow.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::rc::Rc;
+
+use differential_dataflow::Collection;
+use serde::{Deserialize, Serialize};
+use timely::dataflow::channels::pushers::Tee;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use mz_ore::error::ErrorExt;
+use mz_repr::{Diff, Row};
+use mz_sql_server_util::{Lsn, SqlServerError, SqlServerTableDesc};
+use mz_storage_types::errors::SourceErrorDetails;
+use mz_storage_types::sources::{SourceExport, SourceTimestamp, SqlServerSourceConnection};
+use mz_timely_util::builder_async::{AsyncOutputHandle, PressOnDropButton};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
+use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+
+mod replication;
+mod snapshot;
+
+impl SourceRender for SqlServerSourceConnection {
+    type Time = Lsn;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::SqlServer;
+
+    /// Render the ingestion dataflow. This function only connects things together and contains no
+    /// actual processing logic.
+    fn render<G: Scope<Timestamp = Lsn>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        _resume_uppers: impl futures::Stream<Item = Antichain<Lsn>> + 'static,
+        _start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        // Determine which collections need to be snapshot and which already have been.
+        let subsource_resume_uppers: BTreeMap<_, _> = config
+            .source_resume_uppers
+            .iter()
+            .map(|(id, upper)| {
+                assert!(
+                    config.source_exports.contains_key(id),
+                    "all source resume uppers must be present in source exports"
+                );
+
+                (*id, Antichain::from_iter(upper.iter().map(Lsn::decode_row)))
+            })
+            .collect();
+
+        // Collect the tables that we will be ingesting.
+        let mut table_info = BTreeMap::new();
+        for (_id, SourceExport { output_index, .. }) in &config.source_exports {
+            // Output index 0 is the primary source which is not a table.
+            if *output_index == 0 {
+                continue;
+            }
+
+            let desc = &self.details.tables[output_index - 1];
+            table_info.insert(
+                SqlServerTableName::new(&desc.schema_name, &desc.name),
+                (*output_index, desc.clone()),
+            );
+        }
+
+        let (snapshot_updates, rewinds, snapshot_stats, snapshot_err, snapshot_token) =
+            snapshot::render(
+                scope.clone(),
+                config.clone(),
+                self.clone(),
+                subsource_resume_uppers.clone(),
+                table_info.clone(),
+            );
+
+        let (repl_updates, uppers, repl_err, repl_token) = replication::render(
+            scope.clone(),
+            config,
+            self,
+            subsource_resume_uppers,
+            table_info,
+            &rewinds,
+        );
+
+        let updates = snapshot_updates.concat(&repl_updates).map(|(output, res)| {
+            let res = res.map(|row| SourceMessage {
+                key: Row::default(),
+                value: row,
+                metadata: Row::default(),
+            });
+            (output, res)
+        });
+
+        let health_init = std::iter::once(HealthStatusMessage {
+            index: 0,
+            namespace: Self::STATUS_NAMESPACE,
+            update: HealthStatusUpdate::Running,
+        })
+        .to_stream(scope);
+
+        let health_errs = snapshot_err.concat(&repl_err).map(move |err| {
+            // This update will cause the dataflow to restart
+            let err_string = err.display_with_causes().to_string();
+            let update = HealthStatusUpdate::halting(err_string.clone(), None);
+
+            let namespace = match err {
+                ReplicationError::Transient(err)
+                    if matches!(&*err, TransientError::SqlServer(SqlServerError::Ssh(_))) =>
+                {
+                    StatusNamespace::Ssh
+                }
+                _ => Self::STATUS_NAMESPACE,
+            };
+
+            HealthStatusMessage {
+                index: 0,
+                namespace: namespace.clone(),
+                update,
+            }
+        });
+        let health = health_init.concat(&health_errs);
+
+        (
+            updates,
+            Some(uppers),
+            health,
+            snapshot_stats,
+            vec![snapshot_token, repl_token],
+        )
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ReplicationError {
+    #[error(transparent)]
+    Transient(#[from] Rc<TransientError>),
+    #[error(transparent)]
+    Definite(#[from] Rc<DefiniteError>),
+}
+
+/// A transient error that never ends up in the collection of a specific table.
+#[derive(Debug, thiserror::Error)]
+pub enum TransientError {
+    #[error(transparent)]
+    SqlServer(#[from] SqlServerError),
+    #[error(transparent)]
+    Tiberius(#[from] tiberius::error::Error),
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+/// A definite error that always ends up in the collection of a specific table.
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+pub enum DefiniteError {
+    #[error("unable to decode: {0}")]
+    ValueDecodeError(String),
+    #[error("table was dropped or change data capture was disabled: {0}")]
+    TableDropped(String),
+    #[error("sql server change data is no longer available: {0}")]
+    CdcDataUnavailable(String),
+    #[error("sql server configuration error: {0}")]
+    ServerConfigurationError(String),
+}
+
+impl From<DefiniteError> for SourceReaderError {
+    fn from(err: DefiniteError) -> Self {
+        SourceReaderError {
+            inner: SourceErrorDetails::Other(err.to_string()),
+        }
+    }
+}
+
+/// A reference to a SQL Server table. (schema_name, table_name)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
+pub(crate) struct SqlServerTableName(pub(crate) String, pub(crate) String);
+
+impl SqlServerTableName {
+    pub(crate) fn new(schema_name: &str, table_name: &str) -> Self {
+        Self(schema_name.to_string(), table_name.to_string())
+    }
+}
+
+impl fmt::Display for SqlServerTableName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}",
+            mz_sql_server_util::quote_identifier(&self.0),
+            mz_sql_server_util::quote_identifier(&self.1)
+        )
+    }
+}
+
+impl From<&SqlServerTableDesc> for SqlServerTableName {
+    fn from(desc: &SqlServerTableDesc) -> Self {
+        Self::new(&desc.schema_name, &desc.name)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct RewindRequest {
+    /// The table that should be rewound.
+    pub(crate) table: SqlServerTableName,
+    /// The LSN at which the snapshot of the table was taken. All changes with
+    /// an LSN before it have been committed by the snapshot operator at the
+    /// minimum timestamp.
+    pub(crate) snapshot_lsn: Lsn,
+}
+
+async fn return_definite_error(
+    err: DefiniteError,
+    outputs: &[usize],
+    data_handle: &mut AsyncOutputHandle<
+        Lsn,
+        Vec<((usize, Result<Row, DefiniteError>), Lsn, i64)>,
+        Tee<Lsn, Vec<((usize, Result<Row, DefiniteError>), Lsn, i64)>>,
+    >,
+    data_cap_set: &CapabilitySet<Lsn>,
+    definite_error_handle: &mut AsyncOutputHandle<
+        Lsn,
+        Vec<ReplicationError>,
+        Tee<Lsn, Vec<ReplicationError>>,
+    >,
+    definite_error_cap_set: &CapabilitySet<Lsn>,
+) -> () {
+    for output_index in outputs {
+        let update = ((*output_index, Err(err.clone())), Lsn::MAX, 1);
+        data_handle.give(&data_cap_set[0], update).await;
+    }
+    definite_error_handle
+        .give(
+            &definite_error_cap_set[0],
+            ReplicationError::Definite(Rc::new(err)),
+        )
+        .await;
+    ()
+}
+
+/// Verifies that the database is configured for change data capture and
+/// allows `SNAPSHOT` isolation.
+async fn validate_cdc_settings(
+    client: &mut mz_sql_server_util::SqlServerConn,
+) -> Result<(), SqlServerError> {
+    mz_sql_server_util::ensure_cdc_enabled(client).await?;
+    mz_sql_server_util::ensure_snapshot_isolation_enabled(client).await?;
+
+    Ok(())
+}
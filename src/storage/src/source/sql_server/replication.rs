@@ -0,0 +1,325 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the replication side of the [`SqlServerSourceConnection`] ingestion dataflow.
+//!
+//! # Reading change data
+//!
+//! SQL Server's capture job copies the changes made to every CDC-enabled table
+//! into a change table belonging to the table's capture instance. Each change
+//! is tagged with the commit LSN of its transaction, which we use as the
+//! timestamp of the change.
+//!
+//! The replication operator periodically asks SQL Server for the largest LSN
+//! the capture job has processed and reads the changes of each table up to and
+//! including that LSN. Since the capture job processes the log in order, no
+//! change with a smaller LSN can show up afterwards, so the operator's
+//! frontier is advanced just beyond it.
+//!
+//! # Resumption
+//!
+//! The operator resumes reading at the minimum frontier across all
+//! subsources, or at the `initial_lsn` recorded during purification if any
+//! subsource has yet to be snapshotted. If the CDC cleanup job has already
+//! removed changes at the resumption point the source can no longer make
+//! progress and is put in an errored state.
+//!
+//! # Rewinds
+//!
+//! As with the MySQL source, changes for a table that happened before its
+//! snapshot was taken are negated at the minimum timestamp, in addition to
+//! being emitted at their LSN.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::time::Duration;
+
+use differential_dataflow::{AsCollection, Collection};
+use itertools::Itertools;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concat, Map};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::trace;
+
+use mz_ore::cast::CastFrom;
+use mz_ore::future::InTask;
+use mz_ore::result::ResultExt;
+use mz_repr::{Diff, GlobalId, Row};
+use mz_sql_server_util::{
+    get_changes, get_max_lsn, get_min_lsn, pack_sql_server_row, Lsn, SqlServerError,
+    SqlServerTableDesc,
+};
+use mz_storage_types::sources::SqlServerSourceConnection;
+use mz_timely_util::builder_async::{
+    Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+
+use crate::source::types::SourceReaderError;
+use crate::source::RawSourceCreationConfig;
+
+use super::{
+    return_definite_error, validate_cdc_settings, DefiniteError, ReplicationError, RewindRequest,
+    SqlServerTableName, TransientError,
+};
+
+/// Used as a partition id to determine if the worker is
+/// responsible for reading the change tables.
+static REPL_READER: &str = "reader";
+
+/// How long to wait before polling SQL Server for new changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Renders the replication dataflow. See the module documentation for more
+/// information.
+pub(crate) fn render<G: Scope<Timestamp = Lsn>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: SqlServerSourceConnection,
+    subsource_resume_uppers: BTreeMap<GlobalId, Antichain<Lsn>>,
+    table_info: BTreeMap<SqlServerTableName, (usize, SqlServerTableDesc)>,
+    rewind_stream: &Stream<G, RewindRequest>,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, Infallible>,
+    Stream<G, ReplicationError>,
+    PressOnDropButton,
+) {
+    let op_name = format!("SqlServerReplicationReader({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let repl_reader_id = u64::cast_from(config.responsible_worker(REPL_READER));
+    let (mut data_output, data_stream) = builder.new_output();
+    let (upper_output, upper_stream) = builder.new_output();
+    // Captures DefiniteErrors that affect the entire source, including all subsources
+    let (mut definite_error_handle, definite_errors) = builder.new_output();
+    let mut rewind_input = builder.new_input_for_many(
+        rewind_stream,
+        Exchange::new(move |_| repl_reader_id),
+        [&data_output, &upper_output],
+    );
+
+    let output_indexes = table_info
+        .values()
+        .map(|(output_index, _)| *output_index)
+        .collect_vec();
+
+    let (button, transient_errors) = builder.build_fallible(move |caps| {
+        Box::pin(async move {
+            let (id, worker_id) = (config.id, config.worker_id);
+            let [data_cap_set, upper_cap_set, definite_error_cap_set]: &mut [_; 3] =
+                caps.try_into().unwrap();
+
+            // Only run the replication reader on the worker responsible for it.
+            if !config.responsible_for(REPL_READER) {
+                return Ok(());
+            }
+
+            let connection_config = connection
+                .connection
+                .config(
+                    &config.config.connection_context.secrets_reader,
+                    &config.config,
+                    InTask::Yes,
+                )
+                .await?;
+
+            let mut conn = connection_config
+                .connect(
+                    &format!("timely-{worker_id} SQL Server replication reader"),
+                    &config.config.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+
+            match validate_cdc_settings(&mut conn).await {
+                Err(err @ SqlServerError::InvalidSystemSetting { .. }) => {
+                    return Ok(return_definite_error(
+                        DefiniteError::ServerConfigurationError(err.to_string()),
+                        &output_indexes,
+                        &mut data_output,
+                        data_cap_set,
+                        &mut definite_error_handle,
+                        definite_error_cap_set,
+                    )
+                    .await);
+                }
+                Err(err) => Err(err)?,
+                Ok(()) => (),
+            };
+
+            // Calculate the lowest frontier across all subsources, which represents the point which
+            // we should start replication from.
+            let resume_lsn =
+                match Antichain::from_iter(subsource_resume_uppers.into_values().flatten())
+                    .into_option()
+                {
+                    // If any subsource is at the minimum frontier then we are
+                    // either starting from scratch or at least one table has to
+                    // complete its initial snapshot. In either case we resume
+                    // from the LSN recorded during purification so that the
+                    // effective snapshot point is consistent across all tables.
+                    Some(lsn) if lsn == Lsn::minimum() => connection.details.initial_lsn,
+                    Some(lsn) => lsn,
+                    // All subsources have been dropped or are complete.
+                    None => return Ok(()),
+                };
+
+            data_cap_set.downgrade([&resume_lsn]);
+            upper_cap_set.downgrade([&resume_lsn]);
+            trace!(%id, "timely-{worker_id} replication reader started at {resume_lsn}");
+
+            let mut rewinds = BTreeMap::new();
+            while let Some(event) = rewind_input.next().await {
+                if let AsyncEvent::Data(caps, data) = event {
+                    for req in data {
+                        // Check that we will be reading changes from the snapshot point or before.
+                        if req.snapshot_lsn < resume_lsn {
+                            let err = DefiniteError::CdcDataUnavailable(format!(
+                                "snapshot of {} taken at {} is before the resume point {}",
+                                req.table, req.snapshot_lsn, resume_lsn
+                            ));
+                            return Ok(return_definite_error(
+                                err,
+                                &output_indexes,
+                                &mut data_output,
+                                data_cap_set,
+                                &mut definite_error_handle,
+                                definite_error_cap_set,
+                            )
+                            .await);
+                        }
+                        // If the snapshot point is the same as the resume point then we don't need to rewind
+                        if req.snapshot_lsn != resume_lsn {
+                            rewinds.insert(req.table.clone(), (caps.clone(), req));
+                        }
+                    }
+                }
+            }
+            trace!(%id, "timely-{worker_id} pending rewinds {rewinds:?}");
+
+            let mut errored_tables = BTreeSet::new();
+            let mut next_lsn = resume_lsn;
+            let mut final_row = Row::default();
+            loop {
+                let max_lsn = match get_max_lsn(&mut conn).await? {
+                    Some(max_lsn) if next_lsn <= max_lsn => max_lsn,
+                    // The capture job has not processed any new changes.
+                    _ => {
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                        continue;
+                    }
+                };
+
+                for (table, (output_index, table_desc)) in &table_info {
+                    if errored_tables.contains(table) {
+                        continue;
+                    }
+
+                    // The range of changes we can read is bounded by the
+                    // cleanup job, which removes old changes, and disappears
+                    // entirely if CDC is disabled for the table.
+                    match get_min_lsn(&mut conn, &table_desc.capture_instance).await {
+                        Ok(min_lsn) if min_lsn <= next_lsn => (),
+                        Ok(min_lsn) => {
+                            let err = SqlServerError::CdcDataUnavailable {
+                                capture_instance: table_desc.capture_instance.clone(),
+                                requested: next_lsn,
+                                available: min_lsn,
+                            };
+                            let err = DefiniteError::CdcDataUnavailable(err.to_string());
+                            return Ok(return_definite_error(
+                                err,
+                                &output_indexes,
+                                &mut data_output,
+                                data_cap_set,
+                                &mut definite_error_handle,
+                                definite_error_cap_set,
+                            )
+                            .await);
+                        }
+                        Err(err @ SqlServerError::CaptureInstanceMissing { .. }) => {
+                            // Publish the error for this table and stop ingesting it
+                            let update = (
+                                (
+                                    *output_index,
+                                    Err(DefiniteError::TableDropped(err.to_string())),
+                                ),
+                                next_lsn,
+                                1,
+                            );
+                            data_output.give(&data_cap_set[0], update).await;
+                            errored_tables.insert(table.clone());
+                            continue;
+                        }
+                        Err(err) => Err(err)?,
+                    }
+
+                    let changes = get_changes(&mut conn, table_desc, next_lsn, max_lsn).await?;
+                    let mut rewind_count = 0;
+                    for change in changes {
+                        let diff = change.operation.diff();
+                        let event =
+                            match pack_sql_server_row(&mut final_row, change.values, table_desc) {
+                                Ok(row) => Ok(row),
+                                // Produce a DefiniteError in the stream for any rows that fail to decode
+                                Err(err @ SqlServerError::ValueDecodeError { .. }) => {
+                                    Err(DefiniteError::ValueDecodeError(err.to_string()))
+                                }
+                                Err(err) => Err(err)?,
+                            };
+                        let data = (*output_index, event);
+
+                        // Rewind this update if it was already present in the snapshot
+                        if let Some(([data_cap, _upper_cap], rewind_req)) = rewinds.get(table) {
+                            if change.lsn < rewind_req.snapshot_lsn {
+                                rewind_count += 1;
+                                data_output
+                                    .give(data_cap, (data.clone(), Lsn::minimum(), -diff))
+                                    .await;
+                            }
+                        }
+                        let cap = data_cap_set.delayed(&change.lsn);
+                        data_output.give(&cap, (data, change.lsn, diff)).await;
+                    }
+                    trace!(%id, "timely-{worker_id} read changes for {table} up to {max_lsn} \
+                                 with {rewind_count} rewinds");
+                }
+
+                // The capture job has processed the log up to `max_lsn`, so
+                // we will never see changes before the next LSN.
+                next_lsn = max_lsn.increment();
+                trace!(%id, "timely-{worker_id} advancing frontier to {next_lsn}");
+                data_cap_set.downgrade([&next_lsn]);
+                upper_cap_set.downgrade([&next_lsn]);
+                rewinds.retain(|_, (_, req)| {
+                    let res = next_lsn < req.snapshot_lsn;
+                    if !res {
+                        trace!(%id, "timely-{worker_id} removing rewind request {req:?}");
+                    }
+                    res
+                });
+
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+    });
+
+    let replication_updates = data_stream
+        .as_collection()
+        .map(|(output_index, row)| (output_index, row.err_into()));
+
+    let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
+
+    (
+        replication_updates,
+        upper_stream,
+        errors,
+        button.press_on_drop(),
+    )
+}
@@ -0,0 +1,350 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the table snapshot side of the [`SqlServerSourceConnection`] dataflow.
+//!
+//! # Snapshot reading
+//!
+//! As with MySQL, all tables must be snapshotted at a consistent point in
+//! time, which is discovered during planning and exposed to this dataflow as
+//! the `initial_lsn` field of `SqlServerSourceDetails`. SQL Server does not
+//! let us run a transaction at a specific LSN either, so we take the snapshot
+//! at an LSN we can determine and rewind it to `initial_lsn` afterwards.
+//!
+//! ## Producing a snapshot at a known LSN
+//!
+//! Each worker opens a connection and, within a transaction, takes a shared
+//! table lock on each table it is responsible for. While the locks are held
+//! no transaction can modify the tables, so the end of the transaction log
+//! (`snapshot_lsn`) is strictly greater than the commit LSN of every change
+//! that is visible in the tables.
+//!
+//! The worker then opens a second connection and starts a `SNAPSHOT`
+//! isolation transaction, reading from every table once to fix the point in
+//! time the transaction observes. Since the tables are locked, we can pretend
+//! this transaction observes the tables exactly as of `snapshot_lsn`. The
+//! locks are then released and the worker reads the tables in full.
+//!
+//! ## Rewinding the snapshot
+//!
+//! The snapshot contains all changes with an LSN before `snapshot_lsn` while
+//! we want one containing all changes before `initial_lsn`. Since the
+//! replication operator reads all changes starting at `initial_lsn` we ask it,
+//! via a [`RewindRequest`], to negate all changes with an LSN before
+//! `snapshot_lsn` at the minimum timestamp.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+use differential_dataflow::{AsCollection, Collection};
+use futures::TryStreamExt;
+use itertools::Itertools;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::trace;
+
+use mz_ore::future::InTask;
+use mz_ore::result::ResultExt;
+use mz_repr::{Diff, GlobalId, Row};
+use mz_sql_server_util::{
+    get_log_end_lsn, pack_sql_server_row, quote_identifier, Lsn, SqlServerError, SqlServerTableDesc,
+};
+use mz_storage_types::sources::SqlServerSourceConnection;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+
+use crate::source::types::ProgressStatisticsUpdate;
+use crate::source::{RawSourceCreationConfig, SourceReaderError};
+
+use super::{
+    return_definite_error, validate_cdc_settings, DefiniteError, ReplicationError, RewindRequest,
+    SqlServerTableName, TransientError,
+};
+
+/// Renders the snapshot dataflow. See the module documentation for more information.
+pub(crate) fn render<G: Scope<Timestamp = Lsn>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: SqlServerSourceConnection,
+    subsource_resume_uppers: BTreeMap<GlobalId, Antichain<Lsn>>,
+    table_info: BTreeMap<SqlServerTableName, (usize, SqlServerTableDesc)>,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, RewindRequest>,
+    Stream<G, ProgressStatisticsUpdate>,
+    Stream<G, ReplicationError>,
+    PressOnDropButton,
+) {
+    let mut builder = AsyncOperatorBuilder::new(
+        format!("SqlServerSnapshotReader({})", config.id),
+        scope.clone(),
+    );
+
+    let (mut raw_handle, raw_data) = builder.new_output();
+    let (mut rewinds_handle, rewinds) = builder.new_output();
+    // Captures DefiniteErrors that affect the entire source, including all subsources
+    let (mut definite_error_handle, definite_errors) = builder.new_output();
+
+    let (mut stats_output, stats_stream) = builder.new_output();
+
+    // A global view of all exports that need to be snapshot by all workers.
+    let export_indexes_to_snapshot: BTreeSet<_> = subsource_resume_uppers
+        .into_iter()
+        .filter_map(|(id, upper)| {
+            // Determined which collections need to be snapshot and which already have been.
+            if id != config.id && *upper == [Lsn::minimum()] {
+                // Convert from `GlobalId` to output index.
+                Some(config.source_exports[&id].output_index)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let mut all_outputs = vec![];
+    // A map containing only the table infos that this worker should snapshot.
+    let mut reader_snapshot_table_info = BTreeMap::new();
+
+    for (table, val) in table_info {
+        mz_ore::soft_assert_or_log!(
+            val.0 != 0,
+            "primary collection should not be represented in table info"
+        );
+        if !export_indexes_to_snapshot.contains(&val.0) {
+            continue;
+        }
+        all_outputs.push(val.0);
+        if config.responsible_for(&table) {
+            reader_snapshot_table_info.insert(table, val);
+        }
+    }
+
+    let (button, transient_errors): (_, Stream<G, Rc<TransientError>>) =
+        builder.build_fallible(move |caps| {
+            Box::pin(async move {
+                let [data_cap_set, rewind_cap_set, definite_error_cap_set, stats_cap]: &mut [_; 4] =
+                    caps.try_into().unwrap();
+
+                let id = config.id;
+                let worker_id = config.worker_id;
+
+                // If this worker has no tables to snapshot then there is nothing to do.
+                if reader_snapshot_table_info.is_empty() {
+                    trace!(%id, "timely-{worker_id} initializing table reader \
+                                 with no tables to snapshot, exiting");
+                    if !export_indexes_to_snapshot.is_empty() {
+                        // Emit 0, to mark this worker as having started up correctly,
+                        // but having done no snapshotting.
+                        stats_output
+                            .give(
+                                &stats_cap[0],
+                                ProgressStatisticsUpdate::Snapshot {
+                                    records_known: 0,
+                                    records_staged: 0,
+                                },
+                            )
+                            .await;
+                    }
+                    return Ok(());
+                } else {
+                    trace!(%id, "timely-{worker_id} initializing table reader \
+                                 with {} tables to snapshot",
+                           reader_snapshot_table_info.len());
+                }
+
+                let connection_config = connection
+                    .connection
+                    .config(
+                        &config.config.connection_context.secrets_reader,
+                        &config.config,
+                        InTask::Yes,
+                    )
+                    .await?;
+                let task_name = format!("timely-{worker_id} SQL Server snapshotter");
+
+                let mut lock_conn = connection_config
+                    .connect(
+                        &task_name,
+                        &config.config.connection_context.ssh_tunnel_manager,
+                    )
+                    .await?;
+
+                // Verify the database is still configured for CDC.
+                match validate_cdc_settings(&mut lock_conn).await {
+                    Err(err @ SqlServerError::InvalidSystemSetting { .. }) => {
+                        return Ok(return_definite_error(
+                            DefiniteError::ServerConfigurationError(err.to_string()),
+                            &all_outputs,
+                            &mut raw_handle,
+                            data_cap_set,
+                            &mut definite_error_handle,
+                            definite_error_cap_set,
+                        )
+                        .await);
+                    }
+                    Err(err) => Err(err)?,
+                    Ok(()) => (),
+                };
+
+                // Take shared table locks, which are held until the end of
+                // the transaction, to prevent writes to the tables.
+                let lock_statements = reader_snapshot_table_info
+                    .keys()
+                    .map(|t| format!("SELECT TOP 1 1 FROM {t} WITH (TABLOCK, HOLDLOCK);"))
+                    .join(" ");
+                trace!(%id, "timely-{worker_id} acquiring table locks: {lock_statements}");
+                lock_conn
+                    .simple_query(format!("BEGIN TRANSACTION; {lock_statements}"))
+                    .await?
+                    .into_results()
+                    .await?;
+
+                let snapshot_lsn = get_log_end_lsn(&mut lock_conn).await?;
+                trace!(%id, "timely-{worker_id} acquired table locks at: {snapshot_lsn}");
+
+                let mut conn = connection_config
+                    .connect(
+                        &task_name,
+                        &config.config.connection_context.ssh_tunnel_manager,
+                    )
+                    .await?;
+
+                // A `SNAPSHOT` transaction observes the database as of the
+                // first time it reads data, so we read from every table before
+                // releasing the locks.
+                let snapshot_statements = reader_snapshot_table_info
+                    .keys()
+                    .map(|t| format!("SELECT TOP 1 1 FROM {t};"))
+                    .join(" ");
+                conn.simple_query(format!(
+                    "SET TRANSACTION ISOLATION LEVEL SNAPSHOT; \
+                     BEGIN TRANSACTION; {snapshot_statements}"
+                ))
+                .await?
+                .into_results()
+                .await?;
+
+                // We have started our transaction so we can release the locks.
+                lock_conn
+                    .simple_query("ROLLBACK")
+                    .await?
+                    .into_results()
+                    .await?;
+                lock_conn.close().await?;
+
+                trace!(%id, "timely-{worker_id} started transaction");
+
+                let mut snapshot_total = 0;
+                for table in reader_snapshot_table_info.keys() {
+                    let row = conn
+                        .simple_query(format!("SELECT COUNT_BIG(*) FROM {table}"))
+                        .await?
+                        .into_row()
+                        .await?;
+                    let count: Option<i64> = match &row {
+                        Some(row) => row.try_get(0)?,
+                        None => None,
+                    };
+                    snapshot_total += u64::try_from(count.unwrap_or(0)).unwrap_or(0);
+                }
+
+                stats_output
+                    .give(
+                        &stats_cap[0],
+                        ProgressStatisticsUpdate::Snapshot {
+                            records_known: snapshot_total,
+                            records_staged: 0,
+                        },
+                    )
+                    .await;
+
+                // We have established a snapshot LSN so we can broadcast the rewind requests
+                for table in reader_snapshot_table_info.keys() {
+                    trace!(%id, "timely-{worker_id} producing rewind request for {table}");
+                    let req = RewindRequest {
+                        table: table.clone(),
+                        snapshot_lsn,
+                    };
+                    rewinds_handle.give(&rewind_cap_set[0], req).await;
+                }
+                *rewind_cap_set = CapabilitySet::new();
+
+                // Read the snapshot data from the tables
+                let mut final_row = Row::default();
+
+                let mut snapshot_staged = 0;
+                for (table, (output_index, table_desc)) in reader_snapshot_table_info {
+                    let columns = table_desc
+                        .columns
+                        .iter()
+                        .map(|c| quote_identifier(&c.name))
+                        .join(", ");
+                    let query = format!("SELECT {columns} FROM {table}");
+                    trace!(%id, "timely-{worker_id} reading snapshot from \
+                                 table '{table}':\n{table_desc:?}");
+                    let mut results = conn.simple_query(query).await?.into_row_stream();
+                    let mut count = 0;
+                    while let Some(row) = results.try_next().await? {
+                        let event = match pack_sql_server_row(&mut final_row, row, &table_desc) {
+                            Ok(row) => Ok(row),
+                            // Produce a DefiniteError in the stream for any rows that fail to decode
+                            Err(err @ SqlServerError::ValueDecodeError { .. }) => {
+                                Err(DefiniteError::ValueDecodeError(err.to_string()))
+                            }
+                            Err(err) => Err(err)?,
+                        };
+                        raw_handle
+                            .give(&data_cap_set[0], ((output_index, event), Lsn::minimum(), 1))
+                            .await;
+                        count += 1;
+                        snapshot_staged += 1;
+                        if snapshot_staged % 1000 == 0 {
+                            stats_output
+                                .give(
+                                    &stats_cap[0],
+                                    ProgressStatisticsUpdate::Snapshot {
+                                        records_known: snapshot_total,
+                                        records_staged: snapshot_staged,
+                                    },
+                                )
+                                .await;
+                        }
+                    }
+                    trace!(%id, "timely-{worker_id} snapshotted {count} records from \
+                                 table '{table}'");
+                }
+                conn.simple_query("COMMIT").await?.into_results().await?;
+
+                stats_output
+                    .give(
+                        &stats_cap[0],
+                        ProgressStatisticsUpdate::Snapshot {
+                            records_known: snapshot_total.max(snapshot_staged),
+                            records_staged: snapshot_staged,
+                        },
+                    )
+                    .await;
+                Ok(())
+            })
+        });
+
+    let snapshot_updates = raw_data
+        .as_collection()
+        .map(move |(output_index, event)| (output_index, event.err_into()));
+
+    let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
+
+    (
+        snapshot_updates,
+        rewinds,
+        stats_stream,
+        errors,
+        button.press_on_drop(),
+    )
+}
@@ -0,0 +1,275 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! An operator that retracts the contents of a subsource when its upstream table is truncated.
+//!
+//! Upstream systems like PostgreSQL and MySQL report a `TRUNCATE` as a single event that does not
+//! carry the rows it removed. For subsources configured with `RETRACT ON TRUNCATE` the source
+//! readers instead emit a truncate marker at the source timestamp of the truncation, and this
+//! operator is responsible for turning that marker into retractions of everything the subsource
+//! contained right before it.
+//!
+//! ```text
+//!   updates     truncate
+//!      │        markers
+//!      │           │
+//!   ┏━━v━━━━━━━━━━━v━┓
+//!   ┃   truncation   ┃ (exchanged by output index)
+//!   ┗━━━━━━━┯━━━━━━━━┛
+//!           │ updates and
+//!           │ retractions
+//!           v
+//! ```
+//!
+//! # State
+//!
+//! The operator maintains the current contents of every subsource it is responsible for in
+//! memory, which means that enabling `RETRACT ON TRUNCATE` for a table requires memory
+//! proportional to the size of the table. All updates are passed through immediately, but they
+//! are only applied to the state once the input frontier has advanced past their timestamp, so
+//! that the updates of a given timestamp can be applied after any truncation at that timestamp.
+//! Updates that happen in the same upstream transaction as, but before, a truncation are expected
+//! to already be retracted by the source reader.
+//!
+//! When the dataflow restarts the state is rehydrated from the subsource's persist shard as of its
+//! resume upper. Updates and markers at source timestamps that are not beyond the subsource's
+//! resume upper are already reflected in the shard and are therefore not applied to the state.
+//!
+//! # Consistency
+//!
+//! Snapshots are rewound to the minimum timestamp by retracting the replicated updates that
+//! happened before the snapshot was taken. The retractions produced by this operator are not part
+//! of that rewind, so if a table is truncated while its snapshot is in progress the contents of
+//! the subsource at timestamps between the minimum timestamp and the truncation may not
+//! correspond to any state of the upstream table. The contents at and after the truncation are
+//! always correct.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use differential_dataflow::{AsCollection, Collection};
+use mz_ore::cast::CastFrom;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Diff, Row};
+use mz_storage_types::sources::{SourceData, SourceTimestamp};
+use mz_timely_util::builder_async::{
+    Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{CapabilitySet, Concat, Map};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::trace;
+
+use crate::source::types::SourceReaderError;
+use crate::source::RawSourceCreationConfig;
+
+/// Renders the truncation operator for the outputs in `retract_on_truncate`. `truncates` contains
+/// a marker `(output_index, time)` for every truncation of an upstream table.
+///
+/// Updates for outputs that are not in `retract_on_truncate` bypass the operator.
+pub(crate) fn render<G: Scope>(
+    config: &RawSourceCreationConfig,
+    updates: &Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    truncates: &Stream<G, (usize, G::Timestamp)>,
+    retract_on_truncate: BTreeSet<usize>,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Option<PressOnDropButton>,
+)
+where
+    G::Timestamp: SourceTimestamp,
+{
+    if retract_on_truncate.is_empty() {
+        return (updates.clone(), None);
+    }
+
+    let mut builder =
+        AsyncOperatorBuilder::new(format!("TruncationHandler({})", config.id), updates.scope());
+
+    let truncatable = retract_on_truncate.clone();
+    let bypass = updates.filter(move |(output_index, _)| !truncatable.contains(output_index));
+
+    let truncatable = retract_on_truncate.clone();
+    let input = updates
+        .inner
+        .filter(move |((output_index, _), _, _)| truncatable.contains(output_index))
+        .map(|((output_index, event), time, diff)| ((output_index, Some(event)), time, diff))
+        .concat(&truncates.map(|(output_index, time)| ((output_index, None), time, 1)));
+
+    let (mut output_handle, output) = builder.new_output();
+
+    let router = config.clone();
+    let mut input = builder.new_input_for(
+        &input,
+        Exchange::new(move |((output_index, _), _, _): &((usize, _), _, _)| {
+            u64::cast_from(router.responsible_worker(*output_index))
+        }),
+        &output_handle,
+    );
+
+    // The outputs this worker is responsible for, along with the export that they correspond to.
+    let exports: BTreeMap<_, _> = config
+        .source_exports
+        .iter()
+        .filter(|(_, export)| {
+            retract_on_truncate.contains(&export.output_index)
+                && config.responsible_for(export.output_index)
+        })
+        .map(|(id, export)| (export.output_index, (*id, export.storage_metadata.clone())))
+        .collect();
+
+    let config = config.clone();
+    let button = builder.build(move |caps| async move {
+        let [cap]: [_; 1] = caps.try_into().unwrap();
+        let mut cap_set = CapabilitySet::from_elem(cap);
+
+        let (id, worker_id) = (config.id, config.worker_id);
+
+        // The upper of each output in the source timestamp domain. Updates that are not beyond
+        // it are already reflected in the persisted contents.
+        let mut source_uppers = BTreeMap::new();
+        // The contents of each output as of the latest processed timestamp.
+        let mut contents: BTreeMap<usize, BTreeMap<Row, Diff>> = BTreeMap::new();
+
+        for (output_index, (export_id, metadata)) in exports {
+            let source_upper: Antichain<G::Timestamp> = config
+                .source_resume_uppers
+                .get(&export_id)
+                .map(|upper| upper.iter().map(G::Timestamp::decode_row).collect())
+                .unwrap_or_else(|| Antichain::from_elem(G::Timestamp::minimum()));
+            source_uppers.insert(output_index, source_upper);
+
+            let output_contents = contents.entry(output_index).or_default();
+            let upper = match config.resume_uppers.get(&export_id) {
+                Some(upper) => upper,
+                None => continue,
+            };
+            let as_of = match upper.as_option().and_then(|ts| ts.step_back()) {
+                Some(as_of) => as_of,
+                // The output is either empty or will never receive updates again.
+                None => continue,
+            };
+
+            let persist_client = config
+                .persist_clients
+                .open(metadata.persist_location.clone())
+                .await
+                .expect("error creating persist client");
+            let mut read_handle = persist_client
+                .open_leased_reader::<SourceData, (), mz_repr::Timestamp, Diff>(
+                    metadata.data_shard,
+                    Arc::new(metadata.relation_desc.clone()),
+                    Arc::new(UnitSchema),
+                    Diagnostics {
+                        shard_name: export_id.to_string(),
+                        handle_purpose: format!("truncation state for {}", id),
+                    },
+                    false,
+                )
+                .await
+                .expect("shard unavailable");
+            let snapshot = read_handle
+                .snapshot_and_fetch(Antichain::from_elem(as_of))
+                .await
+                .expect("resume upper is beyond the since");
+            for ((key, _val), _ts, diff) in snapshot {
+                if let Ok(SourceData(Ok(row))) = key {
+                    *output_contents.entry(row).or_default() += diff;
+                }
+            }
+            output_contents.retain(|_, diff| *diff != 0);
+            read_handle.expire().await;
+            trace!(%id, "timely-{worker_id} rehydrated {} rows of output {output_index} \
+                         as of {as_of}", output_contents.len());
+        }
+
+        let is_replayed = |output_index: usize, time: &G::Timestamp| {
+            source_uppers
+                .get(&output_index)
+                .map_or(false, |upper| !upper.less_equal(time))
+        };
+
+        // Updates and truncations that are waiting for the frontier to advance past their time.
+        let mut pending: BTreeMap<G::Timestamp, (Vec<(usize, Row, Diff)>, BTreeSet<usize>)> =
+            BTreeMap::new();
+
+        while let Some(event) = input.next().await {
+            match event {
+                AsyncEvent::Data(cap, data) => {
+                    for ((output_index, event), time, diff) in data {
+                        if is_replayed(output_index, &time) {
+                            if let Some(event) = event {
+                                output_handle
+                                    .give(&cap, ((output_index, event), time, diff))
+                                    .await;
+                            }
+                            continue;
+                        }
+                        match event {
+                            Some(event) => {
+                                if let Ok(row) = &event {
+                                    let (updates, _) = pending.entry(time.clone()).or_default();
+                                    updates.push((output_index, row.clone(), diff));
+                                }
+                                output_handle
+                                    .give(&cap, ((output_index, event), time, diff))
+                                    .await;
+                            }
+                            None => {
+                                let (_, truncations) = pending.entry(time).or_default();
+                                truncations.insert(output_index);
+                            }
+                        }
+                    }
+                }
+                AsyncEvent::Progress(frontier) => {
+                    // Source timestamps can be partially ordered, so we can't stop at the first
+                    // time that is not yet complete.
+                    let ready: Vec<_> = pending
+                        .keys()
+                        .filter(|time| !frontier.less_equal(time))
+                        .cloned()
+                        .collect();
+                    for time in ready {
+                        let (updates, truncations) = pending.remove(&time).expect("known to exist");
+
+                        for output_index in truncations {
+                            let output_contents = contents.entry(output_index).or_default();
+                            trace!(%id, "timely-{worker_id} retracting {} rows of output \
+                                         {output_index} at {time}", output_contents.len());
+                            let cap = cap_set.delayed(&time);
+                            for (row, diff) in std::mem::take(output_contents) {
+                                output_handle
+                                    .give(&cap, ((output_index, Ok(row)), time.clone(), -diff))
+                                    .await;
+                            }
+                        }
+
+                        for (output_index, row, diff) in updates {
+                            let output_contents = contents.entry(output_index).or_default();
+                            let count = output_contents.entry(row.clone()).or_default();
+                            *count += diff;
+                            if *count == 0 {
+                                output_contents.remove(&row);
+                            }
+                        }
+                    }
+                    cap_set.downgrade(frontier.iter());
+                }
+            }
+        }
+    });
+
+    (
+        bypass.concat(&output.as_collection()),
+        Some(button.press_on_drop()),
+    )
+}
@@ -27,9 +27,10 @@ use mz_repr::{Diff, GlobalId, Row};
 use mz_service::local::Activatable;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sources::{
-    GenericSourceConnection, IngestionDescription, KafkaSourceConnection,
-    LoadGeneratorSourceConnection, MySqlSourceConnection, PostgresSourceConnection,
-    SourceConnection, SourceData, SourceEnvelope, SourceTimestamp,
+    FileSourceConnection, GenericSourceConnection, IngestionDescription, KafkaSourceConnection,
+    KinesisSourceConnection, LoadGeneratorSourceConnection, MySqlSourceConnection,
+    PostgresSourceConnection, SourceConnection, SourceData, SourceEnvelope, SourceTimestamp,
+    SqlServerSourceConnection,
 };
 use timely::order::PartialOrder;
 use timely::progress::{Antichain, Timestamp};
@@ -37,6 +38,7 @@ use tokio::sync::mpsc;
 
 use crate::source::reclock::{ReclockBatch, ReclockFollower};
 use crate::source::types::SourceRender;
+use crate::source::KafkaTopicPatternSource;
 
 /// A worker that can execute commands that come in on a channel and returns
 /// responses on another channel. This is useful in places where we can't
@@ -339,6 +341,17 @@ impl<T: Timestamp + Lattice + Codec64 + Display> AsyncStorageWorker<T> {
 
                         // Create a specialized description to be able to call the generic method
                         let source_resume_uppers = match ingestion_description.desc.connection {
+                            GenericSourceConnection::Kafka(ref c) if c.topic_is_pattern => {
+                                let uppers = reclock_resume_uppers::<KafkaTopicPatternSource, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
                             GenericSourceConnection::Kafka(_) => {
                                 let uppers = reclock_resume_uppers::<KafkaSourceConnection, _>(
                                     &id,
@@ -384,6 +397,39 @@ impl<T: Timestamp + Lattice + Codec64 + Display> AsyncStorageWorker<T> {
                                     .await;
                                 to_vec_row(uppers)
                             }
+                            GenericSourceConnection::File(_) => {
+                                let uppers = reclock_resume_uppers::<FileSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
+                            GenericSourceConnection::Kinesis(_) => {
+                                let uppers = reclock_resume_uppers::<KinesisSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
+                            GenericSourceConnection::SqlServer(_) => {
+                                let uppers = reclock_resume_uppers::<SqlServerSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
                         };
 
                         let res = response_tx.send(AsyncStorageWorkerResponse::FrontiersUpdated {
@@ -49,10 +49,10 @@ use autospill::{AutoSpillBackend, RocksDBParams};
 use memory::InMemoryHashMap;
 use types::{upsert_bincode_opts, StateValue, UpsertState, UpsertStateBackend, Value};
 
-mod autospill;
-mod memory;
-mod rocksdb;
-mod types;
+pub(crate) mod autospill;
+pub(crate) mod memory;
+pub(crate) mod rocksdb;
+pub(crate) mod types;
 
 pub type UpsertValue = Result<Row, UpsertError>;
 
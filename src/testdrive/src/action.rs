@@ -56,6 +56,7 @@ mod file;
 mod fivetran;
 mod http;
 mod kafka;
+mod kinesis;
 mod mysql;
 mod nop;
 mod persist;
@@ -722,6 +723,9 @@ impl Run for PosCommand {
                     "kafka-verify-data" => kafka::run_verify_data(builtin, state).await,
                     "kafka-verify-commit" => kafka::run_verify_commit(builtin, state).await,
                     "kafka-verify-topic" => kafka::run_verify_topic(builtin, state).await,
+                    "kinesis-create-stream" => kinesis::run_create_stream(builtin, state).await,
+                    "kinesis-ingest" => kinesis::run_ingest(builtin, state).await,
+                    "kinesis-update-shards" => kinesis::run_update_shards(builtin, state).await,
                     "mysql-connect" => mysql::run_connect(builtin, state).await,
                     "mysql-execute" => mysql::run_execute(builtin, state).await,
                     "nop" => nop::run_nop(),
@@ -16,6 +16,7 @@ use rdkafka::consumer::{Consumer, StreamConsumer};
 use rdkafka::error::KafkaError;
 use rdkafka::message::{Headers, Message};
 use rdkafka::types::RDKafkaErrorCode;
+use rdkafka::{Offset, TopicPartitionList};
 use regex::Regex;
 use tokio::pin;
 use tokio_stream::StreamExt;
@@ -100,6 +101,7 @@ pub async fn run_verify_data(
         bail!("kafka-verify-data requires a non-empty list of expected messages");
     }
     let partial_search = cmd.args.opt_parse("partial-search")?;
+    let partition: Option<i32> = cmd.args.opt_parse("partition")?;
     let debug_print_only = cmd.args.opt_bool("debug-print-only")?.unwrap_or(false);
     cmd.args.done()?;
 
@@ -114,9 +116,20 @@ pub async fn run_verify_data(
     config.set("enable.auto.offset.store", "false");
 
     let consumer: StreamConsumer = config.create().context("creating kafka consumer")?;
-    consumer
-        .subscribe(&[&topic])
-        .context("subscribing to kafka topic")?;
+    match partition {
+        // Only read the messages in the specified partition of the topic.
+        Some(partition) => {
+            let mut tpl = TopicPartitionList::new();
+            tpl.add_partition_offset(&topic, partition, Offset::Beginning)
+                .context("assigning kafka topic partition")?;
+            consumer
+                .assign(&tpl)
+                .context("assigning kafka topic partition")?;
+        }
+        None => consumer
+            .subscribe(&[&topic])
+            .context("subscribing to kafka topic")?,
+    }
 
     let (mut stream_messages_remaining, stream_timeout) = match partial_search {
         Some(size) => (size, state.default_timeout),
@@ -163,12 +176,17 @@ pub async fn run_verify_data(
                 let mut headers = vec![];
                 for header_key in &header_keys {
                     // Expect a unique header with the given key and a UTF8-formatted body.
-                    let hs = message.headers().context("expected headers for message")?;
-                    let mut hs = hs.iter().filter(|i| i.key == header_key);
-                    let h = hs.next();
-                    if hs.next().is_some() {
-                        bail!("expected at most one header with key {header_key}");
-                    }
+                    let h = match message.headers() {
+                        Some(hs) => {
+                            let mut hs = hs.iter().filter(|i| i.key == header_key);
+                            let h = hs.next();
+                            if hs.next().is_some() {
+                                bail!("expected at most one header with key {header_key}");
+                            }
+                            h
+                        }
+                        None => None,
+                    };
                     match h {
                         None => headers.push("<missing>".into()),
                         Some(h) => {
@@ -304,7 +322,10 @@ pub async fn run_verify_data(
                 let key = match record.key {
                     Some(bytes) => {
                         if *has_key {
-                            Some(serde_json::from_slice(&bytes).context("decoding json")?)
+                            Some(
+                                serde_json::from_slice(strip_confluent_header(&bytes))
+                                    .context("decoding json")?,
+                            )
                         } else {
                             None
                         }
@@ -313,7 +334,10 @@ pub async fn run_verify_data(
                 };
                 let value = match record.value {
                     None => None,
-                    Some(bytes) => Some(serde_json::from_slice(&bytes).context("decoding json")?),
+                    Some(bytes) => Some(
+                        serde_json::from_slice(strip_confluent_header(&bytes))
+                            .context("decoding json")?,
+                    ),
                 };
 
                 actual_messages.push(Record {
@@ -394,6 +418,18 @@ fn split_headers(input: &str, n_headers: usize) -> anyhow::Result<(Vec<String>,
     Ok((headers, rest))
 }
 
+/// Strips the Confluent wire format header, which JSON messages carry when
+/// their schema is published to a schema registry, from a message.
+///
+/// A JSON document never starts with a zero byte, so the header is
+/// unambiguous.
+fn strip_confluent_header(bytes: &[u8]) -> &[u8] {
+    match bytes {
+        [0, _, _, _, _, rest @ ..] => rest,
+        _ => bytes,
+    }
+}
+
 fn verify_with_partial_search<A>(
     expected: &[Record<A>],
     actual: &[Record<A>],
@@ -7,12 +7,45 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use anyhow::{bail, Context};
+use aws_sdk_kinesis::types::StreamStatus;
+use aws_sdk_kinesis::Client;
+use mz_ore::retry::Retry;
+
+use crate::action::State;
+
 mod create_stream;
 mod ingest;
 mod update_shards;
-mod verify;
 
 pub use create_stream::run_create_stream;
 pub use ingest::run_ingest;
 pub use update_shards::run_update_shards;
-pub use verify::run_verify;
+
+/// Returns the name of the Kinesis stream that testdrive uses for `stream`.
+fn stream_name(stream: &str, state: &State) -> String {
+    format!("testdrive-{}-{}", stream, state.seed)
+}
+
+/// Waits until `stream_name` is active, i.e. done being created or resharded.
+async fn wait_for_active(
+    client: &Client,
+    stream_name: &str,
+    state: &State,
+) -> Result<(), anyhow::Error> {
+    Retry::default()
+        .max_duration(state.default_timeout)
+        .retry_async_canceling(|_| async {
+            let res = client
+                .describe_stream_summary()
+                .stream_name(stream_name)
+                .send()
+                .await
+                .context("describing stream")?;
+            match res.stream_description_summary().map(|s| s.stream_status()) {
+                Some(StreamStatus::Active) => Ok(()),
+                status => bail!("stream {} is not active: {:?}", stream_name, status),
+            }
+        })
+        .await
+}
@@ -0,0 +1,47 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+use aws_sdk_kinesis::primitives::Blob;
+
+use crate::action::kinesis::stream_name;
+use crate::action::{ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+pub async fn run_ingest(
+    mut cmd: BuiltinCommand,
+    state: &mut State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let stream = cmd.args.string("stream")?;
+    let partition_key = cmd.args.string("partition-key")?;
+    cmd.args.done()?;
+
+    let stream_name = stream_name(&stream, state);
+    println!(
+        "Ingesting {} records into Kinesis stream {}",
+        cmd.input.len(),
+        stream_name
+    );
+
+    let client = mz_aws_util::kinesis::new_client(&state.aws_config);
+    // Each line of input is a record. Records are put one at a time so that
+    // they are ingested in order.
+    for record in cmd.input {
+        client
+            .put_record()
+            .stream_name(&stream_name)
+            .partition_key(&partition_key)
+            .data(Blob::new(record))
+            .send()
+            .await
+            .context("putting record")?;
+    }
+
+    Ok(ControlFlow::Continue)
+}
@@ -0,0 +1,45 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+use aws_sdk_kinesis::types::ScalingType;
+
+use crate::action::kinesis::{stream_name, wait_for_active};
+use crate::action::{ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+pub async fn run_update_shards(
+    mut cmd: BuiltinCommand,
+    state: &mut State,
+) -> Result<ControlFlow, anyhow::Error> {
+    let stream = cmd.args.string("stream")?;
+    let shards: i32 = cmd.args.parse("shards")?;
+    cmd.args.done()?;
+
+    let stream_name = stream_name(&stream, state);
+    println!(
+        "Updating shard count of Kinesis stream {} to {}",
+        stream_name, shards
+    );
+
+    // Resharding closes the existing shards and opens new child shards, which
+    // are only read once their parents have been fully read.
+    let client = mz_aws_util::kinesis::new_client(&state.aws_config);
+    client
+        .update_shard_count()
+        .stream_name(&stream_name)
+        .target_shard_count(shards)
+        .scaling_type(ScalingType::UniformScaling)
+        .send()
+        .await
+        .context("updating shard count")?;
+    wait_for_active(&client, &stream_name, state).await?;
+
+    Ok(ControlFlow::Continue)
+}
@@ -69,15 +69,14 @@ pub async fn run_verify(
 ) -> Result<ControlFlow, anyhow::Error> {
     // Parse arguments.
     let subject = cmd.args.string("subject")?;
-    match cmd.args.string("schema-type")?.as_str() {
-        "avro" => (),
+    let schema_type = match cmd.args.string("schema-type")?.as_str() {
+        "avro" => SchemaType::Avro,
+        "json" => SchemaType::Json,
         f => bail!("unknown format: {}", f),
     };
     cmd.args.done()?;
     let expected_schema = match &cmd.input[..] {
-        [expected_schema] => {
-            avro::parse_schema(expected_schema).context("parsing expected avro schema")?
-        }
+        [expected_schema] => expected_schema.clone(),
         _ => bail!("unable to read expected schema input"),
     };
 
@@ -104,13 +103,34 @@ pub async fn run_verify(
         .await
         .context("fetching schema")?;
 
-    let actual_schema = avro::parse_schema(&actual_schema).context("parsing actual avro schema")?;
-    if expected_schema != actual_schema {
-        bail!(
-            "schema did not match\nexpected:\n{:?}\n\nactual:\n{:?}",
-            expected_schema,
-            actual_schema,
-        );
+    match schema_type {
+        SchemaType::Avro => {
+            let expected_schema =
+                avro::parse_schema(&expected_schema).context("parsing expected avro schema")?;
+            let actual_schema =
+                avro::parse_schema(&actual_schema).context("parsing actual avro schema")?;
+            if expected_schema != actual_schema {
+                bail!(
+                    "schema did not match\nexpected:\n{:?}\n\nactual:\n{:?}",
+                    expected_schema,
+                    actual_schema,
+                );
+            }
+        }
+        SchemaType::Json => {
+            let expected_schema: serde_json::Value =
+                serde_json::from_str(&expected_schema).context("parsing expected json schema")?;
+            let actual_schema: serde_json::Value =
+                serde_json::from_str(&actual_schema).context("parsing actual json schema")?;
+            if expected_schema != actual_schema {
+                bail!(
+                    "schema did not match\nexpected:\n{}\n\nactual:\n{}",
+                    expected_schema,
+                    actual_schema,
+                );
+            }
+        }
+        SchemaType::Protobuf => unreachable!("protobuf schemas are rejected above"),
     }
     Ok(ControlFlow::Continue)
 }
@@ -66,7 +66,7 @@ SERVICES = [
 
 
 def workflow_default(c: Composition) -> None:
-    for name in ["secrets-manager", "aws-connection", "copy-to-s3"]:
+    for name in ["secrets-manager", "aws-connection", "copy-to-s3", "kinesis"]:
         with c.test_case(name):
             c.workflow(name)
 
@@ -274,3 +274,31 @@ def workflow_copy_to_s3(c: Composition) -> None:
             Bucket=bucket_name, Prefix=f"{path_prefix}/3/"
         )
         validate_upload(third_upload, set(["1000"]))
+
+
+def workflow_kinesis(c: Composition) -> None:
+    with c.override(
+        Materialized(
+            depends_on=["localstack"],
+            environment_extra=[
+                f"AWS_ENDPOINT_URL={AWS_ENDPOINT_URL_MZ}",
+                f"AWS_ACCESS_KEY_ID={AWS_ACCESS_KEY_ID}",
+                f"AWS_SECRET_ACCESS_KEY={AWS_SECRET_ACCESS_KEY}",
+            ],
+        ),
+        Testdrive(
+            default_timeout="5s",
+            aws_endpoint=AWS_ENDPOINT_URL_MZ,
+            aws_access_key_id=AWS_ACCESS_KEY_ID,
+            aws_secret_access_key=AWS_SECRET_ACCESS_KEY,
+        ),
+    ):
+        c.up("localstack", "materialized")
+        c.run_testdrive_files(
+            f"--var=endpoint={AWS_ENDPOINT_URL_MZ}",
+            f"--var=access-key={AWS_ACCESS_KEY_ID}",
+            f"--var=secret-key={AWS_SECRET_ACCESS_KEY}",
+            f"--var=region={DEFAULT_CLOUD_REGION}",
+            "--default-timeout=300s",
+            "kinesis/kinesis.td",
+        )
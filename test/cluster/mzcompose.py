@@ -1416,6 +1416,28 @@ def workflow_pg_snapshot_resumption(c: Composition) -> None:
             c.run_testdrive_files("pg-snapshot-resumption/05-verify-data.td")
 
 
+def workflow_pg_snapshot_chunk_retry(c: Composition) -> None:
+    """Test that failed chunks of a PostgreSQL snapshot are read again."""
+
+    c.down(destroy_volumes=True)
+
+    with c.override(
+        # Start postgres for the pg source
+        Postgres(),
+        Testdrive(no_reset=True),
+        Clusterd(
+            name="storage",
+            environment_extra=["FAILPOINTS=pg_snapshot_chunk_failure=5*return"],
+        ),
+    ):
+        c.up("materialized", "postgres", "storage")
+
+        c.run_testdrive_files("pg-snapshot-resumption/01-configure-postgres.td")
+        c.run_testdrive_files("pg-snapshot-chunks/01-configure-chunks.td")
+        c.run_testdrive_files("pg-snapshot-resumption/02-create-sources.td")
+        c.run_testdrive_files("pg-snapshot-chunks/02-verify-data.td")
+
+
 def workflow_sink_failure(c: Composition) -> None:
     """Test specific sink failure scenarios"""
 
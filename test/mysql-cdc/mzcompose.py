@@ -16,6 +16,7 @@ from materialize.mysql_util import (
     retrieve_ssl_context_for_mysql,
 )
 from materialize.mzcompose.composition import Composition, WorkflowArgumentParser
+from materialize.mzcompose.services.mariadb import MariaDb
 from materialize.mzcompose.services.materialized import Materialized
 from materialize.mzcompose.services.mysql import MySql
 from materialize.mzcompose.services.test_certs import TestCerts
@@ -47,6 +48,7 @@ SERVICES = [
     ),
     create_mysql(MySql.DEFAULT_VERSION),
     create_mysql_replica(MySql.DEFAULT_VERSION),
+    MariaDb(),
     TestCerts(),
     Testdrive(default_timeout="60s"),
 ]
@@ -129,6 +131,43 @@ def workflow_replica_connection(c: Composition, parser: WorkflowArgumentParser)
         )
 
 
+def workflow_binlog_positions(c: Composition, parser: WorkflowArgumentParser) -> None:
+    """
+    Validates replicating from a MySQL server without GTIDs, whose progress is
+    tracked by binlog file and position instead.
+    """
+
+    mysql_version = get_targeted_mysql_version(parser)
+    mysql = MySql(
+        version=mysql_version,
+        additional_args=[
+            arg for arg in MySql.DEFAULT_ADDITIONAL_ARGS if "gtid" not in arg
+        ]
+        + ["--gtid_mode=OFF", "--enforce_gtid_consistency=OFF"],
+    )
+    with c.override(mysql):
+        c.up("materialized", "mysql")
+        c.run_testdrive_files(
+            f"--var=mysql-root-password={MySql.DEFAULT_ROOT_PASSWORD}",
+            f"--var=default-replica-size={Materialized.Size.DEFAULT_SIZE}-{Materialized.Size.DEFAULT_SIZE}",
+            "override/20-binlog-positions.td",
+        )
+
+
+def workflow_mariadb(c: Composition) -> None:
+    """
+    Validates replicating from a MariaDB server, whose GTIDs are tracked per
+    replication domain and server id.
+    """
+
+    c.up("materialized", "mariadb")
+    c.run_testdrive_files(
+        f"--var=mariadb-root-password={MariaDb.DEFAULT_ROOT_PASSWORD}",
+        f"--var=default-replica-size={Materialized.Size.DEFAULT_SIZE}-{Materialized.Size.DEFAULT_SIZE}",
+        "override/30-mariadb.td",
+    )
+
+
 def workflow_schema_change_restart(
     c: Composition, parser: WorkflowArgumentParser
 ) -> None: